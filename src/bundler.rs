@@ -0,0 +1,67 @@
+use console::style;
+use crate::package_json;
+
+/// Which bundler `next dev` or `next build` runs with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bundler {
+    Turbopack,
+    Webpack,
+}
+
+/// Next.js 16 made Turbopack the default for both `next dev` and `next build`.
+const TURBOPACK_DEFAULT_MAJOR: u32 = 16;
+
+impl Bundler {
+    /// Reads the bundler from a package.json script: `--turbopack`/`--turbo` or `--webpack`
+    /// when given, otherwise the default of the installed Next.js.
+    fn from_script(name: &str) -> Self {
+        let script = package_json::script(name).unwrap_or_default();
+        let flags: Vec<&str> = script.split_whitespace().collect();
+        if flags.iter().any(|flag| matches!(*flag, "--turbopack" | "--turbo")) {
+            Bundler::Turbopack
+        } else if flags.contains(&"--webpack") {
+            Bundler::Webpack
+        } else if package_json::dependency_major_version("next").is_some_and(|major| major >= TURBOPACK_DEFAULT_MAJOR) {
+            Bundler::Turbopack
+        } else {
+            Bundler::Webpack
+        }
+    }
+
+    /// The bundler of the `dev` script.
+    pub fn dev() -> Self {
+        Bundler::from_script("dev")
+    }
+
+    /// The bundler of the `build` script.
+    pub fn build() -> Self {
+        Bundler::from_script("build")
+    }
+
+    /// Whether Turbopack runs in development or production, so webpack-only wiring would
+    /// silently do nothing there.
+    pub fn uses_turbopack() -> bool {
+        Bundler::dev() == Bundler::Turbopack || Bundler::build() == Bundler::Turbopack
+    }
+
+    /// Arguments that make `next build` use webpack. Only Next.js 16 and later need one;
+    /// earlier versions build with webpack unless told otherwise.
+    pub fn force_webpack_args() -> &'static str {
+        if package_json::dependency_major_version("next").is_some_and(|major| major >= TURBOPACK_DEFAULT_MAJOR) {
+            " --webpack"
+        } else {
+            ""
+        }
+    }
+}
+
+/// Warns that the part of a feature named by `what` only works with webpack, and what
+/// nstack did about it, when the project runs Turbopack.
+pub fn warn_webpack_only(what: &str, workaround: &str) {
+    if Bundler::uses_turbopack() {
+        println!(
+            "{}",
+            style(format!("This project uses Turbopack, but {} only supports webpack; {}", what, workaround)).yellow()
+        );
+    }
+}
@@ -0,0 +1,499 @@
+use anyhow::{Result, Context};
+use console::style;
+use dialoguer::{Select, theme::ColorfulTheme};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::project_structure::project_file;
+use crate::prompt;
+
+const BACKUPS_DIR: &str = ".nstack/backups";
+
+/// Files nstack touches outside its own writes: package managers rewrite these during installs.
+const DEPENDENCY_FILES: [&str; 7] = [
+    "package.json",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "bun.lockb",
+    "deno.lock",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Change {
+    path: PathBuf,
+    /// Whether the file existed before, i.e. restoring copies it back instead of deleting it
+    existed: bool,
+    /// Set when the path was moved, in which case restoring moves it back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    moved_to: Option<PathBuf>,
+}
+
+/// Every file one feature creates or modifies, with the originals copied to
+/// `.nstack/backups/<timestamp>/files/` before the first write so they can be restored.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileChangeSet {
+    pub feature: String,
+    #[serde(skip)]
+    timestamp: String,
+    changes: Vec<Change>,
+    /// Directories the feature created, outermost first, removed on revert when left empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    created_dirs: Vec<PathBuf>,
+}
+
+/// The change set of the feature being added. `None` outside `add`, so writes go straight
+/// to disk.
+static CURRENT: Mutex<Option<FileChangeSet>> = Mutex::new(None);
+
+/// Features whose change sets were committed during this run, for the Ctrl-C summary.
+static COMPLETED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn backup_dir(timestamp: &str) -> PathBuf {
+    Path::new(BACKUPS_DIR).join(timestamp)
+}
+
+fn new_timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+
+    // Recipes add several features within the same second
+    let mut timestamp = secs.to_string();
+    let mut suffix = 1;
+    while backup_dir(&timestamp).exists() {
+        timestamp = format!("{}-{}", secs, suffix);
+        suffix += 1;
+    }
+    timestamp
+}
+
+/// Paths are stored relative to the project root so backups survive moving the project.
+fn relative(path: &Path) -> Option<PathBuf> {
+    if path.is_relative() {
+        return Some(path.components().collect());
+    }
+    let cwd = std::env::current_dir().ok()?;
+    path.strip_prefix(cwd).ok().map(Path::to_path_buf)
+}
+
+impl FileChangeSet {
+    fn files_dir(&self) -> PathBuf {
+        backup_dir(&self.timestamp).join("files")
+    }
+
+    fn track(&mut self, path: &Path) -> Result<()> {
+        let Some(path) = relative(path) else {
+            return Ok(());
+        };
+        if self.changes.iter().any(|change| change.path == path) {
+            return Ok(());
+        }
+
+        let existed = path.is_file();
+        if existed {
+            let backup = self.files_dir().join(&path);
+            if let Some(parent) = backup.parent() {
+                fs::create_dir_all(parent).context("Failed to create backup directory")?;
+            }
+            fs::copy(&path, &backup).context(format!("Failed to back up {}", path.display()))?;
+        }
+        self.changes.push(Change { path, existed, moved_to: None });
+        Ok(())
+    }
+
+    fn track_dirs(&mut self, path: &Path) {
+        let Some(path) = relative(path) else {
+            return;
+        };
+        let mut missing: Vec<PathBuf> = path
+            .ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty() && !ancestor.exists())
+            .map(Path::to_path_buf)
+            .collect();
+        missing.reverse();
+        for dir in missing {
+            if !self.created_dirs.contains(&dir) {
+                self.created_dirs.push(dir);
+            }
+        }
+    }
+
+    fn track_move(&mut self, from: &Path, to: &Path) {
+        if let (Some(from), Some(to)) = (relative(from), relative(to)) {
+            self.changes.push(Change { path: from, existed: true, moved_to: Some(to) });
+        }
+    }
+
+    /// Every path the change set touched, including where moved files went.
+    fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.changes
+            .iter()
+            .flat_map(|change| std::iter::once(change.path.as_path()).chain(change.moved_to.as_deref()))
+    }
+
+    fn save(&self) -> Result<()> {
+        let dir = backup_dir(&self.timestamp);
+        fs::create_dir_all(&dir).context("Failed to create backup directory")?;
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(dir.join("changes.json"), content).context("Failed to write changes.json")
+    }
+
+    fn load(timestamp: &str) -> Result<Self> {
+        let path = backup_dir(timestamp).join("changes.json");
+        let content = fs::read_to_string(&path)
+            .context(format!("No backup named {} in {}", timestamp, BACKUPS_DIR))?;
+        let mut change_set: Self = serde_json::from_str(&content)
+            .context(format!("Failed to parse {}", path.display()))?;
+        change_set.timestamp = timestamp.to_string();
+        Ok(change_set)
+    }
+
+    /// Puts every file back the way it was before the feature ran, newest change first.
+    fn revert(&self) -> Result<()> {
+        for change in self.changes.iter().rev() {
+            if let Some(moved_to) = &change.moved_to {
+                if moved_to.exists() && !change.path.exists() {
+                    fs::rename(moved_to, &change.path)
+                        .context(format!("Failed to move {} back", moved_to.display()))?;
+                }
+            } else if change.existed {
+                fs::copy(self.files_dir().join(&change.path), &change.path)
+                    .context(format!("Failed to restore {}", change.path.display()))?;
+            } else if change.path.exists() {
+                fs::remove_file(&change.path)
+                    .context(format!("Failed to remove {}", change.path.display()))?;
+            }
+        }
+        for dir in self.created_dirs.iter().rev() {
+            let is_empty = fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none());
+            if is_empty {
+                fs::remove_dir(dir).context(format!("Failed to remove {}", dir.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Package.json and lockfiles of the app and, in a repository with several apps, of the
+/// repository itself.
+fn dependency_files() -> Vec<PathBuf> {
+    DEPENDENCY_FILES
+        .iter()
+        .flat_map(|file| [project_file(file), PathBuf::from(file)])
+        .collect()
+}
+
+/// Starts recording changes for `feature`, backing up package.json and lockfiles up front
+/// since installs rewrite them without going through `write`.
+pub fn begin(feature: &str) -> Result<()> {
+    let mut change_set = FileChangeSet {
+        feature: feature.to_string(),
+        timestamp: new_timestamp(),
+        changes: Vec::new(),
+        created_dirs: Vec::new(),
+    };
+    for file in dependency_files() {
+        if file.exists() {
+            change_set.track(&file)?;
+        }
+    }
+    *CURRENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(change_set);
+    Ok(())
+}
+
+/// Writes `contents` through a temporary file and a rename, so an interrupted write never
+/// leaves a half-written file, backing up the original first while a change set is active.
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(change_set) = CURRENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).as_mut() {
+        change_set.track(path)?;
+    }
+
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".nstack-tmp");
+    fs::write(&temp, contents).context(format!("Failed to write {}", path.display()))?;
+    fs::rename(&temp, path).context(format!("Failed to write {}", path.display()))
+}
+
+/// Creates `path` and its missing parents, remembering the new ones while a change set is
+/// active so a revert can remove them again.
+pub fn create_dir_all(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(change_set) = CURRENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).as_mut() {
+        change_set.track_dirs(path);
+    }
+    fs::create_dir_all(path).context(format!("Failed to create {}", path.display()))
+}
+
+/// Deletes a file, backing it up first while a change set is active.
+pub fn remove_file(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(change_set) = CURRENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).as_mut() {
+        change_set.track(path)?;
+    }
+    fs::remove_file(path).context(format!("Failed to remove {}", path.display()))
+}
+
+/// Moves a file or directory, remembering the move while a change set is active.
+pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    fs::rename(from, to).context(format!("Failed to move {} to {}", from.display(), to.display()))?;
+    if let Some(change_set) = CURRENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).as_mut() {
+        change_set.track_move(from, to);
+    }
+    Ok(())
+}
+
+/// Whether a change is a file the feature wrote, leaving out package.json, lockfiles and
+/// nstack's own state since other tools own those.
+fn is_generated(change: &Change) -> bool {
+    change.moved_to.is_none()
+        && change.path.is_file()
+        && !dependency_files().contains(&change.path)
+        && !change.path.components().any(|component| component.as_os_str() == ".nstack")
+}
+
+/// Files written so far by the active change set that still exist.
+pub fn written_files() -> Vec<PathBuf> {
+    CURRENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .map(|change_set| {
+            change_set
+                .changes
+                .iter()
+                .filter(|change| is_generated(change))
+                .map(|change| change.path.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Files written by every saved change set as `(path, feature)`, newest feature first.
+pub fn generated_files() -> Result<Vec<(PathBuf, String)>> {
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    for (timestamp, _) in list_backups()? {
+        let change_set = FileChangeSet::load(&timestamp)?;
+        for change in change_set.changes.iter().filter(|change| is_generated(change)) {
+            if !files.iter().any(|(path, _)| *path == change.path) {
+                files.push((change.path.clone(), change_set.feature.clone()));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Keeps the feature's changes and saves the change set so `nstack restore` can undo them.
+/// Returns the backup timestamp.
+pub fn commit() -> Result<Option<String>> {
+    let Some(change_set) = CURRENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take() else {
+        return Ok(None);
+    };
+    change_set.save()?;
+    COMPLETED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(change_set.feature.clone());
+    Ok(Some(change_set.timestamp))
+}
+
+/// Undoes everything the feature wrote after it failed part way, and drops its backup.
+/// Returns the feature and how many files were put back, if one was in flight.
+pub fn rollback() -> Result<Option<(String, usize)>> {
+    let Some(change_set) = CURRENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take() else {
+        return Ok(None);
+    };
+    change_set.revert()?;
+    let dir = backup_dir(&change_set.timestamp);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).context(format!("Failed to remove {}", dir.display()))?;
+    }
+    Ok(Some((change_set.feature, change_set.changes.len())))
+}
+
+/// Features fully added during this run, in order.
+pub fn completed_features() -> Vec<String> {
+    COMPLETED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Saved backups as `(timestamp, feature)`, newest first.
+pub fn list_backups() -> Result<Vec<(String, String)>> {
+    let Ok(entries) = fs::read_dir(BACKUPS_DIR) else {
+        return Ok(Vec::new());
+    };
+
+    let mut backups: Vec<(String, String)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let timestamp = entry.file_name().to_string_lossy().to_string();
+            let change_set = FileChangeSet::load(&timestamp).ok()?;
+            Some((timestamp, change_set.feature))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.0.cmp(&a.0));
+    Ok(backups)
+}
+
+/// Files `change_set` shares with newer backups as `(path, feature)`, oldest feature
+/// first: reverting it would also throw away what those features wrote there.
+fn overlapping_changes(change_set: &FileChangeSet) -> Result<Vec<(PathBuf, String)>> {
+    let mut overlaps: Vec<(PathBuf, String)> = Vec::new();
+    for (timestamp, feature) in list_backups()?.into_iter().rev() {
+        if timestamp <= change_set.timestamp {
+            continue;
+        }
+        let newer = FileChangeSet::load(&timestamp)?;
+        for path in newer.paths() {
+            if change_set.paths().any(|changed| changed == path) && !overlaps.iter().any(|(overlap, _)| overlap == path) {
+                overlaps.push((path.to_path_buf(), feature.clone()));
+            }
+        }
+    }
+    Ok(overlaps)
+}
+
+/// Reverts the files changed by the backup at `timestamp`, asking which one when it is not given.
+/// Asks before reverting files that newer backups changed again.
+pub fn restore(timestamp: Option<String>) -> Result<()> {
+    let timestamp = match timestamp {
+        Some(timestamp) => timestamp,
+        None => {
+            let backups = list_backups()?;
+            if backups.is_empty() {
+                println!("{}", style(format!("No backups found in {}", BACKUPS_DIR)).yellow());
+                return Ok(());
+            }
+            let items: Vec<String> = backups
+                .iter()
+                .map(|(timestamp, feature)| format!("{} - {}", timestamp, feature))
+                .collect();
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Select a backup to restore")
+                .default(0)
+                .items(&items)
+                .interact()?;
+            backups[selection].0.clone()
+        }
+    };
+
+    let change_set = FileChangeSet::load(&timestamp)?;
+    let overlaps = overlapping_changes(&change_set)?;
+    if !overlaps.is_empty() {
+        println!(
+            "{}",
+            style(format!("Features added after {} changed the same files:", change_set.feature)).yellow()
+        );
+        for (path, feature) in &overlaps {
+            println!("• {} - {}", path.display(), feature);
+        }
+        if !prompt::confirm("overwrite_newer", "Restore anyway and lose those changes?", false)? {
+            println!("Restore the newer backups first, newest first.");
+            return Ok(());
+        }
+    }
+    change_set.revert()?;
+    println!(
+        "{}",
+        style(format!("Restored {} file(s) changed by {}", change_set.changes.len(), change_set.feature)).green()
+    );
+    if change_set.changes.iter().any(|change| change.path.ends_with("package.json")) {
+        println!("Run your package manager's install command to sync node_modules.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backups live under the working directory, which is shared by every test thread.
+    static WORKING_DIR: Mutex<()> = Mutex::new(());
+
+    fn in_temp_dir(test: impl FnOnce()) {
+        let _guard = WORKING_DIR.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let dir = tempfile::tempdir().unwrap();
+        let previous = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        test();
+        std::env::set_current_dir(previous).unwrap();
+    }
+
+    fn change_set(feature: &str) -> FileChangeSet {
+        FileChangeSet {
+            feature: feature.to_string(),
+            timestamp: new_timestamp(),
+            changes: Vec::new(),
+            created_dirs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn tracks_files_once_and_reverts_them() {
+        in_temp_dir(|| {
+            fs::write("layout.tsx", "before").unwrap();
+            let mut change_set = change_set("theme");
+            change_set.track(Path::new("layout.tsx")).unwrap();
+            change_set.track(Path::new("layout.tsx")).unwrap();
+            change_set.track(Path::new("theme.ts")).unwrap();
+            fs::write("layout.tsx", "after").unwrap();
+            fs::write("theme.ts", "new").unwrap();
+
+            assert_eq!(change_set.changes.len(), 2);
+            assert!(change_set.changes[0].existed);
+            assert!(!change_set.changes[1].existed);
+
+            change_set.revert().unwrap();
+            assert_eq!(fs::read_to_string("layout.tsx").unwrap(), "before");
+            assert!(!Path::new("theme.ts").exists());
+        });
+    }
+
+    #[test]
+    fn reverting_removes_created_dirs_left_empty() {
+        in_temp_dir(|| {
+            let mut change_set = change_set("og-image");
+            change_set.track_dirs(Path::new("app/api/og"));
+            change_set.track_dirs(Path::new("lib/og"));
+            assert_eq!(
+                change_set.created_dirs,
+                ["app", "app/api", "app/api/og", "lib", "lib/og"].map(PathBuf::from)
+            );
+
+            fs::create_dir_all("app/api/og").unwrap();
+            fs::create_dir_all("lib/og").unwrap();
+            change_set.track(Path::new("app/api/og/route.tsx")).unwrap();
+            fs::write("app/api/og/route.tsx", "").unwrap();
+            // Written by the user afterwards, so lib/ has to stay
+            fs::write("lib/utils.ts", "").unwrap();
+
+            change_set.revert().unwrap();
+            assert!(!Path::new("app").exists());
+            assert!(!Path::new("lib/og").exists());
+            assert!(Path::new("lib/utils.ts").exists());
+        });
+    }
+
+    #[test]
+    fn finds_files_changed_again_by_newer_backups() {
+        in_temp_dir(|| {
+            let mut older = change_set("theme");
+            older.track(Path::new("layout.tsx")).unwrap();
+            older.track(Path::new("theme.ts")).unwrap();
+            older.save().unwrap();
+
+            let mut newer = change_set("toasts");
+            newer.track(Path::new("layout.tsx")).unwrap();
+            newer.track(Path::new("toaster.tsx")).unwrap();
+            newer.save().unwrap();
+
+            assert_eq!(overlapping_changes(&older).unwrap(), [(PathBuf::from("layout.tsx"), "toasts".to_string())]);
+            assert!(overlapping_changes(&newer).unwrap().is_empty());
+        });
+    }
+}
@@ -3,8 +3,30 @@ use clap::{Parser, Subcommand};
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
+    /// Without a subcommand, nstack opens the interactive feature picker
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
+    /// Overwrite existing files without asking
+    #[arg(long, global = true, conflicts_with = "skip_existing")]
+    pub force: bool,
+    /// Keep existing files without asking
+    #[arg(long, global = true)]
+    pub skip_existing: bool,
+    /// Fail instead of changing the lockfile when installing
+    #[arg(long, global = true)]
+    pub frozen: bool,
+    /// Install from the package cache when possible
+    #[arg(long, global = true)]
+    pub prefer_offline: bool,
+    /// Only show errors from the package manager
+    #[arg(long, global = true)]
+    pub silent: bool,
+    /// npm registry for this run's installs, instead of the one in .npmrc
+    #[arg(long, global = true)]
+    pub registry: Option<String>,
+    /// Show all output of package manager commands instead of only warnings and errors
+    #[arg(long, global = true, conflicts_with = "silent")]
+    pub verbose: bool,
 }
 
 #[derive(Subcommand)]
@@ -12,10 +34,109 @@ pub enum Commands {
     Create {
         #[arg(short, long)]
         name: Option<String>,
+        /// Directory to create the project in, e.g. ~/work/clients/acme
+        #[arg(long)]
+        dir: Option<String>,
+        /// Framework to scaffold: next (default), remix, astro, sveltekit, vite or expo
+        #[arg(long)]
+        framework: Option<String>,
+        /// Scaffold a Turborepo workspace with the app in apps/web and shared packages
+        #[arg(long)]
+        monorepo: bool,
+        /// Do not initialize a git repository
+        #[arg(long)]
+        no_git: bool,
+        /// Message of the initial commit, "Initial commit from nstack" by default
+        #[arg(long, conflicts_with = "no_git")]
+        commit_message: Option<String>,
+        /// Name of the initial branch, instead of git's init.defaultBranch
+        #[arg(long, conflicts_with = "no_git")]
+        branch: Option<String>,
+        /// Scaffold without installing dependencies; `nstack install` or the first
+        /// `nstack add` installs them later
+        #[arg(long)]
+        skip_install: bool,
+        /// Named preset from ~/.nstack/presets.toml with creator flags, template and features
+        #[arg(long)]
+        preset: Option<String>,
     },
     Add {
         #[arg(short, long)]
         feature: Option<String>,
+        /// Install the exact package versions recorded in .nstack/manifest.json
+        #[arg(long, conflicts_with = "feature")]
+        from_manifest: bool,
+    },
+    List {
+        /// Only show features already added to this project
+        #[arg(long, conflicts_with = "available")]
+        installed: bool,
+        /// Only show features not yet added to this project
+        #[arg(long)]
+        available: bool,
+    },
+    Info,
+    Apply {
+        /// Recipe file, e.g. stack.toml
+        recipe: String,
+    },
+    Search {
+        /// Keyword matched against feature names and descriptions
+        query: Option<String>,
+        /// Only show features in this category, e.g. database or testing
+        #[arg(short, long)]
+        category: Option<String>,
+        /// Pick one of the results and install it
+        #[arg(short, long)]
+        install: bool,
+    },
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Typecheck and lint the project, pointing failures at generated files
+    Verify {
+        /// Also run the production build
+        #[arg(long)]
+        build: bool,
+    },
+    /// Manage the project's package manager
+    Pm {
+        #[command(subcommand)]
+        action: PmAction,
+    },
+    /// Install dependencies with the project's package manager, e.g. after `create --skip-install`
+    Install,
+    /// Undo the files changed when a feature was added
+    Restore {
+        /// Backup to restore, from .nstack/backups; asks when omitted
+        timestamp: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PmAction {
+    /// Switch the project to another package manager: npm, yarn, pnpm, bun or deno
+    Use {
+        manager: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the value of a setting
+    Get {
+        key: String,
+        /// Read the global setting instead of the project one
+        #[arg(long)]
+        global: bool,
+    },
+    /// Change a setting
+    Set {
+        key: String,
+        value: String,
+        /// Write to ~/.nstack/config instead of the project config
+        #[arg(long)]
+        global: bool,
     },
-    List,
 }
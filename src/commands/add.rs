@@ -1,13 +1,85 @@
-use anyhow::Result;
+use anyhow::{Result, Context};
+use console::style;
 use dialoguer::{Select, theme::ColorfulTheme};
+use std::collections::BTreeMap;
+use crate::change_set;
+use crate::commands::install;
+use crate::compat;
+use crate::conflicts::{self, Resolution};
+use crate::formatter;
+use crate::framework::{self, Framework};
+use crate::hooks::{self, HookStage};
+use crate::manifest::{FeaturePackages, ProjectManifest};
+use crate::package_json;
+use crate::package_manager::{self, PackageManager, Workspace};
+use crate::post_install;
+use crate::project_structure;
+use crate::prompt;
+use crate::readme;
+use crate::features::FEATURES;
 
 // Import each feature module here
 use crate::features::shadcn;
 use crate::features::magicui;
 use crate::features::drizzle;
+use crate::features::schema_conventions;
+use crate::features::og_image;
+use crate::features::sitemap;
+use crate::features::security;
+use crate::features::captcha;
+use crate::features::cookie_consent;
+use crate::features::docker;
+use crate::features::github_actions;
+use crate::features::vercel;
+use crate::features::cloudflare;
+use crate::features::k8s;
+use crate::features::terraform;
+use crate::features::perf;
+use crate::features::vitest;
+use crate::features::playwright;
+use crate::features::cypress;
+use crate::features::storybook;
+use crate::features::eslint_prettier;
+use crate::features::biome;
+use crate::features::husky;
+use crate::features::commitlint;
+use crate::features::changesets;
+use crate::features::ts_strict;
+use crate::features::zustand;
+use crate::features::redux;
+use crate::features::jotai;
+use crate::features::theme;
+use crate::features::i18n;
+use crate::features::motion;
+use crate::features::data_table;
+use crate::features::charts;
+use crate::features::admin;
+use crate::features::landing;
+use crate::features::saas;
+use crate::features::search;
+use crate::features::notifications;
+use crate::features::collaboration;
+use crate::features::maps;
+use crate::features::scheduling;
+use crate::features::import_export;
+use crate::features::images;
+use crate::features::icons;
+use crate::features::toasts;
+use crate::features::supabase;
+use crate::features::firebase;
+use crate::features::convex;
+use crate::features::appwrite;
+use crate::features::pocketbase;
+use crate::features::kysely;
+use crate::features::mongodb;
+use crate::features::gel;
+use crate::features::local_first;
+use crate::features::nativewind;
+use crate::features::expo_router;
+use crate::features::remote;
 
 pub async fn add_feature(feature: Option<String>) -> Result<()> {
-    let features = vec!["shadcn", "magicui", "drizzle"];
+    let features: Vec<&str> = FEATURES.iter().map(|(name, _, _)| *name).collect();
     let selected_feature = match feature {
         Some(f) => f,
         None => {
@@ -20,13 +92,194 @@ pub async fn add_feature(feature: Option<String>) -> Result<()> {
         }
     };
 
-    match selected_feature.as_str() {
+    let is_remote = remote::is_remote_spec(&selected_feature);
+    if !is_remote && !features.contains(&selected_feature.as_str()) {
+        println!("Unknown feature: {}", selected_feature);
+        return Ok(());
+    }
+
+    // Start a fresh record of prompt answers so post hooks only see this feature's options
+    prompt::take_chosen();
+    if !is_remote {
+        framework::check(&selected_feature)?;
+        compat::check(&selected_feature)?;
+    }
+    // Inside a monorepo package, installs run from the root scoped to that package
+    package_manager::set_workspace(Workspace::detect());
+    install::complete_pending()?;
+    let replaced = match conflicts::resolve(&selected_feature)? {
+        Resolution::Continue => Vec::new(),
+        Resolution::Migrate(packages) => packages,
+        Resolution::Abort => {
+            println!("{}", style(format!("Not adding {}", selected_feature)).yellow());
+            return Ok(());
+        }
+    };
+    hooks::run(&selected_feature, HookStage::Pre, &BTreeMap::new())?;
+
+    change_set::begin(&selected_feature)?;
+    if let Err(err) = apply_feature(&selected_feature, is_remote, &replaced).await {
+        // A feature that fails part way must not leave half of its files behind
+        change_set::rollback().context("Failed to roll back the feature's changes")?;
+        return Err(err);
+    }
+    formatter::format_written_files(&change_set::written_files());
+    if let Some(timestamp) = change_set::commit()? {
+        println!("{}", style(format!("Backed up changed files; undo with `nstack restore {}`", timestamp)).dim());
+    }
+
+    hooks::run(&selected_feature, HookStage::Post, &prompt::take_chosen())?;
+    Ok(())
+}
+
+async fn apply_feature(feature: &str, is_remote: bool, replaced: &[&str]) -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    if !replaced.is_empty() {
+        package_manager.uninstall(replaced)?;
+    }
+    let scripts_before = package_json::script_names();
+    let dependencies_before = package_json::dependencies("dependencies");
+    let dev_dependencies_before = package_json::dependencies("devDependencies");
+    if is_remote {
+        // Feature packages from git are recorded by their source so they can be re-applied
+        remote::add_remote_feature(feature).await?;
+    } else {
+        add_builtin_feature(feature).await?;
+    }
+    ProjectManifest::record_feature(feature)?;
+
+    let added_scripts: Vec<String> = package_json::script_names()
+        .into_iter()
+        .filter(|script| !scripts_before.contains(script))
+        .collect();
+    ProjectManifest::record_scripts(feature, &added_scripts)?;
+    let packages = FeaturePackages {
+        dependencies: resolved_changes("dependencies", &dependencies_before),
+        dev_dependencies: resolved_changes("devDependencies", &dev_dependencies_before),
+    };
+    let installed_packages = !packages.dependencies.is_empty() || !packages.dev_dependencies.is_empty();
+    ProjectManifest::record_packages(feature, packages)?;
+    if installed_packages {
+        post_install::run(&package_manager)?;
+    }
+    readme::update(&project_structure::app_dir().unwrap_or_default(), &package_manager, Framework::from_project_config())
+}
+
+/// Packages in `section` that were added or re-ranged since `before`, at the version the
+/// package manager installed, or the declared range when node_modules has no copy.
+fn resolved_changes(section: &str, before: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    package_json::dependencies(section)
+        .into_iter()
+        .filter(|(name, range)| before.get(name) != Some(range))
+        .map(|(name, range)| {
+            let version = package_json::installed_version(&name).unwrap_or(range);
+            (name, version)
+        })
+        .collect()
+}
+
+/// `nstack add --from-manifest`: installs every package features recorded in
+/// .nstack/manifest.json at its recorded version, so a checkout reproduces the stack exactly.
+/// Packages the project no longer declares, e.g. ones a migration removed, are skipped.
+pub fn add_from_manifest() -> Result<()> {
+    let manifest = ProjectManifest::load()?;
+    if manifest.packages.is_empty() {
+        anyhow::bail!("No package versions recorded in .nstack/manifest.json; nstack records them as features are added");
+    }
+
+    let pinned = |section: &str, packages: fn(&FeaturePackages) -> &BTreeMap<String, String>| -> Vec<String> {
+        let declared = package_json::dependencies(section);
+        manifest
+            .packages
+            .values()
+            .flat_map(packages)
+            .filter(|(name, _)| declared.contains_key(*name))
+            .map(|(name, version)| format!("{}@{}", name, version))
+            .collect()
+    };
+    let dependencies = pinned("dependencies", |packages| &packages.dependencies);
+    let dev_dependencies = pinned("devDependencies", |packages| &packages.dev_dependencies);
+    if dependencies.is_empty() && dev_dependencies.is_empty() {
+        println!("{}", style("None of the recorded packages are declared in package.json").yellow());
+        return Ok(());
+    }
+
+    let package_manager = PackageManager::from_project_config()?;
+    println!(
+        "{}",
+        style(format!("Installing {} recorded package(s) with {}...", dependencies.len() + dev_dependencies.len(), package_manager.to_string())).cyan()
+    );
+    for (packages, dev) in [(dependencies, false), (dev_dependencies, true)] {
+        if !packages.is_empty() {
+            let packages: Vec<&str> = packages.iter().map(String::as_str).collect();
+            package_manager.install(&packages, dev)?;
+        }
+    }
+    install::clear_pending()?;
+    println!("{}", style("Installed the versions recorded in .nstack/manifest.json").green());
+    Ok(())
+}
+
+async fn add_builtin_feature(feature: &str) -> Result<()> {
+    match feature {
         "shadcn" => shadcn::add_shadcn().await?,
         "magicui" => magicui::add_magicui().await?,
         "drizzle" => drizzle::add_drizzle().await?,
-        _ => {
-            println!("Unknown feature: {}", selected_feature);
-        }
+        "schema-conventions" => schema_conventions::add_schema_conventions().await?,
+        "og-image" => og_image::add_og_image().await?,
+        "sitemap" => sitemap::add_sitemap().await?,
+        "security" => security::add_security().await?,
+        "captcha" => captcha::add_captcha().await?,
+        "cookie-consent" => cookie_consent::add_cookie_consent().await?,
+        "docker" => docker::add_docker().await?,
+        "github-actions" => github_actions::add_github_actions().await?,
+        "vercel" => vercel::add_vercel().await?,
+        "cloudflare" => cloudflare::add_cloudflare().await?,
+        "k8s" => k8s::add_k8s().await?,
+        "terraform" => terraform::add_terraform().await?,
+        "perf" => perf::add_perf().await?,
+        "vitest" => vitest::add_vitest().await?,
+        "playwright" => playwright::add_playwright().await?,
+        "cypress" => cypress::add_cypress().await?,
+        "storybook" => storybook::add_storybook().await?,
+        "eslint-prettier" => eslint_prettier::add_eslint_prettier().await?,
+        "biome" => biome::add_biome().await?,
+        "husky" => husky::add_husky().await?,
+        "commitlint" => commitlint::add_commitlint().await?,
+        "changesets" => changesets::add_changesets().await?,
+        "ts-strict" => ts_strict::add_ts_strict().await?,
+        "zustand" => zustand::add_zustand().await?,
+        "redux" => redux::add_redux().await?,
+        "jotai" => jotai::add_jotai().await?,
+        "theme" => theme::add_theme().await?,
+        "i18n" => i18n::add_i18n().await?,
+        "motion" => motion::add_motion().await?,
+        "data-table" => data_table::add_data_table().await?,
+        "charts" => charts::add_charts().await?,
+        "admin" => admin::add_admin().await?,
+        "landing" => landing::add_landing().await?,
+        "saas" => saas::add_saas().await?,
+        "search" => search::add_search().await?,
+        "notifications" => notifications::add_notifications().await?,
+        "collaboration" => collaboration::add_collaboration().await?,
+        "maps" => maps::add_maps().await?,
+        "scheduling" => scheduling::add_scheduling().await?,
+        "import-export" => import_export::add_import_export().await?,
+        "images" => images::add_images().await?,
+        "icons" => icons::add_icons().await?,
+        "toasts" => toasts::add_toasts().await?,
+        "supabase" => supabase::add_supabase().await?,
+        "firebase" => firebase::add_firebase().await?,
+        "convex" => convex::add_convex().await?,
+        "appwrite" => appwrite::add_appwrite().await?,
+        "pocketbase" => pocketbase::add_pocketbase().await?,
+        "kysely" => kysely::add_kysely().await?,
+        "mongodb" => mongodb::add_mongodb().await?,
+        "gel" => gel::add_gel().await?,
+        "local-first" => local_first::add_local_first().await?,
+        "nativewind" => nativewind::add_nativewind().await?,
+        "expo-router" => expo_router::add_expo_router().await?,
+        _ => anyhow::bail!("Unknown feature: {}", feature),
     }
     Ok(())
 }
@@ -0,0 +1,249 @@
+use anyhow::{Result, Context};
+use console::style;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use crate::commands::add::add_feature;
+use crate::commands::create::{DEFAULT_COMMIT_MESSAGE, GitOptions, create_project, validate_package_name};
+use crate::config;
+use crate::features::{FEATURES, remote};
+use crate::framework::Framework;
+use crate::manifest::ProjectManifest;
+use crate::project_root;
+use crate::prompt;
+use crate::shell;
+use crate::project_structure::project_file;
+
+/// A whole stack described in TOML: optional project creation, features in install order
+/// with their prompt answers, and commands to run at the end.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Recipe {
+    /// Shell commands run in the project once every feature is added
+    #[serde(default)]
+    scripts: Vec<String>,
+    create: Option<CreateStep>,
+    #[serde(default)]
+    features: Vec<FeatureStep>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateStep {
+    name: String,
+    /// Parent directory of the project, the current one by default
+    dir: Option<String>,
+    package_manager: Option<String>,
+    /// next (default), remix, astro, sveltekit or vite
+    framework: Option<String>,
+    /// Scaffold a Turborepo workspace with the app in apps/web
+    #[serde(default)]
+    monorepo: bool,
+    /// Scaffold without installing dependencies; the first feature installs them
+    #[serde(default)]
+    skip_install: bool,
+    /// Set to false to skip `git init`
+    git: Option<bool>,
+    commit_message: Option<String>,
+    branch: Option<String>,
+    /// Extra flags for the framework's creator, e.g. create-next-app's
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FeatureStep {
+    name: String,
+    /// Answers to the feature's prompts, keyed by recipe key
+    #[serde(default)]
+    answers: BTreeMap<String, toml::Value>,
+}
+
+async fn apply_steps(recipe: Recipe) -> Result<()> {
+    if let Some(create) = recipe.create {
+        let mut answers = BTreeMap::new();
+        if let Some(package_manager) = create.package_manager {
+            answers.insert("package_manager".to_string(), toml::Value::String(package_manager));
+        }
+        prompt::set_answers(answers);
+
+        let framework = Framework::parse(create.framework.as_deref().unwrap_or("next"))?;
+        let git = GitOptions {
+            enabled: create.git.unwrap_or(true),
+            commit_message: create.commit_message.unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE.to_string()),
+            branch: create.branch,
+        };
+        let project_dir = create_project(
+            Some(create.name),
+            create.dir,
+            framework,
+            create.monorepo,
+            create.skip_install,
+            &git,
+            &create.args,
+        ).await?;
+        // Features go into the app, not the workspace root
+        let project_dir = if create.monorepo { format!("{}/apps/web", project_dir) } else { project_dir };
+        std::env::set_current_dir(&project_dir)
+            .context(format!("Failed to enter {}", project_dir))?;
+    } else {
+        project_root::enter(true)?;
+        if !project_file("package.json").exists() {
+            anyhow::bail!("No package.json found. Run the recipe inside a project or add a [create] section");
+        }
+    }
+
+    let total = recipe.features.len();
+    for (index, step) in recipe.features.into_iter().enumerate() {
+        // Recipes can be re-applied; only the missing features are added
+        if ProjectManifest::is_feature_installed(&step.name) {
+            println!("\n{}", style(format!("[{}/{}] {} is already installed, skipping", index + 1, total, step.name)).dim());
+            continue;
+        }
+
+        println!("\n{}", style(format!("[{}/{}] Adding {}", index + 1, total, step.name)).cyan().bold());
+        prompt::set_answers(step.answers);
+        add_feature(Some(step.name.clone()))
+            .await
+            .context(format!("Failed to add {}", step.name))?;
+    }
+
+    for script in &recipe.scripts {
+        println!("\n{}", style(format!("Running: {}", script)).cyan());
+        shell::run(script, &[])?;
+    }
+
+    Ok(())
+}
+
+/// Catches typos before anything is created or installed.
+fn check_features(features: &[FeatureStep], source: &str) -> Result<()> {
+    let unknown = features.iter().find(|step| {
+        !remote::is_remote_spec(&step.name) && !FEATURES.iter().any(|&(name, _, _)| name == step.name)
+    });
+    if let Some(step) = unknown {
+        anyhow::bail!("Unknown feature '{}' in {}", step.name, source);
+    }
+    Ok(())
+}
+
+async fn run_recipe(recipe: Recipe) -> Result<usize> {
+    let feature_count = recipe.features.len();
+    let result = apply_steps(recipe).await;
+    prompt::clear_answers();
+    result.map(|_| feature_count)
+}
+
+pub async fn apply_recipe(path: &str) -> Result<()> {
+    // Resolved against the directory nstack was started in, before any step changes it
+    let recipe_path = std::fs::canonicalize(path).context(format!("Failed to read {}", path))?;
+    let content = std::fs::read_to_string(&recipe_path).context(format!("Failed to read {}", path))?;
+    let recipe: Recipe = toml::from_str(&content).context(format!("Failed to parse {}", path))?;
+    check_features(&recipe.features, path)?;
+
+    let feature_count = run_recipe(recipe).await?;
+    println!("\n{}", style(format!("Recipe applied: {} feature(s)", feature_count)).green().bold());
+    Ok(())
+}
+
+/// An organization's standard starting point, defined in `~/.nstack/presets.toml` under
+/// `[presets.<name>]`: a recipe's `[create]` options without the project name, plus the
+/// features and scripts every project gets.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Preset {
+    package_manager: Option<String>,
+    framework: Option<String>,
+    #[serde(default)]
+    monorepo: bool,
+    #[serde(default)]
+    skip_install: bool,
+    git: Option<bool>,
+    commit_message: Option<String>,
+    branch: Option<String>,
+    /// Extra flags for the framework's creator
+    #[serde(default)]
+    args: Vec<String>,
+    /// Starter to scaffold from instead of the creator's default, e.g. `github:acme/next-starter`
+    template: Option<String>,
+    #[serde(default)]
+    features: Vec<FeatureStep>,
+    #[serde(default)]
+    scripts: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PresetFile {
+    #[serde(default)]
+    presets: BTreeMap<String, Preset>,
+}
+
+fn load_preset(name: &str) -> Result<Preset> {
+    let path = config::global_dir()
+        .context("Could not determine the home directory for presets")?
+        .join("presets.toml");
+    let content = std::fs::read_to_string(&path)
+        .context(format!("Failed to read {}. Define presets there under [presets.<name>]", path.display()))?;
+    let mut file: PresetFile = toml::from_str(&content).context(format!("Failed to parse {}", path.display()))?;
+
+    let names: Vec<String> = file.presets.keys().cloned().collect();
+    file.presets.remove(name).with_context(|| {
+        format!("Unknown preset '{}'. Presets in {}: {}", name, path.display(), names.join(", "))
+    })
+}
+
+/// Options given on the command line next to `--preset`; they win over the preset's.
+pub struct PresetOverrides {
+    pub name: Option<String>,
+    pub dir: Option<String>,
+    pub framework: Option<String>,
+    pub monorepo: bool,
+    pub skip_install: bool,
+    pub git: Option<GitOptions>,
+}
+
+/// `nstack create --preset <name>`: scaffolds the project the preset describes and adds its
+/// features, the same way a recipe with a `[create]` section does.
+pub async fn create_from_preset(preset_name: &str, overrides: PresetOverrides) -> Result<()> {
+    let preset = load_preset(preset_name)?;
+    let source = format!("preset '{}'", preset_name);
+    check_features(&preset.features, &source)?;
+
+    let name = match overrides.name {
+        Some(name) => name,
+        None => prompt::input_validated("name", "Enter project name", "", |name: &String| validate_package_name(name))?,
+    };
+    let framework = overrides.framework.or(preset.framework);
+    let mut args = preset.args;
+    if let Some(template) = &preset.template {
+        args.extend(Framework::parse(framework.as_deref().unwrap_or("next"))?.template_args(template)?);
+    }
+    let (git, commit_message, branch) = match overrides.git {
+        Some(git) => (Some(git.enabled), Some(git.commit_message), git.branch),
+        None => (preset.git, preset.commit_message, preset.branch),
+    };
+
+    let recipe = Recipe {
+        scripts: preset.scripts,
+        create: Some(CreateStep {
+            name,
+            dir: overrides.dir,
+            package_manager: preset.package_manager,
+            framework,
+            monorepo: overrides.monorepo || preset.monorepo,
+            skip_install: overrides.skip_install || preset.skip_install,
+            git,
+            commit_message,
+            branch,
+            args,
+        }),
+        features: preset.features,
+    };
+
+    let feature_count = run_recipe(recipe).await?;
+    println!(
+        "\n{}",
+        style(format!("Created from preset '{}' with {} feature(s)", preset_name, feature_count)).green().bold()
+    );
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use console::style;
+use crate::cli::ConfigAction;
+use crate::config::{self, ConfigScope};
+
+fn scope(global: bool) -> ConfigScope {
+    if global { ConfigScope::Global } else { ConfigScope::Project }
+}
+
+pub fn run_config(action: ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Get { key, global } => {
+            let definition = config::known_key(&key)?;
+            match config::get(scope(global), &key)? {
+                Some(value) => println!("{}", value),
+                None => println!(
+                    "{}",
+                    style(format!("{} is not set ({} config). {}", key, scope(global).as_str(), definition.description)).yellow()
+                ),
+            }
+        }
+        ConfigAction::Set { key, value, global } => {
+            config::set(scope(global), &key, &value)?;
+            println!(
+                "{}",
+                style(format!("Set {} = {} in {} config", key, value, scope(global).as_str())).green()
+            );
+        }
+    }
+    Ok(())
+}
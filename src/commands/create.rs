@@ -1,72 +1,363 @@
 use anyhow::{Result, Context};
 use console::style;
-use dialoguer::{Input, Select, theme::ColorfulTheme};
 use indicatif::ProgressBar;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use crate::commands::add::add_feature;
+use crate::commands::install;
+use crate::commands::monorepo;
+use crate::compat;
+use crate::features::FEATURES;
+use crate::file_writer::{self, ConflictPolicy};
+use crate::framework::Framework;
+use crate::gitignore;
+use crate::npmrc;
 use crate::package_manager::PackageManager;
+use crate::prompt;
+use crate::readme;
 
-pub async fn create_project(name: Option<String>) -> Result<()> {
+pub const DEFAULT_COMMIT_MESSAGE: &str = "Initial commit from nstack";
+
+/// How `create` sets up git once the project is scaffolded.
+pub struct GitOptions {
+    pub enabled: bool,
+    pub commit_message: String,
+    /// Initial branch, or git's `init.defaultBranch` when `None`
+    pub branch: Option<String>,
+}
+
+impl Default for GitOptions {
+    fn default() -> Self {
+        GitOptions { enabled: true, commit_message: DEFAULT_COMMIT_MESSAGE.to_string(), branch: None }
+    }
+}
+
+/// Scaffolds a project with the framework's creator (create-next-app by default) and returns
+/// its directory. `creator_args` are passed through, e.g. `--typescript --tailwind`.
+/// `dir` is the parent directory, the current one by default. With `monorepo`, the app
+/// becomes apps/web of a Turborepo workspace instead. With `skip_install`, dependencies are
+/// left for `nstack install` or the first `nstack add`.
+pub async fn create_project(
+    name: Option<String>,
+    dir: Option<String>,
+    framework: Framework,
+    monorepo: bool,
+    skip_install: bool,
+    git: &GitOptions,
+    creator_args: &[String],
+) -> Result<String> {
     let project_name = match name {
-        Some(name) => name,
-        None => Input::with_theme(&ColorfulTheme::default())
-            .with_prompt("Enter project name")
-            .interact_text()?,
+        Some(name) => {
+            if let Err(message) = validate_package_name(&name) {
+                anyhow::bail!("Invalid project name '{}': {}", name, message);
+            }
+            name
+        }
+        None => prompt::input_validated("name", "Enter project name", "", |name: &String| validate_package_name(name))?,
+    };
+
+    let project_path = match dir {
+        Some(dir) => {
+            let parent = expand_home(&dir);
+            fs::create_dir_all(&parent).context(format!("Failed to create {}", parent.display()))?;
+            parent.join(&project_name)
+        }
+        None => PathBuf::from(&project_name),
     };
+    check_target_dir(&project_path)?;
+    let project_dir = project_path.to_string_lossy().to_string();
+
+    let chosen_pm = choose_package_manager()?;
+
+    // nstack initializes git itself once its own files are in place
+    let mut creator_args = creator_args.to_vec();
+    creator_args.extend(framework.skip_git_args().iter().map(|arg| arg.to_string()));
+
+    if monorepo {
+        monorepo::create_monorepo(&project_dir, framework, &chosen_pm, skip_install, &creator_args)?;
+    } else {
+        if skip_install {
+            creator_args.extend(framework.skip_install_args().iter().map(|arg| arg.to_string()));
+        }
+        run_creator(framework, &chosen_pm, &project_dir, &creator_args)?;
+        // Save the chosen package manager and framework to a config file for future use
+        save_project_config(&project_path, &chosen_pm, framework, &[])?;
+    }
+    if skip_install {
+        install::mark_pending(&project_path)?;
+        if monorepo {
+            install::mark_pending(&project_path.join("apps/web"))?;
+        }
+        println!("{}", style("Skipped installing dependencies; run `nstack install` when you are ready").yellow());
+    }
+
+    readme::update(&project_path, &chosen_pm, framework)?;
+    pin_node_version(&project_path)?;
+    init_git(&project_path, git)?;
+    Ok(project_dir)
+}
+
+const NODE_VERSION_FILES: [&str; 3] = [".nvmrc", ".node-version", ".tool-versions"];
+
+fn validate_node_version(version: &str) -> Result<(), &'static str> {
+    if version.is_empty() || !version.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())) {
+        return Err("use a version like 22 or 22.11.0");
+    }
+    match compat::major(version) {
+        Some(major) if major >= compat::MIN_NODE_MAJOR => Ok(()),
+        _ => Err("nstack projects need Node.js 18 or later"),
+    }
+}
+
+/// Pins the project's Node.js version from day one: the chosen version files and
+/// `engines.node` in package.json, defaulting to the Node.js nstack runs with.
+fn pin_node_version(project_path: &Path) -> Result<()> {
+    let detected = compat::node_version().filter(|version| validate_node_version(version).is_ok());
+    let version = prompt::input_validated(
+        "node_version",
+        "Node.js version to pin",
+        detected.as_deref().unwrap_or("22"),
+        |version: &String| validate_node_version(version),
+    )?;
+    let files = prompt::multi_select(
+        "node_version_files",
+        "Version files to write (nvm, fnm/volta/nodenv, mise/asdf)",
+        &NODE_VERSION_FILES,
+        &[true, false, false],
+    )?;
+
+    for index in files {
+        let file = NODE_VERSION_FILES[index];
+        let content = match file {
+            ".tool-versions" => format!("nodejs {}\n", version),
+            _ => format!("{}\n", version),
+        };
+        fs::write(project_path.join(file), content).context(format!("Failed to write {}", file))?;
+    }
+
+    let package_json_path = project_path.join("package.json");
+    let content = fs::read_to_string(&package_json_path).context("Failed to read package.json")?;
+    let mut package_json: serde_json::Value = serde_json::from_str(&content).context("Failed to parse package.json")?;
+    if package_json["engines"]["node"].is_null() {
+        let major = compat::major(&version).unwrap_or(compat::MIN_NODE_MAJOR);
+        package_json["engines"]["node"] = serde_json::Value::String(format!(">={}", major));
+        fs::write(&package_json_path, serde_json::to_string_pretty(&package_json)? + "\n")
+            .context("Failed to update package.json")?;
+    }
+    Ok(())
+}
+
+/// npm's package name rules, checked up front so the creator does not fail after its prompts.
+pub fn validate_package_name(name: &str) -> Result<(), &'static str> {
+    if name.trim().is_empty() {
+        return Err("the name cannot be empty");
+    }
+    if name.len() > 214 {
+        return Err("npm package names are at most 214 characters");
+    }
+    if name.contains('/') || name.contains('\\') {
+        return Err("the name cannot contain slashes; use --dir to choose where the project goes");
+    }
+    if name.starts_with('.') || name.starts_with('_') {
+        return Err("npm package names cannot start with a dot or an underscore");
+    }
+    if name.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err("npm package names must be lowercase");
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')) {
+        return Err("npm package names may only contain lowercase letters, digits, '-', '.', '_' and '~'");
+    }
+    if matches!(name, "node_modules" | "favicon.ico") {
+        return Err("this name is reserved by npm");
+    }
+    Ok(())
+}
+
+/// Expands a leading `~` to the home directory, as shells do before nstack sees the path.
+fn expand_home(dir: &str) -> PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match (dir.strip_prefix("~"), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            PathBuf::from(home).join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(dir),
+    }
+}
 
-    // Ask user to choose package manager
-    let package_managers = vec!["npm", "yarn", "pnpm", "bun"];
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Choose your package manager")
-        .items(&package_managers)
-        .default(0)
-        .interact()?;
+/// Refuses to scaffold into a directory that already has files unless `--force` is set.
+fn check_target_dir(path: &Path) -> Result<()> {
+    let has_entries = fs::read_dir(path).map(|mut entries| entries.next().is_some()).unwrap_or(false);
+    if path.is_file() {
+        anyhow::bail!("{} exists and is a file", path.display());
+    }
+    if has_entries && file_writer::policy() != ConflictPolicy::Force {
+        anyhow::bail!(
+            "{} already exists and is not empty. Choose another name or --dir, or pass --force to create the project in it anyway",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context(format!("Failed to run git {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        anyhow::bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Ignores nstack's backups and the local .env, then initializes the repository on the
+/// chosen branch and commits the scaffold. A failed commit (e.g. no git identity) only warns.
+fn init_git(dir: &Path, options: &GitOptions) -> Result<()> {
+    gitignore::add_entries_in(dir, "nstack", &[".nstack/backups", ".env"])?;
+    if !options.enabled {
+        return Ok(());
+    }
+    if Command::new("git").arg("--version").output().is_err() {
+        println!("{}", style("git is not installed; skipping repository setup").yellow());
+        return Ok(());
+    }
+
+    if !dir.join(".git").exists() {
+        match &options.branch {
+            Some(branch) => run_git(dir, &["init", "--initial-branch", branch])?,
+            None => run_git(dir, &["init"])?,
+        };
+    } else if let Some(branch) = &options.branch {
+        run_git(dir, &["branch", "-M", branch])?;
+    }
+
+    run_git(dir, &["add", "-A"])?;
+    if run_git(dir, &["status", "--porcelain"])?.trim().is_empty() {
+        return Ok(());
+    }
+    match run_git(dir, &["commit", "-m", &options.commit_message]) {
+        Ok(_) => println!("{}", style(format!("Initialized git with commit \"{}\"", options.commit_message)).green()),
+        Err(err) => println!("{}", style(format!("Initialized git but could not commit: {}", err)).yellow()),
+    }
+    Ok(())
+}
+
+/// Asks which features to add right away and adds them in order inside the new project,
+/// so one `nstack create` session ends with a fully wired stack. `app_dir` is where the
+/// features go, e.g. apps/web of a monorepo.
+pub async fn run_stack_wizard(project_name: &str, app_dir: &str) -> Result<()> {
+    // Feature prompts need a terminal; scripted runs add features with `nstack apply`
+    if !std::io::stdin().is_terminal() {
+        print_next_steps(project_name, true);
+        return Ok(());
+    }
 
-    let chosen_pm = match selection {
+    std::env::set_current_dir(app_dir).context(format!("Failed to enter {}", app_dir))?;
+    let framework = Framework::from_project_config();
+    let available: Vec<(&str, &str)> = FEATURES
+        .iter()
+        .filter(|(name, _, _)| framework.supports(name))
+        .map(|&(name, _, description)| (name, description))
+        .collect();
+    let items: Vec<String> = available
+        .iter()
+        .map(|(name, description)| format!("{} - {}", name, description))
+        .collect();
+
+    let selections = prompt::multi_select(
+        "features",
+        "Which features do you want now? (space to select, enter to confirm)",
+        &items,
+        &vec![false; items.len()],
+    )?;
+
+    let total = selections.len();
+    for (index, selection) in selections.into_iter().enumerate() {
+        let (name, _) = available[selection];
+        println!("\n{}", style(format!("[{}/{}] Adding {}", index + 1, total, name)).cyan().bold());
+        add_feature(Some(name.to_string()))
+            .await
+            .context(format!("Failed to add {}", name))?;
+    }
+
+    if total > 0 {
+        println!("\n{}", style(format!("Added {} feature(s)", total)).green().bold());
+    }
+    print_next_steps(app_dir, total == 0);
+    Ok(())
+}
+
+fn print_next_steps(project_dir: &str, suggest_add: bool) {
+    println!("\n{}", style("Next steps:").green());
+    println!("  cd {}", project_dir);
+    if suggest_add {
+        println!("  nstack add <feature>");
+    }
+}
+
+fn choose_package_manager() -> Result<PackageManager> {
+    let package_managers = vec!["npm", "yarn", "pnpm", "bun", "deno"];
+    let selection = prompt::select("package_manager", "Choose your package manager", &package_managers, 0)?;
+
+    Ok(match selection {
         0 => PackageManager::Npm,
         1 => PackageManager::Yarn,
         2 => PackageManager::Pnpm,
         3 => PackageManager::Bun,
+        4 => PackageManager::Deno,
         _ => unreachable!(),
-    };
+    })
+}
 
-    println!("{}", style(format!("Creating Next.js project with {}...", chosen_pm.to_string())).cyan());
+/// Runs the framework's creator for a project at `path`, relative to the current directory.
+pub fn run_creator(framework: Framework, package_manager: &PackageManager, path: &str, creator_args: &[String]) -> Result<()> {
+    println!(
+        "{}",
+        style(format!("Creating {} project with {}...", framework.display_name(), package_manager.to_string())).cyan()
+    );
 
+    let (command, args) = framework.create_command(package_manager);
     let pb = ProgressBar::new_spinner();
-    pb.set_message(format!("Running create-next-app with {}...", chosen_pm.to_string()));
+    pb.set_message(format!("Running {} {}...", command, args.join(" ")));
 
-    let (command, args) = chosen_pm.create_next_app_command();
     let mut cmd = Command::new(command);
-    cmd.args(args);
-    cmd.arg(&project_name);
+    npmrc::pass_to(&mut cmd);
+    cmd.args(&args);
+    cmd.arg(path);
+    cmd.args(creator_args);
 
     let status = cmd
         .status()
-        .context(format!("Failed to run create-next-app with {}", chosen_pm.to_string()))?;
+        .context(format!("Failed to run {} {}", command, args.join(" ")))?;
 
     if !status.success() {
-        anyhow::bail!("Failed to create Next.js project");
+        anyhow::bail!("Failed to create {} project", framework.display_name());
     }
 
-    // Save the chosen package manager to a config file for future use
-    save_package_manager_config(&project_name, &chosen_pm)?;
-
     pb.finish_with_message("Project created successfully!");
-    println!("\n{}", style("Next steps:").green());
-    println!("  cd {}", project_name);
-    println!("  nstack add <feature>");
-
     Ok(())
 }
 
-fn save_package_manager_config(project_name: &str, package_manager: &PackageManager) -> Result<()> {
-    use std::fs;
-    use std::path::Path;
+/// Writes `<dir>/.nstack/config` with the package manager and framework, plus the workspace
+/// directories for monorepo roots.
+pub fn save_project_config(
+    dir: &Path,
+    package_manager: &PackageManager,
+    framework: Framework,
+    workspaces: &[&str],
+) -> Result<()> {
+    let mut config_content = format!("package_manager={}\nframework={}\n", package_manager.to_string(), framework.as_str());
+    if !workspaces.is_empty() {
+        config_content.push_str(&format!("workspaces={}\n", workspaces.join(",")));
+    }
+    let config_path = dir.join(".nstack");
 
-    let config_content = format!("package_manager={}\n", package_manager.to_string());
-    let config_path = Path::new(project_name).join(".nstack");
-    
     fs::create_dir_all(&config_path)?;
     fs::write(config_path.join("config"), config_content)?;
-    
+
     Ok(())
 }
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use console::style;
+use std::path::Path;
+use std::process::Command;
+use crate::bundler::Bundler;
+use crate::compat::NextCapabilities;
+use crate::manifest::ProjectManifest;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_profile::ProjectProfile;
+use crate::project_structure::{Layout, ProjectStructure};
+
+/// Runs `<program> --version` and returns the first line of its output.
+fn tool_version(program: &str) -> Option<String> {
+    let output = Command::new(program).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout);
+    let version = version.lines().next()?.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Package manager the project is set up for, based on `.nstack/config` or the lockfile.
+fn project_package_manager() -> Option<String> {
+    if Path::new(".nstack").join("config").exists() {
+        return PackageManager::from_project_config()
+            .ok()
+            .map(|pm| format!("{} (.nstack/config)", pm.to_string()));
+    }
+
+    PackageManager::from_lockfile()
+        .map(|pm| format!("{} ({})", pm.to_string(), pm.lockfile()))
+}
+
+fn collect_report() -> Vec<(&'static str, Vec<(String, String)>)> {
+    let not_found = || "not found".to_string();
+
+    let system = vec![
+        ("nstack".to_string(), env!("CARGO_PKG_VERSION").to_string()),
+        ("OS".to_string(), format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)),
+    ];
+
+    let mut binaries = vec![("Node".to_string(), tool_version("node").unwrap_or_else(not_found))];
+    for program in ["npm", "yarn", "pnpm", "bun", "deno"] {
+        binaries.push((program.to_string(), tool_version(program).unwrap_or_else(not_found)));
+    }
+
+    let mut project = Vec::new();
+    if Path::new("package.json").exists() {
+        project.push(("Name".to_string(), package_json::package_name().unwrap_or_else(|| "(unnamed)".to_string())));
+        for dependency in ["next", "react", "typescript", "tailwindcss"] {
+            let version = package_json::dependency_version(dependency).unwrap_or_else(|| "not installed".to_string());
+            project.push((dependency.to_string(), version));
+        }
+
+        let structure = match ProjectStructure::detect().map(|structure| structure.layout()) {
+            Ok(Layout::AppDir) => "app/ at the project root",
+            Ok(Layout::SrcDir) => "src/ directory",
+            Ok(Layout::PagesRouter) => "pages/ at the project root",
+            Ok(Layout::SrcPagesRouter) => "src/pages/ directory",
+            Err(_) => "not detected",
+        };
+        project.push(("Structure".to_string(), structure.to_string()));
+        project.push(("Lint tooling".to_string(), ProjectProfile::detect().describe()));
+        if let Some(capabilities) = NextCapabilities::detect() {
+            let supported: Vec<&str> = [
+                (capabilities.app_router, "App Router"),
+                (capabilities.server_actions, "Server Actions"),
+                (capabilities.edge_runtime, "Edge runtime"),
+            ]
+            .into_iter()
+            .filter_map(|(supported, name)| supported.then_some(name))
+            .collect();
+            let supported = if supported.is_empty() { "none".to_string() } else { supported.join(", ") };
+            project.push(("Next.js capabilities".to_string(), supported));
+            project.push(("Bundler".to_string(), format!("{:?} (dev), {:?} (build)", Bundler::dev(), Bundler::build())));
+        }
+        project.push(("Package manager".to_string(), project_package_manager().unwrap_or_else(|| "not detected".to_string())));
+
+        let features = ProjectManifest::load().map(|manifest| manifest.features).unwrap_or_default();
+        let features = if features.is_empty() { "none".to_string() } else { features.join(", ") };
+        project.push(("nstack features".to_string(), features));
+    } else {
+        project.push(("package.json".to_string(), "not found in the current directory".to_string()));
+    }
+
+    vec![("System", system), ("Binaries", binaries), ("Project", project)]
+}
+
+pub fn show_info() -> Result<()> {
+    let report = collect_report();
+
+    for (section, rows) in &report {
+        println!("\n{}", style(section).cyan().bold());
+        for (label, value) in rows {
+            println!("  {} {}", style(format!("{}:", label)).green(), value);
+        }
+    }
+
+    // Plain text without styling so it can be pasted straight into an issue
+    println!("\n{}", style("Copy this into bug reports:").cyan().bold());
+    println!("```");
+    for (section, rows) in &report {
+        println!("{}:", section);
+        for (label, value) in rows {
+            println!("  {}: {}", label, value);
+        }
+    }
+    println!("```");
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anyhow::{Result, Context};
+use console::style;
+use std::fs;
+use std::path::Path;
+use crate::package_manager::PackageManager;
+
+/// Left by `nstack create --skip-install` until dependencies are installed.
+const PENDING_MARKER: &str = ".nstack/install-pending";
+
+/// Records that the project in `dir` was created without installing its dependencies.
+pub fn mark_pending(dir: &Path) -> Result<()> {
+    let marker = dir.join(PENDING_MARKER);
+    if let Some(parent) = marker.parent() {
+        fs::create_dir_all(parent).context(format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&marker, "").context(format!("Failed to write {}", marker.display()))
+}
+
+pub fn is_pending() -> bool {
+    Path::new(PENDING_MARKER).exists()
+}
+
+/// `nstack install`: installs the project's dependencies with its package manager and
+/// clears the pending state left by `create --skip-install`.
+pub fn install_dependencies() -> Result<()> {
+    if !Path::new("package.json").exists() {
+        anyhow::bail!("No package.json found in the current directory");
+    }
+
+    let package_manager = PackageManager::from_project_config()?;
+    println!("{}", style(format!("Installing dependencies with {}...", package_manager.to_string())).cyan());
+    package_manager.install_all(Path::new("."))?;
+
+    clear_pending()?;
+    println!("{}", style("Dependencies installed").green());
+    Ok(())
+}
+
+pub fn clear_pending() -> Result<()> {
+    if is_pending() {
+        fs::remove_file(PENDING_MARKER).context(format!("Failed to remove {}", PENDING_MARKER))?;
+    }
+    Ok(())
+}
+
+/// Finishes an install skipped at create time, so features never build on a project
+/// without node_modules.
+pub fn complete_pending() -> Result<()> {
+    if !is_pending() {
+        return Ok(());
+    }
+    println!("{}", style("Dependencies were not installed when the project was created").yellow());
+    install_dependencies()
+}
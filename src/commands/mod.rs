@@ -1,2 +1,9 @@
 pub mod create;
-pub mod add; 
\ No newline at end of file
+pub mod monorepo;
+pub mod add;
+pub mod info;
+pub mod config;
+pub mod search;
+pub mod apply;
+pub mod verify; pub mod install;
+pub mod pm;
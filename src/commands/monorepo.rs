@@ -0,0 +1,285 @@
+use anyhow::{Result, Context};
+use console::style;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use crate::commands::create::{run_creator, save_project_config};
+use crate::framework::Framework;
+use crate::next_config::{self, NEXT_CONFIG_FILES};
+use crate::package_manager::PackageManager;
+
+/// Workspaces every scaffolded monorepo starts with, recorded in the root .nstack/config.
+const WORKSPACES: [&str; 3] = ["apps/web", "packages/ui", "packages/config"];
+
+const TURBO_JSON: &str = r#"{
+  "$schema": "https://turbo.build/schema.json",
+  "tasks": {
+    "build": {
+      "dependsOn": ["^build"],
+      "outputs": [".next/**", "!.next/cache/**", "dist/**"]
+    },
+    "lint": {
+      "dependsOn": ["^lint"]
+    },
+    "typecheck": {
+      "dependsOn": ["^typecheck"]
+    },
+    "dev": {
+      "cache": false,
+      "persistent": true
+    }
+  }
+}
+"#;
+
+const PNPM_WORKSPACE: &str = r#"packages:
+  - "apps/*"
+  - "packages/*"
+"#;
+
+const GITIGNORE: &str = r#"node_modules
+.turbo
+.next
+dist
+.env*.local
+"#;
+
+const TSCONFIG_BASE: &str = r#"{
+  "$schema": "https://json.schemastore.org/tsconfig",
+  "compilerOptions": {
+    "target": "ES2022",
+    "lib": ["dom", "dom.iterable", "esnext"],
+    "module": "esnext",
+    "moduleResolution": "bundler",
+    "strict": true,
+    "esModuleInterop": true,
+    "skipLibCheck": true,
+    "resolveJsonModule": true,
+    "isolatedModules": true,
+    "noEmit": true
+  }
+}
+"#;
+
+const TSCONFIG_NEXTJS: &str = r#"{
+  "$schema": "https://json.schemastore.org/tsconfig",
+  "extends": "./base.json",
+  "compilerOptions": {
+    "allowJs": true,
+    "jsx": "preserve",
+    "incremental": true,
+    "plugins": [{ "name": "next" }]
+  }
+}
+"#;
+
+const TSCONFIG_REACT_LIBRARY: &str = r#"{
+  "$schema": "https://json.schemastore.org/tsconfig",
+  "extends": "./base.json",
+  "compilerOptions": {
+    "jsx": "react-jsx"
+  }
+}
+"#;
+
+const ESLINT_CONFIG: &str = r#"import js from "@eslint/js";
+import tseslint from "typescript-eslint";
+
+/** Shared flat config; apps and packages re-export or extend it. */
+export default tseslint.config(
+  { ignores: ["dist/**", ".next/**"] },
+  js.configs.recommended,
+  ...tseslint.configs.recommended,
+);
+"#;
+
+const UI_TSCONFIG: &str = r#"{
+  "extends": "@SCOPE/config/tsconfig/react-library.json",
+  "include": ["src"]
+}
+"#;
+
+const UI_ESLINT_CONFIG: &str = r#"export { default } from "@SCOPE/config/eslint";
+"#;
+
+const UI_BUTTON: &str = r#"import type { ButtonHTMLAttributes } from "react";
+
+export type ButtonProps = ButtonHTMLAttributes<HTMLButtonElement>;
+
+/** Shared button used across apps; style it once here. */
+export function Button({ type = "button", ...props }: ButtonProps) {
+  return <button type={type} {...props} />;
+}
+"#;
+
+/// npm package scope derived from the project name, e.g. `My App` -> `my-app`.
+fn scope(project_name: &str) -> String {
+    let name = Path::new(project_name)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '-' })
+        .collect()
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<()> {
+    let content = serde_json::to_string_pretty(value)? + "\n";
+    fs::write(path, content).context(format!("Failed to write {}", path.display()))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, content).context(format!("Failed to write {}", path.display()))
+}
+
+fn write_root(root: &Path, project_name: &str, package_manager: &PackageManager) -> Result<()> {
+    let mut package_json = json!({
+        "name": scope(project_name),
+        "private": true,
+        "scripts": {
+            "build": "turbo run build",
+            "dev": "turbo run dev",
+            "lint": "turbo run lint",
+            "typecheck": "turbo run typecheck"
+        },
+        "devDependencies": {
+            "turbo": "^2.5.0"
+        }
+    });
+    // pnpm reads its workspaces from pnpm-workspace.yaml instead
+    match package_manager {
+        PackageManager::Pnpm => write_file(&root.join("pnpm-workspace.yaml"), PNPM_WORKSPACE)?,
+        _ => package_json["workspaces"] = json!(["apps/*", "packages/*"]),
+    }
+    write_json(&root.join("package.json"), &package_json)?;
+    write_file(&root.join("turbo.json"), TURBO_JSON)?;
+    write_file(&root.join(".gitignore"), GITIGNORE)
+}
+
+fn write_config_package(root: &Path, scope: &str) -> Result<()> {
+    let dir = root.join("packages/config");
+    write_json(
+        &dir.join("package.json"),
+        &json!({
+            "name": format!("@{}/config", scope),
+            "version": "0.0.0",
+            "private": true,
+            "exports": {
+                "./tsconfig/base.json": "./tsconfig/base.json",
+                "./tsconfig/nextjs.json": "./tsconfig/nextjs.json",
+                "./tsconfig/react-library.json": "./tsconfig/react-library.json",
+                "./eslint": "./eslint.config.mjs"
+            },
+            "dependencies": {
+                "@eslint/js": "^9",
+                "typescript-eslint": "^8"
+            }
+        }),
+    )?;
+    write_file(&dir.join("tsconfig/base.json"), TSCONFIG_BASE)?;
+    write_file(&dir.join("tsconfig/nextjs.json"), TSCONFIG_NEXTJS)?;
+    write_file(&dir.join("tsconfig/react-library.json"), TSCONFIG_REACT_LIBRARY)?;
+    write_file(&dir.join("eslint.config.mjs"), ESLINT_CONFIG)
+}
+
+fn write_ui_package(root: &Path, scope: &str, package_manager: &PackageManager) -> Result<()> {
+    let dir = root.join("packages/ui");
+    write_json(
+        &dir.join("package.json"),
+        &json!({
+            "name": format!("@{}/ui", scope),
+            "version": "0.0.0",
+            "private": true,
+            "exports": {
+                "./button": "./src/button.tsx"
+            },
+            "scripts": {
+                "lint": "eslint .",
+                "typecheck": "tsc --noEmit"
+            },
+            "peerDependencies": {
+                "react": "^19"
+            },
+            "devDependencies": {
+                format!("@{}/config", scope): package_manager.workspace_version(),
+                "@types/react": "^19",
+                "eslint": "^9",
+                "typescript": "^5"
+            }
+        }),
+    )?;
+    write_file(&dir.join("tsconfig.json"), &UI_TSCONFIG.replace("SCOPE", scope))?;
+    write_file(&dir.join("eslint.config.mjs"), &UI_ESLINT_CONFIG.replace("SCOPE", scope))?;
+    write_file(&dir.join("src/button.tsx"), UI_BUTTON)
+}
+
+/// Links apps/web to the shared packages and lets Next.js compile the UI package's TSX.
+fn link_web_app(root: &Path, scope: &str, package_manager: &PackageManager) -> Result<()> {
+    let dir = root.join("apps/web");
+    let package_json_path = dir.join("package.json");
+    let content = fs::read_to_string(&package_json_path).context("Failed to read apps/web/package.json")?;
+    let mut package_json: Value = serde_json::from_str(&content).context("Failed to parse apps/web/package.json")?;
+
+    package_json["dependencies"][format!("@{}/ui", scope)] = json!(package_manager.workspace_version());
+    package_json["devDependencies"][format!("@{}/config", scope)] = json!(package_manager.workspace_version());
+    package_json["scripts"]["typecheck"] = json!("tsc --noEmit");
+    write_json(&package_json_path, &package_json)?;
+
+    if let Some(config) = NEXT_CONFIG_FILES.into_iter().find(|file| dir.join(file).exists()) {
+        let config_path = dir.join(config);
+        next_config::add_to_array_option_in(
+            &config_path,
+            &["transpilePackages"],
+            &[&format!("\"@{}/ui\"", scope)],
+        )?;
+    }
+    Ok(())
+}
+
+/// Scaffolds a Turborepo workspace: apps/web from the framework's creator, a shared UI
+/// package, shared tsconfig and ESLint configs, and turbo pipelines for build, dev, lint
+/// and typecheck.
+pub fn create_monorepo(
+    project_name: &str,
+    framework: Framework,
+    package_manager: &PackageManager,
+    skip_install: bool,
+    creator_args: &[String],
+) -> Result<()> {
+    if framework != Framework::Next {
+        anyhow::bail!("--monorepo scaffolds a Next.js app in apps/web; {} is not supported yet", framework.display_name());
+    }
+
+    let root = Path::new(project_name);
+    let scope = scope(project_name);
+
+    println!("{}", style(format!("Creating Turborepo workspace in {}...", project_name)).cyan());
+    write_root(root, project_name, package_manager)?;
+    write_config_package(root, &scope)?;
+    write_ui_package(root, &scope, package_manager)?;
+
+    // Dependencies are installed once for the whole workspace below
+    let mut args = creator_args.to_vec();
+    args.push("--skip-install".to_string());
+    run_creator(framework, package_manager, &root.join("apps/web").to_string_lossy(), &args)?;
+    link_web_app(root, &scope, package_manager)?;
+
+    save_project_config(root, package_manager, framework, &WORKSPACES)?;
+    save_project_config(&root.join("apps/web"), package_manager, framework, &[])?;
+
+    if !skip_install {
+        println!("{}", style("Installing workspace dependencies...").cyan());
+        package_manager.install_all(root)?;
+    }
+
+    println!("\n{}", style("Workspace created:").green().bold());
+    println!("• apps/web - {} app", framework.display_name());
+    println!("• packages/ui - Shared components as @{}/ui", scope);
+    println!("• packages/config - Shared tsconfig and ESLint config as @{}/config", scope);
+    println!("• turbo.json - build, dev, lint and typecheck pipelines");
+    println!("{}", style("Run `nstack add` inside apps/web to add features to the app").dim());
+    Ok(())
+}
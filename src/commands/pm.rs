@@ -0,0 +1,163 @@
+use anyhow::{Result, Context};
+use console::style;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use crate::cli::PmAction;
+use crate::config::{self, ConfigScope};
+use crate::framework::Framework;
+use crate::manifest::ProjectManifest;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::readme;
+
+/// Features whose generated files hard-code the package manager's commands.
+const PACKAGE_MANAGER_FILES: [(&str, &str); 2] = [
+    ("docker", "Dockerfile"),
+    ("github-actions", ".github/workflows"),
+];
+
+pub fn run_pm(action: PmAction) -> Result<()> {
+    match action {
+        PmAction::Use { manager } => use_package_manager(&manager),
+    }
+}
+
+/// Version of the package manager on PATH, for package.json's `packageManager` field.
+fn installed_version(package_manager: &PackageManager) -> Option<String> {
+    let output = Command::new(package_manager.to_string()).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+/// Points `packageManager` at the new manager so Corepack and CI pick it up. Deno has no
+/// Corepack support, so the field is dropped for it.
+fn update_package_manager_field(package_manager: &PackageManager, version: &str) -> Result<()> {
+    let mut package_json = package_json::read()?;
+    let Some(fields) = package_json.as_object_mut() else {
+        anyhow::bail!("package.json is not an object");
+    };
+
+    match package_manager {
+        PackageManager::Deno => {
+            if fields.remove("packageManager").is_none() {
+                return Ok(());
+            }
+        }
+        _ => {
+            fields.insert(
+                "packageManager".to_string(),
+                Value::String(format!("{}@{}", package_manager.to_string(), version)),
+            );
+        }
+    }
+    package_json::write(&package_json)
+}
+
+/// Removes every lockfile but the new manager's, including bun's older binary lockfile.
+fn remove_old_lockfiles(package_manager: &PackageManager) -> Result<Vec<&'static str>> {
+    let lockfiles = PackageManager::ALL.iter().flat_map(|pm| pm.lockfiles().iter().copied());
+
+    let mut removed = Vec::new();
+    for lockfile in lockfiles {
+        if lockfile != package_manager.lockfile() && Path::new(lockfile).exists() {
+            fs::remove_file(lockfile).context(format!("Failed to remove {}", lockfile))?;
+            removed.push(lockfile);
+        }
+    }
+    Ok(removed)
+}
+
+/// Rewrites the commands in the scripts nstack added, e.g. `pnpm exec` to `npx`.
+fn rewrite_scripts(from: &PackageManager, to: &PackageManager) -> Result<Vec<String>> {
+    let manifest = ProjectManifest::load()?;
+    let prefixes = [
+        (from.exec_command(""), to.exec_command("")),
+        (from.dlx_command(""), to.dlx_command("")),
+        (from.run_script(""), to.run_script("")),
+    ];
+
+    let mut rewritten = Vec::new();
+    for name in manifest.scripts.values().flatten() {
+        let Some(command) = package_json::script(name) else {
+            continue;
+        };
+        let updated = prefixes
+            .iter()
+            .fold(command.clone(), |command, (old, new)| command.replace(old.as_str(), new));
+        if updated != command {
+            rewritten.push((name.clone(), updated));
+        }
+    }
+
+    if !rewritten.is_empty() {
+        let scripts: Vec<(&str, &str)> = rewritten
+            .iter()
+            .map(|(name, command)| (name.as_str(), command.as_str()))
+            .collect();
+        package_json::set_scripts(&scripts)?;
+    }
+    Ok(rewritten.into_iter().map(|(name, _)| name).collect())
+}
+
+/// `nstack pm use <manager>`: switches the project to another package manager and
+/// reinstalls its dependencies from scratch with it.
+pub fn use_package_manager(manager: &str) -> Result<()> {
+    if !Path::new("package.json").exists() {
+        anyhow::bail!("No package.json found in the current directory");
+    }
+    let to = PackageManager::parse(manager)?;
+    let from = PackageManager::from_project_config()?;
+    if from == to && config::get(ConfigScope::Project, "package_manager")?.is_some() {
+        println!("{}", style(format!("The project already uses {}", to.to_string())).yellow());
+        return Ok(());
+    }
+    let Some(version) = installed_version(&to) else {
+        anyhow::bail!("{} is not installed; install it before switching the project to it", to.to_string());
+    };
+
+    config::set(ConfigScope::Project, "package_manager", to.to_string())?;
+    update_package_manager_field(&to, &version)?;
+    let removed = remove_old_lockfiles(&to)?;
+    let rewritten = rewrite_scripts(&from, &to)?;
+    readme::update(Path::new("."), &to, Framework::from_project_config())?;
+
+    // Another manager's node_modules layout confuses installs, pnpm's in particular
+    if Path::new("node_modules").exists() {
+        fs::remove_dir_all("node_modules").context("Failed to remove node_modules")?;
+    }
+    println!("{}", style(format!("Installing dependencies with {}...", to.to_string())).cyan());
+    to.install_all(Path::new("."))?;
+
+    println!("\n{}", style(format!("Switched from {} to {}", from.to_string(), to.to_string())).green().bold());
+    println!("• .nstack/config - package_manager={}", to.to_string());
+    for lockfile in removed {
+        println!("• {} - removed", lockfile);
+    }
+    println!("• {} - regenerated", to.lockfile());
+    if !rewritten.is_empty() {
+        println!("• package.json - rewrote scripts: {}", rewritten.join(", "));
+    }
+
+    let outdated: Vec<&str> = PACKAGE_MANAGER_FILES
+        .iter()
+        .filter(|(feature, path)| ProjectManifest::is_feature_installed(feature) && Path::new(path).exists())
+        .map(|(_, path)| *path)
+        .collect();
+    if !outdated.is_empty() {
+        println!(
+            "{}",
+            style(format!(
+                "{} still use {}; re-add those features with --force to regenerate them",
+                outdated.join(" and "),
+                from.to_string()
+            ))
+            .yellow()
+        );
+    }
+    Ok(())
+}
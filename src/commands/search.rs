@@ -0,0 +1,56 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::{Select, theme::ColorfulTheme};
+use crate::commands::add::add_feature;
+use crate::registry;
+
+pub async fn search_features(query: Option<String>, category: Option<String>, install: bool) -> Result<()> {
+    let query = query.map(|q| q.to_lowercase());
+    let category = category.map(|c| c.to_lowercase());
+
+    let results: Vec<registry::RegistryFeature> = registry::load()
+        .await
+        .into_iter()
+        .filter(|feature| category.as_ref().is_none_or(|c| feature.category == *c))
+        .filter(|feature| {
+            query.as_ref().is_none_or(|q| {
+                feature.name.to_lowercase().contains(q) || feature.description.to_lowercase().contains(q)
+            })
+        })
+        .collect();
+
+    if results.is_empty() {
+        println!("{}", style("No features match your search.").yellow());
+        return Ok(());
+    }
+
+    println!("\n{}", style(format!("{} feature(s) found:", results.len())).cyan().bold());
+    for feature in &results {
+        let kind = if feature.official { style("official").green() } else { style("community").magenta() };
+        println!(
+            "\n{} [{}] {} · {} installs",
+            style(&feature.name).green().bold(),
+            kind,
+            style(&feature.category).dim(),
+            feature.downloads
+        );
+        println!("  {}", feature.description);
+        println!("  {}", style(format!("nstack add --feature {}", feature.install_target)).dim());
+    }
+
+    if !install {
+        return Ok(());
+    }
+
+    let items: Vec<String> = results
+        .iter()
+        .map(|feature| format!("{} - {}", feature.name, feature.description))
+        .collect();
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select a feature to install")
+        .default(0)
+        .items(&items)
+        .interact()?;
+
+    add_feature(Some(results[selection].install_target.clone())).await
+}
@@ -0,0 +1,118 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::path::{Path, PathBuf};
+use crate::change_set;
+use crate::package_json;
+use crate::package_manager::{CommandLine, PackageManager};
+use crate::project_structure::project_file;
+
+/// Output lines shown for a failing step when none of them mention a generated file.
+const TAIL_LINES: usize = 20;
+
+struct Check {
+    name: &'static str,
+    command_line: CommandLine,
+}
+
+fn checks(package_manager: &PackageManager, build: bool) -> Vec<Check> {
+    let mut checks = Vec::new();
+    if project_file("tsconfig.json").exists() {
+        checks.push(Check { name: "Typecheck", command_line: package_manager.exec_line(&["tsc", "--noEmit"]) });
+    }
+    if package_json::has_script("lint") {
+        checks.push(Check { name: "Lint", command_line: package_manager.run_script_line("lint") });
+    }
+    if build {
+        checks.push(Check { name: "Build", command_line: package_manager.run_script_line("build") });
+    }
+    checks
+}
+
+/// Runs a check with its output captured so the spinner stays intact, returning whether it
+/// passed and everything it printed.
+fn run_check(check: &Check) -> Result<(bool, String)> {
+    let output = check
+        .command_line
+        .command()
+        .env("FORCE_COLOR", "0")
+        .output()
+        .context(format!("Failed to run {}", check.command_line))?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok((output.status.success(), text))
+}
+
+/// Normalizes `./src/app/page.tsx` and Windows separators so tool output matches manifest paths.
+fn mentions(line: &str, path: &Path) -> bool {
+    let path = path.to_string_lossy().replace('\\', "/");
+    line.replace('\\', "/").contains(path.trim_start_matches("./"))
+}
+
+/// Prints the output lines that point at files nstack generated, grouped by the feature
+/// that wrote them, or the end of the output when the failure is elsewhere.
+fn report_failure(output: &str, generated: &[(PathBuf, String)]) {
+    let mut attributed = 0;
+    for (path, feature) in generated {
+        let lines: Vec<&str> = output.lines().filter(|line| mentions(line, path)).collect();
+        if lines.is_empty() {
+            continue;
+        }
+        attributed += lines.len();
+        println!(
+            "  {} {}",
+            style(path.display()).red().bold(),
+            style(format!("(generated by {})", feature)).dim()
+        );
+        for line in lines {
+            println!("    {}", line.trim());
+        }
+    }
+
+    if attributed == 0 {
+        println!("  {}", style("No errors point at files nstack generated:").dim());
+        let lines: Vec<&str> = output.lines().collect();
+        for line in &lines[lines.len().saturating_sub(TAIL_LINES)..] {
+            println!("    {}", line);
+        }
+    }
+}
+
+/// Runs the typecheck, lint and optionally the build, attributing failures to the files
+/// features generated so a broken scaffold is easy to spot.
+pub fn verify_project(build: bool) -> Result<()> {
+    if !project_file("package.json").exists() {
+        anyhow::bail!("No package.json found in the current directory");
+    }
+
+    let package_manager = PackageManager::from_project_config()?;
+    let generated = change_set::generated_files()?;
+    let checks = checks(&package_manager, build);
+    if checks.is_empty() {
+        println!("{}", style("Nothing to verify: no tsconfig.json or lint script found").yellow());
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for check in &checks {
+        let pb = ProgressBar::new_spinner();
+        pb.set_message(format!("{}: {}", check.name, check.command_line));
+        let (passed, output) = run_check(check)?;
+        pb.finish_and_clear();
+
+        if passed {
+            println!("{} {}", style("✓").green(), check.name);
+        } else {
+            println!("{} {} ({})", style("✗").red(), check.name, check.command_line);
+            report_failure(&output, &generated);
+            failed.push(check.name);
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!("Verification failed: {}", failed.join(", "));
+    }
+    println!("\n{}", style("Project verified successfully!").green().bold());
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use console::style;
+use std::process::Command;
+use crate::next_config;
+use crate::package_json;
+use crate::project_structure::ProjectStructure;
+
+/// Oldest Node.js release nstack's generated code and the Next.js versions it targets run on.
+pub const MIN_NODE_MAJOR: u32 = 18;
+
+/// Oldest Next.js major a feature works with, and why.
+fn min_next_major(feature: &str) -> Option<(u32, &'static str)> {
+    match feature {
+        "og-image" | "landing" => Some((14, "it imports ImageResponse from next/og")),
+        "cloudflare" => Some((14, "OpenNext supports Next.js 14 and later")),
+        "perf" => Some((14, "it relies on experimental.optimizePackageImports")),
+        "i18n" => Some((14, "next-intl's App Router integration needs Next.js 14")),
+        "drizzle" | "k8s" | "zustand" | "redux" | "jotai" | "theme" | "motion" | "data-table" | "charts"
+        | "admin" | "saas" | "search" | "notifications" | "collaboration" | "maps" | "scheduling" | "import-export" | "images" | "sitemap" | "captcha" | "cookie-consent" | "icons" | "toasts" | "supabase" | "firebase" | "convex" | "appwrite" | "pocketbase" | "kysely"
+        | "mongodb" | "gel" | "local-first" => Some((13, "it scaffolds App Router routes and layouts")),
+        _ => None,
+    }
+}
+
+/// Next.js features a generated file can depend on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Capability {
+    AppRouter,
+    ServerActions,
+    EdgeRuntime,
+}
+
+impl Capability {
+    fn describe(&self) -> &'static str {
+        match self {
+            Capability::AppRouter => "the App Router (Next.js 13.4+, an app directory)",
+            Capability::ServerActions => "Server Actions (Next.js 14+ on the App Router)",
+            Capability::EdgeRuntime => "the Edge runtime for middleware (Next.js 13+)",
+        }
+    }
+}
+
+/// What the project's Next.js can run, from the installed version (or the declared range
+/// before installing), the router layout and experimental flags in next.config.
+#[derive(Debug, Clone, Copy)]
+pub struct NextCapabilities {
+    pub major: u32,
+    pub minor: u32,
+    pub app_router: bool,
+    pub server_actions: bool,
+    pub edge_runtime: bool,
+}
+
+impl NextCapabilities {
+    /// `None` when Next.js is not a dependency or its version is not a number, e.g. `canary`.
+    pub fn detect() -> Option<Self> {
+        let version = package_json::installed_version("next").or_else(|| package_json::dependency_version("next"))?;
+        let mut numbers = version
+            .trim_start_matches(|c: char| !c.is_ascii_digit())
+            .split(|c: char| !c.is_ascii_digit())
+            .map(|part| part.parse::<u32>().ok());
+        let major = numbers.next()??;
+        let minor = numbers.next().flatten().unwrap_or(0);
+
+        let at_least = |required: (u32, u32)| (major, minor) >= required;
+        let experimental = |flag: &str| next_config::option(&["experimental", flag]).as_deref() == Some("true");
+        let app_dir = ProjectStructure::detect().is_ok_and(|structure| structure.is_app_router());
+        // Before 13.4 the app directory and Server Actions were opt-in experiments
+        let app_router = app_dir && (at_least((13, 4)) || (major == 13 && experimental("appDir")));
+        let server_actions = app_router && (at_least((14, 0)) || (major == 13 && experimental("serverActions")));
+
+        Some(NextCapabilities {
+            major,
+            minor,
+            app_router,
+            server_actions,
+            edge_runtime: major >= 13,
+        })
+    }
+
+    pub fn has(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::AppRouter => self.app_router,
+            Capability::ServerActions => self.server_actions,
+            Capability::EdgeRuntime => self.edge_runtime,
+        }
+    }
+}
+
+/// Next.js capabilities a feature's generated code relies on, and what for.
+fn required_capabilities(feature: &str) -> &'static [(Capability, &'static str)] {
+    match feature {
+        "appwrite" => &[(Capability::ServerActions, "its sign-in and notes forms post to Server Actions")],
+        "supabase" => &[
+            (Capability::ServerActions, "its login form posts to Server Actions"),
+            (Capability::EdgeRuntime, "it refreshes sessions in middleware"),
+        ],
+        "i18n" => &[
+            (Capability::AppRouter, "it restructures the app under app/[locale]"),
+            (Capability::EdgeRuntime, "it routes locales in middleware"),
+        ],
+        "og-image" => &[(Capability::AppRouter, "it adds app/opengraph-image.tsx and an app/api/og route handler")],
+        "theme" | "zustand" | "redux" | "jotai" | "motion" | "toasts" | "convex" | "charts" | "data-table"
+        | "admin" | "landing" | "saas" | "search" | "notifications" | "collaboration" | "maps" | "scheduling" | "import-export" | "images" | "sitemap" | "captcha" | "cookie-consent" | "firebase" | "gel" | "local-first" | "pocketbase" => &[(Capability::AppRouter, "it wires providers or pages into the App Router layout")],
+        _ => &[],
+    }
+}
+
+/// First number in a version or range like `v20.11.0`, `^14.2.3` or `>=18.17`.
+pub fn major(version: &str) -> Option<u32> {
+    version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Version of the Node.js on the PATH without the `v`, e.g. `22.11.0`.
+pub fn node_version() -> Option<String> {
+    let output = Command::new("node").arg("--version").output().ok()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().trim_start_matches('v').to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+fn node_major() -> Option<u32> {
+    major(&node_version()?)
+}
+
+/// Refuses features the installed Next.js or Node.js cannot run, and warns when Node.js
+/// does not satisfy the project's own `engines.node`.
+pub fn check(feature: &str) -> Result<()> {
+    if let Some(capabilities) = NextCapabilities::detect() {
+        for (capability, reason) in required_capabilities(feature) {
+            if !capabilities.has(*capability) {
+                anyhow::bail!(
+                    "{} needs {} because {}; this project runs Next.js {}.{}{}.",
+                    feature,
+                    capability.describe(),
+                    reason,
+                    capabilities.major,
+                    capabilities.minor,
+                    if capabilities.app_router { "" } else { " with the Pages Router" }
+                );
+            }
+        }
+    }
+
+    if let (Some((required, reason)), Some(installed)) =
+        (min_next_major(feature), package_json::dependency_major_version("next"))
+        && installed < required
+    {
+        anyhow::bail!(
+            "{} needs Next.js {} or later because {}; this project has Next.js {}. Upgrade with `npx @next/codemod upgrade`.",
+            feature,
+            required,
+            reason,
+            installed
+        );
+    }
+
+    let Some(node) = node_major() else {
+        return Ok(());
+    };
+    if node < MIN_NODE_MAJOR {
+        anyhow::bail!("nstack features need Node.js {} or later; found Node.js {}", MIN_NODE_MAJOR, node);
+    }
+    if let Some(engine) = package_json::node_engine()
+        && major(&engine).is_some_and(|required| node < required)
+    {
+        println!(
+            "{}",
+            style(format!("This project asks for Node.js {} in engines.node, but Node.js {} is running", engine, node)).yellow()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use anyhow::{Result, Context};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a setting is stored. Project settings override global ones.
+#[derive(Debug, Clone, Copy)]
+pub enum ConfigScope {
+    Project,
+    Global,
+}
+
+pub struct ConfigKey {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Accepted values, or `None` for free-form values
+    pub values: Option<&'static [&'static str]>,
+}
+
+pub const KNOWN_KEYS: &[ConfigKey] = &[
+    ConfigKey {
+        name: "package_manager",
+        description: "Package manager used to install dependencies",
+        values: Some(&["npm", "yarn", "pnpm", "bun", "deno"]),
+    },
+    ConfigKey {
+        name: "framework",
+        description: "Framework the project was created with; features that need Next.js are refused otherwise",
+        values: Some(crate::framework::FRAMEWORK_NAMES),
+    },
+    ConfigKey {
+        name: "workspaces",
+        description: "Comma-separated workspace directories of a monorepo, relative to its root",
+        values: None,
+    },
+    ConfigKey {
+        name: "app",
+        description: "App directory nstack works on in a repository with several Next.js apps",
+        values: None,
+    },
+    ConfigKey {
+        name: "frozen_lockfile",
+        description: "Fail installs that would change the lockfile, like --frozen",
+        values: Some(&["true", "false"]),
+    },
+    ConfigKey {
+        name: "prefer_offline",
+        description: "Install from the package cache when possible, like --prefer-offline",
+        values: Some(&["true", "false"]),
+    },
+    ConfigKey {
+        name: "silent_install",
+        description: "Only show errors from installs, like --silent",
+        values: Some(&["true", "false"]),
+    },
+    ConfigKey {
+        name: "default_provider",
+        description: "Database provider preselected by `nstack add --feature drizzle`",
+        values: Some(&["postgresql", "neon", "vercel-postgres", "supabase", "xata", "pglite", "nile", "bun-sql"]),
+    },
+    ConfigKey {
+        name: "update_check",
+        description: "Check for newer nstack releases after each command",
+        values: Some(&["true", "false"]),
+    },
+    ConfigKey {
+        name: "format",
+        description: "Run the project's Prettier or Biome on files nstack writes",
+        values: Some(&["true", "false"]),
+    },
+];
+
+pub fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(PathBuf::from)
+}
+
+/// Directory for settings shared by every project, `~/.nstack`.
+pub fn global_dir() -> Option<PathBuf> {
+    Some(home_dir()?.join(".nstack"))
+}
+
+impl ConfigScope {
+    pub fn path(&self) -> Result<PathBuf> {
+        match self {
+            ConfigScope::Project => Ok(PathBuf::from(".nstack").join("config")),
+            ConfigScope::Global => global_dir()
+                .map(|dir| dir.join("config"))
+                .context("Could not determine the home directory for global config"),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigScope::Project => "project",
+            ConfigScope::Global => "global",
+        }
+    }
+}
+
+/// Returns the known key definition, or an error listing the valid keys.
+pub fn known_key(name: &str) -> Result<&'static ConfigKey> {
+    KNOWN_KEYS.iter().find(|key| key.name == name).with_context(|| {
+        let names: Vec<&str> = KNOWN_KEYS.iter().map(|key| key.name).collect();
+        format!("Unknown config key '{}'. Known keys: {}", name, names.join(", "))
+    })
+}
+
+pub fn validate(name: &str, value: &str) -> Result<()> {
+    let key = known_key(name)?;
+    if let Some(values) = key.values
+        && !values.contains(&value)
+    {
+        anyhow::bail!("Invalid value '{}' for {}. Expected one of: {}", value, name, values.join(", "));
+    }
+    Ok(())
+}
+
+/// Reads a key from one scope. Config files hold one `key=value` pair per line.
+pub fn get(scope: ConfigScope, name: &str) -> Result<Option<String>> {
+    read_key(&scope.path()?, name)
+}
+
+/// Reads a key from the project config of the project in `dir`, e.g. a monorepo root.
+pub fn get_in(dir: &Path, name: &str) -> Option<String> {
+    read_key(&dir.join(".nstack").join("config"), name).ok().flatten()
+}
+
+fn read_key(path: &Path, name: &str) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read {}", path.display()))?;
+
+    Ok(content.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == name).then(|| value.trim().to_string())
+    }))
+}
+
+/// Writes a key to one scope, replacing an existing value and keeping other lines as-is.
+pub fn set(scope: ConfigScope, name: &str, value: &str) -> Result<()> {
+    validate(name, value)?;
+
+    let path = scope.path()?;
+    let content = fs::read_to_string(&path).unwrap_or_default();
+
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, _)) if key.trim() == name => {
+                replaced = true;
+                format!("{}={}", name, value)
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    if !replaced {
+        lines.push(format!("{}={}", name, value));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, lines.join("\n") + "\n").context(format!("Failed to write {}", path.display()))
+}
+
+/// Effective value of a key: the project setting if present, otherwise the global one.
+pub fn value(name: &str) -> Option<String> {
+    [ConfigScope::Project, ConfigScope::Global]
+        .into_iter()
+        .find_map(|scope| get(scope, name).ok().flatten())
+}
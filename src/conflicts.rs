@@ -0,0 +1,176 @@
+use anyhow::Result;
+use console::style;
+use crate::package_json;
+use crate::prompt;
+
+/// Libraries that solve the same problem: the features in a group as `(feature, library)`
+/// and the packages to look for as `(package, library)`. A feature conflicts with every
+/// package of another library in its group.
+type Group = (&'static [(&'static str, &'static str)], &'static [(&'static str, &'static str)]);
+
+const GROUPS: &[Group] = &[
+    // database
+    (
+        &[
+            ("drizzle", "Drizzle ORM"),
+            ("saas", "Drizzle ORM"),
+            ("kysely", "Kysely"),
+            ("mongodb", "Mongoose"),
+            ("gel", "Gel"),
+        ],
+        &[
+            ("@prisma/client", "Prisma"),
+            ("prisma", "Prisma"),
+            ("typeorm", "TypeORM"),
+            ("sequelize", "Sequelize"),
+            ("drizzle-orm", "Drizzle ORM"),
+            ("kysely", "Kysely"),
+            ("mongoose", "Mongoose"),
+            ("gel", "Gel"),
+        ],
+    ),
+    // backend
+    (
+        &[
+            ("supabase", "Supabase"),
+            ("firebase", "Firebase"),
+            ("convex", "Convex"),
+            ("appwrite", "Appwrite"),
+            ("pocketbase", "PocketBase"),
+        ],
+        &[
+            ("@supabase/ssr", "Supabase"),
+            ("@supabase/supabase-js", "Supabase"),
+            ("firebase", "Firebase"),
+            ("firebase-admin", "Firebase"),
+            ("convex", "Convex"),
+            ("node-appwrite", "Appwrite"),
+            ("appwrite", "Appwrite"),
+            ("pocketbase", "PocketBase"),
+        ],
+    ),
+    // state
+    (
+        &[("zustand", "Zustand"), ("redux", "Redux Toolkit"), ("jotai", "Jotai")],
+        &[
+            ("zustand", "Zustand"),
+            ("@reduxjs/toolkit", "Redux Toolkit"),
+            ("redux", "Redux"),
+            ("jotai", "Jotai"),
+            ("recoil", "Recoil"),
+            ("mobx", "MobX"),
+            ("valtio", "Valtio"),
+        ],
+    ),
+    // lint
+    (
+        &[("biome", "Biome"), ("eslint-prettier", "ESLint + Prettier")],
+        &[
+            ("@biomejs/biome", "Biome"),
+            ("eslint-config-prettier", "ESLint + Prettier"),
+            ("rome", "Rome"),
+        ],
+    ),
+    // unit test
+    (
+        &[("vitest", "Vitest")],
+        &[("jest", "Jest"), ("vitest", "Vitest")],
+    ),
+    // e2e test
+    (
+        &[("playwright", "Playwright"), ("cypress", "Cypress")],
+        &[("@playwright/test", "Playwright"), ("cypress", "Cypress")],
+    ),
+    // i18n
+    (
+        &[("i18n", "next-intl")],
+        &[
+            ("next-intl", "next-intl"),
+            ("next-i18next", "next-i18next"),
+            ("react-i18next", "react-i18next"),
+            ("@lingui/core", "Lingui"),
+        ],
+    ),
+    // toasts
+    (
+        &[("toasts", "Sonner")],
+        &[
+            ("sonner", "Sonner"),
+            ("react-hot-toast", "react-hot-toast"),
+            ("react-toastify", "React-Toastify"),
+        ],
+    ),
+    // charts
+    (
+        &[("charts", "Recharts")],
+        &[
+            ("recharts", "Recharts"),
+            ("chart.js", "Chart.js"),
+            ("@nivo/core", "Nivo"),
+            ("@tremor/react", "Tremor"),
+        ],
+    ),
+    // animation
+    (
+        &[("motion", "Motion")],
+        &[
+            ("motion", "Motion"),
+            ("framer-motion", "Framer Motion"),
+            ("@react-spring/web", "React Spring"),
+            ("gsap", "GSAP"),
+        ],
+    ),
+];
+
+/// What to do before adding a feature whose job another installed library already does.
+pub enum Resolution {
+    Continue,
+    /// Remove these packages, then add the feature
+    Migrate(Vec<&'static str>),
+    Abort,
+}
+
+/// Installed packages overlapping with `feature`, as `(package, library)`. Packages of the
+/// library the feature sets up are not conflicts, so re-adding a feature stays quiet.
+pub fn find_conflicts(feature: &str) -> Vec<(&'static str, &'static str)> {
+    GROUPS
+        .iter()
+        .filter_map(|(features, packages)| {
+            let (_, library) = features.iter().find(|(name, _)| *name == feature)?;
+            Some(packages.iter().filter(move |(_, other)| other != library))
+        })
+        .flatten()
+        .copied()
+        .filter(|(package, _)| package_json::has_dependency(package))
+        .collect()
+}
+
+/// Warns about overlapping libraries and asks whether to keep both, migrate by removing
+/// the existing ones, or abort.
+pub fn resolve(feature: &str) -> Result<Resolution> {
+    let conflicts = find_conflicts(feature);
+    if conflicts.is_empty() {
+        return Ok(Resolution::Continue);
+    }
+
+    let mut names: Vec<&str> = conflicts.iter().map(|(_, name)| *name).collect();
+    names.sort_unstable();
+    names.dedup();
+    println!(
+        "{}",
+        style(format!("{} overlaps with {} already in this project", feature, names.join(", "))).yellow()
+    );
+
+    let packages: Vec<&'static str> = conflicts.iter().map(|(package, _)| *package).collect();
+    let options = [
+        "Continue and keep both".to_string(),
+        format!("Migrate: remove {} and continue", packages.join(", ")),
+        "Abort".to_string(),
+    ];
+    let resolution = match prompt::select("conflicts", "How should nstack proceed?", &options, 2)? {
+        0 => Resolution::Continue,
+        1 => Resolution::Migrate(packages),
+        _ => Resolution::Abort,
+    };
+    Ok(resolution)
+}
@@ -0,0 +1,43 @@
+use anyhow::{Result, Context};
+use std::fs;
+use crate::change_set;
+use crate::project_structure::project_file;
+
+const COMPOSE_PATH: &str = "docker-compose.yml";
+
+/// Adds a service (and optionally a named volume) to docker-compose.yml, creating the file
+/// when needed. `service` is the YAML block for the service, indented under `services:`.
+/// Returns `Ok(false)` when a service with the same name already exists.
+pub fn add_service(name: &str, service: &str, volume: Option<&str>) -> Result<bool> {
+    let volume_entry = volume.map(|volume| format!("  {}:\n", volume));
+    let compose_path = project_file(COMPOSE_PATH);
+
+    if !compose_path.exists() {
+        let mut content = format!("services:\n{}", service);
+        if let Some(volume_entry) = &volume_entry {
+            content.push_str(&format!("\nvolumes:\n{}", volume_entry));
+        }
+        change_set::write(&compose_path, content).context("Failed to create docker-compose.yml")?;
+        return Ok(true);
+    }
+
+    let content = fs::read_to_string(&compose_path).context("Failed to read docker-compose.yml")?;
+    if content.contains(&format!("\n  {}:", name)) {
+        return Ok(false);
+    }
+
+    let Some(index) = content.find("services:\n").map(|index| index + "services:\n".len()) else {
+        anyhow::bail!("Could not find `services:` in docker-compose.yml. Add the {} service manually.", name);
+    };
+    let mut updated_content = format!("{}{}\n{}", &content[..index], service, &content[index..]);
+
+    if let Some(volume_entry) = volume_entry {
+        updated_content = match updated_content.find("\nvolumes:\n").map(|index| index + "\nvolumes:\n".len()) {
+            Some(index) => format!("{}{}{}", &updated_content[..index], volume_entry, &updated_content[index..]),
+            None => format!("{}\n\nvolumes:\n{}", updated_content.trim_end(), volume_entry),
+        };
+    }
+
+    change_set::write(&compose_path, updated_content).context("Failed to update docker-compose.yml")?;
+    Ok(true)
+}
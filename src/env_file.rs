@@ -0,0 +1,45 @@
+use anyhow::{Result, Context};
+use std::fs;
+use crate::manifest::ProjectManifest;
+use crate::change_set;
+use crate::project_structure::project_file;
+
+const ENV_PATH: &str = ".env";
+
+/// Appends `vars` to .env under a `# section` comment, skipping variables that are already
+/// defined, and records them in the manifest as required by `feature`.
+pub fn add_env_vars(feature: &str, section: &str, vars: &[(&str, &str)]) -> Result<()> {
+    let env_path = project_file(ENV_PATH);
+    let existing_content = if env_path.exists() {
+        fs::read_to_string(&env_path).context("Failed to read .env")?
+    } else {
+        String::new()
+    };
+
+    let missing: Vec<String> = vars
+        .iter()
+        .filter(|(name, _)| !is_defined(&existing_content, name))
+        .map(|(name, value)| format!("{}=\"{}\"", name, value))
+        .collect();
+
+    if !missing.is_empty() {
+        let block = format!("# {}\n{}", section, missing.join("\n"));
+        let updated_content = if existing_content.trim().is_empty() {
+            format!("{}\n", block)
+        } else {
+            format!("{}\n\n{}\n", existing_content.trim_end(), block)
+        };
+        change_set::write(&env_path, updated_content).context("Failed to update .env")?;
+    }
+
+    let names: Vec<&str> = vars.iter().map(|(name, _)| *name).collect();
+    ProjectManifest::record_env(feature, &names)
+}
+
+fn is_defined(content: &str, name: &str) -> bool {
+    content.lines().any(|line| {
+        line.trim_start()
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    })
+}
@@ -0,0 +1,435 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::features::data_table;
+use crate::file_writer;
+use crate::manifest;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::root_layout;
+
+const NAVIGATION: &str = r#"import { LayoutDashboard, Users, type LucideIcon } from "lucide-react";
+
+export type AdminNavItem = {
+  title: string;
+  href: string;
+  icon: LucideIcon;
+};
+
+// Sidebar entries, in order; breadcrumbs take their titles from here too
+export const ADMIN_NAV: AdminNavItem[] = [
+  { title: "Overview", href: "/admin", icon: LayoutDashboard },
+  { title: "Users", href: "/admin/users", icon: Users },
+];
+
+// Drops a locale or basePath prefix so `/en/admin/users` matches `/admin/users`
+function adminPath(pathname: string) {
+  const index = pathname.indexOf("/admin");
+  return index === -1 ? pathname : pathname.slice(index);
+}
+
+export function isActive(pathname: string, href: string) {
+  const path = adminPath(pathname);
+  return href === "/admin" ? path === href : path === href || path.startsWith(`${href}/`);
+}
+
+function humanize(segment: string) {
+  const words = decodeURIComponent(segment).replace(/[-_]/g, " ");
+  return words.charAt(0).toUpperCase() + words.slice(1);
+}
+
+/** Breadcrumb trail for an admin pathname, e.g. Admin › Users */
+export function adminBreadcrumbs(pathname: string) {
+  const segments = adminPath(pathname).split("/").filter(Boolean);
+  return segments.map((segment, index) => {
+    const href = `/${segments.slice(0, index + 1).join("/")}`;
+    const title = index === 0 ? "Admin" : (ADMIN_NAV.find((item) => item.href === href)?.title ?? humanize(segment));
+    return { title, href };
+  });
+}
+"#;
+
+const SESSION: &str = r#"import { notFound, redirect } from "next/navigation";
+__SESSION_IMPORTS__
+export type SessionUser = {
+  email?: string | null;
+};
+
+__SESSION_USER__
+// Comma-separated emails allowed into /admin; any signed-in user when unset
+function adminEmails() {
+  return (process.env.ADMIN_EMAILS ?? "")
+    .split(",")
+    .map((email) => email.trim().toLowerCase())
+    .filter(Boolean);
+}
+
+/** The signed-in admin. Sends signed-out visitors to sign in and hides /admin from everyone else. */
+export async function requireAdmin(): Promise<SessionUser> {
+  const user = await getSessionUser();
+  if (!user) redirect("__SIGN_IN_ROUTE__");
+
+  const admins = adminEmails();
+  if (admins.length > 0 && !admins.includes(user.email?.toLowerCase() ?? "")) notFound();
+  return user;
+}
+"#;
+
+const SIDEBAR: &str = r#""use client";
+
+import Link from "next/link";
+import { usePathname } from "next/navigation";
+import {
+  Sidebar,
+  SidebarContent,
+  SidebarGroup,
+  SidebarGroupContent,
+  SidebarGroupLabel,
+  SidebarHeader,
+  SidebarMenu,
+  SidebarMenuButton,
+  SidebarMenuItem,
+} from "@/components/ui/sidebar";
+import { ADMIN_NAV, isActive } from "@/lib/admin/navigation";
+
+export function AdminSidebar() {
+  const pathname = usePathname();
+
+  return (
+    <Sidebar collapsible="icon">
+      <SidebarHeader>
+        <Link href="/admin" className="px-2 py-1 text-sm font-semibold">
+          Admin
+        </Link>
+      </SidebarHeader>
+      <SidebarContent>
+        <SidebarGroup>
+          <SidebarGroupLabel>Manage</SidebarGroupLabel>
+          <SidebarGroupContent>
+            <SidebarMenu>
+              {ADMIN_NAV.map((item) => (
+                <SidebarMenuItem key={item.href}>
+                  <SidebarMenuButton asChild isActive={isActive(pathname, item.href)} tooltip={item.title}>
+                    <Link href={item.href}>
+                      <item.icon />
+                      <span>{item.title}</span>
+                    </Link>
+                  </SidebarMenuButton>
+                </SidebarMenuItem>
+              ))}
+            </SidebarMenu>
+          </SidebarGroupContent>
+        </SidebarGroup>
+      </SidebarContent>
+    </Sidebar>
+  );
+}
+"#;
+
+const HEADER: &str = r#""use client";
+
+import Link from "next/link";
+import { usePathname } from "next/navigation";
+import { Fragment } from "react";
+import {
+  Breadcrumb,
+  BreadcrumbItem,
+  BreadcrumbLink,
+  BreadcrumbList,
+  BreadcrumbPage,
+  BreadcrumbSeparator,
+} from "@/components/ui/breadcrumb";
+import { Separator } from "@/components/ui/separator";
+import { SidebarTrigger } from "@/components/ui/sidebar";
+import { adminBreadcrumbs } from "@/lib/admin/navigation";
+
+export function AdminHeader({ email }: { email?: string | null }) {
+  const crumbs = adminBreadcrumbs(usePathname());
+
+  return (
+    <header className="flex h-14 shrink-0 items-center gap-2 border-b px-4">
+      <SidebarTrigger className="-ml-1" />
+      <Separator orientation="vertical" className="mr-2 h-4" />
+      <Breadcrumb>
+        <BreadcrumbList>
+          {crumbs.map((crumb, index) => (
+            <Fragment key={crumb.href}>
+              {index > 0 && <BreadcrumbSeparator />}
+              <BreadcrumbItem>
+                {index === crumbs.length - 1 ? (
+                  <BreadcrumbPage>{crumb.title}</BreadcrumbPage>
+                ) : (
+                  <BreadcrumbLink asChild>
+                    <Link href={crumb.href}>{crumb.title}</Link>
+                  </BreadcrumbLink>
+                )}
+              </BreadcrumbItem>
+            </Fragment>
+          ))}
+        </BreadcrumbList>
+      </Breadcrumb>
+      {email && <span className="ml-auto text-sm text-muted-foreground">{email}</span>}
+    </header>
+  );
+}
+"#;
+
+const LAYOUT: &str = r#"import type { ReactNode } from "react";
+import { AdminHeader } from "@/components/admin/admin-header";
+import { AdminSidebar } from "@/components/admin/admin-sidebar";
+import { SidebarInset, SidebarProvider } from "@/components/ui/sidebar";
+import { requireAdmin } from "@/lib/admin/session";
+
+export default async function AdminLayout({ children }: { children: ReactNode }) {
+  const user = await requireAdmin();
+
+  return (
+    <SidebarProvider>
+      <AdminSidebar />
+      <SidebarInset>
+        <AdminHeader email={user.email} />
+        <div className="flex flex-1 flex-col gap-6 p-6">{children}</div>
+      </SidebarInset>
+    </SidebarProvider>
+  );
+}
+"#;
+
+// Layouts are not re-rendered on client navigation, so every page checks the session too
+const OVERVIEW_PAGE: &str = r#"import Link from "next/link";
+import { ADMIN_NAV } from "@/lib/admin/navigation";
+import { requireAdmin } from "@/lib/admin/session";
+import { parseTableQuery } from "@/lib/data-table";
+import { getUsers } from "@/lib/data/users";
+
+export default async function AdminPage() {
+  await requireAdmin();
+  const { total } = await getUsers(parseTableQuery({}));
+
+  return (
+    <>
+      <h1 className="text-2xl font-semibold">Overview</h1>
+      <div className="grid gap-4 sm:grid-cols-2 lg:grid-cols-3">
+        <div className="rounded-lg border p-4">
+          <p className="text-sm text-muted-foreground">Users</p>
+          <p className="text-3xl font-semibold">{total}</p>
+        </div>
+      </div>
+      <nav className="flex flex-wrap gap-2">
+        {ADMIN_NAV.filter((item) => item.href !== "/admin").map((item) => (
+          <Link key={item.href} href={item.href} className="rounded-md border px-3 py-2 text-sm hover:bg-muted">
+            {item.title}
+          </Link>
+        ))}
+      </nav>
+    </>
+  );
+}
+"#;
+
+const USERS_PAGE: &str = r#"import { requireAdmin } from "@/lib/admin/session";
+import { parseTableQuery, type TableSearchParams } from "@/lib/data-table";
+import { getUsers } from "@/lib/data/users";
+import { UsersTable } from "./users-table";
+
+export default async function SessionUsersPage({ searchParams }: { searchParams: Promise<TableSearchParams> }) {
+  await requireAdmin();
+  const query = parseTableQuery(await searchParams);
+  const { rows, total } = await getUsers(query);
+
+  return (
+    <>
+      <div>
+        <h1 className="text-2xl font-semibold">Users</h1>
+        <p className="text-sm text-muted-foreground">{total} accounts</p>
+      </div>
+      <UsersTable data={rows} total={total} query={query} />
+    </>
+  );
+}
+"#;
+
+/// The import and `getSessionUser` for an auth library from `manifest::installed_auth_library`.
+/// Files using it declare the `SessionUser` type it returns.
+pub fn session_user(auth_library: Option<&str>) -> (&'static str, &'static str) {
+    match auth_library {
+        Some("next-auth") => (
+            "import { auth } from \"@/auth\";\n",
+            r#"async function getSessionUser(): Promise<SessionUser | null> {
+  const session = await auth();
+  return session?.user ?? null;
+}
+"#,
+        ),
+        Some("better-auth") => (
+            "import { headers } from \"next/headers\";\nimport { auth } from \"@/lib/auth\";\n",
+            r#"async function getSessionUser(): Promise<SessionUser | null> {
+  const session = await auth.api.getSession({ headers: await headers() });
+  return session?.user ?? null;
+}
+"#,
+        ),
+        Some("@clerk/nextjs") => (
+            "import { currentUser } from \"@clerk/nextjs/server\";\n",
+            r#"async function getSessionUser(): Promise<SessionUser | null> {
+  const user = await currentUser();
+  return user && { email: user.primaryEmailAddress?.emailAddress };
+}
+"#,
+        ),
+        Some("@supabase/ssr") => (
+            "import { createClient } from \"@/lib/supabase/server\";\n",
+            r#"async function getSessionUser(): Promise<SessionUser | null> {
+  const supabase = await createClient();
+  const { data } = await supabase.auth.getUser();
+  return data.user;
+}
+"#,
+        ),
+        Some("firebase-admin") => (
+            "import { getCurrentUser } from \"@/lib/firebase/session\";\n",
+            r#"async function getSessionUser(): Promise<SessionUser | null> {
+  return getCurrentUser();
+}
+"#,
+        ),
+        Some("node-appwrite") => (
+            "import { getLoggedInUser } from \"@/lib/appwrite/auth\";\n",
+            r#"async function getSessionUser(): Promise<SessionUser | null> {
+  return getLoggedInUser();
+}
+"#,
+        ),
+        _ => (
+            "",
+            r#"// No auth library was installed when this was generated, so only development has a
+// session. Look up your session here once you add one.
+async function getSessionUser(): Promise<SessionUser | null> {
+  return process.env.NODE_ENV === "development" ? { email: null } : null;
+}
+"#,
+        ),
+    }
+}
+
+fn session(auth_library: Option<(&str, &str)>) -> String {
+    let (imports, session_user) = session_user(auth_library.map(|(package, _)| package));
+    let sign_in_route = auth_library.map(|(_, route)| route).unwrap_or("/");
+    SESSION
+        .replace("__SESSION_IMPORTS__", imports)
+        .replace("__SESSION_USER__", session_user)
+        .replace("__SIGN_IN_ROUTE__", sign_in_route)
+}
+
+pub async fn add_admin() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let data_table_path = project_structure.get_components_path().join("data-table/data-table.tsx");
+    if !project_file("components.json").exists() || !data_table_path.exists() {
+        println!(
+            "{}",
+            style("The admin dashboard is built on shadcn/ui and the data table. Run `nstack add --feature shadcn` and `nstack add --feature data-table` first.").yellow()
+        );
+        return Ok(());
+    }
+
+    let auth_library = manifest::installed_auth_library();
+    match auth_library {
+        Some((package, _)) => println!("{}", style(format!("Protecting /admin with {}", package)).yellow()),
+        None => println!(
+            "{}",
+            style("No auth library found; /admin will only open in development until you add one").yellow()
+        ),
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Adding shadcn/ui sidebar and breadcrumb...");
+
+    package_manager.dlx(&["shadcn@latest", "add", "sidebar", "breadcrumb", "separator", "--yes"])?;
+
+    pb.set_message("Creating navigation and session helpers...");
+
+    let lib_path = project_structure.get_lib_path();
+    let admin_lib_path = lib_path.join("admin");
+    change_set::create_dir_all(&admin_lib_path).context("Failed to create lib/admin directory")?;
+    file_writer::write(admin_lib_path.join("navigation.ts"), NAVIGATION)
+        .context("Failed to create navigation.ts")?;
+    file_writer::write(admin_lib_path.join("session.ts"), session(auth_library))
+        .context("Failed to create session.ts")?;
+
+    // The data table serves mock users when it was added before drizzle
+    let has_users_table = std::fs::read_to_string(project_structure.get_db_path().join("schema.ts"))
+        .map(|schema| schema.contains("usersTable"))
+        .unwrap_or(false);
+    let users_loader_path = lib_path.join("data/users.ts");
+    let users_loader_mocked = std::fs::read_to_string(&users_loader_path)
+        .map(|loader| !loader.contains("usersTable"))
+        .unwrap_or(true);
+    if has_users_table && users_loader_mocked {
+        change_set::create_dir_all(lib_path.join("data")).context("Failed to create lib/data directory")?;
+        file_writer::write(&users_loader_path, data_table::DRIZZLE_USERS_LOADER)
+            .context("Failed to update users.ts")?;
+    }
+
+    pb.set_message("Creating the sidebar and header...");
+
+    let admin_components_path = project_structure.get_components_path().join("admin");
+    change_set::create_dir_all(&admin_components_path).context("Failed to create components/admin directory")?;
+    file_writer::write(admin_components_path.join("admin-sidebar.tsx"), SIDEBAR)
+        .context("Failed to create admin-sidebar.tsx")?;
+    file_writer::write(admin_components_path.join("admin-header.tsx"), HEADER)
+        .context("Failed to create admin-header.tsx")?;
+
+    pb.set_message("Creating the admin routes...");
+
+    let group_path = root_layout::routes_dir(&project_structure).join("(admin)");
+    let users_path = group_path.join("admin/users");
+    change_set::create_dir_all(&users_path).context("Failed to create admin route directories")?;
+    file_writer::write(group_path.join("layout.tsx"), LAYOUT)
+        .context("Failed to create layout.tsx")?;
+    file_writer::write(group_path.join("admin/page.tsx"), OVERVIEW_PAGE)
+        .context("Failed to create page.tsx")?;
+    file_writer::write(users_path.join("page.tsx"), USERS_PAGE)
+        .context("Failed to create users page.tsx")?;
+    file_writer::write(users_path.join("columns.tsx"), data_table::USER_COLUMNS)
+        .context("Failed to create columns.tsx")?;
+    file_writer::write(users_path.join("users-table.tsx"), data_table::USERS_TABLE)
+        .context("Failed to create users-table.tsx")?;
+
+    env_file::add_env_vars("admin", "Admin dashboard", &[("ADMIN_EMAILS", "")])?;
+
+    pb.finish_with_message("Admin dashboard created successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/navigation.ts - Sidebar entries and breadcrumbs", admin_lib_path.display());
+    println!(
+        "• {}/session.ts - requireAdmin() guard ({})",
+        admin_lib_path.display(),
+        auth_library.map(|(package, _)| package).unwrap_or("development only")
+    );
+    println!("• {}/ - Sidebar and breadcrumb header", admin_components_path.display());
+    println!("• {}/layout.tsx - Protected sidebar layout for /admin", group_path.display());
+    println!("• {}/ - Users management table", users_path.display());
+    if has_users_table && users_loader_mocked {
+        println!("• {} - Now loads users from the drizzle usersTable", users_loader_path.display());
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Set ADMIN_EMAILS in .env to the comma-separated emails allowed into /admin");
+    println!("2. Start the dev server and open /admin");
+    println!("3. Add pages under {}/admin and list them in ADMIN_NAV", group_path.display());
+
+    Ok(())
+}
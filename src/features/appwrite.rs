@@ -0,0 +1,397 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::env_file;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::root_layout;
+
+const SERVER_SDK: &str = r#"import "server-only";
+import { cookies } from "next/headers";
+import { Account, Client, Databases, Storage, Users } from "node-appwrite";
+
+export const SESSION_COOKIE = "appwrite-session";
+
+function baseClient() {
+  return new Client()
+    .setEndpoint(process.env.NEXT_PUBLIC_APPWRITE_ENDPOINT!)
+    .setProject(process.env.NEXT_PUBLIC_APPWRITE_PROJECT!);
+}
+
+/** Acts as the signed-in user, so collection and bucket permissions apply */
+export async function createSessionClient() {
+  const session = (await cookies()).get(SESSION_COOKIE);
+  if (!session?.value) {
+    throw new Error("No Appwrite session");
+  }
+
+  const client = baseClient().setSession(session.value);
+  return {
+    account: new Account(client),
+    databases: new Databases(client),
+    storage: new Storage(client),
+  };
+}
+
+/** Uses the API key; only for trusted server code such as creating sessions */
+export function createAdminClient() {
+  const client = baseClient().setKey(process.env.NEXT_APPWRITE_KEY!);
+  return {
+    account: new Account(client),
+    databases: new Databases(client),
+    storage: new Storage(client),
+    users: new Users(client),
+  };
+}
+"#;
+
+const BROWSER_SDK: &str = r#"import { Client } from "appwrite";
+
+// For realtime subscriptions in Client Components; data access goes through the server SDK
+export const client = new Client()
+  .setEndpoint(process.env.NEXT_PUBLIC_APPWRITE_ENDPOINT!)
+  .setProject(process.env.NEXT_PUBLIC_APPWRITE_PROJECT!);
+"#;
+
+const AUTH_HELPERS: &str = r#""use server";
+
+import { cookies } from "next/headers";
+import { redirect } from "next/navigation";
+import { ID } from "node-appwrite";
+import { createAdminClient, createSessionClient, SESSION_COOKIE } from "./server";
+
+export async function getLoggedInUser() {
+  try {
+    const { account } = await createSessionClient();
+    return await account.get();
+  } catch {
+    return null;
+  }
+}
+
+async function startSession(email: string, password: string) {
+  const { account } = createAdminClient();
+  const session = await account.createEmailPasswordSession(email, password);
+
+  (await cookies()).set(SESSION_COOKIE, session.secret, {
+    path: "/",
+    httpOnly: true,
+    sameSite: "strict",
+    secure: process.env.NODE_ENV === "production",
+    expires: new Date(session.expire),
+  });
+}
+
+function credentials(formData: FormData) {
+  return {
+    email: String(formData.get("email") ?? ""),
+    password: String(formData.get("password") ?? ""),
+  };
+}
+
+function errorRedirect(error: unknown): never {
+  const message = error instanceof Error ? error.message : "Something went wrong";
+  redirect(`/login?error=${encodeURIComponent(message)}`);
+}
+
+export async function signIn(formData: FormData) {
+  const { email, password } = credentials(formData);
+  try {
+    await startSession(email, password);
+  } catch (error) {
+    errorRedirect(error);
+  }
+  redirect("/notes");
+}
+
+export async function signUp(formData: FormData) {
+  const { email, password } = credentials(formData);
+  try {
+    await createAdminClient().account.create(ID.unique(), email, password);
+    await startSession(email, password);
+  } catch (error) {
+    errorRedirect(error);
+  }
+  redirect("/notes");
+}
+
+export async function signOut() {
+  try {
+    const { account } = await createSessionClient();
+    await account.deleteSession("current");
+  } catch {
+    // The session already expired
+  }
+  (await cookies()).delete(SESSION_COOKIE);
+  redirect("/login");
+}
+"#;
+
+const DATABASE_SERVICE: &str = r#"import "server-only";
+import { ID, type Models, Permission, Query, Role } from "node-appwrite";
+import { createSessionClient } from "./server";
+
+const DATABASE_ID = process.env.NEXT_PUBLIC_APPWRITE_DATABASE_ID!;
+const NOTES_COLLECTION_ID = process.env.NEXT_PUBLIC_APPWRITE_NOTES_COLLECTION_ID!;
+
+/** Attributes of the notes collection: title (string), content (string) */
+export type Note = Models.Document & {
+  title: string;
+  content: string;
+};
+
+export type NoteInput = Pick<Note, "title" | "content">;
+
+export async function listNotes() {
+  const { databases } = await createSessionClient();
+  const { documents } = await databases.listDocuments<Note>(DATABASE_ID, NOTES_COLLECTION_ID, [
+    Query.orderDesc("$createdAt"),
+    Query.limit(100),
+  ]);
+  return documents;
+}
+
+export async function createNote(userId: string, input: NoteInput) {
+  const { databases } = await createSessionClient();
+  // Document-level permissions keep each note private to its author
+  return databases.createDocument<Note>(DATABASE_ID, NOTES_COLLECTION_ID, ID.unique(), input, [
+    Permission.read(Role.user(userId)),
+    Permission.update(Role.user(userId)),
+    Permission.delete(Role.user(userId)),
+  ]);
+}
+
+export async function updateNote(id: string, input: Partial<NoteInput>) {
+  const { databases } = await createSessionClient();
+  return databases.updateDocument<Note>(DATABASE_ID, NOTES_COLLECTION_ID, id, input);
+}
+
+export async function deleteNote(id: string) {
+  const { databases } = await createSessionClient();
+  await databases.deleteDocument(DATABASE_ID, NOTES_COLLECTION_ID, id);
+}
+"#;
+
+const STORAGE_SERVICE: &str = r#"import "server-only";
+import { ID, Permission, Role } from "node-appwrite";
+import { InputFile } from "node-appwrite/file";
+import { createSessionClient } from "./server";
+
+const BUCKET_ID = process.env.NEXT_PUBLIC_APPWRITE_BUCKET_ID!;
+
+export async function uploadFile(userId: string, file: File) {
+  const { storage } = await createSessionClient();
+  const buffer = Buffer.from(await file.arrayBuffer());
+
+  return storage.createFile(BUCKET_ID, ID.unique(), InputFile.fromBuffer(buffer, file.name), [
+    Permission.read(Role.user(userId)),
+    Permission.delete(Role.user(userId)),
+  ]);
+}
+
+/** URL that renders the file in the browser; requires read access for the viewer */
+export function getFileViewUrl(fileId: string) {
+  const endpoint = process.env.NEXT_PUBLIC_APPWRITE_ENDPOINT!;
+  const project = process.env.NEXT_PUBLIC_APPWRITE_PROJECT!;
+  return `${endpoint}/storage/buckets/${BUCKET_ID}/files/${fileId}/view?project=${project}`;
+}
+
+export async function deleteFile(fileId: string) {
+  const { storage } = await createSessionClient();
+  await storage.deleteFile(BUCKET_ID, fileId);
+}
+"#;
+
+const NOTES_ACTIONS: &str = r#""use server";
+
+import { revalidatePath } from "next/cache";
+import { redirect } from "next/navigation";
+import { getLoggedInUser } from "@/lib/appwrite/auth";
+import { createNote, deleteNote } from "@/lib/appwrite/database";
+
+export async function addNote(formData: FormData) {
+  const user = await getLoggedInUser();
+  if (!user) redirect("/login");
+
+  await createNote(user.$id, {
+    title: String(formData.get("title") ?? ""),
+    content: String(formData.get("content") ?? ""),
+  });
+  revalidatePath("/notes");
+}
+
+export async function removeNote(formData: FormData) {
+  await deleteNote(String(formData.get("id")));
+  revalidatePath("/notes");
+}
+"#;
+
+const NOTES_PAGE: &str = r#"import { redirect } from "next/navigation";
+import { getLoggedInUser, signOut } from "@/lib/appwrite/auth";
+import { listNotes } from "@/lib/appwrite/database";
+import { addNote, removeNote } from "./actions";
+
+export default async function NotesPage() {
+  const user = await getLoggedInUser();
+  if (!user) redirect("/login");
+
+  const notes = await listNotes();
+
+  return (
+    <main className="mx-auto flex max-w-xl flex-col gap-6 px-4 py-10">
+      <div className="flex items-center justify-between">
+        <h1 className="text-2xl font-semibold">Notes</h1>
+        <form action={signOut}>
+          <button className="text-sm text-muted-foreground">Sign out {user.email}</button>
+        </form>
+      </div>
+
+      <form action={addNote} className="flex flex-col gap-2">
+        <input name="title" required placeholder="Title" className="rounded-md border px-3 py-2 text-sm" />
+        <textarea name="content" placeholder="Write something..." className="rounded-md border px-3 py-2 text-sm" />
+        <button className="self-end rounded-md bg-primary px-3 py-2 text-sm text-primary-foreground">Add note</button>
+      </form>
+
+      <ul className="flex flex-col gap-3">
+        {notes.map((note) => (
+          <li key={note.$id} className="flex items-start justify-between gap-4 rounded-md border p-4">
+            <div>
+              <h2 className="font-medium">{note.title}</h2>
+              <p className="text-sm text-muted-foreground">{note.content}</p>
+            </div>
+            <form action={removeNote}>
+              <input type="hidden" name="id" value={note.$id} />
+              <button className="text-sm text-red-600">Delete</button>
+            </form>
+          </li>
+        ))}
+      </ul>
+    </main>
+  );
+}
+"#;
+
+const LOGIN_PAGE: &str = r#"import { redirect } from "next/navigation";
+import { getLoggedInUser, signIn, signUp } from "@/lib/appwrite/auth";
+
+export default async function LoginPage({ searchParams }: { searchParams: Promise<{ error?: string }> }) {
+  if (await getLoggedInUser()) redirect("/notes");
+  const { error } = await searchParams;
+
+  return (
+    <main className="mx-auto flex min-h-screen max-w-sm flex-col justify-center gap-6 px-4">
+      <h1 className="text-2xl font-semibold">Sign in</h1>
+      {error ? <p className="text-sm text-red-600">{error}</p> : null}
+
+      <form className="flex flex-col gap-3">
+        <label className="flex flex-col gap-1 text-sm">
+          Email
+          <input name="email" type="email" autoComplete="email" required className="rounded-md border px-3 py-2" />
+        </label>
+        <label className="flex flex-col gap-1 text-sm">
+          Password
+          <input
+            name="password"
+            type="password"
+            autoComplete="current-password"
+            minLength={8}
+            required
+            className="rounded-md border px-3 py-2"
+          />
+        </label>
+        <div className="flex gap-2">
+          <button formAction={signIn} className="flex-1 rounded-md bg-primary px-3 py-2 text-sm text-primary-foreground">
+            Sign in
+          </button>
+          <button formAction={signUp} className="flex-1 rounded-md border px-3 py-2 text-sm">
+            Sign up
+          </button>
+        </div>
+      </form>
+    </main>
+  );
+}
+"#;
+
+pub async fn add_appwrite() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Appwrite SDKs...");
+
+    package_manager.install(&["node-appwrite", "appwrite", "server-only"], false)?;
+
+    pb.set_message("Creating Appwrite clients and services...");
+
+    let appwrite_lib_path = project_structure.get_lib_path().join("appwrite");
+    change_set::create_dir_all(&appwrite_lib_path).context("Failed to create lib/appwrite directory")?;
+    for (file, content) in [
+        ("server.ts", SERVER_SDK),
+        ("client.ts", BROWSER_SDK),
+        ("auth.ts", AUTH_HELPERS),
+        ("database.ts", DATABASE_SERVICE),
+        ("storage.ts", STORAGE_SERVICE),
+    ] {
+        file_writer::write(appwrite_lib_path.join(file), content)
+            .context(format!("Failed to create {}", file))?;
+    }
+
+    pb.set_message("Creating example pages...");
+
+    let routes_dir = root_layout::routes_dir(&project_structure);
+    let login_path = routes_dir.join("login");
+    change_set::create_dir_all(&login_path).context("Failed to create login route directory")?;
+    file_writer::write(login_path.join("page.tsx"), LOGIN_PAGE)
+        .context("Failed to create login page")?;
+
+    let notes_path = routes_dir.join("notes");
+    change_set::create_dir_all(&notes_path).context("Failed to create notes route directory")?;
+    file_writer::write(notes_path.join("page.tsx"), NOTES_PAGE)
+        .context("Failed to create notes page")?;
+    file_writer::write(notes_path.join("actions.ts"), NOTES_ACTIONS)
+        .context("Failed to create notes actions")?;
+
+    env_file::add_env_vars(
+        "appwrite",
+        "Appwrite",
+        &[
+            ("NEXT_PUBLIC_APPWRITE_ENDPOINT", "https://cloud.appwrite.io/v1"),
+            ("NEXT_PUBLIC_APPWRITE_PROJECT", "your-project-id"),
+            ("NEXT_APPWRITE_KEY", "your-api-key"),
+            ("NEXT_PUBLIC_APPWRITE_DATABASE_ID", "your-database-id"),
+            ("NEXT_PUBLIC_APPWRITE_NOTES_COLLECTION_ID", "notes"),
+            ("NEXT_PUBLIC_APPWRITE_BUCKET_ID", "your-bucket-id"),
+        ],
+    )?;
+
+    pb.finish_with_message("Appwrite set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/server.ts - Session and admin clients (node-appwrite)", appwrite_lib_path.display());
+    println!("• {}/client.ts - Browser client for realtime", appwrite_lib_path.display());
+    println!("• {}/auth.ts - Email sign-in, sign-up and sign-out with SSR session cookies", appwrite_lib_path.display());
+    println!("• {}/database.ts - Typed notes document CRUD", appwrite_lib_path.display());
+    println!("• {}/storage.ts - File upload, view URL and delete", appwrite_lib_path.display());
+    println!("• {}/ - Sign-in page", login_path.display());
+    println!("• {}/ - Example notes CRUD page", notes_path.display());
+    println!("• .env - Appwrite endpoint, project, API key and resource ids");
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Create a project and an API key with the sessions.write and users.write scopes in the Appwrite console");
+    println!("2. Create a database with a \"notes\" collection (title and content string attributes) with document security enabled and create access for users");
+    println!("3. Fill in the ids in .env and open /login");
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::{Value, json};
+use crate::change_set;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_profile::{ESLINT_FLAT_CONFIGS, ESLINT_LEGACY_CONFIGS, PRETTIER_CONFIGS};
+use crate::prompt;
+use crate::project_structure::project_file;
+
+const BIOME_CONFIG: &str = r#"{
+  "$schema": "https://biomejs.dev/schemas/2.0.0/schema.json",
+  "vcs": {
+    "enabled": true,
+    "clientKind": "git",
+    "useIgnoreFile": true
+  },
+  "files": {
+    "ignoreUnknown": true,
+    "includes": ["**", "!.next", "!out", "!build", "!coverage", "!next-env.d.ts"]
+  },
+  "formatter": {
+    "enabled": true,
+    "indentStyle": "space",
+    "indentWidth": 2,
+    "lineWidth": 100
+  },
+  "javascript": {
+    "formatter": {
+      "quoteStyle": "double",
+      "trailingCommas": "all",
+      "semicolons": "always"
+    }
+  },
+  "assist": {
+    "actions": {
+      "source": {
+        "organizeImports": "on"
+      }
+    }
+  },
+  "linter": {
+    "enabled": true,
+    "domains": {
+      "next": "recommended",
+      "react": "recommended"
+    },
+    "rules": {
+      "recommended": true,
+      "suspicious": {
+        "noUnknownAtRules": "off"
+      },
+      "correctness": {
+        "noUnusedImports": "error",
+        "noUnusedVariables": "warn"
+      }
+    }
+  }
+}
+"#;
+
+fn is_eslint_or_prettier_package(name: &str) -> bool {
+    name == "eslint"
+        || name == "prettier"
+        || name == "typescript-eslint"
+        || name.starts_with("eslint-")
+        || name.starts_with("@eslint/")
+        || name.starts_with("@typescript-eslint/")
+        || name.starts_with("prettier-plugin-")
+}
+
+/// Merges format-on-save settings into .vscode/settings.json, keeping the user's other settings.
+/// Returns `Ok(false)` when the existing file can't be parsed (e.g. it contains comments).
+fn update_editor_settings() -> Result<bool> {
+    let settings_path = project_file(".vscode/settings.json");
+
+    let mut settings = if settings_path.exists() {
+        let content = std::fs::read_to_string(&settings_path)
+            .context("Failed to read .vscode/settings.json")?;
+        match serde_json::from_str::<Value>(&content) {
+            Ok(value) if value.is_object() => value,
+            _ => return Ok(false),
+        }
+    } else {
+        json!({})
+    };
+
+    settings["editor.defaultFormatter"] = json!("biomejs.biome");
+    settings["editor.formatOnSave"] = json!(true);
+    settings["editor.codeActionsOnSave"] = json!({
+        "quickfix.biome": "explicit",
+        "source.organizeImports.biome": "explicit"
+    });
+    for language in ["[javascript]", "[typescript]", "[typescriptreact]", "[json]", "[css]"] {
+        settings[language] = json!({ "editor.defaultFormatter": "biomejs.biome" });
+    }
+
+    change_set::create_dir_all(project_file(".vscode")).context("Failed to create .vscode directory")?;
+    file_writer::write(settings_path, serde_json::to_string_pretty(&settings)? + "\n")
+        .context("Failed to write .vscode/settings.json")?;
+
+    let extensions_path = project_file(".vscode/extensions.json");
+    if !extensions_path.exists() {
+        file_writer::write(
+            extensions_path,
+            "{\n  \"recommendations\": [\"biomejs.biome\"]\n}\n",
+        )
+        .context("Failed to write .vscode/extensions.json")?;
+    }
+
+    Ok(true)
+}
+
+pub async fn add_biome() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let eslint_prettier_packages: Vec<String> = package_json::dependency_names()
+        .into_iter()
+        .filter(|name| is_eslint_or_prettier_package(name))
+        .collect();
+    let existing_configs: Vec<&str> = ESLINT_FLAT_CONFIGS
+        .into_iter()
+        .chain(ESLINT_LEGACY_CONFIGS)
+        .chain(PRETTIER_CONFIGS)
+        .chain([".prettierignore"])
+        .filter(|config| project_file(config).exists())
+        .collect();
+
+    let remove_eslint_prettier = if eslint_prettier_packages.is_empty() && existing_configs.is_empty() {
+        false
+    } else {
+        prompt::confirm("remove_eslint_prettier", "Remove ESLint and Prettier configs and dependencies?", true)?
+    };
+
+    let add_editor_settings = prompt::confirm("vscode_settings", "Add VS Code settings for format on save?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Biome...");
+
+    package_manager.install(&["@biomejs/biome@^2"], true)?;
+
+    pb.set_message("Creating biome.json...");
+
+    file_writer::write(project_file("biome.json"), BIOME_CONFIG).context("Failed to create biome.json")?;
+
+    if remove_eslint_prettier {
+        pb.set_message("Removing ESLint and Prettier...");
+
+        for config in &existing_configs {
+            change_set::remove_file(project_file(config)).context(format!("Failed to remove {}", config))?;
+        }
+        if !eslint_prettier_packages.is_empty() {
+            let packages: Vec<&str> = eslint_prettier_packages.iter().map(String::as_str).collect();
+            package_manager.uninstall(&packages)?;
+        }
+    }
+
+    pb.set_message("Updating package.json scripts...");
+
+    package_json::set_scripts(&[
+        ("lint", "biome check ."),
+        ("lint:fix", "biome check --write ."),
+        ("format", "biome format --write ."),
+        ("format:check", "biome format ."),
+    ])?;
+
+    let editor_settings_updated = if add_editor_settings {
+        pb.set_message("Adding editor settings...");
+        update_editor_settings()?
+    } else {
+        false
+    };
+
+    pb.finish_with_message("Biome set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• biome.json - Formatter and linter tuned for Next.js and React");
+    if editor_settings_updated {
+        println!("• .vscode/settings.json - Format and organize imports on save");
+    } else if add_editor_settings {
+        println!(
+            "{}",
+            style("• Could not parse .vscode/settings.json; set \"editor.defaultFormatter\": \"biomejs.biome\" manually").yellow()
+        );
+    }
+    if remove_eslint_prettier {
+        println!("• Removed {} config file(s) and {} package(s)", existing_configs.len(), eslint_prettier_packages.len());
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Format and fix the codebase: {}", package_manager.run_script("lint:fix"));
+    println!("2. Install the Biome extension for your editor");
+
+    Ok(())
+}
@@ -0,0 +1,464 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::path::PathBuf;
+use crate::change_set;
+use crate::env_file;
+use crate::file_writer;
+use crate::import_alias;
+use crate::manifest::ProjectManifest;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+use crate::root_layout;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CaptchaProvider {
+    Turnstile,
+    HCaptcha,
+    ReCaptcha,
+}
+
+impl CaptchaProvider {
+    const ALL: [CaptchaProvider; 3] = [CaptchaProvider::Turnstile, CaptchaProvider::HCaptcha, CaptchaProvider::ReCaptcha];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "Cloudflare Turnstile",
+            CaptchaProvider::HCaptcha => "hCaptcha",
+            CaptchaProvider::ReCaptcha => "reCAPTCHA",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "free, usually no puzzle to solve",
+            CaptchaProvider::HCaptcha => "privacy-focused image challenges",
+            CaptchaProvider::ReCaptcha => "Google's v2 \"I'm not a robot\" checkbox",
+        }
+    }
+
+    /// The browser global of the provider's explicit-render API.
+    fn global(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "turnstile",
+            CaptchaProvider::HCaptcha => "hcaptcha",
+            CaptchaProvider::ReCaptcha => "grecaptcha",
+        }
+    }
+
+    fn script_src(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/api.js?render=explicit",
+            CaptchaProvider::HCaptcha => "https://js.hcaptcha.com/1/api.js?render=explicit",
+            CaptchaProvider::ReCaptcha => "https://www.google.com/recaptcha/api.js?render=explicit",
+        }
+    }
+
+    fn verify_url(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/siteverify",
+            CaptchaProvider::HCaptcha => "https://api.hcaptcha.com/siteverify",
+            CaptchaProvider::ReCaptcha => "https://www.google.com/recaptcha/api/siteverify",
+        }
+    }
+
+    /// The provider's name in Better Auth's captcha plugin.
+    fn better_auth_provider(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "cloudflare-turnstile",
+            CaptchaProvider::HCaptcha => "hcaptcha",
+            CaptchaProvider::ReCaptcha => "google-recaptcha",
+        }
+    }
+
+    fn site_key_var(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "NEXT_PUBLIC_TURNSTILE_SITE_KEY",
+            CaptchaProvider::HCaptcha => "NEXT_PUBLIC_HCAPTCHA_SITE_KEY",
+            CaptchaProvider::ReCaptcha => "NEXT_PUBLIC_RECAPTCHA_SITE_KEY",
+        }
+    }
+
+    fn secret_var(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "TURNSTILE_SECRET_KEY",
+            CaptchaProvider::HCaptcha => "HCAPTCHA_SECRET_KEY",
+            CaptchaProvider::ReCaptcha => "RECAPTCHA_SECRET_KEY",
+        }
+    }
+
+    /// The providers' published test keys, which always pass, so forms work before signing up.
+    fn test_keys(&self) -> (&'static str, &'static str) {
+        match self {
+            CaptchaProvider::Turnstile => ("1x00000000000000000000AA", "1x0000000000000000000000000000000AA"),
+            CaptchaProvider::HCaptcha => (
+                "10000000-ffff-ffff-ffff-000000000001",
+                "0x0000000000000000000000000000000000000000",
+            ),
+            CaptchaProvider::ReCaptcha => (
+                "6LeIxAcTAAAAAJcZVRqyHh71UMIEGNQ_MXjiZKhI",
+                "6LeIxAcTAAAAAGG-vFI1TnRWxMZNFuojJ4WifJWe",
+            ),
+        }
+    }
+
+    fn csp_sources(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "https://challenges.cloudflare.com to script-src and frame-src",
+            CaptchaProvider::HCaptcha => "https://hcaptcha.com https://*.hcaptcha.com to script-src, frame-src, style-src and connect-src",
+            CaptchaProvider::ReCaptcha => "https://www.google.com https://www.gstatic.com to script-src and https://www.google.com to frame-src",
+        }
+    }
+
+    fn dashboard(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Turnstile => "https://dash.cloudflare.com/?to=/:account/turnstile",
+            CaptchaProvider::HCaptcha => "https://dashboard.hcaptcha.com/sites",
+            CaptchaProvider::ReCaptcha => "https://www.google.com/recaptcha/admin/create",
+        }
+    }
+}
+
+const WIDGET: &str = r#""use client";
+
+import Script from "next/script";
+import { useEffect, useRef, useState } from "react";
+
+// The explicit-render API Turnstile, hCaptcha and reCAPTCHA v2 share
+type CaptchaApi = {
+  render: (container: HTMLElement, options: Record<string, unknown>) => string | number;
+  reset: (widgetId?: string | number) => void;
+  remove?: (widgetId: string | number) => void;
+  ready?: (callback: () => void) => void;
+};
+
+declare global {
+  interface Window {
+    __GLOBAL__?: CaptchaApi;
+  }
+}
+
+const SITE_KEY = process.env.__SITE_KEY_VAR__!;
+
+/**
+ * __PROVIDER__ challenge that puts its token in a hidden `captchaToken` field of the
+ * enclosing form, for verifyCaptchaForm() on the server. Tokens are single-use, so it
+ * fetches a new one after every submit.
+ */
+export function CaptchaWidget() {
+  const containerRef = useRef<HTMLDivElement>(null);
+  const widgetId = useRef<string | number | null>(null);
+  const [scriptReady, setScriptReady] = useState(false);
+  const [token, setToken] = useState("");
+
+  useEffect(() => {
+    const api = window.__GLOBAL__;
+    const container = containerRef.current;
+    if (!scriptReady || !api || !container) return;
+
+    const render = () => {
+      if (widgetId.current !== null) return;
+      widgetId.current = api.render(container, {
+        sitekey: SITE_KEY,
+        callback: (value: string) => setToken(value),
+        "expired-callback": () => setToken(""),
+      });
+    };
+    __RENDER__
+
+    return () => {
+      // reCAPTCHA cannot remove a widget, so it keeps the one it rendered
+      if (widgetId.current === null || !api.remove) return;
+      api.remove(widgetId.current);
+      widgetId.current = null;
+    };
+  }, [scriptReady]);
+
+  useEffect(() => {
+    const form = containerRef.current?.closest("form");
+    if (!form) return;
+
+    // Reset once the submit handlers have read the current token
+    const onSubmit = () =>
+      setTimeout(() => {
+        if (widgetId.current !== null) window.__GLOBAL__?.reset(widgetId.current);
+        setToken("");
+      });
+    form.addEventListener("submit", onSubmit);
+    return () => form.removeEventListener("submit", onSubmit);
+  }, []);
+
+  return (
+    <>
+      <Script src="__SCRIPT_SRC__" strategy="afterInteractive" onReady={() => setScriptReady(true)} />
+      <div ref={containerRef} />
+      <input type="hidden" name="captchaToken" value={token} />
+    </>
+  );
+}
+"#;
+
+const VERIFY: &str = r#"import { headers } from "next/headers";
+
+const VERIFY_URL = "__VERIFY_URL__";
+
+/** Checks a __PROVIDER__ token with the provider. Tokens expire after a few minutes. */
+export async function verifyCaptcha(token: string, remoteIp?: string): Promise<boolean> {
+  const secret = process.env.__SECRET_VAR__;
+  if (!secret) {
+    throw new Error("__SECRET_VAR__ is not set");
+  }
+  if (!token) return false;
+
+  const body = new URLSearchParams({ secret, response: token });
+  if (remoteIp) body.set("remoteip", remoteIp);
+
+  try {
+    const response = await fetch(VERIFY_URL, { method: "POST", body });
+    const result: { success: boolean } = await response.json();
+    return result.success;
+  } catch (error) {
+    console.error("CAPTCHA verification failed", error);
+    return false;
+  }
+}
+
+/** Verifies the `captchaToken` field <CaptchaWidget> adds to a form, in a server action. */
+export async function verifyCaptchaForm(formData: FormData) {
+  const forwardedFor = (await headers()).get("x-forwarded-for");
+  return verifyCaptcha(String(formData.get("captchaToken") ?? ""), forwardedFor?.split(",")[0].trim());
+}
+"#;
+
+const WIDGET_IMPORT: &str = "import { CaptchaWidget } from \"@/components/captcha/captcha-widget\";";
+const VERIFY_IMPORT: &str = "import { verifyCaptchaForm } from \"@/lib/captcha/verify\";";
+
+/// A generated form and the edits that put the CAPTCHA in front of it.
+struct FormPatch {
+    path: PathBuf,
+    imports: Vec<String>,
+    /// `(anchor, replacement)` pairs; the patch only applies when every anchor is found
+    edits: Vec<(&'static str, String)>,
+}
+
+impl FormPatch {
+    /// `Ok(None)` when the file does not exist or already has the CAPTCHA, and `Ok(Some(false))`
+    /// when it changed too much since it was generated to patch.
+    fn apply(&self) -> Result<Option<bool>> {
+        let Ok(content) = std::fs::read_to_string(&self.path) else {
+            return Ok(None);
+        };
+        if content.contains("captchaToken") || content.contains("CaptchaWidget") || content.contains("captcha(") {
+            return Ok(None);
+        }
+        if !self.edits.iter().all(|(anchor, _)| content.contains(anchor)) {
+            return Ok(Some(false));
+        }
+
+        let mut patched = content;
+        for (anchor, replacement) in &self.edits {
+            patched = patched.replacen(anchor, replacement, 1);
+        }
+        for import in &self.imports {
+            let import = import_alias::rewrite(&self.path, import.as_bytes()).unwrap_or_else(|| import.clone());
+            patched = root_layout::insert_import(&patched, &import);
+        }
+        change_set::write(&self.path, patched).context(format!("Failed to update {}", self.path.display()))?;
+        Ok(Some(true))
+    }
+}
+
+/// Server-action login forms: the widget goes above the buttons, and each action verifies
+/// the token before signing in.
+fn server_action_patches(page: PathBuf, actions: PathBuf, functions: &[&'static str], reject: &str) -> [FormPatch; 2] {
+    let widget = FormPatch {
+        path: page,
+        imports: vec![WIDGET_IMPORT.to_string()],
+        edits: vec![(
+            "        <div className=\"flex gap-2\">\n",
+            "        <CaptchaWidget />\n        <div className=\"flex gap-2\">\n".to_string(),
+        )],
+    };
+    let verify = FormPatch {
+        path: actions,
+        imports: vec![VERIFY_IMPORT.to_string()],
+        edits: functions
+            .iter()
+            .map(|function| (*function, format!("{}  if (!(await verifyCaptchaForm(formData))) {}\n", function, reject)))
+            .collect(),
+    };
+    [widget, verify]
+}
+
+pub async fn add_captcha() -> Result<()> {
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    let items: Vec<String> = CaptchaProvider::ALL
+        .iter()
+        .map(|provider| format!("{} - {}", provider.as_str(), provider.description()))
+        .collect();
+    let provider = CaptchaProvider::ALL[prompt::select("provider", "Select a CAPTCHA provider", &items, 0)?];
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message(format!("Creating the {} widget and verification helper...", provider.as_str()));
+
+    let render = match provider {
+        // reCAPTCHA loads the rest of its API after the script itself
+        CaptchaProvider::ReCaptcha => "api.ready?.(render);",
+        _ => "render();",
+    };
+    let widget = WIDGET
+        .replace("__GLOBAL__", provider.global())
+        .replace("__SITE_KEY_VAR__", provider.site_key_var())
+        .replace("__PROVIDER__", provider.as_str())
+        .replace("__RENDER__", render)
+        .replace("__SCRIPT_SRC__", provider.script_src());
+    let verify = VERIFY
+        .replace("__VERIFY_URL__", provider.verify_url())
+        .replace("__PROVIDER__", provider.as_str())
+        .replace("__SECRET_VAR__", provider.secret_var());
+
+    let components_path = project_structure.get_components_path().join("captcha");
+    change_set::create_dir_all(&components_path).context("Failed to create components/captcha directory")?;
+    file_writer::write(components_path.join("captcha-widget.tsx"), widget)
+        .context("Failed to create captcha-widget.tsx")?;
+
+    let lib_path = project_structure.get_lib_path().join("captcha");
+    change_set::create_dir_all(&lib_path).context("Failed to create lib/captcha directory")?;
+    file_writer::write(lib_path.join("verify.ts"), verify).context("Failed to create verify.ts")?;
+
+    let (site_key, secret) = provider.test_keys();
+    env_file::add_env_vars(
+        "captcha",
+        provider.as_str(),
+        &[(provider.site_key_var(), site_key), (provider.secret_var(), secret)],
+    )?;
+
+    pb.set_message("Adding the CAPTCHA to generated sign-in forms...");
+
+    let routes_dir = root_layout::routes_dir(&project_structure);
+    let lib = project_structure.get_lib_path();
+    let mut patches: Vec<(&str, FormPatch)> = Vec::new();
+    if ProjectManifest::is_feature_installed("supabase") {
+        let login = routes_dir.join("login");
+        let [page, actions] = server_action_patches(
+            login.join("page.tsx"),
+            login.join("actions.ts"),
+            &[
+                "export async function login(formData: FormData) {\n",
+                "export async function signup(formData: FormData) {\n",
+            ],
+            "{\n    redirect(`/login?error=${encodeURIComponent(\"Complete the CAPTCHA to continue\")}`);\n  }",
+        );
+        patches.push(("Supabase sign-in page", page));
+        patches.push(("Supabase login and signup actions", actions));
+    }
+    if ProjectManifest::is_feature_installed("appwrite") {
+        let [page, actions] = server_action_patches(
+            routes_dir.join("login").join("page.tsx"),
+            lib.join("appwrite").join("auth.ts"),
+            &[
+                "export async function signIn(formData: FormData) {\n",
+                "export async function signUp(formData: FormData) {\n",
+            ],
+            "errorRedirect(new Error(\"Complete the CAPTCHA to continue\"));",
+        );
+        patches.push(("Appwrite sign-in page", page));
+        patches.push(("Appwrite signIn and signUp actions", actions));
+    }
+    if ProjectManifest::is_feature_installed("saas") {
+        // Better Auth's captcha plugin checks the token on sign-in and sign-up
+        patches.push((
+            "Better Auth captcha plugin",
+            FormPatch {
+                path: lib.join("auth.ts"),
+                imports: vec!["import { captcha } from \"better-auth/plugins\";".to_string()],
+                edits: vec![(
+                    "  plugins: [nextCookies()],\n",
+                    format!(
+                        "  plugins: [captcha({{ provider: \"{}\", secretKey: process.env.{}! }}), nextCookies()],\n",
+                        provider.better_auth_provider(),
+                        provider.secret_var()
+                    ),
+                )],
+            },
+        ));
+        patches.push((
+            "SaaS sign-in form",
+            FormPatch {
+                path: project_structure.get_components_path().join("saas").join("sign-in-form.tsx"),
+                imports: vec![WIDGET_IMPORT.to_string()],
+                edits: vec![
+                    (
+                        "    const password = String(form.get(\"password\"));\n",
+                        "    const password = String(form.get(\"password\"));\n    const fetchOptions = { headers: { \"x-captcha-response\": String(form.get(\"captchaToken\") ?? \"\") } };\n".to_string(),
+                    ),
+                    (
+                        "authClient.signIn.email({ email, password })",
+                        "authClient.signIn.email({ email, password, fetchOptions })".to_string(),
+                    ),
+                    (
+                        "authClient.signUp.email({ email, password, name: String(form.get(\"name\")) })",
+                        "authClient.signUp.email({ email, password, name: String(form.get(\"name\")), fetchOptions })".to_string(),
+                    ),
+                    (
+                        "      {error && <p className=\"text-sm text-red-600\">{error}</p>}\n",
+                        "      <CaptchaWidget />\n      {error && <p className=\"text-sm text-red-600\">{error}</p>}\n".to_string(),
+                    ),
+                ],
+            },
+        ));
+    }
+
+    let mut integrated = Vec::new();
+    let mut skipped = Vec::new();
+    for (name, patch) in &patches {
+        match patch.apply()? {
+            Some(true) => integrated.push((*name, patch.path.display().to_string())),
+            Some(false) => skipped.push(*name),
+            None => {}
+        }
+    }
+
+    pb.finish_with_message(format!("{} added successfully!", provider.as_str()));
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/captcha-widget.tsx - <CaptchaWidget> for any form", components_path.display());
+    println!("• {}/verify.ts - verifyCaptcha() and verifyCaptchaForm() for server actions", lib_path.display());
+    for (name, path) in &integrated {
+        println!("• {} - {}", path, name);
+    }
+    for name in &skipped {
+        println!(
+            "{}",
+            style(format!("• The {} changed since nstack generated it; add <CaptchaWidget> and verifyCaptchaForm() by hand", name)).yellow()
+        );
+    }
+    if ProjectManifest::is_feature_installed("firebase") {
+        println!(
+            "{}",
+            style("• Firebase signs in from the browser, so protect it with App Check instead of verifyCaptcha()").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!(
+        "1. Replace the test keys in .env with a site key and secret from {}",
+        provider.dashboard()
+    );
+    println!("2. Put <CaptchaWidget /> inside your forms and call verifyCaptchaForm(formData) in their actions");
+    if ProjectManifest::is_feature_installed("security") {
+        println!("3. Add {} in your CSP", provider.csp_sources());
+    }
+
+    Ok(())
+}
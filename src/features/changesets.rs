@@ -0,0 +1,148 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::json;
+use crate::change_set;
+use crate::file_writer;
+use crate::features::github_actions;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::prompt;
+use crate::project_structure::project_file;
+
+const CHANGESET_README: &str = r#"# Changesets
+
+Run `changeset` to describe a change before opening a pull request. Each changeset records
+which packages changed and whether the change is a major, minor or patch bump.
+
+When changesets land on the base branch, the release workflow opens a "Version Packages"
+pull request that bumps versions and updates changelogs. Merging it publishes the release.
+"#;
+
+fn changeset_config(base_branch: &str, publish: bool) -> serde_json::Value {
+    json!({
+        "$schema": "https://unpkg.com/@changesets/config@3.0.0/schema.json",
+        "changelog": "@changesets/cli/changelog",
+        "commit": false,
+        "fixed": [],
+        "linked": [],
+        "access": if publish { "public" } else { "restricted" },
+        "baseBranch": base_branch,
+        "updateInternalDependencies": "patch",
+        "ignore": [],
+        // Apps are usually private; still version and tag them so releases are tracked
+        "privatePackages": { "version": true, "tag": true }
+    })
+}
+
+fn release_workflow(package_manager: &PackageManager, base_branch: &str, publish: bool) -> String {
+    let npm_token = if publish {
+        "          NPM_TOKEN: ${{ secrets.NPM_TOKEN }}\n"
+    } else {
+        ""
+    };
+
+    format!(
+        r#"name: Release
+
+on:
+  push:
+    branches: [{}]
+
+concurrency: ${{{{ github.workflow }}}}-${{{{ github.ref }}}}
+
+permissions:
+  contents: write
+  pull-requests: write
+
+jobs:
+  release:
+    runs-on: ubuntu-latest
+    steps:
+{}      - uses: changesets/action@v1
+        with:
+          version: {}
+          publish: {}
+          title: "chore: version packages"
+          commit: "chore: version packages"
+        env:
+          GITHUB_TOKEN: ${{{{ secrets.GITHUB_TOKEN }}}}
+{}"#,
+        base_branch,
+        github_actions::setup_steps(package_manager),
+        package_manager.run_script("version-packages"),
+        package_manager.run_script("release"),
+        npm_token
+    )
+}
+
+pub async fn add_changesets() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let base_branch = prompt::input("base_branch", "Base branch for releases", "main")?;
+
+    let is_private = package_json::read()
+        .map(|package_json| package_json["private"].as_bool().unwrap_or(false))
+        .unwrap_or(false);
+
+    let publish = prompt::confirm("publish", "Publish packages to npm on release?", !is_private)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing @changesets/cli...");
+
+    package_manager.install(&["@changesets/cli"], true)?;
+
+    pb.set_message("Creating .changeset config...");
+
+    change_set::create_dir_all(project_file(".changeset")).context("Failed to create .changeset directory")?;
+    file_writer::write(
+        project_file(".changeset/config.json"),
+        serde_json::to_string_pretty(&changeset_config(&base_branch, publish))? + "\n",
+    )
+    .context("Failed to create .changeset/config.json")?;
+    file_writer::write(project_file(".changeset/README.md"), CHANGESET_README)
+        .context("Failed to create .changeset/README.md")?;
+
+    pb.set_message("Creating release workflow...");
+
+    change_set::create_dir_all(project_file(".github/workflows")).context("Failed to create .github/workflows directory")?;
+    file_writer::write(
+        project_file(".github/workflows/release.yml"),
+        release_workflow(&package_manager, &base_branch, publish),
+    )
+    .context("Failed to create release.yml")?;
+
+    pb.set_message("Updating package.json scripts...");
+
+    // Private packages can't be published, so releases only create git tags
+    let release_command = if publish { "changeset publish" } else { "changeset tag" };
+    package_json::add_scripts(&[
+        ("changeset", "changeset"),
+        ("version-packages", "changeset version"),
+        ("release", release_command),
+    ])?;
+
+    pb.finish_with_message("Changesets set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• .changeset/config.json - Changesets configuration (base branch: {})", base_branch);
+    println!("• .github/workflows/release.yml - Opens version PRs and releases on merge");
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Describe a change: {}", package_manager.run_script("changeset"));
+    println!("2. Allow GitHub Actions to create pull requests in the repository settings");
+    if publish {
+        println!("3. Add an NPM_TOKEN secret to the repository");
+    }
+
+    Ok(())
+}
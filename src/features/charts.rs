@@ -0,0 +1,349 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::root_layout;
+
+const DASHBOARD_DATA: &str = r#"export type Stat = {
+  label: string;
+  value: string;
+  /** Change against the previous period, in percent */
+  change: number;
+};
+
+export type RevenuePoint = { month: string; revenue: number; expenses: number };
+export type SignupPoint = { date: string; signups: number };
+export type TrafficSource = { source: string; visitors: number };
+
+export type DashboardData = {
+  stats: Stat[];
+  revenue: RevenuePoint[];
+  signups: SignupPoint[];
+  traffic: TrafficSource[];
+};
+
+const MONTHS = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+function mockRevenue(): RevenuePoint[] {
+  return MONTHS.map((month, index) => ({
+    month,
+    revenue: 4000 + index * 650 + (index % 3) * 900,
+    expenses: 2500 + index * 300 + (index % 2) * 700,
+  }));
+}
+
+function mockSignups(days: number): SignupPoint[] {
+  const today = new Date();
+  return Array.from({ length: days }, (_, index) => {
+    const date = new Date(today);
+    date.setDate(today.getDate() - (days - 1 - index));
+    return { date: date.toISOString().slice(0, 10), signups: 20 + ((index * 37) % 45) };
+  });
+}
+
+function mockTraffic(): TrafficSource[] {
+  return [
+    { source: "Direct", visitors: 4200 },
+    { source: "Search", visitors: 3100 },
+    { source: "Social", visitors: 1800 },
+    { source: "Referral", visitors: 900 },
+  ];
+}
+
+function formatCurrency(value: number) {
+  return new Intl.NumberFormat("en-US", { style: "currency", currency: "USD", maximumFractionDigits: 0 }).format(value);
+}
+"#;
+
+const MOCK_LOADER: &str = r#"
+// Mock data until a database feature is installed (e.g. `nstack add --feature drizzle`)
+export async function getDashboardData(): Promise<DashboardData> {
+  const revenue = mockRevenue();
+  const signups = mockSignups(30);
+  const totalRevenue = revenue.reduce((sum, point) => sum + point.revenue, 0);
+
+  return {
+    stats: [
+      { label: "Revenue", value: formatCurrency(totalRevenue), change: 12.5 },
+      { label: "Users", value: "2,350", change: 8.1 },
+      { label: "Signups (30d)", value: signups.reduce((sum, point) => sum + point.signups, 0).toLocaleString(), change: -2.4 },
+      { label: "Active now", value: "573", change: 4.3 },
+    ],
+    revenue,
+    signups,
+    traffic: mockTraffic(),
+  };
+}
+"#;
+
+const DRIZZLE_IMPORTS: &str = r#"import { count, gte, sql } from "drizzle-orm";
+import { db } from "@/db";
+import { postsTable, usersTable } from "@/db/schema";
+
+"#;
+
+const DRIZZLE_LOADER: &str = r#"
+function percentChange(current: number, previous: number) {
+  if (previous === 0) return current > 0 ? 100 : 0;
+  return Math.round(((current - previous) / previous) * 1000) / 10;
+}
+
+// Users and posts come from the database; revenue and traffic stay mocked until you track them
+export async function getDashboardData(): Promise<DashboardData> {
+  const since = new Date();
+  since.setDate(since.getDate() - 59);
+  const day = sql<string>`to_char(${usersTable.createdAt}, 'YYYY-MM-DD')`;
+
+  const [userTotals, postTotals, dailySignups] = await Promise.all([
+    db.select({ total: count() }).from(usersTable),
+    db.select({ total: count() }).from(postsTable),
+    db
+      .select({ date: day, signups: count() })
+      .from(usersTable)
+      .where(gte(usersTable.createdAt, since))
+      .groupBy(day)
+      .orderBy(day),
+  ]);
+
+  const signupsByDate = new Map(dailySignups.map((row) => [row.date, row.signups]));
+  const window = mockSignups(60).map((point) => ({ date: point.date, signups: signupsByDate.get(point.date) ?? 0 }));
+  const previous = window.slice(0, 30).reduce((sum, point) => sum + point.signups, 0);
+  const signups = window.slice(30);
+  const current = signups.reduce((sum, point) => sum + point.signups, 0);
+
+  const revenue = mockRevenue();
+  const totalRevenue = revenue.reduce((sum, point) => sum + point.revenue, 0);
+
+  return {
+    stats: [
+      { label: "Revenue", value: formatCurrency(totalRevenue), change: 12.5 },
+      { label: "Users", value: (userTotals[0]?.total ?? 0).toLocaleString(), change: percentChange(current, previous) },
+      { label: "Signups (30d)", value: current.toLocaleString(), change: percentChange(current, previous) },
+      { label: "Posts", value: (postTotals[0]?.total ?? 0).toLocaleString(), change: 0 },
+    ],
+    revenue,
+    signups,
+    traffic: mockTraffic(),
+  };
+}
+"#;
+
+const STAT_CARD: &str = r#"import type { ReactNode } from "react";
+import type { Stat } from "@/lib/data/dashboard";
+
+export function StatCard({ stat }: { stat: Stat }) {
+  const positive = stat.change >= 0;
+
+  return (
+    <div className="rounded-xl border bg-card p-6 text-card-foreground shadow-sm">
+      <p className="text-sm font-medium text-muted-foreground">{stat.label}</p>
+      <p className="mt-2 text-2xl font-bold">{stat.value}</p>
+      <p className={positive ? "mt-1 text-xs text-emerald-600" : "mt-1 text-xs text-red-600"}>
+        {positive ? "+" : ""}
+        {stat.change}% from last period
+      </p>
+    </div>
+  );
+}
+
+export function ChartCard({ title, description, children }: { title: string; description?: string; children: ReactNode }) {
+  return (
+    <div className="rounded-xl border bg-card p-6 text-card-foreground shadow-sm">
+      <h2 className="font-semibold">{title}</h2>
+      {description ? <p className="text-sm text-muted-foreground">{description}</p> : null}
+      <div className="mt-4 h-72">{children}</div>
+    </div>
+  );
+}
+"#;
+
+// Colors read shadcn/ui's --chart-* variables when they exist, with Tailwind defaults otherwise
+const LINE_CHART: &str = r#""use client";
+
+import { CartesianGrid, Line, LineChart, ResponsiveContainer, Tooltip, XAxis, YAxis } from "recharts";
+import type { SignupPoint } from "@/lib/data/dashboard";
+
+export function SignupsLineChart({ data }: { data: SignupPoint[] }) {
+  return (
+    <ResponsiveContainer width="100%" height="100%">
+      <LineChart data={data} margin={{ left: -20, right: 8 }}>
+        <CartesianGrid strokeDasharray="3 3" vertical={false} className="stroke-muted" />
+        <XAxis dataKey="date" tickFormatter={(date: string) => date.slice(5)} tickLine={false} axisLine={false} fontSize={12} />
+        <YAxis allowDecimals={false} tickLine={false} axisLine={false} fontSize={12} />
+        <Tooltip />
+        <Line type="monotone" dataKey="signups" stroke="var(--chart-1, #2563eb)" strokeWidth={2} dot={false} />
+      </LineChart>
+    </ResponsiveContainer>
+  );
+}
+"#;
+
+const BAR_CHART: &str = r#""use client";
+
+import { Bar, BarChart, CartesianGrid, Legend, ResponsiveContainer, Tooltip, XAxis, YAxis } from "recharts";
+import type { RevenuePoint } from "@/lib/data/dashboard";
+
+export function RevenueBarChart({ data }: { data: RevenuePoint[] }) {
+  return (
+    <ResponsiveContainer width="100%" height="100%">
+      <BarChart data={data} margin={{ left: -10, right: 8 }}>
+        <CartesianGrid strokeDasharray="3 3" vertical={false} className="stroke-muted" />
+        <XAxis dataKey="month" tickLine={false} axisLine={false} fontSize={12} />
+        <YAxis tickFormatter={(value: number) => `$${value / 1000}k`} tickLine={false} axisLine={false} fontSize={12} />
+        <Tooltip />
+        <Legend />
+        <Bar dataKey="revenue" name="Revenue" fill="var(--chart-1, #2563eb)" radius={[4, 4, 0, 0]} />
+        <Bar dataKey="expenses" name="Expenses" fill="var(--chart-2, #60a5fa)" radius={[4, 4, 0, 0]} />
+      </BarChart>
+    </ResponsiveContainer>
+  );
+}
+"#;
+
+const PIE_CHART: &str = r#""use client";
+
+import { Cell, Legend, Pie, PieChart, ResponsiveContainer, Tooltip } from "recharts";
+import type { TrafficSource } from "@/lib/data/dashboard";
+
+const COLORS = [
+  "var(--chart-1, #2563eb)",
+  "var(--chart-2, #60a5fa)",
+  "var(--chart-3, #f59e0b)",
+  "var(--chart-4, #10b981)",
+  "var(--chart-5, #ef4444)",
+];
+
+export function TrafficPieChart({ data }: { data: TrafficSource[] }) {
+  return (
+    <ResponsiveContainer width="100%" height="100%">
+      <PieChart>
+        <Pie data={data} dataKey="visitors" nameKey="source" innerRadius="55%" outerRadius="80%" paddingAngle={2}>
+          {data.map((entry, index) => (
+            <Cell key={entry.source} fill={COLORS[index % COLORS.length]} />
+          ))}
+        </Pie>
+        <Tooltip />
+        <Legend />
+      </PieChart>
+    </ResponsiveContainer>
+  );
+}
+"#;
+
+const DASHBOARD_PAGE: &str = r#"import { SignupsLineChart } from "@/components/charts/line-chart";
+import { RevenueBarChart } from "@/components/charts/bar-chart";
+import { TrafficPieChart } from "@/components/charts/pie-chart";
+import { ChartCard, StatCard } from "@/components/charts/stat-card";
+import { getDashboardData } from "@/lib/data/dashboard";
+
+export const dynamic = "force-dynamic";
+
+export default async function DashboardPage() {
+  const data = await getDashboardData();
+
+  return (
+    <main className="container mx-auto flex flex-col gap-6 px-4 py-10">
+      <h1 className="text-2xl font-semibold">Dashboard</h1>
+
+      <div className="grid gap-4 sm:grid-cols-2 lg:grid-cols-4">
+        {data.stats.map((stat) => (
+          <StatCard key={stat.label} stat={stat} />
+        ))}
+      </div>
+
+      <div className="grid gap-4 lg:grid-cols-2">
+        <ChartCard title="Revenue" description="Revenue and expenses per month">
+          <RevenueBarChart data={data.revenue} />
+        </ChartCard>
+        <ChartCard title="Signups" description="New users over the last 30 days">
+          <SignupsLineChart data={data.signups} />
+        </ChartCard>
+      </div>
+
+      <ChartCard title="Traffic sources" description="Visitors by acquisition channel">
+        <TrafficPieChart data={data.traffic} />
+      </ChartCard>
+    </main>
+  );
+}
+"#;
+
+pub async fn add_charts() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    // The signups query relies on the default postgres schema from the drizzle feature
+    let schema_path = project_structure.get_db_path().join("schema.ts");
+    let use_database = std::fs::read_to_string(&schema_path)
+        .map(|schema| schema.contains("usersTable") && schema.contains("postsTable") && schema.contains("pg-core"))
+        .unwrap_or(false);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Recharts...");
+
+    package_manager.install(&["recharts"], false)?;
+
+    pb.set_message("Creating the dashboard loader...");
+
+    let lib_path = project_structure.get_lib_path();
+    change_set::create_dir_all(lib_path.join("data")).context("Failed to create lib/data directory")?;
+    let loader = if use_database {
+        format!("{}{}{}", DRIZZLE_IMPORTS, DASHBOARD_DATA, DRIZZLE_LOADER)
+    } else {
+        format!("{}{}", DASHBOARD_DATA, MOCK_LOADER)
+    };
+    file_writer::write(lib_path.join("data/dashboard.ts"), loader)
+        .context("Failed to create dashboard.ts")?;
+
+    pb.set_message("Creating chart components...");
+
+    let charts_path = project_structure.get_components_path().join("charts");
+    change_set::create_dir_all(&charts_path).context("Failed to create charts directory")?;
+    file_writer::write(charts_path.join("stat-card.tsx"), STAT_CARD)
+        .context("Failed to create stat-card.tsx")?;
+    file_writer::write(charts_path.join("line-chart.tsx"), LINE_CHART)
+        .context("Failed to create line-chart.tsx")?;
+    file_writer::write(charts_path.join("bar-chart.tsx"), BAR_CHART)
+        .context("Failed to create bar-chart.tsx")?;
+    file_writer::write(charts_path.join("pie-chart.tsx"), PIE_CHART)
+        .context("Failed to create pie-chart.tsx")?;
+
+    pb.set_message("Creating the dashboard page...");
+
+    let page_path = root_layout::routes_dir(&project_structure).join("dashboard");
+    change_set::create_dir_all(&page_path).context("Failed to create dashboard route directory")?;
+    file_writer::write(page_path.join("page.tsx"), DASHBOARD_PAGE)
+        .context("Failed to create page.tsx")?;
+
+    pb.finish_with_message("Dashboard charts created successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!(
+        "• {}/data/dashboard.ts - Typed dashboard loader ({})",
+        lib_path.display(),
+        if use_database { "users and posts from drizzle" } else { "mock data" }
+    );
+    println!("• {}/stat-card.tsx - Stat and chart cards", charts_path.display());
+    println!("• {}/line-chart.tsx - Signups line chart", charts_path.display());
+    println!("• {}/bar-chart.tsx - Revenue bar chart", charts_path.display());
+    println!("• {}/pie-chart.tsx - Traffic sources pie chart", charts_path.display());
+    println!("• {}/page.tsx - Dashboard page", page_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Start the dev server and open /dashboard");
+    println!("2. Replace the mocked series in {}/data/dashboard.ts with your own queries", lib_path.display());
+
+    Ok(())
+}
@@ -0,0 +1,702 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::features::admin;
+use crate::file_writer;
+use crate::manifest;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+use crate::root_layout;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RealtimeProvider {
+    Liveblocks,
+    PartyKit,
+}
+
+impl RealtimeProvider {
+    const ALL: [RealtimeProvider; 2] = [RealtimeProvider::Liveblocks, RealtimeProvider::PartyKit];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RealtimeProvider::Liveblocks => "Liveblocks",
+            RealtimeProvider::PartyKit => "PartyKit",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            RealtimeProvider::Liveblocks => "hosted rooms with presence and storage",
+            RealtimeProvider::PartyKit => "your own room server on Cloudflare",
+        }
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        match self {
+            RealtimeProvider::Liveblocks => &["@liveblocks/client", "@liveblocks/react", "@liveblocks/node"],
+            RealtimeProvider::PartyKit => &["partysocket"],
+        }
+    }
+
+    fn auth_route(&self) -> &'static str {
+        match self {
+            RealtimeProvider::Liveblocks => "liveblocks-auth",
+            RealtimeProvider::PartyKit => "party-auth",
+        }
+    }
+
+    fn env_vars(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            RealtimeProvider::Liveblocks => &[("LIVEBLOCKS_SECRET_KEY", "")],
+            RealtimeProvider::PartyKit => &[("NEXT_PUBLIC_PARTYKIT_HOST", "localhost:1999"), ("PARTYKIT_SECRET", "")],
+        }
+    }
+}
+
+const COLLABORATOR: &str = r##"__SESSION_IMPORTS__
+export type SessionUser = {
+  email?: string | null;
+  name?: string | null;
+};
+
+export type Collaborator = {
+  id: string;
+  name: string;
+  color: string;
+};
+
+const COLORS = ["#e11d48", "#db2777", "#9333ea", "#2563eb", "#0891b2", "#059669", "#ca8a04", "#ea580c"];
+
+__SESSION_USER__
+/** The signed-in user as others in a room see them, or null when signed out. */
+export async function getCollaborator(): Promise<Collaborator | null> {
+  const user = await getSessionUser();
+  if (!user) return null;
+
+  // A session without an email (development without an auth library) joins as a guest
+  const id = user.email ?? `guest-${crypto.randomUUID().slice(0, 8)}`;
+  const hash = [...id].reduce((total, char) => total + char.charCodeAt(0), 0);
+  return {
+    id,
+    name: user.name || id.split("@")[0],
+    color: COLORS[hash % COLORS.length],
+  };
+}
+"##;
+
+const LIVEBLOCKS_CONFIG: &str = r#"// Types for every Liveblocks hook in the app
+declare global {
+  interface Liveblocks {
+    Presence: {
+      cursor: { x: number; y: number } | null;
+    };
+    Storage: {
+      text: string;
+    };
+    UserMeta: {
+      id: string;
+      info: { name: string; color: string };
+    };
+  }
+}
+
+export {};
+"#;
+
+const LIVEBLOCKS_AUTH: &str = r#"import { Liveblocks } from "@liveblocks/node";
+import { getCollaborator } from "@/lib/collaboration/collaborator";
+
+const liveblocks = new Liveblocks({ secret: process.env.LIVEBLOCKS_SECRET_KEY! });
+
+export async function POST(request: Request) {
+  const user = await getCollaborator();
+  if (!user) {
+    return new Response("Unauthorized", { status: 401 });
+  }
+
+  const { room } = await request.json();
+  const session = liveblocks.prepareSession(user.id, {
+    userInfo: { name: user.name, color: user.color },
+  });
+  // Every signed-in user may join every room; check membership here to restrict that
+  session.allow(room, session.FULL_ACCESS);
+
+  const { status, body } = await session.authorize();
+  return new Response(body, { status });
+}
+"#;
+
+const LIVEBLOCKS_ROOM: &str = r#""use client";
+
+import {
+  ClientSideSuspense,
+  LiveblocksProvider,
+  RoomProvider,
+  useMutation,
+  useStorage,
+} from "@liveblocks/react/suspense";
+import type { ReactNode } from "react";
+
+export { useOthers, useSelf, useUpdateMyPresence } from "@liveblocks/react/suspense";
+
+type RoomProps = {
+  id: string;
+  children: ReactNode;
+};
+
+export function Room({ id, children }: RoomProps) {
+  return (
+    <LiveblocksProvider authEndpoint="/api/liveblocks-auth">
+      <RoomProvider id={id} initialPresence={{ cursor: null }} initialStorage={{ text: "" }}>
+        <ClientSideSuspense fallback={<p className="text-sm text-gray-500">Connecting…</p>}>
+          {children}
+        </ClientSideSuspense>
+      </RoomProvider>
+    </LiveblocksProvider>
+  );
+}
+
+/** The room's shared text and a setter that syncs it to everyone. */
+export function useSharedText() {
+  const text = useStorage((root) => root.text);
+  const setText = useMutation(({ storage }, value: string) => storage.set("text", value), []);
+  return [text, setText] as const;
+}
+"#;
+
+const PARTYKIT_JSON: &str = r#"{
+  "$schema": "https://www.partykit.io/schema.json",
+  "name": "__NAME__",
+  "main": "party/index.ts"
+}
+"#;
+
+const PARTY_SERVER: &str = r#"import type * as Party from "partykit/server";
+
+type User = { id: string; name: string; color: string };
+type Cursor = { x: number; y: number } | null;
+type ConnectionState = { user: User; cursor: Cursor };
+
+type Peer = {
+  connectionId: string;
+  info: User;
+  presence: { cursor: Cursor };
+};
+
+type ClientMessage = { type: "cursor"; cursor: Cursor } | { type: "text"; text: string };
+
+/** One room: who is connected, where their cursors are, and the shared text. */
+export default class CollaborationServer implements Party.Server {
+  text = "";
+
+  constructor(readonly room: Party.Room) {}
+
+  async onStart() {
+    this.text = (await this.room.storage.get<string>("text")) ?? "";
+  }
+
+  // Only connections carrying a token from /api/party-auth get in
+  static async onBeforeConnect(request: Party.Request, lobby: Party.Lobby) {
+    const token = new URL(request.url).searchParams.get("token") ?? "";
+    const user = await verifyToken(token, String(lobby.env.PARTYKIT_SECRET ?? ""));
+    if (!user) {
+      return new Response("Unauthorized", { status: 401 });
+    }
+    request.headers.set("X-User", JSON.stringify(user));
+    return request;
+  }
+
+  onConnect(connection: Party.Connection<ConnectionState>, { request }: Party.ConnectionContext) {
+    const user = JSON.parse(request.headers.get("X-User")!) as User;
+    connection.setState({ user, cursor: null });
+    connection.send(
+      JSON.stringify({ type: "sync", connectionId: connection.id, text: this.text, peers: this.peers() }),
+    );
+    this.broadcastPeers();
+  }
+
+  onMessage(message: string, sender: Party.Connection<ConnectionState>) {
+    const data = JSON.parse(message) as ClientMessage;
+    if (data.type === "cursor" && sender.state) {
+      sender.setState({ ...sender.state, cursor: data.cursor });
+      this.broadcastPeers();
+    } else if (data.type === "text") {
+      this.text = data.text;
+      this.room.broadcast(JSON.stringify({ type: "text", text: this.text }), [sender.id]);
+      void this.room.storage.put("text", this.text);
+    }
+  }
+
+  onClose() {
+    this.broadcastPeers();
+  }
+
+  peers(): Peer[] {
+    return [...this.room.getConnections<ConnectionState>()].flatMap((connection) =>
+      connection.state
+        ? [{ connectionId: connection.id, info: connection.state.user, presence: { cursor: connection.state.cursor } }]
+        : [],
+    );
+  }
+
+  broadcastPeers() {
+    this.room.broadcast(JSON.stringify({ type: "presence", peers: this.peers() }));
+  }
+}
+
+/** Checks a token signed by /api/party-auth with the same PARTYKIT_SECRET. */
+async function verifyToken(token: string, secret: string): Promise<User | null> {
+  const [payload, signature] = token.split(".");
+  if (!payload || !signature || !secret) return null;
+
+  const encoder = new TextEncoder();
+  const key = await crypto.subtle.importKey("raw", encoder.encode(secret), { name: "HMAC", hash: "SHA-256" }, false, [
+    "verify",
+  ]);
+  const valid = await crypto.subtle.verify("HMAC", key, fromBase64Url(signature), encoder.encode(payload));
+  if (!valid) return null;
+
+  const { user, expires } = JSON.parse(new TextDecoder().decode(fromBase64Url(payload)));
+  return expires > Date.now() ? user : null;
+}
+
+function fromBase64Url(value: string) {
+  return Uint8Array.from(atob(value.replace(/-/g, "+").replace(/_/g, "/")), (char) => char.charCodeAt(0));
+}
+"#;
+
+const PARTYKIT_AUTH: &str = r#"import { getCollaborator } from "@/lib/collaboration/collaborator";
+
+const TOKEN_TTL_MS = 60 * 60 * 1000;
+
+/** A short-lived token the PartyKit server verifies before letting a connection in. */
+export async function GET() {
+  const user = await getCollaborator();
+  if (!user) {
+    return new Response("Unauthorized", { status: 401 });
+  }
+
+  const secret = process.env.PARTYKIT_SECRET;
+  if (!secret) {
+    return new Response("PARTYKIT_SECRET is not set", { status: 500 });
+  }
+
+  const payload = Buffer.from(JSON.stringify({ user, expires: Date.now() + TOKEN_TTL_MS })).toString("base64url");
+  const encoder = new TextEncoder();
+  const key = await crypto.subtle.importKey("raw", encoder.encode(secret), { name: "HMAC", hash: "SHA-256" }, false, [
+    "sign",
+  ]);
+  const signature = await crypto.subtle.sign("HMAC", key, encoder.encode(payload));
+
+  return Response.json({ token: `${payload}.${Buffer.from(signature).toString("base64url")}` });
+}
+"#;
+
+const PARTYKIT_ROOM: &str = r#""use client";
+
+import usePartySocket from "partysocket/react";
+import { createContext, useCallback, useContext, useRef, useState, type ReactNode } from "react";
+
+type Cursor = { x: number; y: number } | null;
+type Presence = { cursor: Cursor };
+
+type Peer = {
+  connectionId: string;
+  info: { id: string; name: string; color: string };
+  presence: Presence;
+};
+
+type ServerMessage =
+  | { type: "sync"; connectionId: string; text: string; peers: Peer[] }
+  | { type: "presence"; peers: Peer[] }
+  | { type: "text"; text: string };
+
+type RoomState = {
+  connectionId: string;
+  peers: Peer[];
+  text: string;
+  setText: (text: string) => void;
+  send: (message: object) => void;
+};
+
+const RoomContext = createContext<RoomState | null>(null);
+
+async function getToken() {
+  const response = await fetch("/api/party-auth");
+  if (!response.ok) {
+    throw new Error(`Could not join the room: ${response.status}`);
+  }
+  const { token } = await response.json();
+  return { token };
+}
+
+type RoomProps = {
+  id: string;
+  children: ReactNode;
+};
+
+export function Room({ id, children }: RoomProps) {
+  const [connectionId, setConnectionId] = useState<string | null>(null);
+  const [peers, setPeers] = useState<Peer[]>([]);
+  const [text, setText] = useState("");
+
+  const socket = usePartySocket({
+    host: process.env.NEXT_PUBLIC_PARTYKIT_HOST!,
+    room: id,
+    query: getToken,
+    onMessage(event) {
+      const message = JSON.parse(event.data) as ServerMessage;
+      if (message.type === "sync") {
+        setConnectionId(message.connectionId);
+        setText(message.text);
+        setPeers(message.peers);
+      } else if (message.type === "presence") {
+        setPeers(message.peers);
+      } else {
+        setText(message.text);
+      }
+    },
+  });
+  const send = useCallback((message: object) => socket.send(JSON.stringify(message)), [socket]);
+
+  if (connectionId === null) {
+    return <p className="text-sm text-gray-500">Connecting…</p>;
+  }
+
+  return (
+    <RoomContext.Provider value={{ connectionId, peers, text, setText, send }}>{children}</RoomContext.Provider>
+  );
+}
+
+function useRoom() {
+  const room = useContext(RoomContext);
+  if (!room) {
+    throw new Error("Collaboration hooks must be used inside <Room>");
+  }
+  return room;
+}
+
+export function useSelf() {
+  const { connectionId, peers } = useRoom();
+  return peers.find((peer) => peer.connectionId === connectionId)!;
+}
+
+export function useOthers() {
+  const { connectionId, peers } = useRoom();
+  return peers.filter((peer) => peer.connectionId !== connectionId);
+}
+
+export function useUpdateMyPresence() {
+  const { send } = useRoom();
+  const pending = useRef<Presence | null>(null);
+
+  return useCallback(
+    (presence: Presence) => {
+      // Send at most one cursor update per animation frame
+      if (pending.current === null) {
+        requestAnimationFrame(() => {
+          send({ type: "cursor", cursor: pending.current?.cursor ?? null });
+          pending.current = null;
+        });
+      }
+      pending.current = presence;
+    },
+    [send],
+  );
+}
+
+/** The room's shared text and a setter that syncs it to everyone. */
+export function useSharedText() {
+  const { text, setText, send } = useRoom();
+  const update = useCallback(
+    (value: string) => {
+      setText(value);
+      send({ type: "text", text: value });
+    },
+    [setText, send],
+  );
+  return [text, update] as const;
+}
+"#;
+
+const AVATARS: &str = r#""use client";
+
+import { useOthers, useSelf } from "@/components/collaboration/room";
+
+const MAX_SHOWN = 4;
+
+/** Everyone in the room, starting with you. */
+export function Avatars() {
+  const self = useSelf();
+  const others = useOthers();
+  const hidden = others.length - MAX_SHOWN;
+
+  return (
+    <div className="flex -space-x-2">
+      <Avatar name={`${self.info.name} (you)`} color={self.info.color} />
+      {others.slice(0, MAX_SHOWN).map(({ connectionId, info }) => (
+        <Avatar key={connectionId} name={info.name} color={info.color} />
+      ))}
+      {hidden > 0 && (
+        <div className="flex h-8 w-8 items-center justify-center rounded-full border-2 border-white bg-gray-200 text-xs font-medium text-gray-700">
+          +{hidden}
+        </div>
+      )}
+    </div>
+  );
+}
+
+function Avatar({ name, color }: { name: string; color: string }) {
+  return (
+    <div
+      title={name}
+      className="flex h-8 w-8 items-center justify-center rounded-full border-2 border-white text-xs font-medium text-white"
+      style={{ backgroundColor: color }}
+    >
+      {name.charAt(0).toUpperCase()}
+    </div>
+  );
+}
+"#;
+
+const CURSORS: &str = r#""use client";
+
+import type { PointerEvent, ReactNode } from "react";
+import { useOthers, useUpdateMyPresence } from "@/components/collaboration/room";
+
+/** Shares your pointer position inside this area and draws everyone else's. */
+export function Cursors({ children }: { children: ReactNode }) {
+  const others = useOthers();
+  const updateMyPresence = useUpdateMyPresence();
+
+  const onPointerMove = (event: PointerEvent<HTMLDivElement>) => {
+    const bounds = event.currentTarget.getBoundingClientRect();
+    updateMyPresence({
+      cursor: { x: Math.round(event.clientX - bounds.left), y: Math.round(event.clientY - bounds.top) },
+    });
+  };
+
+  return (
+    <div className="relative" onPointerMove={onPointerMove} onPointerLeave={() => updateMyPresence({ cursor: null })}>
+      {children}
+      {others.map(
+        ({ connectionId, info, presence }) =>
+          presence.cursor && (
+            <div
+              key={connectionId}
+              className="pointer-events-none absolute left-0 top-0 transition-transform duration-75"
+              style={{ transform: `translate(${presence.cursor.x}px, ${presence.cursor.y}px)` }}
+            >
+              <svg width="18" height="18" viewBox="0 0 24 24" fill={info.color} aria-hidden="true">
+                <path d="M4 2l16 9-7 2-3 7z" />
+              </svg>
+              <span className="ml-3 rounded px-1.5 py-0.5 text-xs text-white" style={{ backgroundColor: info.color }}>
+                {info.name}
+              </span>
+            </div>
+          ),
+      )}
+    </div>
+  );
+}
+"#;
+
+const EDITOR: &str = r#""use client";
+
+import { useSharedText } from "@/components/collaboration/room";
+
+/** A shared plain-text note. The latest edit wins, so use Yjs or Tiptap for real documents. */
+export function CollaborativeEditor() {
+  const [text, setText] = useSharedText();
+
+  return (
+    <textarea
+      value={text}
+      onChange={(event) => setText(event.target.value)}
+      placeholder="Start typing; everyone in the room sees it live"
+      className="h-64 w-full resize-none rounded-md border bg-transparent p-3 text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+    />
+  );
+}
+"#;
+
+const PAGE: &str = r#"import type { Metadata } from "next";
+import { Avatars } from "@/components/collaboration/avatars";
+import { CollaborativeEditor } from "@/components/collaboration/collaborative-editor";
+import { Cursors } from "@/components/collaboration/cursors";
+import { Room } from "@/components/collaboration/room";
+
+export const metadata: Metadata = {
+  title: "Collaborate",
+};
+
+export default function CollaboratePage() {
+  return (
+    <main className="mx-auto max-w-3xl space-y-4 p-8">
+      <Room id="demo">
+        <div className="flex items-center justify-between">
+          <div>
+            <h1 className="text-2xl font-semibold">Collaborate</h1>
+            <p className="text-sm text-gray-500">Open this page in another window to see cursors and edits live.</p>
+          </div>
+          <Avatars />
+        </div>
+        <Cursors>
+          <CollaborativeEditor />
+        </Cursors>
+      </Room>
+    </main>
+  );
+}
+"#;
+
+/// A PartyKit project name from package.json: lowercase letters, digits and dashes.
+fn party_name() -> String {
+    let name: String = package_json::package_name()
+        .unwrap_or_else(|| "app".to_string())
+        .rsplit('/')
+        .next()
+        .unwrap_or("app")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    format!("{}-party", name.trim_matches('-'))
+}
+
+pub async fn add_collaboration() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let descriptions: Vec<String> = RealtimeProvider::ALL
+        .iter()
+        .map(|provider| format!("{} - {}", provider.as_str(), provider.description()))
+        .collect();
+    let selection = prompt::select("provider", "Select a realtime provider", &descriptions, 0)?;
+    let provider = RealtimeProvider::ALL[selection];
+
+    let auth_library = manifest::installed_auth_library();
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message(format!("Installing {} dependencies...", provider.as_str()));
+
+    package_manager.install(provider.dependencies(), false)?;
+    if provider == RealtimeProvider::PartyKit {
+        package_manager.install(&["partykit"], true)?;
+    }
+
+    pb.set_message("Creating the auth endpoint...");
+
+    let lib_path = project_structure.get_lib_path().join("collaboration");
+    change_set::create_dir_all(&lib_path).context("Failed to create lib/collaboration directory")?;
+    let (session_imports, session_user) = admin::session_user(auth_library.map(|(package, _)| package));
+    let collaborator = COLLABORATOR
+        .replace("__SESSION_IMPORTS__", session_imports)
+        .replace("__SESSION_USER__", session_user);
+    file_writer::write(lib_path.join("collaborator.ts"), collaborator.trim_start())
+        .context("Failed to create collaborator.ts")?;
+
+    let auth_path = project_structure.get_api_path().join(provider.auth_route());
+    change_set::create_dir_all(&auth_path).context("Failed to create auth route directory")?;
+    let auth_route = match provider {
+        RealtimeProvider::Liveblocks => LIVEBLOCKS_AUTH,
+        RealtimeProvider::PartyKit => PARTYKIT_AUTH,
+    };
+    file_writer::write(auth_path.join("route.ts"), auth_route).context("Failed to create route.ts")?;
+
+    let liveblocks_config = project_structure.get_source_root().join("liveblocks.config.ts");
+    match provider {
+        RealtimeProvider::Liveblocks => {
+            file_writer::write(&liveblocks_config, LIVEBLOCKS_CONFIG)
+                .context("Failed to create liveblocks.config.ts")?;
+        }
+        RealtimeProvider::PartyKit => {
+            pb.set_message("Creating the PartyKit server...");
+
+            file_writer::write(project_file("partykit.json"), PARTYKIT_JSON.replace("__NAME__", &party_name()))
+                .context("Failed to create partykit.json")?;
+            change_set::create_dir_all(project_file("party")).context("Failed to create party directory")?;
+            file_writer::write(project_file("party/index.ts"), PARTY_SERVER).context("Failed to create party/index.ts")?;
+            package_json::add_scripts(&[
+                ("party:dev", "partykit dev"),
+                ("party:deploy", "partykit deploy --with-vars"),
+            ])?;
+        }
+    }
+
+    pb.set_message("Creating the room, presence and editor components...");
+
+    let components_path = project_structure.get_components_path().join("collaboration");
+    change_set::create_dir_all(&components_path).context("Failed to create components/collaboration directory")?;
+    let room = match provider {
+        RealtimeProvider::Liveblocks => LIVEBLOCKS_ROOM,
+        RealtimeProvider::PartyKit => PARTYKIT_ROOM,
+    };
+    for (file, content) in [
+        ("room.tsx", room),
+        ("avatars.tsx", AVATARS),
+        ("cursors.tsx", CURSORS),
+        ("collaborative-editor.tsx", EDITOR),
+    ] {
+        file_writer::write(components_path.join(file), content).context(format!("Failed to create {}", file))?;
+    }
+
+    pb.set_message("Creating the demo page...");
+
+    let page_path = root_layout::routes_dir(&project_structure).join("collaborate");
+    change_set::create_dir_all(&page_path).context("Failed to create collaborate route directory")?;
+    file_writer::write(page_path.join("page.tsx"), PAGE).context("Failed to create page.tsx")?;
+
+    env_file::add_env_vars("collaboration", "Realtime collaboration", provider.env_vars())?;
+
+    pb.finish_with_message(format!("{} collaboration added successfully!", provider.as_str()));
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/collaborator.ts - The signed-in user's name and cursor color", lib_path.display());
+    println!("• {}/route.ts - Auth endpoint that lets signed-in users into rooms", auth_path.display());
+    match provider {
+        RealtimeProvider::Liveblocks => {
+            println!("• {} - Presence, storage and user types", liveblocks_config.display());
+        }
+        RealtimeProvider::PartyKit => {
+            println!("• partykit.json and party/index.ts - Room server for presence and shared text");
+        }
+    }
+    println!("• {}/ - <Room>, presence avatars, live cursors and a shared editor", components_path.display());
+    println!("• {}/page.tsx - Collaborative demo page", page_path.display());
+
+    if auth_library.is_none() {
+        println!(
+            "{}",
+            style("No auth library found; rooms are only open in development, to guests, until getSessionUser() in collaborator.ts returns the signed-in user").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    match provider {
+        RealtimeProvider::Liveblocks => {
+            println!("1. Set LIVEBLOCKS_SECRET_KEY in .env from the Liveblocks dashboard");
+            println!("2. Start the dev server: {} and open /collaborate in two windows", package_manager.run_script("dev"));
+        }
+        RealtimeProvider::PartyKit => {
+            println!("1. Set PARTYKIT_SECRET in .env, e.g. to the output of `openssl rand -base64 32`");
+            println!("2. Run the room server next to Next: {}", package_manager.run_script("party:dev"));
+            println!("3. Open /collaborate in two windows");
+            println!("4. Deploy with {} and set NEXT_PUBLIC_PARTYKIT_HOST to its host", package_manager.run_script("party:deploy"));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::json;
+use crate::file_writer;
+use crate::features::husky;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::prompt;
+use crate::project_structure::project_file;
+
+const COMMITLINT_CONFIG: &str = r#"/** @type {import("@commitlint/types").UserConfig} */
+const config = {
+  extends: ["@commitlint/config-conventional"],
+  rules: {
+    // Allow longer bodies such as pasted stack traces or changelog notes
+    "body-max-line-length": [0, "always"],
+  },
+};
+
+export default config;
+"#;
+
+pub async fn add_commitlint() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let add_commitizen = prompt::confirm("commitizen", "Add a commitizen prompt for writing conventional commits?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing commitlint...");
+
+    let mut dev_dependencies = vec!["@commitlint/cli", "@commitlint/config-conventional"];
+    if add_commitizen {
+        dev_dependencies.extend(["commitizen", "cz-conventional-changelog"]);
+    }
+    package_manager.install(&dev_dependencies, true)?;
+    husky::ensure_husky(&package_manager)?;
+
+    pb.set_message("Creating commitlint.config.mjs...");
+
+    file_writer::write(project_file("commitlint.config.mjs"), COMMITLINT_CONFIG)
+        .context("Failed to create commitlint.config.mjs")?;
+
+    pb.set_message("Creating commit-msg hook...");
+
+    husky::write_hook(
+        "commit-msg",
+        &package_manager.exec_command("commitlint --edit \"$1\""),
+    )?;
+
+    if add_commitizen {
+        pb.set_message("Configuring commitizen...");
+
+        let mut package_json = package_json::read()?;
+        package_json["config"]["commitizen"] = json!({ "path": "cz-conventional-changelog" });
+        package_json::write(&package_json)?;
+        package_json::add_scripts(&[("commit", "cz")])?;
+    }
+
+    pb.finish_with_message("Commit message linting set up!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• commitlint.config.mjs - Conventional commit rules");
+    println!("• .husky/commit-msg - Rejects commits that don't follow the convention");
+    if add_commitizen {
+        println!("• package.json - commitizen config and commit script");
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Write commits like `feat: add billing page` or `fix(auth): handle expired sessions`");
+    if add_commitizen {
+        println!("2. Use the guided prompt: {}", package_manager.run_script("commit"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::env_file;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::root_layout;
+
+const CONVEX_DIR: &str = "convex";
+
+const CONVEX_TSCONFIG: &str = r#"{
+  "compilerOptions": {
+    "allowJs": true,
+    "strict": true,
+    "moduleResolution": "Bundler",
+    "jsx": "react-jsx",
+    "skipLibCheck": true,
+    "allowSyntheticDefaultImports": true,
+    "target": "ESNext",
+    "lib": ["ES2021", "dom"],
+    "forceConsistentCasingInFileNames": true,
+    "module": "ESNext",
+    "isolatedModules": true,
+    "noEmit": true
+  },
+  "include": ["./**/*"],
+  "exclude": ["./_generated"]
+}
+"#;
+
+const SCHEMA: &str = r#"import { defineSchema, defineTable } from "convex/server";
+import { v } from "convex/values";
+
+export default defineSchema({
+  tasks: defineTable({
+    text: v.string(),
+    isCompleted: v.boolean(),
+    // Subject of the signed-in user's identity, when auth is configured
+    userId: v.optional(v.string()),
+  }).index("by_user", ["userId"]),
+});
+"#;
+
+const TASKS: &str = r#"import { v } from "convex/values";
+import { mutation, query } from "./_generated/server";
+
+export const list = query({
+  args: {},
+  handler: async (ctx) => {
+    return await ctx.db.query("tasks").order("desc").take(100);
+  },
+});
+
+export const create = mutation({
+  args: { text: v.string() },
+  handler: async (ctx, { text }) => {
+    const identity = await ctx.auth.getUserIdentity();
+    return await ctx.db.insert("tasks", { text, isCompleted: false, userId: identity?.subject });
+  },
+});
+
+export const toggle = mutation({
+  args: { id: v.id("tasks") },
+  handler: async (ctx, { id }) => {
+    const task = await ctx.db.get(id);
+    if (!task) {
+      throw new Error("Task not found");
+    }
+    await ctx.db.patch(id, { isCompleted: !task.isCompleted });
+  },
+});
+
+export const remove = mutation({
+  args: { id: v.id("tasks") },
+  handler: async (ctx, { id }) => {
+    await ctx.db.delete(id);
+  },
+});
+"#;
+
+const CLERK_AUTH_CONFIG: &str = r#"// Set CLERK_JWT_ISSUER_DOMAIN in the Convex dashboard to your Clerk Frontend API URL
+export default {
+  providers: [
+    {
+      domain: process.env.CLERK_JWT_ISSUER_DOMAIN,
+      applicationID: "convex",
+    },
+  ],
+};
+"#;
+
+const PROVIDER: &str = r#""use client";
+
+import { ConvexProvider, ConvexReactClient } from "convex/react";
+import type { ReactNode } from "react";
+
+const convex = new ConvexReactClient(process.env.NEXT_PUBLIC_CONVEX_URL!);
+
+export function ConvexClientProvider({ children }: { children: ReactNode }) {
+  return <ConvexProvider client={convex}>{children}</ConvexProvider>;
+}
+"#;
+
+const CLERK_PROVIDER: &str = r#""use client";
+
+import { useAuth } from "@clerk/nextjs";
+import { ConvexReactClient } from "convex/react";
+import { ConvexProviderWithClerk } from "convex/react-clerk";
+import type { ReactNode } from "react";
+
+const convex = new ConvexReactClient(process.env.NEXT_PUBLIC_CONVEX_URL!);
+
+// Must render inside <ClerkProvider> so Convex can fetch Clerk session tokens
+export function ConvexClientProvider({ children }: { children: ReactNode }) {
+  return (
+    <ConvexProviderWithClerk client={convex} useAuth={useAuth}>
+      {children}
+    </ConvexProviderWithClerk>
+  );
+}
+"#;
+
+fn tasks_page(api_import: &str) -> String {
+    format!(
+        r#""use client";
+
+import {{ useMutation, useQuery }} from "convex/react";
+import {{ type FormEvent, useState }} from "react";
+import {{ api }} from "{}";
+
+export default function TasksPage() {{
+  const tasks = useQuery(api.tasks.list);
+  const createTask = useMutation(api.tasks.create);
+  const toggleTask = useMutation(api.tasks.toggle);
+  const removeTask = useMutation(api.tasks.remove);
+  const [text, setText] = useState("");
+
+  async function handleSubmit(event: FormEvent<HTMLFormElement>) {{
+    event.preventDefault();
+    if (!text.trim()) return;
+    await createTask({{ text: text.trim() }});
+    setText("");
+  }}
+
+  return (
+    <main className="mx-auto flex max-w-md flex-col gap-6 px-4 py-10">
+      <h1 className="text-2xl font-semibold">Tasks</h1>
+
+      <form onSubmit={{handleSubmit}} className="flex gap-2">
+        <input
+          value={{text}}
+          onChange={{(event) => setText(event.target.value)}}
+          placeholder="Add a task"
+          className="flex-1 rounded-md border px-3 py-2 text-sm"
+        />
+        <button type="submit" className="rounded-md bg-primary px-3 py-2 text-sm text-primary-foreground">
+          Add
+        </button>
+      </form>
+
+      {{tasks === undefined ? (
+        <p className="text-sm text-muted-foreground">Loading...</p>
+      ) : (
+        <ul className="flex flex-col gap-2">
+          {{tasks.map((task) => (
+            <li key={{task._id}} className="flex items-center gap-3 rounded-md border px-3 py-2">
+              <input type="checkbox" checked={{task.isCompleted}} onChange={{() => toggleTask({{ id: task._id }})}} />
+              <span className={{task.isCompleted ? "flex-1 text-muted-foreground line-through" : "flex-1"}}>{{task.text}}</span>
+              <button type="button" onClick={{() => removeTask({{ id: task._id }})}} className="text-sm text-red-600">
+                Delete
+              </button>
+            </li>
+          ))}}
+        </ul>
+      )}}
+    </main>
+  );
+}}
+"#,
+        api_import
+    )
+}
+
+pub async fn add_convex() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let use_clerk = package_json::has_dependency("@clerk/nextjs");
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Convex...");
+
+    package_manager.install(&["convex"], false)?;
+    package_manager.install(&["npm-run-all2"], true)?;
+
+    pb.set_message("Creating Convex functions...");
+
+    let convex_dir = project_file(CONVEX_DIR);
+    change_set::create_dir_all(&convex_dir).context("Failed to create convex directory")?;
+    let mut convex_files = vec![
+        ("tsconfig.json", CONVEX_TSCONFIG),
+        ("schema.ts", SCHEMA),
+        ("tasks.ts", TASKS),
+    ];
+    if use_clerk {
+        convex_files.push(("auth.config.ts", CLERK_AUTH_CONFIG));
+    }
+    for (file, content) in convex_files {
+        let path = convex_dir.join(file);
+        if !path.exists() {
+            file_writer::write(&path, content).context(format!("Failed to create {}", path.display()))?;
+        }
+    }
+
+    pb.set_message("Adding ConvexClientProvider to the root layout...");
+
+    let providers_path = project_structure.get_components_path().join("providers");
+    change_set::create_dir_all(&providers_path).context("Failed to create providers directory")?;
+    file_writer::write(
+        providers_path.join("convex-client-provider.tsx"),
+        if use_clerk { CLERK_PROVIDER } else { PROVIDER },
+    )
+    .context("Failed to create convex-client-provider.tsx")?;
+
+    root_layout::add_import(
+        &project_structure,
+        "import { ConvexClientProvider } from \"@/components/providers/convex-client-provider\";",
+    )?;
+    root_layout::wrap_children(&project_structure, "<ConvexClientProvider>", "</ConvexClientProvider>")?;
+
+    pb.set_message("Creating the tasks page...");
+
+    // convex/ sits at the project root, outside the `@/` alias in src layouts
+    let routes_dir = root_layout::routes_dir(&project_structure);
+    let page_path = routes_dir.join("tasks");
+    let depth = page_path.components().count();
+    let api_import = format!("{}convex/_generated/api", "../".repeat(depth));
+    change_set::create_dir_all(&page_path).context("Failed to create tasks route directory")?;
+    file_writer::write(page_path.join("page.tsx"), tasks_page(&api_import))
+        .context("Failed to create page.tsx")?;
+
+    pb.set_message("Updating package.json scripts...");
+
+    // Run Next and the Convex dev server together under `dev`
+    let next_dev = package_json::script("dev")
+        .filter(|command| !command.contains("run-p"))
+        .or_else(|| package_json::script("dev:next"))
+        .unwrap_or_else(|| "next dev".to_string());
+    package_json::set_scripts(&[
+        ("dev", "run-p --race dev:next dev:convex"),
+        ("dev:next", &next_dev),
+        ("dev:convex", "convex dev"),
+    ])?;
+    package_json::add_scripts(&[("convex:deploy", "convex deploy")])?;
+
+    // `convex dev` writes the real URL to .env.local, which takes precedence
+    env_file::add_env_vars(
+        "convex",
+        "Convex",
+        &[("NEXT_PUBLIC_CONVEX_URL", "https://your-deployment.convex.cloud")],
+    )?;
+
+    pb.finish_with_message("Convex set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• convex/schema.ts - Example tasks table");
+    println!("• convex/tasks.ts - list query and create/toggle/remove mutations");
+    if use_clerk {
+        println!("• convex/auth.config.ts - Clerk as the Convex auth provider");
+    }
+    println!(
+        "• {}/convex-client-provider.tsx - {}, added to the root layout",
+        providers_path.display(),
+        if use_clerk { "ConvexProviderWithClerk" } else { "ConvexProvider" }
+    );
+    println!("• {}/page.tsx - Realtime tasks example", page_path.display());
+    println!("• package.json - dev runs Next and `convex dev` together");
+
+    println!("\n{}", style("Next steps:").green());
+    println!(
+        "1. Link a deployment and generate convex/_generated: {}",
+        package_manager.exec_command("convex dev --once")
+    );
+    let mut step = 2;
+    if use_clerk {
+        println!("{}. Create a \"convex\" JWT template in Clerk and set CLERK_JWT_ISSUER_DOMAIN in the Convex dashboard", step);
+        step += 1;
+    }
+    println!("{}. Start both servers: {} and open /tasks", step, package_manager.run_script("dev"));
+
+    Ok(())
+}
@@ -0,0 +1,504 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::file_writer;
+use crate::package_json;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+use crate::root_layout;
+
+const CONSENT: &str = r#"export const CONSENT_COOKIE = "consent";
+
+/** Bump to ask every visitor again after the categories or the privacy policy change. */
+export const CONSENT_VERSION = 1;
+
+/** Cookie categories visitors opt into. Strictly necessary cookies never need consent. */
+export const CONSENT_CATEGORIES = {
+  analytics: {
+    label: "Analytics",
+    description: "Helps us understand how the site is used so we can improve it.",
+  },
+  marketing: {
+    label: "Marketing",
+    description: "Measures ad campaigns and personalizes the ads you see elsewhere.",
+  },
+} as const;
+
+export type ConsentCategory = keyof typeof CONSENT_CATEGORIES;
+
+export type ConsentChoices = Record<ConsentCategory, boolean>;
+
+export type Consent = ConsentChoices & {
+  version: number;
+  updatedAt: string;
+};
+
+/** The consent stored in the cookie, or null when the visitor has not decided on this version. */
+export function parseConsent(value: string | undefined): Consent | null {
+  if (!value) return null;
+  try {
+    const consent = JSON.parse(decodeURIComponent(value)) as Consent;
+    return consent.version === CONSENT_VERSION ? consent : null;
+  } catch {
+    return null;
+  }
+}
+"#;
+
+const STORE: &str = r#"import { useSyncExternalStore } from "react";
+import {
+  CONSENT_COOKIE,
+  CONSENT_VERSION,
+  parseConsent,
+  type Consent,
+  type ConsentCategory,
+  type ConsentChoices,
+} from "@/lib/consent/consent";
+
+// Regulators accept asking again after six months
+const MAX_AGE_SECONDS = 60 * 60 * 24 * 180;
+const OPEN_PREFERENCES_EVENT = "consent:open-preferences";
+
+const listeners = new Set<() => void>();
+let cached: { raw: string | undefined; consent: Consent | null } = { raw: undefined, consent: null };
+
+function readCookie() {
+  const prefix = `${CONSENT_COOKIE}=`;
+  return document.cookie
+    .split("; ")
+    .find((cookie) => cookie.startsWith(prefix))
+    ?.slice(prefix.length);
+}
+
+/** The visitor's stored choices, or null before they decide. */
+export function getConsent(): Consent | null {
+  const raw = readCookie();
+  if (raw !== cached.raw) {
+    cached = { raw, consent: parseConsent(raw) };
+  }
+  return cached.consent;
+}
+
+/**
+ * Stores the visitor's choices. Withdrawing consent reloads the page, because scripts that
+ * already ran cannot be unloaded.
+ */
+export function setConsent(choices: ConsentChoices) {
+  const previous = getConsent();
+  const consent: Consent = { ...choices, version: CONSENT_VERSION, updatedAt: new Date().toISOString() };
+  const secure = window.location.protocol === "https:" ? "; secure" : "";
+  document.cookie = `${CONSENT_COOKIE}=${encodeURIComponent(JSON.stringify(consent))}; path=/; max-age=${MAX_AGE_SECONDS}; samesite=lax${secure}`;
+
+  const withdrawn = previous && (Object.keys(choices) as ConsentCategory[]).some((category) => previous[category] && !choices[category]);
+  if (withdrawn) {
+    window.location.reload();
+    return;
+  }
+  listeners.forEach((listener) => listener());
+}
+
+function subscribe(listener: () => void) {
+  listeners.add(listener);
+  return () => listeners.delete(listener);
+}
+
+/** The visitor's choices, or null before they decide and while rendering on the server. */
+export function useConsent() {
+  return useSyncExternalStore(subscribe, getConsent, () => null);
+}
+
+export function useHasConsent(category: ConsentCategory) {
+  return useConsent()?.[category] === true;
+}
+
+/** Reopens the preferences dialog, e.g. from a "Cookie settings" link in the footer. */
+export function openConsentPreferences() {
+  window.dispatchEvent(new Event(OPEN_PREFERENCES_EVENT));
+}
+
+export function onOpenConsentPreferences(listener: () => void) {
+  window.addEventListener(OPEN_PREFERENCES_EVENT, listener);
+  return () => window.removeEventListener(OPEN_PREFERENCES_EVENT, listener);
+}
+"#;
+
+const SERVER: &str = r#"import { cookies } from "next/headers";
+import { CONSENT_COOKIE, parseConsent } from "@/lib/consent/consent";
+
+/** The visitor's consent in server components, to leave out tracking they have not allowed. */
+export async function getServerConsent() {
+  return parseConsent((await cookies()).get(CONSENT_COOKIE)?.value);
+}
+"#;
+
+const BANNER: &str = r#""use client";
+
+import Link from "next/link";
+import { useEffect, useState, useSyncExternalStore } from "react";
+import { CONSENT_CATEGORIES, type ConsentCategory, type ConsentChoices } from "@/lib/consent/consent";
+import { onOpenConsentPreferences, openConsentPreferences, setConsent, useConsent } from "@/lib/consent/store";
+import { ConsentPreferences } from "./consent-preferences";
+
+const CATEGORIES = Object.keys(CONSENT_CATEGORIES) as ConsentCategory[];
+
+function choicesFor(allowed: boolean) {
+  return Object.fromEntries(CATEGORIES.map((category) => [category, allowed])) as ConsentChoices;
+}
+
+const noop = () => () => {};
+
+/**
+ * Asks for consent until the visitor decides, with rejecting as easy as accepting, and
+ * hosts the preferences dialog openConsentPreferences() opens.
+ */
+export function ConsentBanner() {
+  const consent = useConsent();
+  // Server HTML never shows the banner, so visitors who already decided never see it flash
+  const hydrated = useSyncExternalStore(noop, () => true, () => false);
+  const [customizing, setCustomizing] = useState(false);
+
+  useEffect(() => onOpenConsentPreferences(() => setCustomizing(true)), []);
+
+  if (!hydrated) return null;
+  if (customizing) {
+    return <ConsentPreferences initial={consent ?? choicesFor(false)} onClose={() => setCustomizing(false)} />;
+  }
+  if (consent) return null;
+
+  return (
+    <div
+      role="region"
+      aria-label="Cookie consent"
+      className="fixed inset-x-4 bottom-4 z-50 mx-auto flex max-w-2xl flex-col gap-3 rounded-lg border bg-background p-4 text-sm shadow-lg sm:flex-row sm:items-center"
+    >
+      <p className="flex-1 text-muted-foreground">
+        We use cookies for analytics and marketing only if you allow them. Read our{" "}
+        <Link href="/privacy" className="underline">
+          privacy policy
+        </Link>
+        .
+      </p>
+      <div className="flex gap-2">
+        <button type="button" onClick={() => setCustomizing(true)} className="rounded-md px-3 py-2 underline">
+          Customize
+        </button>
+        <button type="button" onClick={() => setConsent(choicesFor(false))} className="rounded-md border px-3 py-2">
+          Reject all
+        </button>
+        <button type="button" onClick={() => setConsent(choicesFor(true))} className="rounded-md border px-3 py-2">
+          Accept all
+        </button>
+      </div>
+    </div>
+  );
+}
+
+/** A "Cookie settings" link for the footer or the privacy policy. */
+export function ConsentPreferencesButton({ className }: { className?: string }) {
+  return (
+    <button type="button" onClick={openConsentPreferences} className={className ?? "underline"}>
+      Cookie settings
+    </button>
+  );
+}
+"#;
+
+const PREFERENCES: &str = r#""use client";
+
+import { useEffect, useRef, useState } from "react";
+import { CONSENT_CATEGORIES, type ConsentCategory, type ConsentChoices } from "@/lib/consent/consent";
+import { setConsent } from "@/lib/consent/store";
+
+/** Per-category choices in a modal dialog. */
+export function ConsentPreferences({ initial, onClose }: { initial: ConsentChoices; onClose: () => void }) {
+  const dialogRef = useRef<HTMLDialogElement>(null);
+  const [choices, setChoices] = useState(initial);
+
+  useEffect(() => {
+    dialogRef.current?.showModal();
+  }, []);
+
+  function save() {
+    setConsent(choices);
+    onClose();
+  }
+
+  return (
+    <dialog
+      ref={dialogRef}
+      onClose={onClose}
+      aria-labelledby="consent-preferences-title"
+      className="w-full max-w-lg rounded-lg border bg-background p-6 text-sm backdrop:bg-black/50"
+    >
+      <h2 id="consent-preferences-title" className="text-lg font-semibold">
+        Cookie settings
+      </h2>
+      <ul className="mt-4 flex flex-col gap-4">
+        <li className="flex items-start justify-between gap-4">
+          <div>
+            <p className="font-medium">Strictly necessary</p>
+            <p className="text-muted-foreground">Keeps you signed in and remembers these settings. Always on.</p>
+          </div>
+          <input type="checkbox" checked disabled aria-label="Strictly necessary" />
+        </li>
+        {(Object.entries(CONSENT_CATEGORIES) as [ConsentCategory, (typeof CONSENT_CATEGORIES)[ConsentCategory]][]).map(
+          ([category, { label, description }]) => (
+            <li key={category} className="flex items-start justify-between gap-4">
+              <label htmlFor={`consent-${category}`}>
+                <span className="block font-medium">{label}</span>
+                <span className="block text-muted-foreground">{description}</span>
+              </label>
+              <input
+                id={`consent-${category}`}
+                type="checkbox"
+                checked={choices[category]}
+                onChange={(event) => setChoices({ ...choices, [category]: event.target.checked })}
+              />
+            </li>
+          ),
+        )}
+      </ul>
+      <div className="mt-6 flex justify-end gap-2">
+        <button type="button" onClick={() => dialogRef.current?.close()} className="rounded-md px-3 py-2">
+          Cancel
+        </button>
+        <button type="button" onClick={save} className="rounded-md border px-3 py-2">
+          Save choices
+        </button>
+      </div>
+    </dialog>
+  );
+}
+"#;
+
+const GATE: &str = r#""use client";
+
+import Script, { type ScriptProps } from "next/script";
+import type { ReactNode } from "react";
+import type { ConsentCategory } from "@/lib/consent/consent";
+import { useHasConsent } from "@/lib/consent/store";
+
+/** A next/script that loads only once the visitor allows `category`. */
+export function ConsentScript({ category, ...props }: ScriptProps & { category: ConsentCategory }) {
+  return useHasConsent(category) ? <Script {...props} /> : null;
+}
+
+/** Renders `children`, e.g. a video embed that sets cookies, only once the visitor allows `category`. */
+export function ConsentGate({
+  category,
+  children,
+  fallback = null,
+}: {
+  category: ConsentCategory;
+  children: ReactNode;
+  fallback?: ReactNode;
+}) {
+  return useHasConsent(category) ? children : fallback;
+}
+"#;
+
+const ANALYTICS_CONSENT: &str = r#""use client";
+
+__IMPORTS__
+/** Holds analytics back until the visitor allows the analytics category. */
+export function AnalyticsConsent() {
+  const analytics = useHasConsent("analytics");
+__BODY__}
+"#;
+
+const POSTHOG_EFFECT: &str = r#"
+  // Initialize PostHog with opt_out_capturing_by_default: true so nothing is sent before this runs
+  useEffect(() => {
+    if (analytics) {
+      posthog.opt_in_capturing();
+    } else if (posthog.has_opted_in_capturing()) {
+      posthog.opt_out_capturing();
+    }
+  }, [analytics]);
+"#;
+
+const PRIVACY_PAGE: &str = r#"import type { Metadata } from "next";
+import { ConsentPreferencesButton } from "@/components/consent/consent-banner";
+import { CONSENT_CATEGORIES } from "@/lib/consent/consent";
+
+export const metadata: Metadata = {
+  title: "Privacy policy",
+};
+
+// A starting point, not legal advice: have it reviewed for the data you actually process
+export default function PrivacyPage() {
+  return (
+    <main className="mx-auto flex max-w-2xl flex-col gap-6 px-4 py-12 text-sm leading-relaxed">
+      <h1 className="text-3xl font-semibold">Privacy policy</h1>
+
+      <section className="flex flex-col gap-2">
+        <h2 className="text-xl font-semibold">Who we are</h2>
+        <p>
+          __COMPANY__ is responsible for the personal data processed on this site. Contact us at{" "}
+          <a href="mailto:__EMAIL__" className="underline">
+            __EMAIL__
+          </a>
+          .
+        </p>
+      </section>
+
+      <section className="flex flex-col gap-2">
+        <h2 className="text-xl font-semibold">What we collect</h2>
+        <p>Describe the account, payment and usage data you collect, why you need it and how long you keep it.</p>
+      </section>
+
+      <section className="flex flex-col gap-2">
+        <h2 className="text-xl font-semibold">Cookies</h2>
+        <p>Strictly necessary cookies keep the site working. Everything else is off until you allow it:</p>
+        <ul className="list-disc pl-5">
+          {Object.values(CONSENT_CATEGORIES).map(({ label, description }) => (
+            <li key={label}>
+              <strong>{label}</strong>: {description}
+            </li>
+          ))}
+        </ul>
+        <p>
+          You can change your choices at any time: <ConsentPreferencesButton />
+        </p>
+      </section>
+
+      <section className="flex flex-col gap-2">
+        <h2 className="text-xl font-semibold">Your rights</h2>
+        <p>
+          You can ask to access, correct, export or delete your data, and object to or restrict how we process it. You
+          can also complain to your data protection authority.
+        </p>
+      </section>
+    </main>
+  );
+}
+"#;
+
+pub async fn add_cookie_consent() -> Result<()> {
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    let company = prompt::input("company", "Company or site name for the privacy policy", "My App")?;
+    let contact_email = prompt::input("contact_email", "Privacy contact email", "privacy@example.com")?;
+
+    let posthog = package_json::has_dependency("posthog-js");
+    let plausible = package_json::has_dependency("next-plausible");
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Creating the consent store...");
+
+    let lib_path = project_structure.get_lib_path().join("consent");
+    change_set::create_dir_all(&lib_path).context("Failed to create lib/consent directory")?;
+    file_writer::write(lib_path.join("consent.ts"), CONSENT).context("Failed to create consent.ts")?;
+    file_writer::write(lib_path.join("store.ts"), STORE).context("Failed to create store.ts")?;
+    file_writer::write(lib_path.join("server.ts"), SERVER).context("Failed to create server.ts")?;
+
+    pb.set_message("Creating the consent banner and preferences dialog...");
+
+    let components_path = project_structure.get_components_path().join("consent");
+    change_set::create_dir_all(&components_path).context("Failed to create components/consent directory")?;
+    file_writer::write(components_path.join("consent-banner.tsx"), BANNER)
+        .context("Failed to create consent-banner.tsx")?;
+    file_writer::write(components_path.join("consent-preferences.tsx"), PREFERENCES)
+        .context("Failed to create consent-preferences.tsx")?;
+    file_writer::write(components_path.join("consent-gate.tsx"), GATE).context("Failed to create consent-gate.tsx")?;
+
+    let analytics = posthog || plausible;
+    if analytics {
+        let mut imports = Vec::new();
+        let mut body = String::new();
+        if plausible {
+            imports.push("import PlausibleProvider from \"next-plausible\";");
+        }
+        if posthog {
+            imports.push("import posthog from \"posthog-js\";");
+            imports.push("import { useEffect } from \"react\";");
+            body.push_str(POSTHOG_EFFECT);
+        }
+        imports.push("import { useHasConsent } from \"@/lib/consent/store\";");
+        if plausible {
+            body.push_str("\n  return <PlausibleProvider domain={process.env.NEXT_PUBLIC_PLAUSIBLE_DOMAIN!} enabled={analytics} />;\n");
+        } else {
+            body.push_str("\n  return null;\n");
+        }
+
+        let analytics_consent = ANALYTICS_CONSENT
+            .replace("__IMPORTS__", &format!("{}\n", imports.join("\n")))
+            .replace("__BODY__", &body);
+        file_writer::write(components_path.join("analytics-consent.tsx"), analytics_consent)
+            .context("Failed to create analytics-consent.tsx")?;
+        if plausible {
+            env_file::add_env_vars("cookie-consent", "Plausible", &[("NEXT_PUBLIC_PLAUSIBLE_DOMAIN", "")])?;
+        }
+    }
+
+    pb.set_message("Creating the privacy policy page...");
+
+    let privacy_path = root_layout::routes_dir(&project_structure).join("privacy");
+    change_set::create_dir_all(&privacy_path).context("Failed to create privacy route directory")?;
+    let privacy_page = PRIVACY_PAGE
+        .replace("__COMPANY__", &company)
+        .replace("__EMAIL__", &contact_email);
+    file_writer::write(privacy_path.join("page.tsx"), privacy_page).context("Failed to create privacy page")?;
+
+    pb.set_message("Adding the consent banner to the root layout...");
+
+    root_layout::add_import(
+        &project_structure,
+        "import { ConsentBanner } from \"@/components/consent/consent-banner\";",
+    )?;
+    root_layout::append_child(&project_structure, "body", "<ConsentBanner />")?;
+    if analytics {
+        root_layout::add_import(
+            &project_structure,
+            "import { AnalyticsConsent } from \"@/components/consent/analytics-consent\";",
+        )?;
+        root_layout::append_child(&project_structure, "body", "<AnalyticsConsent />")?;
+    }
+
+    pb.finish_with_message("Cookie consent set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/store.ts - Consent cookie store with useConsent() and setConsent()", lib_path.display());
+    println!("• {}/server.ts - getServerConsent() for server components", lib_path.display());
+    println!("• {}/consent-banner.tsx - Banner and preferences dialog, added to the root layout", components_path.display());
+    println!("• {}/consent-gate.tsx - <ConsentScript> and <ConsentGate> to hold back scripts and embeds", components_path.display());
+    if analytics {
+        let services: Vec<&str> = [(posthog, "PostHog"), (plausible, "Plausible")]
+            .into_iter()
+            .filter_map(|(installed, name)| installed.then_some(name))
+            .collect();
+        println!(
+            "• {}/analytics-consent.tsx - Starts {} only after consent",
+            components_path.display(),
+            services.join(" and ")
+        );
+    }
+    println!("• {}/page.tsx - Privacy policy stub", privacy_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Fill in the privacy policy at /privacy and list your categories in CONSENT_CATEGORIES");
+    println!("2. Load third-party scripts with <ConsentScript category=\"marketing\" src=\"...\" />");
+    let mut step = 3;
+    if posthog {
+        println!("{}. Initialize PostHog with opt_out_capturing_by_default: true", step);
+        step += 1;
+    }
+    if plausible {
+        println!("{}. Set NEXT_PUBLIC_PLAUSIBLE_DOMAIN and remove any other <PlausibleProvider>", step);
+        step += 1;
+    }
+    println!("{}. Put <ConsentPreferencesButton /> in your footer", step);
+
+    Ok(())
+}
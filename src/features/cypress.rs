@@ -0,0 +1,292 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::env_file;
+use crate::gitignore;
+use crate::manifest;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_profile::ProjectProfile;
+use crate::project_structure::{project_file, ProjectStructure};
+
+const CYPRESS_CONFIG: &str = r#"import { defineConfig } from "cypress";
+import { loadEnvConfig } from "@next/env";
+
+// Load .env files the same way Next.js does
+loadEnvConfig(process.cwd());
+
+export default defineConfig({
+  e2e: {
+    baseUrl: process.env.CYPRESS_BASE_URL ?? "http://localhost:3000",
+    specPattern: "cypress/e2e/**/*.cy.{ts,tsx}",
+    supportFile: "cypress/support/e2e.ts",
+  },
+  component: {
+    devServer: {
+      framework: "next",
+      bundler: "webpack",
+    },
+    specPattern: "cypress/component/**/*.cy.{ts,tsx}",
+    supportFile: "cypress/support/component.tsx",
+  },
+  env: {
+    USER_EMAIL: process.env.E2E_USER_EMAIL,
+    USER_PASSWORD: process.env.E2E_USER_PASSWORD,
+  },
+  video: false,
+  screenshotOnRunFailure: true,
+});
+"#;
+
+const CYPRESS_TSCONFIG: &str = r#"{
+  "extends": "../tsconfig.json",
+  "compilerOptions": {
+    "types": ["cypress", "node"],
+    "isolatedModules": false
+  },
+  "include": ["**/*.ts", "**/*.tsx", "../cypress.config.ts"]
+}
+"#;
+
+const E2E_SUPPORT: &str = r#"import "./commands";
+"#;
+
+const COMPONENT_SUPPORT: &str = r#"import "./commands";
+import "__GLOBALS_CSS__";
+import { mount } from "cypress/react";
+
+declare global {
+  __IGNORE_NAMESPACE__
+  namespace Cypress {
+    interface Chainable {
+      mount: typeof mount;
+    }
+  }
+}
+
+Cypress.Commands.add("mount", mount);
+"#;
+
+const EMPTY_COMMANDS: &str = r#"// Add custom commands here, e.g. Cypress.Commands.add("name", () => { ... })
+export {};
+"#;
+
+fn login_commands(sign_in_path: &str) -> String {
+    format!(
+        r#"declare global {{
+  __IGNORE_NAMESPACE__
+  namespace Cypress {{
+    interface Chainable {{
+      login(email?: string, password?: string): Chainable<void>;
+    }}
+  }}
+}}
+
+/**
+ * Signs in through the UI once per spec and caches the session with cy.session.
+ * Credentials default to E2E_USER_EMAIL / E2E_USER_PASSWORD from .env.
+ */
+Cypress.Commands.add(
+  "login",
+  (email = Cypress.env("USER_EMAIL"), password = Cypress.env("USER_PASSWORD")) => {{
+    cy.session([email], () => {{
+      cy.visit("{}");
+      cy.findByLabelText(/email/i).type(email);
+      cy.findByLabelText(/password/i).type(password, {{ log: false }});
+      cy.findByRole("button", {{ name: /sign in|log in|continue/i }}).click();
+      cy.location("pathname").should("not.match", /sign-?in|login/);
+    }});
+  }},
+);
+
+export {{}};
+"#,
+        sign_in_path
+    )
+}
+
+const HOME_SPEC: &str = r#"describe("homepage", () => {
+  it("loads", () => {
+    cy.visit("/");
+    cy.title().should("not.be.empty");
+  });
+});
+"#;
+
+const AUTHENTICATED_SPEC: &str = r#"describe("authenticated", () => {
+  beforeEach(() => {
+    cy.login();
+  });
+
+  it("keeps the session between visits", () => {
+    cy.visit("/");
+    cy.getCookies().should("not.be.empty");
+  });
+});
+"#;
+
+const BUTTON_COMPONENT_SPEC: &str = r#"import { Button } from "@/components/ui/button";
+
+describe("<Button />", () => {
+  it("calls onClick", () => {
+    const onClick = cy.stub().as("onClick");
+    cy.mount(<Button onClick={onClick}>Save</Button>);
+
+    cy.findByRole("button", { name: "Save" }).click();
+    cy.get("@onClick").should("have.been.calledOnce");
+  });
+});
+"#;
+
+const EXAMPLE_COMPONENT_SPEC: &str = r#"import { useState } from "react";
+
+function Toggle() {
+  const [on, setOn] = useState(false);
+  return (
+    <button type="button" onClick={() => setOn((value) => !value)}>
+      {on ? "On" : "Off"}
+    </button>
+  );
+}
+
+describe("<Toggle />", () => {
+  it("toggles its label", () => {
+    cy.mount(<Toggle />);
+
+    cy.findByRole("button", { name: "Off" }).click();
+    cy.findByRole("button", { name: "On" }).should("exist");
+  });
+});
+"#;
+
+pub async fn add_cypress() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let auth_library = manifest::installed_auth_library();
+    if let Some((library, _)) = auth_library {
+        println!("{}", style(format!("Detected {}, adding a cy.login() command", library)).yellow());
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Cypress...");
+
+    package_manager.install(
+        &["cypress", "@testing-library/cypress", "start-server-and-test"],
+        true,
+    )?;
+
+    pb.set_message("Creating cypress.config.ts...");
+
+    file_writer::write(project_file("cypress.config.ts"), CYPRESS_CONFIG)
+        .context("Failed to create cypress.config.ts")?;
+
+    for dir in ["cypress/e2e", "cypress/component", "cypress/support", "cypress/fixtures"] {
+        change_set::create_dir_all(project_file(dir)).context(format!("Failed to create {} directory", dir))?;
+    }
+    file_writer::write(project_file("cypress/tsconfig.json"), CYPRESS_TSCONFIG)
+        .context("Failed to create cypress/tsconfig.json")?;
+
+    pb.set_message("Creating support files and commands...");
+
+    // Cypress commands are typed by augmenting its namespace, which linters flag
+    let ignore_namespace = ProjectProfile::detect().ignore_next_line(
+        "@typescript-eslint/no-namespace",
+        "lint/style/noNamespace",
+        "Cypress types custom commands through its namespace",
+    );
+    let commands = match auth_library {
+        Some((_, sign_in_path)) => login_commands(sign_in_path),
+        None => EMPTY_COMMANDS.to_string(),
+    };
+    file_writer::write(
+        project_file("cypress/support/commands.ts"),
+        format!("import \"@testing-library/cypress/add-commands\";\n\n{}", commands.replace("__IGNORE_NAMESPACE__", &ignore_namespace)),
+    )
+    .context("Failed to create commands.ts")?;
+    file_writer::write(project_file("cypress/support/e2e.ts"), E2E_SUPPORT)
+        .context("Failed to create e2e.ts")?;
+
+    // Support files live in cypress/support, so the stylesheet import is relative to it
+    let globals_css = format!("../../{}", project_structure.relative_path(&project_structure.get_globals_css_path()));
+    file_writer::write(
+        project_file("cypress/support/component.tsx"),
+        COMPONENT_SUPPORT
+            .replace("__GLOBALS_CSS__", &globals_css)
+            .replace("__IGNORE_NAMESPACE__", &ignore_namespace),
+    )
+    .context("Failed to create component.tsx")?;
+
+    pb.set_message("Creating example specs...");
+
+    file_writer::write(project_file("cypress/e2e/home.cy.ts"), HOME_SPEC)
+        .context("Failed to create home.cy.ts")?;
+    if auth_library.is_some() {
+        file_writer::write(project_file("cypress/e2e/authenticated.cy.ts"), AUTHENTICATED_SPEC)
+            .context("Failed to create authenticated.cy.ts")?;
+
+        env_file::add_env_vars(
+            "cypress",
+            "Cypress test user",
+            &[("E2E_USER_EMAIL", "test@example.com"), ("E2E_USER_PASSWORD", "")],
+        )?;
+    }
+
+    let button_path = project_structure.get_components_path().join("ui/button.tsx");
+    let (component_spec_name, component_spec) = if button_path.exists() {
+        ("button.cy.tsx", BUTTON_COMPONENT_SPEC)
+    } else {
+        ("toggle.cy.tsx", EXAMPLE_COMPONENT_SPEC)
+    };
+    file_writer::write(project_file("cypress/component").join(component_spec_name), component_spec)
+        .context(format!("Failed to create {}", component_spec_name))?;
+
+    pb.set_message("Updating package.json scripts...");
+
+    let added_scripts = package_json::add_scripts(&[
+        ("cy:open", "cypress open"),
+        ("cy:run", "cypress run --e2e"),
+        ("cy:component", "cypress run --component"),
+        ("test:e2e", "start-server-and-test dev http://localhost:3000 cy:run"),
+    ])?;
+
+    gitignore::add_entries("Cypress", &["/cypress/videos/", "/cypress/screenshots/", "/cypress/downloads/"])?;
+
+    pb.finish_with_message("Cypress set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• cypress.config.ts - E2E and React component testing config");
+    println!("• cypress/support/ - Testing Library commands{}", if auth_library.is_some() { " and cy.login()" } else { "" });
+    println!("• cypress/e2e/ - Example end-to-end specs");
+    println!("• cypress/component/{} - Example component test", component_spec_name);
+
+    if !added_scripts.iter().any(|script| script == "test:e2e") {
+        println!(
+            "{}",
+            style("• test:e2e already exists; run Cypress headless with the cy:run script").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    let mut step = 1;
+    if auth_library.is_some() {
+        println!("{}. Set E2E_USER_EMAIL and E2E_USER_PASSWORD in .env for a test account", step);
+        step += 1;
+    }
+    println!("{}. Open the Cypress app: {}", step, package_manager.run_script("cy:open"));
+    println!("{}. Run component tests: {}", step + 1, package_manager.run_script("cy:component"));
+    println!("{}. Run e2e tests against the dev server: {}", step + 2, package_manager.run_script("test:e2e"));
+
+    Ok(())
+}
@@ -0,0 +1,549 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::root_layout;
+
+const TABLE_QUERY: &str = r#"export type SortOrder = "asc" | "desc";
+
+export type TableQuery = {
+  page: number;
+  pageSize: number;
+  sort: string | undefined;
+  order: SortOrder;
+  q: string;
+};
+
+export type TablePage<T> = {
+  rows: T[];
+  total: number;
+};
+
+export type TableSearchParams = Record<string, string | string[] | undefined>;
+
+export const PAGE_SIZES = [10, 20, 50, 100];
+
+function first(value: string | string[] | undefined) {
+  return Array.isArray(value) ? value[0] : value;
+}
+
+/**
+ * Reads pagination, sorting and filtering state from the URL so tables are shareable and
+ * the server only loads the rows being shown.
+ */
+export function parseTableQuery(params: TableSearchParams): TableQuery {
+  const page = Math.max(1, Number(first(params.page)) || 1);
+  const requestedSize = Number(first(params.pageSize));
+  const pageSize = PAGE_SIZES.includes(requestedSize) ? requestedSize : 10;
+
+  return {
+    page,
+    pageSize,
+    sort: first(params.sort),
+    order: first(params.order) === "desc" ? "desc" : "asc",
+    q: first(params.q)?.trim() ?? "",
+  };
+}
+"#;
+
+const CSV_HELPERS: &str = r#"function escapeCell(value: unknown): string {
+  if (value === null || value === undefined) return "";
+  const text = value instanceof Date ? value.toISOString() : String(value);
+  return /[",\r\n]/.test(text) ? `"${text.replace(/"/g, '""')}"` : text;
+}
+
+export function toCsv(headers: string[], rows: unknown[][]): string {
+  return [headers, ...rows].map((row) => row.map(escapeCell).join(",")).join("\r\n");
+}
+
+export function downloadCsv(fileName: string, headers: string[], rows: unknown[][]) {
+  const blob = new Blob([toCsv(headers, rows)], { type: "text/csv;charset=utf-8" });
+  const url = URL.createObjectURL(blob);
+  const link = document.createElement("a");
+  link.href = url;
+  link.download = fileName;
+  link.click();
+  URL.revokeObjectURL(url);
+}
+"#;
+
+const DATA_TABLE: &str = r#""use client";
+
+import {
+  type Column,
+  type ColumnDef,
+  flexRender,
+  getCoreRowModel,
+  type RowSelectionState,
+  type SortingState,
+  useReactTable,
+} from "@tanstack/react-table";
+import { ArrowDown, ArrowUp, ArrowUpDown, Download } from "lucide-react";
+import { usePathname, useRouter, useSearchParams } from "next/navigation";
+import { useEffect, useState, useTransition } from "react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Table, TableBody, TableCell, TableHead, TableHeader, TableRow } from "@/components/ui/table";
+import { downloadCsv } from "@/lib/csv";
+import { PAGE_SIZES, type TableQuery } from "@/lib/data-table";
+
+type DataTableProps<TData, TValue> = {
+  columns: ColumnDef<TData, TValue>[];
+  data: TData[];
+  total: number;
+  query: TableQuery;
+  searchPlaceholder?: string;
+  exportFileName?: string;
+};
+
+/**
+ * Server-driven table: pagination, sorting and search live in the URL and the page
+ * re-renders on the server with the new query.
+ */
+export function DataTable<TData, TValue>({
+  columns,
+  data,
+  total,
+  query,
+  searchPlaceholder = "Search...",
+  exportFileName = "export",
+}: DataTableProps<TData, TValue>) {
+  const router = useRouter();
+  const pathname = usePathname();
+  const searchParams = useSearchParams();
+  const [isPending, startTransition] = useTransition();
+  const [rowSelection, setRowSelection] = useState<RowSelectionState>({});
+  const [search, setSearch] = useState(query.q);
+
+  const pageCount = Math.max(1, Math.ceil(total / query.pageSize));
+  const sorting: SortingState = query.sort ? [{ id: query.sort, desc: query.order === "desc" }] : [];
+
+  function updateSearchParams(updates: Record<string, string | number | undefined>) {
+    const params = new URLSearchParams(searchParams.toString());
+    for (const [key, value] of Object.entries(updates)) {
+      if (value === undefined || value === "") {
+        params.delete(key);
+      } else {
+        params.set(key, String(value));
+      }
+    }
+    startTransition(() => router.replace(`${pathname}?${params.toString()}`, { scroll: false }));
+  }
+
+  // Selection is per page; clear it when new rows arrive
+  useEffect(() => setRowSelection({}), [data]);
+
+  // Debounce typing before asking the server for filtered rows
+  useEffect(() => {
+    if (search === query.q) return;
+    const timeout = setTimeout(() => updateSearchParams({ q: search, page: undefined }), 300);
+    return () => clearTimeout(timeout);
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [search]);
+
+  const table = useReactTable({
+    data,
+    columns,
+    pageCount,
+    state: {
+      sorting,
+      rowSelection,
+      pagination: { pageIndex: query.page - 1, pageSize: query.pageSize },
+    },
+    manualPagination: true,
+    manualSorting: true,
+    manualFiltering: true,
+    enableRowSelection: true,
+    onRowSelectionChange: setRowSelection,
+    onSortingChange: (updater) => {
+      const [next] = typeof updater === "function" ? updater(sorting) : updater;
+      updateSearchParams({
+        sort: next?.id,
+        order: next ? (next.desc ? "desc" : "asc") : undefined,
+        page: undefined,
+      });
+    },
+    getCoreRowModel: getCoreRowModel(),
+  });
+
+  const selectedRows = table.getSelectedRowModel().rows;
+
+  // Exports the selected rows, or every row on the current page when nothing is selected
+  function exportCsv() {
+    const rows = selectedRows.length > 0 ? selectedRows : table.getRowModel().rows;
+    const exportColumns = table.getVisibleLeafColumns().filter((column) => column.id !== "select");
+    downloadCsv(
+      `${exportFileName}.csv`,
+      exportColumns.map((column) => column.id),
+      rows.map((row) => exportColumns.map((column) => row.getValue(column.id))),
+    );
+  }
+
+  return (
+    <div className="flex flex-col gap-4">
+      <div className="flex items-center justify-between gap-2">
+        <Input
+          value={search}
+          onChange={(event) => setSearch(event.target.value)}
+          placeholder={searchPlaceholder}
+          className="max-w-sm"
+        />
+        <Button variant="outline" size="sm" onClick={exportCsv}>
+          <Download />
+          {selectedRows.length > 0 ? `Export ${selectedRows.length} selected` : "Export CSV"}
+        </Button>
+      </div>
+
+      <div className={isPending ? "rounded-md border opacity-60" : "rounded-md border"}>
+        <Table>
+          <TableHeader>
+            {table.getHeaderGroups().map((headerGroup) => (
+              <TableRow key={headerGroup.id}>
+                {headerGroup.headers.map((header) => (
+                  <TableHead key={header.id}>
+                    {header.isPlaceholder ? null : flexRender(header.column.columnDef.header, header.getContext())}
+                  </TableHead>
+                ))}
+              </TableRow>
+            ))}
+          </TableHeader>
+          <TableBody>
+            {table.getRowModel().rows.length > 0 ? (
+              table.getRowModel().rows.map((row) => (
+                <TableRow key={row.id} data-state={row.getIsSelected() ? "selected" : undefined}>
+                  {row.getVisibleCells().map((cell) => (
+                    <TableCell key={cell.id}>{flexRender(cell.column.columnDef.cell, cell.getContext())}</TableCell>
+                  ))}
+                </TableRow>
+              ))
+            ) : (
+              <TableRow>
+                <TableCell colSpan={columns.length} className="h-24 text-center">
+                  No results.
+                </TableCell>
+              </TableRow>
+            )}
+          </TableBody>
+        </Table>
+      </div>
+
+      <div className="flex flex-wrap items-center justify-between gap-2 text-sm text-muted-foreground">
+        <span>
+          {selectedRows.length} of {data.length} row(s) selected · {total} total
+        </span>
+        <div className="flex items-center gap-2">
+          <select
+            value={query.pageSize}
+            onChange={(event) => updateSearchParams({ pageSize: event.target.value, page: undefined })}
+            className="rounded-md border bg-background px-2 py-1"
+          >
+            {PAGE_SIZES.map((size) => (
+              <option key={size} value={size}>
+                {size} / page
+              </option>
+            ))}
+          </select>
+          <span>
+            Page {query.page} of {pageCount}
+          </span>
+          <Button
+            variant="outline"
+            size="sm"
+            disabled={query.page <= 1}
+            onClick={() => updateSearchParams({ page: query.page - 1 })}
+          >
+            Previous
+          </Button>
+          <Button
+            variant="outline"
+            size="sm"
+            disabled={query.page >= pageCount}
+            onClick={() => updateSearchParams({ page: query.page + 1 })}
+          >
+            Next
+          </Button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export function DataTableColumnHeader<TData, TValue>({
+  column,
+  title,
+}: {
+  column: Column<TData, TValue>;
+  title: string;
+}) {
+  if (!column.getCanSort()) {
+    return <span>{title}</span>;
+  }
+
+  const sorted = column.getIsSorted();
+  return (
+    <Button variant="ghost" size="sm" className="-ml-3 h-8" onClick={() => column.toggleSorting(sorted === "asc")}>
+      {title}
+      {sorted === "asc" ? <ArrowUp /> : sorted === "desc" ? <ArrowDown /> : <ArrowUpDown />}
+    </Button>
+  );
+}
+"#;
+
+pub const DRIZZLE_USERS_LOADER: &str = r#"import { asc, count, desc, ilike, or } from "drizzle-orm";
+import { db } from "@/db";
+import { usersTable } from "@/db/schema";
+import type { TablePage, TableQuery } from "@/lib/data-table";
+
+export type { User } from "@/db/schema";
+
+const SORTABLE_COLUMNS = {
+  id: usersTable.id,
+  name: usersTable.name,
+  email: usersTable.email,
+  createdAt: usersTable.createdAt,
+};
+
+function isSortable(column: string | undefined): column is keyof typeof SORTABLE_COLUMNS {
+  return column !== undefined && column in SORTABLE_COLUMNS;
+}
+
+export async function getUsers(query: TableQuery): Promise<TablePage<typeof usersTable.$inferSelect>> {
+  const where = query.q
+    ? or(ilike(usersTable.name, `%${query.q}%`), ilike(usersTable.email, `%${query.q}%`))
+    : undefined;
+  const sortColumn = isSortable(query.sort) ? SORTABLE_COLUMNS[query.sort] : usersTable.id;
+
+  const [rows, totals] = await Promise.all([
+    db
+      .select()
+      .from(usersTable)
+      .where(where)
+      .orderBy(query.order === "desc" ? desc(sortColumn) : asc(sortColumn))
+      .limit(query.pageSize)
+      .offset((query.page - 1) * query.pageSize),
+    db.select({ total: count() }).from(usersTable).where(where),
+  ]);
+
+  return { rows, total: totals[0]?.total ?? 0 };
+}
+"#;
+
+const MOCK_USERS_LOADER: &str = r#"import type { TablePage, TableQuery } from "@/lib/data-table";
+
+export type User = {
+  id: number;
+  name: string;
+  email: string;
+  createdAt: Date;
+};
+
+// Mock data until a database feature is installed (e.g. `nstack add --feature drizzle`)
+const FIRST_NAMES = ["Ada", "Grace", "Alan", "Linus", "Margaret", "Ken", "Barbara", "Dennis", "Radia", "Guido"];
+const LAST_NAMES = ["Lovelace", "Hopper", "Turing", "Torvalds", "Hamilton", "Thompson", "Liskov", "Ritchie", "Perlman", "van Rossum"];
+
+const USERS: User[] = Array.from({ length: 87 }, (_, index) => {
+  const firstName = FIRST_NAMES[index % FIRST_NAMES.length] ?? "User";
+  const lastName = LAST_NAMES[Math.floor(index / FIRST_NAMES.length) % LAST_NAMES.length] ?? `${index}`;
+  return {
+    id: index + 1,
+    name: `${firstName} ${lastName}`,
+    email: `${firstName}.${lastName}${index}@example.com`.toLowerCase().replace(/\s/g, ""),
+    createdAt: new Date(Date.UTC(2024, 0, 1 + index * 3)),
+  };
+});
+
+const SORTABLE_COLUMNS = ["id", "name", "email", "createdAt"] as const;
+
+function isSortable(column: string | undefined): column is (typeof SORTABLE_COLUMNS)[number] {
+  return SORTABLE_COLUMNS.some((sortable) => sortable === column);
+}
+
+export async function getUsers(query: TableQuery): Promise<TablePage<User>> {
+  const search = query.q.toLowerCase();
+  const filtered = search
+    ? USERS.filter((user) => user.name.toLowerCase().includes(search) || user.email.includes(search))
+    : USERS;
+
+  const sortKey = isSortable(query.sort) ? query.sort : "id";
+  const direction = query.order === "desc" ? -1 : 1;
+  const sorted = [...filtered].sort((a, b) => (a[sortKey] > b[sortKey] ? direction : a[sortKey] < b[sortKey] ? -direction : 0));
+
+  const start = (query.page - 1) * query.pageSize;
+  return { rows: sorted.slice(start, start + query.pageSize), total: filtered.length };
+}
+"#;
+
+pub const USER_COLUMNS: &str = r#""use client";
+
+import type { ColumnDef } from "@tanstack/react-table";
+import { DataTableColumnHeader } from "@/components/data-table/data-table";
+import { Checkbox } from "@/components/ui/checkbox";
+import type { User } from "@/lib/data/users";
+
+export const columns: ColumnDef<User>[] = [
+  {
+    id: "select",
+    header: ({ table }) => (
+      <Checkbox
+        checked={table.getIsAllPageRowsSelected() || (table.getIsSomePageRowsSelected() && "indeterminate")}
+        onCheckedChange={(value) => table.toggleAllPageRowsSelected(!!value)}
+        aria-label="Select all"
+      />
+    ),
+    cell: ({ row }) => (
+      <Checkbox
+        checked={row.getIsSelected()}
+        onCheckedChange={(value) => row.toggleSelected(!!value)}
+        aria-label="Select row"
+      />
+    ),
+    enableSorting: false,
+    enableHiding: false,
+  },
+  {
+    accessorKey: "id",
+    header: ({ column }) => <DataTableColumnHeader column={column} title="ID" />,
+  },
+  {
+    accessorKey: "name",
+    header: ({ column }) => <DataTableColumnHeader column={column} title="Name" />,
+  },
+  {
+    accessorKey: "email",
+    header: ({ column }) => <DataTableColumnHeader column={column} title="Email" />,
+  },
+  {
+    accessorKey: "createdAt",
+    header: ({ column }) => <DataTableColumnHeader column={column} title="Created" />,
+    cell: ({ row }) => new Date(row.original.createdAt).toLocaleDateString(),
+  },
+];
+"#;
+
+pub const USERS_TABLE: &str = r#""use client";
+
+import { DataTable } from "@/components/data-table/data-table";
+import type { TableQuery } from "@/lib/data-table";
+import type { User } from "@/lib/data/users";
+import { columns } from "./columns";
+
+// Column definitions contain functions, so they are bound here on the client
+export function UsersTable({ data, total, query }: { data: User[]; total: number; query: TableQuery }) {
+  return (
+    <DataTable
+      columns={columns}
+      data={data}
+      total={total}
+      query={query}
+      searchPlaceholder="Search by name or email..."
+      exportFileName="users"
+    />
+  );
+}
+"#;
+
+const USERS_PAGE: &str = r#"import { getUsers } from "@/lib/data/users";
+import { parseTableQuery, type TableSearchParams } from "@/lib/data-table";
+import { UsersTable } from "./users-table";
+
+export default async function UsersPage({ searchParams }: { searchParams: Promise<TableSearchParams> }) {
+  const query = parseTableQuery(await searchParams);
+  const { rows, total } = await getUsers(query);
+
+  return (
+    <main className="container mx-auto flex flex-col gap-6 px-4 py-10">
+      <h1 className="text-2xl font-semibold">Users</h1>
+      <UsersTable data={rows} total={total} query={query} />
+    </main>
+  );
+}
+"#;
+
+pub async fn add_data_table() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    if !project_file("components.json").exists() {
+        println!(
+            "{}",
+            style("The data table is built on shadcn/ui. Run `nstack add --feature shadcn` first.").yellow()
+        );
+        return Ok(());
+    }
+
+    // The drizzle users table, unless the project uses a schema without one (e.g. Nile)
+    let schema_path = project_structure.get_db_path().join("schema.ts");
+    let has_users_table = std::fs::read_to_string(&schema_path)
+        .map(|schema| schema.contains("usersTable"))
+        .unwrap_or(false);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing TanStack Table...");
+
+    package_manager.install(&["@tanstack/react-table"], false)?;
+
+    pb.set_message("Adding shadcn/ui table primitives...");
+
+    package_manager.dlx(&["shadcn@latest", "add", "table", "checkbox", "button", "input", "--yes"])?;
+
+    pb.set_message("Creating data table components...");
+
+    let lib_path = project_structure.get_lib_path();
+    change_set::create_dir_all(lib_path.join("data")).context("Failed to create lib/data directory")?;
+    file_writer::write(lib_path.join("data-table.ts"), TABLE_QUERY)
+        .context("Failed to create data-table.ts")?;
+    file_writer::write(lib_path.join("csv.ts"), CSV_HELPERS)
+        .context("Failed to create csv.ts")?;
+    file_writer::write(
+        lib_path.join("data/users.ts"),
+        if has_users_table { DRIZZLE_USERS_LOADER } else { MOCK_USERS_LOADER },
+    )
+    .context("Failed to create users.ts")?;
+
+    let data_table_path = project_structure.get_components_path().join("data-table");
+    change_set::create_dir_all(&data_table_path).context("Failed to create data-table directory")?;
+    file_writer::write(data_table_path.join("data-table.tsx"), DATA_TABLE)
+        .context("Failed to create data-table.tsx")?;
+
+    pb.set_message("Creating the users page...");
+
+    let page_path = root_layout::routes_dir(&project_structure).join("users");
+    change_set::create_dir_all(&page_path).context("Failed to create users route directory")?;
+    file_writer::write(page_path.join("columns.tsx"), USER_COLUMNS)
+        .context("Failed to create columns.tsx")?;
+    file_writer::write(page_path.join("users-table.tsx"), USERS_TABLE)
+        .context("Failed to create users-table.tsx")?;
+    file_writer::write(page_path.join("page.tsx"), USERS_PAGE)
+        .context("Failed to create page.tsx")?;
+
+    pb.finish_with_message("Data table created successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/data-table.tsx - Reusable server-driven table with selection and CSV export", data_table_path.display());
+    println!("• {}/data-table.ts - Search param parsing for pagination, sorting and filtering", lib_path.display());
+    println!("• {}/csv.ts - CSV export helpers", lib_path.display());
+    println!(
+        "• {}/data/users.ts - Users loader ({})",
+        lib_path.display(),
+        if has_users_table { "drizzle usersTable" } else { "mock data" }
+    );
+    println!("• {}/ - Users page and column definitions", page_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Start the dev server and open /users");
+    println!("2. Copy the users route as a starting point for your own tables");
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::file_writer;
+use crate::features::drizzle;
+use crate::manifest::ProjectManifest;
+use crate::next_config;
+use crate::package_manager::PackageManager;
+use crate::prompt;
+use crate::project_structure::project_file;
+
+const DOCKERIGNORE: &str = r#"Dockerfile
+.dockerignore
+docker-compose.yml
+node_modules
+npm-debug.log*
+yarn-debug.log*
+yarn-error.log*
+.pnpm-debug.log*
+.next
+out
+build
+coverage
+.git
+.gitignore
+.env*.local
+.vercel
+.nstack
+README.md
+"#;
+
+fn base_setup(package_manager: &PackageManager) -> &'static str {
+    match package_manager {
+        PackageManager::Npm => "",
+        PackageManager::Yarn | PackageManager::Pnpm => "RUN corepack enable\n",
+        PackageManager::Bun => "RUN npm install -g bun\n",
+        PackageManager::Deno => "COPY --from=denoland/deno:bin /deno /usr/local/bin/deno\n",
+    }
+}
+
+fn dockerfile(package_manager: &PackageManager) -> String {
+    format!(
+        r#"# syntax=docker/dockerfile:1
+
+FROM node:20-alpine AS base
+RUN apk add --no-cache libc6-compat
+WORKDIR /app
+{base_setup}
+# Install dependencies only when the lockfile changes
+FROM base AS deps
+COPY package.json {lockfile}* ./
+RUN {install}
+
+# Build the application
+FROM base AS builder
+COPY --from=deps /app/node_modules ./node_modules
+COPY . .
+# The runner copies public/, which projects without static assets do not have
+RUN mkdir -p public
+ENV NEXT_TELEMETRY_DISABLED=1
+RUN {build}
+
+# Production image with only the standalone output
+FROM base AS runner
+ENV NODE_ENV=production
+ENV NEXT_TELEMETRY_DISABLED=1
+
+RUN addgroup --system --gid 1001 nodejs \
+    && adduser --system --uid 1001 nextjs
+
+COPY --from=builder /app/public ./public
+COPY --from=builder --chown=nextjs:nodejs /app/.next/standalone ./
+COPY --from=builder --chown=nextjs:nodejs /app/.next/static ./.next/static
+
+USER nextjs
+
+EXPOSE 3000
+ENV PORT=3000
+ENV HOSTNAME="0.0.0.0"
+
+CMD ["node", "server.js"]
+"#,
+        base_setup = base_setup(package_manager),
+        lockfile = package_manager.lockfile(),
+        install = package_manager.frozen_install_command(),
+        build = package_manager.run_script("build"),
+    )
+}
+
+// `.env` is optional so `docker compose up` works before one exists; a missing
+// required env file aborts compose.
+fn docker_compose(include_postgres: bool) -> String {
+    if !include_postgres {
+        return r#"services:
+  app:
+    build: .
+    ports:
+      - "3000:3000"
+    env_file:
+      - path: .env
+        required: false
+    restart: unless-stopped
+"#
+        .to_string();
+    }
+
+    format!(
+        r#"services:
+  app:
+    build: .
+    ports:
+      - "3000:3000"
+    env_file:
+      - path: .env
+        required: false
+    environment:
+      {env_var}: postgresql://postgres:postgres@db:5432/app
+    depends_on:
+      db:
+        condition: service_healthy
+    restart: unless-stopped
+
+  db:
+    image: postgres:16-alpine
+    environment:
+      POSTGRES_USER: postgres
+      POSTGRES_PASSWORD: postgres
+      POSTGRES_DB: app
+    ports:
+      - "5432:5432"
+    volumes:
+      - postgres-data:/var/lib/postgresql/data
+    healthcheck:
+      test: ["CMD-SHELL", "pg_isready -U postgres -d app"]
+      interval: 5s
+      timeout: 5s
+      retries: 10
+
+volumes:
+  postgres-data:
+"#,
+        env_var = drizzle::installed_env_variable(),
+    )
+}
+
+pub async fn add_docker() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let generate_compose = prompt::confirm("compose", "Generate docker-compose.yml?", true)?;
+
+    let drizzle_installed = ProjectManifest::is_feature_installed("drizzle");
+    let include_postgres = generate_compose
+        && drizzle_installed
+        && prompt::confirm("postgres", "Drizzle detected. Add a Postgres service to docker-compose.yml?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Enabling standalone output in next.config...");
+
+    // The runner stage copies .next/standalone, which only exists with this option
+    let standalone_added = next_config::set_next_config_option("output", "'standalone'")?;
+
+    pb.set_message("Creating Dockerfile...");
+
+    file_writer::write(project_file("Dockerfile"), dockerfile(&package_manager))
+        .context("Failed to create Dockerfile")?;
+    file_writer::write(project_file(".dockerignore"), DOCKERIGNORE)
+        .context("Failed to create .dockerignore")?;
+
+    if generate_compose {
+        pb.set_message("Creating docker-compose.yml...");
+        file_writer::write(project_file("docker-compose.yml"), docker_compose(include_postgres))
+            .context("Failed to create docker-compose.yml")?;
+    }
+
+    pb.finish_with_message("Docker setup completed!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• Dockerfile - Multi-stage build for {}", package_manager.to_string());
+    println!("• .dockerignore - Files excluded from the build context");
+    if generate_compose {
+        if include_postgres {
+            println!("• docker-compose.yml - App and Postgres services");
+        } else {
+            println!("• docker-compose.yml - App service");
+        }
+    }
+    if standalone_added {
+        println!("• next.config - Set output: 'standalone'");
+    } else {
+        println!(
+            "{}",
+            style("• next.config already defines `output`; make sure it is 'standalone'").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    if generate_compose {
+        println!("1. Start everything with: docker compose up --build");
+    } else {
+        println!("1. Build the image with: docker build -t my-app .");
+        println!("2. Run it with: docker run -p 3000:3000 --env-file .env my-app");
+    }
+
+    Ok(())
+}
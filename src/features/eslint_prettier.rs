@@ -0,0 +1,227 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_profile::{ESLINT_FLAT_CONFIGS, ESLINT_LEGACY_CONFIGS, ProjectProfile};
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+use crate::tailwind::TailwindVersion;
+
+fn eslint_config(tailwind_plugin: bool) -> String {
+    let (tailwind_import, tailwind_config) = if tailwind_plugin {
+        (
+            "import tailwind from \"eslint-plugin-tailwindcss\";\n",
+            "  ...tailwind.configs[\"flat/recommended\"],\n",
+        )
+    } else {
+        ("", "")
+    };
+
+    format!(
+        r#"import {{ dirname }} from "node:path";
+import {{ fileURLToPath }} from "node:url";
+import {{ FlatCompat }} from "@eslint/eslintrc";
+import prettier from "eslint-config-prettier";
+import simpleImportSort from "eslint-plugin-simple-import-sort";
+{}import tseslint from "typescript-eslint";
+
+const __dirname = dirname(fileURLToPath(import.meta.url));
+const compat = new FlatCompat({{ baseDirectory: __dirname }});
+
+export default tseslint.config(
+  {{
+    ignores: [".next/**", "out/**", "build/**", "coverage/**", "next-env.d.ts"],
+  }},
+  ...compat.extends("next/core-web-vitals"),
+  ...tseslint.configs.strictTypeChecked,
+  ...tseslint.configs.stylisticTypeChecked,
+{}  {{
+    languageOptions: {{
+      parserOptions: {{
+        projectService: true,
+        tsconfigRootDir: __dirname,
+      }},
+    }},
+    plugins: {{
+      "simple-import-sort": simpleImportSort,
+    }},
+    rules: {{
+      "simple-import-sort/imports": "error",
+      "simple-import-sort/exports": "error",
+      "@typescript-eslint/consistent-type-imports": ["error", {{ fixStyle: "inline-type-imports" }}],
+      "@typescript-eslint/no-unused-vars": ["error", {{ argsIgnorePattern: "^_", varsIgnorePattern: "^_" }}],
+      // Allow async handlers in JSX attributes like onClick
+      "@typescript-eslint/no-misused-promises": ["error", {{ checksVoidReturn: {{ attributes: false }} }}],
+      "@typescript-eslint/restrict-template-expressions": ["error", {{ allowNumber: true }}],
+      eqeqeq: ["error", "smart"],
+      "no-console": ["warn", {{ allow: ["warn", "error"] }}],
+    }},
+  }},
+  {{
+    files: ["**/*.{{js,mjs,cjs}}"],
+    ...tseslint.configs.disableTypeChecked,
+  }},
+  // Must come last so formatting rules never fight prettier
+  prettier,
+);
+"#,
+        tailwind_import, tailwind_config
+    )
+}
+
+fn prettier_config(tailwind: bool, tailwind_stylesheet: Option<&str>) -> String {
+    let mut config = serde_json::json!({
+        "semi": true,
+        "singleQuote": false,
+        "trailingComma": "all",
+        "printWidth": 100,
+        "tabWidth": 2,
+    });
+
+    if tailwind {
+        config["plugins"] = serde_json::json!(["prettier-plugin-tailwindcss"]);
+        // Tailwind v4 has no tailwind.config, so the plugin reads the theme from the stylesheet
+        if let Some(stylesheet) = tailwind_stylesheet {
+            config["tailwindStylesheet"] = serde_json::json!(format!("./{}", stylesheet));
+        }
+        config["tailwindFunctions"] = serde_json::json!(["cn", "cva"]);
+    }
+
+    serde_json::to_string_pretty(&config).unwrap_or_default() + "\n"
+}
+
+const PRETTIER_IGNORE: &str = r#".next
+out
+build
+coverage
+node_modules
+pnpm-lock.yaml
+package-lock.json
+yarn.lock
+bun.lock
+deno.lock
+"#;
+
+pub async fn add_eslint_prettier() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let profile = ProjectProfile::detect();
+    if profile.biome {
+        let keep_biome = prompt::confirm(
+            "keep_biome",
+            "This project lints and formats with Biome. Keep Biome instead of adding ESLint and Prettier?",
+            true,
+        )?;
+        if keep_biome {
+            println!("{}", style("Keeping Biome; run `nstack add --feature biome` to update its config").yellow());
+            return Ok(());
+        }
+    }
+
+    // Every config the flat eslint.config.mjs replaces
+    let existing_configs: Vec<&str> = ESLINT_LEGACY_CONFIGS
+        .into_iter()
+        .chain(ESLINT_FLAT_CONFIGS)
+        .filter(|config| project_file(config).exists())
+        .collect();
+
+    if !existing_configs.is_empty() {
+        let replace = prompt::confirm(
+            "replace_config",
+            &format!(
+                "Replace the existing ESLint config ({}) with the strict config?",
+                existing_configs.join(", ")
+            ),
+            true,
+        )?;
+
+        if !replace {
+            println!("{}", style("Keeping the existing ESLint config").yellow());
+            return Ok(());
+        }
+    }
+
+    let tailwind_version = TailwindVersion::detect(&project_structure);
+    let tailwind = tailwind_version.is_some();
+    // eslint-plugin-tailwindcss only supports Tailwind v3
+    let tailwind_eslint_plugin = tailwind_version == Some(TailwindVersion::V3);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing ESLint and Prettier...");
+
+    let mut dev_dependencies = vec![
+        "eslint@^9",
+        "@eslint/eslintrc",
+        "eslint-config-next",
+        "typescript-eslint",
+        "eslint-plugin-simple-import-sort",
+        "eslint-config-prettier",
+        "prettier",
+    ];
+    if tailwind {
+        dev_dependencies.push("prettier-plugin-tailwindcss");
+    }
+    if tailwind_eslint_plugin {
+        dev_dependencies.push("eslint-plugin-tailwindcss");
+    }
+    package_manager.install(&dev_dependencies, true)?;
+
+    pb.set_message("Writing eslint.config.mjs...");
+
+    for config in existing_configs.iter().filter(|config| **config != "eslint.config.mjs") {
+        change_set::remove_file(project_file(config)).context(format!("Failed to remove {}", config))?;
+    }
+    file_writer::write(project_file("eslint.config.mjs"), eslint_config(tailwind_eslint_plugin))
+        .context("Failed to create eslint.config.mjs")?;
+
+    pb.set_message("Writing Prettier config...");
+
+    let tailwind_stylesheet = (tailwind_version == Some(TailwindVersion::V4)).then(|| project_structure.relative_path(&project_structure.get_globals_css_path()));
+    file_writer::write(project_file(".prettierrc.json"), prettier_config(tailwind, tailwind_stylesheet.as_deref()))
+        .context("Failed to create .prettierrc.json")?;
+    file_writer::write(project_file(".prettierignore"), PRETTIER_IGNORE)
+        .context("Failed to create .prettierignore")?;
+
+    pb.set_message("Updating package.json scripts...");
+
+    // `next lint` is deprecated in favour of running the ESLint CLI directly
+    package_json::set_scripts(&[
+        ("lint", "eslint ."),
+        ("lint:fix", "eslint . --fix"),
+        ("format", "prettier --write ."),
+        ("format:check", "prettier --check ."),
+    ])?;
+
+    pb.finish_with_message("ESLint and Prettier configured!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• eslint.config.mjs - Flat config with typescript-eslint strict rules and import sorting");
+    if tailwind_eslint_plugin {
+        println!("• eslint.config.mjs - Tailwind class name checks");
+    }
+    println!(
+        "• .prettierrc.json - Shared formatting{}",
+        if tailwind { " with Tailwind class sorting" } else { "" }
+    );
+    println!("• .prettierignore - Skips build output and lockfiles");
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Format the codebase: {}", package_manager.run_script("format"));
+    println!("2. Auto-fix lint issues: {}", package_manager.run_script("lint:fix"));
+    println!("3. Review remaining strict-mode errors: {}", package_manager.run_script("lint"));
+
+    Ok(())
+}
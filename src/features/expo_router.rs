@@ -0,0 +1,222 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::Value;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::project_file;
+
+const ROOT_LAYOUT: &str = r#"import { Stack } from "expo-router";
+import { StatusBar } from "expo-status-bar";
+
+export default function RootLayout() {
+  return (
+    <>
+      <Stack>
+        <Stack.Screen name="(tabs)" options={{ headerShown: false }} />
+        <Stack.Screen name="modal" options={{ presentation: "modal", title: "Details" }} />
+      </Stack>
+      <StatusBar style="auto" />
+    </>
+  );
+}
+"#;
+
+const TABS_LAYOUT: &str = r#"import { Tabs } from "expo-router";
+
+export default function TabsLayout() {
+  return (
+    <Tabs screenOptions={{ headerShown: true }}>
+      <Tabs.Screen name="index" options={{ title: "Home" }} />
+      <Tabs.Screen name="settings" options={{ title: "Settings" }} />
+    </Tabs>
+  );
+}
+"#;
+
+const HOME_SCREEN: &str = r##"import { Link } from "expo-router";
+import { StyleSheet, Text, View } from "react-native";
+
+export default function HomeScreen() {
+  return (
+    <View style={styles.container}>
+      <Text style={styles.title}>Home</Text>
+      <Link href="/modal" style={styles.link}>
+        Open details
+      </Link>
+    </View>
+  );
+}
+
+const styles = StyleSheet.create({
+  container: { flex: 1, alignItems: "center", justifyContent: "center", gap: 12 },
+  title: { fontSize: 24, fontWeight: "600" },
+  link: { color: "#2563eb" },
+});
+"##;
+
+const SETTINGS_SCREEN: &str = r#"import { StyleSheet, Text, View } from "react-native";
+
+export default function SettingsScreen() {
+  return (
+    <View style={styles.container}>
+      <Text style={styles.title}>Settings</Text>
+    </View>
+  );
+}
+
+const styles = StyleSheet.create({
+  container: { flex: 1, alignItems: "center", justifyContent: "center" },
+  title: { fontSize: 24, fontWeight: "600" },
+});
+"#;
+
+const MODAL_SCREEN: &str = r##"import { router } from "expo-router";
+import { Pressable, StyleSheet, Text, View } from "react-native";
+
+export default function ModalScreen() {
+  return (
+    <View style={styles.container}>
+      <Text style={styles.title}>Details</Text>
+      <Pressable onPress={() => router.back()}>
+        <Text style={styles.link}>Close</Text>
+      </Pressable>
+    </View>
+  );
+}
+
+const styles = StyleSheet.create({
+  container: { flex: 1, alignItems: "center", justifyContent: "center", gap: 12 },
+  title: { fontSize: 20, fontWeight: "600" },
+  link: { color: "#2563eb" },
+});
+"##;
+
+const NOT_FOUND_SCREEN: &str = r##"import { Link, Stack } from "expo-router";
+import { StyleSheet, Text, View } from "react-native";
+
+export default function NotFoundScreen() {
+  return (
+    <>
+      <Stack.Screen options={{ title: "Not found" }} />
+      <View style={styles.container}>
+        <Text style={styles.title}>This screen does not exist.</Text>
+        <Link href="/" style={styles.link}>
+          Go to the home screen
+        </Link>
+      </View>
+    </>
+  );
+}
+
+const styles = StyleSheet.create({
+  container: { flex: 1, alignItems: "center", justifyContent: "center", gap: 12 },
+  title: { fontSize: 18, fontWeight: "600" },
+  link: { color: "#2563eb" },
+});
+"##;
+
+/// Points package.json at the Expo Router entry so the app directory is the route tree.
+fn set_entry_point() -> Result<()> {
+    let mut package_json = package_json::read()?;
+    if package_json["main"].as_str() == Some("expo-router/entry") {
+        return Ok(());
+    }
+    package_json["main"] = Value::String("expo-router/entry".to_string());
+    package_json::write(&package_json)
+}
+
+/// Adds a deep link scheme to app.json, which Expo Router needs for linking. Returns the
+/// scheme when one was added.
+fn add_scheme() -> Result<Option<String>> {
+    if !project_file("app.json").exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(project_file("app.json")).context("Failed to read app.json")?;
+    let mut app_json: Value = serde_json::from_str(&content).context("Failed to parse app.json")?;
+    if !app_json["expo"].is_object() || app_json["expo"].get("scheme").is_some() {
+        return Ok(None);
+    }
+
+    let scheme: String = package_json::package_name()
+        .unwrap_or_else(|| "app".to_string())
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect::<String>()
+        .to_lowercase();
+    app_json["expo"]["scheme"] = Value::String(scheme.clone());
+    change_set::write(project_file("app.json"), serde_json::to_string_pretty(&app_json)? + "\n")
+        .context("Failed to update app.json")?;
+    Ok(Some(scheme))
+}
+
+pub async fn add_expo_router() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Expo Router...");
+
+    // `expo install` picks the versions matching the project's Expo SDK
+    package_manager
+        .exec(&[
+            "expo",
+            "install",
+            "expo-router",
+            "react-native-safe-area-context",
+            "react-native-screens",
+            "expo-linking",
+            "expo-constants",
+            "expo-status-bar",
+        ])
+        .context("Failed to install Expo Router")?;
+
+    pb.set_message("Creating routes...");
+
+    change_set::create_dir_all(project_file("app/(tabs)")).context("Failed to create app/(tabs) directory")?;
+    file_writer::write(project_file("app/_layout.tsx"), ROOT_LAYOUT)
+        .context("Failed to create app/_layout.tsx")?;
+    file_writer::write(project_file("app/(tabs)/_layout.tsx"), TABS_LAYOUT)
+        .context("Failed to create app/(tabs)/_layout.tsx")?;
+    file_writer::write(project_file("app/(tabs)/index.tsx"), HOME_SCREEN)
+        .context("Failed to create app/(tabs)/index.tsx")?;
+    file_writer::write(project_file("app/(tabs)/settings.tsx"), SETTINGS_SCREEN)
+        .context("Failed to create app/(tabs)/settings.tsx")?;
+    file_writer::write(project_file("app/modal.tsx"), MODAL_SCREEN)
+        .context("Failed to create app/modal.tsx")?;
+    file_writer::write(project_file("app/+not-found.tsx"), NOT_FOUND_SCREEN)
+        .context("Failed to create app/+not-found.tsx")?;
+
+    pb.set_message("Configuring the entry point...");
+    set_entry_point()?;
+    let scheme = add_scheme()?;
+
+    pb.finish_with_message("Expo Router set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• app/_layout.tsx - Root stack with a modal screen");
+    println!("• app/(tabs)/_layout.tsx - Tab navigator");
+    println!("• app/(tabs)/index.tsx and settings.tsx - Tab screens");
+    println!("• app/modal.tsx - Modal presented over the tabs");
+    println!("• app/+not-found.tsx - Fallback for unknown routes");
+    println!("• package.json - main set to expo-router/entry");
+    if let Some(scheme) = scheme {
+        println!("• app.json - deep link scheme \"{}\"", scheme);
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("• Remove App.tsx if it is still there; routes now live in app/");
+    println!("• Start the app: {}", package_manager.exec_command("expo start"));
+
+    Ok(())
+}
@@ -0,0 +1,480 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::json;
+use crate::change_set;
+use crate::file_writer;
+use crate::env_file;
+use crate::gitignore;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::root_layout;
+
+const CLIENT_SDK: &str = r#"import { getApp, getApps, initializeApp } from "firebase/app";
+import { connectAuthEmulator, getAuth } from "firebase/auth";
+import { connectFirestoreEmulator, getFirestore } from "firebase/firestore";
+import { connectStorageEmulator, getStorage } from "firebase/storage";
+
+const firebaseConfig = {
+  apiKey: process.env.NEXT_PUBLIC_FIREBASE_API_KEY,
+  authDomain: process.env.NEXT_PUBLIC_FIREBASE_AUTH_DOMAIN,
+  projectId: process.env.NEXT_PUBLIC_FIREBASE_PROJECT_ID,
+  storageBucket: process.env.NEXT_PUBLIC_FIREBASE_STORAGE_BUCKET,
+  appId: process.env.NEXT_PUBLIC_FIREBASE_APP_ID,
+};
+
+// Reuse the app across hot reloads
+const isNewApp = getApps().length === 0;
+export const app = isNewApp ? initializeApp(firebaseConfig) : getApp();
+export const auth = getAuth(app);
+export const db = getFirestore(app);
+export const storage = getStorage(app);
+
+if (isNewApp && process.env.NEXT_PUBLIC_USE_FIREBASE_EMULATORS === "true") {
+  connectAuthEmulator(auth, "http://127.0.0.1:9099", { disableWarnings: true });
+  connectFirestoreEmulator(db, "127.0.0.1", 8080);
+  connectStorageEmulator(storage, "127.0.0.1", 9199);
+}
+"#;
+
+const ADMIN_SDK: &str = r#"import "server-only";
+import { cert, getApp, getApps, initializeApp } from "firebase-admin/app";
+import { getAuth } from "firebase-admin/auth";
+import { getFirestore } from "firebase-admin/firestore";
+import { getStorage } from "firebase-admin/storage";
+
+const projectId = process.env.NEXT_PUBLIC_FIREBASE_PROJECT_ID;
+const useEmulators = process.env.NEXT_PUBLIC_USE_FIREBASE_EMULATORS === "true";
+
+if (useEmulators) {
+  // The Admin SDK picks emulators up from these variables and needs no credentials
+  process.env.FIREBASE_AUTH_EMULATOR_HOST ??= "127.0.0.1:9099";
+  process.env.FIRESTORE_EMULATOR_HOST ??= "127.0.0.1:8080";
+  process.env.FIREBASE_STORAGE_EMULATOR_HOST ??= "127.0.0.1:9199";
+}
+
+function createAdminApp() {
+  if (useEmulators) {
+    return initializeApp({ projectId });
+  }
+
+  return initializeApp({
+    credential: cert({
+      projectId,
+      clientEmail: process.env.FIREBASE_CLIENT_EMAIL,
+      // Private keys are stored with escaped newlines in .env
+      privateKey: process.env.FIREBASE_PRIVATE_KEY?.replace(/\\n/g, "\n"),
+    }),
+    storageBucket: process.env.NEXT_PUBLIC_FIREBASE_STORAGE_BUCKET,
+  });
+}
+
+export const adminApp = getApps().length === 0 ? createAdminApp() : getApp();
+export const adminAuth = getAuth(adminApp);
+export const adminDb = getFirestore(adminApp);
+export const adminStorage = getStorage(adminApp);
+"#;
+
+const CONVERTERS: &str = r#"import {
+  collection,
+  type DocumentData,
+  type FirestoreDataConverter,
+  type QueryDocumentSnapshot,
+  type SnapshotOptions,
+  type Timestamp,
+  type WithFieldValue,
+} from "firebase/firestore";
+import { db } from "./client";
+
+/**
+ * Converter that attaches the document id on read and strips it on write, so collections
+ * are typed end to end: `collection(db, "posts").withConverter(converter<Post>())`.
+ */
+export function converter<T extends { id: string }>(): FirestoreDataConverter<T> {
+  return {
+    toFirestore(data: WithFieldValue<T>): DocumentData {
+      const { id: _id, ...rest } = data as WithFieldValue<T> & { id?: string };
+      return rest;
+    },
+    fromFirestore(snapshot: QueryDocumentSnapshot, options?: SnapshotOptions): T {
+      return { ...snapshot.data(options), id: snapshot.id } as T;
+    },
+  };
+}
+
+export type Post = {
+  id: string;
+  title: string;
+  body: string;
+  authorId: string;
+  createdAt: Timestamp;
+};
+
+export const postsCollection = collection(db, "posts").withConverter(converter<Post>());
+"#;
+
+const STORAGE_HELPERS: &str = r#"import { deleteObject, getDownloadURL, ref, uploadBytes } from "firebase/storage";
+import { storage } from "./client";
+
+/**
+ * Uploads under `users/<uid>/`, the folder storage.rules lets each user write to.
+ * Returns the object path and its download URL.
+ */
+export async function uploadFile(uid: string, file: File) {
+  const extension = file.name.includes(".") ? `.${file.name.split(".").pop()}` : "";
+  const path = `users/${uid}/${crypto.randomUUID()}${extension}`;
+
+  const snapshot = await uploadBytes(ref(storage, path), file, { contentType: file.type });
+  return { path, url: await getDownloadURL(snapshot.ref) };
+}
+
+export function getFileUrl(path: string) {
+  return getDownloadURL(ref(storage, path));
+}
+
+export function deleteFile(path: string) {
+  return deleteObject(ref(storage, path));
+}
+"#;
+
+const SESSION: &str = r#"import "server-only";
+import { cookies } from "next/headers";
+import { adminAuth } from "./admin";
+
+export const SESSION_COOKIE = "__session";
+const SESSION_DURATION_MS = 5 * 24 * 60 * 60 * 1000;
+
+/** Exchanges a fresh ID token from the client SDK for an httpOnly session cookie */
+export async function createSession(idToken: string) {
+  const sessionCookie = await adminAuth.createSessionCookie(idToken, { expiresIn: SESSION_DURATION_MS });
+  const cookieStore = await cookies();
+
+  cookieStore.set(SESSION_COOKIE, sessionCookie, {
+    maxAge: SESSION_DURATION_MS / 1000,
+    httpOnly: true,
+    secure: process.env.NODE_ENV === "production",
+    sameSite: "lax",
+    path: "/",
+  });
+}
+
+export async function clearSession() {
+  const cookieStore = await cookies();
+  const sessionCookie = cookieStore.get(SESSION_COOKIE)?.value;
+  cookieStore.delete(SESSION_COOKIE);
+
+  if (sessionCookie) {
+    const decoded = await adminAuth.verifySessionCookie(sessionCookie).catch(() => null);
+    if (decoded) {
+      await adminAuth.revokeRefreshTokens(decoded.sub);
+    }
+  }
+}
+
+/** The signed-in user for Server Components, Route Handlers and Server Actions */
+export async function getCurrentUser() {
+  const cookieStore = await cookies();
+  const sessionCookie = cookieStore.get(SESSION_COOKIE)?.value;
+  if (!sessionCookie) return null;
+
+  return adminAuth.verifySessionCookie(sessionCookie, true).catch(() => null);
+}
+"#;
+
+const CLIENT_AUTH: &str = r#"import {
+  createUserWithEmailAndPassword,
+  signInWithEmailAndPassword,
+  signOut as firebaseSignOut,
+  type UserCredential,
+} from "firebase/auth";
+import { auth } from "./client";
+
+async function startSession(credential: UserCredential) {
+  const idToken = await credential.user.getIdToken();
+  const response = await fetch("/api/auth/session", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ idToken }),
+  });
+  if (!response.ok) {
+    throw new Error("Could not start a session");
+  }
+}
+
+export async function signIn(email: string, password: string) {
+  await startSession(await signInWithEmailAndPassword(auth, email, password));
+}
+
+export async function signUp(email: string, password: string) {
+  await startSession(await createUserWithEmailAndPassword(auth, email, password));
+}
+
+// Signs out of both the server session and the client SDK, which Firestore and Storage rules use
+export async function signOut() {
+  await fetch("/api/auth/session", { method: "DELETE" });
+  await firebaseSignOut(auth);
+}
+"#;
+
+const SESSION_ROUTE: &str = r#"import { NextResponse } from "next/server";
+import { clearSession, createSession } from "@/lib/firebase/session";
+
+export async function POST(request: Request) {
+  const { idToken } = (await request.json()) as { idToken?: string };
+  if (!idToken) {
+    return NextResponse.json({ error: "Missing idToken" }, { status: 400 });
+  }
+
+  try {
+    await createSession(idToken);
+    return NextResponse.json({ status: "ok" });
+  } catch {
+    return NextResponse.json({ error: "Invalid or expired ID token" }, { status: 401 });
+  }
+}
+
+export async function DELETE() {
+  await clearSession();
+  return NextResponse.json({ status: "ok" });
+}
+"#;
+
+const LOGIN_PAGE: &str = r#""use client";
+
+import { useRouter } from "next/navigation";
+import { type FormEvent, useState } from "react";
+import { signIn, signUp } from "@/lib/firebase/auth";
+
+export default function LoginPage() {
+  const router = useRouter();
+  const [error, setError] = useState<string | null>(null);
+  const [pending, setPending] = useState(false);
+
+  async function handleSubmit(event: FormEvent<HTMLFormElement>) {
+    event.preventDefault();
+    const submitter = (event.nativeEvent as SubmitEvent).submitter as HTMLButtonElement | null;
+    const form = new FormData(event.currentTarget);
+    const email = String(form.get("email") ?? "");
+    const password = String(form.get("password") ?? "");
+
+    setPending(true);
+    setError(null);
+    try {
+      await (submitter?.value === "signup" ? signUp : signIn)(email, password);
+      router.push("/");
+      router.refresh();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : "Could not sign in");
+    } finally {
+      setPending(false);
+    }
+  }
+
+  return (
+    <main className="mx-auto flex min-h-screen max-w-sm flex-col justify-center gap-6 px-4">
+      <h1 className="text-2xl font-semibold">Sign in</h1>
+      {error ? <p className="text-sm text-red-600">{error}</p> : null}
+
+      <form onSubmit={handleSubmit} className="flex flex-col gap-3">
+        <label className="flex flex-col gap-1 text-sm">
+          Email
+          <input name="email" type="email" autoComplete="email" required className="rounded-md border px-3 py-2" />
+        </label>
+        <label className="flex flex-col gap-1 text-sm">
+          Password
+          <input
+            name="password"
+            type="password"
+            autoComplete="current-password"
+            minLength={6}
+            required
+            className="rounded-md border px-3 py-2"
+          />
+        </label>
+        <div className="flex gap-2">
+          <button
+            type="submit"
+            value="signin"
+            disabled={pending}
+            className="flex-1 rounded-md bg-primary px-3 py-2 text-sm text-primary-foreground disabled:opacity-50"
+          >
+            Sign in
+          </button>
+          <button type="submit" value="signup" disabled={pending} className="flex-1 rounded-md border px-3 py-2 text-sm">
+            Sign up
+          </button>
+        </div>
+      </form>
+    </main>
+  );
+}
+"#;
+
+const FIRESTORE_RULES: &str = r#"rules_version = '2';
+
+service cloud.firestore {
+  match /databases/{database}/documents {
+    // Anyone can read posts; only the author can create, change or delete them
+    match /posts/{postId} {
+      allow read: if true;
+      allow create: if request.auth != null && request.resource.data.authorId == request.auth.uid;
+      allow update, delete: if request.auth != null && resource.data.authorId == request.auth.uid;
+    }
+
+    // Everything else is denied until you add rules for it
+    match /{document=**} {
+      allow read, write: if false;
+    }
+  }
+}
+"#;
+
+const STORAGE_RULES: &str = r#"rules_version = '2';
+
+service firebase.storage {
+  match /b/{bucket}/o {
+    // Users manage files in their own folder; files are readable by signed-in users
+    match /users/{userId}/{allPaths=**} {
+      allow read: if request.auth != null;
+      allow write: if request.auth != null && request.auth.uid == userId
+        && request.resource.size < 10 * 1024 * 1024;
+    }
+  }
+}
+"#;
+
+fn firebase_json() -> serde_json::Value {
+    json!({
+        "firestore": {
+            "rules": "firestore.rules"
+        },
+        "storage": {
+            "rules": "storage.rules"
+        },
+        "emulators": {
+            "auth": { "port": 9099 },
+            "firestore": { "port": 8080 },
+            "storage": { "port": 9199 },
+            "ui": { "enabled": true, "port": 4000 },
+            "singleProjectMode": true
+        }
+    })
+}
+
+pub async fn add_firebase() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    // `demo-` projects run fully offline against the emulators
+    let demo_project_id = format!(
+        "demo-{}",
+        package_json::package_name().unwrap_or_else(|| "app".to_string())
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Firebase SDKs...");
+
+    package_manager.install(&["firebase", "firebase-admin", "server-only"], false)?;
+    package_manager.install(&["firebase-tools"], true)?;
+
+    pb.set_message("Creating Firebase clients...");
+
+    let firebase_lib_path = project_structure.get_lib_path().join("firebase");
+    change_set::create_dir_all(&firebase_lib_path).context("Failed to create lib/firebase directory")?;
+    for (file, content) in [
+        ("client.ts", CLIENT_SDK),
+        ("admin.ts", ADMIN_SDK),
+        ("converters.ts", CONVERTERS),
+        ("storage.ts", STORAGE_HELPERS),
+        ("session.ts", SESSION),
+        ("auth.ts", CLIENT_AUTH),
+    ] {
+        file_writer::write(firebase_lib_path.join(file), content)
+            .context(format!("Failed to create {}", file))?;
+    }
+
+    pb.set_message("Creating auth routes...");
+
+    let session_route_path = project_structure.get_app_path().join("api/auth/session");
+    change_set::create_dir_all(&session_route_path).context("Failed to create api/auth/session directory")?;
+    file_writer::write(session_route_path.join("route.ts"), SESSION_ROUTE)
+        .context("Failed to create the session route")?;
+
+    let login_path = root_layout::routes_dir(&project_structure).join("login");
+    change_set::create_dir_all(&login_path).context("Failed to create login route directory")?;
+    file_writer::write(login_path.join("page.tsx"), LOGIN_PAGE)
+        .context("Failed to create login page")?;
+
+    pb.set_message("Configuring the emulator suite...");
+
+    if !project_file("firebase.json").exists() {
+        file_writer::write(project_file("firebase.json"), serde_json::to_string_pretty(&firebase_json())? + "\n")
+            .context("Failed to create firebase.json")?;
+    }
+    if !project_file(".firebaserc").exists() {
+        let firebaserc = json!({ "projects": { "default": demo_project_id } });
+        file_writer::write(project_file(".firebaserc"), serde_json::to_string_pretty(&firebaserc)? + "\n")
+            .context("Failed to create .firebaserc")?;
+    }
+    for (file, content) in [("firestore.rules", FIRESTORE_RULES), ("storage.rules", STORAGE_RULES)] {
+        if !project_file(file).exists() {
+            file_writer::write(project_file(file), content).context(format!("Failed to create {}", file))?;
+        }
+    }
+
+    package_json::add_scripts(&[(
+        "emulators",
+        "firebase emulators:start --import=.firebase-data --export-on-exit",
+    )])?;
+
+    gitignore::add_entries(
+        "Firebase",
+        &[".firebase/", ".firebase-data/", "firebase-debug.log", "firestore-debug.log", "ui-debug.log"],
+    )?;
+
+    let storage_bucket = format!("{}.appspot.com", demo_project_id);
+    env_file::add_env_vars(
+        "firebase",
+        "Firebase",
+        &[
+            ("NEXT_PUBLIC_FIREBASE_API_KEY", "demo-api-key"),
+            ("NEXT_PUBLIC_FIREBASE_AUTH_DOMAIN", "localhost"),
+            ("NEXT_PUBLIC_FIREBASE_PROJECT_ID", &demo_project_id),
+            ("NEXT_PUBLIC_FIREBASE_STORAGE_BUCKET", &storage_bucket),
+            ("NEXT_PUBLIC_FIREBASE_APP_ID", "demo-app-id"),
+            ("NEXT_PUBLIC_USE_FIREBASE_EMULATORS", "true"),
+            ("FIREBASE_CLIENT_EMAIL", "your-service-account-email"),
+            ("FIREBASE_PRIVATE_KEY", "your-service-account-private-key"),
+        ],
+    )?;
+
+    pb.finish_with_message("Firebase set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/client.ts - Client SDK with emulator support", firebase_lib_path.display());
+    println!("• {}/admin.ts - Admin SDK, server only", firebase_lib_path.display());
+    println!("• {}/converters.ts - Typed Firestore converters", firebase_lib_path.display());
+    println!("• {}/storage.ts - Upload, download URL and delete helpers", firebase_lib_path.display());
+    println!("• {}/session.ts and auth.ts - Session cookie auth", firebase_lib_path.display());
+    println!("• {}/route.ts - Creates and clears session cookies", session_route_path.display());
+    println!("• {}/page.tsx - Email and password sign-in", login_path.display());
+    println!("• firebase.json, .firebaserc, firestore.rules, storage.rules - Emulator suite and security rules");
+    println!("• .env - Firebase config for the {} emulator project", demo_project_id);
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Start the emulators: {}", package_manager.run_script("emulators"));
+    println!("2. Open the Emulator UI at http://127.0.0.1:4000");
+    println!("3. For production, set the real project config and service account in .env and NEXT_PUBLIC_USE_FIREBASE_EMULATORS=\"false\"");
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::gitignore;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::root_layout;
+
+const SCHEMA_PATH: &str = "dbschema/default.gel";
+
+const SCHEMA: &str = r#"module default {
+  type User {
+    required name: str;
+    required email: str {
+      constraint exclusive;
+    };
+    multi posts := .<author[is Post];
+  }
+
+  type Post {
+    required title: str;
+    content: str;
+    required author: User;
+    required created_at: datetime {
+      default := datetime_current();
+    };
+  }
+}
+"#;
+
+fn client_helper(query_builder_import: &str) -> String {
+    format!(
+        r#"import {{ createClient }} from "gel";
+import e from "{}";
+
+// Reuse one client (and its connection pool) across hot reloads
+const globalForGel = globalThis as unknown as {{ gel?: ReturnType<typeof createClient> }};
+
+export const client = globalForGel.gel ?? createClient();
+
+if (process.env.NODE_ENV !== "production") {{
+  globalForGel.gel = client;
+}}
+
+export {{ e }};
+"#,
+        query_builder_import
+    )
+}
+
+const POSTS_PAGE: &str = r#"import { client, e } from "@/lib/gel";
+
+export const dynamic = "force-dynamic";
+
+export default async function PostsPage() {
+  const posts = await e
+    .select(e.Post, (post) => ({
+      id: true,
+      title: true,
+      content: true,
+      created_at: true,
+      author: { name: true },
+      order_by: { expression: post.created_at, direction: e.DESC },
+      limit: 20,
+    }))
+    .run(client);
+
+  const userCount = await e.count(e.User).run(client);
+
+  return (
+    <main className="mx-auto flex max-w-xl flex-col gap-6 px-4 py-10">
+      <div>
+        <h1 className="text-2xl font-semibold">Posts</h1>
+        <p className="text-sm text-muted-foreground">{userCount} authors</p>
+      </div>
+
+      <ul className="flex flex-col gap-3">
+        {posts.map((post) => (
+          <li key={post.id} className="rounded-md border p-4">
+            <h2 className="font-medium">{post.title}</h2>
+            {post.content ? <p className="text-sm text-muted-foreground">{post.content}</p> : null}
+            <p className="mt-2 text-xs text-muted-foreground">
+              {post.author.name} · {post.created_at.toLocaleDateString()}
+            </p>
+          </li>
+        ))}
+      </ul>
+    </main>
+  );
+}
+"#;
+
+pub async fn add_gel() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Gel...");
+
+    package_manager.install(&["gel"], false)?;
+    package_manager.install(&["@gel/generate"], true)?;
+
+    pb.set_message("Initializing the Gel project...");
+
+    // Creates gel.toml, dbschema/ and a local instance linked to this directory
+    if !project_file("gel.toml").exists() {
+        package_manager
+            .exec(&["gel", "project", "init", "--non-interactive"])
+            .context("Failed to initialize the Gel project")?;
+    }
+
+    let schema_path = project_file(SCHEMA_PATH);
+    let schema_is_empty = std::fs::read_to_string(&schema_path)
+        .map(|schema| !schema.contains("type "))
+        .unwrap_or(true);
+    if schema_is_empty {
+        change_set::create_dir_all(project_file("dbschema")).context("Failed to create dbschema directory")?;
+        file_writer::write(&schema_path, SCHEMA).context("Failed to create default.gel")?;
+    }
+
+    pb.set_message("Creating the client helper...");
+
+    // dbschema/ lives at the project root, outside the `@/` alias in src layouts
+    let lib_path = project_structure.get_lib_path();
+    let depth = lib_path.components().count();
+    let query_builder_import = format!("{}dbschema/edgeql-js", "../".repeat(depth));
+    change_set::create_dir_all(&lib_path).context("Failed to create lib directory")?;
+    file_writer::write(lib_path.join("gel.ts"), client_helper(&query_builder_import))
+        .context("Failed to create gel.ts")?;
+
+    pb.set_message("Creating the example page...");
+
+    let page_path = root_layout::routes_dir(&project_structure).join("posts");
+    change_set::create_dir_all(&page_path).context("Failed to create posts route directory")?;
+    file_writer::write(page_path.join("page.tsx"), POSTS_PAGE)
+        .context("Failed to create page.tsx")?;
+
+    package_json::add_scripts(&[
+        ("gel:migrate", "gel migration create && gel migrate"),
+        ("gel:generate", "generate edgeql-js && generate interfaces"),
+    ])?;
+
+    gitignore::add_entries("Gel", &["dbschema/edgeql-js", "dbschema/interfaces.ts"])?;
+
+    pb.finish_with_message("Gel set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    if schema_is_empty {
+        println!("• {} - Example User and Post types", schema_path.display());
+    }
+    println!("• {}/gel.ts - Typed client and query builder", lib_path.display());
+    println!("• {}/page.tsx - Example queries in a Server Component", page_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Apply the schema: {}", package_manager.run_script("gel:migrate"));
+    println!("2. Generate the query builder: {}", package_manager.run_script("gel:generate"));
+    println!("3. In production, set GEL_INSTANCE and GEL_SECRET_KEY, and run gel:generate before next build");
+
+    Ok(())
+}
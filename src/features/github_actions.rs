@@ -0,0 +1,271 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::features::drizzle;
+use crate::manifest::ProjectManifest;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::prompt;
+use crate::project_structure::project_file;
+
+#[derive(Debug, Clone, PartialEq)]
+enum CiJob {
+    Lint,
+    Typecheck,
+    Test,
+    Build,
+    PreviewDeploy,
+}
+
+impl CiJob {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CiJob::Lint => "lint",
+            CiJob::Typecheck => "typecheck",
+            CiJob::Test => "test",
+            CiJob::Build => "build",
+            CiJob::PreviewDeploy => "preview",
+        }
+    }
+
+    fn get_description(&self) -> &'static str {
+        match self {
+            CiJob::Lint => "Lint - run the project's lint script",
+            CiJob::Typecheck => "Typecheck - tsc --noEmit",
+            CiJob::Test => "Test - run the project's test script",
+            CiJob::Build => "Build - next build",
+            CiJob::PreviewDeploy => "Preview deploy - deploy pull requests to Vercel",
+        }
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        match self {
+            CiJob::Lint => package_json::has_script("lint"),
+            CiJob::Typecheck | CiJob::Build => true,
+            CiJob::Test => package_json::has_script("test"),
+            CiJob::PreviewDeploy => false,
+        }
+    }
+}
+
+/// Checkout, Node/package manager setup with caching, and a frozen install.
+pub fn setup_steps(package_manager: &PackageManager) -> String {
+    let node_setup = match package_manager {
+        PackageManager::Npm => r#"      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: npm
+"#,
+        PackageManager::Yarn => r#"      - run: corepack enable
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: yarn
+"#,
+        PackageManager::Pnpm => r#"      - uses: pnpm/action-setup@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: pnpm
+"#,
+        PackageManager::Bun => r#"      - uses: oven-sh/setup-bun@v2
+      - uses: actions/cache@v4
+        with:
+          path: ~/.bun/install/cache
+          key: ${{ runner.os }}-bun-${{ hashFiles('bun.lock*') }}
+          restore-keys: ${{ runner.os }}-bun-
+"#,
+        PackageManager::Deno => r#"      - uses: denoland/setup-deno@v2
+        with:
+          deno-version: v2.x
+          cache: true
+"#,
+    };
+
+    format!(
+        "      - uses: actions/checkout@v4\n{}      - run: {}\n",
+        node_setup,
+        package_manager.frozen_install_command()
+    )
+}
+
+fn job_steps(job: &CiJob, package_manager: &PackageManager) -> String {
+    match job {
+        CiJob::Lint => format!("      - run: {}\n", package_manager.run_script("lint")),
+        CiJob::Typecheck => {
+            let command = if package_json::has_script("typecheck") {
+                package_manager.run_script("typecheck")
+            } else {
+                package_manager.exec_command("tsc --noEmit")
+            };
+            format!("      - run: {}\n", command)
+        }
+        CiJob::Test => format!("      - run: {}\n", package_manager.run_script("test")),
+        CiJob::Build => format!(
+            r#"      - uses: actions/cache@v4
+        with:
+          path: .next/cache
+          key: ${{{{ runner.os }}}}-nextjs-${{{{ hashFiles('{}') }}}}-${{{{ hashFiles('**/*.ts', '**/*.tsx') }}}}
+          restore-keys: ${{{{ runner.os }}}}-nextjs-${{{{ hashFiles('{}') }}}}-
+      - run: {}
+"#,
+            package_manager.lockfile(),
+            package_manager.lockfile(),
+            package_manager.run_script("build")
+        ),
+        CiJob::PreviewDeploy => [
+            "vercel pull --yes --environment=preview",
+            "vercel build",
+            "vercel deploy --prebuilt",
+        ]
+        .iter()
+        .map(|command| {
+            format!(
+                "      - run: {} --token=${{{{ secrets.VERCEL_TOKEN }}}}\n",
+                package_manager.dlx_command(command)
+            )
+        })
+        .collect(),
+    }
+}
+
+fn workflow(jobs: &[CiJob], package_manager: &PackageManager, database_env: Option<&str>) -> String {
+    let mut content = String::from(
+        r#"name: CI
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+concurrency:
+  group: ${{ github.workflow }}-${{ github.ref }}
+  cancel-in-progress: true
+
+jobs:
+"#,
+    );
+
+    let setup = setup_steps(package_manager);
+
+    for job in jobs {
+        content.push_str(&format!("  {}:\n", job.as_str()));
+
+        if *job == CiJob::PreviewDeploy {
+            content.push_str("    if: github.event_name == 'pull_request'\n");
+            let needs: Vec<&str> = jobs
+                .iter()
+                .filter(|j| **j != CiJob::PreviewDeploy)
+                .map(|j| j.as_str())
+                .collect();
+            if !needs.is_empty() {
+                content.push_str(&format!("    needs: [{}]\n", needs.join(", ")));
+            }
+        }
+
+        content.push_str("    runs-on: ubuntu-latest\n");
+
+        match job {
+            CiJob::PreviewDeploy => content.push_str(
+                r#"    env:
+      VERCEL_ORG_ID: ${{ secrets.VERCEL_ORG_ID }}
+      VERCEL_PROJECT_ID: ${{ secrets.VERCEL_PROJECT_ID }}
+"#,
+            ),
+            CiJob::Build | CiJob::Test => {
+                if let Some(env_var) = database_env {
+                    content.push_str(&format!(
+                        "    env:\n      {}: ${{{{ secrets.{} }}}}\n",
+                        env_var, env_var
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        content.push_str("    steps:\n");
+        content.push_str(&setup);
+        content.push_str(&job_steps(job, package_manager));
+        content.push('\n');
+    }
+
+    content.trim_end().to_string() + "\n"
+}
+
+pub async fn add_github_actions() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let available_jobs = [
+        CiJob::Lint,
+        CiJob::Typecheck,
+        CiJob::Test,
+        CiJob::Build,
+        CiJob::PreviewDeploy,
+    ];
+    let job_names: Vec<&str> = available_jobs.iter().map(|j| j.get_description()).collect();
+    let defaults: Vec<bool> = available_jobs.iter().map(|j| j.enabled_by_default()).collect();
+
+    let selections = prompt::multi_select("jobs", "Select the CI jobs to generate (space to toggle)", &job_names, &defaults)?;
+
+    if selections.is_empty() {
+        println!("{}", style("No jobs selected, nothing to generate.").yellow());
+        return Ok(());
+    }
+
+    let jobs: Vec<CiJob> = selections.iter().map(|&i| available_jobs[i].clone()).collect();
+
+    // Database-backed builds need the connection string available as a secret
+    let database_env = if ProjectManifest::is_feature_installed("drizzle") {
+        Some(drizzle::installed_env_variable())
+    } else {
+        None
+    };
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Creating GitHub Actions workflow...");
+
+    change_set::create_dir_all(project_file(".github/workflows"))
+        .context("Failed to create .github/workflows directory")?;
+    file_writer::write(
+        project_file(".github/workflows/ci.yml"),
+        workflow(&jobs, &package_manager, database_env),
+    )
+    .context("Failed to create ci.yml")?;
+
+    pb.finish_with_message("GitHub Actions workflow created!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• .github/workflows/ci.yml - CI workflow ({})",
+        jobs.iter().map(|j| j.as_str()).collect::<Vec<_>>().join(", "));
+
+    let mut secrets: Vec<&str> = Vec::new();
+    if let Some(env_var) = database_env
+        && jobs.iter().any(|j| matches!(j, CiJob::Build | CiJob::Test))
+    {
+        secrets.push(env_var);
+    }
+    if jobs.contains(&CiJob::PreviewDeploy) {
+        secrets.extend(["VERCEL_TOKEN", "VERCEL_ORG_ID", "VERCEL_PROJECT_ID"]);
+    }
+
+    if !secrets.is_empty() {
+        println!("\n{}", style("Next steps:").green());
+        println!("Add these repository secrets in GitHub (Settings → Secrets and variables → Actions):");
+        for secret in secrets {
+            println!("• {}", secret);
+        }
+    }
+
+    Ok(())
+}
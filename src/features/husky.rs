@@ -0,0 +1,134 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::json;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_profile::ProjectProfile;
+use crate::prompt;
+use crate::project_structure::project_file;
+
+const HOOKS_DIR: &str = ".husky";
+
+/// Installs husky and wires the `prepare` script, then activates the hooks if the
+/// project is already a git repository. Safe to call when husky is set up.
+pub fn ensure_husky(package_manager: &PackageManager) -> Result<()> {
+    if !package_json::has_dependency("husky") {
+        package_manager.install(&["husky"], true)?;
+    }
+
+    // `prepare` runs after every install, so hooks are activated for the whole team
+    package_json::add_scripts(&[("prepare", "husky")])?;
+    change_set::create_dir_all(project_file(HOOKS_DIR)).context("Failed to create .husky directory")?;
+
+    if project_file(".git").exists() {
+        package_manager.exec(&["husky"]).context("Failed to activate husky git hooks")?;
+    }
+
+    Ok(())
+}
+
+/// Writes `.husky/<name>` with `commands` and makes it executable.
+pub fn write_hook(name: &str, commands: &str) -> Result<()> {
+    let hook_path = project_file(HOOKS_DIR).join(name);
+    file_writer::write(&hook_path, format!("{}\n", commands.trim_end()))
+        .context(format!("Failed to create {}", hook_path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook_path, std::fs::Permissions::from_mode(0o755))
+            .context(format!("Failed to make {} executable", hook_path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// lint-staged globs for whichever linter and formatter the project uses.
+fn lint_staged_config(profile: &ProjectProfile) -> (serde_json::Value, String) {
+    if profile.biome {
+        (
+            json!({
+                "*.{js,jsx,ts,tsx,mjs,cjs,json,jsonc,css}": "biome check --write --no-errors-on-unmatched --files-ignore-unknown=true"
+            }),
+            "Biome".to_string(),
+        )
+    } else if profile.prettier {
+        let mut config = json!({
+            "*.{json,css,scss,md,mdx,yml,yaml}": "prettier --write"
+        });
+        config["*.{js,jsx,ts,tsx,mjs,cjs}"] = if profile.eslint.is_some() {
+            json!(["eslint --fix", "prettier --write"])
+        } else {
+            json!("prettier --write")
+        };
+        (config, profile.describe())
+    } else {
+        (
+            json!({
+                "*.{js,jsx,ts,tsx,mjs,cjs}": "eslint --fix"
+            }),
+            "ESLint".to_string(),
+        )
+    }
+}
+
+pub async fn add_husky() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    if !project_file(".git").exists() {
+        println!(
+            "{}",
+            style("No git repository found. Hooks will be activated on the next install after `git init`.").yellow()
+        );
+    }
+
+    let add_pre_push = prompt::confirm("pre_push", "Add a pre-push hook that runs the type checker?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing husky and lint-staged...");
+
+    package_manager.install(&["lint-staged"], true)?;
+    ensure_husky(&package_manager)?;
+
+    pb.set_message("Configuring lint-staged...");
+
+    let (config, formatter) = lint_staged_config(&ProjectProfile::detect());
+    file_writer::write(project_file(".lintstagedrc.json"), serde_json::to_string_pretty(&config)? + "\n")
+        .context("Failed to create .lintstagedrc.json")?;
+
+    pb.set_message("Creating git hooks...");
+
+    write_hook("pre-commit", &package_manager.exec_command("lint-staged"))?;
+
+    if add_pre_push {
+        package_json::add_scripts(&[("typecheck", "tsc --noEmit")])?;
+        write_hook("pre-push", &package_manager.run_script("typecheck"))?;
+    }
+
+    pb.finish_with_message("Git hooks set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• .husky/pre-commit - Runs lint-staged on staged files");
+    if add_pre_push {
+        println!("• .husky/pre-push - Runs the typecheck script");
+    }
+    println!("• .lintstagedrc.json - {} on staged files", formatter);
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Commit as usual; staged files are fixed and formatted automatically");
+    println!("2. Skip hooks in an emergency with `git commit --no-verify`");
+
+    Ok(())
+}
@@ -0,0 +1,395 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::json;
+use std::path::Path;
+use crate::change_set;
+use crate::file_writer;
+use crate::next_config;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+use crate::root_layout;
+
+// Files that must stay at the app root: shared styles and metadata routes
+const ROOT_ONLY_FILES: [&str; 9] = [
+    "globals.css",
+    "favicon.ico",
+    "robots.ts",
+    "sitemap.ts",
+    "sitemap.xml",
+    "sitemaps",
+    "manifest.ts",
+    "icon.tsx",
+    "apple-icon.tsx",
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Locale {
+    German,
+    Spanish,
+    French,
+    Japanese,
+    Portuguese,
+}
+
+impl Locale {
+    fn code(&self) -> &'static str {
+        match self {
+            Locale::German => "de",
+            Locale::Spanish => "es",
+            Locale::French => "fr",
+            Locale::Japanese => "ja",
+            Locale::Portuguese => "pt",
+        }
+    }
+
+    fn get_description(&self) -> &'static str {
+        match self {
+            Locale::German => "German (de)",
+            Locale::Spanish => "Spanish (es)",
+            Locale::French => "French (fr)",
+            Locale::Japanese => "Japanese (ja)",
+            Locale::Portuguese => "Portuguese (pt)",
+        }
+    }
+
+    /// (title, description, language label, native name)
+    fn translations(&self) -> (&'static str, &'static str, &'static str, &'static str) {
+        match self {
+            Locale::German => ("Willkommen", "Diese Seite ist jetzt übersetzt.", "Sprache", "Deutsch"),
+            Locale::Spanish => ("Bienvenido", "Esta página ahora está traducida.", "Idioma", "Español"),
+            Locale::French => ("Bienvenue", "Cette page est maintenant traduite.", "Langue", "Français"),
+            Locale::Japanese => ("ようこそ", "このページは翻訳されました。", "言語", "日本語"),
+            Locale::Portuguese => ("Bem-vindo", "Esta página agora está traduzida.", "Idioma", "Português"),
+        }
+    }
+}
+
+fn messages(title: &str, description: &str, label: &str, second: Locale) -> serde_json::Value {
+    let (_, _, _, second_name) = second.translations();
+    json!({
+        "HomePage": {
+            "title": title,
+            "description": description
+        },
+        "LanguageSwitcher": {
+            "label": label,
+            "locales": {
+                "en": "English",
+                second.code(): second_name
+            }
+        }
+    })
+}
+
+fn routing_config(second: Locale) -> String {
+    format!(
+        r#"import {{ defineRouting }} from "next-intl/routing";
+
+export const routing = defineRouting({{
+  locales: ["en", "{}"],
+  defaultLocale: "en",
+}});
+"#,
+        second.code()
+    )
+}
+
+const NAVIGATION: &str = r#"import { createNavigation } from "next-intl/navigation";
+import { routing } from "./routing";
+
+// Locale-aware wrappers around Next.js navigation APIs
+export const { Link, redirect, usePathname, useRouter, getPathname } = createNavigation(routing);
+"#;
+
+fn request_config(messages_import: &str) -> String {
+    format!(
+        r#"import {{ hasLocale }} from "next-intl";
+import {{ getRequestConfig }} from "next-intl/server";
+import {{ routing }} from "./routing";
+
+export default getRequestConfig(async ({{ requestLocale }}) => {{
+  const requested = await requestLocale;
+  const locale = hasLocale(routing.locales, requested) ? requested : routing.defaultLocale;
+
+  return {{
+    locale,
+    messages: (await import(`{}/${{locale}}.json`)).default,
+  }};
+}});
+"#,
+        messages_import
+    )
+}
+
+const MIDDLEWARE: &str = r#"import createMiddleware from "next-intl/middleware";
+import { routing } from "./i18n/routing";
+
+export default createMiddleware(routing);
+
+export const config = {
+  // Skip API routes, Next.js internals and files with an extension
+  matcher: "/((?!api|trpc|_next|_vercel|.*\\..*).*)",
+};
+"#;
+
+const GLOBAL_TYPES: &str = r#"import type messages from "./messages/en.json";
+import type { routing } from "__ROUTING_IMPORT__";
+
+// Type-checks locales and message keys passed to useTranslations / getTranslations
+declare module "next-intl" {
+  interface AppConfig {
+    Locale: (typeof routing.locales)[number];
+    Messages: typeof messages;
+  }
+}
+"#;
+
+const LANGUAGE_SWITCHER: &str = r#""use client";
+
+import { useLocale, useTranslations } from "next-intl";
+import { useTransition } from "react";
+import { usePathname, useRouter } from "@/i18n/navigation";
+import { routing } from "@/i18n/routing";
+
+export function LanguageSwitcher() {
+  const t = useTranslations("LanguageSwitcher");
+  const locale = useLocale();
+  const router = useRouter();
+  const pathname = usePathname();
+  const [isPending, startTransition] = useTransition();
+
+  return (
+    <label className="flex items-center gap-2 text-sm">
+      {t("label")}
+      <select
+        className="rounded-md border bg-background px-2 py-1"
+        value={locale}
+        disabled={isPending}
+        onChange={(event) => {
+          const nextLocale = event.target.value as (typeof routing.locales)[number];
+          startTransition(() => router.replace(pathname, { locale: nextLocale }));
+        }}
+      >
+        {routing.locales.map((option) => (
+          <option key={option} value={option}>
+            {t(`locales.${option}`)}
+          </option>
+        ))}
+      </select>
+    </label>
+  );
+}
+"#;
+
+/// Moves every route and layout into `app/[locale]`, leaving API routes and root-only files.
+fn move_routes_into_locale(app_path: &Path) -> Result<Vec<String>> {
+    let locale_path = app_path.join("[locale]");
+    change_set::create_dir_all(&locale_path).context("Failed to create app/[locale] directory")?;
+
+    let mut moved = Vec::new();
+    for entry in std::fs::read_dir(app_path).context("Failed to read the app directory")? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == "api" || name == "[locale]" || ROOT_ONLY_FILES.contains(&name.as_str()) {
+            continue;
+        }
+
+        change_set::rename(entry.path(), locale_path.join(&name))
+            .context(format!("Failed to move {} into [locale]", name))?;
+        moved.push(name);
+    }
+
+    moved.sort();
+    Ok(moved)
+}
+
+/// Turns the moved root layout into a locale-aware layout. Returns `Ok(false)` when the
+/// props signature isn't the create-next-app default and has to be updated by hand.
+fn localize_layout(layout_path: &Path) -> Result<bool> {
+    let mut content = std::fs::read_to_string(layout_path)
+        .context(format!("Failed to read {}", layout_path.display()))?;
+
+    // Relative imports now resolve from [locale]
+    content = content
+        .replace("\"./globals.css\"", "\"../globals.css\"")
+        .replace("'./globals.css'", "'../globals.css'");
+
+    let default_props = "  children,\n}: Readonly<{\n  children: React.ReactNode;\n}>) {\n";
+    let signature_updated = content.contains("export default function RootLayout(") && content.contains(default_props);
+    if signature_updated {
+        content = content
+            .replace("export default function RootLayout(", "export default async function RootLayout(")
+            .replace("<html lang=\"en\"", "<html lang={locale}")
+            .replace(
+                default_props,
+                "  children,\n  params,\n}: Readonly<{\n  children: React.ReactNode;\n  params: Promise<{ locale: string }>;\n}>) {\n  const { locale } = await params;\n  if (!hasLocale(routing.locales, locale)) {\n    notFound();\n  }\n  // Enables static rendering for this locale\n  setRequestLocale(locale);\n\n",
+            );
+
+        if let Some(index) = content.find("export default async function RootLayout(") {
+            content.insert_str(
+                index,
+                "export function generateStaticParams() {\n  return routing.locales.map((locale) => ({ locale }));\n}\n\n",
+            );
+        }
+    }
+
+    file_writer::write(layout_path, content)
+        .context(format!("Failed to update {}", layout_path.display()))?;
+
+    Ok(signature_updated)
+}
+
+pub async fn add_i18n() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let app_path = project_structure.get_app_path();
+    let middleware_path = project_structure.get_middleware_path();
+
+    if middleware_path.exists() {
+        println!(
+            "{}",
+            style(format!(
+                "{} already exists. Combine it with next-intl's middleware manually after setup.",
+                middleware_path.display()
+            ))
+            .yellow()
+        );
+    }
+
+    let available_locales = [
+        Locale::German,
+        Locale::Spanish,
+        Locale::French,
+        Locale::Japanese,
+        Locale::Portuguese,
+    ];
+    let locale_names: Vec<&str> = available_locales.iter().map(|l| l.get_description()).collect();
+    let selection = prompt::select("locale", "Which locale should be added alongside English?", &locale_names, 0)?;
+    let second_locale = available_locales[selection];
+
+    let restructure = prompt::confirm(
+        "restructure",
+        &format!("Move routes and the root layout into {}/[locale]?", app_path.display()),
+        true,
+    )?;
+    if !restructure {
+        println!(
+            "{}",
+            style("Locale routing needs the app directory under [locale]. Aborting without changes.").yellow()
+        );
+        return Ok(());
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing next-intl...");
+
+    package_manager.install(&["next-intl"], false)?;
+
+    pb.set_message("Adding the next-intl plugin to next.config...");
+
+    next_config::add_import(
+        "createNextIntlPlugin",
+        "next-intl/plugin",
+        Some("const withNextIntl = createNextIntlPlugin();"),
+    )?;
+    next_config::wrap_export("withNextIntl")?;
+
+    pb.set_message("Creating routing, request config and middleware...");
+
+    let i18n_path = project_structure.get_i18n_path();
+    change_set::create_dir_all(&i18n_path).context("Failed to create i18n directory")?;
+    file_writer::write(i18n_path.join("routing.ts"), routing_config(second_locale))
+        .context("Failed to create routing.ts")?;
+    file_writer::write(i18n_path.join("navigation.ts"), NAVIGATION)
+        .context("Failed to create navigation.ts")?;
+
+    // messages/ lives at the project root, so src/ projects need one more level up
+    let messages_import = format!("{}messages", "../".repeat(i18n_path.components().count()));
+    file_writer::write(i18n_path.join("request.ts"), request_config(&messages_import))
+        .context("Failed to create request.ts")?;
+
+    let middleware_created = !middleware_path.exists();
+    if middleware_created {
+        file_writer::write(&middleware_path, MIDDLEWARE).context("Failed to create middleware.ts")?;
+    }
+
+    pb.set_message("Creating messages...");
+
+    change_set::create_dir_all(project_file("messages")).context("Failed to create messages directory")?;
+    let english = messages("Welcome", "This page is now translated.", "Language", second_locale);
+    file_writer::write(project_file("messages/en.json"), serde_json::to_string_pretty(&english)? + "\n")
+        .context("Failed to create messages/en.json")?;
+    let (title, description, label, _) = second_locale.translations();
+    file_writer::write(
+        format!("messages/{}.json", second_locale.code()),
+        serde_json::to_string_pretty(&messages(title, description, label, second_locale))? + "\n",
+    )
+    .context("Failed to create locale messages")?;
+
+    file_writer::write(
+        project_file("global.d.ts"),
+        GLOBAL_TYPES.replace("__ROUTING_IMPORT__", &format!("./{}/routing", project_structure.relative_path(&i18n_path))),
+    )
+    .context("Failed to create global.d.ts")?;
+
+    pb.set_message("Moving routes into [locale]...");
+
+    let moved = move_routes_into_locale(&app_path)?;
+
+    let layout_localized = match root_layout::find_root_layout(&project_structure) {
+        Some(layout_path) => localize_layout(&layout_path)?,
+        None => false,
+    };
+    if layout_localized {
+        root_layout::add_import(&project_structure, "import { notFound } from \"next/navigation\";")?;
+        root_layout::add_import(&project_structure, "import { hasLocale, NextIntlClientProvider } from \"next-intl\";")?;
+        root_layout::add_import(&project_structure, "import { setRequestLocale } from \"next-intl/server\";")?;
+        root_layout::add_import(&project_structure, "import { routing } from \"@/i18n/routing\";")?;
+        root_layout::wrap_children(&project_structure, "<NextIntlClientProvider>", "</NextIntlClientProvider>")?;
+    }
+
+    let components_path = project_structure.get_components_path();
+    change_set::create_dir_all(&components_path).context("Failed to create components directory")?;
+    file_writer::write(components_path.join("language-switcher.tsx"), LANGUAGE_SWITCHER)
+        .context("Failed to create language-switcher.tsx")?;
+
+    pb.finish_with_message("Internationalization set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/routing.ts - Locales: en, {}", i18n_path.display(), second_locale.code());
+    println!("• {}/request.ts - Loads messages per request", i18n_path.display());
+    println!("• {}/navigation.ts - Locale-aware Link and router", i18n_path.display());
+    if middleware_created {
+        println!("• {} - Locale detection and routing", middleware_path.display());
+    }
+    println!("• messages/en.json, messages/{}.json - Translations", second_locale.code());
+    println!("• global.d.ts - Typed locales and message keys");
+    println!("• {}/language-switcher.tsx - Language select", components_path.display());
+    println!("• Moved into {}/[locale]: {}", app_path.display(), moved.join(", "));
+
+    if !layout_localized {
+        println!(
+            "{}",
+            style("Could not update the root layout automatically. Read `locale` from params, set <html lang={locale}> and wrap children in <NextIntlClientProvider>.").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Translate pages with useTranslations(\"HomePage\") or getTranslations");
+    println!("2. Replace next/link imports with Link from @/i18n/navigation");
+    println!("3. Render <LanguageSwitcher /> in your layout");
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::next_config;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+#[derive(Clone, Copy, PartialEq)]
+enum IconLibrary {
+    Lucide,
+    Iconify,
+}
+
+impl IconLibrary {
+    fn package(&self) -> &'static str {
+        match self {
+            IconLibrary::Lucide => "lucide-react",
+            IconLibrary::Iconify => "@iconify/react",
+        }
+    }
+}
+
+const ICON_SIZES: &str = r#"export const iconSizes = {
+  xs: 12,
+  sm: 16,
+  md: 20,
+  lg: 24,
+  xl: 32,
+} as const;
+
+export type IconSize = keyof typeof iconSizes;
+"#;
+
+const LUCIDE_ICON: &str = r#"import type { LucideIcon, LucideProps } from "lucide-react";
+import { type IconSize, iconSizes } from "./sizes";
+
+type IconProps = Omit<LucideProps, "size" | "ref"> & {
+  icon: LucideIcon;
+  size?: IconSize;
+  /** Accessible name; icons without a label are treated as decorative */
+  label?: string;
+};
+
+/**
+ * Renders a lucide icon on the shared size scale. Strokes stay 2px at every size
+ * unless `strokeWidth` is overridden.
+ */
+export function Icon({ icon: Component, size = "md", strokeWidth = 2, label, ...props }: IconProps) {
+  return (
+    <Component
+      size={iconSizes[size]}
+      strokeWidth={strokeWidth}
+      absoluteStrokeWidth
+      role={label ? "img" : undefined}
+      aria-label={label}
+      aria-hidden={label ? undefined : true}
+      {...props}
+    />
+  );
+}
+"#;
+
+const LUCIDE_REGISTRY: &str = r#"import {
+  Check,
+  ChevronDown,
+  ChevronRight,
+  Command,
+  LoaderCircle,
+  type LucideIcon,
+  Menu,
+  Search,
+  Settings,
+  TriangleAlert,
+  User,
+  X,
+} from "lucide-react";
+
+/**
+ * App-wide icon names. Import from here instead of lucide-react directly so icons can be
+ * swapped in one place.
+ */
+export const Icons = {
+  logo: Command,
+  spinner: LoaderCircle,
+  close: X,
+  menu: Menu,
+  search: Search,
+  settings: Settings,
+  user: User,
+  check: Check,
+  warning: TriangleAlert,
+  chevronDown: ChevronDown,
+  chevronRight: ChevronRight,
+} satisfies Record<string, LucideIcon>;
+
+export type IconName = keyof typeof Icons;
+"#;
+
+const ICONIFY_ICON: &str = r#""use client";
+
+import { Icon as IconifyIcon, type IconProps as IconifyProps } from "@iconify/react";
+import { type IconSize, iconSizes } from "./sizes";
+
+type IconProps = Omit<IconifyProps, "width" | "height" | "ref"> & {
+  size?: IconSize;
+  /** Accessible name; icons without a label are treated as decorative */
+  label?: string;
+};
+
+/**
+ * Renders any Iconify icon (e.g. "lucide:search", "mdi:github") on the shared size scale.
+ */
+export function Icon({ size = "md", label, ...props }: IconProps) {
+  return (
+    <IconifyIcon
+      width={iconSizes[size]}
+      height={iconSizes[size]}
+      role={label ? "img" : undefined}
+      aria-label={label}
+      aria-hidden={label ? undefined : true}
+      {...props}
+    />
+  );
+}
+"#;
+
+const ICONIFY_REGISTRY: &str = r#"/**
+ * App-wide icon names mapped to Iconify ids. Use these instead of raw ids so icons can be
+ * swapped in one place. Browse ids at https://icon-sets.iconify.design
+ */
+export const Icons = {
+  logo: "lucide:command",
+  spinner: "lucide:loader-circle",
+  close: "lucide:x",
+  menu: "lucide:menu",
+  search: "lucide:search",
+  settings: "lucide:settings",
+  user: "lucide:user",
+  check: "lucide:check",
+  warning: "lucide:triangle-alert",
+  chevronDown: "lucide:chevron-down",
+  chevronRight: "lucide:chevron-right",
+} as const;
+
+export type IconName = keyof typeof Icons;
+"#;
+
+pub async fn add_icons() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let libraries = vec!["Lucide (lucide-react)", "Iconify (@iconify/react, 200k+ icons loaded on demand)"];
+    let selection = prompt::select("library", "Select an icon library", &libraries, 0)?;
+    let library = if selection == 0 { IconLibrary::Lucide } else { IconLibrary::Iconify };
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message(format!("Installing {}...", library.package()));
+
+    package_manager.install(&[library.package()], false)?;
+
+    pb.set_message("Creating icon components...");
+
+    let icons_path = project_structure.get_components_path().join("icons");
+    change_set::create_dir_all(&icons_path).context("Failed to create icons directory")?;
+    file_writer::write(icons_path.join("sizes.ts"), ICON_SIZES)
+        .context("Failed to create sizes.ts")?;
+    let (icon, registry) = match library {
+        IconLibrary::Lucide => (LUCIDE_ICON, LUCIDE_REGISTRY),
+        IconLibrary::Iconify => (ICONIFY_ICON, ICONIFY_REGISTRY),
+    };
+    file_writer::write(icons_path.join("icon.tsx"), icon)
+        .context("Failed to create icon.tsx")?;
+    file_writer::write(icons_path.join("index.ts"), registry)
+        .context("Failed to create index.ts")?;
+
+    // Iconify fetches icon data at runtime, so only lucide's barrel file needs optimizing
+    let optimize_added = if library == IconLibrary::Lucide {
+        pb.set_message("Configuring optimizePackageImports...");
+        next_config::add_to_array_option(
+            &["experimental", "optimizePackageImports"],
+            &["\"lucide-react\""],
+        )?
+    } else {
+        false
+    };
+
+    pb.finish_with_message("Icons set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/icon.tsx - Typed <Icon> wrapper with size and stroke conventions", icons_path.display());
+    println!("• {}/sizes.ts - Shared icon size scale", icons_path.display());
+    println!("• {}/index.ts - App-wide icon registry", icons_path.display());
+    if optimize_added {
+        println!("• next.config - optimizePackageImports for lucide-react");
+    } else if library == IconLibrary::Lucide {
+        println!(
+            "{}",
+            style("next.config already has an `experimental` block; add \"lucide-react\" to optimizePackageImports if it is missing").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    match library {
+        IconLibrary::Lucide => println!("1. Render icons with <Icon icon={{Icons.search}} size=\"sm\" />"),
+        IconLibrary::Iconify => println!("1. Render icons with <Icon icon={{Icons.search}} size=\"sm\" />, or any Iconify id"),
+    }
+    println!("2. Add the icons your app uses to {}/index.ts", icons_path.display());
+
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::next_config;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+/// An image host offered in the remotePatterns picker.
+struct ImageHost {
+    name: &'static str,
+    hostname: &'static str,
+    pathname: Option<&'static str>,
+    /// Preselected when any of these packages is installed; empty means always preselected
+    detected_by: &'static [&'static str],
+}
+
+const IMAGE_HOSTS: &[ImageHost] = &[
+    ImageHost { name: "Unsplash", hostname: "images.unsplash.com", pathname: None, detected_by: &[] },
+    ImageHost { name: "Cloudinary", hostname: "res.cloudinary.com", pathname: None, detected_by: &[] },
+    ImageHost { name: "GitHub avatars", hostname: "avatars.githubusercontent.com", pathname: None, detected_by: &[] },
+    ImageHost { name: "Google avatars", hostname: "lh3.googleusercontent.com", pathname: None, detected_by: &[] },
+    ImageHost {
+        name: "Vercel Blob",
+        hostname: "*.public.blob.vercel-storage.com",
+        pathname: None,
+        detected_by: &["@vercel/blob"],
+    },
+    ImageHost {
+        name: "Amazon S3",
+        hostname: "**.amazonaws.com",
+        pathname: None,
+        detected_by: &["@aws-sdk/client-s3"],
+    },
+    ImageHost {
+        name: "Supabase Storage",
+        hostname: "*.supabase.co",
+        pathname: Some("/storage/v1/object/public/**"),
+        detected_by: &["@supabase/supabase-js", "@supabase/ssr"],
+    },
+    ImageHost { name: "UploadThing", hostname: "utfs.io", pathname: None, detected_by: &["uploadthing"] },
+    ImageHost {
+        name: "Firebase Storage",
+        hostname: "firebasestorage.googleapis.com",
+        pathname: None,
+        detected_by: &["firebase"],
+    },
+    ImageHost { name: "Sanity", hostname: "cdn.sanity.io", pathname: None, detected_by: &["next-sanity", "@sanity/client"] },
+    ImageHost { name: "Contentful", hostname: "images.ctfassets.net", pathname: None, detected_by: &["contentful"] },
+    ImageHost { name: "Cloudflare Images", hostname: "imagedelivery.net", pathname: None, detected_by: &[] },
+    ImageHost { name: "Gravatar", hostname: "www.gravatar.com", pathname: None, detected_by: &[] },
+];
+
+impl ImageHost {
+    fn preselected(&self) -> bool {
+        self.detected_by.is_empty() && matches!(self.name, "Unsplash" | "GitHub avatars" | "Google avatars")
+            || self.detected_by.iter().any(|package| package_json::has_dependency(package))
+    }
+
+    fn label(&self) -> String {
+        format!("{} ({})", self.name, self.hostname)
+    }
+}
+
+/// A `remotePatterns` entry for an https host.
+fn remote_pattern(hostname: &str, pathname: Option<&str>) -> String {
+    match pathname {
+        Some(pathname) => format!("{{ protocol: \"https\", hostname: \"{}\", pathname: \"{}\" }}", hostname, pathname),
+        None => format!("{{ protocol: \"https\", hostname: \"{}\" }}", hostname),
+    }
+}
+
+fn validate_hosts(value: &str) -> Result<(), &'static str> {
+    let valid = value
+        .split(',')
+        .map(str::trim)
+        .all(|host| host.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '*')));
+    if valid {
+        Ok(())
+    } else {
+        Err("Enter hostnames only, without https:// or paths")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DeviceSizes {
+    Standard,
+    UpTo4k,
+}
+
+impl DeviceSizes {
+    const ALL: [DeviceSizes; 2] = [DeviceSizes::Standard, DeviceSizes::UpTo4k];
+
+    fn description(&self) -> &'static str {
+        match self {
+            DeviceSizes::Standard => "Standard - 640 to 1920px, fewer variants to generate and cache",
+            DeviceSizes::UpTo4k => "Up to 4K - the Next.js defaults, including 2048 and 3840px",
+        }
+    }
+
+    fn value(&self) -> &'static str {
+        match self {
+            DeviceSizes::Standard => "[640, 750, 828, 1080, 1200, 1920]",
+            DeviceSizes::UpTo4k => "[640, 750, 828, 1080, 1200, 1920, 2048, 3840]",
+        }
+    }
+}
+
+const BLUR: &str = r#"import { readFile } from "node:fs/promises";
+import path from "node:path";
+import { getPlaiceholder } from "plaiceholder";
+import { cache } from "react";
+
+/**
+ * A tiny base64 image for `placeholder="blur"`, from a remote URL or a path under public/.
+ * Returns undefined when the image cannot be read, so the page still renders without one.
+ */
+export const getBlurDataURL = cache(async (src: string): Promise<string | undefined> => {
+  try {
+    const buffer = /^https?:\/\//.test(src)
+      ? Buffer.from(await (await fetch(src, { cache: "force-cache" })).arrayBuffer())
+      : await readFile(path.join(process.cwd(), "public", src));
+    const { base64 } = await getPlaiceholder(buffer, { size: 10 });
+    return base64;
+  } catch (error) {
+    console.error(`Could not create a blur placeholder for ${src}`, error);
+    return undefined;
+  }
+});
+"#;
+
+const OPTIMIZED_IMAGE: &str = r#"import Image, { type ImageProps } from "next/image";
+import { getBlurDataURL } from "@/lib/images/blur";
+
+type OptimizedImageProps = ImageProps & {
+  /** Set to false to skip the blur placeholder */
+  blur?: boolean;
+};
+
+// Static imports of jpg, png, webp and avif files come with a blurDataURL from Next.js
+function staticBlurDataURL(src: ImageProps["src"]) {
+  if (typeof src === "string") return undefined;
+  return ("default" in src ? src.default : src).blurDataURL;
+}
+
+/**
+ * next/image with a blur placeholder: static imports use the one Next.js generates and
+ * string sources get one from plaiceholder when the page renders. A server component, so
+ * pass `blurDataURL` yourself from client components. `fill` images default to `sizes="100vw"`.
+ */
+export async function OptimizedImage({ src, alt, blur = true, placeholder, blurDataURL, sizes, ...props }: OptimizedImageProps) {
+  const blurData =
+    blurDataURL ??
+    staticBlurDataURL(src) ??
+    (blur && !placeholder && typeof src === "string" ? await getBlurDataURL(src) : undefined);
+
+  return (
+    <Image
+      src={src}
+      alt={alt}
+      sizes={sizes ?? (props.fill ? "100vw" : undefined)}
+      placeholder={placeholder ?? (blur && blurData ? "blur" : "empty")}
+      blurDataURL={blurData}
+      {...props}
+    />
+  );
+}
+"#;
+
+pub async fn add_images() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let labels: Vec<String> = IMAGE_HOSTS.iter().map(ImageHost::label).collect();
+    let defaults: Vec<bool> = IMAGE_HOSTS.iter().map(ImageHost::preselected).collect();
+    let selections = prompt::multi_select(
+        "hosts",
+        "Select the image hosts next/image may load from (space to toggle)",
+        &labels,
+        &defaults,
+    )?;
+    let extra_hosts = prompt::input_validated(
+        "extra_hosts",
+        "Other image hostnames, comma separated (blank for none)",
+        "",
+        |input| validate_hosts(input),
+    )?;
+
+    let avif = prompt::confirm("avif", "Serve AVIF before WebP? (smaller files, slower first encode)", true)?;
+    let size_descriptions: Vec<&str> = DeviceSizes::ALL.iter().map(DeviceSizes::description).collect();
+    let device_sizes = DeviceSizes::ALL[prompt::select("device_sizes", "Select the device sizes to generate", &size_descriptions, 0)?];
+
+    let mut patterns: Vec<String> = selections
+        .iter()
+        .map(|&index| remote_pattern(IMAGE_HOSTS[index].hostname, IMAGE_HOSTS[index].pathname))
+        .collect();
+    let extra: Vec<&str> = extra_hosts.split(',').map(str::trim).filter(|host| !host.is_empty()).collect();
+    patterns.extend(extra.iter().map(|host| remote_pattern(host, None)));
+
+    let pb = ProgressBar::new_spinner();
+
+    pb.set_message("Installing plaiceholder and sharp...");
+    package_manager.install(&["plaiceholder", "sharp"], false)?;
+
+    pb.set_message("Configuring next/image in next.config...");
+
+    let pattern_items: Vec<&str> = patterns.iter().map(String::as_str).collect();
+    let patterns_added = !pattern_items.is_empty() && next_config::add_to_array_option(&["images", "remotePatterns"], &pattern_items)?;
+    let formats = if avif { "[\"image/avif\", \"image/webp\"]" } else { "[\"image/webp\"]" };
+    let formats_set = next_config::set_nested_option(&["images", "formats"], formats)?;
+    let device_sizes_set = next_config::set_nested_option(&["images", "deviceSizes"], device_sizes.value())?;
+
+    pb.set_message("Creating the OptimizedImage component...");
+
+    let lib_path = project_structure.get_lib_path().join("images");
+    change_set::create_dir_all(&lib_path).context("Failed to create lib/images directory")?;
+    file_writer::write(lib_path.join("blur.ts"), BLUR).context("Failed to create blur.ts")?;
+
+    let components_path = project_structure.get_components_path().join("images");
+    change_set::create_dir_all(&components_path).context("Failed to create components/images directory")?;
+    file_writer::write(components_path.join("optimized-image.tsx"), OPTIMIZED_IMAGE)
+        .context("Failed to create optimized-image.tsx")?;
+
+    pb.finish_with_message("Image handling configured!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    if patterns_added {
+        println!("• next.config - remotePatterns for {} host(s)", patterns.len());
+    }
+    if formats_set {
+        println!("• next.config - formats {}", formats);
+    }
+    if device_sizes_set {
+        println!("• next.config - deviceSizes {}", device_sizes.value());
+    }
+    if !formats_set || !device_sizes_set {
+        println!(
+            "{}",
+            style("• next.config already sets images.formats or images.deviceSizes; those were left as they are").yellow()
+        );
+    }
+    println!("• {}/blur.ts - getBlurDataURL() with plaiceholder", lib_path.display());
+    println!("• {}/optimized-image.tsx - <OptimizedImage> with automatic blur placeholders", components_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Swap next/image for <OptimizedImage> in server components");
+    println!("2. Add more hosts to images.remotePatterns in next.config as you need them");
+
+    Ok(())
+}
@@ -0,0 +1,606 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::features::admin;
+use crate::file_writer;
+use crate::manifest;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+use crate::root_layout;
+
+const CONFIG: &str = r#"/**
+ * Tables `GET /api/export/[table]` may download, by their database name. Leave out tables
+ * holding secrets or other people's data, such as users and sessions.
+ */
+export const EXPORTABLE_TABLES: string[] = [__EXPORTABLE__];
+
+/** Rows sent to the server per import request */
+export const IMPORT_BATCH_SIZE = 500;
+"#;
+
+const SCHEMAS: &str = r#"import { z } from "zod";
+
+/**
+ * What each importable table accepts from a spreadsheet row, keyed by the name shown on
+ * the import page. Add a schema here and its table in importers.ts to import another table.
+ */
+export const importSchemas = {
+  posts: z.object({
+    title: z.string().trim().min(1, "Title is required"),
+    content: z.string().trim().min(1, "Content is required"),
+  }),
+};
+
+export type ImportTarget = keyof typeof importSchemas;
+
+export type RowError = {
+  /** The spreadsheet row number, counting the header as row 1 */
+  row: number;
+  message: string;
+};
+
+/** Validates parsed rows, splitting them into valid rows and a report of the invalid ones. */
+export function validateRows(target: ImportTarget, rows: Record<string, unknown>[], firstRow = 2) {
+  const schema = importSchemas[target];
+  const valid: z.infer<typeof schema>[] = [];
+  const errors: RowError[] = [];
+
+  rows.forEach((row, index) => {
+    const result = schema.safeParse(row);
+    if (result.success) {
+      valid.push(result.data);
+    } else {
+      const message = result.error.issues
+        .map((issue) => (issue.path.length > 0 ? `${issue.path.join(".")}: ${issue.message}` : issue.message))
+        .join("; ");
+      errors.push({ row: firstRow + index, message });
+    }
+  });
+
+  return { valid, errors };
+}
+"#;
+
+const IMPORTERS: &str = r#"import { postsTable } from "@/db/schema";
+import type { ImportTarget } from "@/lib/import-export/schemas";
+
+/** The drizzle table each import target inserts into. */
+export const importTables = {
+  posts: postsTable,
+} satisfies Record<ImportTarget, unknown>;
+"#;
+
+const CSV: &str = r#"// Cells starting with these run as formulas when a spreadsheet opens the file
+const FORMULA_PREFIX = /^[=+\-@\t\r]/;
+
+function escapeCell(value: unknown): string {
+  if (value === null || value === undefined) return "";
+  let text =
+    value instanceof Date ? value.toISOString() : typeof value === "object" ? JSON.stringify(value) : String(value);
+  if (FORMULA_PREFIX.test(text)) text = `'${text}`;
+  return /[",\r\n]/.test(text) ? `"${text.replace(/"/g, '""')}"` : text;
+}
+
+/** One CSV line, with its line break. */
+export function toCsvRow(cells: unknown[]): string {
+  return `${cells.map(escapeCell).join(",")}\r\n`;
+}
+"#;
+
+const ACCESS: &str = r#"__SESSION_IMPORTS__
+export type SessionUser = {
+  email?: string | null;
+};
+
+__SESSION_USER__
+/** Imports and exports are only for signed-in users. */
+export async function canTransferData() {
+  return (await getSessionUser()) !== null;
+}
+"#;
+
+const ACTIONS: &str = r#""use server";
+
+import { db } from "@/db";
+import { canTransferData } from "@/lib/import-export/access";
+import { importTables } from "@/lib/import-export/importers";
+import { importSchemas, validateRows, type ImportTarget, type RowError } from "@/lib/import-export/schemas";
+
+export type ImportResult = {
+  inserted: number;
+  errors: RowError[];
+};
+
+/**
+ * Inserts one batch of parsed rows. They are validated again here, since anything the
+ * browser sends can be forged. `firstRow` keeps error row numbers matching the file.
+ */
+export async function importRows(target: ImportTarget, rows: Record<string, unknown>[], firstRow: number): Promise<ImportResult> {
+  if (!(await canTransferData())) {
+    throw new Error("Sign in to import data");
+  }
+  if (!Object.hasOwn(importSchemas, target)) {
+    throw new Error(`Unknown import target: ${target}`);
+  }
+
+  const { valid, errors } = validateRows(target, rows, firstRow);
+  if (valid.length > 0) {
+    await db.insert(importTables[target]).values(valid);
+  }
+  return { inserted: valid.length, errors };
+}
+"#;
+
+const CSV_EXPORT_ROUTE: &str = r#"import { asc, getTableColumns, getTableName, is } from "drizzle-orm";
+import { PgTable } from "drizzle-orm/pg-core";
+import { db } from "@/db";
+import * as schema from "@/db/schema";
+import { canTransferData } from "@/lib/import-export/access";
+import { EXPORTABLE_TABLES } from "@/lib/import-export/config";
+import { toCsvRow } from "@/lib/import-export/csv";
+__XLSX_IMPORT__
+const BATCH_SIZE = 1000;
+
+function findTable(name: string) {
+  if (!EXPORTABLE_TABLES.includes(name)) return null;
+  return Object.values(schema).find((value): value is PgTable => is(value, PgTable) && getTableName(value) === name) ?? null;
+}
+
+/**
+ * `GET /api/export/[table]` downloads a table as CSV, streamed a batch at a time so large
+ * tables never sit in memory.__XLSX_DOC__
+ */
+export async function GET(request: Request, { params }: { params: Promise<{ table: string }> }) {
+  if (!(await canTransferData())) {
+    return new Response("Unauthorized", { status: 401 });
+  }
+
+  const { table: name } = await params;
+  const table = findTable(name);
+  if (!table) {
+    return new Response("Not found", { status: 404 });
+  }
+
+  const columns = Object.entries(getTableColumns(table));
+  const keys = columns.map(([key]) => key);
+  // A stable order so batches never skip or repeat rows
+  const orderBy = columns.filter(([, column]) => column.primary).map(([, column]) => asc(column));
+  const stamp = new Date().toISOString().slice(0, 10);
+__XLSX_BRANCH__
+  let offset = 0;
+  const encoder = new TextEncoder();
+  const stream = new ReadableStream<Uint8Array>({
+    start(controller) {
+      controller.enqueue(encoder.encode(toCsvRow(keys)));
+    },
+    async pull(controller) {
+      const rows: Record<string, unknown>[] = await db
+        .select()
+        .from(table)
+        .orderBy(...orderBy)
+        .limit(BATCH_SIZE)
+        .offset(offset);
+      offset += rows.length;
+
+      if (rows.length > 0) {
+        controller.enqueue(encoder.encode(rows.map((row) => toCsvRow(keys.map((key) => row[key]))).join("")));
+      }
+      if (rows.length < BATCH_SIZE) {
+        controller.close();
+      }
+    },
+  });
+
+  return new Response(stream, {
+    headers: {
+      "Content-Type": "text/csv; charset=utf-8",
+      "Content-Disposition": `attachment; filename="${name}-${stamp}.csv"`,
+      "Cache-Control": "no-store",
+    },
+  });
+}
+"#;
+
+const XLSX_IMPORT: &str = "import writeXlsxFile from \"write-excel-file/node\";\n";
+
+const XLSX_DOC: &str = "\n * `?format=xlsx` builds an Excel file in memory instead.";
+
+const XLSX_BRANCH: &str = r#"
+  if (new URL(request.url).searchParams.get("format") === "xlsx") {
+    const rows: Record<string, unknown>[] = await db.select().from(table).orderBy(...orderBy);
+    const cell = (value: unknown) =>
+      value === null || value === undefined
+        ? { value: null }
+        : value instanceof Date || typeof value === "string" || typeof value === "number" || typeof value === "boolean"
+          ? { value }
+          : { value: JSON.stringify(value) };
+    const buffer = await writeXlsxFile(
+      [keys.map((key) => ({ value: key, fontWeight: "bold" as const })), ...rows.map((row) => keys.map((key) => cell(row[key])))],
+      { buffer: true },
+    );
+    return new Response(new Uint8Array(buffer), {
+      headers: {
+        "Content-Type": "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "Content-Disposition": `attachment; filename="${name}-${stamp}.xlsx"`,
+        "Cache-Control": "no-store",
+      },
+    });
+  }
+"#;
+
+const IMPORTER: &str = r#""use client";
+
+import Papa from "papaparse";
+__READ_XLSX_IMPORT__import { useState } from "react";
+import { importRows } from "@/lib/import-export/actions";
+import { IMPORT_BATCH_SIZE } from "@/lib/import-export/config";
+import { toCsvRow } from "@/lib/import-export/csv";
+import { importSchemas, validateRows, type ImportTarget, type RowError } from "@/lib/import-export/schemas";
+
+type Parsed = {
+  fileName: string;
+  rows: Record<string, unknown>[];
+  errors: RowError[];
+};
+
+const TARGETS = Object.keys(importSchemas) as ImportTarget[];
+const SHOWN_ERRORS = 50;
+__PARSE_FILE__
+function downloadErrors(errors: RowError[]) {
+  const csv = toCsvRow(["row", "error"]) + errors.map((error) => toCsvRow([error.row, error.message])).join("");
+  const url = URL.createObjectURL(new Blob([csv], { type: "text/csv;charset=utf-8" }));
+  const link = document.createElement("a");
+  link.href = url;
+  link.download = "import-errors.csv";
+  link.click();
+  URL.revokeObjectURL(url);
+}
+
+/** Parses a file in the browser, reports invalid rows, then imports the valid ones in batches. */
+export function CsvImporter() {
+  const [target, setTarget] = useState<ImportTarget>(TARGETS[0]);
+  const [parsed, setParsed] = useState<Parsed | null>(null);
+  const [progress, setProgress] = useState<{ done: number; inserted: number } | null>(null);
+  const [importing, setImporting] = useState(false);
+  const [failure, setFailure] = useState<string | null>(null);
+
+  const { valid, errors: validationErrors } = parsed
+    ? validateRows(target, parsed.rows)
+    : { valid: [], errors: [] as RowError[] };
+  const errors = [...(parsed?.errors ?? []), ...validationErrors];
+
+  const onFile = async (file: File | undefined) => {
+    setParsed(null);
+    setProgress(null);
+    setFailure(null);
+    if (!file) return;
+
+    try {
+      const { rows, errors } = await parseFile(file);
+      setParsed({ fileName: file.name, rows, errors });
+    } catch (error) {
+      setFailure(error instanceof Error ? error.message : "Could not read the file");
+    }
+  };
+
+  const runImport = async () => {
+    if (!parsed) return;
+    setImporting(true);
+    setFailure(null);
+    setProgress({ done: 0, inserted: 0 });
+
+    try {
+      let inserted = 0;
+      for (let start = 0; start < parsed.rows.length; start += IMPORT_BATCH_SIZE) {
+        const batch = parsed.rows.slice(start, start + IMPORT_BATCH_SIZE);
+        const result = await importRows(target, batch, start + 2);
+        inserted += result.inserted;
+        setProgress({ done: start + batch.length, inserted });
+      }
+    } catch (error) {
+      setFailure(error instanceof Error ? error.message : "Import failed");
+    } finally {
+      setImporting(false);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="flex flex-wrap items-center gap-3">
+        <select
+          value={target}
+          onChange={(event) => setTarget(event.target.value as ImportTarget)}
+          disabled={importing}
+          className="rounded-md border bg-transparent px-3 py-2 text-sm"
+        >
+          {TARGETS.map((name) => (
+            <option key={name} value={name}>
+              {name}
+            </option>
+          ))}
+        </select>
+        <input
+          type="file"
+          accept="__ACCEPT__"
+          disabled={importing}
+          onChange={(event) => onFile(event.target.files?.[0])}
+          className="text-sm"
+        />
+      </div>
+      <p className="text-xs text-gray-500">
+        The first row holds column names: {Object.keys(importSchemas[target].shape).join(", ")}.
+      </p>
+
+      {parsed && (
+        <div className="space-y-3 rounded-md border p-4">
+          <p className="text-sm">
+            <span className="font-medium">{parsed.fileName}</span>: {valid.length} valid, {errors.length} with errors
+          </p>
+
+          {errors.length > 0 && (
+            <div className="space-y-2">
+              <table className="w-full text-left text-sm">
+                <thead>
+                  <tr className="border-b text-gray-500">
+                    <th className="py-1 pr-4 font-medium">Row</th>
+                    <th className="py-1 font-medium">Problem</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {errors.slice(0, SHOWN_ERRORS).map((error) => (
+                    <tr key={`${error.row}-${error.message}`} className="border-b last:border-0">
+                      <td className="py-1 pr-4 tabular-nums">{error.row}</td>
+                      <td className="py-1 text-red-600">{error.message}</td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+              <button type="button" onClick={() => downloadErrors(errors)} className="text-sm underline">
+                Download all {errors.length} errors
+              </button>
+            </div>
+          )}
+
+          {progress && (
+            <div className="space-y-1">
+              <progress value={progress.done} max={parsed.rows.length} className="w-full" />
+              <p className="text-xs text-gray-500">
+                {progress.done} of {parsed.rows.length} rows processed, {progress.inserted} imported
+              </p>
+            </div>
+          )}
+
+          <button
+            type="button"
+            onClick={runImport}
+            disabled={importing || valid.length === 0 || progress?.done === parsed.rows.length}
+            className="rounded-md bg-blue-600 px-4 py-2 text-sm font-medium text-white disabled:opacity-50"
+          >
+            {importing ? "Importing…" : `Import ${valid.length} rows`}
+          </button>
+        </div>
+      )}
+
+      {failure && <p className="text-sm text-red-600">{failure}</p>}
+    </div>
+  );
+}
+"#;
+
+const PARSE_CSV: &str = r#"
+function parseFile(file: File): Promise<Omit<Parsed, "fileName">> {
+  return new Promise((resolve, reject) => {
+    Papa.parse<Record<string, unknown>>(file, {
+      header: true,
+      skipEmptyLines: "greedy",
+      transformHeader: (header) => header.trim(),
+      complete: ({ data, errors }) =>
+        resolve({
+          rows: data,
+          errors: errors.map((error) => ({ row: (error.row ?? 0) + 2, message: error.message })),
+        }),
+      error: reject,
+    });
+  });
+}
+"#;
+
+const PARSE_CSV_OR_XLSX: &str = r#"
+async function parseFile(file: File): Promise<Omit<Parsed, "fileName">> {
+  if (!file.name.toLowerCase().endsWith(".xlsx")) {
+    return new Promise((resolve, reject) => {
+      Papa.parse<Record<string, unknown>>(file, {
+        header: true,
+        skipEmptyLines: "greedy",
+        transformHeader: (header) => header.trim(),
+        complete: ({ data, errors }) =>
+          resolve({
+            rows: data,
+            errors: errors.map((error) => ({ row: (error.row ?? 0) + 2, message: error.message })),
+          }),
+        error: reject,
+      });
+    });
+  }
+
+  // Excel cells keep their types, so text columns are turned back into strings for validation
+  const [header = [], ...body] = await readXlsxFile(file);
+  const keys = header.map((cell) => String(cell ?? "").trim());
+  const rows = body.map((cells) =>
+    Object.fromEntries(keys.map((key, index) => [key, cells[index] === null ? "" : String(cells[index])])),
+  );
+  return { rows, errors: [] };
+}
+"#;
+
+const PAGE: &str = r#"import type { Metadata } from "next";
+import { CsvImporter } from "@/components/import-export/csv-importer";
+import { EXPORTABLE_TABLES } from "@/lib/import-export/config";
+
+export const metadata: Metadata = {
+  title: "Import and export",
+};
+
+export default function ImportExportPage() {
+  return (
+    <main className="mx-auto max-w-3xl space-y-10 p-8">
+      <section className="space-y-4">
+        <div>
+          <h1 className="text-2xl font-semibold">Import</h1>
+          <p className="text-sm text-gray-500">Rows are checked in your browser before anything is saved.</p>
+        </div>
+        <CsvImporter />
+      </section>
+
+      <section className="space-y-3">
+        <h2 className="text-xl font-semibold">Export</h2>
+        {EXPORTABLE_TABLES.length === 0 ? (
+          <p className="text-sm text-gray-500">Add tables to EXPORTABLE_TABLES to export them.</p>
+        ) : (
+          <ul className="space-y-2 text-sm">
+            {EXPORTABLE_TABLES.map((table) => (
+              <li key={table} className="flex items-center gap-3">
+                <span className="font-medium">{table}</span>
+                <a href={`/api/export/${table}`} className="underline">
+                  CSV
+                </a>__XLSX_LINK__
+              </li>
+            ))}
+          </ul>
+        )}
+      </section>
+    </main>
+  );
+}
+"#;
+
+const XLSX_LINK: &str = r#"
+                <a href={`/api/export/${table}?format=xlsx`} className="underline">
+                  Excel
+                </a>"#;
+
+pub async fn add_import_export() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let schema_path = project_structure.get_db_path().join("schema.ts");
+    let schema = std::fs::read_to_string(&schema_path).unwrap_or_default();
+    if !schema.contains("drizzle-orm/pg-core") {
+        println!(
+            "{}",
+            style("Import and export read and write drizzle tables on Postgres. Run `nstack add --feature drizzle` first.").yellow()
+        );
+        return Ok(());
+    }
+    let has_posts = schema.contains("export const postsTable ");
+
+    let excel = prompt::confirm("excel", "Also import and export Excel (.xlsx) files?", false)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing papaparse and zod...");
+
+    let mut dependencies = vec!["papaparse", "zod"];
+    if excel {
+        dependencies.extend(["read-excel-file", "write-excel-file"]);
+    }
+    package_manager.install(&dependencies, false)?;
+    package_manager.install(&["@types/papaparse"], true)?;
+
+    pb.set_message("Creating validation schemas and import actions...");
+
+    let lib_path = project_structure.get_lib_path().join("import-export");
+    change_set::create_dir_all(&lib_path).context("Failed to create lib/import-export directory")?;
+    let exportable = if has_posts { "\"posts\"" } else { "" };
+    file_writer::write(lib_path.join("config.ts"), CONFIG.replace("__EXPORTABLE__", exportable))
+        .context("Failed to create config.ts")?;
+    file_writer::write(lib_path.join("csv.ts"), CSV).context("Failed to create csv.ts")?;
+    file_writer::write(lib_path.join("schemas.ts"), SCHEMAS).context("Failed to create schemas.ts")?;
+    file_writer::write(lib_path.join("importers.ts"), IMPORTERS).context("Failed to create importers.ts")?;
+    file_writer::write(lib_path.join("actions.ts"), ACTIONS).context("Failed to create actions.ts")?;
+
+    let auth_library = manifest::installed_auth_library();
+    let (session_imports, session_user) = admin::session_user(auth_library.map(|(package, _)| package));
+    let access = ACCESS
+        .replace("__SESSION_IMPORTS__", session_imports)
+        .replace("__SESSION_USER__", session_user);
+    file_writer::write(lib_path.join("access.ts"), access.trim_start()).context("Failed to create access.ts")?;
+
+    pb.set_message("Creating the export route...");
+
+    let route_path = project_structure.get_api_path().join("export").join("[table]");
+    change_set::create_dir_all(&route_path).context("Failed to create export route directory")?;
+    let route = if excel {
+        CSV_EXPORT_ROUTE
+            .replace("__XLSX_IMPORT__", XLSX_IMPORT)
+            .replace("__XLSX_DOC__", XLSX_DOC)
+            .replace("__XLSX_BRANCH__", XLSX_BRANCH)
+    } else {
+        CSV_EXPORT_ROUTE
+            .replace("__XLSX_IMPORT__", "")
+            .replace("__XLSX_DOC__", "")
+            .replace("__XLSX_BRANCH__", "")
+            .replace("GET(request: Request", "GET(_request: Request")
+    };
+    file_writer::write(route_path.join("route.ts"), route).context("Failed to create route.ts")?;
+
+    pb.set_message("Creating the import page...");
+
+    let components_path = project_structure.get_components_path().join("import-export");
+    change_set::create_dir_all(&components_path).context("Failed to create components/import-export directory")?;
+    let importer = IMPORTER
+        .replace("__READ_XLSX_IMPORT__", if excel { "import readXlsxFile from \"read-excel-file\";\n" } else { "" })
+        .replace("__PARSE_FILE__", if excel { PARSE_CSV_OR_XLSX } else { PARSE_CSV })
+        .replace("__ACCEPT__", if excel { ".csv,text/csv,.xlsx" } else { ".csv,text/csv" });
+    file_writer::write(components_path.join("csv-importer.tsx"), importer)
+        .context("Failed to create csv-importer.tsx")?;
+
+    let page_path = root_layout::routes_dir(&project_structure).join("import");
+    change_set::create_dir_all(&page_path).context("Failed to create import route directory")?;
+    file_writer::write(page_path.join("page.tsx"), PAGE.replace("__XLSX_LINK__", if excel { XLSX_LINK } else { "" }))
+        .context("Failed to create page.tsx")?;
+
+    pb.finish_with_message("Import and export added successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/schemas.ts - Zod row schemas per import target", lib_path.display());
+    println!("• {}/actions.ts - importRows server action for batch inserts", lib_path.display());
+    println!("• {}/config.ts - Exportable tables and the import batch size", lib_path.display());
+    println!(
+        "• {}/route.ts - GET /api/export/[table], streamed CSV{}",
+        route_path.display(),
+        if excel { " or Excel" } else { "" }
+    );
+    println!("• {}/csv-importer.tsx - Parsing, error report and import progress", components_path.display());
+    println!("• {}/page.tsx - Import and export page", page_path.display());
+
+    if !has_posts {
+        println!(
+            "{}",
+            style("No postsTable found; point the posts entries in schemas.ts and importers.ts at your own table").yellow()
+        );
+    }
+    if auth_library.is_none() {
+        println!(
+            "{}",
+            style("No auth library found; imports and exports only work in development until getSessionUser() in access.ts returns the signed-in user").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Open /import and upload a CSV with a header row");
+    println!("2. Add import targets in schemas.ts and importers.ts");
+    println!("3. List the tables to export in EXPORTABLE_TABLES in config.ts");
+
+    Ok(())
+}
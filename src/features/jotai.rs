@@ -0,0 +1,172 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+use crate::root_layout;
+
+const JOTAI_PROVIDER: &str = r#""use client";
+
+import { Provider } from "jotai";
+import type { ReactNode } from "react";
+
+/**
+ * Gives each request its own atom store so server-rendered values never leak between users.
+ */
+export function JotaiProvider({ children }: { children: ReactNode }) {
+  return <Provider>{children}</Provider>;
+}
+"#;
+
+const ATOMS: &str = r#"import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+
+export type Theme = "light" | "dark" | "system";
+
+// Persisted in localStorage under the given key
+export const themeAtom = atomWithStorage<Theme>("theme-preference", "system");
+
+export const countAtom = atom(0);
+
+// Derived atom: recomputed whenever countAtom changes
+export const doubledCountAtom = atom((get) => get(countAtom) * 2);
+
+export type CurrentUser = {
+  id: string;
+  name: string;
+};
+
+// Filled from server-rendered data with <HydrateAtoms />
+export const currentUserAtom = atom<CurrentUser | null>(null);
+"#;
+
+const HYDRATE_ATOMS: &str = r#""use client";
+
+import type { WritableAtom } from "jotai";
+import { useHydrateAtoms } from "jotai/utils";
+import type { ReactNode } from "react";
+
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+type AnyWritableAtom = WritableAtom<unknown, any[], unknown>;
+
+type HydrateAtomsProps = {
+  initialValues: Iterable<readonly [AnyWritableAtom, unknown]>;
+  children: ReactNode;
+};
+
+/**
+ * Seeds atoms with data fetched in a server component, before the first client render:
+ *
+ *   const user = await getCurrentUser();
+ *   <HydrateAtoms initialValues={[[currentUserAtom, user]]}>...</HydrateAtoms>
+ */
+export function HydrateAtoms({ initialValues, children }: HydrateAtomsProps) {
+  useHydrateAtoms(new Map(initialValues));
+  return children;
+}
+"#;
+
+const ATOMS_DEMO: &str = r#""use client";
+
+import { useAtom, useAtomValue } from "jotai";
+import { countAtom, doubledCountAtom, themeAtom, type Theme } from "@/lib/atoms";
+
+export function AtomsDemo() {
+  const [count, setCount] = useAtom(countAtom);
+  const doubled = useAtomValue(doubledCountAtom);
+  const [theme, setTheme] = useAtom(themeAtom);
+
+  return (
+    <div className="flex flex-col gap-4 rounded-lg border p-4">
+      <div className="flex items-center gap-2">
+        <button type="button" className="rounded border px-3 py-1" onClick={() => setCount((c) => c + 1)}>
+          Count: {count}
+        </button>
+        <span className="text-sm text-muted-foreground">Doubled: {doubled}</span>
+      </div>
+      <label className="flex items-center gap-2 text-sm">
+        Theme preference (persisted)
+        <select
+          className="rounded border px-2 py-1"
+          value={theme}
+          onChange={(event) => setTheme(event.target.value as Theme)}
+        >
+          <option value="system">System</option>
+          <option value="light">Light</option>
+          <option value="dark">Dark</option>
+        </select>
+      </label>
+    </div>
+  );
+}
+"#;
+
+pub async fn add_jotai() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let wrap_layout = prompt::confirm("wrap_layout", "Wrap the root layout with JotaiProvider?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing jotai...");
+
+    package_manager.install(&["jotai"], false)?;
+
+    pb.set_message("Creating atoms and providers...");
+
+    let lib_path = project_structure.get_lib_path();
+    change_set::create_dir_all(&lib_path).context("Failed to create lib directory")?;
+    file_writer::write(lib_path.join("atoms.ts"), ATOMS)
+        .context("Failed to create atoms.ts")?;
+
+    let components_path = project_structure.get_components_path();
+    let providers_path = components_path.join("providers");
+    change_set::create_dir_all(&providers_path).context("Failed to create providers directory")?;
+    file_writer::write(providers_path.join("jotai-provider.tsx"), JOTAI_PROVIDER)
+        .context("Failed to create jotai-provider.tsx")?;
+    file_writer::write(providers_path.join("hydrate-atoms.tsx"), HYDRATE_ATOMS)
+        .context("Failed to create hydrate-atoms.tsx")?;
+    file_writer::write(components_path.join("atoms-demo.tsx"), ATOMS_DEMO)
+        .context("Failed to create atoms-demo.tsx")?;
+
+    if wrap_layout {
+        pb.set_message("Adding JotaiProvider to the root layout...");
+
+        root_layout::add_import(
+            &project_structure,
+            "import { JotaiProvider } from \"@/components/providers/jotai-provider\";",
+        )?;
+        root_layout::wrap_children(&project_structure, "<JotaiProvider>", "</JotaiProvider>")?;
+    }
+
+    pb.finish_with_message("Jotai set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/atoms.ts - Example atoms, including atomWithStorage", lib_path.display());
+    println!("• {}/jotai-provider.tsx - Per-request atom store", providers_path.display());
+    println!("• {}/hydrate-atoms.tsx - Seeds atoms with server-rendered data", providers_path.display());
+    println!("• {}/atoms-demo.tsx - Example component using the atoms", components_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    let mut step = 1;
+    if !wrap_layout {
+        println!("{}. Wrap your layout with <JotaiProvider>", step);
+        step += 1;
+    }
+    println!("{}. Render <AtomsDemo /> on a page to try it out", step);
+    println!("{}. Pass server data to atoms with <HydrateAtoms initialValues={{...}}>", step + 1);
+
+    Ok(())
+}
@@ -0,0 +1,476 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::gitignore;
+use crate::manifest::ProjectManifest;
+use crate::package_json;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+
+const HEALTH_ROUTE: &str = r#"import { NextResponse } from "next/server";
+
+export const dynamic = "force-dynamic";
+
+// Liveness probe: the process is up and serving requests
+export function GET() {
+  return NextResponse.json({ status: "ok" });
+}
+"#;
+
+const READY_ROUTE: &str = r#"import { NextResponse } from "next/server";
+
+export const dynamic = "force-dynamic";
+
+// Readiness probe: the app can serve traffic
+export async function GET() {
+  return NextResponse.json({ status: "ready" });
+}
+"#;
+
+const READY_ROUTE_WITH_DB: &str = r#"import { NextResponse } from "next/server";
+import { sql } from "drizzle-orm";
+import { db } from "@/db";
+
+export const dynamic = "force-dynamic";
+
+// Readiness probe: only route traffic to pods that can reach the database
+export async function GET() {
+  try {
+    await db.execute(sql`select 1`);
+    return NextResponse.json({ status: "ready" });
+  } catch {
+    return NextResponse.json({ status: "unavailable" }, { status: 503 });
+  }
+}
+"#;
+
+const DEPLOYMENT: &str = r#"apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: __NAME__
+  labels:
+    app.kubernetes.io/name: __NAME__
+spec:
+  replicas: __REPLICAS__
+  selector:
+    matchLabels:
+      app.kubernetes.io/name: __NAME__
+  template:
+    metadata:
+      labels:
+        app.kubernetes.io/name: __NAME__
+    spec:
+      securityContext:
+        runAsNonRoot: true
+        runAsUser: 1001
+      containers:
+        - name: __NAME__
+          image: __IMAGE__
+          ports:
+            - name: http
+              containerPort: 3000
+          envFrom:
+            - configMapRef:
+                name: __NAME__-config
+            - secretRef:
+                name: __NAME__-secrets
+          livenessProbe:
+            httpGet:
+              path: /api/health
+              port: http
+            initialDelaySeconds: 10
+            periodSeconds: 15
+          readinessProbe:
+            httpGet:
+              path: /api/ready
+              port: http
+            initialDelaySeconds: 5
+            periodSeconds: 10
+          resources:
+            requests:
+              cpu: 100m
+              memory: 256Mi
+            limits:
+              memory: 512Mi
+"#;
+
+const SERVICE: &str = r#"apiVersion: v1
+kind: Service
+metadata:
+  name: __NAME__
+spec:
+  selector:
+    app.kubernetes.io/name: __NAME__
+  ports:
+    - name: http
+      port: 80
+      targetPort: http
+"#;
+
+const INGRESS: &str = r#"apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: __NAME__
+spec:
+  ingressClassName: nginx
+  rules:
+    - host: __HOST__
+      http:
+        paths:
+          - path: /
+            pathType: Prefix
+            backend:
+              service:
+                name: __NAME__
+                port:
+                  name: http
+"#;
+
+const KUSTOMIZATION: &str = r#"apiVersion: kustomize.config.k8s.io/v1beta1
+kind: Kustomization
+resources:
+  - configmap.yaml
+  - deployment.yaml
+  - service.yaml
+  - ingress.yaml
+# secret.yaml is created from secret.example.yaml and kept out of version control
+"#;
+
+const CHART_YAML: &str = r#"apiVersion: v2
+name: __NAME__
+description: Helm chart for the __NAME__ Next.js application
+type: application
+version: 0.1.0
+appVersion: "1.0.0"
+"#;
+
+const HELM_HELPERS: &str = r#"{{- define "app.name" -}}
+{{- default .Chart.Name .Values.nameOverride | trunc 63 | trimSuffix "-" -}}
+{{- end -}}
+
+{{- define "app.labels" -}}
+app.kubernetes.io/name: {{ include "app.name" . }}
+app.kubernetes.io/instance: {{ .Release.Name }}
+{{- end -}}
+"#;
+
+const HELM_DEPLOYMENT: &str = r#"apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: {{ include "app.name" . }}
+  labels:
+    {{- include "app.labels" . | nindent 4 }}
+spec:
+  replicas: {{ .Values.replicaCount }}
+  selector:
+    matchLabels:
+      {{- include "app.labels" . | nindent 6 }}
+  template:
+    metadata:
+      labels:
+        {{- include "app.labels" . | nindent 8 }}
+      annotations:
+        checksum/config: {{ include (print $.Template.BasePath "/configmap.yaml") . | sha256sum }}
+    spec:
+      securityContext:
+        runAsNonRoot: true
+        runAsUser: 1001
+      containers:
+        - name: {{ include "app.name" . }}
+          image: "{{ .Values.image.repository }}:{{ .Values.image.tag }}"
+          imagePullPolicy: {{ .Values.image.pullPolicy }}
+          ports:
+            - name: http
+              containerPort: 3000
+          envFrom:
+            - configMapRef:
+                name: {{ include "app.name" . }}-config
+            - secretRef:
+                name: {{ include "app.name" . }}-secrets
+          livenessProbe:
+            httpGet:
+              path: /api/health
+              port: http
+          readinessProbe:
+            httpGet:
+              path: /api/ready
+              port: http
+          resources:
+            {{- toYaml .Values.resources | nindent 12 }}
+"#;
+
+const HELM_SERVICE: &str = r#"apiVersion: v1
+kind: Service
+metadata:
+  name: {{ include "app.name" . }}
+  labels:
+    {{- include "app.labels" . | nindent 4 }}
+spec:
+  type: {{ .Values.service.type }}
+  selector:
+    {{- include "app.labels" . | nindent 4 }}
+  ports:
+    - name: http
+      port: {{ .Values.service.port }}
+      targetPort: http
+"#;
+
+const HELM_INGRESS: &str = r#"{{- if .Values.ingress.enabled }}
+apiVersion: networking.k8s.io/v1
+kind: Ingress
+metadata:
+  name: {{ include "app.name" . }}
+  labels:
+    {{- include "app.labels" . | nindent 4 }}
+spec:
+  ingressClassName: {{ .Values.ingress.className }}
+  rules:
+    - host: {{ .Values.ingress.host }}
+      http:
+        paths:
+          - path: /
+            pathType: Prefix
+            backend:
+              service:
+                name: {{ include "app.name" . }}
+                port:
+                  name: http
+{{- end }}
+"#;
+
+const HELM_CONFIGMAP: &str = r#"apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: {{ include "app.name" . }}-config
+data:
+  {{- range $key, $value := .Values.config }}
+  {{ $key }}: {{ $value | quote }}
+  {{- end }}
+"#;
+
+const HELM_SECRET: &str = r#"apiVersion: v1
+kind: Secret
+metadata:
+  name: {{ include "app.name" . }}-secrets
+type: Opaque
+stringData:
+  {{- range $key, $value := .Values.secrets }}
+  {{ $key }}: {{ $value | quote }}
+  {{- end }}
+"#;
+
+// Only NEXT_PUBLIC_ variables are safe to keep in a ConfigMap; everything else is treated as secret
+fn split_env_vars(required_env: &[(String, String)]) -> (Vec<String>, Vec<String>) {
+    let mut config = Vec::new();
+    let mut secrets = Vec::new();
+    for (_, var) in required_env {
+        if var.starts_with("NEXT_PUBLIC_") {
+            config.push(var.clone());
+        } else {
+            secrets.push(var.clone());
+        }
+    }
+    (config, secrets)
+}
+
+fn configmap(name: &str, config_vars: &[String]) -> String {
+    let mut content = format!(
+        "apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: {}-config\ndata:\n  NODE_ENV: \"production\"\n",
+        name
+    );
+    for var in config_vars {
+        content.push_str(&format!("  {}: \"\"\n", var));
+    }
+    content
+}
+
+fn secret_example(name: &str, secret_vars: &[String]) -> String {
+    let mut content = format!(
+        "# Copy to secret.yaml, fill in the values and apply with: kubectl apply -f k8s/secret.yaml\napiVersion: v1\nkind: Secret\nmetadata:\n  name: {}-secrets\ntype: Opaque\nstringData:\n",
+        name
+    );
+    if secret_vars.is_empty() {
+        content.push_str("  {}\n");
+    }
+    for var in secret_vars {
+        content.push_str(&format!("  {}: \"<change-me>\"\n", var));
+    }
+    content
+}
+
+fn helm_values(image: &str, host: &str, replicas: &str, config_vars: &[String], secret_vars: &[String]) -> String {
+    let (repository, tag) = image.rsplit_once(':').unwrap_or((image, "latest"));
+
+    let mut content = format!(
+        r#"replicaCount: {}
+
+image:
+  repository: {}
+  tag: "{}"
+  pullPolicy: IfNotPresent
+
+service:
+  type: ClusterIP
+  port: 80
+
+ingress:
+  enabled: true
+  className: nginx
+  host: {}
+
+resources:
+  requests:
+    cpu: 100m
+    memory: 256Mi
+  limits:
+    memory: 512Mi
+
+config:
+  NODE_ENV: "production"
+"#,
+        replicas, repository, tag, host
+    );
+    for var in config_vars {
+        content.push_str(&format!("  {}: \"\"\n", var));
+    }
+
+    content.push_str("\n# Override with --set or a values file that is kept out of version control\nsecrets:");
+    if secret_vars.is_empty() {
+        content.push_str(" {}\n");
+    } else {
+        content.push('\n');
+        for var in secret_vars {
+            content.push_str(&format!("  {}: \"\"\n", var));
+        }
+    }
+    content
+}
+
+pub async fn add_k8s() -> Result<()> {
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    if !ProjectManifest::is_feature_installed("docker") {
+        println!(
+            "{}",
+            style("The manifests expect a container image. Run `nstack add --feature docker` to generate a Dockerfile.").yellow()
+        );
+    }
+
+    let name = package_json::package_name().unwrap_or_else(|| "my-app".to_string());
+
+    let image = prompt::input("image", "Container image", &format!("ghcr.io/your-org/{}:latest", name))?;
+    let host = prompt::input("host", "Ingress host", &format!("{}.example.com", name))?;
+    let replicas = prompt::input_validated("replicas", "Replicas", "2", |input| {
+        input.parse::<u32>().map(|_| ()).map_err(|_| "Enter a whole number")
+    })?;
+    let generate_helm = prompt::confirm("helm", "Also generate a Helm chart?", false)?;
+
+    let required_env = ProjectManifest::load()?.required_env_vars();
+    let (config_vars, secret_vars) = split_env_vars(&required_env);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Creating health endpoints...");
+
+    let api_path = project_structure.get_app_path().join("api");
+    change_set::create_dir_all(api_path.join("health"))
+        .context("Failed to create api/health directory")?;
+    file_writer::write(api_path.join("health/route.ts"), HEALTH_ROUTE)
+        .context("Failed to create health route")?;
+
+    let ready_route = if ProjectManifest::is_feature_installed("drizzle") {
+        READY_ROUTE_WITH_DB
+    } else {
+        READY_ROUTE
+    };
+    change_set::create_dir_all(api_path.join("ready"))
+        .context("Failed to create api/ready directory")?;
+    file_writer::write(api_path.join("ready/route.ts"), ready_route)
+        .context("Failed to create ready route")?;
+
+    pb.set_message("Creating Kubernetes manifests...");
+
+    let render = |template: &str| {
+        template
+            .replace("__NAME__", &name)
+            .replace("__IMAGE__", &image)
+            .replace("__HOST__", &host)
+            .replace("__REPLICAS__", &replicas)
+    };
+
+    change_set::create_dir_all(project_file("k8s")).context("Failed to create k8s directory")?;
+    let manifests = [
+        ("deployment.yaml", render(DEPLOYMENT)),
+        ("service.yaml", render(SERVICE)),
+        ("ingress.yaml", render(INGRESS)),
+        ("configmap.yaml", configmap(&name, &config_vars)),
+        ("secret.example.yaml", secret_example(&name, &secret_vars)),
+        ("kustomization.yaml", KUSTOMIZATION.to_string()),
+    ];
+    for (file, content) in manifests {
+        file_writer::write(project_file("k8s").join(file), content)
+            .context(format!("Failed to create k8s/{}", file))?;
+    }
+
+    if generate_helm {
+        pb.set_message("Creating Helm chart...");
+
+        let chart_path = format!("helm/{}", name);
+        change_set::create_dir_all(format!("{}/templates", chart_path))
+            .context("Failed to create Helm chart directory")?;
+
+        let chart_files = [
+            ("Chart.yaml", render(CHART_YAML)),
+            ("values.yaml", helm_values(&image, &host, &replicas, &config_vars, &secret_vars)),
+            ("templates/_helpers.tpl", HELM_HELPERS.to_string()),
+            ("templates/deployment.yaml", HELM_DEPLOYMENT.to_string()),
+            ("templates/service.yaml", HELM_SERVICE.to_string()),
+            ("templates/ingress.yaml", HELM_INGRESS.to_string()),
+            ("templates/configmap.yaml", HELM_CONFIGMAP.to_string()),
+            ("templates/secret.yaml", HELM_SECRET.to_string()),
+        ];
+        for (file, content) in chart_files {
+            file_writer::write(format!("{}/{}", chart_path, file), content)
+                .context(format!("Failed to create {}/{}", chart_path, file))?;
+        }
+    }
+
+    gitignore::add_entries("Kubernetes", &["k8s/secret.yaml"])?;
+
+    pb.finish_with_message("Kubernetes manifests created!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/health/route.ts - Liveness probe endpoint", api_path.display());
+    println!("• {}/ready/route.ts - Readiness probe endpoint", api_path.display());
+    println!("• k8s/ - Deployment, Service, Ingress, ConfigMap and Secret template");
+    if generate_helm {
+        println!("• helm/{}/ - Helm chart", name);
+    }
+
+    if !secret_vars.is_empty() {
+        println!("\n{}", style("Secrets required by installed features:").cyan().bold());
+        for var in &secret_vars {
+            println!("• {}", var);
+        }
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Build and push the image: docker build -t {} . && docker push {}", image, image);
+    println!("2. cp k8s/secret.example.yaml k8s/secret.yaml and fill in the values");
+    println!("3. kubectl apply -f k8s/secret.yaml && kubectl apply -k k8s/");
+    if generate_helm {
+        println!("   or: helm install {} ./helm/{}", name, name);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,511 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::file_writer;
+use crate::manifest::ProjectManifest;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+use crate::root_layout;
+
+const CONTENT: &str = r##"import { Gauge, Layers, LockKeyhole, Palette, Rocket, Sparkles, type LucideIcon } from "lucide-react";
+
+// Everything the landing page says lives here, so copy changes never touch the components
+export const landing = {
+  name: "__SITE_NAME__",
+  tagline: "__TAGLINE__",
+  description: "Launch faster with a production-ready stack, sensible defaults and the pieces every product needs.",
+  announcement: "Now in public beta",
+  cta: { label: "Get started", href: "#pricing" },
+};
+
+export const features: { title: string; description: string; icon: LucideIcon }[] = [
+  { title: "Fast by default", description: "Server rendering and streaming keep every page quick.", icon: Gauge },
+  { title: "Composable", description: "Small, typed building blocks you can rearrange freely.", icon: Layers },
+  { title: "Secure", description: "Authentication and sensible headers out of the box.", icon: LockKeyhole },
+  { title: "Themeable", description: "Design tokens with light and dark modes built in.", icon: Palette },
+  { title: "Ready to ship", description: "CI, previews and deployments wired from day one.", icon: Rocket },
+  { title: "Delightful", description: "Polished details your users will notice.", icon: Sparkles },
+];
+
+export type Plan = {
+  name: string;
+  price: string;
+  period?: string;
+  description: string;
+  features: string[];
+  cta: string;
+  href: string;
+  featured?: boolean;
+};
+
+export const plans: Plan[] = [
+  {
+    name: "Starter",
+    price: "$0",
+    description: "For side projects and trying things out.",
+    features: ["1 project", "Community support", "Basic analytics"],
+    cta: "Start for free",
+    href: "#",
+  },
+  {
+    name: "Pro",
+    price: "$19",
+    period: "month",
+    description: "For growing products and small teams.",
+    features: ["Unlimited projects", "Priority support", "Advanced analytics", "Custom domains"],
+    cta: "Start a trial",
+    href: "#",
+    featured: true,
+  },
+  {
+    name: "Enterprise",
+    price: "Custom",
+    description: "For organizations with advanced needs.",
+    features: ["SSO and audit logs", "Dedicated support", "SLA", "On-premise option"],
+    cta: "Contact sales",
+    href: "#",
+  },
+];
+
+export const faqs: { question: string; answer: string }[] = [
+  { question: "Is there a free plan?", answer: "Yes. The Starter plan is free forever for personal projects." },
+  { question: "Can I cancel anytime?", answer: "Plans are billed monthly and you can cancel from your account at any time." },
+  { question: "Do you offer discounts?", answer: "Students, non-profits and open-source maintainers get 50% off. Get in touch." },
+  { question: "Where is my data stored?", answer: "In the region you choose when creating a project, encrypted at rest." },
+];
+
+export const footerLinks: { title: string; links: { label: string; href: string }[] }[] = [
+  { title: "Product", links: [{ label: "Features", href: "#features" }, { label: "Pricing", href: "#pricing" }, { label: "FAQ", href: "#faq" }] },
+  { title: "Company", links: [{ label: "About", href: "#" }, { label: "Blog", href: "#" }, { label: "Careers", href: "#" }] },
+  { title: "Legal", links: [{ label: "Privacy", href: "#" }, { label: "Terms", href: "#" }] },
+];
+"##;
+
+const HEADER: &str = r##"import Link from "next/link";
+import { Button } from "@/components/ui/button";
+__THEME_TOGGLE_IMPORT__import { landing } from "@/lib/landing";
+
+export function SiteHeader() {
+  return (
+    <header className="sticky top-0 z-40 border-b bg-background/80 backdrop-blur">
+      <div className="container mx-auto flex h-16 items-center justify-between px-4">
+        <Link href="/" className="font-semibold">
+          {landing.name}
+        </Link>
+        <nav className="hidden items-center gap-6 text-sm text-muted-foreground md:flex">
+          <a href="#features" className="hover:text-foreground">
+            Features
+          </a>
+          <a href="#pricing" className="hover:text-foreground">
+            Pricing
+          </a>
+          <a href="#faq" className="hover:text-foreground">
+            FAQ
+          </a>
+        </nav>
+        <div className="flex items-center gap-2">
+__THEME_TOGGLE__          <Button asChild size="sm">
+            <a href={landing.cta.href}>{landing.cta.label}</a>
+          </Button>
+        </div>
+      </div>
+    </header>
+  );
+}
+"##;
+
+const HERO: &str = r##"import { ArrowRight } from "lucide-react";
+__BADGE_IMPORT__import { Button } from "@/components/ui/button";
+import { landing } from "@/lib/landing";
+
+export function Hero() {
+  return (
+    <section className="container mx-auto flex flex-col items-center gap-6 px-4 py-24 text-center md:py-32">
+__BADGE__      <h1 className="max-w-3xl text-4xl font-bold tracking-tight text-balance sm:text-6xl">{landing.tagline}</h1>
+      <p className="max-w-2xl text-lg text-muted-foreground">{landing.description}</p>
+      <div className="flex flex-col gap-3 sm:flex-row">
+        <Button asChild size="lg">
+          <a href={landing.cta.href}>
+            {landing.cta.label}
+            <ArrowRight />
+          </a>
+        </Button>
+        <Button asChild size="lg" variant="outline">
+          <a href="#features">See features</a>
+        </Button>
+      </div>
+    </section>
+  );
+}
+"##;
+
+const FEATURES: &str = r#"import { Card, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { features } from "@/lib/landing";
+
+export function Features() {
+  return (
+    <section id="features" className="border-t bg-muted/30 py-24">
+      <div className="container mx-auto px-4">
+        <div className="mx-auto mb-12 max-w-2xl text-center">
+          <h2 className="text-3xl font-bold tracking-tight sm:text-4xl">Everything you need</h2>
+          <p className="mt-4 text-muted-foreground">All the essentials, none of the busywork.</p>
+        </div>
+        <div className="grid gap-6 sm:grid-cols-2 lg:grid-cols-3">
+          {features.map((feature) => (
+            <Card key={feature.title}>
+              <CardHeader>
+                <feature.icon className="mb-2 size-8 text-primary" />
+                <CardTitle>{feature.title}</CardTitle>
+                <CardDescription>{feature.description}</CardDescription>
+              </CardHeader>
+            </Card>
+          ))}
+        </div>
+      </div>
+    </section>
+  );
+}
+"#;
+
+const PRICING: &str = r#"import { Check } from "lucide-react";
+__BEAM_IMPORT__import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardFooter, CardHeader, CardTitle } from "@/components/ui/card";
+import { plans } from "@/lib/landing";
+import { cn } from "@/lib/utils";
+
+export function Pricing() {
+  return (
+    <section id="pricing" className="py-24">
+      <div className="container mx-auto px-4">
+        <div className="mx-auto mb-12 max-w-2xl text-center">
+          <h2 className="text-3xl font-bold tracking-tight sm:text-4xl">Simple, transparent pricing</h2>
+          <p className="mt-4 text-muted-foreground">Start free and upgrade when you grow.</p>
+        </div>
+        <div className="grid gap-6 lg:grid-cols-3">
+          {plans.map((plan) => (
+            <Card key={plan.name} className={cn("relative flex flex-col overflow-hidden", plan.featured && "border-primary shadow-lg")}>
+              <CardHeader>
+                <div className="flex items-center justify-between">
+                  <CardTitle>{plan.name}</CardTitle>
+                  {plan.featured && <Badge>Popular</Badge>}
+                </div>
+                <CardDescription>{plan.description}</CardDescription>
+                <p className="pt-4">
+                  <span className="text-4xl font-bold">{plan.price}</span>
+                  {plan.period && <span className="text-muted-foreground">/{plan.period}</span>}
+                </p>
+              </CardHeader>
+              <CardContent className="flex-1">
+                <ul className="space-y-2 text-sm">
+                  {plan.features.map((feature) => (
+                    <li key={feature} className="flex items-center gap-2">
+                      <Check className="size-4 text-primary" />
+                      {feature}
+                    </li>
+                  ))}
+                </ul>
+              </CardContent>
+              <CardFooter>
+                <Button asChild className="w-full" variant={plan.featured ? "default" : "outline"}>
+                  <a href={plan.href}>{plan.cta}</a>
+                </Button>
+              </CardFooter>
+__BEAM__            </Card>
+          ))}
+        </div>
+      </div>
+    </section>
+  );
+}
+"#;
+
+const FAQ: &str = r#"import { Accordion, AccordionContent, AccordionItem, AccordionTrigger } from "@/components/ui/accordion";
+import { faqs } from "@/lib/landing";
+
+export function Faq() {
+  return (
+    <section id="faq" className="border-t bg-muted/30 py-24">
+      <div className="container mx-auto max-w-3xl px-4">
+        <h2 className="mb-8 text-center text-3xl font-bold tracking-tight sm:text-4xl">Frequently asked questions</h2>
+        <Accordion type="single" collapsible className="w-full">
+          {faqs.map((faq, index) => (
+            <AccordionItem key={faq.question} value={`item-${index}`}>
+              <AccordionTrigger>{faq.question}</AccordionTrigger>
+              <AccordionContent className="text-muted-foreground">{faq.answer}</AccordionContent>
+            </AccordionItem>
+          ))}
+        </Accordion>
+      </div>
+    </section>
+  );
+}
+"#;
+
+const FOOTER: &str = r#"import Link from "next/link";
+import { footerLinks, landing } from "@/lib/landing";
+
+export function SiteFooter() {
+  return (
+    <footer className="border-t py-12">
+      <div className="container mx-auto grid gap-8 px-4 sm:grid-cols-2 lg:grid-cols-4">
+        <div>
+          <p className="font-semibold">{landing.name}</p>
+          <p className="mt-2 text-sm text-muted-foreground">{landing.tagline}</p>
+        </div>
+        {footerLinks.map((group) => (
+          <div key={group.title}>
+            <p className="text-sm font-medium">{group.title}</p>
+            <ul className="mt-3 space-y-2 text-sm text-muted-foreground">
+              {group.links.map((link) => (
+                <li key={link.label}>
+                  <Link href={link.href} className="hover:text-foreground">
+                    {link.label}
+                  </Link>
+                </li>
+              ))}
+            </ul>
+          </div>
+        ))}
+      </div>
+      <p className="container mx-auto mt-12 px-4 text-sm text-muted-foreground">
+        © {new Date().getFullYear()} {landing.name}. All rights reserved.
+      </p>
+    </footer>
+  );
+}
+"#;
+
+const PAGE: &str = r#"import type { Metadata } from "next";
+import { Faq } from "@/components/landing/faq";
+import { Features } from "@/components/landing/features";
+import { Hero } from "@/components/landing/hero";
+import { Pricing } from "@/components/landing/pricing";
+import { SiteFooter } from "@/components/landing/site-footer";
+import { SiteHeader } from "@/components/landing/site-header";
+import { landing } from "@/lib/landing";
+__METADATA_IMPORT__
+export const metadata: Metadata = __METADATA__;
+
+export default function Home() {
+  return (
+    <>
+      <SiteHeader />
+      <main>
+        <Hero />
+        <Features />
+        <Pricing />
+        <Faq />
+      </main>
+      <SiteFooter />
+    </>
+  );
+}
+"#;
+
+const STATIC_METADATA: &str = r#"{
+  metadataBase: new URL(process.env.NEXT_PUBLIC_SITE_URL ?? "http://localhost:3000"),
+  title: landing.name,
+  description: landing.description,
+  openGraph: {
+    title: landing.name,
+    description: landing.description,
+    siteName: landing.name,
+    type: "website",
+  },
+  twitter: {
+    card: "summary_large_image",
+    title: landing.name,
+    description: landing.description,
+  },
+}"#;
+
+const OG_IMAGE: &str = r##"import { ImageResponse } from "next/og";
+import { landing } from "@/lib/landing";
+
+export const alt = landing.name;
+export const size = { width: 1200, height: 630 };
+export const contentType = "image/png";
+
+// Rendered by ImageResponse (satori), so only flexbox layouts and inline styles are supported.
+export default function Image() {
+  return new ImageResponse(
+    (
+      <div
+        style={{
+          width: "100%",
+          height: "100%",
+          display: "flex",
+          flexDirection: "column",
+          justifyContent: "center",
+          padding: "80px",
+          background: "#0a0a0a",
+          color: "#fafafa",
+        }}
+      >
+        <div style={{ fontSize: "40px", fontWeight: 700, color: "#a3a3a3" }}>{landing.name}</div>
+        <div style={{ marginTop: "24px", fontSize: "72px", fontWeight: 700, lineHeight: 1.1 }}>{landing.tagline}</div>
+      </div>
+    ),
+    size,
+  );
+}
+"##;
+
+/// magicui components the landing page uses when magicui is installed.
+const MAGICUI_COMPONENTS: [&str; 2] = ["animated-shiny-text", "border-beam"];
+
+pub async fn add_landing() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    if !project_file("components.json").exists() {
+        println!(
+            "{}",
+            style("The landing page is built on shadcn/ui. Run `nstack add --feature shadcn` first.").yellow()
+        );
+        return Ok(());
+    }
+
+    let default_name = package_json::package_name().unwrap_or_else(|| "My App".to_string());
+    let site_name = prompt::input("site_name", "Product name", &default_name)?;
+    let tagline = prompt::input("tagline", "Headline for the hero section", "Build your next idea faster")?;
+
+    let components_path = project_structure.get_components_path();
+    let lib_path = project_structure.get_lib_path();
+    let use_magicui = ProjectManifest::is_feature_installed("magicui");
+    let has_theme_toggle = components_path.join("theme-toggle.tsx").exists();
+    let has_og_helpers = lib_path.join("og.ts").exists();
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Adding shadcn/ui components...");
+
+    package_manager.dlx(&["shadcn@latest", "add", "button", "card", "badge", "accordion", "--yes"])?;
+    if use_magicui {
+        pb.set_message("Adding magicui components...");
+        let urls: Vec<String> = MAGICUI_COMPONENTS
+            .iter()
+            .map(|component| format!("https://magicui.design/r/{}.json", component))
+            .collect();
+        let mut args = vec!["shadcn@latest", "add"];
+        args.extend(urls.iter().map(String::as_str));
+        args.push("--yes");
+        package_manager.dlx(&args)?;
+    }
+
+    pb.set_message("Creating landing page content...");
+
+    change_set::create_dir_all(&lib_path).context("Failed to create lib directory")?;
+    let content = CONTENT
+        .replace("__SITE_NAME__", &site_name.replace('"', "\\\""))
+        .replace("__TAGLINE__", &tagline.replace('"', "\\\""));
+    file_writer::write(lib_path.join("landing.ts"), content)
+        .context("Failed to create landing.ts")?;
+
+    pb.set_message("Creating landing page sections...");
+
+    let (theme_toggle_import, theme_toggle) = if has_theme_toggle {
+        ("import { ThemeToggle } from \"@/components/theme-toggle\";\n", "          <ThemeToggle />\n")
+    } else {
+        ("", "")
+    };
+    let (badge_import, badge) = if use_magicui {
+        (
+            "import { AnimatedShinyText } from \"@/components/magicui/animated-shiny-text\";\n",
+            "      <div className=\"rounded-full border bg-muted/50 px-4 py-1 text-sm\">\n        <AnimatedShinyText>{landing.announcement}</AnimatedShinyText>\n      </div>\n",
+        )
+    } else {
+        (
+            "import { Badge } from \"@/components/ui/badge\";\n",
+            "      <Badge variant=\"secondary\">{landing.announcement}</Badge>\n",
+        )
+    };
+    let (beam_import, beam) = if use_magicui {
+        (
+            "import { BorderBeam } from \"@/components/magicui/border-beam\";\n",
+            "              {plan.featured && <BorderBeam size={250} duration={12} />}\n",
+        )
+    } else {
+        ("", "")
+    };
+
+    let landing_path = components_path.join("landing");
+    change_set::create_dir_all(&landing_path).context("Failed to create components/landing directory")?;
+    for (file, content) in [
+        (
+            "site-header.tsx",
+            HEADER.replace("__THEME_TOGGLE_IMPORT__", theme_toggle_import).replace("__THEME_TOGGLE__", theme_toggle),
+        ),
+        ("hero.tsx", HERO.replace("__BADGE_IMPORT__", badge_import).replace("__BADGE__", badge)),
+        ("features.tsx", FEATURES.to_string()),
+        ("pricing.tsx", PRICING.replace("__BEAM_IMPORT__", beam_import).replace("__BEAM__", beam)),
+        ("faq.tsx", FAQ.to_string()),
+        ("site-footer.tsx", FOOTER.to_string()),
+    ] {
+        file_writer::write(landing_path.join(file), content)
+            .context(format!("Failed to create {}", file))?;
+    }
+
+    pb.set_message("Creating the landing page...");
+
+    // Pages get metadata and OG images from og-image's helpers when it is installed
+    let routes_dir = root_layout::routes_dir(&project_structure);
+    let page = if has_og_helpers {
+        PAGE.replace("__METADATA_IMPORT__", "import { ogMetadata } from \"@/lib/og\";\n")
+            .replace("__METADATA__", "ogMetadata({ title: landing.name, subtitle: landing.description })")
+    } else {
+        PAGE.replace("__METADATA_IMPORT__", "").replace("__METADATA__", STATIC_METADATA)
+    };
+    let page_written = file_writer::write(routes_dir.join("page.tsx"), page)
+        .context("Failed to create page.tsx")?;
+    let og_image_path = routes_dir.join("opengraph-image.tsx");
+    let og_image_created = !og_image_path.exists();
+    if og_image_created {
+        file_writer::write(&og_image_path, OG_IMAGE).context("Failed to create opengraph-image.tsx")?;
+    }
+
+    env_file::add_env_vars("landing", "Site", &[("NEXT_PUBLIC_SITE_URL", "http://localhost:3000")])?;
+
+    pb.finish_with_message("Landing page created successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/landing.ts - Copy, features, plans and FAQ content", lib_path.display());
+    println!(
+        "• {}/ - Header, hero, features, pricing, FAQ and footer{}",
+        landing_path.display(),
+        if use_magicui { " with magicui accents" } else { "" }
+    );
+    if page_written {
+        println!("• {}/page.tsx - Landing page with metadata", routes_dir.display());
+    } else {
+        println!(
+            "{}",
+            style(format!("• Kept the existing {}/page.tsx; render the landing sections there yourself", routes_dir.display())).yellow()
+        );
+    }
+    if og_image_created {
+        println!("• {} - Open Graph image from the landing copy", og_image_path.display());
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Edit {}/landing.ts with your own copy and plans", lib_path.display());
+    println!("2. Set NEXT_PUBLIC_SITE_URL in .env to your production URL");
+    if !has_theme_toggle {
+        println!("3. Run `nstack add --feature theme` for a dark mode toggle in the header");
+    }
+
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use anyhow::{Result, Context};
 use console::style;
 use indicatif::ProgressBar;
-use std::process::Command;
-use crate::project_structure::ProjectStructure;
+use crate::change_set;
+use crate::features::shadcn;
+use crate::file_writer;
+use crate::tailwind;
+use crate::project_structure::{project_file, ProjectStructure};
 use crate::package_manager::PackageManager;
 
 pub async fn add_magicui() -> Result<()> {
@@ -21,54 +24,36 @@ pub async fn add_magicui() -> Result<()> {
         "{}",
         style(format!(
             "Project structure: {}",
-            format!("{:?}", project_structure).to_lowercase()
+            format!("{:?}", project_structure.layout()).to_lowercase()
         ))
         .yellow()
     );
 
+    // magicui builds on shadcn/ui's components.json and theme variables
+    let tailwind_version = tailwind::ensure_installed(&project_structure, &package_manager, "magicui")?;
+    println!(
+        "{}",
+        style(format!("Tailwind CSS: {}", tailwind_version.as_str())).yellow()
+    );
+
     let pb = ProgressBar::new_spinner();
     pb.set_message("Installing magicui dependencies...");
 
     // Install required dependencies
-    let (cmd, install) = package_manager.install_command();
-    Command::new(cmd)
-        .args([
-            install,
+    package_manager
+        .add(&[
             "class-variance-authority",
             "clsx",
             "tailwind-merge",
             "lucide-react",
-            "tw-animate-css",
+            shadcn::animate_package(tailwind_version),
         ])
-        .status()
+        .run()
         .context("Failed to install magicui dependencies")?;
 
     pb.set_message("Setting up configuration files...");
 
-    // Create components.json
-    let components_json = format!(r#"{{
-  "$schema": "https://ui.shadcn.com/schema.json",
-  "style": "new-york",
-  "rsc": false,
-  "tsx": true,
-  "tailwind": {{
-    "config": "",
-    "css": "{}",
-    "baseColor": "neutral",
-    "cssVariables": true,
-    "prefix": ""
-  }},
-  "aliases": {{
-    "components": "@/components",
-    "utils": "@/lib/utils",
-    "ui": "@/components/ui",
-    "lib": "@/lib",
-    "hooks": "@/hooks"
-  }},
-  "iconLibrary": "lucide"
-}}"#, project_structure.get_globals_css_path());
-
-    std::fs::write("components.json", components_json)
+    file_writer::write(project_file("components.json"), shadcn::components_json(&project_structure, tailwind_version))
         .context("Failed to create components.json")?;
 
     // Create utils.ts
@@ -80,146 +65,17 @@ export function cn(...inputs: ClassValue[]) {
 }"#;
 
     let lib_path = project_structure.get_lib_path();
-    std::fs::create_dir_all(lib_path).context("Failed to create lib directory")?;
-    std::fs::write(format!("{}/utils.ts", lib_path), utils_ts)
+    change_set::create_dir_all(&lib_path).context("Failed to create lib directory")?;
+    file_writer::write(lib_path.join("utils.ts"), utils_ts)
         .context("Failed to create utils.ts")?;
 
-    // Update globals.css
-    let globals_css = r#"@import "tailwindcss";
-@import "tw-animate-css";
-
-@custom-variant dark (&:is(.dark *));
-
-:root {
-  --background: oklch(1 0 0);
-  --foreground: oklch(0.145 0 0);
-  --card: oklch(1 0 0);
-  --card-foreground: oklch(0.145 0 0);
-  --popover: oklch(1 0 0);
-  --popover-foreground: oklch(0.145 0 0);
-  --primary: oklch(0.205 0 0);
-  --primary-foreground: oklch(0.985 0 0);
-  --secondary: oklch(0.97 0 0);
-  --secondary-foreground: oklch(0.205 0 0);
-  --muted: oklch(0.97 0 0);
-  --muted-foreground: oklch(0.556 0 0);
-  --accent: oklch(0.97 0 0);
-  --accent-foreground: oklch(0.205 0 0);
-  --destructive: oklch(0.577 0.245 27.325);
-  --destructive-foreground: oklch(0.577 0.245 27.325);
-  --border: oklch(0.922 0 0);
-  --input: oklch(0.922 0 0);
-  --ring: oklch(0.708 0 0);
-  --chart-1: oklch(0.646 0.222 41.116);
-  --chart-2: oklch(0.6 0.118 184.704);
-  --chart-3: oklch(0.398 0.07 227.392);
-  --chart-4: oklch(0.828 0.189 84.429);
-  --chart-5: oklch(0.769 0.188 70.08);
-  --radius: 0.625rem;
-  --sidebar: oklch(0.985 0 0);
-  --sidebar-foreground: oklch(0.145 0 0);
-  --sidebar-primary: oklch(0.205 0 0);
-  --sidebar-primary-foreground: oklch(0.985 0 0);
-  --sidebar-accent: oklch(0.97 0 0);
-  --sidebar-accent-foreground: oklch(0.205 0 0);
-  --sidebar-border: oklch(0.922 0 0);
-  --sidebar-ring: oklch(0.708 0 0);
-}
-
-.dark {
-  --background: oklch(0.145 0 0);
-  --foreground: oklch(0.985 0 0);
-  --card: oklch(0.145 0 0);
-  --card-foreground: oklch(0.985 0 0);
-  --popover: oklch(0.145 0 0);
-  --popover-foreground: oklch(0.985 0 0);
-  --primary: oklch(0.985 0 0);
-  --primary-foreground: oklch(0.205 0 0);
-  --secondary: oklch(0.269 0 0);
-  --secondary-foreground: oklch(0.985 0 0);
-  --muted: oklch(0.269 0 0);
-  --muted-foreground: oklch(0.708 0 0);
-  --accent: oklch(0.269 0 0);
-  --accent-foreground: oklch(0.985 0 0);
-  --destructive: oklch(0.396 0.141 25.723);
-  --destructive-foreground: oklch(0.637 0.237 25.331);
-  --border: oklch(0.269 0 0);
-  --input: oklch(0.269 0 0);
-  --ring: oklch(0.439 0 0);
-  --chart-1: oklch(0.488 0.243 264.376);
-  --chart-2: oklch(0.696 0.17 162.48);
-  --chart-3: oklch(0.769 0.188 70.08);
-  --chart-4: oklch(0.627 0.265 303.9);
-  --chart-5: oklch(0.645 0.246 16.439);
-  --sidebar: oklch(0.205 0 0);
-  --sidebar-foreground: oklch(0.985 0 0);
-  --sidebar-primary: oklch(0.488 0.243 264.376);
-  --sidebar-primary-foreground: oklch(0.985 0 0);
-  --sidebar-accent: oklch(0.269 0 0);
-  --sidebar-accent-foreground: oklch(0.985 0 0);
-  --sidebar-border: oklch(0.269 0 0);
-  --sidebar-ring: oklch(0.439 0 0);
-}
-
-@theme inline {
-  --color-background: var(--background);
-  --color-foreground: var(--foreground);
-  --color-card: var(--card);
-  --color-card-foreground: var(--card-foreground);
-  --color-popover: var(--popover);
-  --color-popover-foreground: var(--popover-foreground);
-  --color-primary: var(--primary);
-  --color-primary-foreground: var(--primary-foreground);
-  --color-secondary: var(--secondary);
-  --color-secondary-foreground: var(--secondary-foreground);
-  --color-muted: var(--muted);
-  --color-muted-foreground: var(--muted-foreground);
-  --color-accent: var(--accent);
-  --color-accent-foreground: var(--accent-foreground);
-  --color-destructive: var(--destructive);
-  --color-destructive-foreground: var(--destructive-foreground);
-  --color-border: var(--border);
-  --color-input: var(--input);
-  --color-ring: var(--ring);
-  --color-chart-1: var(--chart-1);
-  --color-chart-2: var(--chart-2);
-  --color-chart-3: var(--chart-3);
-  --color-chart-4: var(--chart-4);
-  --color-chart-5: var(--chart-5);
-  --radius-sm: calc(var(--radius) - 4px);
-  --radius-md: calc(var(--radius) - 2px);
-  --radius-lg: var(--radius);
-  --radius-xl: calc(var(--radius) + 4px);
-  --color-sidebar: var(--sidebar);
-  --color-sidebar-foreground: var(--sidebar-foreground);
-  --color-sidebar-primary: var(--sidebar-primary);
-  --color-sidebar-primary-foreground: var(--sidebar-primary-foreground);
-  --color-sidebar-accent: var(--sidebar-accent);
-  --color-sidebar-accent-foreground: var(--sidebar-accent-foreground);
-  --color-sidebar-border: var(--sidebar-border);
-  --color-sidebar-ring: var(--sidebar-ring);
-}
-
-@layer base {
-  * {
-    @apply border-border outline-ring/50;
-  }
-  body {
-    @apply bg-background text-foreground;
-  }
-}"#;
-
-    let globals_css_path = project_structure.get_globals_css_path();
-    std::fs::write(globals_css_path, globals_css)
-        .context("Failed to update globals.css")?;
+    pb.set_message(format!("Adding theme variables for Tailwind {}...", tailwind_version.as_str()));
+    shadcn::setup_theme(&project_structure, tailwind_version)?;
 
     // Create components and components/ui directories
     pb.set_message("Creating component directories...");
-    let components_path = match project_structure {
-        ProjectStructure::AppDir => "components/ui",
-        ProjectStructure::SrcDir => "src/components/ui",
-    };
-    std::fs::create_dir_all(components_path)
+    let components_path = project_structure.get_components_path().join("ui");
+    change_set::create_dir_all(&components_path)
         .context("Failed to create components/ui directory")?;
 
     pb.finish_with_message("magicui installed successfully!");
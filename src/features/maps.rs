@@ -0,0 +1,678 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+use crate::root_layout;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MapProvider {
+    MapLibre,
+    Mapbox,
+    Leaflet,
+    Google,
+}
+
+impl MapProvider {
+    const ALL: [MapProvider; 4] = [MapProvider::MapLibre, MapProvider::Mapbox, MapProvider::Leaflet, MapProvider::Google];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            MapProvider::MapLibre => "MapLibre",
+            MapProvider::Mapbox => "Mapbox GL",
+            MapProvider::Leaflet => "Leaflet",
+            MapProvider::Google => "Google Maps",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            MapProvider::MapLibre => "open-source vector maps, no token needed",
+            MapProvider::Mapbox => "Mapbox vector maps and geocoding",
+            MapProvider::Leaflet => "lightweight raster maps with OpenStreetMap tiles",
+            MapProvider::Google => "Google Maps with advanced markers",
+        }
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        match self {
+            MapProvider::MapLibre => &["maplibre-gl"],
+            MapProvider::Mapbox => &["mapbox-gl"],
+            MapProvider::Leaflet => &["leaflet"],
+            MapProvider::Google => &["@vis.gl/react-google-maps"],
+        }
+    }
+
+    fn dev_dependencies(&self) -> &'static [&'static str] {
+        match self {
+            MapProvider::Leaflet => &["@types/leaflet"],
+            _ => &[],
+        }
+    }
+
+    fn env_vars(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            MapProvider::MapLibre => &[("NEXT_PUBLIC_MAP_STYLE_URL", "https://tiles.openfreemap.org/styles/liberty")],
+            MapProvider::Mapbox => &[("NEXT_PUBLIC_MAPBOX_TOKEN", "")],
+            MapProvider::Leaflet => &[],
+            MapProvider::Google => &[
+                ("NEXT_PUBLIC_GOOGLE_MAPS_API_KEY", ""),
+                ("NEXT_PUBLIC_GOOGLE_MAPS_MAP_ID", "DEMO_MAP_ID"),
+                ("GOOGLE_MAPS_API_KEY", ""),
+            ],
+        }
+    }
+}
+
+const TYPES: &str = r#"export type LngLat = {
+  lng: number;
+  lat: number;
+};
+
+export type MapMarker = {
+  id: string;
+  position: LngLat;
+  label?: string;
+};
+
+export type Place = {
+  id: string;
+  name: string;
+  position: LngLat;
+};
+
+export type MapViewProps = {
+  center: LngLat;
+  zoom?: number;
+  markers?: MapMarker[];
+  className?: string;
+};
+"#;
+
+const MARKERS: &str = r#"import type { LngLat, MapMarker, Place } from "@/lib/maps/types";
+
+export const DEFAULT_CENTER: LngLat = { lng: -0.1276, lat: 51.5072 };
+export const DEFAULT_ZOOM = 12;
+
+export function placeToMarker(place: Place): MapMarker {
+  return { id: place.id, position: place.position, label: place.name };
+}
+
+/** The midpoint of some markers, or DEFAULT_CENTER when there are none. */
+export function centerOf(markers: MapMarker[]): LngLat {
+  if (markers.length === 0) return DEFAULT_CENTER;
+
+  const total = markers.reduce(
+    (sum, { position }) => ({ lng: sum.lng + position.lng, lat: sum.lat + position.lat }),
+    { lng: 0, lat: 0 },
+  );
+  return { lng: total.lng / markers.length, lat: total.lat / markers.length };
+}
+"#;
+
+const NOMINATIM_GEOCODING: &str = r#"import type { LngLat, Place } from "@/lib/maps/types";
+
+// OpenStreetMap's Nominatim asks for an identifying User-Agent and at most one request a second;
+// move to a paid geocoder before sending real traffic
+const NOMINATIM_URL = "https://nominatim.openstreetmap.org";
+const USER_AGENT = "__USER_AGENT__";
+
+type NominatimResult = {
+  place_id: number;
+  display_name: string;
+  lat: string;
+  lon: string;
+};
+
+/** Places matching a free-text query, best match first. */
+export async function geocode(query: string, limit = 5): Promise<Place[]> {
+  const params = new URLSearchParams({ q: query, format: "jsonv2", limit: String(limit) });
+  const response = await fetch(`${NOMINATIM_URL}/search?${params}`, { headers: { "User-Agent": USER_AGENT } });
+  if (!response.ok) throw new Error(`Geocoding failed: ${response.status}`);
+
+  const results: NominatimResult[] = await response.json();
+  return results.map((result) => ({
+    id: String(result.place_id),
+    name: result.display_name,
+    position: { lng: Number(result.lon), lat: Number(result.lat) },
+  }));
+}
+
+/** The address at a position, or null when there is none. */
+export async function reverseGeocode({ lng, lat }: LngLat): Promise<string | null> {
+  const params = new URLSearchParams({ lat: String(lat), lon: String(lng), format: "jsonv2" });
+  const response = await fetch(`${NOMINATIM_URL}/reverse?${params}`, { headers: { "User-Agent": USER_AGENT } });
+  if (!response.ok) return null;
+
+  const result: Partial<NominatimResult> = await response.json();
+  return result.display_name ?? null;
+}
+"#;
+
+const MAPBOX_GEOCODING: &str = r#"import type { LngLat, Place } from "@/lib/maps/types";
+
+const GEOCODING_URL = "https://api.mapbox.com/search/geocode/v6";
+
+type GeocodingResponse = {
+  features: {
+    id: string;
+    geometry: { coordinates: [number, number] };
+    properties: { full_address?: string; name: string };
+  }[];
+};
+
+/** Places matching a free-text query, best match first. */
+export async function geocode(query: string, limit = 5): Promise<Place[]> {
+  const params = new URLSearchParams({
+    q: query,
+    limit: String(limit),
+    access_token: process.env.NEXT_PUBLIC_MAPBOX_TOKEN!,
+  });
+  const response = await fetch(`${GEOCODING_URL}/forward?${params}`);
+  if (!response.ok) throw new Error(`Geocoding failed: ${response.status}`);
+
+  const { features }: GeocodingResponse = await response.json();
+  return features.map((feature) => ({
+    id: feature.id,
+    name: feature.properties.full_address ?? feature.properties.name,
+    position: { lng: feature.geometry.coordinates[0], lat: feature.geometry.coordinates[1] },
+  }));
+}
+
+/** The address at a position, or null when there is none. */
+export async function reverseGeocode({ lng, lat }: LngLat): Promise<string | null> {
+  const params = new URLSearchParams({
+    longitude: String(lng),
+    latitude: String(lat),
+    limit: "1",
+    access_token: process.env.NEXT_PUBLIC_MAPBOX_TOKEN!,
+  });
+  const response = await fetch(`${GEOCODING_URL}/reverse?${params}`);
+  if (!response.ok) return null;
+
+  const { features }: GeocodingResponse = await response.json();
+  const [feature] = features;
+  return feature ? (feature.properties.full_address ?? feature.properties.name) : null;
+}
+"#;
+
+const GOOGLE_GEOCODING: &str = r#"import type { LngLat, Place } from "@/lib/maps/types";
+
+// Geocodes with GOOGLE_MAPS_API_KEY, a server key, since the browser key is usually restricted to your site
+const GEOCODING_URL = "https://maps.googleapis.com/maps/api/geocode/json";
+
+type GeocodingResponse = {
+  results: {
+    place_id: string;
+    formatted_address: string;
+    geometry: { location: { lat: number; lng: number } };
+  }[];
+};
+
+/** Places matching a free-text query, best match first. */
+export async function geocode(query: string, limit = 5): Promise<Place[]> {
+  const params = new URLSearchParams({ address: query, key: process.env.GOOGLE_MAPS_API_KEY! });
+  const response = await fetch(`${GEOCODING_URL}?${params}`);
+  if (!response.ok) throw new Error(`Geocoding failed: ${response.status}`);
+
+  const { results }: GeocodingResponse = await response.json();
+  return results.slice(0, limit).map((result) => ({
+    id: result.place_id,
+    name: result.formatted_address,
+    position: result.geometry.location,
+  }));
+}
+
+/** The address at a position, or null when there is none. */
+export async function reverseGeocode({ lng, lat }: LngLat): Promise<string | null> {
+  const params = new URLSearchParams({ latlng: `${lat},${lng}`, key: process.env.GOOGLE_MAPS_API_KEY! });
+  const response = await fetch(`${GEOCODING_URL}?${params}`);
+  if (!response.ok) return null;
+
+  const { results }: GeocodingResponse = await response.json();
+  return results[0]?.formatted_address ?? null;
+}
+"#;
+
+const GEOCODE_ROUTE: &str = r#"import { geocode } from "@/lib/maps/geocoding";
+
+/** `GET /api/geocode?q=` keeps geocoding keys and rate limits on the server. */
+export async function GET(request: Request) {
+  const query = new URL(request.url).searchParams.get("q")?.trim();
+  if (!query) {
+    return Response.json([]);
+  }
+
+  try {
+    return Response.json(await geocode(query));
+  } catch (error) {
+    console.error(error);
+    return Response.json({ error: "Geocoding failed" }, { status: 502 });
+  }
+}
+"#;
+
+const GL_CANVAS: &str = r#""use client";
+
+import gl from "__PACKAGE__";
+import "__PACKAGE__/dist/__PACKAGE__.css";
+import { useEffect, useRef } from "react";
+import { DEFAULT_ZOOM } from "@/lib/maps/markers";
+import type { MapViewProps } from "@/lib/maps/types";
+__SETUP__
+export default function MapCanvas({ center, zoom = DEFAULT_ZOOM, markers = [], className }: MapViewProps) {
+  const container = useRef<HTMLDivElement>(null);
+  const map = useRef<gl.Map | null>(null);
+
+  // Created once; the effects below follow later props
+  useEffect(() => {
+    map.current = new gl.Map({
+      container: container.current!,
+      style: __STYLE__,
+      center: [center.lng, center.lat],
+      zoom,
+    });
+    map.current.addControl(new gl.NavigationControl(), "top-right");
+    return () => {
+      map.current?.remove();
+      map.current = null;
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  useEffect(() => {
+    map.current?.flyTo({ center: [center.lng, center.lat], zoom });
+  }, [center.lng, center.lat, zoom]);
+
+  useEffect(() => {
+    const instance = map.current;
+    if (!instance) return;
+
+    const added = markers.map((marker) => {
+      const pin = new gl.Marker().setLngLat([marker.position.lng, marker.position.lat]);
+      if (marker.label) pin.setPopup(new gl.Popup({ offset: 24 }).setText(marker.label));
+      return pin.addTo(instance);
+    });
+    return () => added.forEach((marker) => marker.remove());
+  }, [markers]);
+
+  return <div ref={container} className={className ?? "h-96 w-full rounded-md"} />;
+}
+"#;
+
+const LEAFLET_CANVAS: &str = r#""use client";
+
+import L from "leaflet";
+import "leaflet/dist/leaflet.css";
+import icon from "leaflet/dist/images/marker-icon.png";
+import iconRetina from "leaflet/dist/images/marker-icon-2x.png";
+import shadow from "leaflet/dist/images/marker-shadow.png";
+import { useEffect, useRef } from "react";
+import { DEFAULT_ZOOM } from "@/lib/maps/markers";
+import type { MapViewProps } from "@/lib/maps/types";
+
+// Leaflet's default icon paths break under bundlers, so point it at the bundled images
+const markerIcon = L.icon({
+  iconUrl: icon.src,
+  iconRetinaUrl: iconRetina.src,
+  shadowUrl: shadow.src,
+  iconSize: [25, 41],
+  iconAnchor: [12, 41],
+  popupAnchor: [1, -34],
+});
+
+export default function MapCanvas({ center, zoom = DEFAULT_ZOOM, markers = [], className }: MapViewProps) {
+  const container = useRef<HTMLDivElement>(null);
+  const map = useRef<L.Map | null>(null);
+
+  // Created once; the effects below follow later props
+  useEffect(() => {
+    map.current = L.map(container.current!).setView([center.lat, center.lng], zoom);
+    L.tileLayer("https://tile.openstreetmap.org/{z}/{x}/{y}.png", {
+      maxZoom: 19,
+      attribution: '&copy; <a href="https://www.openstreetmap.org/copyright">OpenStreetMap</a> contributors',
+    }).addTo(map.current);
+    return () => {
+      map.current?.remove();
+      map.current = null;
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  useEffect(() => {
+    map.current?.flyTo([center.lat, center.lng], zoom);
+  }, [center.lat, center.lng, zoom]);
+
+  useEffect(() => {
+    const instance = map.current;
+    if (!instance) return;
+
+    const added = markers.map((marker) => {
+      const pin = L.marker([marker.position.lat, marker.position.lng], { icon: markerIcon });
+      if (marker.label) pin.bindPopup(marker.label);
+      return pin.addTo(instance);
+    });
+    return () => added.forEach((marker) => marker.remove());
+  }, [markers]);
+
+  return <div ref={container} className={className ?? "h-96 w-full rounded-md"} />;
+}
+"#;
+
+const GOOGLE_CANVAS: &str = r#""use client";
+
+import { AdvancedMarker, APIProvider, Map as GoogleMap, useMap } from "@vis.gl/react-google-maps";
+import { useEffect } from "react";
+import { DEFAULT_ZOOM } from "@/lib/maps/markers";
+import type { LngLat, MapViewProps } from "@/lib/maps/types";
+
+export default function MapCanvas({ center, zoom = DEFAULT_ZOOM, markers = [], className }: MapViewProps) {
+  return (
+    <APIProvider apiKey={process.env.NEXT_PUBLIC_GOOGLE_MAPS_API_KEY!}>
+      <GoogleMap
+        className={className ?? "h-96 w-full overflow-hidden rounded-md"}
+        defaultCenter={center}
+        defaultZoom={zoom}
+        mapId={process.env.NEXT_PUBLIC_GOOGLE_MAPS_MAP_ID}
+        gestureHandling="greedy"
+      >
+        {markers.map((marker) => (
+          <AdvancedMarker key={marker.id} position={marker.position} title={marker.label} />
+        ))}
+      </GoogleMap>
+      <FollowCenter center={center} zoom={zoom} />
+    </APIProvider>
+  );
+}
+
+// The map is uncontrolled, so later center and zoom props pan it instead
+function FollowCenter({ center, zoom }: { center: LngLat; zoom: number }) {
+  const map = useMap();
+
+  useEffect(() => {
+    map?.panTo(center);
+    map?.setZoom(zoom);
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [map, center.lat, center.lng, zoom]);
+
+  return null;
+}
+"#;
+
+const MAP_VIEW: &str = r#""use client";
+
+import dynamic from "next/dynamic";
+
+/** The map, rendered in the browser only because map libraries need `window`. */
+export const MapView = dynamic(() => import("@/components/maps/map-canvas"), {
+  ssr: false,
+  loading: () => <div className="h-96 w-full animate-pulse rounded-md bg-gray-100 dark:bg-neutral-800" />,
+});
+"#;
+
+const LOCATION_SEARCH: &str = r#""use client";
+
+import { useEffect, useState } from "react";
+import type { Place } from "@/lib/maps/types";
+
+type LocationSearchProps = {
+  onSelect: (place: Place) => void;
+};
+
+export function LocationSearch({ onSelect }: LocationSearchProps) {
+  const [query, setQuery] = useState("");
+  const [places, setPlaces] = useState<Place[]>([]);
+  const [loading, setLoading] = useState(false);
+
+  // Debounced so typing does not hit the geocoder's rate limit
+  useEffect(() => {
+    const trimmed = query.trim();
+    if (trimmed.length < 3) {
+      setPlaces([]);
+      return;
+    }
+
+    const controller = new AbortController();
+    const timeout = setTimeout(async () => {
+      setLoading(true);
+      try {
+        const response = await fetch(`/api/geocode?q=${encodeURIComponent(trimmed)}`, { signal: controller.signal });
+        setPlaces(response.ok ? await response.json() : []);
+      } catch {
+        // Aborted by a newer query
+      } finally {
+        setLoading(false);
+      }
+    }, 400);
+
+    return () => {
+      clearTimeout(timeout);
+      controller.abort();
+    };
+  }, [query]);
+
+  return (
+    <div className="relative">
+      <input
+        type="search"
+        value={query}
+        onChange={(event) => setQuery(event.target.value)}
+        placeholder="Search for a place"
+        aria-label="Search for a place"
+        className="w-full rounded-md border bg-transparent px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+      />
+      {loading && <span className="absolute right-3 top-2.5 text-xs text-gray-400">Searching…</span>}
+      {places.length > 0 && (
+        <ul className="absolute z-10 mt-1 w-full overflow-hidden rounded-md border bg-white shadow-lg dark:bg-neutral-900">
+          {places.map((place) => (
+            <li key={place.id}>
+              <button
+                type="button"
+                onClick={() => {
+                  onSelect(place);
+                  setQuery("");
+                  setPlaces([]);
+                }}
+                className="block w-full truncate px-3 py-2 text-left text-sm hover:bg-gray-100 dark:hover:bg-neutral-800"
+              >
+                {place.name}
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
+"#;
+
+const MAP_EXPLORER: &str = r#""use client";
+
+import { useState } from "react";
+import { LocationSearch } from "@/components/maps/location-search";
+import { MapView } from "@/components/maps/map-view";
+import { DEFAULT_CENTER, placeToMarker } from "@/lib/maps/markers";
+import type { LngLat, MapMarker } from "@/lib/maps/types";
+
+/** Search for places, drop a marker on each and fly to the latest. */
+export function MapExplorer() {
+  const [center, setCenter] = useState<LngLat>(DEFAULT_CENTER);
+  const [markers, setMarkers] = useState<MapMarker[]>([]);
+
+  return (
+    <div className="space-y-4">
+      <LocationSearch
+        onSelect={(place) => {
+          setCenter(place.position);
+          setMarkers((current) => [...current.filter((marker) => marker.id !== place.id), placeToMarker(place)]);
+        }}
+      />
+      <MapView center={center} zoom={14} markers={markers} className="h-[32rem] w-full rounded-md" />
+      {markers.length > 0 && (
+        <ul className="space-y-1 text-sm">
+          {markers.map((marker) => (
+            <li key={marker.id} className="flex items-center justify-between gap-4">
+              <button type="button" onClick={() => setCenter(marker.position)} className="truncate text-left hover:underline">
+                {marker.label}
+              </button>
+              <button
+                type="button"
+                onClick={() => setMarkers((current) => current.filter(({ id }) => id !== marker.id))}
+                className="text-xs text-gray-500 hover:text-red-600"
+              >
+                Remove
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
+"#;
+
+const PAGE: &str = r#"import type { Metadata } from "next";
+import { MapExplorer } from "@/components/maps/map-explorer";
+
+export const metadata: Metadata = {
+  title: "Map",
+};
+
+export default function MapPage() {
+  return (
+    <main className="mx-auto max-w-4xl space-y-4 p-8">
+      <div>
+        <h1 className="text-2xl font-semibold">Map</h1>
+        <p className="text-sm text-gray-500">Search for a place to drop a marker on it.</p>
+      </div>
+      <MapExplorer />
+    </main>
+  );
+}
+"#;
+
+fn map_canvas(provider: MapProvider) -> String {
+    match provider {
+        MapProvider::MapLibre => GL_CANVAS
+            .replace("__PACKAGE__", "maplibre-gl")
+            .replace("__SETUP__", "")
+            .replace("__STYLE__", "process.env.NEXT_PUBLIC_MAP_STYLE_URL ?? \"https://tiles.openfreemap.org/styles/liberty\""),
+        MapProvider::Mapbox => GL_CANVAS
+            .replace("__PACKAGE__", "mapbox-gl")
+            .replace("__SETUP__", "\ngl.accessToken = process.env.NEXT_PUBLIC_MAPBOX_TOKEN!;\n")
+            .replace("__STYLE__", "\"mapbox://styles/mapbox/streets-v12\""),
+        MapProvider::Leaflet => LEAFLET_CANVAS.to_string(),
+        MapProvider::Google => GOOGLE_CANVAS.to_string(),
+    }
+}
+
+fn geocoding(provider: MapProvider) -> String {
+    match provider {
+        MapProvider::Mapbox => MAPBOX_GEOCODING.to_string(),
+        MapProvider::Google => GOOGLE_GEOCODING.to_string(),
+        MapProvider::MapLibre | MapProvider::Leaflet => {
+            let app = package_json::package_name().unwrap_or_else(|| "nextjs-app".to_string());
+            NOMINATIM_GEOCODING.replace("__USER_AGENT__", &app)
+        }
+    }
+}
+
+pub async fn add_maps() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let descriptions: Vec<String> = MapProvider::ALL
+        .iter()
+        .map(|provider| format!("{} - {}", provider.as_str(), provider.description()))
+        .collect();
+    let selection = prompt::select("provider", "Select a map provider", &descriptions, 0)?;
+    let provider = MapProvider::ALL[selection];
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message(format!("Installing {}...", provider.as_str()));
+
+    package_manager.install(provider.dependencies(), false)?;
+    if !provider.dev_dependencies().is_empty() {
+        package_manager.install(provider.dev_dependencies(), true)?;
+    }
+
+    pb.set_message("Creating marker and geocoding helpers...");
+
+    let lib_path = project_structure.get_lib_path().join("maps");
+    change_set::create_dir_all(&lib_path).context("Failed to create lib/maps directory")?;
+    file_writer::write(lib_path.join("types.ts"), TYPES).context("Failed to create types.ts")?;
+    file_writer::write(lib_path.join("markers.ts"), MARKERS).context("Failed to create markers.ts")?;
+    file_writer::write(lib_path.join("geocoding.ts"), geocoding(provider))
+        .context("Failed to create geocoding.ts")?;
+
+    let route_path = project_structure.get_api_path().join("geocode");
+    change_set::create_dir_all(&route_path).context("Failed to create geocode route directory")?;
+    file_writer::write(route_path.join("route.ts"), GEOCODE_ROUTE).context("Failed to create route.ts")?;
+
+    pb.set_message("Creating map components...");
+
+    let components_path = project_structure.get_components_path().join("maps");
+    change_set::create_dir_all(&components_path).context("Failed to create components/maps directory")?;
+    file_writer::write(components_path.join("map-canvas.tsx"), map_canvas(provider))
+        .context("Failed to create map-canvas.tsx")?;
+    file_writer::write(components_path.join("map-view.tsx"), MAP_VIEW).context("Failed to create map-view.tsx")?;
+    file_writer::write(components_path.join("location-search.tsx"), LOCATION_SEARCH)
+        .context("Failed to create location-search.tsx")?;
+    file_writer::write(components_path.join("map-explorer.tsx"), MAP_EXPLORER)
+        .context("Failed to create map-explorer.tsx")?;
+
+    pb.set_message("Creating the map page...");
+
+    let page_path = root_layout::routes_dir(&project_structure).join("map");
+    change_set::create_dir_all(&page_path).context("Failed to create map route directory")?;
+    file_writer::write(page_path.join("page.tsx"), PAGE).context("Failed to create page.tsx")?;
+
+    if !provider.env_vars().is_empty() {
+        env_file::add_env_vars("maps", "Maps", provider.env_vars())?;
+    }
+
+    pb.finish_with_message(format!("{} map added successfully!", provider.as_str()));
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/ - Map types, marker helpers, and geocode()/reverseGeocode()", lib_path.display());
+    println!("• {}/route.ts - GET /api/geocode?q= for location search", route_path.display());
+    println!("• {}/map-view.tsx - <MapView>, loaded on the client only", components_path.display());
+    println!("• {}/map-canvas.tsx - {} map with markers", components_path.display(), provider.as_str());
+    println!("• {}/location-search.tsx - Debounced place search", components_path.display());
+    println!("• {}/page.tsx - Example map with location search", page_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    let mut step = 1;
+    match provider {
+        MapProvider::Mapbox => {
+            println!("{}. Set NEXT_PUBLIC_MAPBOX_TOKEN in .env to a public token from your Mapbox account", step);
+            step += 1;
+        }
+        MapProvider::Google => {
+            println!("{}. Set NEXT_PUBLIC_GOOGLE_MAPS_API_KEY (Maps JavaScript API) and GOOGLE_MAPS_API_KEY (Geocoding API) in .env", step);
+            println!("{}. Replace DEMO_MAP_ID with a map ID from the Cloud console before going live", step + 1);
+            step += 2;
+        }
+        MapProvider::MapLibre | MapProvider::Leaflet => {
+            println!("{}. Geocoding uses OpenStreetMap's Nominatim, which allows one request a second; swap geocode() for a paid service in production", step);
+            step += 1;
+        }
+    }
+    println!("{}. Start the dev server: {} and open /map", step, package_manager.run_script("dev"));
+
+    Ok(())
+}
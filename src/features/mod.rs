@@ -1,27 +1,224 @@
 pub mod shadcn;
 pub mod magicui;
 pub mod drizzle;
+pub mod schema_conventions;
+pub mod og_image;
+pub mod sitemap;
+pub mod security;
+pub mod captcha;
+pub mod cookie_consent;
+pub mod docker;
+pub mod github_actions;
+pub mod vercel;
+pub mod cloudflare;
+pub mod k8s;
+pub mod terraform;
+pub mod perf;
+pub mod vitest;
+pub mod playwright;
+pub mod cypress;
+pub mod storybook;
+pub mod eslint_prettier;
+pub mod biome;
+pub mod husky;
+pub mod commitlint;
+pub mod changesets;
+pub mod ts_strict;
+pub mod zustand;
+pub mod redux;
+pub mod jotai;
+pub mod theme;
+pub mod i18n;
+pub mod motion;
+pub mod data_table;
+pub mod charts;
+pub mod admin;
+pub mod landing;
+pub mod saas;
+pub mod search;
+pub mod notifications;
+pub mod collaboration;
+pub mod maps;
+pub mod scheduling;
+pub mod import_export;
+pub mod images;
+pub mod icons;
+pub mod toasts;
+pub mod supabase;
+pub mod firebase;
+pub mod convex;
+pub mod appwrite;
+pub mod pocketbase;
+pub mod kysely;
+pub mod mongodb;
+pub mod gel;
+pub mod local_first;
+pub mod nativewind;
+pub mod expo_router;
+pub mod remote;
 
 use console::style;
 use anyhow::Result;
+use crate::framework::Framework;
+use crate::manifest::ProjectManifest;
+use crate::project_structure::project_file;
 
-pub fn list_features() -> Result<()> {
-    let features = vec![
-        ("shadcn", "Add shadcn/ui components and configuration"),
-        ("magicui", "Add magicui components and configuration"),
-        ("drizzle", "Add Drizzle ORM with database configuration and schema setup")
-    ];
+/// Built-in features as `(name, category, description)`, grouped by category; the picker
+/// shows the categories in the order they first appear here.
+pub const FEATURES: &[(&str, &str, &str)] = &[
+    ("shadcn", "ui", "Add shadcn/ui components and configuration"),
+    ("magicui", "ui", "Add magicui components and configuration"),
+    ("theme", "ui", "Add dark mode with next-themes and a theme toggle"),
+    ("motion", "ui", "Add Motion animation primitives and route transitions"),
+    ("data-table", "ui", "TanStack data table with server-side pagination, sorting and CSV export"),
+    ("charts", "ui", "Dashboard page with Recharts line, bar and pie charts"),
+    ("admin", "ui", "Admin dashboard with a sidebar layout, protected routes and user management"),
+    ("landing", "ui", "Marketing landing page with hero, features, pricing, FAQ and OG metadata"),
+    ("maps", "ui", "Maps with MapLibre, Mapbox GL, Leaflet or Google Maps, geocoding and a location search page"),
+    ("images", "ui", "next/image remote hosts, formats and device sizes, with an OptimizedImage blur-placeholder wrapper"),
+    ("cookie-consent", "ui", "GDPR cookie consent banner and preferences, consent-gated scripts and analytics, and a privacy policy page"),
+    ("icons", "ui", "Icon library with a typed Icon wrapper and registry"),
+    ("toasts", "ui", "Sonner toasts with typed helpers and API error handling"),
+    ("drizzle", "database", "Add Drizzle ORM with database configuration and schema setup"),
+    ("schema-conventions", "database", "Drizzle column helpers for ids, timestamps and soft deletes"),
+    ("import-export", "database", "CSV and Excel import with Zod validation and batch inserts, and streamed table exports"),
+    ("kysely", "database", "Kysely query builder with codegen and example queries"),
+    ("mongodb", "database", "MongoDB with Mongoose, a User model and example route"),
+    ("gel", "database", "Gel (EdgeDB) schema, query builder and typed client"),
+    ("local-first", "database", "Local-first sync with ElectricSQL or Zero"),
+    ("og-image", "seo", "Add dynamic Open Graph image generation with next/og"),
+    ("sitemap", "seo", "Paginated sitemap index generated from drizzle tables, refreshed by ISR or a revalidation webhook"),
+    ("security", "tooling", "Security headers: nonce-based CSP middleware or next.config headers, HSTS and CSP sources for installed services"),
+    ("perf", "tooling", "Add bundle analysis, size-limit budgets and package import optimizations"),
+    ("eslint-prettier", "tooling", "Add a strict ESLint flat config and Prettier with Tailwind sorting"),
+    ("biome", "tooling", "Replace ESLint and Prettier with Biome"),
+    ("husky", "tooling", "Add husky git hooks with lint-staged"),
+    ("commitlint", "tooling", "Enforce conventional commits with commitlint and a commit-msg hook"),
+    ("changesets", "tooling", "Add a Changesets versioning and release workflow"),
+    ("ts-strict", "tooling", "Harden tsconfig with stricter compiler flags and ts-reset"),
+    ("captcha", "backend", "CAPTCHA with Cloudflare Turnstile, hCaptcha or reCAPTCHA: widget, server verification and sign-in form integration"),
+    ("saas", "backend", "SaaS starter: drizzle, Better Auth, Stripe subscriptions, Resend email and a billing dashboard"),
+    ("search", "backend", "Search with Postgres full-text, Meilisearch, Typesense or Algolia and a search box"),
+    ("notifications", "backend", "In-app notifications stored in drizzle or delivered through Novu or Knock, with a bell dropdown"),
+    ("collaboration", "backend", "Realtime collaboration with Liveblocks or PartyKit: rooms, presence avatars, live cursors and a shared editor"),
+    ("scheduling", "backend", "Bookings with drizzle availability, timezone-safe slots and a booking page, or a Cal.com embed"),
+    ("supabase", "backend", "Supabase clients, auth, storage helpers and local dev"),
+    ("firebase", "backend", "Firebase client/admin SDKs, session auth and emulators"),
+    ("convex", "backend", "Convex backend with example functions and provider"),
+    ("appwrite", "backend", "Appwrite SDKs with SSR auth, database and storage services"),
+    ("pocketbase", "backend", "PocketBase client, SSR auth cookie, hooks and docker service"),
+    ("docker", "deployment", "Add a multi-stage Dockerfile, .dockerignore and docker-compose setup"),
+    ("github-actions", "deployment", "Add a GitHub Actions CI workflow for lint, typecheck, test and build"),
+    ("vercel", "deployment", "Add vercel.json, required env var docs and an env pull script"),
+    ("cloudflare", "deployment", "Deploy to Cloudflare Workers with OpenNext and wrangler bindings"),
+    ("k8s", "deployment", "Add Kubernetes manifests, probe endpoints and an optional Helm chart"),
+    ("terraform", "deployment", "Add a Terraform starter for Vercel, AWS or Cloudflare infrastructure"),
+    ("vitest", "testing", "Add Vitest and React Testing Library with example tests"),
+    ("playwright", "testing", "Add Playwright end-to-end tests with CI-ready config"),
+    ("cypress", "testing", "Add Cypress end-to-end and component testing"),
+    ("storybook", "testing", "Add Storybook 8 with stories for shadcn/ui components"),
+    ("zustand", "state", "Add Zustand state management with an SSR-safe provider"),
+    ("redux", "state", "Add Redux Toolkit with RTK Query and a per-request store"),
+    ("jotai", "state", "Add Jotai atoms with a provider and hydration helpers"),
+    ("i18n", "i18n", "Add internationalization with next-intl and locale routing"),
+    ("nativewind", "mobile", "Tailwind styling for Expo apps with NativeWind"),
+    ("expo-router", "mobile", "Expo Router with a tab layout, modal and not-found screens"),
+];
 
-    println!("\n{}", style("Available Features:").cyan().bold());
-    println!("{}", style("----------------").cyan());
+/// Scheduled jobs of built-in features as `(feature, route, schedule)`. The feature writes a
+/// GET route handler at `route` that checks `CRON_SECRET`; deployment features such as
+/// vercel schedule every route an installed feature wrote.
+pub const CRON_JOBS: &[(&str, &str, &str)] = &[
+    ("notifications", "/api/cron/notifications-cleanup", "0 3 * * *"),
+];
 
-    for (name, description) in features {
-        println!("{} - {}", style(name).green().bold(), description);
+/// Lists features, marking each as installed or not when run inside a project.
+/// `installed` and `available` restrict the list to one side.
+pub fn list_features(installed: bool, available: bool) -> Result<()> {
+    let in_project = project_file("package.json").exists();
+    if (installed || available) && !in_project {
+        anyhow::bail!("--installed and --available need a package.json in the current directory");
     }
 
-    println!("\n{}", style("Usage:").cyan().bold());
-    println!("  nstack add --feature <feature-name>");
-    println!("  nstack add (for interactive selection)");
+    let title = if installed {
+        "Installed Features:"
+    } else if available {
+        "Available Features:"
+    } else if in_project {
+        "Features:"
+    } else {
+        "Available Features:"
+    };
+    println!("\n{}", style(title).cyan().bold());
+    println!("{}", style("-".repeat(title.len() - 1)).cyan());
+
+    let total = FEATURES.len();
+    let framework = if in_project { Framework::from_project_config() } else { Framework::Next };
+    let mut shown = 0;
+    let mut installed_count = 0;
+    for &(name, _, description) in FEATURES {
+        if !in_project {
+            println!("{} - {}", style(name).green().bold(), description);
+            shown += 1;
+            continue;
+        }
+
+        let is_installed = ProjectManifest::is_feature_installed(name);
+        if is_installed {
+            installed_count += 1;
+        }
+        if (installed && !is_installed) || (available && is_installed) {
+            continue;
+        }
+
+        if !framework.supports(name) {
+            // Keep the full list visible, but make clear these do not fit this project
+            println!(
+                "{} {} - {}",
+                style("-").dim(),
+                style(name).dim(),
+                style(format!("{} (not available for {})", description, framework.display_name())).dim()
+            );
+            shown += 1;
+            continue;
+        }
+
+        let marker = if is_installed { style("✓").green() } else { style("·").dim() };
+        println!("{} {} - {}", marker, style(name).green().bold(), description);
+        shown += 1;
+    }
+
+    if shown == 0 {
+        println!("{}", style(if installed { "No features installed yet." } else { "Every feature is already installed." }).yellow());
+    }
+
+    if in_project {
+        println!("\n{}", style(format!("{} of {} features installed", installed_count, total)).cyan());
+        if installed_count < total {
+            println!("  nstack add --feature <feature-name>");
+        }
+    } else {
+        println!("\n{}", style("Usage:").cyan().bold());
+        println!("  nstack add --feature <feature-name>");
+        println!("  nstack add (for interactive selection)");
+    }
 
     Ok(())
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn features_are_grouped_by_category() {
+        let mut seen: Vec<&str> = Vec::new();
+        for window in FEATURES.windows(2) {
+            let (previous, current) = (window[0].1, window[1].1);
+            if previous != current {
+                seen.push(previous);
+                assert!(!seen.contains(&current), "{} features are split up around {}", current, window[1].0);
+            }
+        }
+    }
+}
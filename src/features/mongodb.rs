@@ -0,0 +1,172 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::docker_compose;
+use crate::env_file;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+const COMPOSE_SERVICE: &str = r#"  mongo:
+    image: mongo:8
+    ports:
+      - "27017:27017"
+    volumes:
+      - mongo-data:/data/db
+    restart: unless-stopped
+"#;
+
+const CONNECTION: &str = r#"import mongoose from "mongoose";
+
+const MONGODB_URI = process.env.MONGODB_URI;
+
+type MongooseCache = {
+  connection: typeof mongoose | null;
+  promise: Promise<typeof mongoose> | null;
+};
+
+// Hot reloads re-evaluate this module, so the connection is cached on globalThis
+const globalForMongoose = globalThis as unknown as { mongoose?: MongooseCache };
+const cached = (globalForMongoose.mongoose ??= { connection: null, promise: null });
+
+export async function connectToDatabase() {
+  if (cached.connection) {
+    return cached.connection;
+  }
+
+  if (!MONGODB_URI) {
+    throw new Error("MONGODB_URI is not set");
+  }
+
+  cached.promise ??= mongoose.connect(MONGODB_URI, { bufferCommands: false });
+
+  try {
+    cached.connection = await cached.promise;
+  } catch (error) {
+    cached.promise = null;
+    throw error;
+  }
+
+  return cached.connection;
+}
+"#;
+
+const USER_MODEL: &str = r#"import { type InferSchemaType, model, models, type Model, Schema } from "mongoose";
+
+const userSchema = new Schema(
+  {
+    name: { type: String, required: true, trim: true },
+    email: { type: String, required: true, unique: true, lowercase: true, trim: true },
+    role: { type: String, enum: ["user", "admin"], default: "user" },
+  },
+  { timestamps: true },
+);
+
+export type User = InferSchemaType<typeof userSchema>;
+
+// Reuse the compiled model across hot reloads
+export const UserModel: Model<User> = models.User ?? model<User>("User", userSchema);
+"#;
+
+const USERS_ROUTE: &str = r#"import { NextResponse } from "next/server";
+import { connectToDatabase } from "@/lib/mongodb";
+import { UserModel } from "@/lib/models/user";
+
+export async function GET() {
+  await connectToDatabase();
+  const users = await UserModel.find().sort({ createdAt: -1 }).limit(50).lean();
+
+  return NextResponse.json(users);
+}
+
+export async function POST(request: Request) {
+  await connectToDatabase();
+  const body = (await request.json()) as { name?: string; email?: string };
+
+  try {
+    const user = await UserModel.create({ name: body.name, email: body.email });
+    return NextResponse.json(user, { status: 201 });
+  } catch (error) {
+    const message = error instanceof Error ? error.message : "Could not create user";
+    return NextResponse.json({ error: message }, { status: 400 });
+  }
+}
+"#;
+
+pub async fn add_mongodb() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let add_compose_service =
+        prompt::confirm("compose", "Add a MongoDB service to docker-compose.yml for local development?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing mongoose...");
+
+    package_manager.install(&["mongoose"], false)?;
+
+    pb.set_message("Creating the connection helper and User model...");
+
+    let lib_path = project_structure.get_lib_path();
+    change_set::create_dir_all(lib_path.join("models")).context("Failed to create lib/models directory")?;
+    file_writer::write(lib_path.join("mongodb.ts"), CONNECTION)
+        .context("Failed to create mongodb.ts")?;
+    file_writer::write(lib_path.join("models/user.ts"), USER_MODEL)
+        .context("Failed to create user.ts")?;
+
+    pb.set_message("Creating example API route...");
+
+    // Other database features also generate /api/users; never overwrite theirs
+    let route_path = project_structure.get_app_path().join("api/users");
+    let route_file = route_path.join("route.ts");
+    let route_created = !route_file.exists();
+    if route_created {
+        change_set::create_dir_all(&route_path).context("Failed to create api/users directory")?;
+        file_writer::write(&route_file, USERS_ROUTE).context("Failed to create the users route")?;
+    }
+
+    let compose_updated = if add_compose_service {
+        pb.set_message("Adding MongoDB to docker-compose.yml...");
+        docker_compose::add_service("mongo", COMPOSE_SERVICE, Some("mongo-data"))?
+    } else {
+        false
+    };
+
+    env_file::add_env_vars("mongodb", "MongoDB", &[("MONGODB_URI", "mongodb://localhost:27017/app")])?;
+
+    pb.finish_with_message("MongoDB set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/mongodb.ts - Cached mongoose connection", lib_path.display());
+    println!("• {}/models/user.ts - Example User model with inferred types", lib_path.display());
+    if route_created {
+        println!("• {} - List and create users", route_file.display());
+    } else {
+        println!("{}", style(format!("• {} already exists; skipped the example route", route_file.display())).yellow());
+    }
+    if compose_updated {
+        println!("• docker-compose.yml - MongoDB service on port 27017");
+    }
+    println!("• .env - MONGODB_URI");
+
+    println!("\n{}", style("Next steps:").green());
+    if add_compose_service {
+        println!("1. Start MongoDB: docker compose up -d mongo");
+    } else {
+        println!("1. Set MONGODB_URI in .env to your MongoDB or Atlas connection string");
+    }
+    println!("2. Call connectToDatabase() before using models in route handlers and Server Components");
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::root_layout;
+
+const MOTION_PROVIDER: &str = r#""use client";
+
+import { MotionConfig } from "motion/react";
+import type { ReactNode } from "react";
+
+/**
+ * reducedMotion="user" disables transform and layout animations for visitors with
+ * "reduce motion" enabled, while keeping opacity fades.
+ */
+export function MotionProvider({ children }: { children: ReactNode }) {
+  return <MotionConfig reducedMotion="user">{children}</MotionConfig>;
+}
+"#;
+
+const FADE_IN: &str = r#""use client";
+
+import { type HTMLMotionProps, motion } from "motion/react";
+
+type FadeInProps = HTMLMotionProps<"div"> & {
+  delay?: number;
+  /** Distance in px the element travels upward while fading in */
+  offset?: number;
+};
+
+export function FadeIn({ delay = 0, offset = 12, children, ...props }: FadeInProps) {
+  return (
+    <motion.div
+      initial={{ opacity: 0, y: offset }}
+      whileInView={{ opacity: 1, y: 0 }}
+      viewport={{ once: true, margin: "-10% 0px" }}
+      transition={{ duration: 0.4, delay, ease: "easeOut" }}
+      {...props}
+    >
+      {children}
+    </motion.div>
+  );
+}
+"#;
+
+const STAGGER: &str = r#""use client";
+
+import { type HTMLMotionProps, motion, type Variants } from "motion/react";
+
+const container: Variants = {
+  hidden: {},
+  visible: (stagger: number) => ({
+    transition: { staggerChildren: stagger },
+  }),
+};
+
+const item: Variants = {
+  hidden: { opacity: 0, y: 12 },
+  visible: { opacity: 1, y: 0, transition: { duration: 0.3, ease: "easeOut" } },
+};
+
+type StaggerProps = HTMLMotionProps<"div"> & {
+  /** Delay in seconds between each child */
+  stagger?: number;
+};
+
+/**
+ * Animates <StaggerItem> children in one after another when scrolled into view.
+ */
+export function Stagger({ stagger = 0.08, children, ...props }: StaggerProps) {
+  return (
+    <motion.div
+      variants={container}
+      custom={stagger}
+      initial="hidden"
+      whileInView="visible"
+      viewport={{ once: true }}
+      {...props}
+    >
+      {children}
+    </motion.div>
+  );
+}
+
+export function StaggerItem({ children, ...props }: HTMLMotionProps<"div">) {
+  return (
+    <motion.div variants={item} {...props}>
+      {children}
+    </motion.div>
+  );
+}
+"#;
+
+const ROUTE_TEMPLATE: &str = r#""use client";
+
+import { motion, useReducedMotion } from "motion/react";
+import type { ReactNode } from "react";
+
+/**
+ * Unlike layouts, templates re-mount on every navigation, so this plays on each route change.
+ */
+export default function Template({ children }: { children: ReactNode }) {
+  const shouldReduceMotion = useReducedMotion();
+
+  return (
+    <motion.div
+      initial={{ opacity: 0, y: shouldReduceMotion ? 0 : 8 }}
+      animate={{ opacity: 1, y: 0 }}
+      transition={{ duration: 0.25, ease: "easeOut" }}
+    >
+      {children}
+    </motion.div>
+  );
+}
+"#;
+
+pub async fn add_motion() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing motion...");
+
+    package_manager.install(&["motion"], false)?;
+
+    pb.set_message("Creating animation primitives...");
+
+    let motion_path = project_structure.get_components_path().join("motion");
+    change_set::create_dir_all(&motion_path).context("Failed to create motion directory")?;
+    file_writer::write(motion_path.join("motion-provider.tsx"), MOTION_PROVIDER)
+        .context("Failed to create motion-provider.tsx")?;
+    file_writer::write(motion_path.join("fade-in.tsx"), FADE_IN)
+        .context("Failed to create fade-in.tsx")?;
+    file_writer::write(motion_path.join("stagger.tsx"), STAGGER)
+        .context("Failed to create stagger.tsx")?;
+
+    pb.set_message("Creating route transition template...");
+
+    // The template sits next to the root layout so it wraps every page
+    let template_path = root_layout::routes_dir(&project_structure).join("template.tsx");
+    let template_created = !template_path.exists();
+    if template_created {
+        file_writer::write(&template_path, ROUTE_TEMPLATE)
+            .context("Failed to create template.tsx")?;
+    }
+
+    pb.set_message("Adding MotionProvider to the root layout...");
+
+    root_layout::add_import(
+        &project_structure,
+        "import { MotionProvider } from \"@/components/motion/motion-provider\";",
+    )?;
+    root_layout::wrap_children(&project_structure, "<MotionProvider>", "</MotionProvider>")?;
+
+    pb.finish_with_message("Motion set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/fade-in.tsx - Fade and slide in on scroll", motion_path.display());
+    println!("• {}/stagger.tsx - Stagger container and items", motion_path.display());
+    println!("• {}/motion-provider.tsx - Respects the reduced motion setting, added to the root layout", motion_path.display());
+    if template_created {
+        println!("• {} - Route transition", template_path.display());
+    } else {
+        println!("{}", style(format!("• {} already exists; skipped the route transition", template_path.display())).yellow());
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Wrap sections in <FadeIn> or lists in <Stagger> with <StaggerItem> children");
+    println!("2. Check animations with \"reduce motion\" enabled in your OS settings");
+
+    Ok(())
+}
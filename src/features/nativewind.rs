@@ -0,0 +1,120 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_manager::PackageManager;
+use crate::project_structure::project_file;
+
+const TAILWIND_CONFIG: &str = r#"/** @type {import('tailwindcss').Config} */
+module.exports = {
+  content: ["./app/**/*.{js,jsx,ts,tsx}", "./components/**/*.{js,jsx,ts,tsx}"],
+  presets: [require("nativewind/preset")],
+  theme: {
+    extend: {},
+  },
+  plugins: [],
+};
+"#;
+
+const GLOBAL_CSS: &str = r#"@tailwind base;
+@tailwind components;
+@tailwind utilities;
+"#;
+
+const BABEL_CONFIG: &str = r#"module.exports = function (api) {
+  api.cache(true);
+  return {
+    presets: [["babel-preset-expo", { jsxImportSource: "nativewind" }], "nativewind/babel"],
+  };
+};
+"#;
+
+const METRO_CONFIG: &str = r#"const { getDefaultConfig } = require("expo/metro-config");
+const { withNativeWind } = require("nativewind/metro");
+
+const config = getDefaultConfig(__dirname);
+
+module.exports = withNativeWind(config, { input: "./global.css" });
+"#;
+
+const NATIVEWIND_ENV: &str = r#"/// <reference types="nativewind/types" />
+"#;
+
+/// Root layouts of the Expo Router templates, checked in order.
+const ROOT_LAYOUTS: [&str; 2] = ["app/_layout.tsx", "src/app/_layout.tsx"];
+
+/// Imports global.css at the top of the Expo Router root layout so the styles load once.
+fn import_global_css() -> Result<Option<&'static str>> {
+    let Some(layout) = ROOT_LAYOUTS.into_iter().find(|path| project_file(path).exists()) else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(project_file(layout)).context(format!("Failed to read {}", layout))?;
+    if content.contains("global.css") {
+        return Ok(Some(layout));
+    }
+
+    let relative = if layout.starts_with("src/") { "../../global.css" } else { "../global.css" };
+    change_set::write(project_file(layout), format!("import \"{}\";\n{}", relative, content))
+        .context(format!("Failed to update {}", layout))?;
+    Ok(Some(layout))
+}
+
+pub async fn add_nativewind() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing NativeWind...");
+
+    // `expo install` picks the native module versions matching the project's Expo SDK
+    package_manager
+        .exec(&["expo", "install", "nativewind", "react-native-reanimated", "react-native-safe-area-context"])
+        .context("Failed to install NativeWind")?;
+    // NativeWind v4 compiles with Tailwind CSS v3
+    package_manager.install(&["tailwindcss@^3.4.17", "prettier-plugin-tailwindcss"], true)?;
+
+    pb.set_message("Creating NativeWind configuration...");
+
+    file_writer::write(project_file("tailwind.config.js"), TAILWIND_CONFIG)
+        .context("Failed to create tailwind.config.js")?;
+    file_writer::write(project_file("global.css"), GLOBAL_CSS)
+        .context("Failed to create global.css")?;
+    file_writer::write(project_file("babel.config.js"), BABEL_CONFIG)
+        .context("Failed to create babel.config.js")?;
+    file_writer::write(project_file("metro.config.js"), METRO_CONFIG)
+        .context("Failed to create metro.config.js")?;
+    file_writer::write(project_file("nativewind-env.d.ts"), NATIVEWIND_ENV)
+        .context("Failed to create nativewind-env.d.ts")?;
+
+    let layout = import_global_css()?;
+
+    pb.finish_with_message("NativeWind set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• tailwind.config.js - Tailwind config with the NativeWind preset");
+    println!("• global.css - Tailwind directives");
+    println!("• babel.config.js - Expo preset compiling className with NativeWind");
+    println!("• metro.config.js - Metro bundler wired to global.css");
+    println!("• nativewind-env.d.ts - className types for React Native components");
+    if let Some(layout) = layout {
+        println!("• {} - imports global.css", layout);
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    if layout.is_none() {
+        println!("• Import \"./global.css\" in your app's entry file");
+    }
+    println!("• Style components with className, e.g. <View className=\"flex-1 items-center\" />");
+    println!("• Restart Metro with a cleared cache: {}", package_manager.exec_command("expo start -c"));
+
+    Ok(())
+}
@@ -0,0 +1,541 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::features::admin;
+use crate::features::schema_conventions::{self, IdStrategy};
+use crate::file_writer;
+use crate::manifest;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NotificationProvider {
+    SelfHosted,
+    Novu,
+    Knock,
+}
+
+impl NotificationProvider {
+    const ALL: [NotificationProvider; 3] =
+        [NotificationProvider::SelfHosted, NotificationProvider::Novu, NotificationProvider::Knock];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            NotificationProvider::SelfHosted => "Self-hosted",
+            NotificationProvider::Novu => "Novu",
+            NotificationProvider::Knock => "Knock",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            NotificationProvider::SelfHosted => "a notifications table in your drizzle database",
+            NotificationProvider::Novu => "Novu's hosted inbox and workflows",
+            NotificationProvider::Knock => "Knock's hosted feed and workflows",
+        }
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        match self {
+            NotificationProvider::SelfHosted => &[],
+            NotificationProvider::Novu => &["@novu/nextjs", "@novu/api"],
+            NotificationProvider::Knock => &["@knocklabs/react", "@knocklabs/node"],
+        }
+    }
+
+    fn env_vars(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            NotificationProvider::SelfHosted => &[],
+            NotificationProvider::Novu => &[("NOVU_SECRET_KEY", ""), ("NEXT_PUBLIC_NOVU_APPLICATION_IDENTIFIER", "")],
+            NotificationProvider::Knock => &[
+                ("KNOCK_SECRET_API_KEY", ""),
+                ("NEXT_PUBLIC_KNOCK_PUBLIC_API_KEY", ""),
+                ("NEXT_PUBLIC_KNOCK_FEED_CHANNEL_ID", ""),
+            ],
+        }
+    }
+}
+
+const NOTIFICATIONS_TABLE: &str = r#"
+// In-app notifications
+export const notificationsTable = pgTable("notifications", {
+  id: __ID__,
+  userId: __FOREIGN_ID__("user_id").notNull().references(() => usersTable.id, { onDelete: "cascade" }),
+  title: text("title").notNull(),
+  body: text("body"),
+  href: text("href"),
+  readAt: timestamp("read_at"),
+  __TIMESTAMPS__
+});
+
+export type Notification = typeof notificationsTable.$inferSelect;
+"#;
+
+const CURRENT_USER: &str = r#"import { eq } from "drizzle-orm";
+import { db } from "@/db";
+import { usersTable } from "@/db/schema";
+__SESSION_IMPORTS__
+export type SessionUser = {
+  email?: string | null;
+};
+
+__SESSION_USER__
+/** The signed-in user's usersTable id, matched by email, or null when signed out. */
+export async function getCurrentUserId() {
+  const user = await getSessionUser();
+  if (!user?.email) return null;
+
+  const [row] = await db
+    .select({ id: usersTable.id })
+    .from(usersTable)
+    .where(eq(usersTable.email, user.email))
+    .limit(1);
+  return row?.id ?? null;
+}
+"#;
+
+const QUERIES: &str = r#"import { and, count, desc, eq, isNull } from "drizzle-orm";
+import { db } from "@/db";
+import { notificationsTable, type Notification } from "@/db/schema";
+
+export async function getNotifications(userId: Notification["userId"], limit = 20) {
+  return db
+    .select()
+    .from(notificationsTable)
+    .where(eq(notificationsTable.userId, userId))
+    .orderBy(desc(notificationsTable.createdAt))
+    .limit(limit);
+}
+
+export async function getUnreadCount(userId: Notification["userId"]) {
+  const [{ total }] = await db
+    .select({ total: count() })
+    .from(notificationsTable)
+    .where(and(eq(notificationsTable.userId, userId), isNull(notificationsTable.readAt)));
+  return total;
+}
+"#;
+
+const SELF_HOSTED_NOTIFY: &str = r#"import { db } from "@/db";
+import { notificationsTable, type Notification } from "@/db/schema";
+
+type NewNotification = {
+  userId: Notification["userId"];
+  title: string;
+  body?: string;
+  /** Where clicking the notification leads */
+  href?: string;
+};
+
+/** Sends an in-app notification; call it from server code wherever something happens. */
+export async function notify(notification: NewNotification) {
+  const [created] = await db.insert(notificationsTable).values(notification).returning();
+  return created;
+}
+"#;
+
+const CLEANUP_CRON: &str = r#"import { and, isNotNull, lt } from "drizzle-orm";
+import { db } from "@/db";
+import { notificationsTable } from "@/db/schema";
+
+/** Days a read notification is kept before the cleanup job deletes it. */
+const RETENTION_DAYS = 30;
+
+/**
+ * Deletes notifications read more than RETENTION_DAYS ago. Vercel Cron calls it with
+ * `Authorization: Bearer $CRON_SECRET`; any other scheduler can send the same header.
+ */
+export async function GET(request: Request) {
+  const secret = process.env.CRON_SECRET;
+  if (!secret || request.headers.get("authorization") !== `Bearer ${secret}`) {
+    return new Response("Unauthorized", { status: 401 });
+  }
+
+  const cutoff = new Date(Date.now() - RETENTION_DAYS * 24 * 60 * 60 * 1000);
+  const deleted = await db
+    .delete(notificationsTable)
+    .where(and(isNotNull(notificationsTable.readAt), lt(notificationsTable.readAt, cutoff)))
+    .returning({ id: notificationsTable.id });
+  return Response.json({ deleted: deleted.length });
+}
+"#;
+
+const ACTIONS: &str = r#""use server";
+
+import { and, eq, isNull } from "drizzle-orm";
+import { revalidatePath } from "next/cache";
+import { db } from "@/db";
+import { notificationsTable, type Notification } from "@/db/schema";
+import { getCurrentUserId } from "@/lib/notifications/current-user";
+
+export async function markAsRead(id: Notification["id"]) {
+  const userId = await getCurrentUserId();
+  if (userId === null) return;
+
+  await db
+    .update(notificationsTable)
+    .set({ readAt: new Date() })
+    .where(and(eq(notificationsTable.id, id), eq(notificationsTable.userId, userId)));
+  revalidatePath("/", "layout");
+}
+
+export async function markAllAsRead() {
+  const userId = await getCurrentUserId();
+  if (userId === null) return;
+
+  await db
+    .update(notificationsTable)
+    .set({ readAt: new Date() })
+    .where(and(eq(notificationsTable.userId, userId), isNull(notificationsTable.readAt)));
+  revalidatePath("/", "layout");
+}
+"#;
+
+const SELF_HOSTED_BELL: &str = r#"import { NotificationDropdown } from "@/components/notifications/notification-dropdown";
+import { getCurrentUserId } from "@/lib/notifications/current-user";
+import { getNotifications, getUnreadCount } from "@/lib/notifications/queries";
+
+/** The bell for the signed-in user; renders nothing when signed out. */
+export async function NotificationBell() {
+  const userId = await getCurrentUserId();
+  if (userId === null) return null;
+
+  const [notifications, unreadCount] = await Promise.all([getNotifications(userId), getUnreadCount(userId)]);
+  return <NotificationDropdown notifications={notifications} unreadCount={unreadCount} />;
+}
+"#;
+
+const DROPDOWN: &str = r##""use client";
+
+import Link from "next/link";
+import { useEffect, useRef, useState, useTransition } from "react";
+import type { Notification } from "@/db/schema";
+import { markAllAsRead, markAsRead } from "@/lib/notifications/actions";
+
+type NotificationDropdownProps = {
+  notifications: Notification[];
+  unreadCount: number;
+};
+
+export function NotificationDropdown({ notifications, unreadCount }: NotificationDropdownProps) {
+  const [open, setOpen] = useState(false);
+  const [pending, startTransition] = useTransition();
+  const container = useRef<HTMLDivElement>(null);
+
+  // Close on a click outside or Escape
+  useEffect(() => {
+    if (!open) return;
+    const onPointerDown = (event: PointerEvent) => {
+      if (!container.current?.contains(event.target as Node)) setOpen(false);
+    };
+    const onKeyDown = (event: KeyboardEvent) => {
+      if (event.key === "Escape") setOpen(false);
+    };
+    document.addEventListener("pointerdown", onPointerDown);
+    document.addEventListener("keydown", onKeyDown);
+    return () => {
+      document.removeEventListener("pointerdown", onPointerDown);
+      document.removeEventListener("keydown", onKeyDown);
+    };
+  }, [open]);
+
+  return (
+    <div ref={container} className="relative">
+      <button
+        type="button"
+        aria-label={unreadCount > 0 ? `Notifications, ${unreadCount} unread` : "Notifications"}
+        aria-expanded={open}
+        onClick={() => setOpen(!open)}
+        className="relative rounded-md p-2 hover:bg-gray-100 dark:hover:bg-neutral-800"
+      >
+        <svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" aria-hidden="true">
+          <path d="M6 8a6 6 0 0 1 12 0c0 7 3 9 3 9H3s3-2 3-9" />
+          <path d="M10.3 21a1.94 1.94 0 0 0 3.4 0" />
+        </svg>
+        {unreadCount > 0 && (
+          <span className="absolute -right-0.5 -top-0.5 flex h-4 min-w-4 items-center justify-center rounded-full bg-red-600 px-1 text-[10px] font-medium text-white">
+            {unreadCount > 99 ? "99+" : unreadCount}
+          </span>
+        )}
+      </button>
+
+      {open && (
+        <div className="absolute right-0 z-50 mt-2 w-80 rounded-md border bg-white shadow-lg dark:bg-neutral-900">
+          <div className="flex items-center justify-between border-b px-3 py-2">
+            <span className="text-sm font-medium">Notifications</span>
+            {unreadCount > 0 && (
+              <button
+                type="button"
+                disabled={pending}
+                onClick={() => startTransition(() => markAllAsRead())}
+                className="text-xs text-gray-600 underline disabled:opacity-50"
+              >
+                Mark all as read
+              </button>
+            )}
+          </div>
+          {notifications.length === 0 ? (
+            <p className="px-3 py-6 text-center text-sm text-gray-500">You&apos;re all caught up</p>
+          ) : (
+            <ul className="max-h-96 overflow-y-auto">
+              {notifications.map((notification) => (
+                <li key={notification.id} className={notification.readAt ? "" : "bg-blue-50 dark:bg-blue-950/40"}>
+                  <Link
+                    href={notification.href ?? "#"}
+                    onClick={() => {
+                      setOpen(false);
+                      if (!notification.readAt) startTransition(() => markAsRead(notification.id));
+                    }}
+                    className="block px-3 py-2 hover:bg-gray-100 dark:hover:bg-neutral-800"
+                  >
+                    <div className="text-sm font-medium">{notification.title}</div>
+                    {notification.body && <div className="text-sm text-gray-600">{notification.body}</div>}
+                    <div className="mt-1 text-xs text-gray-400">{notification.createdAt.toLocaleString()}</div>
+                  </Link>
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+"##;
+
+const NOVU_NOTIFY: &str = r#"import { Novu } from "@novu/api";
+
+const novu = new Novu({ secretKey: process.env.NOVU_SECRET_KEY });
+
+type NewNotification = {
+  userId: string | number;
+  title: string;
+  body?: string;
+  /** Where clicking the notification leads */
+  href?: string;
+};
+
+/**
+ * Sends an in-app notification through the `in-app` workflow, which renders `title`,
+ * `body` and `href` from its payload. Create the workflow in the Novu dashboard.
+ */
+export async function notify({ userId, ...payload }: NewNotification) {
+  await novu.trigger({
+    workflowId: "in-app",
+    to: { subscriberId: String(userId) },
+    payload,
+  });
+}
+"#;
+
+const NOVU_BELL: &str = r#""use client";
+
+import { Inbox } from "@novu/nextjs";
+
+/** The Novu inbox for a user; pass the same id `notify()` is called with. */
+export function NotificationBell({ userId }: { userId: string | number }) {
+  return (
+    <Inbox
+      applicationIdentifier={process.env.NEXT_PUBLIC_NOVU_APPLICATION_IDENTIFIER!}
+      subscriberId={String(userId)}
+    />
+  );
+}
+"#;
+
+const KNOCK_NOTIFY: &str = r#"import { Knock } from "@knocklabs/node";
+
+const knock = new Knock({ apiKey: process.env.KNOCK_SECRET_API_KEY });
+
+type NewNotification = {
+  userId: string | number;
+  title: string;
+  body?: string;
+  /** Where clicking the notification leads */
+  href?: string;
+};
+
+/**
+ * Sends an in-app notification through the `in-app` workflow, which renders `title`,
+ * `body` and `href` from its data. Create the workflow in the Knock dashboard.
+ */
+export async function notify({ userId, ...data }: NewNotification) {
+  await knock.workflows.trigger("in-app", {
+    recipients: [String(userId)],
+    data,
+  });
+}
+"#;
+
+const KNOCK_BELL: &str = r#""use client";
+
+import { KnockFeedProvider, KnockProvider, NotificationFeedPopover, NotificationIconButton } from "@knocklabs/react";
+import { useRef, useState } from "react";
+import "@knocklabs/react/dist/index.css";
+
+/** The Knock feed for a user; pass the same id `notify()` is called with. */
+export function NotificationBell({ userId }: { userId: string | number }) {
+  const [open, setOpen] = useState(false);
+  const buttonRef = useRef<HTMLButtonElement>(null);
+
+  return (
+    <KnockProvider apiKey={process.env.NEXT_PUBLIC_KNOCK_PUBLIC_API_KEY!} userId={String(userId)}>
+      <KnockFeedProvider feedId={process.env.NEXT_PUBLIC_KNOCK_FEED_CHANNEL_ID!}>
+        <NotificationIconButton ref={buttonRef} onClick={() => setOpen(!open)} />
+        <NotificationFeedPopover buttonRef={buttonRef} isVisible={open} onClose={() => setOpen(false)} />
+      </KnockFeedProvider>
+    </KnockProvider>
+  );
+}
+"#;
+
+pub async fn add_notifications() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let descriptions: Vec<String> = NotificationProvider::ALL
+        .iter()
+        .map(|provider| format!("{} - {}", provider.as_str(), provider.description()))
+        .collect();
+    let selection = prompt::select("provider", "Where should notifications live?", &descriptions, 0)?;
+    let provider = NotificationProvider::ALL[selection];
+
+    let db_path = project_structure.get_db_path();
+    let schema_path = db_path.join("schema.ts");
+    let schema = std::fs::read_to_string(&schema_path).unwrap_or_default();
+    if provider == NotificationProvider::SelfHosted
+        && (!schema.contains("drizzle-orm/pg-core") || !schema.contains("export const usersTable"))
+    {
+        println!(
+            "{}",
+            style("Self-hosted notifications are stored next to the drizzle usersTable. Run `nstack add --feature drizzle` first, or pick Novu or Knock.").yellow()
+        );
+        return Ok(());
+    }
+
+    let auth_library = manifest::installed_auth_library();
+    let pb = ProgressBar::new_spinner();
+    if !provider.dependencies().is_empty() {
+        pb.set_message(format!("Installing {} dependencies...", provider.as_str()));
+        package_manager.install(provider.dependencies(), false)?;
+    }
+
+    let notifications_lib_path = project_structure.get_lib_path().join("notifications");
+    let cleanup_path = project_structure.get_api_path().join("cron").join("notifications-cleanup");
+    let components_path = project_structure.get_components_path().join("notifications");
+    change_set::create_dir_all(&notifications_lib_path).context("Failed to create lib/notifications directory")?;
+    change_set::create_dir_all(&components_path).context("Failed to create components/notifications directory")?;
+
+    match provider {
+        NotificationProvider::SelfHosted => {
+            pb.set_message("Adding the notifications table to the schema...");
+
+            if !schema.contains("export const notificationsTable ") {
+                let helpers = IdStrategy::installed(&db_path).is_some();
+                let table = schema_conventions::render_columns(NOTIFICATIONS_TABLE, helpers);
+                schema_conventions::append_tables(
+                    &schema_path,
+                    &schema,
+                    &[&table],
+                    &["pgTable", "integer", "text", "timestamp"],
+                )?;
+            }
+
+            pb.set_message("Creating queries and server actions...");
+
+            let (session_imports, session_user) = admin::session_user(auth_library.map(|(package, _)| package));
+            let current_user = CURRENT_USER
+                .replace("__SESSION_IMPORTS__", session_imports)
+                .replace("__SESSION_USER__", session_user);
+            file_writer::write(notifications_lib_path.join("current-user.ts"), current_user)
+                .context("Failed to create current-user.ts")?;
+            file_writer::write(notifications_lib_path.join("queries.ts"), QUERIES)
+                .context("Failed to create queries.ts")?;
+            file_writer::write(notifications_lib_path.join("notify.ts"), SELF_HOSTED_NOTIFY)
+                .context("Failed to create notify.ts")?;
+            file_writer::write(notifications_lib_path.join("actions.ts"), ACTIONS)
+                .context("Failed to create actions.ts")?;
+
+            pb.set_message("Creating the notification bell...");
+
+            file_writer::write(components_path.join("notification-bell.tsx"), SELF_HOSTED_BELL)
+                .context("Failed to create notification-bell.tsx")?;
+            file_writer::write(components_path.join("notification-dropdown.tsx"), DROPDOWN)
+                .context("Failed to create notification-dropdown.tsx")?;
+
+            pb.set_message("Creating the cleanup cron route...");
+
+            change_set::create_dir_all(&cleanup_path).context("Failed to create the cleanup cron directory")?;
+            file_writer::write(cleanup_path.join("route.ts"), CLEANUP_CRON)
+                .context("Failed to create the cleanup cron route")?;
+            env_file::add_env_vars("notifications", "Notifications", &[("CRON_SECRET", "")])?;
+        }
+        NotificationProvider::Novu | NotificationProvider::Knock => {
+            pb.set_message(format!("Creating the {} client and bell...", provider.as_str()));
+
+            let (notify, bell) = match provider {
+                NotificationProvider::Novu => (NOVU_NOTIFY, NOVU_BELL),
+                _ => (KNOCK_NOTIFY, KNOCK_BELL),
+            };
+            file_writer::write(notifications_lib_path.join("notify.ts"), notify)
+                .context("Failed to create notify.ts")?;
+            file_writer::write(components_path.join("notification-bell.tsx"), bell)
+                .context("Failed to create notification-bell.tsx")?;
+            env_file::add_env_vars("notifications", "Notifications", provider.env_vars())?;
+        }
+    }
+
+    pb.finish_with_message(format!("{} notifications added successfully!", provider.as_str()));
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    if provider == NotificationProvider::SelfHosted {
+        println!("• {} - notificationsTable", schema_path.display());
+        println!("• {}/ - notify(), queries, read/unread server actions and the current user", notifications_lib_path.display());
+        println!("• {}/ - Bell with an unread count and a dropdown", components_path.display());
+        println!("• {}/route.ts - Daily cleanup of notifications read over 30 days ago", cleanup_path.display());
+    } else {
+        println!("• {}/notify.ts - notify() through the {} `in-app` workflow", notifications_lib_path.display(), provider.as_str());
+        println!("• {}/notification-bell.tsx - {} inbox", components_path.display(), provider.as_str());
+    }
+
+    if provider == NotificationProvider::SelfHosted && auth_library.is_none() {
+        println!(
+            "{}",
+            style("No auth library found; the bell stays hidden until getSessionUser() in current-user.ts returns the signed-in user").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    match provider {
+        NotificationProvider::SelfHosted => {
+            println!("1. Push the schema with `db:push` or generate a migration with `db:generate`");
+            println!("2. Render <NotificationBell /> in your header");
+            println!("3. Set CRON_SECRET (e.g. `openssl rand -hex 32`) and schedule GET /api/cron/notifications-cleanup daily; `nstack add --feature vercel` adds it to vercel.json");
+        }
+        NotificationProvider::Novu => {
+            println!("1. Set NOVU_SECRET_KEY and NEXT_PUBLIC_NOVU_APPLICATION_IDENTIFIER in .env");
+            println!("2. Create an `in-app` workflow in the Novu dashboard");
+            println!("3. Render <NotificationBell userId={{user.id}} /> in your header");
+        }
+        NotificationProvider::Knock => {
+            println!("1. Set the KNOCK_* keys and the feed channel id in .env");
+            println!("2. Create an `in-app` workflow with an in-app feed step in the Knock dashboard");
+            println!("3. Render <NotificationBell userId={{user.id}} /> in your header");
+        }
+    }
+    println!("4. Call notify({{ userId, title, body, href }}) from server code");
+
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::env_file;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+const OG_HELPERS: &str = r#"import type { Metadata } from "next";
+
+export const OG_SIZE = { width: 1200, height: 630 };
+
+export const siteConfig = {
+  name: "__SITE_NAME__",
+  accentColor: "__ACCENT_COLOR__",
+  url: process.env.NEXT_PUBLIC_SITE_URL ?? "http://localhost:3000",
+};
+
+export type OgImageType = "default" | "post" | "product";
+
+export interface OgImageParams {
+  title: string;
+  subtitle?: string;
+  type?: OgImageType;
+  price?: string;
+}
+
+/**
+ * Loads a Google Font as an ArrayBuffer for use with ImageResponse.
+ * Passing `text` subsets the font so only the required glyphs are fetched.
+ */
+export async function loadGoogleFont(font: string, weight: number, text?: string) {
+  const params = new URLSearchParams({ family: `${font}:wght@${weight}` });
+  if (text) params.set("text", text);
+
+  const css = await (await fetch(`https://fonts.googleapis.com/css2?${params}`)).text();
+  const fontUrl = css.match(/src: url\((.+?)\) format\('(opentype|truetype)'\)/)?.[1];
+  if (!fontUrl) {
+    throw new Error(`Failed to load font data for ${font}`);
+  }
+
+  const response = await fetch(fontUrl);
+  if (!response.ok) {
+    throw new Error(`Failed to fetch font file for ${font}`);
+  }
+  return response.arrayBuffer();
+}
+
+/**
+ * Builds the URL of the parametrized OG image generator.
+ */
+export function ogImageUrl({ title, subtitle, type = "default", price }: OgImageParams) {
+  const url = new URL("/api/og", siteConfig.url);
+  url.searchParams.set("title", title);
+  url.searchParams.set("type", type);
+  if (subtitle) url.searchParams.set("subtitle", subtitle);
+  if (price) url.searchParams.set("price", price);
+  return url.toString();
+}
+
+/**
+ * Returns the openGraph/twitter metadata for a page, pointing at the generated image.
+ *
+ * export async function generateMetadata({ params }) {
+ *   const post = await getPost(params.slug);
+ *   return ogMetadata({ title: post.title, subtitle: post.excerpt, type: "post" });
+ * }
+ */
+export function ogMetadata(params: OgImageParams): Metadata {
+  const image = { url: ogImageUrl(params), ...OG_SIZE, alt: params.title };
+  // Spread rather than set to undefined, which exactOptionalPropertyTypes rejects
+  const description = params.subtitle ? { description: params.subtitle } : {};
+
+  return {
+    title: params.title,
+    ...description,
+    openGraph: {
+      title: params.title,
+      ...description,
+      siteName: siteConfig.name,
+      type: params.type === "post" ? "article" : "website",
+      images: [image],
+    },
+    twitter: {
+      card: "summary_large_image",
+      title: params.title,
+      ...description,
+      images: [image.url],
+    },
+  };
+}
+"#;
+
+const OG_TEMPLATE: &str = r##"import { siteConfig, type OgImageType } from "@/lib/og";
+
+interface OgTemplateProps {
+  title: string;
+  subtitle?: string;
+  type?: OgImageType;
+  price?: string;
+}
+
+const labels: Record<OgImageType, string | null> = {
+  default: null,
+  post: "Blog",
+  product: "Product",
+};
+
+// Rendered by ImageResponse (satori), so only flexbox layouts and inline styles are supported.
+export function OgTemplate({ title, subtitle, type = "default", price }: OgTemplateProps) {
+  const label = labels[type];
+
+  return (
+    <div
+      style={{
+        width: "100%",
+        height: "100%",
+        display: "flex",
+        flexDirection: "column",
+        justifyContent: "space-between",
+        padding: "72px",
+        background: "#0a0a0a",
+        color: "#fafafa",
+        fontFamily: "Inter",
+      }}
+    >
+      <div style={{ display: "flex", alignItems: "center", gap: "16px" }}>
+        <div
+          style={{
+            width: "48px",
+            height: "48px",
+            borderRadius: "12px",
+            background: siteConfig.accentColor,
+          }}
+        />
+        <span style={{ fontSize: "32px", fontWeight: 700 }}>{siteConfig.name}</span>
+        {label && (
+          <span
+            style={{
+              marginLeft: "16px",
+              padding: "6px 16px",
+              borderRadius: "9999px",
+              border: `2px solid ${siteConfig.accentColor}`,
+              fontSize: "24px",
+            }}
+          >
+            {label}
+          </span>
+        )}
+      </div>
+
+      <div style={{ display: "flex", flexDirection: "column", gap: "24px" }}>
+        <div style={{ fontSize: title.length > 60 ? "56px" : "72px", fontWeight: 700, lineHeight: 1.1 }}>
+          {title}
+        </div>
+        {subtitle && (
+          <div style={{ fontSize: "32px", color: "#a3a3a3", lineHeight: 1.4 }}>{subtitle}</div>
+        )}
+      </div>
+
+      <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center" }}>
+        <span style={{ fontSize: "24px", color: "#737373" }}>
+          {siteConfig.url.replace(/^https?:\/\//, "")}
+        </span>
+        {price && (
+          <span style={{ fontSize: "40px", fontWeight: 700, color: siteConfig.accentColor }}>
+            {price}
+          </span>
+        )}
+      </div>
+    </div>
+  );
+}
+"##;
+
+const ROOT_OG_IMAGE: &str = r#"import { ImageResponse } from "next/og";
+import { OgTemplate } from "@/components/og/og-template";
+import { OG_SIZE, loadGoogleFont, siteConfig } from "@/lib/og";
+
+export const alt = siteConfig.name;
+export const size = OG_SIZE;
+export const contentType = "image/png";
+
+export default async function Image() {
+  const title = `Welcome to ${siteConfig.name}`;
+
+  return new ImageResponse(<OgTemplate title={title} />, {
+    ...size,
+    fonts: [
+      {
+        name: "Inter",
+        data: await loadGoogleFont("Inter", 700, `${siteConfig.name}${title}`),
+        weight: 700,
+        style: "normal",
+      },
+    ],
+  });
+}
+"#;
+
+const OG_ROUTE: &str = r#"import { ImageResponse } from "next/og";
+import type { NextRequest } from "next/server";
+import { OgTemplate } from "@/components/og/og-template";
+import { OG_SIZE, loadGoogleFont, siteConfig, type OgImageType } from "@/lib/og";
+
+const TYPES: OgImageType[] = ["default", "post", "product"];
+
+export async function GET(request: NextRequest) {
+  const { searchParams } = request.nextUrl;
+
+  const title = searchParams.get("title")?.slice(0, 120) ?? siteConfig.name;
+  const subtitle = searchParams.get("subtitle")?.slice(0, 200) ?? undefined;
+  const price = searchParams.get("price")?.slice(0, 20) ?? undefined;
+  const requestedType = searchParams.get("type") as OgImageType | null;
+  const type = requestedType && TYPES.includes(requestedType) ? requestedType : "default";
+
+  const text = [siteConfig.name, title, subtitle, price, "BlogProduct"].filter(Boolean).join("");
+
+  try {
+    const [regular, bold] = await Promise.all([
+      loadGoogleFont("Inter", 400, text),
+      loadGoogleFont("Inter", 700, text),
+    ]);
+
+    return new ImageResponse(
+      <OgTemplate title={title} type={type} {...(subtitle ? { subtitle } : {})} {...(price ? { price } : {})} />,
+      {
+        ...OG_SIZE,
+        fonts: [
+          { name: "Inter", data: regular, weight: 400, style: "normal" },
+          { name: "Inter", data: bold, weight: 700, style: "normal" },
+        ],
+        headers: {
+          "Cache-Control": "public, immutable, no-transform, max-age=31536000",
+        },
+      },
+    );
+  } catch (error) {
+    return new Response("Failed to generate OG image", { status: 500 });
+  }
+}
+"#;
+
+pub async fn add_og_image() -> Result<()> {
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    let site_name = prompt::input("site_name", "Site name shown on OG images", "My App")?;
+    let accent_color = prompt::input("accent_color", "Brand accent color", "#6366f1")?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Creating OG image helpers...");
+
+    let lib_path = project_structure.get_lib_path();
+    change_set::create_dir_all(&lib_path).context("Failed to create lib directory")?;
+    let helpers = OG_HELPERS
+        .replace("__SITE_NAME__", &site_name.replace('"', "\\\""))
+        .replace("__ACCENT_COLOR__", &accent_color);
+    file_writer::write(lib_path.join("og.ts"), helpers)
+        .context("Failed to create og.ts")?;
+
+    pb.set_message("Creating OG image template...");
+
+    let og_components_path = project_structure.get_components_path().join("og");
+    change_set::create_dir_all(&og_components_path)
+        .context("Failed to create components/og directory")?;
+    file_writer::write(og_components_path.join("og-template.tsx"), OG_TEMPLATE)
+        .context("Failed to create og-template.tsx")?;
+
+    pb.set_message("Creating opengraph-image routes...");
+
+    let app_path = project_structure.get_app_path();
+    change_set::create_dir_all(&app_path).context("Failed to create app directory")?;
+    file_writer::write(app_path.join("opengraph-image.tsx"), ROOT_OG_IMAGE)
+        .context("Failed to create opengraph-image.tsx")?;
+
+    let og_route_path = app_path.join("api/og");
+    change_set::create_dir_all(&og_route_path).context("Failed to create api/og directory")?;
+    file_writer::write(og_route_path.join("route.tsx"), OG_ROUTE)
+        .context("Failed to create api/og/route.tsx")?;
+
+    // Absolute URLs are required for OG images, so make sure the site URL is configurable
+    env_file::add_env_vars("og-image", "Site", &[("NEXT_PUBLIC_SITE_URL", "http://localhost:3000")])?;
+
+    pb.finish_with_message("OG image generation set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/og.ts - Font loading and metadata helpers", lib_path.display());
+    println!("• {}/og-template.tsx - Branded OG image template", og_components_path.display());
+    println!("• {}/opengraph-image.tsx - Default site OG image", app_path.display());
+    println!("• {}/route.tsx - Parametrized generator for post/product pages", og_route_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Set NEXT_PUBLIC_SITE_URL in .env to your production URL");
+    println!("2. Use ogMetadata() from @/lib/og in generateMetadata for posts and products");
+    println!("3. Preview an image at /api/og?title=Hello&type=post");
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::json;
+use std::path::Path;
+use crate::bundler::{self, Bundler};
+use crate::file_writer;
+use crate::next_config;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+
+// Libraries with many per-module exports that benefit from optimizePackageImports
+const BARREL_PACKAGES: [&str; 9] = [
+    "lucide-react",
+    "@radix-ui/react-icons",
+    "motion",
+    "framer-motion",
+    "recharts",
+    "date-fns",
+    "react-icons",
+    "@tabler/icons-react",
+    "@heroicons/react",
+];
+
+/// Collects page directories relative to the app dir, e.g. `""` for `/` and `blog/[slug]`.
+fn discover_pages(dir: &Path, relative: &str, extensions: &[String], pages: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            // API routes and private folders don't ship client bundles
+            if name == "api" || name.starts_with('_') {
+                continue;
+            }
+            let child = if relative.is_empty() { name } else { format!("{}/{}", relative, name) };
+            discover_pages(&path, &child, extensions, pages);
+        } else if extensions.iter().any(|extension| name == format!("page.{}", extension)) {
+            pages.push(relative.to_string());
+        }
+    }
+}
+
+/// Pages-router counterpart of `discover_pages`: every page file is a route, with
+/// `index` standing for its directory.
+fn discover_pages_router(dir: &Path, relative: &str, extensions: &[String], pages: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        // API routes and _app/_document don't ship page bundles
+        if name == "api" || name.starts_with('_') {
+            continue;
+        }
+
+        let join = |segment: &str| if relative.is_empty() { segment.to_string() } else { format!("{}/{}", relative, segment) };
+        if path.is_dir() {
+            discover_pages_router(&path, &join(&name), extensions, pages);
+        } else if let Some(stem) = extensions.iter().find_map(|extension| name.strip_suffix(&format!(".{}", extension))) {
+            pages.push(if stem == "index" { relative.to_string() } else { join(stem) });
+        }
+    }
+}
+
+fn route_name(page_dir: &str) -> String {
+    // Route groups like (marketing) don't appear in the URL
+    let segments: Vec<&str> = page_dir
+        .split('/')
+        .filter(|segment| !(segment.is_empty() || segment.starts_with('(') && segment.ends_with(')')))
+        .collect();
+    format!("/{}", segments.join("/"))
+}
+
+fn size_limit_config(pages: &[String], budget_kb: &str, app_router: bool) -> serde_json::Value {
+    let mut entries = vec![json!({
+        "name": "shared framework chunks",
+        "path": ".next/static/chunks/*.js",
+        "limit": "150 kB"
+    })];
+
+    for page in pages {
+        let chunks = if !app_router {
+            format!(".next/static/chunks/pages/{}-*.js", if page.is_empty() { "index" } else { page })
+        } else if page.is_empty() {
+            ".next/static/chunks/app/page-*.js".to_string()
+        } else {
+            format!(".next/static/chunks/app/{}/page-*.js", page)
+        };
+        entries.push(json!({
+            "name": format!("route {}", route_name(page)),
+            "path": chunks,
+            "limit": format!("{} kB", budget_kb)
+        }));
+    }
+
+    serde_json::Value::Array(entries)
+}
+
+pub async fn add_perf() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let add_size_limit = prompt::confirm("size_limit", "Add size-limit with per-route bundle budgets?", true)?;
+
+    let budget_kb: String = if add_size_limit {
+        prompt::input_validated("budget_kb", "Budget per route (kB, gzipped)", "50", |input| {
+            input.parse::<u32>().map(|_| ()).map_err(|_| "Enter a whole number")
+        })?
+    } else {
+        String::new()
+    };
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing @next/bundle-analyzer...");
+
+    let mut dev_dependencies = vec!["@next/bundle-analyzer", "cross-env"];
+    if add_size_limit {
+        dev_dependencies.extend(["size-limit", "@size-limit/file"]);
+    }
+
+    package_manager.install(&dev_dependencies, true)?;
+
+    pb.set_message("Wiring the bundle analyzer into next.config...");
+
+    next_config::add_import(
+        "bundleAnalyzer",
+        "@next/bundle-analyzer",
+        Some("const withBundleAnalyzer = bundleAnalyzer({ enabled: process.env.ANALYZE === \"true\" });"),
+    )?;
+    next_config::wrap_export("withBundleAnalyzer")?;
+
+    let optimized_packages: Vec<&str> = BARREL_PACKAGES
+        .into_iter()
+        .filter(|package| package_json::has_dependency(package))
+        .collect();
+
+    let optimize_added = if optimized_packages.is_empty() {
+        false
+    } else {
+        let items: Vec<String> = optimized_packages
+            .iter()
+            .map(|package| format!("\"{}\"", package))
+            .collect();
+        let items: Vec<&str> = items.iter().map(String::as_str).collect();
+        next_config::add_to_array_option(&["experimental", "optimizePackageImports"], &items)?
+    };
+
+    pb.set_message("Updating package.json scripts...");
+
+    // The analyzer hooks into webpack, so the analyze build must not run on Turbopack
+    let analyze = format!("cross-env ANALYZE=true next build{}", Bundler::force_webpack_args());
+    let mut scripts = vec![("analyze", analyze.as_str())];
+    if add_size_limit {
+        scripts.push(("size", "size-limit"));
+    }
+    package_json::add_scripts(&scripts)?;
+
+    let mut route_count = 0;
+    if add_size_limit {
+        pb.set_message("Creating size-limit budgets...");
+
+        let mut pages = Vec::new();
+        let extensions = project_structure.page_extensions();
+        if project_structure.is_app_router() {
+            discover_pages(&project_structure.get_app_path(), "", &extensions, &mut pages);
+        } else {
+            discover_pages_router(&project_structure.get_pages_path(), "", &extensions, &mut pages);
+        }
+        pages.sort();
+        route_count = pages.len();
+
+        let config = serde_json::to_string_pretty(&size_limit_config(&pages, &budget_kb, project_structure.is_app_router()))?;
+        file_writer::write(project_file(".size-limit.json"), config + "\n")
+            .context("Failed to create .size-limit.json")?;
+    }
+
+    pb.finish_with_message("Performance tooling set up!");
+
+    bundler::warn_webpack_only(
+        "@next/bundle-analyzer",
+        "the analyze script builds with webpack, so its sizes can differ from your Turbopack build",
+    );
+    if add_size_limit && Bundler::build() == Bundler::Turbopack {
+        println!(
+            "{}",
+            style(format!(
+                "size-limit budgets match webpack chunk paths; run `next build{}` before checking them",
+                Bundler::force_webpack_args()
+            ))
+            .yellow()
+        );
+    }
+
+    println!("\n{}", style("Changes:").cyan().bold());
+    println!("• next.config - Wrapped with withBundleAnalyzer (enabled with ANALYZE=true)");
+    if optimize_added {
+        println!("• next.config - optimizePackageImports for {}", optimized_packages.join(", "));
+    } else if !optimized_packages.is_empty() {
+        println!(
+            "{}",
+            style(format!(
+                "• next.config already has an experimental section; add optimizePackageImports: [{}] manually",
+                optimized_packages.join(", ")
+            ))
+            .yellow()
+        );
+    }
+    if add_size_limit {
+        println!("• .size-limit.json - {} kB budget for {} route(s) plus shared chunks", budget_kb, route_count);
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Inspect bundles: {}", package_manager.run_script("analyze"));
+    if add_size_limit {
+        println!("2. Check budgets after a build: {}", package_manager.run_script("size"));
+        println!("3. Re-run `nstack add --feature perf` after adding routes to refresh budgets");
+    }
+
+    Ok(())
+}
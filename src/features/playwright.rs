@@ -0,0 +1,258 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::env_file;
+use crate::gitignore;
+use crate::manifest::{self, ProjectManifest};
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::project_file;
+
+fn playwright_config(package_manager: &PackageManager, with_auth: bool) -> String {
+    let projects = if with_auth {
+        r#"  projects: [
+    // Signs in once and saves the session for the authenticated tests
+    { name: "setup", testMatch: /.*\.setup\.ts/ },
+    {
+      name: "chromium",
+      use: { ...devices["Desktop Chrome"] },
+      dependencies: ["setup"],
+    },
+  ],"#
+    } else {
+        r#"  projects: [
+    {
+      name: "chromium",
+      use: { ...devices["Desktop Chrome"] },
+    },
+  ],"#
+    };
+
+    format!(
+        r#"import {{ defineConfig, devices }} from "@playwright/test";
+import {{ loadEnvConfig }} from "@next/env";
+
+// Load .env files the same way Next.js does
+loadEnvConfig(process.cwd());
+
+const PORT = Number(process.env.PORT ?? 3000);
+const baseURL = process.env.PLAYWRIGHT_BASE_URL ?? `http://localhost:${{PORT}}`;
+
+export default defineConfig({{
+  testDir: "./e2e",
+  fullyParallel: true,
+  forbidOnly: !!process.env.CI,
+  retries: process.env.CI ? 2 : 0,
+  workers: process.env.CI ? 1 : undefined,
+  reporter: process.env.CI
+    ? [["github"], ["html", {{ open: "never" }}], ["junit", {{ outputFile: "test-results/junit.xml" }}]]
+    : [["list"], ["html", {{ open: "on-failure" }}]],
+  use: {{
+    baseURL,
+    trace: "on-first-retry",
+    screenshot: "only-on-failure",
+  }},
+{}
+  webServer: {{
+    // CI tests the production build; locally the dev server is reused if already running
+    command: process.env.CI ? "{} && {}" : "{}",
+    url: baseURL,
+    reuseExistingServer: !process.env.CI,
+    timeout: 120_000,
+  }},
+}});
+"#,
+        projects,
+        package_manager.run_script("build"),
+        package_manager.run_script("start"),
+        package_manager.run_script("dev"),
+    )
+}
+
+fn example_spec(with_users_api: bool) -> String {
+    let mut content = String::from(
+        r#"import { expect, test } from "@playwright/test";
+
+test.describe("homepage", () => {
+  test("loads without errors", async ({ page }) => {
+    const errors: string[] = [];
+    page.on("pageerror", (error) => errors.push(error.message));
+
+    const response = await page.goto("/");
+
+    expect(response?.ok()).toBeTruthy();
+    await expect(page).toHaveTitle(/.+/);
+    expect(errors).toEqual([]);
+  });
+});
+"#,
+    );
+
+    if with_users_api {
+        content.push_str(
+            r#"
+test.describe("users API", () => {
+  test("lists users", async ({ request }) => {
+    const response = await request.get("/api/users");
+
+    expect(response.ok()).toBeTruthy();
+    expect(Array.isArray(await response.json())).toBe(true);
+  });
+});
+"#,
+        );
+    }
+
+    content
+}
+
+fn auth_setup(sign_in_path: &str) -> String {
+    format!(
+        r#"import {{ expect, test as setup }} from "@playwright/test";
+import {{ AUTH_FILE }} from "./fixtures";
+
+setup("authenticate", async ({{ page }}) => {{
+  const email = process.env.E2E_USER_EMAIL;
+  const password = process.env.E2E_USER_PASSWORD;
+  if (!email || !password) {{
+    throw new Error("Set E2E_USER_EMAIL and E2E_USER_PASSWORD to run authenticated tests");
+  }}
+
+  await page.goto("{}");
+  await page.getByLabel(/email/i).fill(email);
+  await page.getByLabel(/password/i).fill(password);
+  await page.getByRole("button", {{ name: /sign in|log in|continue/i }}).click();
+
+  // Adjust to a page only signed-in users can reach
+  await expect(page).not.toHaveURL(/sign-?in|login/);
+
+  await page.context().storageState({{ path: AUTH_FILE }});
+}});
+"#,
+        sign_in_path
+    )
+}
+
+const AUTH_FIXTURES: &str = r#"import { test as base, type Page } from "@playwright/test";
+
+export const AUTH_FILE = "playwright/.auth/user.json";
+
+type Fixtures = {
+  authenticatedPage: Page;
+};
+
+/**
+ * `authenticatedPage` reuses the session saved by auth.setup.ts, so tests start signed in.
+ */
+export const test = base.extend<Fixtures>({
+  authenticatedPage: async ({ browser }, use) => {
+    const context = await browser.newContext({ storageState: AUTH_FILE });
+    const page = await context.newPage();
+    await use(page);
+    await context.close();
+  },
+});
+
+export { expect } from "@playwright/test";
+"#;
+
+const AUTHENTICATED_SPEC: &str = r#"import { expect, test } from "./fixtures";
+
+test("signed-in users keep their session", async ({ authenticatedPage }) => {
+  await authenticatedPage.goto("/");
+  const cookies = await authenticatedPage.context().cookies();
+
+  expect(cookies.length).toBeGreaterThan(0);
+});
+"#;
+
+pub async fn add_playwright() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let auth_library = manifest::installed_auth_library();
+    let with_users_api = ProjectManifest::is_feature_installed("drizzle");
+
+    if let Some((library, _)) = auth_library {
+        println!("{}", style(format!("Detected {}, adding authenticated fixtures", library)).yellow());
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Playwright...");
+
+    package_manager.install(&["@playwright/test"], true)?;
+
+    pb.set_message("Creating playwright.config.ts...");
+
+    file_writer::write(project_file("playwright.config.ts"), playwright_config(&package_manager, auth_library.is_some()))
+        .context("Failed to create playwright.config.ts")?;
+
+    pb.set_message("Creating example specs...");
+
+    change_set::create_dir_all(project_file("e2e")).context("Failed to create e2e directory")?;
+    file_writer::write(project_file("e2e/home.spec.ts"), example_spec(with_users_api))
+        .context("Failed to create home.spec.ts")?;
+
+    if let Some((_, sign_in_path)) = auth_library {
+        file_writer::write(project_file("e2e/fixtures.ts"), AUTH_FIXTURES)
+            .context("Failed to create fixtures.ts")?;
+        file_writer::write(project_file("e2e/auth.setup.ts"), auth_setup(sign_in_path))
+            .context("Failed to create auth.setup.ts")?;
+        file_writer::write(project_file("e2e/authenticated.spec.ts"), AUTHENTICATED_SPEC)
+            .context("Failed to create authenticated.spec.ts")?;
+
+        env_file::add_env_vars(
+            "playwright",
+            "Playwright test user",
+            &[("E2E_USER_EMAIL", "test@example.com"), ("E2E_USER_PASSWORD", "")],
+        )?;
+    }
+
+    pb.set_message("Updating package.json scripts...");
+
+    package_json::add_scripts(&[
+        ("test:e2e", "playwright test"),
+        ("test:e2e:ui", "playwright test --ui"),
+    ])?;
+
+    gitignore::add_entries(
+        "Playwright",
+        &["/test-results/", "/playwright-report/", "/blob-report/", "/playwright/.auth/"],
+    )?;
+
+    pb.finish_with_message("Playwright set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• playwright.config.ts - Config with webServer and CI reporters");
+    println!("• e2e/home.spec.ts - Homepage{} checks", if with_users_api { " and users API" } else { "" });
+    if auth_library.is_some() {
+        println!("• e2e/auth.setup.ts - Signs in once and stores the session");
+        println!("• e2e/fixtures.ts - authenticatedPage fixture");
+        println!("• e2e/authenticated.spec.ts - Example authenticated test");
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!(
+        "1. Install browsers: {}",
+        package_manager.exec_command("playwright install --with-deps chromium")
+    );
+    let mut step = 2;
+    if auth_library.is_some() {
+        println!("{}. Set E2E_USER_EMAIL and E2E_USER_PASSWORD in .env for a test account", step);
+        step += 1;
+    }
+    println!("{}. Run the tests: {}", step, package_manager.run_script("test:e2e"));
+    println!("{}. Debug interactively: {}", step + 1, package_manager.run_script("test:e2e:ui"));
+
+    Ok(())
+}
@@ -0,0 +1,377 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::docker_compose;
+use crate::env_file;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, slash_path, ProjectStructure};
+use crate::root_layout;
+
+const MIGRATIONS_DIR: &str = "pocketbase/pb_migrations";
+
+const COMPOSE_SERVICE: &str = r#"  pocketbase:
+    image: ghcr.io/muchobien/pocketbase:latest
+    ports:
+      - "8090:8090"
+    volumes:
+      - pocketbase-data:/pb_data
+      - ./pocketbase/pb_migrations:/pb_migrations
+    restart: unless-stopped
+"#;
+
+const POSTS_MIGRATION: &str = r#"/// <reference path="../pb_data/types.d.ts" />
+
+// Posts readable by anyone; signed-in users create posts and manage their own
+migrate(
+  (app) => {
+    const collection = new Collection({
+      type: "base",
+      name: "posts",
+      listRule: "",
+      viewRule: "",
+      createRule: "@request.auth.id != '' && author = @request.auth.id",
+      updateRule: "author = @request.auth.id",
+      deleteRule: "author = @request.auth.id",
+      fields: [
+        { name: "title", type: "text", required: true, max: 200 },
+        { name: "body", type: "text" },
+        { name: "author", type: "relation", required: true, collectionId: "_pb_users_auth_", maxSelect: 1, cascadeDelete: true },
+        { name: "created", type: "autodate", onCreate: true },
+        { name: "updated", type: "autodate", onCreate: true, onUpdate: true },
+      ],
+    });
+
+    return app.save(collection);
+  },
+  (app) => {
+    return app.delete(app.findCollectionByNameOrId("posts"));
+  },
+);
+"#;
+
+const TYPES: &str = r#"// Matches the starter migration. Regenerate from the running instance with the pb:types script.
+import type PocketBase from "pocketbase";
+import type { RecordService } from "pocketbase";
+
+export type BaseSystemFields = {
+  id: string;
+  collectionId: string;
+  collectionName: string;
+  created: string;
+  updated: string;
+};
+
+export type UsersRecord = {
+  email: string;
+  name?: string;
+  avatar?: string;
+  verified: boolean;
+};
+
+export type PostsRecord = {
+  title: string;
+  body?: string;
+  author: string;
+};
+
+export type UsersResponse = BaseSystemFields & UsersRecord;
+export type PostsResponse = BaseSystemFields & PostsRecord;
+
+export type CollectionRecords = {
+  users: UsersRecord;
+  posts: PostsRecord;
+};
+
+export type CollectionResponses = {
+  users: UsersResponse;
+  posts: PostsResponse;
+};
+
+export type TypedPocketBase = PocketBase & {
+  collection(idOrName: "users"): RecordService<UsersResponse>;
+  collection(idOrName: "posts"): RecordService<PostsResponse>;
+};
+"#;
+
+const BROWSER_CLIENT: &str = r#"import PocketBase from "pocketbase";
+import type { TypedPocketBase } from "./types";
+
+let browserClient: TypedPocketBase | undefined;
+
+/** One client per tab; auth changes are mirrored to the pb_auth cookie for SSR */
+export function createBrowserClient() {
+  if (!browserClient) {
+    const client = new PocketBase(process.env.NEXT_PUBLIC_POCKETBASE_URL) as TypedPocketBase;
+    client.authStore.loadFromCookie(document.cookie);
+    client.authStore.onChange(() => {
+      document.cookie = client.authStore.exportToCookie({ httpOnly: false, secure: window.location.protocol === "https:" });
+    });
+    browserClient = client;
+  }
+  return browserClient;
+}
+"#;
+
+const SERVER_CLIENT: &str = r#"import "server-only";
+import { cookies } from "next/headers";
+import PocketBase from "pocketbase";
+import type { TypedPocketBase } from "./types";
+
+/** A fresh client per request, authenticated from the pb_auth cookie set by the browser client */
+export async function createServerClient() {
+  const pb = new PocketBase(process.env.NEXT_PUBLIC_POCKETBASE_URL) as TypedPocketBase;
+  const cookieStore = await cookies();
+  pb.authStore.loadFromCookie(cookieStore.toString());
+  return pb;
+}
+"#;
+
+const HOOKS: &str = r#""use client";
+
+import type { RecordListOptions } from "pocketbase";
+import { useCallback, useEffect, useState } from "react";
+import { createBrowserClient } from "./client";
+import type { CollectionRecords, CollectionResponses } from "./types";
+
+type CollectionName = keyof CollectionResponses;
+
+/** Loads a collection and keeps it in sync through realtime subscriptions */
+export function useCollection<K extends CollectionName>(name: K, options?: RecordListOptions) {
+  const [records, setRecords] = useState<CollectionResponses[K][]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<Error | null>(null);
+  const optionsKey = JSON.stringify(options ?? {});
+
+  useEffect(() => {
+    const collection = createBrowserClient().collection<CollectionResponses[K]>(name);
+    const listOptions: RecordListOptions = { sort: "-created", ...JSON.parse(optionsKey) };
+    let cancelled = false;
+
+    async function load() {
+      try {
+        const list = await collection.getFullList(listOptions);
+        if (!cancelled) {
+          setRecords(list);
+          setError(null);
+        }
+      } catch (err) {
+        if (!cancelled) setError(err instanceof Error ? err : new Error(String(err)));
+      } finally {
+        if (!cancelled) setLoading(false);
+      }
+    }
+
+    void load();
+    const unsubscribe = collection.subscribe("*", () => void load());
+
+    return () => {
+      cancelled = true;
+      void unsubscribe.then((stop) => stop());
+    };
+  }, [name, optionsKey]);
+
+  return { records, loading, error };
+}
+
+export function useCreateRecord<K extends CollectionName>(name: K) {
+  const [pending, setPending] = useState(false);
+
+  const create = useCallback(
+    async (data: Partial<CollectionRecords[K]>) => {
+      setPending(true);
+      try {
+        return await createBrowserClient().collection<CollectionResponses[K]>(name).create(data);
+      } finally {
+        setPending(false);
+      }
+    },
+    [name],
+  );
+
+  return { create, pending };
+}
+
+export function useDeleteRecord<K extends CollectionName>(name: K) {
+  return useCallback((id: string) => createBrowserClient().collection(name).delete(id), [name]);
+}
+"#;
+
+const POSTS_PAGE: &str = r#""use client";
+
+import { type FormEvent, useEffect, useState } from "react";
+import { createBrowserClient } from "@/lib/pocketbase/client";
+import { useCollection, useCreateRecord, useDeleteRecord } from "@/lib/pocketbase/hooks";
+
+export default function PostsPage() {
+  const { records: posts, loading, error } = useCollection("posts");
+  const { create, pending } = useCreateRecord("posts");
+  const deletePost = useDeleteRecord("posts");
+  const [userId, setUserId] = useState<string | null>(null);
+
+  useEffect(() => {
+    const pb = createBrowserClient();
+    setUserId(pb.authStore.record?.id ?? null);
+    return pb.authStore.onChange(() => setUserId(pb.authStore.record?.id ?? null));
+  }, []);
+
+  async function handleSignIn(event: FormEvent<HTMLFormElement>) {
+    event.preventDefault();
+    const form = new FormData(event.currentTarget);
+    await createBrowserClient()
+      .collection("users")
+      .authWithPassword(String(form.get("email")), String(form.get("password")));
+  }
+
+  async function handleCreate(event: FormEvent<HTMLFormElement>) {
+    event.preventDefault();
+    if (!userId) return;
+    const formElement = event.currentTarget;
+    const form = new FormData(formElement);
+    await create({ title: String(form.get("title")), body: String(form.get("body")), author: userId });
+    formElement.reset();
+  }
+
+  return (
+    <main className="mx-auto flex max-w-xl flex-col gap-6 px-4 py-10">
+      <h1 className="text-2xl font-semibold">Posts</h1>
+
+      {userId ? (
+        <form onSubmit={handleCreate} className="flex flex-col gap-2">
+          <input name="title" required placeholder="Title" className="rounded-md border px-3 py-2 text-sm" />
+          <textarea name="body" placeholder="Write something..." className="rounded-md border px-3 py-2 text-sm" />
+          <div className="flex justify-between">
+            <button type="button" onClick={() => createBrowserClient().authStore.clear()} className="text-sm text-muted-foreground">
+              Sign out
+            </button>
+            <button type="submit" disabled={pending} className="rounded-md bg-primary px-3 py-2 text-sm text-primary-foreground">
+              Publish
+            </button>
+          </div>
+        </form>
+      ) : (
+        <form onSubmit={handleSignIn} className="flex flex-col gap-2">
+          <input name="email" type="email" required placeholder="Email" className="rounded-md border px-3 py-2 text-sm" />
+          <input name="password" type="password" required placeholder="Password" className="rounded-md border px-3 py-2 text-sm" />
+          <button type="submit" className="self-end rounded-md border px-3 py-2 text-sm">
+            Sign in to post
+          </button>
+        </form>
+      )}
+
+      {error ? <p className="text-sm text-red-600">{error.message}</p> : null}
+      {loading ? <p className="text-sm text-muted-foreground">Loading...</p> : null}
+
+      <ul className="flex flex-col gap-3">
+        {posts.map((post) => (
+          <li key={post.id} className="flex items-start justify-between gap-4 rounded-md border p-4">
+            <div>
+              <h2 className="font-medium">{post.title}</h2>
+              {post.body ? <p className="text-sm text-muted-foreground">{post.body}</p> : null}
+            </div>
+            {post.author === userId ? (
+              <button type="button" onClick={() => void deletePost(post.id)} className="text-sm text-red-600">
+                Delete
+              </button>
+            ) : null}
+          </li>
+        ))}
+      </ul>
+    </main>
+  );
+}
+"#;
+
+pub async fn add_pocketbase() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing PocketBase SDK...");
+
+    package_manager.install(&["pocketbase", "server-only"], false)?;
+    package_manager.install(&["pocketbase-typegen"], true)?;
+
+    pb.set_message("Creating PocketBase clients and hooks...");
+
+    let pocketbase_lib_path = project_structure.get_lib_path().join("pocketbase");
+    change_set::create_dir_all(&pocketbase_lib_path).context("Failed to create lib/pocketbase directory")?;
+    let types_path = pocketbase_lib_path.join("types.ts");
+    if !types_path.exists() {
+        file_writer::write(&types_path, TYPES).context("Failed to create types.ts")?;
+    }
+    for (file, content) in [
+        ("client.ts", BROWSER_CLIENT),
+        ("server.ts", SERVER_CLIENT),
+        ("hooks.ts", HOOKS),
+    ] {
+        file_writer::write(pocketbase_lib_path.join(file), content)
+            .context(format!("Failed to create {}", file))?;
+    }
+
+    pb.set_message("Creating the posts page...");
+
+    let page_path = root_layout::routes_dir(&project_structure).join("posts");
+    change_set::create_dir_all(&page_path).context("Failed to create posts route directory")?;
+    file_writer::write(page_path.join("page.tsx"), POSTS_PAGE)
+        .context("Failed to create page.tsx")?;
+
+    pb.set_message("Configuring local PocketBase...");
+
+    let migrations_dir = project_file(MIGRATIONS_DIR);
+    change_set::create_dir_all(&migrations_dir).context("Failed to create pb_migrations directory")?;
+    let migration_path = migrations_dir.join("1700000000_created_posts.js");
+    if !migration_path.exists() {
+        file_writer::write(&migration_path, POSTS_MIGRATION)
+            .context("Failed to create the posts migration")?;
+    }
+    let compose_updated = docker_compose::add_service("pocketbase", COMPOSE_SERVICE, Some("pocketbase-data"))?;
+
+    let types_script = format!("pocketbase-typegen --env --out {}", project_structure.relative_path(&types_path));
+    package_json::add_scripts(&[
+        ("pb:up", "docker compose up -d pocketbase"),
+        ("pb:types", &types_script),
+    ])?;
+
+    env_file::add_env_vars(
+        "pocketbase",
+        "PocketBase",
+        &[
+            ("NEXT_PUBLIC_POCKETBASE_URL", "http://127.0.0.1:8090"),
+            ("PB_TYPEGEN_URL", "http://127.0.0.1:8090"),
+            ("PB_TYPEGEN_EMAIL", "admin@example.com"),
+            ("PB_TYPEGEN_PASSWORD", "your-superuser-password"),
+        ],
+    )?;
+
+    pb.finish_with_message("PocketBase set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/client.ts - Browser client with the auth store mirrored to a cookie", pocketbase_lib_path.display());
+    println!("• {}/server.ts - Per-request server client authenticated from the cookie", pocketbase_lib_path.display());
+    println!("• {}/hooks.ts - useCollection (realtime), useCreateRecord and useDeleteRecord", pocketbase_lib_path.display());
+    println!("• {} - Collection types", slash_path(&types_path));
+    println!("• {}/page.tsx - Example list and create UI", page_path.display());
+    println!("• {} - Posts collection migration", migration_path.display());
+    if compose_updated {
+        println!("• docker-compose.yml - PocketBase service on port 8090");
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Start PocketBase: {}", package_manager.run_script("pb:up"));
+    println!("2. Create a superuser at http://127.0.0.1:8090/_/ and set PB_TYPEGEN_EMAIL/PB_TYPEGEN_PASSWORD in .env");
+    println!("3. Regenerate types after schema changes: {}", package_manager.run_script("pb:types"));
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::manifest::ProjectManifest;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::root_layout;
+
+const STORE: &str = r#"import { configureStore } from "@reduxjs/toolkit";
+import { setupListeners } from "@reduxjs/toolkit/query";
+import { counterSlice } from "./features/counter-slice";
+import { api } from "./services/api";
+
+/**
+ * Creates a new store. The app router renders per request, so the store must not be a
+ * module-level singleton or state would leak between users.
+ */
+export function makeStore() {
+  const store = configureStore({
+    reducer: {
+      [counterSlice.reducerPath]: counterSlice.reducer,
+      [api.reducerPath]: api.reducer,
+    },
+    middleware: (getDefaultMiddleware) => getDefaultMiddleware().concat(api.middleware),
+  });
+
+  // Enables refetchOnFocus / refetchOnReconnect for RTK Query
+  setupListeners(store.dispatch);
+  return store;
+}
+
+export type AppStore = ReturnType<typeof makeStore>;
+export type RootState = ReturnType<AppStore["getState"]>;
+export type AppDispatch = AppStore["dispatch"];
+"#;
+
+const HOOKS: &str = r#"import { useDispatch, useSelector, useStore } from "react-redux";
+import type { AppDispatch, AppStore, RootState } from "./store";
+
+// Use these throughout the app instead of the untyped react-redux hooks
+export const useAppDispatch = useDispatch.withTypes<AppDispatch>();
+export const useAppSelector = useSelector.withTypes<RootState>();
+export const useAppStore = useStore.withTypes<AppStore>();
+"#;
+
+const COUNTER_SLICE: &str = r#"import { createSlice, type PayloadAction } from "@reduxjs/toolkit";
+
+export type CounterState = {
+  value: number;
+};
+
+const initialState: CounterState = {
+  value: 0,
+};
+
+export const counterSlice = createSlice({
+  name: "counter",
+  initialState,
+  reducers: {
+    increment: (state) => {
+      state.value += 1;
+    },
+    decrement: (state) => {
+      state.value -= 1;
+    },
+    incrementByAmount: (state, action: PayloadAction<number>) => {
+      state.value += action.payload;
+    },
+  },
+  selectors: {
+    selectCount: (counter) => counter.value,
+  },
+});
+
+export const { increment, decrement, incrementByAmount } = counterSlice.actions;
+export const { selectCount } = counterSlice.selectors;
+"#;
+
+fn api_service(with_users_api: bool) -> String {
+    let note = if with_users_api {
+        "// Talks to the users route generated by the drizzle feature"
+    } else {
+        "// Example service: point the endpoints at your own API routes"
+    };
+
+    format!(
+        r#"import {{ createApi, fetchBaseQuery }} from "@reduxjs/toolkit/query/react";
+
+export type User = {{
+  id: number;
+  name: string;
+  email: string;
+}};
+
+{}
+export const api = createApi({{
+  reducerPath: "api",
+  baseQuery: fetchBaseQuery({{ baseUrl: "/api" }}),
+  tagTypes: ["User"],
+  endpoints: (build) => ({{
+    getUsers: build.query<User[], void>({{
+      query: () => "users",
+      providesTags: ["User"],
+    }}),
+    createUser: build.mutation<User, Omit<User, "id">>({{
+      query: (body) => ({{ url: "users", method: "POST", body }}),
+      invalidatesTags: ["User"],
+    }}),
+  }}),
+}});
+
+export const {{ useGetUsersQuery, useCreateUserMutation }} = api;
+"#,
+        note
+    )
+}
+
+const STORE_PROVIDER: &str = r#""use client";
+
+import { type ReactNode, useRef } from "react";
+import { Provider } from "react-redux";
+import { type AppStore, makeStore } from "@/lib/store/store";
+
+export function StoreProvider({ children }: { children: ReactNode }) {
+  const storeRef = useRef<AppStore | null>(null);
+  if (storeRef.current === null) {
+    // Created once per request on the server and once in the browser
+    storeRef.current = makeStore();
+  }
+
+  return <Provider store={storeRef.current}>{children}</Provider>;
+}
+"#;
+
+pub async fn add_redux() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let with_users_api = ProjectManifest::is_feature_installed("drizzle");
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Redux Toolkit...");
+
+    package_manager.install(&["@reduxjs/toolkit", "react-redux"], false)?;
+
+    pb.set_message("Creating store, slice and API service...");
+
+    let store_path = project_structure.get_lib_path().join("store");
+    for dir in ["features", "services"] {
+        change_set::create_dir_all(store_path.join(dir))
+            .context("Failed to create store directory")?;
+    }
+    file_writer::write(store_path.join("store.ts"), STORE)
+        .context("Failed to create store.ts")?;
+    file_writer::write(store_path.join("hooks.ts"), HOOKS)
+        .context("Failed to create hooks.ts")?;
+    file_writer::write(store_path.join("features/counter-slice.ts"), COUNTER_SLICE)
+        .context("Failed to create counter-slice.ts")?;
+    file_writer::write(store_path.join("services/api.ts"), api_service(with_users_api))
+        .context("Failed to create api.ts")?;
+
+    let providers_path = project_structure.get_components_path().join("providers");
+    change_set::create_dir_all(&providers_path).context("Failed to create providers directory")?;
+    file_writer::write(providers_path.join("store-provider.tsx"), STORE_PROVIDER)
+        .context("Failed to create store-provider.tsx")?;
+
+    pb.set_message("Adding StoreProvider to the root layout...");
+
+    root_layout::add_import(
+        &project_structure,
+        "import { StoreProvider } from \"@/components/providers/store-provider\";",
+    )?;
+    root_layout::wrap_children(&project_structure, "<StoreProvider>", "</StoreProvider>")?;
+
+    pb.finish_with_message("Redux Toolkit set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/store.ts - Per-request store factory", store_path.display());
+    println!("• {}/hooks.ts - Typed useAppDispatch / useAppSelector hooks", store_path.display());
+    println!("• {}/features/counter-slice.ts - Example slice", store_path.display());
+    println!("• {}/services/api.ts - RTK Query service for /api/users", store_path.display());
+    println!("• {}/store-provider.tsx - Client provider, added to the root layout", providers_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Read state in client components with useAppSelector(selectCount)");
+    if with_users_api {
+        println!("2. Fetch users with useGetUsersQuery()");
+    } else {
+        println!("2. Point services/api.ts at your API routes, or add one with `nstack add --feature drizzle`");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,369 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use crate::change_set;
+use crate::env_file;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::root_layout;
+
+/// File every feature package must have at its root.
+const MANIFEST_FILE: &str = "nstack-feature.json";
+
+/// Placeholders a template destination may start with.
+const PLACEHOLDERS: [&str; 5] = ["app", "routes", "components", "lib", "db"];
+
+/// A feature package is declarative: it lists packages, template files, scripts and env
+/// vars, and nstack applies them. It never runs code from the package.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct FeatureManifest {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    dependencies: Vec<String>,
+    #[serde(default)]
+    dev_dependencies: Vec<String>,
+    #[serde(default)]
+    files: Vec<TemplateFile>,
+    #[serde(default)]
+    scripts: BTreeMap<String, String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    next_steps: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateFile {
+    /// Path of the template inside the package
+    template: String,
+    /// Destination in the project; may start with `{app}`, `{routes}`, `{components}`,
+    /// `{lib}` or `{db}`
+    path: String,
+}
+
+/// Whether `--feature` names a feature package rather than a built-in feature.
+pub fn is_remote_spec(spec: &str) -> bool {
+    spec.starts_with("github:")
+        || spec.starts_with("gitlab:")
+        || spec.starts_with("https://")
+        || spec.starts_with("git@")
+        || spec.starts_with("file://")
+}
+
+/// Turns `github:owner/repo#ref` into a clone URL and optional ref.
+fn resolve_spec(spec: &str) -> Result<(String, Option<String>)> {
+    let (source, git_ref) = match spec.split_once('#') {
+        Some((source, git_ref)) => (source, Some(git_ref.to_string())),
+        None => (spec, None),
+    };
+
+    let url = if let Some(repo) = source.strip_prefix("github:") {
+        format!("https://github.com/{}.git", repo)
+    } else if let Some(repo) = source.strip_prefix("gitlab:") {
+        format!("https://gitlab.com/{}.git", repo)
+    } else {
+        source.to_string()
+    };
+
+    if url.ends_with("/.git") || url.contains("//.git") {
+        anyhow::bail!("Invalid feature source '{}'. Expected github:owner/repo", spec);
+    }
+
+    Ok((url, git_ref))
+}
+
+fn clone_package(url: &str, git_ref: Option<&str>, destination: &Path) -> Result<()> {
+    let mut command = Command::new("git");
+    command.args(["clone", "--depth", "1", "--quiet"]);
+    if let Some(git_ref) = git_ref {
+        command.args(["--branch", git_ref]);
+    }
+
+    let status = command
+        .arg(url)
+        .arg(destination)
+        .status()
+        .context("Failed to run git. Installing features from a git URL requires git")?;
+
+    if !status.success() {
+        anyhow::bail!("Failed to clone {}", url);
+    }
+    Ok(())
+}
+
+/// Rejects absolute paths and `..` so a package can only read its own files and only
+/// write inside the project.
+fn is_contained(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Resolves a file of the package, rejecting symlinks and anything that ends up outside
+/// `package_dir`, so a package cannot copy files from elsewhere on the machine.
+fn package_file(package_dir: &Path, path: &str) -> Result<PathBuf> {
+    let file = package_dir.join(path);
+    let metadata = std::fs::symlink_metadata(&file).context(format!("'{}' does not exist in the feature package", path))?;
+    if metadata.file_type().is_symlink() {
+        anyhow::bail!("'{}' is a symlink, which feature packages may not contain", path);
+    }
+    if !metadata.is_file() {
+        anyhow::bail!("'{}' is not a file", path);
+    }
+
+    let root = package_dir.canonicalize().context("Failed to resolve the feature package directory")?;
+    let resolved = file.canonicalize().context(format!("Failed to resolve '{}'", path))?;
+    if !resolved.starts_with(&root) {
+        anyhow::bail!("'{}' resolves outside the feature package", path);
+    }
+    Ok(resolved)
+}
+
+fn validate(manifest: &FeatureManifest, package_dir: &Path) -> Result<()> {
+    let valid_name = !manifest.name.is_empty()
+        && manifest.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_name {
+        anyhow::bail!("Invalid feature name '{}': use lowercase letters, digits and dashes", manifest.name);
+    }
+
+    for file in &manifest.files {
+        if !is_contained(&file.template) {
+            anyhow::bail!("Template path '{}' must stay inside the feature package", file.template);
+        }
+        package_file(package_dir, &file.template).context(format!("Invalid template listed in {}", MANIFEST_FILE))?;
+
+        let (placeholder, destination) = split_placeholder(&file.path)?;
+        if let Some(placeholder) = placeholder
+            && !PLACEHOLDERS.contains(&placeholder)
+        {
+            anyhow::bail!("Unknown placeholder '{{{}}}' in '{}'", placeholder, file.path);
+        }
+        if !is_contained(destination) {
+            anyhow::bail!("Destination '{}' must be a relative path inside the project", file.path);
+        }
+    }
+
+    let invalid_env = manifest.env.keys().find(|name| {
+        name.is_empty() || !name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    });
+    if let Some(name) = invalid_env {
+        anyhow::bail!("Invalid environment variable name '{}'", name);
+    }
+
+    Ok(())
+}
+
+/// Splits a destination into its leading `{placeholder}`, if any, and the path after it.
+/// A `}` further into the path is just part of the path.
+fn split_placeholder(path: &str) -> Result<(Option<&str>, &str)> {
+    let Some(rest) = path.strip_prefix('{') else {
+        return Ok((None, path));
+    };
+    let Some((placeholder, rest)) = rest.split_once('}') else {
+        anyhow::bail!("Unclosed placeholder in '{}'", path);
+    };
+    Ok((Some(placeholder), rest.trim_start_matches('/')))
+}
+
+fn resolve_destination(path: &str, project_structure: &ProjectStructure) -> Result<PathBuf> {
+    let (Some(placeholder), rest) = split_placeholder(path)? else {
+        return Ok(PathBuf::from(path));
+    };
+
+    let base = match placeholder {
+        "app" => project_structure.get_app_path(),
+        "routes" => root_layout::routes_dir(project_structure),
+        "components" => project_structure.get_components_path(),
+        "lib" => project_structure.get_lib_path(),
+        "db" => project_structure.get_db_path(),
+        _ => anyhow::bail!("Unknown placeholder '{{{}}}' in '{}'", placeholder, path),
+    };
+
+    Ok(Path::new(&base).join(rest))
+}
+
+fn apply(manifest: &FeatureManifest, package_dir: &Path, package_manager: &PackageManager) -> Result<()> {
+    let project_structure = ProjectStructure::detect()?;
+
+    let pb = ProgressBar::new_spinner();
+
+    let dependencies: Vec<&str> = manifest.dependencies.iter().map(String::as_str).collect();
+    let dev_dependencies: Vec<&str> = manifest.dev_dependencies.iter().map(String::as_str).collect();
+    if !dependencies.is_empty() {
+        pb.set_message("Installing dependencies...");
+        package_manager.install(&dependencies, false)?;
+    }
+    if !dev_dependencies.is_empty() {
+        pb.set_message("Installing dev dependencies...");
+        package_manager.install(&dev_dependencies, true)?;
+    }
+
+    pb.set_message("Copying templates...");
+
+    let mut created = Vec::new();
+    let mut skipped = Vec::new();
+    for file in &manifest.files {
+        let destination = resolve_destination(&file.path, &project_structure)?;
+        // Never overwrite project files with package templates
+        if destination.exists() {
+            skipped.push(destination);
+            continue;
+        }
+
+        if let Some(parent) = destination.parent() {
+            change_set::create_dir_all(parent).context(format!("Failed to create {}", parent.display()))?;
+        }
+        let template = std::fs::read(package_file(package_dir, &file.template)?)
+            .context(format!("Failed to read template {}", file.template))?;
+        change_set::write(&destination, template)
+            .context(format!("Failed to create {}", destination.display()))?;
+        created.push(destination);
+    }
+
+    if !manifest.scripts.is_empty() {
+        let scripts: Vec<(&str, &str)> = manifest.scripts.iter().map(|(name, command)| (name.as_str(), command.as_str())).collect();
+        package_json::add_scripts(&scripts)?;
+    }
+    if !manifest.env.is_empty() {
+        let vars: Vec<(&str, &str)> = manifest.env.iter().map(|(name, value)| (name.as_str(), value.as_str())).collect();
+        env_file::add_env_vars(&manifest.name, &manifest.name, &vars)?;
+    }
+
+    pb.finish_with_message(format!("{} set up successfully!", manifest.name));
+
+    if !created.is_empty() || !skipped.is_empty() {
+        println!("\n{}", style("Files created:").cyan().bold());
+        for path in &created {
+            println!("• {}", path.display());
+        }
+        for path in &skipped {
+            println!("{}", style(format!("• {} already exists; skipped", path.display())).yellow());
+        }
+    }
+
+    if !manifest.next_steps.is_empty() {
+        println!("\n{}", style("Next steps:").green());
+        for (index, step) in manifest.next_steps.iter().enumerate() {
+            println!("{}. {}", index + 1, step);
+        }
+    }
+
+    Ok(())
+}
+
+/// Downloads a feature package from git, validates its manifest and applies it.
+pub async fn add_remote_feature(spec: &str) -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let (url, git_ref) = resolve_spec(spec)?;
+    // Removed when dropped, including when cloning or applying fails
+    let staging = tempfile::Builder::new()
+        .prefix("nstack-feature-")
+        .tempdir()
+        .context("Failed to create the download directory")?;
+    let package_dir = staging.path();
+
+    println!("{}", style(format!("Fetching {}...", url)).cyan());
+
+    clone_package(&url, git_ref.as_deref(), package_dir)?;
+
+    let manifest_path = package_file(package_dir, MANIFEST_FILE)
+        .context(format!("{} is not an nstack feature", spec))?;
+    let content = std::fs::read_to_string(&manifest_path)
+        .context(format!("Failed to read {}", MANIFEST_FILE))?;
+    let manifest: FeatureManifest = serde_json::from_str(&content)
+        .context(format!("Failed to parse {}", MANIFEST_FILE))?;
+    validate(&manifest, package_dir)?;
+
+    println!(
+        "{}",
+        style(format!("Adding {}{}", manifest.name, if manifest.description.is_empty() { String::new() } else { format!(" - {}", manifest.description) })).green().bold()
+    );
+    apply(&manifest, package_dir, &package_manager)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(destination: &str, template: &str) -> FeatureManifest {
+        let json = serde_json::json!({
+            "name": "example",
+            "files": [{ "template": template, "path": destination }],
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn package() -> tempfile::TempDir {
+        let package = tempfile::tempdir().unwrap();
+        std::fs::create_dir(package.path().join("templates")).unwrap();
+        std::fs::write(package.path().join("templates/route.ts"), "export {};\n").unwrap();
+        package
+    }
+
+    #[test]
+    fn accepts_destinations_inside_the_project() {
+        let package = package();
+        for destination in ["{app}/api/example/route.ts", "{lib}example.ts", "lib/example.ts", "./lib/{example}.ts"] {
+            assert!(validate(&manifest(destination, "templates/route.ts"), package.path()).is_ok(), "{}", destination);
+        }
+    }
+
+    #[test]
+    fn rejects_destinations_outside_the_project() {
+        let package = package();
+        for destination in [
+            "../../etc/}x",
+            "../outside.ts",
+            "/etc/passwd",
+            "{app}/../../outside.ts",
+            "{lib}../outside.ts",
+            "lib/../../outside.ts",
+            "{app}",
+            "",
+        ] {
+            assert!(validate(&manifest(destination, "templates/route.ts"), package.path()).is_err(), "{}", destination);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_and_unclosed_placeholders() {
+        let package = package();
+        assert!(validate(&manifest("{home}/.bashrc", "templates/route.ts"), package.path()).is_err());
+        assert!(validate(&manifest("{app/route.ts", "templates/route.ts"), package.path()).is_err());
+    }
+
+    #[test]
+    fn rejects_templates_outside_the_package() {
+        let package = package();
+        assert!(validate(&manifest("lib/example.ts", "../route.ts"), package.path()).is_err());
+        assert!(validate(&manifest("lib/example.ts", "/etc/passwd"), package.path()).is_err());
+        assert!(validate(&manifest("lib/example.ts", "templates/missing.ts"), package.path()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinked_templates() {
+        let package = package();
+        std::os::unix::fs::symlink("/etc/hostname", package.path().join("templates/link.ts")).unwrap();
+        assert!(validate(&manifest("lib/example.ts", "templates/link.ts"), package.path()).is_err());
+    }
+}
@@ -0,0 +1,819 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::features::drizzle;
+use crate::features::schema_conventions::{self, IdStrategy};
+use crate::file_writer;
+use crate::manifest::{self, ProjectManifest};
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+use crate::root_layout;
+
+/// The `email` column of the users table drizzle generates, which the auth columns follow.
+const STOCK_EMAIL_COLUMN: &str = "  email: varchar(\"email\", { length: 255 }).notNull().unique(),\n";
+
+/// Columns Better Auth needs on the users table besides id, name, email and timestamps.
+const AUTH_USER_COLUMNS: &str = r#"  emailVerified: boolean("email_verified").default(false).notNull(),
+  image: text("image"),
+"#;
+
+const USERS_TABLE: &str = r#"
+// Users table
+export const usersTable = pgTable("users", {
+  id: __ID__,
+  name: varchar("name", { length: 255 }).notNull(),
+  email: varchar("email", { length: 255 }).notNull().unique(),
+  emailVerified: boolean("email_verified").default(false).notNull(),
+  image: text("image"),
+  __TIMESTAMPS__
+});
+
+export type User = typeof usersTable.$inferSelect;
+export type NewUser = typeof usersTable.$inferInsert;
+"#;
+
+/// Tables appended to the drizzle schema, by export name. Their ids and timestamps follow
+/// the schema conventions when the project has them, see `schema_conventions::render_columns`.
+const SCHEMA_TABLES: [(&str, &str); 4] = [
+    ("sessionsTable", r#"
+// Auth sessions (Better Auth)
+export const sessionsTable = pgTable("sessions", {
+  id: __ID__,
+  userId: __FOREIGN_ID__("user_id").notNull().references(() => usersTable.id, { onDelete: "cascade" }),
+  token: text("token").notNull().unique(),
+  expiresAt: timestamp("expires_at").notNull(),
+  ipAddress: text("ip_address"),
+  userAgent: text("user_agent"),
+  __TIMESTAMPS__
+});
+"#),
+    ("accountsTable", r#"
+// Credential and OAuth accounts (Better Auth)
+export const accountsTable = pgTable("accounts", {
+  id: __ID__,
+  userId: __FOREIGN_ID__("user_id").notNull().references(() => usersTable.id, { onDelete: "cascade" }),
+  accountId: text("account_id").notNull(),
+  providerId: text("provider_id").notNull(),
+  accessToken: text("access_token"),
+  refreshToken: text("refresh_token"),
+  idToken: text("id_token"),
+  accessTokenExpiresAt: timestamp("access_token_expires_at"),
+  refreshTokenExpiresAt: timestamp("refresh_token_expires_at"),
+  scope: text("scope"),
+  password: text("password"),
+  __TIMESTAMPS__
+});
+"#),
+    ("verificationsTable", r#"
+// Email verification and password reset tokens (Better Auth)
+export const verificationsTable = pgTable("verifications", {
+  id: __ID__,
+  identifier: text("identifier").notNull(),
+  value: text("value").notNull(),
+  expiresAt: timestamp("expires_at").notNull(),
+  __TIMESTAMPS__
+});
+"#),
+    ("subscriptionsTable", r#"
+// Stripe subscriptions, one per user
+export const subscriptionsTable = pgTable("subscriptions", {
+  id: __ID__,
+  userId: __FOREIGN_ID__("user_id").notNull().unique().references(() => usersTable.id, { onDelete: "cascade" }),
+  stripeCustomerId: text("stripe_customer_id").notNull().unique(),
+  stripeSubscriptionId: text("stripe_subscription_id").unique(),
+  stripePriceId: text("stripe_price_id"),
+  status: varchar("status", { length: 32 }).default("incomplete").notNull(),
+  currentPeriodEnd: timestamp("current_period_end"),
+  cancelAtPeriodEnd: boolean("cancel_at_period_end").default(false).notNull(),
+  __TIMESTAMPS__
+});
+
+export type Subscription = typeof subscriptionsTable.$inferSelect;
+"#),
+];
+
+const PG_CORE_IMPORTS: [&str; 6] = ["pgTable", "integer", "varchar", "text", "timestamp", "boolean"];
+
+const SITE: &str = r#"export const siteConfig = {
+  name: "__APP_NAME__",
+  url: process.env.NEXT_PUBLIC_SITE_URL ?? "http://localhost:3000",
+};
+"#;
+
+const AUTH: &str = r#"import { betterAuth } from "better-auth";
+import { drizzleAdapter } from "better-auth/adapters/drizzle";
+import { nextCookies } from "better-auth/next-js";
+import { headers } from "next/headers";
+import { db } from "@/db";
+import { accountsTable, sessionsTable, usersTable, verificationsTable } from "@/db/schema";
+import { WelcomeEmail } from "@/emails/welcome-email";
+import { sendEmail } from "@/lib/email";
+import { siteConfig } from "@/lib/site";
+
+export const auth = betterAuth({
+  baseURL: siteConfig.url,
+  database: drizzleAdapter(db, {
+    provider: "pg",
+    usePlural: true,
+    schema: {
+      users: usersTable,
+      sessions: sessionsTable,
+      accounts: accountsTable,
+      verifications: verificationsTable,
+    },
+  }),
+__ID_GENERATION__
+  emailAndPassword: { enabled: true },
+  databaseHooks: {
+    user: {
+      create: {
+        after: async (user) => {
+          await sendEmail({
+            to: user.email,
+            subject: `Welcome to ${siteConfig.name}`,
+            react: WelcomeEmail({ name: user.name }),
+          });
+        },
+      },
+    },
+  },
+  plugins: [nextCookies()],
+});
+
+export type CurrentUser = Pick<typeof usersTable.$inferSelect, "id" | "name" | "email">;
+
+/** The signed-in user in a server component, route handler or server action. */
+export async function getCurrentUser(): Promise<CurrentUser | null> {
+  const session = await auth.api.getSession({ headers: await headers() });
+  if (!session) return null;
+  const { id, name, email } = session.user;
+  return { __USER_ID__, name, email };
+}
+"#;
+
+const AUTH_CLIENT: &str = r#"import { createAuthClient } from "better-auth/react";
+
+export const authClient = createAuthClient();
+"#;
+
+const AUTH_ROUTE: &str = r#"import { toNextJsHandler } from "better-auth/next-js";
+import { auth } from "@/lib/auth";
+
+export const { GET, POST } = toNextJsHandler(auth);
+"#;
+
+const EMAIL: &str = r#"import type { ReactElement } from "react";
+import { Resend } from "resend";
+
+let resend: Resend | undefined;
+
+type Email = {
+  to: string;
+  subject: string;
+  react: ReactElement;
+};
+
+/**
+ * Sends a transactional email through Resend. Without RESEND_API_KEY, e.g. in local
+ * development, the email is logged instead so sign-ups and webhooks keep working.
+ */
+export async function sendEmail({ to, subject, react }: Email) {
+  if (!process.env.RESEND_API_KEY) {
+    console.info(`[email] RESEND_API_KEY is not set; skipped "${subject}" to ${to}`);
+    return;
+  }
+
+  resend ??= new Resend(process.env.RESEND_API_KEY);
+  const { error } = await resend.emails.send({ from: process.env.EMAIL_FROM!, to, subject, react });
+  if (error) {
+    throw new Error(`Failed to send "${subject}": ${error.message}`);
+  }
+}
+"#;
+
+const WELCOME_EMAIL: &str = r##"import { Body, Button, Container, Head, Heading, Html, Preview, Text } from "@react-email/components";
+import { siteConfig } from "@/lib/site";
+
+export function WelcomeEmail({ name }: { name: string }) {
+  return (
+    <Html>
+      <Head />
+      <Preview>Welcome to {siteConfig.name}</Preview>
+      <Body style={{ fontFamily: "sans-serif", backgroundColor: "#f6f6f6" }}>
+        <Container style={{ backgroundColor: "#ffffff", padding: "32px", borderRadius: "8px" }}>
+          <Heading>Welcome, {name}!</Heading>
+          <Text>Thanks for signing up for {siteConfig.name}. Your dashboard is ready.</Text>
+          <Button
+            href={`${siteConfig.url}/dashboard`}
+            style={{ backgroundColor: "#000000", color: "#ffffff", padding: "12px 20px", borderRadius: "6px" }}
+          >
+            Open your dashboard
+          </Button>
+        </Container>
+      </Body>
+    </Html>
+  );
+}
+
+export default WelcomeEmail;
+"##;
+
+const SUBSCRIPTION_EMAIL: &str = r##"import { Body, Container, Head, Heading, Html, Preview, Text } from "@react-email/components";
+import { siteConfig } from "@/lib/site";
+
+type SubscriptionEmailProps = {
+  name: string;
+  plan: string;
+  status: "started" | "canceled";
+};
+
+export function SubscriptionEmail({ name, plan, status }: SubscriptionEmailProps) {
+  const summary = status === "started" ? `Your ${plan} plan is active` : `Your ${plan} plan was canceled`;
+  return (
+    <Html>
+      <Head />
+      <Preview>{summary}</Preview>
+      <Body style={{ fontFamily: "sans-serif", backgroundColor: "#f6f6f6" }}>
+        <Container style={{ backgroundColor: "#ffffff", padding: "32px", borderRadius: "8px" }}>
+          <Heading>{summary}</Heading>
+          <Text>Hi {name},</Text>
+          <Text>
+            {status === "started"
+              ? `Thanks for subscribing to ${siteConfig.name} ${plan}. You can manage billing from your dashboard at any time.`
+              : `You will keep access until the end of the current billing period. You can resubscribe from your dashboard.`}
+          </Text>
+        </Container>
+      </Body>
+    </Html>
+  );
+}
+
+export default SubscriptionEmail;
+"##;
+
+const STRIPE: &str = r#"import Stripe from "stripe";
+
+export const stripe = new Stripe(process.env.STRIPE_SECRET_KEY!);
+"#;
+
+const PLANS: &str = r#"export type Plan = {
+  id: "free" | "pro";
+  name: string;
+  price: string;
+  features: string[];
+  /** Stripe price the plan is sold at; the free plan has none */
+  priceId?: string;
+};
+
+export const FREE_PLAN: Plan = {
+  id: "free",
+  name: "Free",
+  price: "$0",
+  features: ["1 project", "Community support"],
+};
+
+export const PRO_PLAN: Plan = {
+  id: "pro",
+  name: "Pro",
+  price: "$19/month",
+  features: ["Unlimited projects", "Priority support", "Advanced analytics"],
+  priceId: process.env.STRIPE_PRO_PRICE_ID,
+};
+
+export const PLANS = [FREE_PLAN, PRO_PLAN];
+"#;
+
+const SUBSCRIPTION: &str = r#"import { eq } from "drizzle-orm";
+import { db } from "@/db";
+import { subscriptionsTable, type Subscription } from "@/db/schema";
+import { FREE_PLAN, PLANS, type Plan } from "@/lib/billing/plans";
+
+const ACTIVE_STATUSES = ["active", "trialing", "past_due"];
+
+export async function getSubscription(userId: Subscription["userId"]): Promise<Subscription | null> {
+  const [subscription] = await db
+    .select()
+    .from(subscriptionsTable)
+    .where(eq(subscriptionsTable.userId, userId))
+    .limit(1);
+  return subscription ?? null;
+}
+
+export function isActive(subscription: Subscription | null): subscription is Subscription {
+  return subscription !== null && ACTIVE_STATUSES.includes(subscription.status);
+}
+
+/** The plan a user is on: the plan of their active subscription's price, otherwise free. */
+export function planFor(subscription: Subscription | null): Plan {
+  if (!isActive(subscription)) return FREE_PLAN;
+  return PLANS.find((plan) => plan.priceId && plan.priceId === subscription.stripePriceId) ?? FREE_PLAN;
+}
+"#;
+
+const CHECKOUT_ROUTE: &str = r#"import { NextResponse } from "next/server";
+import { db } from "@/db";
+import { subscriptionsTable } from "@/db/schema";
+import { getCurrentUser } from "@/lib/auth";
+import { PRO_PLAN } from "@/lib/billing/plans";
+import { getSubscription } from "@/lib/billing/subscription";
+import { siteConfig } from "@/lib/site";
+import { stripe } from "@/lib/stripe";
+
+export async function POST() {
+  const user = await getCurrentUser();
+  if (!user) {
+    return NextResponse.redirect(new URL("/sign-in", siteConfig.url), 303);
+  }
+  if (!PRO_PLAN.priceId) {
+    return new Response("STRIPE_PRO_PRICE_ID is not set", { status: 500 });
+  }
+
+  // Each user gets one Stripe customer, created on their first checkout
+  let subscription = await getSubscription(user.id);
+  if (!subscription) {
+    const customer = await stripe.customers.create({
+      email: user.email,
+      name: user.name,
+      metadata: { userId: String(user.id) },
+    });
+    [subscription] = await db
+      .insert(subscriptionsTable)
+      .values({ userId: user.id, stripeCustomerId: customer.id })
+      .returning();
+  }
+
+  const session = await stripe.checkout.sessions.create({
+    mode: "subscription",
+    customer: subscription.stripeCustomerId,
+    line_items: [{ price: PRO_PLAN.priceId, quantity: 1 }],
+    success_url: `${siteConfig.url}/dashboard?checkout=success`,
+    cancel_url: `${siteConfig.url}/dashboard`,
+    subscription_data: { metadata: { userId: String(user.id) } },
+  });
+  return NextResponse.redirect(session.url!, 303);
+}
+"#;
+
+const PORTAL_ROUTE: &str = r#"import { NextResponse } from "next/server";
+import { getCurrentUser } from "@/lib/auth";
+import { getSubscription } from "@/lib/billing/subscription";
+import { siteConfig } from "@/lib/site";
+import { stripe } from "@/lib/stripe";
+
+export async function POST() {
+  const user = await getCurrentUser();
+  if (!user) {
+    return NextResponse.redirect(new URL("/sign-in", siteConfig.url), 303);
+  }
+
+  const subscription = await getSubscription(user.id);
+  if (!subscription) {
+    return NextResponse.redirect(new URL("/dashboard", siteConfig.url), 303);
+  }
+
+  const session = await stripe.billingPortal.sessions.create({
+    customer: subscription.stripeCustomerId,
+    return_url: `${siteConfig.url}/dashboard`,
+  });
+  return NextResponse.redirect(session.url, 303);
+}
+"#;
+
+const WEBHOOK_ROUTE: &str = r#"import { eq } from "drizzle-orm";
+import type Stripe from "stripe";
+import { db } from "@/db";
+import { subscriptionsTable, usersTable } from "@/db/schema";
+import { SubscriptionEmail } from "@/emails/subscription-email";
+import { planFor } from "@/lib/billing/subscription";
+import { sendEmail } from "@/lib/email";
+import { stripe } from "@/lib/stripe";
+
+export async function POST(request: Request) {
+  const body = await request.text();
+  const signature = request.headers.get("stripe-signature") ?? "";
+
+  let event: Stripe.Event;
+  try {
+    event = stripe.webhooks.constructEvent(body, signature, process.env.STRIPE_WEBHOOK_SECRET!);
+  } catch (error) {
+    return new Response(`Webhook error: ${(error as Error).message}`, { status: 400 });
+  }
+
+  switch (event.type) {
+    case "customer.subscription.created":
+    case "customer.subscription.updated":
+    case "customer.subscription.deleted":
+      await syncSubscription(event.data.object, event.type);
+      break;
+  }
+
+  return Response.json({ received: true });
+}
+
+/**
+ * Mirrors a Stripe subscription onto the customer's row, and emails the user when it
+ * starts or ends.
+ */
+async function syncSubscription(subscription: Stripe.Subscription, type: Stripe.Event.Type) {
+  const customerId = typeof subscription.customer === "string" ? subscription.customer : subscription.customer.id;
+  const item = subscription.items.data[0];
+
+  const [row] = await db
+    .update(subscriptionsTable)
+    .set({
+      stripeSubscriptionId: subscription.id,
+      stripePriceId: item?.price.id ?? null,
+      status: subscription.status,
+      currentPeriodEnd: item ? new Date(item.current_period_end * 1000) : null,
+      cancelAtPeriodEnd: subscription.cancel_at_period_end,
+      updatedAt: new Date(),
+    })
+    .where(eq(subscriptionsTable.stripeCustomerId, customerId))
+    .returning();
+  if (!row || type === "customer.subscription.updated") return;
+
+  const [user] = await db.select().from(usersTable).where(eq(usersTable.id, row.userId)).limit(1);
+  if (!user) return;
+
+  const started = type === "customer.subscription.created";
+  const plan = planFor(started ? row : { ...row, status: "active" });
+  await sendEmail({
+    to: user.email,
+    subject: started ? `Your ${plan.name} plan is active` : `Your ${plan.name} plan was canceled`,
+    react: SubscriptionEmail({ name: user.name, plan: plan.name, status: started ? "started" : "canceled" }),
+  });
+}
+"#;
+
+const SIGN_IN_FORM: &str = r#""use client";
+
+import { useRouter } from "next/navigation";
+import { useState, type FormEvent } from "react";
+import { authClient } from "@/lib/auth-client";
+
+export function SignInForm() {
+  const router = useRouter();
+  const [mode, setMode] = useState<"sign-in" | "sign-up">("sign-in");
+  const [error, setError] = useState<string | null>(null);
+  const [pending, setPending] = useState(false);
+
+  async function onSubmit(event: FormEvent<HTMLFormElement>) {
+    event.preventDefault();
+    const form = new FormData(event.currentTarget);
+    const email = String(form.get("email"));
+    const password = String(form.get("password"));
+
+    setPending(true);
+    setError(null);
+    const { error } =
+      mode === "sign-in"
+        ? await authClient.signIn.email({ email, password })
+        : await authClient.signUp.email({ email, password, name: String(form.get("name")) });
+    setPending(false);
+
+    if (error) {
+      setError(error.message ?? "Something went wrong");
+      return;
+    }
+    router.push("/dashboard");
+    router.refresh();
+  }
+
+  return (
+    <form onSubmit={onSubmit} className="flex w-full max-w-sm flex-col gap-4">
+      <h1 className="text-2xl font-semibold">{mode === "sign-in" ? "Sign in" : "Create an account"}</h1>
+      {mode === "sign-up" && (
+        <input name="name" placeholder="Name" required className="rounded-md border px-3 py-2" />
+      )}
+      <input name="email" type="email" placeholder="Email" required className="rounded-md border px-3 py-2" />
+      <input
+        name="password"
+        type="password"
+        placeholder="Password"
+        minLength={8}
+        required
+        className="rounded-md border px-3 py-2"
+      />
+      {error && <p className="text-sm text-red-600">{error}</p>}
+      <button type="submit" disabled={pending} className="rounded-md bg-black px-3 py-2 text-white disabled:opacity-50">
+        {pending ? "Please wait..." : mode === "sign-in" ? "Sign in" : "Sign up"}
+      </button>
+      <button
+        type="button"
+        onClick={() => setMode(mode === "sign-in" ? "sign-up" : "sign-in")}
+        className="text-sm text-gray-600 underline"
+      >
+        {mode === "sign-in" ? "No account yet? Sign up" : "Already have an account? Sign in"}
+      </button>
+    </form>
+  );
+}
+"#;
+
+const SIGN_OUT_BUTTON: &str = r#""use client";
+
+import { useRouter } from "next/navigation";
+import { authClient } from "@/lib/auth-client";
+
+export function SignOutButton() {
+  const router = useRouter();
+
+  async function signOut() {
+    await authClient.signOut();
+    router.push("/sign-in");
+    router.refresh();
+  }
+
+  return (
+    <button type="button" onClick={signOut} className="text-sm text-gray-600 underline">
+      Sign out
+    </button>
+  );
+}
+"#;
+
+const SIGN_IN_PAGE: &str = r#"import { redirect } from "next/navigation";
+import { SignInForm } from "@/components/saas/sign-in-form";
+import { getCurrentUser } from "@/lib/auth";
+
+export default async function SignInPage() {
+  if (await getCurrentUser()) redirect("/dashboard");
+
+  return (
+    <main className="flex min-h-screen items-center justify-center p-6">
+      <SignInForm />
+    </main>
+  );
+}
+"#;
+
+const DASHBOARD_PAGE: &str = r#"import { redirect } from "next/navigation";
+import { SignOutButton } from "@/components/saas/sign-out-button";
+import { getCurrentUser } from "@/lib/auth";
+import { PRO_PLAN } from "@/lib/billing/plans";
+import { getSubscription, isActive, planFor } from "@/lib/billing/subscription";
+import { siteConfig } from "@/lib/site";
+
+export const metadata = {
+  title: `Dashboard | ${siteConfig.name}`,
+};
+
+export default async function DashboardPage() {
+  const user = await getCurrentUser();
+  if (!user) redirect("/sign-in");
+
+  const subscription = await getSubscription(user.id);
+  const plan = planFor(subscription);
+  const subscribed = isActive(subscription);
+
+  return (
+    <main className="mx-auto flex max-w-3xl flex-col gap-8 p-6">
+      <header className="flex items-center justify-between">
+        <div>
+          <h1 className="text-2xl font-semibold">Welcome, {user.name}</h1>
+          <p className="text-sm text-gray-600">{user.email}</p>
+        </div>
+        <SignOutButton />
+      </header>
+
+      <section className="rounded-lg border p-6">
+        <h2 className="text-lg font-medium">Billing</h2>
+        <p className="mt-1 text-sm text-gray-600">
+          You are on the <strong>{plan.name}</strong> plan
+          {subscribed && subscription.currentPeriodEnd && (
+            <>
+              {subscription.cancelAtPeriodEnd ? ", ending on " : ", renewing on "}
+              {subscription.currentPeriodEnd.toLocaleDateString()}
+            </>
+          )}
+          .
+        </p>
+        {subscribed ? (
+          <form action="/api/billing/portal" method="POST" className="mt-4">
+            <button type="submit" className="rounded-md border px-3 py-2">
+              Manage billing
+            </button>
+          </form>
+        ) : (
+          <form action="/api/billing/checkout" method="POST" className="mt-4">
+            <button type="submit" className="rounded-md bg-black px-3 py-2 text-white">
+              Upgrade to {PRO_PLAN.name} ({PRO_PLAN.price})
+            </button>
+          </form>
+        )}
+      </section>
+
+      <section className="rounded-lg border p-6">
+        <h2 className="text-lg font-medium">{PRO_PLAN.name} features</h2>
+        {plan.id === "pro" ? (
+          <ul className="mt-2 list-disc pl-5 text-sm">
+            {PRO_PLAN.features.map((feature) => (
+              <li key={feature}>{feature}</li>
+            ))}
+          </ul>
+        ) : (
+          <p className="mt-1 text-sm text-gray-600">Upgrade to unlock {PRO_PLAN.features.join(", ").toLowerCase()}.</p>
+        )}
+      </section>
+    </main>
+  );
+}
+"#;
+
+/// lib/auth.ts for the schema's ids: Better Auth generates string ids unless told that
+/// the database or the `id()` helper does.
+fn auth_ts(id_strategy: Option<IdStrategy>) -> String {
+    let (id_generation, user_id) = match id_strategy {
+        None | Some(IdStrategy::Serial) => (
+            "  // The schema uses integer identity ids, generated by the database\n  advanced: { database: { useNumberId: true } },",
+            "id: Number(id)",
+        ),
+        Some(IdStrategy::Uuid | IdStrategy::Ulid) => (
+            "  // Ids come from the schema's id() helper\n  advanced: { database: { generateId: false } },",
+            "id",
+        ),
+    };
+    AUTH.replace("__ID_GENERATION__", id_generation).replace("__USER_ID__", user_id)
+}
+
+/// Adds the auth and billing tables to the drizzle schema, and the auth columns to its
+/// users table. Returns the updated schema and whether the users table could be extended.
+fn extend_schema(schema: &str, helpers: bool) -> (String, Vec<String>, bool) {
+    let mut schema = schema.to_string();
+    let mut tables = Vec::new();
+    let mut users_ready = true;
+
+    if !schema.contains("export const usersTable") {
+        tables.push(schema_conventions::render_columns(USERS_TABLE, helpers));
+    } else if !schema.contains("emailVerified") {
+        if schema.contains(STOCK_EMAIL_COLUMN) {
+            schema = schema.replacen(STOCK_EMAIL_COLUMN, &format!("{}{}", STOCK_EMAIL_COLUMN, AUTH_USER_COLUMNS), 1);
+        } else {
+            users_ready = false;
+        }
+    }
+
+    for (name, table) in SCHEMA_TABLES {
+        if !schema.contains(&format!("export const {} ", name)) {
+            tables.push(schema_conventions::render_columns(table, helpers));
+        }
+    }
+    (schema, tables, users_ready)
+}
+
+pub async fn add_saas() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    if let Some((package, _)) = manifest::installed_auth_library().filter(|(package, _)| *package != "better-auth") {
+        println!(
+            "{}",
+            style(format!("The SaaS starter sets up Better Auth, but this project already uses {}. Remove it first or wire billing into it by hand.", package)).yellow()
+        );
+        return Ok(());
+    }
+
+    // The starter shares drizzle's schema, connection and DATABASE_URL, so set it up first
+    if !ProjectManifest::is_feature_installed("drizzle") {
+        println!("\n{}", style("Setting up Drizzle ORM for the SaaS schema").cyan().bold());
+        drizzle::add_drizzle().await.context("Failed to set up Drizzle ORM")?;
+        ProjectManifest::record_feature("drizzle")?;
+        println!();
+    }
+
+    let project_structure = ProjectStructure::detect()?;
+    let id_strategy = IdStrategy::installed(&project_structure.get_db_path());
+    let default_name = package_json::package_name().unwrap_or_else(|| "My App".to_string());
+    let app_name = prompt::input("app_name", "Product name", &default_name)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Better Auth, Stripe and Resend...");
+
+    package_manager
+        .add(&["better-auth", "stripe", "resend", "@react-email/components"])
+        .run()
+        .context("Failed to install SaaS dependencies")?;
+
+    pb.set_message("Adding auth and billing tables to the schema...");
+
+    let schema_path = project_structure.get_db_path().join("schema.ts");
+    let schema = std::fs::read_to_string(&schema_path).unwrap_or_default();
+    let (schema, tables, users_ready) = extend_schema(&schema, id_strategy.is_some());
+    let tables: Vec<&str> = tables.iter().map(String::as_str).collect();
+    schema_conventions::append_tables(&schema_path, &schema, &tables, &PG_CORE_IMPORTS)?;
+
+    pb.set_message("Creating auth, email and billing helpers...");
+
+    let lib_path = project_structure.get_lib_path();
+    let billing_lib_path = lib_path.join("billing");
+    change_set::create_dir_all(&billing_lib_path).context("Failed to create lib/billing directory")?;
+    file_writer::write(lib_path.join("site.ts"), SITE.replace("__APP_NAME__", &app_name.replace('"', "\\\"")))
+        .context("Failed to create site.ts")?;
+    file_writer::write(lib_path.join("auth.ts"), auth_ts(id_strategy)).context("Failed to create auth.ts")?;
+    file_writer::write(lib_path.join("auth-client.ts"), AUTH_CLIENT).context("Failed to create auth-client.ts")?;
+    file_writer::write(lib_path.join("email.ts"), EMAIL).context("Failed to create email.ts")?;
+    file_writer::write(lib_path.join("stripe.ts"), STRIPE).context("Failed to create stripe.ts")?;
+    file_writer::write(billing_lib_path.join("plans.ts"), PLANS).context("Failed to create plans.ts")?;
+    file_writer::write(billing_lib_path.join("subscription.ts"), SUBSCRIPTION)
+        .context("Failed to create subscription.ts")?;
+
+    let emails_path = project_structure.get_source_root().join("emails");
+    change_set::create_dir_all(&emails_path).context("Failed to create emails directory")?;
+    file_writer::write(emails_path.join("welcome-email.tsx"), WELCOME_EMAIL)
+        .context("Failed to create welcome-email.tsx")?;
+    file_writer::write(emails_path.join("subscription-email.tsx"), SUBSCRIPTION_EMAIL)
+        .context("Failed to create subscription-email.tsx")?;
+
+    pb.set_message("Creating auth and billing routes...");
+
+    let api_path = project_structure.get_api_path();
+    let routes = [
+        ("auth/[...all]", AUTH_ROUTE),
+        ("billing/checkout", CHECKOUT_ROUTE),
+        ("billing/portal", PORTAL_ROUTE),
+        ("webhooks/stripe", WEBHOOK_ROUTE),
+    ];
+    for (route, content) in routes {
+        let route_path = api_path.join(route);
+        change_set::create_dir_all(&route_path).context(format!("Failed to create api/{} directory", route))?;
+        file_writer::write(route_path.join("route.ts"), content)
+            .context(format!("Failed to create api/{}/route.ts", route))?;
+    }
+
+    pb.set_message("Creating the sign-in page and dashboard...");
+
+    let components_path = project_structure.get_components_path().join("saas");
+    change_set::create_dir_all(&components_path).context("Failed to create components/saas directory")?;
+    file_writer::write(components_path.join("sign-in-form.tsx"), SIGN_IN_FORM)
+        .context("Failed to create sign-in-form.tsx")?;
+    file_writer::write(components_path.join("sign-out-button.tsx"), SIGN_OUT_BUTTON)
+        .context("Failed to create sign-out-button.tsx")?;
+
+    let routes_dir = root_layout::routes_dir(&project_structure);
+    let sign_in_path = routes_dir.join("(auth)/sign-in");
+    let dashboard_path = routes_dir.join("dashboard");
+    change_set::create_dir_all(&sign_in_path).context("Failed to create sign-in directory")?;
+    change_set::create_dir_all(&dashboard_path).context("Failed to create dashboard directory")?;
+    file_writer::write(sign_in_path.join("page.tsx"), SIGN_IN_PAGE).context("Failed to create sign-in page.tsx")?;
+    file_writer::write(dashboard_path.join("page.tsx"), DASHBOARD_PAGE)
+        .context("Failed to create dashboard page.tsx")?;
+
+    package_json::add_scripts(&[("stripe:listen", "stripe listen --forward-to localhost:3000/api/webhooks/stripe")])?;
+
+    env_file::add_env_vars(
+        "saas",
+        "SaaS starter",
+        &[
+            ("NEXT_PUBLIC_SITE_URL", "http://localhost:3000"),
+            ("BETTER_AUTH_SECRET", ""),
+            ("STRIPE_SECRET_KEY", ""),
+            ("STRIPE_WEBHOOK_SECRET", ""),
+            ("STRIPE_PRO_PRICE_ID", ""),
+            ("RESEND_API_KEY", ""),
+            ("EMAIL_FROM", "onboarding@resend.dev"),
+        ],
+    )?;
+
+    pb.finish_with_message("SaaS starter created successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {} - Users, sessions, accounts, verifications and subscriptions tables", schema_path.display());
+    println!("• {}/auth.ts - Better Auth on the drizzle schema, with getCurrentUser()", lib_path.display());
+    println!("• {}/email.ts - Resend sender; templates in {}/", lib_path.display(), emails_path.display());
+    println!("• {}/ - Plans and subscription lookups", billing_lib_path.display());
+    println!("• {}/ - Auth handler, Stripe checkout, portal and webhook routes", api_path.display());
+    println!("• {}/page.tsx - Email and password sign-in", sign_in_path.display());
+    println!("• {}/page.tsx - Billing-aware dashboard", dashboard_path.display());
+
+    if !users_ready {
+        println!(
+            "{}",
+            style(format!(
+                "Add emailVerified (boolean, default false) and image (text) columns to usersTable in {}; Better Auth needs them",
+                schema_path.display()
+            ))
+            .yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Set BETTER_AUTH_SECRET in .env, e.g. to the output of `openssl rand -base64 32`");
+    println!("2. Create a Pro product in Stripe and set STRIPE_SECRET_KEY and STRIPE_PRO_PRICE_ID");
+    println!("3. Run `stripe:listen` and copy its signing secret into STRIPE_WEBHOOK_SECRET");
+    println!("4. Set RESEND_API_KEY and EMAIL_FROM to send emails; without them they are logged");
+    println!("5. Push the schema with `db:push`, then sign up at /sign-in and open /dashboard");
+
+    Ok(())
+}
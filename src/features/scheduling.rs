@@ -0,0 +1,663 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::features::schema_conventions::{self, IdStrategy};
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+use crate::root_layout;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SchedulingProvider {
+    SelfHosted,
+    CalCom,
+}
+
+impl SchedulingProvider {
+    const ALL: [SchedulingProvider; 2] = [SchedulingProvider::SelfHosted, SchedulingProvider::CalCom];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SchedulingProvider::SelfHosted => "Self-hosted",
+            SchedulingProvider::CalCom => "Cal.com",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            SchedulingProvider::SelfHosted => "availability and bookings in your drizzle database",
+            SchedulingProvider::CalCom => "embed a Cal.com booking page",
+        }
+    }
+}
+
+const SCHEDULING_TABLES: &str = r#"
+// Scheduling: weekly availability, in the host's timezone
+export const availabilityTable = pgTable("availability", {
+  id: __ID__,
+  /** 0 is Sunday */
+  weekday: integer("weekday").notNull(),
+  startTime: time("start_time").notNull(),
+  endTime: time("end_time").notNull(),
+  timezone: text("timezone").notNull().default("UTC"),
+});
+
+// Scheduling: bookings, stored as UTC instants
+export const bookingsTable = pgTable("bookings", {
+  id: __ID__,
+  name: text("name").notNull(),
+  email: text("email").notNull(),
+  notes: text("notes"),
+  startsAt: timestamp("starts_at", { withTimezone: true }).notNull(),
+  endsAt: timestamp("ends_at", { withTimezone: true }).notNull(),
+  status: text("status", { enum: ["confirmed", "cancelled"] }).notNull().default("confirmed"),
+  __TIMESTAMPS__
+}, (table) => [
+  // Two live bookings never start together, even when requests race
+  uniqueIndex("bookings_starts_at_live").on(table.startsAt).where(sql`${table.status} <> 'cancelled'`),
+]);
+
+export type Availability = typeof availabilityTable.$inferSelect;
+export type Booking = typeof bookingsTable.$inferSelect;
+"#;
+
+const CONFIG: &str = r#"/** Length of every bookable slot */
+export const SLOT_MINUTES = 30;
+
+/** How soon the next bookable slot may start */
+export const MIN_NOTICE_MINUTES = 120;
+
+/** How many days ahead visitors can book */
+export const BOOKING_WINDOW_DAYS = 30;
+"#;
+
+const SLOTS: &str = r#"import type { Availability, Booking } from "@/db/schema";
+import { BOOKING_WINDOW_DAYS, MIN_NOTICE_MINUTES, SLOT_MINUTES } from "@/lib/scheduling/config";
+
+export type Slot = {
+  start: Date;
+  end: Date;
+};
+
+type AvailabilityWindow = Pick<Availability, "weekday" | "startTime" | "endTime" | "timezone">;
+type BusyTime = Pick<Booking, "startsAt" | "endsAt">;
+
+const MINUTE = 60_000;
+const DAY = 24 * 60 * MINUTE;
+
+/** How far `timeZone` is ahead of UTC at an instant, in minutes. */
+function offsetMinutes(instant: Date, timeZone: string) {
+  const parts = Object.fromEntries(
+    new Intl.DateTimeFormat("en-US", {
+      timeZone,
+      hourCycle: "h23",
+      year: "numeric",
+      month: "2-digit",
+      day: "2-digit",
+      hour: "2-digit",
+      minute: "2-digit",
+      second: "2-digit",
+    })
+      .formatToParts(instant)
+      .map(({ type, value }) => [type, Number(value)]),
+  );
+  const wallClock = Date.UTC(parts.year, parts.month - 1, parts.day, parts.hour, parts.minute, parts.second);
+  return (wallClock - Math.floor(instant.getTime() / 1000) * 1000) / MINUTE;
+}
+
+/** The instant a wall-clock date and time ("2025-03-30", "09:30") happen in `timeZone`. */
+export function zonedTimeToUtc(date: string, time: string, timeZone: string): Date {
+  const [year, month, day] = date.split("-").map(Number);
+  const [hours, minutes] = time.split(":").map(Number);
+  const wallClock = Date.UTC(year, month - 1, day, hours, minutes);
+
+  // A second pass picks up the offset on the other side of a daylight saving change
+  const guess = wallClock - offsetMinutes(new Date(wallClock), timeZone) * MINUTE;
+  return new Date(wallClock - offsetMinutes(new Date(guess), timeZone) * MINUTE);
+}
+
+/** The calendar date ("2025-03-30") an instant falls on in `timeZone`. */
+export function dateInZone(instant: Date, timeZone: string) {
+  return new Intl.DateTimeFormat("en-CA", { timeZone, year: "numeric", month: "2-digit", day: "2-digit" }).format(instant);
+}
+
+export function overlaps(a: Slot, b: Slot) {
+  return a.start < b.end && b.start < a.end;
+}
+
+/** Whether visitors may book on a date: a real "YYYY-MM-DD" inside the booking window. */
+export function isBookableDate(date: string, now = new Date()) {
+  if (!/^\d{4}-\d{2}-\d{2}$/.test(date) || Number.isNaN(Date.parse(date))) return false;
+
+  // A day of slack either side, since the date is a calendar day in the host's timezone
+  const days = (Date.parse(`${date}T00:00:00Z`) - now.getTime()) / DAY;
+  return days > -2 && days <= BOOKING_WINDOW_DAYS;
+}
+
+function toMinutes(time: string) {
+  const [hours, minutes] = time.split(":").map(Number);
+  return hours * 60 + minutes;
+}
+
+function fromMinutes(total: number) {
+  return `${String(Math.floor(total / 60)).padStart(2, "0")}:${String(total % 60).padStart(2, "0")}`;
+}
+
+type SlotOptions = {
+  /** A calendar day, read in each availability window's own timezone */
+  date: string;
+  availability: AvailabilityWindow[];
+  bookings: BusyTime[];
+  now?: Date;
+  slotMinutes?: number;
+};
+
+/**
+ * The free slots on a day. Each slot is converted from the host's wall clock separately,
+ * so days when the clocks change keep their usual hours.
+ */
+export function getAvailableSlots({
+  date,
+  availability,
+  bookings,
+  now = new Date(),
+  slotMinutes = SLOT_MINUTES,
+}: SlotOptions): Slot[] {
+  const [year, month, day] = date.split("-").map(Number);
+  const weekday = new Date(Date.UTC(year, month - 1, day)).getUTCDay();
+  const earliest = now.getTime() + MIN_NOTICE_MINUTES * MINUTE;
+  const busy = bookings.map(({ startsAt, endsAt }) => ({ start: startsAt, end: endsAt }));
+
+  return availability
+    .filter((window) => window.weekday === weekday)
+    .flatMap((window) => {
+      const slots: Slot[] = [];
+      const end = toMinutes(window.endTime);
+      for (let minutes = toMinutes(window.startTime); minutes + slotMinutes <= end; minutes += slotMinutes) {
+        const start = zonedTimeToUtc(date, fromMinutes(minutes), window.timezone);
+        slots.push({ start, end: new Date(start.getTime() + slotMinutes * MINUTE) });
+      }
+      return slots;
+    })
+    .filter((slot) => slot.start.getTime() >= earliest && !busy.some((time) => overlaps(slot, time)))
+    .sort((a, b) => a.start.getTime() - b.start.getTime());
+}
+"#;
+
+const QUERIES: &str = r#"import { and, asc, eq, gt, gte, lt, ne } from "drizzle-orm";
+import { db } from "@/db";
+import { availabilityTable, bookingsTable } from "@/db/schema";
+import { getAvailableSlots } from "@/lib/scheduling/slots";
+
+const DAY = 24 * 60 * 60 * 1000;
+
+/** The free slots on a calendar day. */
+export async function getSlots(date: string, now = new Date()) {
+  // A day either side covers the date in every timezone
+  const midnight = Date.parse(`${date}T00:00:00Z`);
+  const [availability, bookings] = await Promise.all([
+    db.select().from(availabilityTable),
+    db
+      .select({ startsAt: bookingsTable.startsAt, endsAt: bookingsTable.endsAt })
+      .from(bookingsTable)
+      .where(
+        and(
+          ne(bookingsTable.status, "cancelled"),
+          lt(bookingsTable.startsAt, new Date(midnight + 2 * DAY)),
+          gt(bookingsTable.endsAt, new Date(midnight - DAY)),
+        ),
+      ),
+  ]);
+  return getAvailableSlots({ date, availability, bookings, now });
+}
+
+export async function getUpcomingBookings(now = new Date()) {
+  return db
+    .select()
+    .from(bookingsTable)
+    .where(and(eq(bookingsTable.status, "confirmed"), gte(bookingsTable.startsAt, now)))
+    .orderBy(asc(bookingsTable.startsAt));
+}
+"#;
+
+const ACTIONS: &str = r#""use server";
+
+import { revalidatePath } from "next/cache";
+import { db } from "@/db";
+import { bookingsTable } from "@/db/schema";
+import { getSlots } from "@/lib/scheduling/queries";
+import { isBookableDate } from "@/lib/scheduling/slots";
+
+export type SlotOption = {
+  start: string;
+  end: string;
+};
+
+export type BookingResult =
+  | { status: "booked"; start: string; end: string }
+  | { status: "error"; message: string };
+
+export async function fetchSlots(date: string): Promise<SlotOption[]> {
+  if (!isBookableDate(date)) return [];
+
+  const slots = await getSlots(date);
+  return slots.map(({ start, end }) => ({ start: start.toISOString(), end: end.toISOString() }));
+}
+
+export async function bookSlot(formData: FormData): Promise<BookingResult> {
+  const name = String(formData.get("name") ?? "").trim();
+  const email = String(formData.get("email") ?? "").trim();
+  const notes = String(formData.get("notes") ?? "").trim();
+  const date = String(formData.get("date") ?? "");
+  const start = new Date(String(formData.get("start") ?? ""));
+
+  if (!name || !/^\S+@\S+\.\S+$/.test(email)) {
+    return { status: "error", message: "Enter your name and a valid email" };
+  }
+  if (!isBookableDate(date) || Number.isNaN(start.getTime())) {
+    return { status: "error", message: "Pick a time to book" };
+  }
+
+  // Only a slot that is still offered can be booked: inside availability, far enough ahead
+  // and clear of other bookings
+  const slot = (await getSlots(date)).find((candidate) => candidate.start.getTime() === start.getTime());
+  if (!slot) {
+    return { status: "error", message: "That time is no longer available, please pick another" };
+  }
+
+  try {
+    await db.insert(bookingsTable).values({
+      name,
+      email,
+      notes: notes || null,
+      startsAt: slot.start,
+      endsAt: slot.end,
+    });
+  } catch (error) {
+    if (isUniqueViolation(error)) {
+      return { status: "error", message: "That time was just booked, please pick another" };
+    }
+    throw error;
+  }
+
+  revalidatePath("/book");
+  return { status: "booked", start: slot.start.toISOString(), end: slot.end.toISOString() };
+}
+
+// Postgres drivers report the code on the error, drizzle on its cause
+function isUniqueViolation(error: unknown): boolean {
+  if (typeof error !== "object" || error === null) return false;
+  const { code, cause } = error as { code?: string; cause?: unknown };
+  return code === "23505" || isUniqueViolation(cause);
+}
+"#;
+
+const SEED_SCRIPT: &str = r#"import { db } from "@/db";
+import { availabilityTable } from "@/db/schema";
+
+// Monday to Friday, 9:00 to 17:00 in the host's timezone; edit and re-run to change it
+const TIMEZONE = "__TIMEZONE__";
+const WEEKDAYS = [1, 2, 3, 4, 5];
+
+async function main() {
+  await db.delete(availabilityTable);
+  await db
+    .insert(availabilityTable)
+    .values(WEEKDAYS.map((weekday) => ({ weekday, startTime: "09:00", endTime: "17:00", timezone: TIMEZONE })));
+  console.log(`Availability set for ${WEEKDAYS.length} weekdays in ${TIMEZONE}`);
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
+"#;
+
+const BOOKING_WIDGET: &str = r#""use client";
+
+import { useMemo, useState, useTransition } from "react";
+import { bookSlot, fetchSlots, type BookingResult, type SlotOption } from "@/lib/scheduling/actions";
+import { BOOKING_WINDOW_DAYS } from "@/lib/scheduling/config";
+
+function localDate(date: Date) {
+  return `${date.getFullYear()}-${String(date.getMonth() + 1).padStart(2, "0")}-${String(date.getDate()).padStart(2, "0")}`;
+}
+
+function formatTime(iso: string) {
+  return new Date(iso).toLocaleTimeString([], { hour: "numeric", minute: "2-digit" });
+}
+
+/** Pick a day, then a time shown in the visitor's own timezone, then book it. */
+export function BookingWidget() {
+  const days = useMemo(() => {
+    const today = new Date();
+    return Array.from({ length: BOOKING_WINDOW_DAYS }, (_, offset) => {
+      const day = new Date(today.getFullYear(), today.getMonth(), today.getDate() + offset);
+      return { value: localDate(day), label: day.toLocaleDateString([], { weekday: "short", day: "numeric", month: "short" }) };
+    });
+  }, []);
+  const timeZone = useMemo(() => Intl.DateTimeFormat().resolvedOptions().timeZone, []);
+
+  const [date, setDate] = useState<string | null>(null);
+  const [slots, setSlots] = useState<SlotOption[]>([]);
+  const [selected, setSelected] = useState<SlotOption | null>(null);
+  const [result, setResult] = useState<BookingResult | null>(null);
+  const [loading, startLoading] = useTransition();
+  const [booking, startBooking] = useTransition();
+
+  const selectDate = (value: string) => {
+    setDate(value);
+    setSelected(null);
+    setResult(null);
+    startLoading(async () => setSlots(await fetchSlots(value)));
+  };
+
+  if (result?.status === "booked") {
+    return (
+      <div className="rounded-md border p-6 text-center">
+        <h2 className="text-lg font-semibold">You&apos;re booked</h2>
+        <p className="mt-1 text-sm text-gray-600">
+          {new Date(result.start).toLocaleDateString([], { dateStyle: "full" })}, {formatTime(result.start)} to{" "}
+          {formatTime(result.end)} ({timeZone})
+        </p>
+      </div>
+    );
+  }
+
+  return (
+    <div className="space-y-6">
+      <div className="flex gap-2 overflow-x-auto pb-2">
+        {days.map((day) => (
+          <button
+            key={day.value}
+            type="button"
+            onClick={() => selectDate(day.value)}
+            className={`shrink-0 rounded-md border px-3 py-2 text-sm ${
+              day.value === date ? "border-blue-600 bg-blue-600 text-white" : "hover:bg-gray-100 dark:hover:bg-neutral-800"
+            }`}
+          >
+            {day.label}
+          </button>
+        ))}
+      </div>
+
+      {date && (
+        <div>
+          <p className="mb-2 text-sm text-gray-500">Times in {timeZone}</p>
+          {loading ? (
+            <p className="text-sm text-gray-500">Loading times…</p>
+          ) : slots.length === 0 ? (
+            <p className="text-sm text-gray-500">No times left on this day.</p>
+          ) : (
+            <div className="grid grid-cols-3 gap-2 sm:grid-cols-4">
+              {slots.map((slot) => (
+                <button
+                  key={slot.start}
+                  type="button"
+                  onClick={() => setSelected(slot)}
+                  className={`rounded-md border px-3 py-2 text-sm ${
+                    slot.start === selected?.start ? "border-blue-600 bg-blue-600 text-white" : "hover:bg-gray-100 dark:hover:bg-neutral-800"
+                  }`}
+                >
+                  {formatTime(slot.start)}
+                </button>
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+
+      {date && selected && (
+        <form
+          action={(formData) => startBooking(async () => setResult(await bookSlot(formData)))}
+          className="space-y-3 rounded-md border p-4"
+        >
+          <p className="text-sm font-medium">
+            {formatTime(selected.start)} to {formatTime(selected.end)}
+          </p>
+          <input type="hidden" name="date" value={date} />
+          <input type="hidden" name="start" value={selected.start} />
+          <input name="name" required placeholder="Your name" className="w-full rounded-md border bg-transparent px-3 py-2 text-sm" />
+          <input name="email" type="email" required placeholder="you@example.com" className="w-full rounded-md border bg-transparent px-3 py-2 text-sm" />
+          <textarea name="notes" placeholder="Anything we should know?" className="w-full rounded-md border bg-transparent px-3 py-2 text-sm" />
+          {result?.status === "error" && <p className="text-sm text-red-600">{result.message}</p>}
+          <button
+            type="submit"
+            disabled={booking}
+            className="rounded-md bg-blue-600 px-4 py-2 text-sm font-medium text-white disabled:opacity-50"
+          >
+            {booking ? "Booking…" : "Confirm booking"}
+          </button>
+        </form>
+      )}
+    </div>
+  );
+}
+"#;
+
+const SELF_HOSTED_PAGE: &str = r#"import type { Metadata } from "next";
+import { BookingWidget } from "@/components/scheduling/booking-widget";
+
+export const metadata: Metadata = {
+  title: "Book a time",
+};
+
+export default function BookPage() {
+  return (
+    <main className="mx-auto max-w-2xl space-y-6 p-8">
+      <div>
+        <h1 className="text-2xl font-semibold">Book a time</h1>
+        <p className="text-sm text-gray-500">Pick a day and a time that works for you.</p>
+      </div>
+      <BookingWidget />
+    </main>
+  );
+}
+"#;
+
+const CAL_EMBED: &str = r#""use client";
+
+import Cal, { getCalApi } from "@calcom/embed-react";
+import { useEffect } from "react";
+
+type CalEmbedProps = {
+  /** A Cal.com event link such as "your-username/30min" */
+  calLink?: string;
+};
+
+export function CalEmbed({ calLink = process.env.NEXT_PUBLIC_CAL_LINK! }: CalEmbedProps) {
+  useEffect(() => {
+    (async () => {
+      const cal = await getCalApi();
+      cal("ui", { layout: "month_view", hideEventTypeDetails: false });
+    })();
+  }, []);
+
+  return (
+    <Cal
+      calLink={calLink}
+      config={{ layout: "month_view" }}
+      style={{ width: "100%", height: "100%", overflow: "scroll" }}
+    />
+  );
+}
+"#;
+
+const CAL_PAGE: &str = r#"import type { Metadata } from "next";
+import { CalEmbed } from "@/components/scheduling/cal-embed";
+
+export const metadata: Metadata = {
+  title: "Book a time",
+};
+
+export default function BookPage() {
+  return (
+    <main className="mx-auto max-w-4xl space-y-6 p-8">
+      <h1 className="text-2xl font-semibold">Book a time</h1>
+      <CalEmbed />
+    </main>
+  );
+}
+"#;
+
+/// Adds `name` to the schema's `drizzle-orm` import, or adds the import at the top.
+fn add_drizzle_orm_import(schema: &str, name: &str) -> String {
+    let import_line = schema
+        .lines()
+        .find(|line| line.starts_with("import {") && line.contains("\"drizzle-orm\""));
+    match import_line {
+        Some(line) => {
+            let Some((imported, _)) = line.split_once('{').and_then(|(_, rest)| rest.split_once('}')) else {
+                return schema.to_string();
+            };
+            let mut names: Vec<&str> = imported.split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+            if names.contains(&name) {
+                return schema.to_string();
+            }
+            names.push(name);
+            schema.replacen(line, &format!("import {{ {} }} from \"drizzle-orm\";", names.join(", ")), 1)
+        }
+        None => format!("import {{ {} }} from \"drizzle-orm\";\n{}", name, schema),
+    }
+}
+
+pub async fn add_scheduling() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let descriptions: Vec<String> = SchedulingProvider::ALL
+        .iter()
+        .map(|provider| format!("{} - {}", provider.as_str(), provider.description()))
+        .collect();
+    let selection = prompt::select("provider", "How should visitors book time?", &descriptions, 0)?;
+    let provider = SchedulingProvider::ALL[selection];
+
+    let db_path = project_structure.get_db_path();
+    let schema_path = db_path.join("schema.ts");
+    let schema = std::fs::read_to_string(&schema_path).unwrap_or_default();
+    if provider == SchedulingProvider::SelfHosted && !schema.contains("drizzle-orm/pg-core") {
+        println!(
+            "{}",
+            style("Self-hosted scheduling stores bookings with drizzle on Postgres. Run `nstack add --feature drizzle` first, or pick Cal.com.").yellow()
+        );
+        return Ok(());
+    }
+
+    let timezone = match provider {
+        SchedulingProvider::SelfHosted => prompt::input("timezone", "Host timezone (IANA name, e.g. Europe/London)", "UTC")?,
+        SchedulingProvider::CalCom => String::new(),
+    };
+
+    let routes_dir = root_layout::routes_dir(&project_structure);
+    let page_path = routes_dir.join("book");
+    let components_path = project_structure.get_components_path().join("scheduling");
+    let lib_path = project_structure.get_lib_path().join("scheduling");
+
+    let pb = ProgressBar::new_spinner();
+
+    match provider {
+        SchedulingProvider::SelfHosted => {
+            pb.set_message("Installing tsx...");
+
+            package_manager.install(&["tsx"], true)?;
+
+            pb.set_message("Adding availability and bookings tables to the schema...");
+
+            if !schema.contains("export const bookingsTable ") {
+                let helpers = IdStrategy::installed(&db_path).is_some();
+                let tables = schema_conventions::render_columns(SCHEDULING_TABLES, helpers);
+                schema_conventions::append_tables(
+                    &schema_path,
+                    &add_drizzle_orm_import(&schema, "sql"),
+                    &[&tables],
+                    &["pgTable", "integer", "text", "time", "timestamp", "uniqueIndex"],
+                )?;
+            }
+
+            pb.set_message("Creating slot helpers and booking actions...");
+
+            change_set::create_dir_all(&lib_path).context("Failed to create lib/scheduling directory")?;
+            file_writer::write(lib_path.join("config.ts"), CONFIG).context("Failed to create config.ts")?;
+            file_writer::write(lib_path.join("slots.ts"), SLOTS).context("Failed to create slots.ts")?;
+            file_writer::write(lib_path.join("queries.ts"), QUERIES).context("Failed to create queries.ts")?;
+            file_writer::write(lib_path.join("actions.ts"), ACTIONS).context("Failed to create actions.ts")?;
+
+            change_set::create_dir_all(project_file("scripts")).context("Failed to create scripts directory")?;
+            file_writer::write(project_file("scripts/seed-availability.ts"), SEED_SCRIPT.replace("__TIMEZONE__", &timezone))
+                .context("Failed to create seed-availability.ts")?;
+            package_json::add_scripts(&[("scheduling:seed", "tsx --env-file=.env scripts/seed-availability.ts")])?;
+
+            pb.set_message("Creating the booking page...");
+
+            change_set::create_dir_all(&components_path).context("Failed to create components/scheduling directory")?;
+            file_writer::write(components_path.join("booking-widget.tsx"), BOOKING_WIDGET)
+                .context("Failed to create booking-widget.tsx")?;
+            change_set::create_dir_all(&page_path).context("Failed to create book route directory")?;
+            file_writer::write(page_path.join("page.tsx"), SELF_HOSTED_PAGE).context("Failed to create page.tsx")?;
+        }
+        SchedulingProvider::CalCom => {
+            pb.set_message("Installing the Cal.com embed...");
+
+            package_manager.install(&["@calcom/embed-react"], false)?;
+
+            pb.set_message("Creating the booking page...");
+
+            change_set::create_dir_all(&components_path).context("Failed to create components/scheduling directory")?;
+            file_writer::write(components_path.join("cal-embed.tsx"), CAL_EMBED)
+                .context("Failed to create cal-embed.tsx")?;
+            change_set::create_dir_all(&page_path).context("Failed to create book route directory")?;
+            file_writer::write(page_path.join("page.tsx"), CAL_PAGE).context("Failed to create page.tsx")?;
+
+            env_file::add_env_vars("scheduling", "Scheduling", &[("NEXT_PUBLIC_CAL_LINK", "your-username/30min")])?;
+        }
+    }
+
+    pb.finish_with_message(format!("{} scheduling added successfully!", provider.as_str()));
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    match provider {
+        SchedulingProvider::SelfHosted => {
+            println!("• {} - availabilityTable and bookingsTable", schema_path.display());
+            println!("• {}/slots.ts - Timezone-safe slot calculation", lib_path.display());
+            println!("• {}/actions.ts - fetchSlots and bookSlot server actions with conflict checks", lib_path.display());
+            println!("• {}/queries.ts - Slots for a day and upcoming bookings", lib_path.display());
+            println!("• scripts/seed-availability.ts - Weekday 9:00 to 17:00 availability in {}", timezone);
+            println!("• {}/booking-widget.tsx - Embeddable <BookingWidget>", components_path.display());
+        }
+        SchedulingProvider::CalCom => {
+            println!("• {}/cal-embed.tsx - Inline Cal.com booking embed", components_path.display());
+        }
+    }
+    println!("• {}/page.tsx - Booking page", page_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    match provider {
+        SchedulingProvider::SelfHosted => {
+            println!("1. Push the schema with `db:push` or generate a migration with `db:generate`");
+            println!("2. Seed availability: {}", package_manager.run_script("scheduling:seed"));
+            println!("3. Open /book, or render <BookingWidget /> on any page");
+            println!("4. Tune slot length, notice and booking window in {}/config.ts", lib_path.display());
+        }
+        SchedulingProvider::CalCom => {
+            println!("1. Set NEXT_PUBLIC_CAL_LINK in .env to your Cal.com event, e.g. your-username/30min");
+            println!("2. Open /book, or render <CalEmbed calLink=\"...\" /> on any page");
+        }
+    }
+
+    Ok(())
+}
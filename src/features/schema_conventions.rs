@@ -0,0 +1,357 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::path::Path;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+/// How generated tables key their rows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IdStrategy {
+    Serial,
+    Uuid,
+    Ulid,
+}
+
+impl IdStrategy {
+    const ALL: [IdStrategy; 3] = [IdStrategy::Serial, IdStrategy::Uuid, IdStrategy::Ulid];
+
+    fn description(&self) -> &'static str {
+        match self {
+            IdStrategy::Serial => "serial - integer identity generated by the database",
+            IdStrategy::Uuid => "uuid - random UUIDs generated by the database",
+            IdStrategy::Ulid => "ulid - time-sortable ULIDs generated by the app",
+        }
+    }
+
+    /// Imports and the `id`/`foreignId` helpers of db/columns.ts.
+    fn id_helpers(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            IdStrategy::Serial => (
+                "integer",
+                "",
+                r#"/** Primary key: an integer identity generated by the database. */
+export const id = () => integer("id").primaryKey().generatedAlwaysAsIdentity();
+
+/** A column referencing another table's `id`, with the same type. */
+export const foreignId = (name: string) => integer(name);"#,
+            ),
+            IdStrategy::Uuid => (
+                "uuid",
+                "",
+                r#"/** Primary key: a random UUID generated by the database. */
+export const id = () => uuid("id").primaryKey().defaultRandom();
+
+/** A column referencing another table's `id`, with the same type. */
+export const foreignId = (name: string) => uuid(name);"#,
+            ),
+            IdStrategy::Ulid => (
+                "char",
+                "import { ulid } from \"ulid\";\n",
+                r#"/** Primary key: a ULID, which sorts by creation time, generated when the row is inserted. */
+export const id = () => char("id", { length: 26 }).primaryKey().$defaultFn(() => ulid());
+
+/** A column referencing another table's `id`, with the same type. */
+export const foreignId = (name: string) => char(name, { length: 26 });"#,
+            ),
+        }
+    }
+
+    /// Packages the helpers need besides drizzle-orm.
+    pub fn dependencies(&self) -> &'static [&'static str] {
+        match self {
+            IdStrategy::Ulid => &["ulid"],
+            IdStrategy::Serial | IdStrategy::Uuid => &[],
+        }
+    }
+
+    /// The strategy of the db/columns.ts in `db_path`, if the project has one.
+    pub fn installed(db_path: &Path) -> Option<IdStrategy> {
+        let columns = std::fs::read_to_string(db_path.join("columns.ts")).ok()?;
+        if columns.contains("generatedAlwaysAsIdentity") {
+            Some(IdStrategy::Serial)
+        } else if columns.contains("defaultRandom") {
+            Some(IdStrategy::Uuid)
+        } else if columns.contains("ulid()") {
+            Some(IdStrategy::Ulid)
+        } else {
+            None
+        }
+    }
+}
+
+const COLUMNS: &str = r#"import { isNull } from "drizzle-orm";
+import { __ID_TYPE__, timestamp, type AnyPgColumn } from "drizzle-orm/pg-core";
+__EXTRA_IMPORTS__
+// Column conventions shared by every table in schema.ts
+
+__ID_HELPERS__
+
+/** `createdAt` and `updatedAt`; drizzle bumps `updatedAt` on every update. */
+export const timestamps = {
+  createdAt: timestamp("created_at").defaultNow().notNull(),
+  updatedAt: timestamp("updated_at").defaultNow().notNull().$onUpdate(() => new Date()),
+};
+
+/** A `deletedAt` column for soft deletes. */
+export const softDelete = {
+  deletedAt: timestamp("deleted_at"),
+};
+
+/** Rows that have not been soft-deleted, e.g. `.where(notDeleted(postsTable))`. */
+export const notDeleted = (table: { deletedAt: AnyPgColumn }) => isNull(table.deletedAt);
+"#;
+
+/// Identity id column drizzle generated before the conventions existed.
+const LEGACY_ID_COLUMN: &str = "id: integer(\"id\").primaryKey().generatedAlwaysAsIdentity(),";
+const LEGACY_CREATED_AT: &str = "createdAt: timestamp(\"created_at\").defaultNow().notNull(),";
+const LEGACY_UPDATED_AT: &str = "updatedAt: timestamp(\"updated_at\").defaultNow().notNull(),";
+
+/// Helpers of db/columns.ts paired with how schema.ts uses them.
+const HELPERS: [(&str, &str); 4] = [
+    ("foreignId", "foreignId("),
+    ("id", "id()"),
+    ("softDelete", "...softDelete"),
+    ("timestamps", "...timestamps"),
+];
+
+/// The db/columns.ts helpers for `strategy`.
+pub fn columns_ts(strategy: IdStrategy) -> String {
+    let (id_type, extra_imports, id_helpers) = strategy.id_helpers();
+    COLUMNS
+        .replace("__ID_TYPE__", id_type)
+        .replace("__EXTRA_IMPORTS__", extra_imports)
+        .replace("__ID_HELPERS__", id_helpers)
+}
+
+/// Asks for the id strategy, suggesting `current` when the project already has one.
+pub fn select_id_strategy(current: Option<IdStrategy>) -> Result<IdStrategy> {
+    let descriptions: Vec<&str> = IdStrategy::ALL.iter().map(IdStrategy::description).collect();
+    let default = current
+        .and_then(|current| IdStrategy::ALL.iter().position(|strategy| *strategy == current))
+        .unwrap_or(0);
+    let selection = prompt::select("id_strategy", "How should tables generate their ids?", &descriptions, default)?;
+    Ok(IdStrategy::ALL[selection])
+}
+
+/// Fills in a table template written with `__ID__`, `__FOREIGN_ID__(name)` and a
+/// `__TIMESTAMPS__` line: with the db/columns.ts helpers when `helpers` is set, otherwise
+/// with the plain integer identity columns drizzle used to generate.
+pub fn render_columns(template: &str, helpers: bool) -> String {
+    if helpers {
+        template
+            .replace("__ID__", "id()")
+            .replace("__FOREIGN_ID__(", "foreignId(")
+            .replace("__TIMESTAMPS__", "...timestamps,")
+    } else {
+        template
+            .replace("__ID__", "integer(\"id\").primaryKey().generatedAlwaysAsIdentity()")
+            .replace("__FOREIGN_ID__(", "integer(")
+            .replace("__TIMESTAMPS__", &format!("{}\n  {}", LEGACY_CREATED_AT, LEGACY_UPDATED_AT))
+    }
+}
+
+/// Adds `names` to the schema's `drizzle-orm/pg-core` import, or adds the import.
+pub fn add_pg_core_imports(schema: &str, names: &[&str]) -> String {
+    let Some((import_line, mut imported)) = pg_core_import(schema) else {
+        return format!("import {{ {} }} from \"drizzle-orm/pg-core\";\n{}", names.join(", "), schema);
+    };
+    for name in names {
+        if !imported.contains(name) {
+            imported.push(name);
+        }
+    }
+    let updated = format!("import {{ {} }} from \"drizzle-orm/pg-core\";", imported.join(", "));
+    schema.replacen(import_line, &updated, 1)
+}
+
+/// The `drizzle-orm/pg-core` import line and the names it imports.
+fn pg_core_import(schema: &str) -> Option<(&str, Vec<&str>)> {
+    let import_line = schema
+        .lines()
+        .find(|line| line.starts_with("import {") && line.contains("\"drizzle-orm/pg-core\""))?;
+    let (imported, _) = import_line.split_once('{')?.1.split_once('}')?;
+    let names = imported.split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+    Some((import_line, names))
+}
+
+/// Imports the db/columns.ts helpers the schema uses and drops `drizzle-orm/pg-core`
+/// column builders it no longer calls.
+pub fn tidy_imports(schema: &str) -> String {
+    let mut schema = schema.to_string();
+
+    if let Some((import_line, imported)) = pg_core_import(&schema) {
+        let body = schema.replacen(import_line, "", 1);
+        let used: Vec<&str> = imported
+            .iter()
+            .copied()
+            .filter(|name| {
+                // Types are kept; column builders only while something still calls them
+                name.starts_with("type ") || name.starts_with(char::is_uppercase) || body.contains(&format!("{}(", name))
+            })
+            .collect();
+        if used.len() < imported.len() {
+            let updated = format!("import {{ {} }} from \"drizzle-orm/pg-core\";", used.join(", "));
+            schema = schema.replacen(import_line, &updated, 1);
+        }
+    }
+
+    let columns_import = schema
+        .lines()
+        .find(|line| line.starts_with("import {") && line.contains("\"./columns\""))
+        .map(str::to_string);
+    let mut helpers: Vec<&str> = columns_import
+        .as_deref()
+        .and_then(|line| line.split_once('{')?.1.split_once('}'))
+        .map(|(imported, _)| imported.split(',').map(str::trim).filter(|name| !name.is_empty()).collect())
+        .unwrap_or_default();
+    for (helper, usage) in HELPERS {
+        if !helpers.contains(&helper) && schema.contains(usage) {
+            helpers.push(helper);
+        }
+    }
+    if helpers.is_empty() {
+        return schema;
+    }
+    helpers.sort_unstable();
+    let updated = format!("import {{ {} }} from \"./columns\";", helpers.join(", "));
+
+    match columns_import {
+        Some(line) => schema.replacen(&line, &updated, 1),
+        None => {
+            // Right after the last import at the top of the file
+            let mut lines: Vec<&str> = schema.lines().collect();
+            let position = lines.iter().take_while(|line| line.starts_with("import ") || line.trim().is_empty()).count();
+            let position = lines[..position].iter().rposition(|line| line.starts_with("import ")).map_or(0, |last| last + 1);
+            lines.insert(position, &updated);
+            let trailing_newline = if schema.ends_with('\n') { "\n" } else { "" };
+            format!("{}{}", lines.join("\n"), trailing_newline)
+        }
+    }
+}
+
+/// Appends `tables` to the drizzle schema at `schema_path`, importing the `pg_core` builders
+/// they call and tidying the imports. `schema` is the current content, with any edits the
+/// caller made to existing tables. It is the project's own file, so the merge is backed up
+/// rather than offered as a replacement.
+pub fn append_tables(schema_path: &Path, schema: &str, tables: &[&str], pg_core: &[&str]) -> Result<()> {
+    let mut updated = schema.trim_end().to_string();
+    for table in tables {
+        updated = format!("{}\n{}", updated.trim_end(), table);
+    }
+    let updated = tidy_imports(&add_pg_core_imports(&updated, pg_core));
+
+    if let Some(parent) = schema_path.parent() {
+        change_set::create_dir_all(parent).context("Failed to create db directory")?;
+    }
+    change_set::write(schema_path, updated).context("Failed to update schema.ts")
+}
+
+/// Rewrites the identity ids, their foreign keys and the timestamp pairs drizzle used to
+/// generate to the db/columns.ts helpers.
+pub fn refactor_schema(schema: &str) -> String {
+    let lines: Vec<&str> = schema.lines().collect();
+    let mut refactored: Vec<String> = Vec::with_capacity(lines.len());
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let trimmed = line.trim();
+
+        if trimmed == LEGACY_CREATED_AT && lines.get(index + 1).is_some_and(|next| next.trim() == LEGACY_UPDATED_AT) {
+            refactored.push(format!("{}...timestamps,", indent));
+            index += 2;
+            continue;
+        }
+        if trimmed == LEGACY_ID_COLUMN {
+            refactored.push(format!("{}id: id(),", indent));
+        } else if trimmed.contains(": integer(\"") && trimmed.contains(".references(") {
+            refactored.push(line.replacen("integer(", "foreignId(", 1));
+        } else {
+            refactored.push(line.to_string());
+        }
+        index += 1;
+    }
+
+    let trailing_newline = if schema.ends_with('\n') { "\n" } else { "" };
+    tidy_imports(&format!("{}{}", refactored.join("\n"), trailing_newline))
+}
+
+pub async fn add_schema_conventions() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let db_path = project_structure.get_db_path();
+    let schema_path = db_path.join("schema.ts");
+    let schema = std::fs::read_to_string(&schema_path).unwrap_or_default();
+    if !schema.contains("drizzle-orm/pg-core") {
+        println!(
+            "{}",
+            style("Schema conventions are column helpers for a Postgres drizzle schema. Run `nstack add --feature drizzle` first.").yellow()
+        );
+        return Ok(());
+    }
+
+    let strategy = select_id_strategy(IdStrategy::installed(&db_path))?;
+
+    let pb = ProgressBar::new_spinner();
+    if !strategy.dependencies().is_empty() {
+        pb.set_message("Installing id dependencies...");
+        package_manager
+            .add(strategy.dependencies())
+            .run()
+            .context("Failed to install id dependencies")?;
+    }
+
+    pb.set_message("Creating column helpers...");
+
+    let columns_path = db_path.join("columns.ts");
+    file_writer::write(&columns_path, columns_ts(strategy)).context("Failed to create columns.ts")?;
+
+    pb.set_message("Refactoring the schema to use them...");
+
+    let refactored = refactor_schema(&schema);
+    let changed_ids = schema.contains(LEGACY_ID_COLUMN);
+    if refactored != schema {
+        // The project's own schema, so the rewrite is backed up rather than offered as a replacement
+        change_set::write(&schema_path, &refactored).context("Failed to update schema.ts")?;
+    }
+
+    pb.finish_with_message("Schema conventions added successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {} - id(), foreignId(), timestamps, softDelete and notDeleted()", columns_path.display());
+    if refactored != schema {
+        println!("• {} - Tables now use the column helpers", schema_path.display());
+    }
+
+    if changed_ids && strategy != IdStrategy::Serial {
+        println!(
+            "{}",
+            style(format!(
+                "Existing tables switch from integer to {} ids; migrate or reset any rows they already hold",
+                format!("{:?}", strategy).to_lowercase()
+            ))
+            .yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Build new tables with `id: id()`, `foreignId(\"..._id\")` and `...timestamps`");
+    println!("2. Spread `...softDelete` into tables that soft-delete and filter with `notDeleted(table)`");
+    println!("3. Run `db:generate` to create a migration for the changed columns");
+
+    Ok(())
+}
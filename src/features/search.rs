@@ -0,0 +1,705 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::docker_compose;
+use crate::env_file;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SearchProvider {
+    Postgres,
+    Meilisearch,
+    Typesense,
+    Algolia,
+}
+
+impl SearchProvider {
+    const ALL: [SearchProvider; 4] = [
+        SearchProvider::Postgres,
+        SearchProvider::Meilisearch,
+        SearchProvider::Typesense,
+        SearchProvider::Algolia,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SearchProvider::Postgres => "Postgres",
+            SearchProvider::Meilisearch => "Meilisearch",
+            SearchProvider::Typesense => "Typesense",
+            SearchProvider::Algolia => "Algolia",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            SearchProvider::Postgres => "Postgres full-text search through drizzle, no extra service",
+            SearchProvider::Meilisearch => "Meilisearch, open source and typo tolerant",
+            SearchProvider::Typesense => "Typesense, open source and typo tolerant",
+            SearchProvider::Algolia => "Algolia, hosted search",
+        }
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        match self {
+            SearchProvider::Postgres => &[],
+            SearchProvider::Meilisearch => &["meilisearch"],
+            SearchProvider::Typesense => &["typesense"],
+            SearchProvider::Algolia => &["algoliasearch"],
+        }
+    }
+
+    fn env_vars(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            SearchProvider::Postgres => &[],
+            SearchProvider::Meilisearch => &[
+                ("MEILISEARCH_HOST", "http://localhost:7700"),
+                ("MEILISEARCH_API_KEY", "change-me-to-a-long-master-key"),
+            ],
+            SearchProvider::Typesense => &[
+                ("TYPESENSE_HOST", "localhost"),
+                ("TYPESENSE_PORT", "8108"),
+                ("TYPESENSE_PROTOCOL", "http"),
+                ("TYPESENSE_API_KEY", "change-me"),
+            ],
+            SearchProvider::Algolia => &[("ALGOLIA_APP_ID", ""), ("ALGOLIA_ADMIN_API_KEY", "")],
+        }
+    }
+
+    /// The docker-compose service that runs the engine locally, as `(name, service, volume)`.
+    fn compose_service(&self) -> Option<(&'static str, &'static str, &'static str)> {
+        match self {
+            SearchProvider::Meilisearch => Some(("meilisearch", MEILISEARCH_SERVICE, "meilisearch-data")),
+            SearchProvider::Typesense => Some(("typesense", TYPESENSE_SERVICE, "typesense-data")),
+            SearchProvider::Postgres | SearchProvider::Algolia => None,
+        }
+    }
+
+    fn client_code(&self) -> &'static str {
+        match self {
+            SearchProvider::Postgres => POSTGRES_SEARCH,
+            SearchProvider::Meilisearch => MEILISEARCH_SEARCH,
+            SearchProvider::Typesense => TYPESENSE_SEARCH,
+            SearchProvider::Algolia => ALGOLIA_SEARCH,
+        }
+    }
+}
+
+const MEILISEARCH_SERVICE: &str = r#"  meilisearch:
+    image: getmeili/meilisearch:v1.12
+    ports:
+      - "7700:7700"
+    environment:
+      MEILI_MASTER_KEY: ${MEILISEARCH_API_KEY}
+    volumes:
+      - meilisearch-data:/meili_data
+    restart: unless-stopped
+"#;
+
+const TYPESENSE_SERVICE: &str = r#"  typesense:
+    image: typesense/typesense:27.1
+    ports:
+      - "8108:8108"
+    command: --data-dir /data --api-key=${TYPESENSE_API_KEY} --enable-cors
+    volumes:
+      - typesense-data:/data
+    restart: unless-stopped
+"#;
+
+const TYPES: &str = r#"/** A record in the search index. */
+export type SearchDocument = {
+  id: string;
+  title: string;
+  content: string;
+  url: string;
+};
+
+/** A search result; matches in `snippet` are wrapped in <mark></mark>. */
+export type SearchHit = {
+  id: string;
+  title: string;
+  snippet: string;
+  url: string;
+};
+
+export const SEARCH_INDEX = "posts";
+"#;
+
+/// Documents from the drizzle posts table, for the hosted engines.
+const POSTS_DOCUMENTS: &str = r#"import { db } from "@/db";
+import { postsTable } from "@/db/schema";
+__NOT_DELETED_IMPORT__import type { SearchDocument } from "@/lib/search/types";
+
+export function toSearchDocument(post: typeof postsTable.$inferSelect): SearchDocument {
+  return {
+    id: String(post.id),
+    title: post.title,
+    content: post.content,
+    url: `/posts/${post.id}`,
+  };
+}
+
+/** Everything `search:index` sends to the engine. */
+export async function loadDocuments(): Promise<SearchDocument[]> {
+  const posts = await db.select().from(postsTable)__NOT_DELETED_WHERE__;
+  return posts.map(toSearchDocument);
+}
+"#;
+
+/// Documents for projects without a posts table to index yet.
+const SAMPLE_DOCUMENTS: &str = r#"import type { SearchDocument } from "@/lib/search/types";
+
+// Sample documents until there is real content to index; load it from your database here
+const SAMPLE_DOCUMENTS: SearchDocument[] = [
+  { id: "1", title: "Getting started", content: "Install the app, sign in and create your first project.", url: "/" },
+  { id: "2", title: "Inviting your team", content: "Share a project with teammates by email and manage their roles.", url: "/" },
+  { id: "3", title: "Billing and plans", content: "Upgrade, downgrade or cancel your subscription at any time.", url: "/" },
+];
+
+/** Everything `search:index` sends to the engine. */
+export async function loadDocuments(): Promise<SearchDocument[]> {
+  return SAMPLE_DOCUMENTS;
+}
+"#;
+
+const POSTGRES_SEARCH: &str = r#"import { and, desc, sql } from "drizzle-orm";
+import { db } from "@/db";
+import { postsTable } from "@/db/schema";
+__NOT_DELETED_IMPORT__import type { SearchHit } from "@/lib/search/types";
+
+// Keep in sync with the posts_search_idx expression created by `search:index`
+const document = sql`to_tsvector('english', ${postsTable.title} || ' ' || ${postsTable.content})`;
+
+export async function searchDocuments(query: string, limit = 10): Promise<SearchHit[]> {
+  const tsquery = sql`websearch_to_tsquery('english', ${query})`;
+  const rows = await db
+    .select({
+      id: postsTable.id,
+      title: postsTable.title,
+      snippet: sql<string>`ts_headline('english', ${postsTable.content}, ${tsquery}, 'StartSel=<mark>, StopSel=</mark>, MaxWords=20, MinWords=8')`,
+    })
+    .from(postsTable)
+    .where(and(sql`${document} @@ ${tsquery}`__NOT_DELETED_CONDITION__))
+    .orderBy(desc(sql`ts_rank(${document}, ${tsquery})`))
+    .limit(limit);
+
+  return rows.map((row) => ({ ...row, id: String(row.id), url: `/posts/${row.id}` }));
+}
+"#;
+
+const MEILISEARCH_SEARCH: &str = r#"import { Meilisearch } from "meilisearch";
+import { SEARCH_INDEX, type SearchDocument, type SearchHit } from "@/lib/search/types";
+
+const client = new Meilisearch({
+  host: process.env.MEILISEARCH_HOST!,
+  apiKey: process.env.MEILISEARCH_API_KEY,
+});
+const index = client.index<SearchDocument>(SEARCH_INDEX);
+
+/** The index definition: which fields are searched and returned. */
+export async function configureIndex() {
+  await index.updateSettings({
+    searchableAttributes: ["title", "content"],
+    displayedAttributes: ["id", "title", "content", "url"],
+  });
+}
+
+export async function indexDocuments(documents: SearchDocument[]) {
+  await index.addDocuments(documents, { primaryKey: "id" });
+}
+
+/** Call after a document is created or updated so search stays current. */
+export async function syncDocument(document: SearchDocument) {
+  await indexDocuments([document]);
+}
+
+/** Call after a document is deleted. */
+export async function removeDocument(id: string) {
+  await index.deleteDocument(id);
+}
+
+export async function searchDocuments(query: string, limit = 10): Promise<SearchHit[]> {
+  const { hits } = await index.search(query, {
+    limit,
+    attributesToCrop: ["content"],
+    cropLength: 20,
+    attributesToHighlight: ["content"],
+    highlightPreTag: "<mark>",
+    highlightPostTag: "</mark>",
+  });
+  return hits.map((hit) => ({
+    id: hit.id,
+    title: hit.title,
+    snippet: hit._formatted?.content ?? hit.content,
+    url: hit.url,
+  }));
+}
+"#;
+
+const TYPESENSE_SEARCH: &str = r#"import Typesense from "typesense";
+import { SEARCH_INDEX, type SearchDocument, type SearchHit } from "@/lib/search/types";
+
+const client = new Typesense.Client({
+  nodes: [
+    {
+      host: process.env.TYPESENSE_HOST!,
+      port: Number(process.env.TYPESENSE_PORT ?? 8108),
+      protocol: process.env.TYPESENSE_PROTOCOL ?? "http",
+    },
+  ],
+  apiKey: process.env.TYPESENSE_API_KEY!,
+  connectionTimeoutSeconds: 5,
+});
+
+/** The index definition: the collection schema, created on the first `search:index`. */
+export async function configureIndex() {
+  if (await client.collections(SEARCH_INDEX).exists()) return;
+  await client.collections().create({
+    name: SEARCH_INDEX,
+    fields: [
+      { name: "title", type: "string" },
+      { name: "content", type: "string" },
+      { name: "url", type: "string", index: false, optional: true },
+    ],
+  });
+}
+
+export async function indexDocuments(documents: SearchDocument[]) {
+  await client.collections<SearchDocument>(SEARCH_INDEX).documents().import(documents, { action: "upsert" });
+}
+
+/** Call after a document is created or updated so search stays current. */
+export async function syncDocument(document: SearchDocument) {
+  await client.collections<SearchDocument>(SEARCH_INDEX).documents().upsert(document);
+}
+
+/** Call after a document is deleted. */
+export async function removeDocument(id: string) {
+  await client.collections(SEARCH_INDEX).documents(id).delete();
+}
+
+export async function searchDocuments(query: string, limit = 10): Promise<SearchHit[]> {
+  const result = await client.collections<SearchDocument>(SEARCH_INDEX).documents().search({
+    q: query,
+    query_by: "title,content",
+    per_page: limit,
+    highlight_fields: "content",
+  });
+  return (result.hits ?? []).map((hit) => ({
+    id: hit.document.id,
+    title: hit.document.title,
+    snippet: hit.highlight?.content?.snippet ?? hit.document.content,
+    url: hit.document.url,
+  }));
+}
+"#;
+
+const ALGOLIA_SEARCH: &str = r#"import { algoliasearch } from "algoliasearch";
+import { SEARCH_INDEX, type SearchDocument, type SearchHit } from "@/lib/search/types";
+
+const client = algoliasearch(process.env.ALGOLIA_APP_ID!, process.env.ALGOLIA_ADMIN_API_KEY!);
+
+/** The index definition: which fields are searched and snippeted. */
+export async function configureIndex() {
+  await client.setSettings({
+    indexName: SEARCH_INDEX,
+    indexSettings: {
+      searchableAttributes: ["title", "content"],
+      attributesToSnippet: ["content:20"],
+      highlightPreTag: "<mark>",
+      highlightPostTag: "</mark>",
+    },
+  });
+}
+
+export async function indexDocuments(documents: SearchDocument[]) {
+  await client.saveObjects({
+    indexName: SEARCH_INDEX,
+    objects: documents.map((document) => ({ objectID: document.id, ...document })),
+  });
+}
+
+/** Call after a document is created or updated so search stays current. */
+export async function syncDocument(document: SearchDocument) {
+  await indexDocuments([document]);
+}
+
+/** Call after a document is deleted. */
+export async function removeDocument(id: string) {
+  await client.deleteObject({ indexName: SEARCH_INDEX, objectID: id });
+}
+
+export async function searchDocuments(query: string, limit = 10): Promise<SearchHit[]> {
+  const { hits } = await client.searchSingleIndex<SearchDocument>({
+    indexName: SEARCH_INDEX,
+    searchParams: { query, hitsPerPage: limit },
+  });
+  return hits.map((hit) => ({
+    id: hit.objectID,
+    title: hit.title,
+    snippet: (hit._snippetResult?.content as { value?: string } | undefined)?.value ?? hit.content,
+    url: hit.url,
+  }));
+}
+"#;
+
+const POSTGRES_INDEX_SCRIPT: &str = r#"import { sql } from "drizzle-orm";
+import { db } from "@/db";
+
+// Postgres keeps the index current on every write; this only has to create it once
+async function main() {
+  await db.execute(
+    sql`CREATE INDEX IF NOT EXISTS posts_search_idx ON posts USING gin (to_tsvector('english', title || ' ' || content))`,
+  );
+  console.log("Created the posts_search_idx full-text index");
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
+"#;
+
+const ENGINE_INDEX_SCRIPT: &str = r#"import { loadDocuments } from "@/lib/search/documents";
+import { configureIndex, indexDocuments } from "@/lib/search";
+
+// Rebuilds the index from scratch; syncDocument() keeps it current afterwards
+async function main() {
+  await configureIndex();
+  const documents = await loadDocuments();
+  await indexDocuments(documents);
+  console.log(`Indexed ${documents.length} documents`);
+}
+
+main()
+  .then(() => process.exit(0))
+  .catch((error) => {
+    console.error(error);
+    process.exit(1);
+  });
+"#;
+
+const SEARCH_ROUTE: &str = r#"import { type NextRequest, NextResponse } from "next/server";
+import { searchDocuments } from "@/lib/search";
+
+export async function GET(request: NextRequest) {
+  const query = request.nextUrl.searchParams.get("q")?.trim() ?? "";
+  if (query.length < 2) {
+    return NextResponse.json({ hits: [] });
+  }
+
+  try {
+    const hits = await searchDocuments(query);
+    return NextResponse.json({ hits });
+  } catch (error) {
+    console.error(error);
+    return NextResponse.json({ error: "Search failed" }, { status: 500 });
+  }
+}
+"#;
+
+const SEARCH_BOX: &str = r#""use client";
+
+import { useRouter } from "next/navigation";
+import { useEffect, useId, useState, type KeyboardEvent } from "react";
+import type { SearchHit } from "@/lib/search/types";
+
+const DEBOUNCE_MS = 250;
+
+/** Renders a snippet's <mark> matches as highlights, and everything else as text. */
+function Snippet({ value }: { value: string }) {
+  const parts = value.split(/<mark>(.*?)<\/mark>/g);
+  return (
+    <>
+      {parts.map((part, index) =>
+        index % 2 === 1 ? (
+          <mark key={index} className="bg-yellow-200 text-inherit">
+            {part}
+          </mark>
+        ) : (
+          part
+        ),
+      )}
+    </>
+  );
+}
+
+export function SearchBox({ placeholder = "Search..." }: { placeholder?: string }) {
+  const router = useRouter();
+  const listId = useId();
+  const [query, setQuery] = useState("");
+  const [hits, setHits] = useState<SearchHit[]>([]);
+  const [active, setActive] = useState(-1);
+  const [open, setOpen] = useState(false);
+  const [loading, setLoading] = useState(false);
+
+  useEffect(() => {
+    if (query.trim().length < 2) {
+      setHits([]);
+      return;
+    }
+
+    // Only search once typing pauses, and drop responses for older queries
+    const controller = new AbortController();
+    const timeout = setTimeout(async () => {
+      setLoading(true);
+      try {
+        const response = await fetch(`/api/search?q=${encodeURIComponent(query)}`, { signal: controller.signal });
+        const { hits } = (await response.json()) as { hits?: SearchHit[] };
+        setHits(hits ?? []);
+        setActive(-1);
+        setOpen(true);
+      } catch (error) {
+        if ((error as Error).name !== "AbortError") setHits([]);
+      } finally {
+        setLoading(false);
+      }
+    }, DEBOUNCE_MS);
+
+    return () => {
+      clearTimeout(timeout);
+      controller.abort();
+    };
+  }, [query]);
+
+  function select(hit: SearchHit) {
+    setOpen(false);
+    router.push(hit.url);
+  }
+
+  function onKeyDown(event: KeyboardEvent<HTMLInputElement>) {
+    if (event.key === "ArrowDown") {
+      event.preventDefault();
+      setOpen(true);
+      setActive((index) => Math.min(index + 1, hits.length - 1));
+    } else if (event.key === "ArrowUp") {
+      event.preventDefault();
+      setActive((index) => Math.max(index - 1, 0));
+    } else if (event.key === "Enter" && active >= 0 && hits[active]) {
+      event.preventDefault();
+      select(hits[active]);
+    } else if (event.key === "Escape") {
+      setOpen(false);
+    }
+  }
+
+  const showResults = open && query.trim().length >= 2;
+
+  return (
+    <div className="relative w-full max-w-md">
+      <input
+        type="search"
+        role="combobox"
+        aria-expanded={showResults}
+        aria-controls={listId}
+        aria-activedescendant={active >= 0 ? `${listId}-${active}` : undefined}
+        aria-autocomplete="list"
+        value={query}
+        placeholder={placeholder}
+        onChange={(event) => setQuery(event.target.value)}
+        onKeyDown={onKeyDown}
+        onFocus={() => setOpen(true)}
+        onBlur={() => setOpen(false)}
+        className="w-full rounded-md border px-3 py-2"
+      />
+      {showResults && (
+        <ul id={listId} role="listbox" className="absolute z-50 mt-1 w-full overflow-hidden rounded-md border bg-white shadow-lg dark:bg-neutral-900">
+          {hits.length === 0 ? (
+            <li className="px-3 py-2 text-sm text-gray-500">{loading ? "Searching..." : "No results"}</li>
+          ) : (
+            hits.map((hit, index) => (
+              <li
+                key={hit.id}
+                id={`${listId}-${index}`}
+                role="option"
+                aria-selected={index === active}
+                // Select before the input's blur closes the list
+                onMouseDown={(event) => {
+                  event.preventDefault();
+                  select(hit);
+                }}
+                onMouseEnter={() => setActive(index)}
+                className={`cursor-pointer px-3 py-2 ${index === active ? "bg-gray-100 dark:bg-neutral-800" : ""}`}
+              >
+                <div className="font-medium">{hit.title}</div>
+                <div className="line-clamp-2 text-sm text-gray-500">
+                  <Snippet value={hit.snippet} />
+                </div>
+              </li>
+            ))
+          )}
+        </ul>
+      )}
+    </div>
+  );
+}
+"#;
+
+/// The posts table in the drizzle schema, and whether it soft-deletes through the schema
+/// conventions' `softDelete` columns, when there is one.
+fn posts_table(project_structure: &ProjectStructure) -> Option<bool> {
+    let schema = std::fs::read_to_string(project_structure.get_db_path().join("schema.ts")).ok()?;
+    if !schema.contains("drizzle-orm/pg-core") {
+        return None;
+    }
+    let start = schema.find("export const postsTable")?;
+    let table = &schema[start..];
+    let table = &table[..table.find("});").unwrap_or(table.len())];
+    Some(table.contains("...softDelete"))
+}
+
+/// Fills in the soft-delete filter of a posts query template.
+fn filter_deleted(template: &str, soft_delete: bool) -> String {
+    if soft_delete {
+        template
+            .replace("__NOT_DELETED_IMPORT__", "import { notDeleted } from \"@/db/columns\";\n")
+            .replace("__NOT_DELETED_WHERE__", ".where(notDeleted(postsTable))")
+            .replace("__NOT_DELETED_CONDITION__", ", notDeleted(postsTable)")
+    } else {
+        template
+            .replace("__NOT_DELETED_IMPORT__", "")
+            .replace("__NOT_DELETED_WHERE__", "")
+            .replace("__NOT_DELETED_CONDITION__", "")
+    }
+}
+
+pub async fn add_search() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let descriptions: Vec<String> = SearchProvider::ALL
+        .iter()
+        .map(|provider| format!("{} - {}", provider.as_str(), provider.description()))
+        .collect();
+    let selection = prompt::select("provider", "Select a search provider", &descriptions, 0)?;
+    let provider = SearchProvider::ALL[selection];
+
+    let posts = posts_table(&project_structure);
+    if provider == SearchProvider::Postgres && posts.is_none() {
+        println!(
+            "{}",
+            style("Postgres full-text search runs on the drizzle posts table. Run `nstack add --feature drizzle` first, or pick a search engine.").yellow()
+        );
+        return Ok(());
+    }
+    let soft_delete = posts.unwrap_or(false);
+
+    let add_compose_service = match provider.compose_service() {
+        Some(_) => prompt::confirm(
+            "compose",
+            &format!("Add a {} service to docker-compose.yml for local development?", provider.as_str()),
+            true,
+        )?,
+        None => false,
+    };
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message(format!("Installing {} dependencies...", provider.as_str()));
+
+    if !provider.dependencies().is_empty() {
+        package_manager.install(provider.dependencies(), false)?;
+    }
+    package_manager.install(&["tsx"], true)?;
+
+    pb.set_message("Creating the search client...");
+
+    let search_lib_path = project_structure.get_lib_path().join("search");
+    change_set::create_dir_all(&search_lib_path).context("Failed to create lib/search directory")?;
+    file_writer::write(search_lib_path.join("types.ts"), TYPES).context("Failed to create types.ts")?;
+    file_writer::write(search_lib_path.join("index.ts"), filter_deleted(provider.client_code(), soft_delete))
+        .context("Failed to create the search client")?;
+    if provider != SearchProvider::Postgres {
+        let documents = if posts.is_some() { filter_deleted(POSTS_DOCUMENTS, soft_delete) } else { SAMPLE_DOCUMENTS.to_string() };
+        file_writer::write(search_lib_path.join("documents.ts"), documents)
+            .context("Failed to create documents.ts")?;
+    }
+
+    pb.set_message("Creating the indexing script...");
+
+    change_set::create_dir_all(project_file("scripts")).context("Failed to create scripts directory")?;
+    let index_script = match provider {
+        SearchProvider::Postgres => POSTGRES_INDEX_SCRIPT,
+        _ => ENGINE_INDEX_SCRIPT,
+    };
+    file_writer::write(project_file("scripts/search-index.ts"), index_script).context("Failed to create search-index.ts")?;
+    package_json::add_scripts(&[("search:index", "tsx --env-file=.env scripts/search-index.ts")])?;
+
+    pb.set_message("Creating the search route and component...");
+
+    let route_path = project_structure.get_api_path().join("search");
+    change_set::create_dir_all(&route_path).context("Failed to create api/search directory")?;
+    file_writer::write(route_path.join("route.ts"), SEARCH_ROUTE).context("Failed to create the search route")?;
+
+    let components_path = project_structure.get_components_path().join("search");
+    change_set::create_dir_all(&components_path).context("Failed to create components/search directory")?;
+    file_writer::write(components_path.join("search-box.tsx"), SEARCH_BOX)
+        .context("Failed to create search-box.tsx")?;
+
+    let compose_updated = match provider.compose_service().filter(|_| add_compose_service) {
+        Some((name, service, volume)) => {
+            pb.set_message(format!("Adding {} to docker-compose.yml...", provider.as_str()));
+            docker_compose::add_service(name, service, Some(volume))?
+        }
+        None => false,
+    };
+
+    if !provider.env_vars().is_empty() {
+        env_file::add_env_vars("search", "Search", provider.env_vars())?;
+    }
+
+    pb.finish_with_message(format!("{} search added successfully!", provider.as_str()));
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    match provider {
+        SearchProvider::Postgres => println!("• {}/ - Ranked full-text query and shared types", search_lib_path.display()),
+        _ => println!("• {}/ - {} client, index definition and shared types", search_lib_path.display(), provider.as_str()),
+    }
+    if provider != SearchProvider::Postgres {
+        println!(
+            "• {}/documents.ts - Documents to index ({})",
+            search_lib_path.display(),
+            if posts.is_some() { "drizzle posts" } else { "sample data" }
+        );
+    }
+    match provider {
+        SearchProvider::Postgres => println!("• scripts/search-index.ts - `search:index` script creating the GIN index"),
+        _ => println!("• scripts/search-index.ts - `search:index` script"),
+    }
+    println!("• {}/route.ts - GET /api/search?q=", route_path.display());
+    println!("• {}/search-box.tsx - Debounced search box with keyboard navigation", components_path.display());
+    if compose_updated {
+        println!("• docker-compose.yml - {} service", provider.as_str());
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    let mut step = 1;
+    if let Some((name, _, _)) = provider.compose_service().filter(|_| add_compose_service) {
+        println!("{}. Start {}: docker compose up -d {}", step, provider.as_str(), name);
+        step += 1;
+    } else if provider == SearchProvider::Algolia {
+        println!("{}. Set ALGOLIA_APP_ID and ALGOLIA_ADMIN_API_KEY in .env", step);
+        step += 1;
+    }
+    match provider {
+        SearchProvider::Postgres => println!("{}. Run `search:index` once to create the full-text index", step),
+        _ => println!("{}. Run `search:index` to index your documents, and call syncDocument() after writes", step),
+    }
+    println!("{}. Render <SearchBox /> from {}, e.g. in your header", step + 1, components_path.display());
+
+    Ok(())
+}
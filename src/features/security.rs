@@ -0,0 +1,440 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::compat::NextCapabilities;
+use crate::file_writer;
+use crate::next_config;
+use crate::package_json;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HeaderMode {
+    Middleware,
+    NextConfig,
+}
+
+impl HeaderMode {
+    const ALL: [HeaderMode; 2] = [HeaderMode::Middleware, HeaderMode::NextConfig];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            HeaderMode::Middleware => "Middleware",
+            HeaderMode::NextConfig => "next.config",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            HeaderMode::Middleware => "nonce-based CSP per request; pages render dynamically",
+            HeaderMode::NextConfig => "static headers() with 'unsafe-inline' scripts; keeps static rendering",
+        }
+    }
+}
+
+/// CSP directives in the order the header lists them, with the sources every app needs.
+const BASE_DIRECTIVES: &[(&str, &[&str])] = &[
+    ("default-src", &["'self'"]),
+    ("script-src", &["'self'"]),
+    // next/font, styled-jsx and animation libraries set inline styles
+    ("style-src", &["'self'", "'unsafe-inline'"]),
+    ("img-src", &["'self'", "blob:", "data:"]),
+    ("font-src", &["'self'"]),
+    ("connect-src", &["'self'"]),
+    ("frame-src", &["'self'"]),
+    ("worker-src", &["'self'"]),
+    ("object-src", &["'none'"]),
+    ("base-uri", &["'self'"]),
+    ("form-action", &["'self'"]),
+    ("frame-ancestors", &["'none'"]),
+    ("upgrade-insecure-requests", &[]),
+];
+
+/// Headers sent with every response besides the CSP.
+const SECURITY_HEADERS: &[(&str, &str)] = &[
+    ("Strict-Transport-Security", "max-age=63072000; includeSubDomains; preload"),
+    ("X-Frame-Options", "DENY"),
+    ("X-Content-Type-Options", "nosniff"),
+    ("Referrer-Policy", "strict-origin-when-cross-origin"),
+    ("Permissions-Policy", "camera=(), microphone=(), geolocation=(), browsing-topics=()"),
+];
+
+/// Sources the browser code of a package loads from, as `(package, service, directives)`.
+type PackageSources = (&'static str, &'static str, &'static [(&'static str, &'static str)]);
+
+const PACKAGE_SOURCES: &[PackageSources] = &[
+    (
+        "posthog-js",
+        "PostHog",
+        &[
+            ("script-src", "https://us-assets.i.posthog.com https://eu-assets.i.posthog.com"),
+            (
+                "connect-src",
+                "https://us.i.posthog.com https://eu.i.posthog.com https://us-assets.i.posthog.com https://eu-assets.i.posthog.com",
+            ),
+        ],
+    ),
+    (
+        "@sentry/nextjs",
+        "Sentry",
+        &[
+            ("connect-src", "https://*.ingest.sentry.io https://*.ingest.us.sentry.io https://*.ingest.de.sentry.io"),
+            ("worker-src", "blob:"),
+        ],
+    ),
+    (
+        "@stripe/stripe-js",
+        "Stripe.js",
+        &[
+            ("script-src", "https://js.stripe.com"),
+            ("connect-src", "https://api.stripe.com"),
+            ("frame-src", "https://js.stripe.com https://hooks.stripe.com"),
+        ],
+    ),
+    ("next-plausible", "Plausible", &[("script-src", "https://plausible.io"), ("connect-src", "https://plausible.io")]),
+    ("@vercel/analytics", "Vercel Analytics", &[("script-src", "https://va.vercel-scripts.com")]),
+    ("@vercel/speed-insights", "Vercel Speed Insights", &[("script-src", "https://va.vercel-scripts.com")]),
+    (
+        "@clerk/nextjs",
+        "Clerk",
+        &[
+            ("script-src", "https://*.clerk.accounts.dev https://challenges.cloudflare.com"),
+            ("connect-src", "https://*.clerk.accounts.dev"),
+            ("frame-src", "https://challenges.cloudflare.com"),
+            ("img-src", "https://img.clerk.com"),
+            ("worker-src", "blob:"),
+        ],
+    ),
+    (
+        "@supabase/supabase-js",
+        "Supabase",
+        &[("connect-src", "https://*.supabase.co wss://*.supabase.co"), ("img-src", "https://*.supabase.co")],
+    ),
+    (
+        "firebase",
+        "Firebase",
+        &[
+            ("script-src", "https://apis.google.com"),
+            ("connect-src", "https://*.googleapis.com https://*.firebaseio.com wss://*.firebaseio.com"),
+            ("frame-src", "https://*.firebaseapp.com"),
+        ],
+    ),
+    ("convex", "Convex", &[("connect-src", "https://*.convex.cloud wss://*.convex.cloud")]),
+    ("@liveblocks/client", "Liveblocks", &[("connect-src", "https://api.liveblocks.io wss://api.liveblocks.io")]),
+    ("partysocket", "PartyKit", &[("connect-src", "https://*.partykit.dev wss://*.partykit.dev")]),
+    ("@novu/nextjs", "Novu", &[("connect-src", "https://api.novu.co wss://ws.novu.co")]),
+    ("@knocklabs/react", "Knock", &[("connect-src", "https://api.knock.app wss://api.knock.app")]),
+    ("maplibre-gl", "MapLibre", &[("connect-src", "https://tiles.openfreemap.org"), ("worker-src", "blob:")]),
+    (
+        "mapbox-gl",
+        "Mapbox GL",
+        &[("connect-src", "https://api.mapbox.com https://events.mapbox.com"), ("worker-src", "blob:")],
+    ),
+    ("leaflet", "Leaflet", &[("img-src", "https://tile.openstreetmap.org")]),
+    (
+        "@vis.gl/react-google-maps",
+        "Google Maps",
+        &[
+            ("script-src", "https://maps.googleapis.com"),
+            ("style-src", "https://fonts.googleapis.com"),
+            ("img-src", "https://maps.gstatic.com https://maps.googleapis.com"),
+            ("font-src", "https://fonts.gstatic.com"),
+            ("connect-src", "https://maps.googleapis.com"),
+        ],
+    ),
+    (
+        "@calcom/embed-react",
+        "Cal.com",
+        &[
+            ("script-src", "https://app.cal.com"),
+            ("connect-src", "https://app.cal.com"),
+            ("frame-src", "https://app.cal.com"),
+        ],
+    ),
+    (
+        "uploadthing",
+        "UploadThing",
+        &[("connect-src", "https://*.ingest.uploadthing.com https://utfs.io"), ("img-src", "https://utfs.io")],
+    ),
+    ("algoliasearch", "Algolia", &[("connect-src", "https://*.algolia.net https://*.algolianet.com")]),
+];
+
+/// The base directives plus the sources of every installed package in `PACKAGE_SOURCES`,
+/// and the services they belong to.
+fn csp_directives() -> (Vec<(&'static str, Vec<&'static str>)>, Vec<&'static str>) {
+    let mut directives: Vec<(&str, Vec<&str>)> =
+        BASE_DIRECTIVES.iter().map(|(name, sources)| (*name, sources.to_vec())).collect();
+    let mut services = Vec::new();
+
+    for (package, service, additions) in PACKAGE_SOURCES {
+        if !package_json::has_dependency(package) {
+            continue;
+        }
+        services.push(*service);
+        for (directive, sources) in *additions {
+            let Some((_, existing)) = directives.iter_mut().find(|(name, _)| name == directive) else {
+                continue;
+            };
+            for source in sources.split_whitespace() {
+                if !existing.contains(&source) {
+                    existing.push(source);
+                }
+            }
+        }
+    }
+
+    (directives, services)
+}
+
+/// The directives as a TypeScript object literal, one directive per line.
+fn directives_ts(directives: &[(&str, Vec<&str>)]) -> String {
+    directives
+        .iter()
+        .map(|(name, sources)| {
+            let sources: Vec<String> = sources.iter().map(|source| format!("\"{}\"", source)).collect();
+            format!("  \"{}\": [{}],\n", name, sources.join(", "))
+        })
+        .collect()
+}
+
+/// The static `headers()` of next.config: scripts need 'unsafe-inline' without nonces, and
+/// development adds 'unsafe-eval' for React and a websocket for hot reloading, and drops the
+/// https upgrade localhost cannot serve.
+fn next_config_headers(directives: &[(&str, Vec<&str>)]) -> String {
+    let policy: Vec<String> = directives
+        .iter()
+        .map(|(name, sources)| match *name {
+            "script-src" => format!(
+                "            `script-src {} 'unsafe-inline'${{process.env.NODE_ENV === \"development\" ? \" 'unsafe-eval'\" : \"\"}}`,",
+                sources.join(" ")
+            ),
+            "connect-src" => format!(
+                "            `connect-src {}${{process.env.NODE_ENV === \"development\" ? \" ws:\" : \"\"}}`,",
+                sources.join(" ")
+            ),
+            "upgrade-insecure-requests" => {
+                "            ...(process.env.NODE_ENV === \"development\" ? [] : [\"upgrade-insecure-requests\"]),".to_string()
+            }
+            _ => format!("            \"{}\",", [*name].iter().chain(sources).copied().collect::<Vec<_>>().join(" ")),
+        })
+        .collect();
+
+    let headers: Vec<String> = SECURITY_HEADERS
+        .iter()
+        .map(|(key, value)| format!("        {{ key: \"{}\", value: \"{}\" }},", key, value))
+        .collect();
+
+    format!(
+        "async () => [\n    {{\n      source: \"/(.*)\",\n      headers: [\n        {{\n          key: \"Content-Security-Policy\",\n          value: [\n{}\n          ].join(\"; \"),\n        }},\n{}\n      ],\n    }},\n  ]",
+        policy.join("\n"),
+        headers.join("\n")
+    )
+}
+
+const CSP: &str = r#"/**
+ * Allowed sources per CSP directive.__DETECTED__ Add the origins your own
+ * scripts, images and API calls use.
+ */
+export const CSP_DIRECTIVES: Record<string, string[]> = {
+__DIRECTIVES__};
+
+/** Headers sent with every response besides the CSP. */
+export const SECURITY_HEADERS: Record<string, string> = {
+__HEADERS__};
+
+const isDev = process.env.NODE_ENV === "development";
+
+/**
+ * The Content-Security-Policy for one request. Scripts need the nonce, and 'strict-dynamic'
+ * lets the scripts they load run too.
+ */
+export function contentSecurityPolicy(nonce: string) {
+  const directives = Object.fromEntries(Object.entries(CSP_DIRECTIVES).map(([name, sources]) => [name, [...sources]]));
+  directives["script-src"].push(`'nonce-${nonce}'`, "'strict-dynamic'");
+
+  if (isDev) {
+    // React uses eval for debugging, hot reloading needs a websocket and localhost has no https
+    directives["script-src"].push("'unsafe-eval'");
+    directives["connect-src"].push("ws:");
+    delete directives["upgrade-insecure-requests"];
+  }
+
+  return Object.entries(directives)
+    .map(([name, sources]) => [name, ...sources].join(" "))
+    .join("; ");
+}
+"#;
+
+const SECURITY_MIDDLEWARE: &str = r#"import { NextResponse, type NextRequest } from "next/server";
+import { contentSecurityPolicy, SECURITY_HEADERS } from "@/lib/security/csp";
+
+/**
+ * Gives the request a fresh nonce, which Next.js adds to its own scripts when it finds the
+ * CSP on the request, and sets the security headers on the response.
+ */
+export function securityMiddleware(request: NextRequest) {
+  const nonce = btoa(crypto.randomUUID());
+  const policy = contentSecurityPolicy(nonce);
+
+  const requestHeaders = new Headers(request.headers);
+  requestHeaders.set("x-nonce", nonce);
+  requestHeaders.set("Content-Security-Policy", policy);
+
+  const response = NextResponse.next({ request: { headers: requestHeaders } });
+  response.headers.set("Content-Security-Policy", policy);
+  for (const [name, value] of Object.entries(SECURITY_HEADERS)) {
+    response.headers.set(name, value);
+  }
+  return response;
+}
+"#;
+
+const MIDDLEWARE: &str = r#"import type { NextRequest } from "next/server";
+import { securityMiddleware } from "@/lib/security/middleware";
+
+export function middleware(request: NextRequest) {
+  return securityMiddleware(request);
+}
+
+export const config = {
+  matcher: [
+    {
+      // Static files and prefetches never render scripts
+      source: "/((?!api|_next/static|_next/image|favicon.ico).*)",
+      missing: [
+        { type: "header", key: "next-router-prefetch" },
+        { type: "header", key: "purpose", value: "prefetch" },
+      ],
+    },
+  ],
+};
+"#;
+
+const NONCE: &str = r#"import { headers } from "next/headers";
+
+/** The CSP nonce of the current request, for `<Script nonce={...}>` and inline scripts. */
+export async function getNonce() {
+  return (await headers()).get("x-nonce") ?? undefined;
+}
+"#;
+
+pub async fn add_security() -> Result<()> {
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    // Next.js only puts nonces on its scripts in the App Router, and a 13.x app directory
+    // may still be the experimental one
+    let app_router = NextCapabilities::detect()
+        .map_or(project_structure.is_app_router(), |capabilities| capabilities.app_router);
+    let mode = if app_router {
+        let items: Vec<String> = HeaderMode::ALL
+            .iter()
+            .map(|mode| format!("{} - {}", mode.as_str(), mode.description()))
+            .collect();
+        HeaderMode::ALL[prompt::select("mode", "Where should the security headers be set?", &items, 0)?]
+    } else {
+        println!(
+            "{}",
+            style("Nonce-based CSP needs the App Router; setting static headers in next.config instead").yellow()
+        );
+        HeaderMode::NextConfig
+    };
+
+    let (directives, services) = csp_directives();
+
+    let pb = ProgressBar::new_spinner();
+    let lib_path = project_structure.get_lib_path().join("security");
+    let middleware_path = project_structure.get_middleware_path();
+    let mut middleware_created = false;
+    let mut headers_added = false;
+
+    match mode {
+        HeaderMode::Middleware => {
+            pb.set_message("Creating the CSP and security middleware...");
+
+            let detected = if services.is_empty() {
+                String::new()
+            } else {
+                format!(" Includes the sources {} loads from.", services.join(", "))
+            };
+            let headers: String = SECURITY_HEADERS
+                .iter()
+                .map(|(key, value)| format!("  \"{}\": \"{}\",\n", key, value))
+                .collect();
+            let csp = CSP
+                .replace("__DETECTED__", &detected)
+                .replace("__DIRECTIVES__", &directives_ts(&directives))
+                .replace("__HEADERS__", &headers);
+
+            change_set::create_dir_all(&lib_path).context("Failed to create lib/security directory")?;
+            file_writer::write(lib_path.join("csp.ts"), csp).context("Failed to create csp.ts")?;
+            file_writer::write(lib_path.join("middleware.ts"), SECURITY_MIDDLEWARE)
+                .context("Failed to create lib/security/middleware.ts")?;
+            file_writer::write(lib_path.join("nonce.ts"), NONCE).context("Failed to create nonce.ts")?;
+
+            middleware_created = !middleware_path.exists();
+            if middleware_created {
+                file_writer::write(&middleware_path, MIDDLEWARE)
+                    .context(format!("Failed to create {}", middleware_path.display()))?;
+            }
+        }
+        HeaderMode::NextConfig => {
+            pb.set_message("Adding security headers to next.config...");
+            headers_added = next_config::set_nested_option(&["headers"], &next_config_headers(&directives))?;
+        }
+    }
+
+    pb.finish_with_message("Security headers set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    match mode {
+        HeaderMode::Middleware => {
+            println!("• {}/csp.ts - CSP directives and security headers", lib_path.display());
+            println!("• {}/middleware.ts - Per-request nonce and response headers", lib_path.display());
+            println!("• {}/nonce.ts - getNonce() for your own scripts", lib_path.display());
+            if middleware_created {
+                println!("• {} - Runs securityMiddleware() on every page request", middleware_path.display());
+            } else {
+                println!(
+                    "{}",
+                    style(format!(
+                        "• {} already exists; return securityMiddleware(request) from it, or copy its nonce and headers into your response",
+                        middleware_path.display()
+                    ))
+                    .yellow()
+                );
+            }
+        }
+        HeaderMode::NextConfig if headers_added => {
+            println!("• next.config - headers() with the CSP, HSTS, frame, content type, referrer and permissions policies");
+        }
+        HeaderMode::NextConfig => {
+            println!(
+                "{}",
+                style("next.config already defines headers(); add the CSP and security headers to it manually").yellow()
+            );
+        }
+    }
+    if !services.is_empty() {
+        println!("• CSP sources for {}", services.join(", "));
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Check the browser console for CSP violations and add the origins your app needs");
+    match mode {
+        HeaderMode::Middleware => println!("2. Pass getNonce() to <Script nonce={{...}}> for third-party scripts"),
+        HeaderMode::NextConfig => println!("2. Switch to the middleware mode later to drop 'unsafe-inline' scripts"),
+    }
+    println!("3. Submit your domain to hstspreload.org once every subdomain serves https");
+
+    Ok(())
+}
@@ -1,91 +1,17 @@
 use anyhow::{Result, Context};
 use console::style;
 use indicatif::ProgressBar;
-use std::process::Command;
-use crate::project_structure::ProjectStructure;
+use crate::change_set;
+use crate::file_writer;
+use crate::globals_css;
+use crate::import_alias;
+use crate::next_config;
+use crate::project_structure::{project_file, ProjectStructure};
 use crate::package_manager::PackageManager;
+use crate::tailwind::{self, TailwindVersion};
 
-pub async fn add_shadcn() -> Result<()> {
-    let package_manager = PackageManager::from_project_config()?;
-    let project_structure = ProjectStructure::detect()?;
-
-    println!(
-        "{}",
-        style(format!(
-            "Using package manager: {}",
-            package_manager.to_string()
-        ))
-        .yellow()
-    );
-    println!(
-        "{}",
-        style(format!(
-            "Project structure: {}",
-            format!("{:?}", project_structure).to_lowercase()
-        ))
-        .yellow()
-    );
-
-    let pb = ProgressBar::new_spinner();
-    pb.set_message("Installing shadcn/ui dependencies...");
-
-    // Install required dependencies
-    let (cmd, install) = package_manager.install_command();
-    Command::new(cmd)
-        .args([
-            install,
-            "class-variance-authority",
-            "clsx",
-            "tailwind-merge",
-            "lucide-react",
-            "tw-animate-css",
-        ])
-        .status()
-        .context("Failed to install shadcn/ui dependencies")?;
-
-    pb.set_message("Setting up configuration files...");
-
-    // Create components.json
-    let components_json = format!(r#"{{
-  "$schema": "https://ui.shadcn.com/schema.json",
-  "style": "new-york",
-  "rsc": false,
-  "tsx": true,
-  "tailwind": {{
-    "config": "",
-    "css": "{}",
-    "baseColor": "neutral",
-    "cssVariables": true,
-    "prefix": ""
-  }},
-  "aliases": {{
-    "components": "@/components",
-    "utils": "@/lib/utils",
-    "ui": "@/components/ui",
-    "lib": "@/lib",
-    "hooks": "@/hooks"
-  }},
-  "iconLibrary": "lucide"
-}}"#, project_structure.get_globals_css_path());
-
-    std::fs::write("components.json", components_json)
-        .context("Failed to create components.json")?;
-
-    // Create utils.ts
-    let utils_ts = r#"import { type ClassValue, clsx } from "clsx"
-import { twMerge } from "tailwind-merge"
- 
-export function cn(...inputs: ClassValue[]) {
-  return twMerge(clsx(inputs))
-}"#;
-
-    let lib_path = project_structure.get_lib_path();
-    std::fs::create_dir_all(lib_path).context("Failed to create lib directory")?;
-    std::fs::write(format!("{}/utils.ts", lib_path), utils_ts)
-        .context("Failed to create utils.ts")?;
-
-    // Update globals.css
-    let globals_css = r#"@import "tailwindcss";
+// Tailwind v4 reads theme tokens from CSS: variables plus an `@theme inline` mapping
+const GLOBALS_CSS_V4: &str = r#"@import "tailwindcss";
 @import "tw-animate-css";
 
 @custom-variant dark (&:is(.dark *));
@@ -209,17 +135,247 @@ export function cn(...inputs: ClassValue[]) {
   }
 }"#;
 
-    let globals_css_path = project_structure.get_globals_css_path();
-    std::fs::write(globals_css_path, globals_css)
-        .context("Failed to update globals.css")?;
+// Tailwind v3 takes HSL channels in CSS and maps them to colors in tailwind.config
+const GLOBALS_CSS_V3: &str = r#"@tailwind base;
+@tailwind components;
+@tailwind utilities;
+
+@layer base {
+  :root {
+    --background: 0 0% 100%;
+    --foreground: 0 0% 3.9%;
+    --card: 0 0% 100%;
+    --card-foreground: 0 0% 3.9%;
+    --popover: 0 0% 100%;
+    --popover-foreground: 0 0% 3.9%;
+    --primary: 0 0% 9%;
+    --primary-foreground: 0 0% 98%;
+    --secondary: 0 0% 96.1%;
+    --secondary-foreground: 0 0% 9%;
+    --muted: 0 0% 96.1%;
+    --muted-foreground: 0 0% 45.1%;
+    --accent: 0 0% 96.1%;
+    --accent-foreground: 0 0% 9%;
+    --destructive: 0 84.2% 60.2%;
+    --destructive-foreground: 0 0% 98%;
+    --border: 0 0% 89.8%;
+    --input: 0 0% 89.8%;
+    --ring: 0 0% 3.9%;
+    --chart-1: 12 76% 61%;
+    --chart-2: 173 58% 39%;
+    --chart-3: 197 37% 24%;
+    --chart-4: 43 74% 66%;
+    --chart-5: 27 87% 67%;
+    --radius: 0.5rem;
+  }
+  .dark {
+    --background: 0 0% 3.9%;
+    --foreground: 0 0% 98%;
+    --card: 0 0% 3.9%;
+    --card-foreground: 0 0% 98%;
+    --popover: 0 0% 3.9%;
+    --popover-foreground: 0 0% 98%;
+    --primary: 0 0% 98%;
+    --primary-foreground: 0 0% 9%;
+    --secondary: 0 0% 14.9%;
+    --secondary-foreground: 0 0% 98%;
+    --muted: 0 0% 14.9%;
+    --muted-foreground: 0 0% 63.9%;
+    --accent: 0 0% 14.9%;
+    --accent-foreground: 0 0% 98%;
+    --destructive: 0 62.8% 30.6%;
+    --destructive-foreground: 0 0% 98%;
+    --border: 0 0% 14.9%;
+    --input: 0 0% 14.9%;
+    --ring: 0 0% 83.1%;
+    --chart-1: 220 70% 50%;
+    --chart-2: 160 60% 45%;
+    --chart-3: 30 80% 55%;
+    --chart-4: 280 65% 60%;
+    --chart-5: 340 75% 55%;
+  }
+  * {
+    @apply border-border;
+  }
+  body {
+    @apply bg-background text-foreground;
+  }
+}"#;
+
+/// `theme.extend` entries tailwind.config needs on v3 so classes like `bg-primary` exist.
+const TAILWIND_V3_THEME: [(&str, &str); 16] = [
+    ("colors.border", "\"hsl(var(--border))\""),
+    ("colors.input", "\"hsl(var(--input))\""),
+    ("colors.ring", "\"hsl(var(--ring))\""),
+    ("colors.background", "\"hsl(var(--background))\""),
+    ("colors.foreground", "\"hsl(var(--foreground))\""),
+    ("colors.primary", "{ DEFAULT: \"hsl(var(--primary))\", foreground: \"hsl(var(--primary-foreground))\" }"),
+    ("colors.secondary", "{ DEFAULT: \"hsl(var(--secondary))\", foreground: \"hsl(var(--secondary-foreground))\" }"),
+    ("colors.destructive", "{ DEFAULT: \"hsl(var(--destructive))\", foreground: \"hsl(var(--destructive-foreground))\" }"),
+    ("colors.muted", "{ DEFAULT: \"hsl(var(--muted))\", foreground: \"hsl(var(--muted-foreground))\" }"),
+    ("colors.accent", "{ DEFAULT: \"hsl(var(--accent))\", foreground: \"hsl(var(--accent-foreground))\" }"),
+    ("colors.popover", "{ DEFAULT: \"hsl(var(--popover))\", foreground: \"hsl(var(--popover-foreground))\" }"),
+    ("colors.card", "{ DEFAULT: \"hsl(var(--card))\", foreground: \"hsl(var(--card-foreground))\" }"),
+    ("colors.chart", "{ \"1\": \"hsl(var(--chart-1))\", \"2\": \"hsl(var(--chart-2))\", \"3\": \"hsl(var(--chart-3))\", \"4\": \"hsl(var(--chart-4))\", \"5\": \"hsl(var(--chart-5))\" }"),
+    ("borderRadius.lg", "\"var(--radius)\""),
+    ("borderRadius.md", "\"calc(var(--radius) - 2px)\""),
+    ("borderRadius.sm", "\"calc(var(--radius) - 4px)\""),
+];
+
+/// The animation utilities package matching the installed Tailwind version.
+pub fn animate_package(tailwind_version: TailwindVersion) -> &'static str {
+    match tailwind_version {
+        TailwindVersion::V3 => "tailwindcss-animate",
+        TailwindVersion::V4 => "tw-animate-css",
+    }
+}
+
+/// components.json pointing the shadcn CLI at globals.css and, on v3, tailwind.config.
+pub fn components_json(project_structure: &ProjectStructure, tailwind_version: TailwindVersion) -> String {
+    let tailwind_config = match tailwind_version {
+        TailwindVersion::V3 => tailwind::find_config()
+            .map(|path| project_structure.relative_path(&path))
+            .unwrap_or_else(|| "tailwind.config.ts".to_string()),
+        TailwindVersion::V4 => String::new(),
+    };
+
+    // The shadcn CLI needs aliases, so projects without one keep the default `@/`
+    let alias = |module: &str| import_alias::specifier(module).unwrap_or_else(|| format!("@/{}", module));
+
+    format!(r#"{{
+  "$schema": "https://ui.shadcn.com/schema.json",
+  "style": "new-york",
+  "rsc": false,
+  "tsx": true,
+  "tailwind": {{
+    "config": "{}",
+    "css": "{}",
+    "baseColor": "neutral",
+    "cssVariables": true,
+    "prefix": ""
+  }},
+  "aliases": {{
+    "components": "{}",
+    "utils": "{}",
+    "ui": "{}",
+    "lib": "{}",
+    "hooks": "{}"
+  }},
+  "iconLibrary": "lucide"
+}}"#,
+        tailwind_config,
+        project_structure.relative_path(&project_structure.get_globals_css_path()),
+        alias("components"),
+        alias("lib/utils"),
+        alias("components/ui"),
+        alias("lib"),
+        alias("hooks"),
+    )
+}
+
+/// Adds the theme variables to globals.css and, on v3, the matching colors, dark mode and
+/// animate plugin to tailwind.config.
+pub fn setup_theme(project_structure: &ProjectStructure, tailwind_version: TailwindVersion) -> Result<()> {
+    if tailwind_version == TailwindVersion::V4 {
+        globals_css::merge(project_structure, GLOBALS_CSS_V4)?;
+        return Ok(());
+    }
+
+    globals_css::merge(project_structure, GLOBALS_CSS_V3)?;
+
+    let Some(config_path) = tailwind::find_config() else {
+        anyhow::bail!("Could not find tailwind.config. shadcn/ui on Tailwind v3 needs one to map theme colors");
+    };
+    next_config::set_option_in(&config_path, &["darkMode"], "[\"class\"]")?;
+    // Entries are inserted first in their object, so going backwards keeps the listed order
+    for (key, value) in TAILWIND_V3_THEME.into_iter().rev() {
+        let mut path = vec!["theme", "extend"];
+        path.extend(key.split('.'));
+        next_config::set_option_in(&config_path, &path, value)?;
+    }
+    // jiti gives .ts and CommonJS configs `require`; plain ES module configs import the plugin
+    let plugin = if config_path.extension().is_some_and(|extension| extension == "mjs") {
+        let content = std::fs::read_to_string(&config_path)
+            .context(format!("Failed to read {}", config_path.display()))?;
+        if !content.contains("\"tailwindcss-animate\"") {
+            change_set::write(&config_path, format!("import tailwindcssAnimate from \"tailwindcss-animate\";\n{}", content))
+                .context(format!("Failed to update {}", config_path.display()))?;
+        }
+        "tailwindcssAnimate"
+    } else {
+        "require(\"tailwindcss-animate\")"
+    };
+    next_config::add_to_array_option_in(&config_path, &["plugins"], &[plugin])?;
+    Ok(())
+}
+
+pub async fn add_shadcn() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    let tailwind_version = tailwind::ensure_installed(&project_structure, &package_manager, "shadcn/ui")?;
+    println!(
+        "{}",
+        style(format!("Tailwind CSS: {}", tailwind_version.as_str())).yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing shadcn/ui dependencies...");
+
+    // Install required dependencies
+    package_manager
+        .add(&[
+            "class-variance-authority",
+            "clsx",
+            "tailwind-merge",
+            "lucide-react",
+            animate_package(tailwind_version),
+        ])
+        .run()
+        .context("Failed to install shadcn/ui dependencies")?;
+
+    pb.set_message("Setting up configuration files...");
+
+    file_writer::write(project_file("components.json"), components_json(&project_structure, tailwind_version))
+        .context("Failed to create components.json")?;
+
+    // Create utils.ts
+    let utils_ts = r#"import { type ClassValue, clsx } from "clsx"
+import { twMerge } from "tailwind-merge"
+ 
+export function cn(...inputs: ClassValue[]) {
+  return twMerge(clsx(inputs))
+}"#;
+
+    let lib_path = project_structure.get_lib_path();
+    change_set::create_dir_all(&lib_path).context("Failed to create lib directory")?;
+    file_writer::write(lib_path.join("utils.ts"), utils_ts)
+        .context("Failed to create utils.ts")?;
+
+    pb.set_message(format!("Adding theme variables for Tailwind {}...", tailwind_version.as_str()));
+    setup_theme(&project_structure, tailwind_version)?;
 
     // Create components and components/ui directories
     pb.set_message("Creating component directories...");
-    let components_path = match project_structure {
-        ProjectStructure::AppDir => "components/ui",
-        ProjectStructure::SrcDir => "src/components/ui",
-    };
-    std::fs::create_dir_all(components_path)
+    let components_path = project_structure.get_components_path().join("ui");
+    change_set::create_dir_all(&components_path)
         .context("Failed to create components/ui directory")?;
 
     pb.finish_with_message("shadcn/ui installed successfully!");
@@ -0,0 +1,484 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::env_file;
+use crate::file_writer;
+use crate::package_json;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Regeneration {
+    Isr,
+    Webhook,
+}
+
+impl Regeneration {
+    const ALL: [Regeneration; 2] = [Regeneration::Isr, Regeneration::Webhook];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Regeneration::Isr => "ISR",
+            Regeneration::Webhook => "Webhook",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Regeneration::Isr => "rebuild the sitemap at most once per interval",
+            Regeneration::Webhook => "keep it cached until POST /api/revalidate/sitemap is called",
+        }
+    }
+}
+
+/// A table of the drizzle schema and the columns the sitemap can use.
+struct SchemaTable {
+    /// The exported variable, e.g. `postsTable`
+    export: String,
+    /// The SQL table name, e.g. `posts`
+    name: String,
+    columns: Vec<String>,
+    soft_delete: bool,
+}
+
+impl SchemaTable {
+    /// The column in the row's URL: `slug` when there is one, the id otherwise.
+    fn slug_column(&self) -> &str {
+        if self.columns.iter().any(|column| column == "slug") { "slug" } else { "id" }
+    }
+
+    fn last_modified_column(&self) -> Option<&str> {
+        ["updatedAt", "createdAt"]
+            .into_iter()
+            .find(|wanted| self.columns.iter().any(|column| column == wanted))
+    }
+
+    fn url_segment(&self) -> String {
+        self.name.replace('_', "-")
+    }
+
+    fn label(&self) -> String {
+        format!("{} - /{}/[{}]", self.name, self.url_segment(), self.slug_column())
+    }
+}
+
+/// The `pgTable` declarations of a drizzle schema, with the columns the schema conventions'
+/// `...timestamps` spread adds.
+fn schema_tables(schema: &str) -> Vec<SchemaTable> {
+    let mut tables = Vec::new();
+    for (start, _) in schema.match_indices("export const ") {
+        let declaration = &schema[start + "export const ".len()..];
+        let Some((export, rest)) = declaration.split_once(" = pgTable(\"") else {
+            continue;
+        };
+        if export.contains(char::is_whitespace) {
+            continue;
+        }
+        let Some((name, body)) = rest.split_once('"') else {
+            continue;
+        };
+        let body = &body[..body.find("});").unwrap_or(body.len())];
+
+        let mut columns: Vec<String> = body
+            .lines()
+            .filter_map(|line| line.trim().split_once(':'))
+            .map(|(key, _)| key.trim())
+            .filter(|key| !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+            .map(str::to_string)
+            .collect();
+        if body.contains("...timestamps") {
+            columns.extend(["createdAt".to_string(), "updatedAt".to_string()]);
+        }
+
+        tables.push(SchemaTable {
+            export: export.to_string(),
+            name: name.to_string(),
+            columns,
+            soft_delete: body.contains("...softDelete"),
+        });
+    }
+    tables
+}
+
+/// A `SITEMAP_SOURCES` entry mapping a table to its URLs.
+fn source_entry(table: &SchemaTable) -> String {
+    let key = if table.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        table.name.clone()
+    } else {
+        format!("\"{}\"", table.name)
+    };
+    let mut entry = format!(
+        "  {}: {{\n    table: {},\n    slug: {}.{},\n",
+        key,
+        table.export,
+        table.export,
+        table.slug_column()
+    );
+    if let Some(column) = table.last_modified_column() {
+        entry.push_str(&format!("    lastModified: {}.{},\n", table.export, column));
+    }
+    if table.soft_delete {
+        entry.push_str(&format!("    where: notDeleted({}),\n", table.export));
+    }
+    entry.push_str(&format!(
+        "    path: (slug) => `/{}/${{slug}}`,\n    changeFrequency: \"weekly\",\n  }},\n",
+        table.url_segment()
+    ));
+    entry
+}
+
+const CONFIG: &str = r#"import type { SQL } from "drizzle-orm";
+import type { AnyPgColumn, PgTable } from "drizzle-orm/pg-core";
+__SCHEMA_IMPORTS__
+/** Absolute URLs in the sitemap start with this. */
+export const SITE_URL = (process.env.NEXT_PUBLIC_SITE_URL ?? "http://localhost:3000").replace(/\/$/, "");
+
+/** URLs per sitemap file; the sitemap protocol allows at most 50,000. */
+export const SITEMAP_PAGE_SIZE = 50_000;
+
+/** Seconds the sitemap queries stay cached, or false to keep them until revalidateSitemap() runs. */
+export const SITEMAP_REVALIDATE: number | false = __REVALIDATE__;
+
+/** Routes that no table backs, listed in /sitemaps/static/0.xml. */
+export const STATIC_PATHS = ["/"];
+
+export interface SitemapSource {
+  table: PgTable;
+  /** The column that identifies a row in its URL */
+  slug: AnyPgColumn;
+  /** The column for <lastmod>, when the table tracks updates */
+  lastModified?: AnyPgColumn;
+  /** Rows to leave out, e.g. drafts or soft-deleted rows */
+  where?: SQL;
+  path: (slug: string) => string;
+  changeFrequency?: "always" | "hourly" | "daily" | "weekly" | "monthly" | "yearly" | "never";
+  priority?: number;
+}
+
+/**
+ * Tables in the sitemap, keyed by the name in their sitemap URLs (/sitemaps/<name>/<page>.xml).
+ * Add an entry to map another table.
+ */
+export const SITEMAP_SOURCES: Record<string, SitemapSource> = {
+__SOURCES__};
+"#;
+
+const QUERIES: &str = r#"import { asc, count, sql } from "drizzle-orm";
+import { revalidateTag, unstable_cache } from "next/cache";
+import { db } from "@/db";
+import { SITEMAP_PAGE_SIZE, SITEMAP_REVALIDATE, SITEMAP_SOURCES } from "@/lib/sitemap/config";
+
+const SITEMAP_TAG = "sitemap";
+const cacheOptions = { tags: [SITEMAP_TAG], revalidate: SITEMAP_REVALIDATE };
+
+/** Sitemap files per source; at least one, so every source appears in the index. */
+export const getSitemapPageCounts = unstable_cache(
+  async () => {
+    const counts: Record<string, number> = {};
+    for (const [name, source] of Object.entries(SITEMAP_SOURCES)) {
+      const [{ total }] = await db.select({ total: count() }).from(source.table).where(source.where);
+      counts[name] = Math.max(1, Math.ceil(total / SITEMAP_PAGE_SIZE));
+    }
+    return counts;
+  },
+  ["sitemap-page-counts"],
+  cacheOptions,
+);
+
+/** One page of a source's rows, ordered by slug so pages stay stable between rebuilds. */
+export const getSitemapRows = unstable_cache(
+  async (name: string, page: number) => {
+    const source = SITEMAP_SOURCES[name];
+    const rows = await db
+      .select({ slug: source.slug, lastModified: source.lastModified ?? sql<null>`null` })
+      .from(source.table)
+      .where(source.where)
+      .orderBy(asc(source.slug))
+      .limit(SITEMAP_PAGE_SIZE)
+      .offset(page * SITEMAP_PAGE_SIZE);
+
+    // The cache stores JSON, so dates travel as ISO strings
+    return rows.map((row) => ({
+      slug: String(row.slug),
+      lastModified: row.lastModified ? new Date(row.lastModified).toISOString() : undefined,
+    }));
+  },
+  ["sitemap-rows"],
+  cacheOptions,
+);
+
+/** Drops the cached sitemap so the next request rebuilds it, e.g. after publishing a post. */
+export function revalidateSitemap() {
+  revalidateTag(SITEMAP_TAG__REVALIDATE_PROFILE__);
+}
+"#;
+
+const XML: &str = r#"export interface SitemapUrl {
+  url: string;
+  lastModified?: string;
+  changeFrequency?: string;
+  priority?: number;
+}
+
+const XML_ESCAPES: Record<string, string> = { "&": "&amp;", "<": "&lt;", ">": "&gt;", '"': "&quot;", "'": "&apos;" };
+
+export function escapeXml(value: string) {
+  return value.replace(/[&<>"']/g, (char) => XML_ESCAPES[char]);
+}
+
+export function urlSetXml(urls: SitemapUrl[]) {
+  const entries = urls.map(({ url, lastModified, changeFrequency, priority }) => {
+    const tags = [`<loc>${escapeXml(url)}</loc>`];
+    if (lastModified) tags.push(`<lastmod>${lastModified}</lastmod>`);
+    if (changeFrequency) tags.push(`<changefreq>${changeFrequency}</changefreq>`);
+    if (priority !== undefined) tags.push(`<priority>${priority}</priority>`);
+    return `<url>${tags.join("")}</url>`;
+  });
+  return `<?xml version="1.0" encoding="UTF-8"?>\n<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">\n${entries.join("\n")}\n</urlset>\n`;
+}
+
+export function sitemapIndexXml(urls: string[]) {
+  const entries = urls.map((url) => `<sitemap><loc>${escapeXml(url)}</loc></sitemap>`);
+  return `<?xml version="1.0" encoding="UTF-8"?>\n<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">\n${entries.join("\n")}\n</sitemapindex>\n`;
+}
+
+export function xmlResponse(xml: string) {
+  return new Response(xml, { headers: { "Content-Type": "application/xml; charset=utf-8" } });
+}
+"#;
+
+const INDEX_ROUTE: &str = r#"import { SITE_URL } from "@/lib/sitemap/config";
+import { getSitemapPageCounts } from "@/lib/sitemap/queries";
+import { sitemapIndexXml, xmlResponse } from "@/lib/sitemap/xml";
+
+// Rendered per request so builds never query the database; the queries are cached instead
+export const dynamic = "force-dynamic";
+
+/** `GET /sitemap.xml` is a sitemap index listing every page of every sitemap source. */
+export async function GET() {
+  const urls = [`${SITE_URL}/sitemaps/static/0.xml`];
+  for (const [name, pages] of Object.entries(await getSitemapPageCounts())) {
+    for (let page = 0; page < pages; page++) {
+      urls.push(`${SITE_URL}/sitemaps/${name}/${page}.xml`);
+    }
+  }
+  return xmlResponse(sitemapIndexXml(urls));
+}
+"#;
+
+const PAGE_ROUTE: &str = r#"import { SITE_URL, SITEMAP_SOURCES, STATIC_PATHS } from "@/lib/sitemap/config";
+import { getSitemapRows } from "@/lib/sitemap/queries";
+import { urlSetXml, xmlResponse } from "@/lib/sitemap/xml";
+
+export const dynamic = "force-dynamic";
+
+function notFound() {
+  return new Response("Not found", { status: 404 });
+}
+
+/** `GET /sitemaps/[source]/[page].xml` lists one page of a table's URLs, or the static routes. */
+export async function GET(_request: Request, { params }: { params: Promise<{ source: string; page: string }> }) {
+  const { source: name, page: file } = await params;
+  const page = Number(/^(\d+)\.xml$/.exec(file)?.[1]);
+  if (!Number.isSafeInteger(page)) return notFound();
+
+  if (name === "static") {
+    if (page !== 0) return notFound();
+    return xmlResponse(urlSetXml(STATIC_PATHS.map((path) => ({ url: `${SITE_URL}${path}` }))));
+  }
+
+  if (!Object.hasOwn(SITEMAP_SOURCES, name)) return notFound();
+  const source = SITEMAP_SOURCES[name];
+  const rows = await getSitemapRows(name, page);
+  if (rows.length === 0 && page > 0) return notFound();
+
+  return xmlResponse(
+    urlSetXml(
+      rows.map((row) => ({
+        url: `${SITE_URL}${source.path(row.slug)}`,
+        lastModified: row.lastModified,
+        changeFrequency: source.changeFrequency,
+        priority: source.priority,
+      })),
+    ),
+  );
+}
+"#;
+
+const WEBHOOK_ROUTE: &str = r#"import { revalidateSitemap } from "@/lib/sitemap/queries";
+
+/**
+ * `POST /api/revalidate/sitemap` rebuilds the sitemap on its next request. Call it from a CMS
+ * webhook or a deploy hook with `Authorization: Bearer <SITEMAP_REVALIDATE_SECRET>`.
+ */
+export async function POST(request: Request) {
+  const secret = process.env.SITEMAP_REVALIDATE_SECRET;
+  if (!secret || request.headers.get("authorization") !== `Bearer ${secret}`) {
+    return new Response("Unauthorized", { status: 401 });
+  }
+
+  revalidateSitemap();
+  return Response.json({ revalidated: true, now: Date.now() });
+}
+"#;
+
+pub async fn add_sitemap() -> Result<()> {
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    let schema_path = project_structure.get_db_path().join("schema.ts");
+    let schema = std::fs::read_to_string(&schema_path).unwrap_or_default();
+    if !schema.contains("drizzle-orm/pg-core") {
+        println!(
+            "{}",
+            style("The sitemap reads slugs from drizzle tables on Postgres. Run `nstack add --feature drizzle` first.").yellow()
+        );
+        return Ok(());
+    }
+
+    let app_path = project_structure.get_app_path();
+    let existing = ["sitemap.ts", "sitemap.js", "sitemap.xml"]
+        .into_iter()
+        .find(|file| app_path.join(file).is_file());
+    if let Some(existing) = existing {
+        println!(
+            "{}",
+            style(format!(
+                "{}/{} already serves /sitemap.xml; remove it to generate the sitemap from the database",
+                app_path.display(),
+                existing
+            ))
+            .yellow()
+        );
+        return Ok(());
+    }
+
+    let tables = schema_tables(&schema);
+    let labels: Vec<String> = tables.iter().map(SchemaTable::label).collect();
+    let any_slugs = tables.iter().any(|table| table.slug_column() == "slug");
+    let defaults: Vec<bool> = tables
+        .iter()
+        .map(|table| if any_slugs { table.slug_column() == "slug" } else { table.export == "postsTable" })
+        .collect();
+    let selections = prompt::multi_select(
+        "tables",
+        "Select the tables whose rows get sitemap URLs (space to toggle)",
+        &labels,
+        &defaults,
+    )?;
+    let selected: Vec<&SchemaTable> = selections.iter().map(|&index| &tables[index]).collect();
+
+    let regeneration_items: Vec<String> = Regeneration::ALL
+        .iter()
+        .map(|regeneration| format!("{} - {}", regeneration.as_str(), regeneration.description()))
+        .collect();
+    let regeneration = Regeneration::ALL[prompt::select(
+        "regeneration",
+        "How should the sitemap pick up new content?",
+        &regeneration_items,
+        0,
+    )?];
+    let revalidate = if regeneration == Regeneration::Isr {
+        prompt::input_validated("revalidate", "Seconds between rebuilds", "3600", |input| {
+            input.parse::<u32>().map(|_| ()).map_err(|_| "Enter a whole number")
+        })?
+    } else {
+        "false".to_string()
+    };
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Creating the sitemap configuration...");
+
+    let mut schema_imports: Vec<&str> = selected.iter().map(|table| table.export.as_str()).collect();
+    schema_imports.sort();
+    let mut imports = String::new();
+    if selected.iter().any(|table| table.soft_delete) {
+        imports.push_str("import { notDeleted } from \"@/db/columns\";\n");
+    }
+    if !schema_imports.is_empty() {
+        imports.push_str(&format!("import {{ {} }} from \"@/db/schema\";\n", schema_imports.join(", ")));
+    }
+    let config = CONFIG
+        .replace("__SCHEMA_IMPORTS__", &imports)
+        .replace("__REVALIDATE__", &revalidate)
+        .replace("__SOURCES__", &selected.iter().map(|table| source_entry(table)).collect::<String>());
+
+    // Next.js 16 asks how stale tagged data may be served; webhooks want it gone right away
+    let revalidate_profile = if package_json::dependency_major_version("next").is_some_and(|major| major >= 16) {
+        ", { expire: 0 }"
+    } else {
+        ""
+    };
+
+    let lib_path = project_structure.get_lib_path().join("sitemap");
+    change_set::create_dir_all(&lib_path).context("Failed to create lib/sitemap directory")?;
+    file_writer::write(lib_path.join("config.ts"), config).context("Failed to create config.ts")?;
+    file_writer::write(lib_path.join("queries.ts"), QUERIES.replace("__REVALIDATE_PROFILE__", revalidate_profile))
+        .context("Failed to create queries.ts")?;
+    file_writer::write(lib_path.join("xml.ts"), XML).context("Failed to create xml.ts")?;
+
+    pb.set_message("Creating the sitemap routes...");
+
+    let index_path = app_path.join("sitemap.xml");
+    change_set::create_dir_all(&index_path).context("Failed to create sitemap.xml route directory")?;
+    file_writer::write(index_path.join("route.ts"), INDEX_ROUTE).context("Failed to create sitemap.xml/route.ts")?;
+
+    let page_path = app_path.join("sitemaps").join("[source]").join("[page]");
+    change_set::create_dir_all(&page_path).context("Failed to create sitemaps route directory")?;
+    file_writer::write(page_path.join("route.ts"), PAGE_ROUTE).context("Failed to create sitemaps route.ts")?;
+
+    let webhook_path = project_structure.get_api_path().join("revalidate").join("sitemap");
+    if regeneration == Regeneration::Webhook {
+        change_set::create_dir_all(&webhook_path).context("Failed to create revalidate route directory")?;
+        file_writer::write(webhook_path.join("route.ts"), WEBHOOK_ROUTE)
+            .context("Failed to create the revalidation webhook")?;
+    }
+
+    let mut env_vars = vec![("NEXT_PUBLIC_SITE_URL", "http://localhost:3000")];
+    if regeneration == Regeneration::Webhook {
+        env_vars.push(("SITEMAP_REVALIDATE_SECRET", ""));
+    }
+    env_file::add_env_vars("sitemap", "Sitemap", &env_vars)?;
+
+    pb.finish_with_message("Sitemap generation set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/config.ts - Table and column mapping for sitemap URLs", lib_path.display());
+    println!("• {}/queries.ts - Cached page counts and rows, and revalidateSitemap()", lib_path.display());
+    println!("• {}/route.ts - Sitemap index at /sitemap.xml", index_path.display());
+    println!("• {}/route.ts - Paginated sitemaps at /sitemaps/[source]/[page].xml", page_path.display());
+    if regeneration == Regeneration::Webhook {
+        println!("• {}/route.ts - Revalidation webhook", webhook_path.display());
+    }
+
+    if selected.is_empty() {
+        println!(
+            "{}",
+            style("No tables selected; the sitemap lists STATIC_PATHS until you add SITEMAP_SOURCES in config.ts").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Set NEXT_PUBLIC_SITE_URL in .env to your production URL");
+    println!("2. Check the path() of each source in config.ts matches your routes");
+    match regeneration {
+        Regeneration::Isr => {
+            println!("3. Call revalidateSitemap() after publishing to skip the {} second wait", revalidate)
+        }
+        Regeneration::Webhook => {
+            println!("3. Set SITEMAP_REVALIDATE_SECRET (e.g. `openssl rand -hex 32`) and POST to /api/revalidate/sitemap when content changes")
+        }
+    }
+    println!("4. Add `Sitemap: <site url>/sitemap.xml` to robots.txt and submit it in Search Console");
+
+    Ok(())
+}
@@ -0,0 +1,278 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::gitignore;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+
+fn main_config(components_path: &str) -> String {
+    format!(
+        r#"import type {{ StorybookConfig }} from "@storybook/nextjs";
+
+const config: StorybookConfig = {{
+  stories: ["../{}/**/*.mdx", "../{}/**/*.stories.@(ts|tsx)"],
+  addons: ["@storybook/addon-essentials", "@storybook/addon-interactions"],
+  framework: {{
+    name: "@storybook/nextjs",
+    options: {{}},
+  }},
+  staticDirs: ["../public"],
+}};
+
+export default config;
+"#,
+        components_path, components_path
+    )
+}
+
+const PREVIEW_CONFIG: &str = r#"import type { Preview } from "@storybook/react";
+// Tailwind styles and theme variables used by the app
+import "__GLOBALS_CSS__";
+
+const preview: Preview = {
+  parameters: {
+    controls: {
+      matchers: {
+        color: /(background|color)$/i,
+        date: /Date$/i,
+      },
+    },
+    nextjs: {
+      appDirectory: true,
+    },
+  },
+};
+
+export default preview;
+"#;
+
+const BUTTON_STORIES: &str = r#"import type { Meta, StoryObj } from "@storybook/react";
+import { expect, fn, userEvent, within } from "@storybook/test";
+import { Button } from "./button";
+
+const meta = {
+  title: "UI/Button",
+  component: Button,
+  args: { children: "Button", onClick: fn() },
+  argTypes: {
+    variant: {
+      control: "select",
+      options: ["default", "destructive", "outline", "secondary", "ghost", "link"],
+    },
+    size: { control: "select", options: ["default", "sm", "lg", "icon"] },
+  },
+} satisfies Meta<typeof Button>;
+
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const Default: Story = {};
+
+export const Outline: Story = {
+  args: { variant: "outline" },
+};
+
+export const Destructive: Story = {
+  args: { variant: "destructive", children: "Delete" },
+};
+
+export const Clicked: Story = {
+  play: async ({ args, canvasElement }) => {
+    const canvas = within(canvasElement);
+    await userEvent.click(canvas.getByRole("button"));
+    await expect(args.onClick).toHaveBeenCalledOnce();
+  },
+};
+"#;
+
+const BADGE_STORIES: &str = r#"import type { Meta, StoryObj } from "@storybook/react";
+import { Badge } from "./badge";
+
+const meta = {
+  title: "UI/Badge",
+  component: Badge,
+  args: { children: "Badge" },
+  argTypes: {
+    variant: {
+      control: "select",
+      options: ["default", "secondary", "destructive", "outline"],
+    },
+  },
+} satisfies Meta<typeof Badge>;
+
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const Default: Story = {};
+
+export const Secondary: Story = {
+  args: { variant: "secondary" },
+};
+"#;
+
+const INPUT_STORIES: &str = r#"import type { Meta, StoryObj } from "@storybook/react";
+import { expect, userEvent, within } from "@storybook/test";
+import { Input } from "./input";
+
+const meta = {
+  title: "UI/Input",
+  component: Input,
+  args: { placeholder: "Email", type: "email" },
+} satisfies Meta<typeof Input>;
+
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const Default: Story = {};
+
+export const Disabled: Story = {
+  args: { disabled: true },
+};
+
+export const Typing: Story = {
+  play: async ({ canvasElement }) => {
+    const canvas = within(canvasElement);
+    const input = canvas.getByPlaceholderText("Email");
+    await userEvent.type(input, "jane@example.com");
+    await expect(input).toHaveValue("jane@example.com");
+  },
+};
+"#;
+
+const CARD_STORIES: &str = r#"import type { Meta, StoryObj } from "@storybook/react";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "./card";
+
+const meta = {
+  title: "UI/Card",
+  component: Card,
+} satisfies Meta<typeof Card>;
+
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const Default: Story = {
+  render: () => (
+    <Card className="w-80">
+      <CardHeader>
+        <CardTitle>Card title</CardTitle>
+        <CardDescription>A short description.</CardDescription>
+      </CardHeader>
+      <CardContent>Card content goes here.</CardContent>
+    </Card>
+  ),
+};
+"#;
+
+const INTRODUCTION_DOCS: &str = r#"import { Meta } from "@storybook/blocks";
+
+<Meta title="Introduction" />
+
+# Components
+
+Stories live next to the components they document (`*.stories.tsx`).
+Add stories for shadcn/ui components with `nstack add --feature storybook` after generating them,
+or write your own using the examples in `ui/`.
+"#;
+
+// shadcn/ui components with example stories, keyed by their generated file name
+const COMPONENT_STORIES: [(&str, &str); 4] = [
+    ("button", BUTTON_STORIES),
+    ("badge", BADGE_STORIES),
+    ("input", INPUT_STORIES),
+    ("card", CARD_STORIES),
+];
+
+pub async fn add_storybook() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Storybook 8...");
+
+    package_manager.install(
+        &[
+            "storybook@^8",
+            "@storybook/nextjs@^8",
+            "@storybook/react@^8",
+            "@storybook/blocks@^8",
+            "@storybook/addon-essentials@^8",
+            "@storybook/addon-interactions@^8",
+            "@storybook/test@^8",
+        ],
+        true,
+    )?;
+
+    pb.set_message("Creating .storybook configuration...");
+
+    let components_path = project_structure.get_components_path();
+    change_set::create_dir_all(project_file(".storybook")).context("Failed to create .storybook directory")?;
+    file_writer::write(project_file(".storybook/main.ts"), main_config(&project_structure.relative_path(&components_path)))
+        .context("Failed to create .storybook/main.ts")?;
+    file_writer::write(
+        project_file(".storybook/preview.ts"),
+        PREVIEW_CONFIG.replace("__GLOBALS_CSS__", &format!("../{}", project_structure.relative_path(&project_structure.get_globals_css_path()))),
+    )
+    .context("Failed to create .storybook/preview.ts")?;
+
+    pb.set_message("Creating example stories...");
+
+    change_set::create_dir_all(&components_path).context("Failed to create components directory")?;
+    file_writer::write(components_path.join("introduction.mdx"), INTRODUCTION_DOCS)
+        .context("Failed to create introduction.mdx")?;
+
+    let ui_path = components_path.join("ui");
+    let mut created_stories = Vec::new();
+    for (component, stories) in COMPONENT_STORIES {
+        let story_path = ui_path.join(format!("{}.stories.tsx", component));
+        if ui_path.join(format!("{}.tsx", component)).exists() && !story_path.exists() {
+            file_writer::write(&story_path, stories)
+                .context(format!("Failed to create {}", story_path.display()))?;
+            created_stories.push(story_path);
+        }
+    }
+
+    pb.set_message("Updating package.json scripts...");
+
+    package_json::add_scripts(&[
+        ("storybook", "storybook dev -p 6006"),
+        ("build-storybook", "storybook build"),
+    ])?;
+
+    gitignore::add_entries("Storybook", &["/storybook-static", "*storybook.log"])?;
+
+    pb.finish_with_message("Storybook set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• .storybook/main.ts - Next.js framework preset with essentials and interactions");
+    println!("• .storybook/preview.ts - Loads globals.css so Tailwind styles apply");
+    println!("• {}/introduction.mdx - Docs landing page", components_path.display());
+    for story in &created_stories {
+        println!("• {} - Example stories", story.display());
+    }
+
+    if created_stories.is_empty() {
+        println!(
+            "{}",
+            style("No shadcn/ui components found. Generate some (e.g. button, input) and re-run this feature to add stories.").yellow()
+        );
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Start Storybook: {}", package_manager.run_script("storybook"));
+    println!("2. Open the Interactions panel to step through play functions");
+    println!("3. Build a static site: {}", package_manager.run_script("build-storybook"));
+
+    Ok(())
+}
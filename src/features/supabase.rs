@@ -0,0 +1,464 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::path::Path;
+use crate::change_set;
+use crate::file_writer;
+use crate::env_file;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, slash_path, ProjectStructure};
+use crate::root_layout;
+
+// Defaults printed by `supabase start`; replace with the hosted project's values in production
+const LOCAL_API_URL: &str = "http://127.0.0.1:54321";
+
+const BROWSER_CLIENT: &str = r#"import { createBrowserClient } from "@supabase/ssr";
+import type { Database } from "./database.types";
+
+export function createClient() {
+  return createBrowserClient<Database>(
+    process.env.NEXT_PUBLIC_SUPABASE_URL!,
+    process.env.NEXT_PUBLIC_SUPABASE_ANON_KEY!,
+  );
+}
+"#;
+
+const SERVER_CLIENT: &str = r#"import { createServerClient } from "@supabase/ssr";
+import { cookies } from "next/headers";
+import type { Database } from "./database.types";
+
+export async function createClient() {
+  const cookieStore = await cookies();
+
+  return createServerClient<Database>(
+    process.env.NEXT_PUBLIC_SUPABASE_URL!,
+    process.env.NEXT_PUBLIC_SUPABASE_ANON_KEY!,
+    {
+      cookies: {
+        getAll() {
+          return cookieStore.getAll();
+        },
+        setAll(cookiesToSet) {
+          try {
+            cookiesToSet.forEach(({ name, value, options }) => cookieStore.set(name, value, options));
+          } catch {
+            // Server Components cannot set cookies; the middleware refreshes the session instead
+          }
+        },
+      },
+    },
+  );
+}
+"#;
+
+const SESSION_MIDDLEWARE: &str = r#"import { createServerClient } from "@supabase/ssr";
+import { type NextRequest, NextResponse } from "next/server";
+import type { Database } from "./database.types";
+
+export async function updateSession(request: NextRequest) {
+  let supabaseResponse = NextResponse.next({ request });
+
+  const supabase = createServerClient<Database>(
+    process.env.NEXT_PUBLIC_SUPABASE_URL!,
+    process.env.NEXT_PUBLIC_SUPABASE_ANON_KEY!,
+    {
+      cookies: {
+        getAll() {
+          return request.cookies.getAll();
+        },
+        setAll(cookiesToSet) {
+          cookiesToSet.forEach(({ name, value }) => request.cookies.set(name, value));
+          supabaseResponse = NextResponse.next({ request });
+          cookiesToSet.forEach(({ name, value, options }) => supabaseResponse.cookies.set(name, value, options));
+        },
+      },
+    },
+  );
+
+  // Refreshes an expired auth token. Keep this directly after createServerClient.
+  await supabase.auth.getUser();
+
+  return supabaseResponse;
+}
+"#;
+
+const MIDDLEWARE: &str = r#"import type { NextRequest } from "next/server";
+import { updateSession } from "@/lib/supabase/middleware";
+
+export async function middleware(request: NextRequest) {
+  return await updateSession(request);
+}
+
+export const config = {
+  matcher: ["/((?!_next/static|_next/image|favicon.ico|.*\\.(?:svg|png|jpg|jpeg|gif|webp)$).*)"],
+};
+"#;
+
+const DATABASE_TYPES: &str = r#"// Placeholder matching the starter migration. Regenerate after schema changes with the
+// db:types script (requires `supabase start`).
+export type Json = string | number | boolean | null | { [key: string]: Json | undefined } | Json[];
+
+export type Database = {
+  public: {
+    Tables: {
+      profiles: {
+        Row: {
+          id: string;
+          full_name: string | null;
+          avatar_url: string | null;
+          updated_at: string;
+        };
+        Insert: {
+          id: string;
+          full_name?: string | null;
+          avatar_url?: string | null;
+          updated_at?: string;
+        };
+        Update: {
+          id?: string;
+          full_name?: string | null;
+          avatar_url?: string | null;
+          updated_at?: string;
+        };
+        Relationships: [];
+      };
+    };
+    Views: { [_ in never]: never };
+    Functions: { [_ in never]: never };
+    Enums: { [_ in never]: never };
+    CompositeTypes: { [_ in never]: never };
+  };
+};
+"#;
+
+const STORAGE_HELPERS: &str = r#"import type { SupabaseClient } from "@supabase/supabase-js";
+import type { Database } from "./database.types";
+
+type Client = SupabaseClient<Database>;
+
+export const AVATARS_BUCKET = "avatars";
+
+/**
+ * Uploads into `<userId>/`, the folder the starter storage policies let each user write to.
+ * Returns the object path.
+ */
+export async function uploadFile(supabase: Client, bucket: string, userId: string, file: File) {
+  const extension = file.name.includes(".") ? `.${file.name.split(".").pop()}` : "";
+  const path = `${userId}/${crypto.randomUUID()}${extension}`;
+
+  const { data, error } = await supabase.storage.from(bucket).upload(path, file, {
+    cacheControl: "3600",
+    contentType: file.type,
+    upsert: false,
+  });
+  if (error) throw error;
+
+  return data.path;
+}
+
+export function getPublicUrl(supabase: Client, bucket: string, path: string) {
+  return supabase.storage.from(bucket).getPublicUrl(path).data.publicUrl;
+}
+
+/** Temporary URL for files in private buckets */
+export async function createSignedUrl(supabase: Client, bucket: string, path: string, expiresIn = 3600) {
+  const { data, error } = await supabase.storage.from(bucket).createSignedUrl(path, expiresIn);
+  if (error) throw error;
+
+  return data.signedUrl;
+}
+
+export async function removeFiles(supabase: Client, bucket: string, paths: string[]) {
+  const { error } = await supabase.storage.from(bucket).remove(paths);
+  if (error) throw error;
+}
+"#;
+
+const STARTER_MIGRATION: &str = r#"-- Profiles: one row per auth user, readable by everyone and editable by its owner
+create table public.profiles (
+  id uuid primary key references auth.users on delete cascade,
+  full_name text,
+  avatar_url text,
+  updated_at timestamptz not null default now()
+);
+
+alter table public.profiles enable row level security;
+
+create policy "Profiles are viewable by everyone"
+  on public.profiles for select
+  using (true);
+
+create policy "Users can insert their own profile"
+  on public.profiles for insert
+  with check ((select auth.uid()) = id);
+
+create policy "Users can update their own profile"
+  on public.profiles for update
+  using ((select auth.uid()) = id);
+
+-- Create a profile whenever a user signs up
+create function public.handle_new_user()
+returns trigger
+language plpgsql
+security definer set search_path = ''
+as $$
+begin
+  insert into public.profiles (id, full_name, avatar_url)
+  values (new.id, new.raw_user_meta_data ->> 'full_name', new.raw_user_meta_data ->> 'avatar_url');
+  return new;
+end;
+$$;
+
+create trigger on_auth_user_created
+  after insert on auth.users
+  for each row execute procedure public.handle_new_user();
+
+-- Storage: public avatars bucket where users manage files in their own folder (<user id>/...)
+insert into storage.buckets (id, name, public)
+values ('avatars', 'avatars', true);
+
+create policy "Avatar images are publicly accessible"
+  on storage.objects for select
+  using (bucket_id = 'avatars');
+
+create policy "Users can upload their own avatars"
+  on storage.objects for insert to authenticated
+  with check (bucket_id = 'avatars' and (storage.foldername(name))[1] = (select auth.uid())::text);
+
+create policy "Users can update their own avatars"
+  on storage.objects for update to authenticated
+  using (bucket_id = 'avatars' and (storage.foldername(name))[1] = (select auth.uid())::text);
+
+create policy "Users can delete their own avatars"
+  on storage.objects for delete to authenticated
+  using (bucket_id = 'avatars' and (storage.foldername(name))[1] = (select auth.uid())::text);
+"#;
+
+const LOGIN_ACTIONS: &str = r#""use server";
+
+import { redirect } from "next/navigation";
+import { createClient } from "@/lib/supabase/server";
+
+function credentials(formData: FormData) {
+  return {
+    email: String(formData.get("email") ?? ""),
+    password: String(formData.get("password") ?? ""),
+  };
+}
+
+export async function login(formData: FormData) {
+  const supabase = await createClient();
+  const { error } = await supabase.auth.signInWithPassword(credentials(formData));
+
+  if (error) {
+    redirect(`/login?error=${encodeURIComponent(error.message)}`);
+  }
+  redirect("/");
+}
+
+export async function signup(formData: FormData) {
+  const supabase = await createClient();
+  const { error } = await supabase.auth.signUp(credentials(formData));
+
+  if (error) {
+    redirect(`/login?error=${encodeURIComponent(error.message)}`);
+  }
+  redirect(`/login?message=${encodeURIComponent("Check your email to confirm your account")}`);
+}
+
+export async function signOut() {
+  const supabase = await createClient();
+  await supabase.auth.signOut();
+  redirect("/login");
+}
+"#;
+
+const LOGIN_PAGE: &str = r#"import { login, signup } from "./actions";
+
+export default async function LoginPage({
+  searchParams,
+}: {
+  searchParams: Promise<{ error?: string; message?: string }>;
+}) {
+  const { error, message } = await searchParams;
+
+  return (
+    <main className="mx-auto flex min-h-screen max-w-sm flex-col justify-center gap-6 px-4">
+      <h1 className="text-2xl font-semibold">Sign in</h1>
+      {error ? <p className="text-sm text-red-600">{error}</p> : null}
+      {message ? <p className="text-sm text-muted-foreground">{message}</p> : null}
+
+      <form className="flex flex-col gap-3">
+        <label className="flex flex-col gap-1 text-sm">
+          Email
+          <input name="email" type="email" autoComplete="email" required className="rounded-md border px-3 py-2" />
+        </label>
+        <label className="flex flex-col gap-1 text-sm">
+          Password
+          <input
+            name="password"
+            type="password"
+            autoComplete="current-password"
+            minLength={6}
+            required
+            className="rounded-md border px-3 py-2"
+          />
+        </label>
+        <div className="flex gap-2">
+          <button formAction={login} className="flex-1 rounded-md bg-primary px-3 py-2 text-sm text-primary-foreground">
+            Sign in
+          </button>
+          <button formAction={signup} className="flex-1 rounded-md border px-3 py-2 text-sm">
+            Sign up
+          </button>
+        </div>
+      </form>
+    </main>
+  );
+}
+"#;
+
+const CONFIRM_ROUTE: &str = r#"import type { EmailOtpType } from "@supabase/supabase-js";
+import { redirect } from "next/navigation";
+import type { NextRequest } from "next/server";
+import { createClient } from "@/lib/supabase/server";
+
+// Target of the confirmation email link: {{ .SiteURL }}/auth/confirm?token_hash={{ .TokenHash }}&type=email
+export async function GET(request: NextRequest) {
+  const { searchParams } = request.nextUrl;
+  const tokenHash = searchParams.get("token_hash");
+  const type = searchParams.get("type") as EmailOtpType | null;
+  const next = searchParams.get("next") ?? "/";
+
+  if (tokenHash && type) {
+    const supabase = await createClient();
+    const { error } = await supabase.auth.verifyOtp({ type, token_hash: tokenHash });
+    if (!error) {
+      redirect(next.startsWith("/") ? next : "/");
+    }
+  }
+
+  redirect(`/login?error=${encodeURIComponent("Could not verify your email")}`);
+}
+"#;
+
+pub async fn add_supabase() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Supabase packages...");
+
+    package_manager.install(&["@supabase/supabase-js", "@supabase/ssr"], false)?;
+    package_manager.install(&["supabase"], true)?;
+
+    pb.set_message("Creating Supabase clients...");
+
+    let supabase_lib_path = project_structure.get_lib_path().join("supabase");
+    change_set::create_dir_all(&supabase_lib_path).context("Failed to create lib/supabase directory")?;
+    file_writer::write(supabase_lib_path.join("client.ts"), BROWSER_CLIENT)
+        .context("Failed to create client.ts")?;
+    file_writer::write(supabase_lib_path.join("server.ts"), SERVER_CLIENT)
+        .context("Failed to create server.ts")?;
+    file_writer::write(supabase_lib_path.join("middleware.ts"), SESSION_MIDDLEWARE)
+        .context("Failed to create middleware.ts")?;
+    file_writer::write(supabase_lib_path.join("storage.ts"), STORAGE_HELPERS)
+        .context("Failed to create storage.ts")?;
+
+    // Keep types generated by an earlier run of db:types
+    let types_path = supabase_lib_path.join("database.types.ts");
+    if !types_path.exists() {
+        file_writer::write(&types_path, DATABASE_TYPES)
+            .context("Failed to create database.types.ts")?;
+    }
+
+    pb.set_message("Adding session middleware...");
+
+    let middleware_path = project_structure.get_middleware_path();
+    let middleware_created = !middleware_path.exists();
+    if middleware_created {
+        file_writer::write(&middleware_path, MIDDLEWARE)
+            .context(format!("Failed to create {}", middleware_path.display()))?;
+    }
+
+    pb.set_message("Creating auth routes...");
+
+    let login_path = root_layout::routes_dir(&project_structure).join("login");
+    change_set::create_dir_all(&login_path).context("Failed to create login route directory")?;
+    file_writer::write(login_path.join("page.tsx"), LOGIN_PAGE)
+        .context("Failed to create login page")?;
+    file_writer::write(login_path.join("actions.ts"), LOGIN_ACTIONS)
+        .context("Failed to create login actions")?;
+
+    // Email links hit this route directly, so it stays outside any [locale] segment
+    let confirm_path = project_structure.get_app_path().join("auth/confirm");
+    change_set::create_dir_all(&confirm_path).context("Failed to create auth/confirm directory")?;
+    file_writer::write(confirm_path.join("route.ts"), CONFIRM_ROUTE)
+        .context("Failed to create auth/confirm route")?;
+
+    pb.set_message("Setting up local development...");
+
+    if !project_file("supabase/config.toml").exists() {
+        package_manager.exec(&["supabase", "init"]).context("Failed to run supabase init")?;
+    }
+    change_set::create_dir_all(project_file("supabase/migrations")).context("Failed to create supabase/migrations")?;
+    let migration_path = "supabase/migrations/0001_profiles_and_storage.sql";
+    if !Path::new(migration_path).exists() {
+        file_writer::write(migration_path, STARTER_MIGRATION)
+            .context("Failed to create the starter migration")?;
+    }
+
+    let types_script = format!("supabase gen types typescript --local > {}", project_structure.relative_path(&types_path));
+    package_json::add_scripts(&[
+        ("supabase:start", "supabase start"),
+        ("supabase:stop", "supabase stop"),
+        ("db:reset", "supabase db reset"),
+        ("db:types", &types_script),
+    ])?;
+
+    env_file::add_env_vars(
+        "supabase",
+        "Supabase",
+        &[
+            ("NEXT_PUBLIC_SUPABASE_URL", LOCAL_API_URL),
+            ("NEXT_PUBLIC_SUPABASE_ANON_KEY", "your-anon-key"),
+        ],
+    )?;
+
+    pb.finish_with_message("Supabase set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/client.ts and server.ts - Typed browser and server clients", supabase_lib_path.display());
+    println!("• {}/middleware.ts - Session refresh", supabase_lib_path.display());
+    println!("• {}/storage.ts - Upload, public URL, signed URL and delete helpers", supabase_lib_path.display());
+    println!("• {} - Generated database types", slash_path(&types_path));
+    if middleware_created {
+        println!("• {} - Refreshes the Supabase session on every request", middleware_path.display());
+    } else {
+        println!(
+            "{}",
+            style(format!("• {} already exists; call updateSession(request) from it to refresh sessions", middleware_path.display())).yellow()
+        );
+    }
+    println!("• {}/ - Email and password sign-in with server actions", login_path.display());
+    println!("• {}/route.ts - Email confirmation handler", confirm_path.display());
+    println!("• {} - Profiles table, avatars bucket and RLS policies", migration_path);
+    println!("• .env - Supabase URL and anon key");
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Start the local stack: {}", package_manager.run_script("supabase:start"));
+    println!("2. Copy the printed API URL and anon key into .env");
+    println!("3. Regenerate types after schema changes: {}", package_manager.run_script("db:types"));
+
+    Ok(())
+}
@@ -0,0 +1,544 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::features::drizzle;
+use crate::gitignore;
+use crate::manifest::ProjectManifest;
+use crate::package_json;
+use crate::prompt;
+use crate::project_structure::project_file;
+
+#[derive(Debug, Clone, PartialEq)]
+enum CloudTarget {
+    Vercel,
+    Aws,
+    Cloudflare,
+}
+
+impl CloudTarget {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CloudTarget::Vercel => "Vercel",
+            CloudTarget::Aws => "AWS",
+            CloudTarget::Cloudflare => "Cloudflare",
+        }
+    }
+
+    fn get_description(&self) -> &'static str {
+        match self {
+            CloudTarget::Vercel => "Vercel project, environment variables and domain",
+            CloudTarget::Aws => "App Runner service from an ECR image with Route 53 DNS",
+            CloudTarget::Cloudflare => "Workers custom domain, secrets and R2 cache bucket",
+        }
+    }
+
+    fn databases(&self) -> Vec<Database> {
+        match self {
+            CloudTarget::Aws => vec![Database::Rds, Database::Neon, Database::None],
+            _ => vec![Database::Neon, Database::None],
+        }
+    }
+
+    fn required_providers(&self, database: &Database) -> String {
+        let mut providers = match self {
+            CloudTarget::Vercel => r#"    vercel = {
+      source  = "vercel/vercel"
+      version = "~> 2.0"
+    }
+"#
+            .to_string(),
+            CloudTarget::Aws => r#"    aws = {
+      source  = "hashicorp/aws"
+      version = "~> 5.0"
+    }
+"#
+            .to_string(),
+            CloudTarget::Cloudflare => r#"    cloudflare = {
+      source  = "cloudflare/cloudflare"
+      version = "~> 4.0"
+    }
+"#
+            .to_string(),
+        };
+        if *database == Database::Neon {
+            providers.push_str(
+                r#"    neon = {
+      source  = "kislerdm/neon"
+      version = "~> 0.6"
+    }
+"#,
+            );
+        }
+        providers
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Database {
+    Neon,
+    Rds,
+    None,
+}
+
+impl Database {
+    fn get_description(&self) -> &'static str {
+        match self {
+            Database::Neon => "Neon - serverless Postgres",
+            Database::Rds => "RDS - managed Postgres on AWS",
+            Database::None => "None - manage the database elsewhere",
+        }
+    }
+}
+
+fn versions_tf(target: &CloudTarget, database: &Database) -> String {
+    format!(
+        r#"terraform {{
+  required_version = ">= 1.5"
+
+  required_providers {{
+{}  }}
+}}
+"#,
+        target.required_providers(database)
+    )
+}
+
+fn variable_name(env_var: &str) -> String {
+    env_var.to_lowercase()
+}
+
+fn variables_tf(target: &CloudTarget, database: &Database, env_vars: &[String]) -> String {
+    let mut content = String::from(
+        r#"variable "project_name" {
+  type        = string
+  description = "Name used for the app and its resources"
+}
+
+variable "domain" {
+  type        = string
+  description = "Custom domain the app is served from"
+}
+"#,
+    );
+
+    let target_variables = match target {
+        CloudTarget::Vercel => r#"
+variable "vercel_api_token" {
+  type      = string
+  sensitive = true
+}
+
+variable "git_repository" {
+  type        = string
+  description = "GitHub repository in owner/name form"
+}
+"#,
+        CloudTarget::Aws => r#"
+variable "aws_region" {
+  type    = string
+  default = "us-east-1"
+}
+
+variable "image_tag" {
+  type    = string
+  default = "latest"
+}
+
+variable "route53_zone_id" {
+  type = string
+}
+"#,
+        CloudTarget::Cloudflare => r#"
+variable "cloudflare_api_token" {
+  type      = string
+  sensitive = true
+}
+
+variable "cloudflare_account_id" {
+  type = string
+}
+
+variable "cloudflare_zone_id" {
+  type = string
+}
+"#,
+    };
+    content.push_str(target_variables);
+
+    match database {
+        Database::Neon => content.push_str(
+            r#"
+variable "neon_api_key" {
+  type      = string
+  sensitive = true
+}
+
+variable "neon_region" {
+  type    = string
+  default = "aws-us-east-1"
+}
+"#,
+        ),
+        Database::Rds => content.push_str(
+            r#"
+variable "db_password" {
+  type      = string
+  sensitive = true
+}
+"#,
+        ),
+        Database::None => {}
+    }
+
+    for env_var in env_vars {
+        content.push_str(&format!(
+            r#"
+variable "{}" {{
+  type        = string
+  description = "{} environment variable"
+  sensitive   = {}
+}}
+"#,
+            variable_name(env_var),
+            env_var,
+            !env_var.starts_with("NEXT_PUBLIC_")
+        ));
+    }
+
+    content
+}
+
+// Builds the HCL map of environment variables passed to the app, wiring the database
+// connection string from the managed database when there is one.
+fn app_env_locals(database: &Database, env_vars: &[String], database_env: &str) -> String {
+    let mut entries: Vec<String> = env_vars
+        .iter()
+        .map(|var| format!("    {} = var.{}", var, variable_name(var)))
+        .collect();
+
+    match database {
+        Database::Neon => entries.push(format!("    {} = neon_project.db.connection_uri", database_env)),
+        Database::Rds => entries.push(format!(
+            "    {} = \"postgresql://app:${{var.db_password}}@${{aws_db_instance.db.endpoint}}/app\"",
+            database_env
+        )),
+        Database::None => {}
+    }
+
+    format!("locals {{\n  app_env = {{\n{}\n  }}\n}}\n", entries.join("\n"))
+}
+
+fn database_tf(database: &Database) -> &'static str {
+    match database {
+        Database::Neon => r#"
+provider "neon" {
+  api_key = var.neon_api_key
+}
+
+resource "neon_project" "db" {
+  name      = var.project_name
+  region_id = var.neon_region
+}
+"#,
+        Database::Rds => r#"
+resource "aws_db_instance" "db" {
+  identifier          = "${var.project_name}-db"
+  engine              = "postgres"
+  engine_version      = "16"
+  instance_class      = "db.t4g.micro"
+  allocated_storage   = 20
+  db_name             = "app"
+  username            = "app"
+  password            = var.db_password
+  skip_final_snapshot = true
+}
+"#,
+        Database::None => "",
+    }
+}
+
+fn main_tf(target: &CloudTarget) -> &'static str {
+    match target {
+        CloudTarget::Vercel => r#"
+provider "vercel" {
+  api_token = var.vercel_api_token
+}
+
+resource "vercel_project" "app" {
+  name      = var.project_name
+  framework = "nextjs"
+
+  git_repository = {
+    type = "github"
+    repo = var.git_repository
+  }
+}
+
+resource "vercel_project_environment_variable" "app" {
+  for_each = local.app_env
+
+  project_id = vercel_project.app.id
+  key        = each.key
+  value      = each.value
+  target     = ["production", "preview"]
+  sensitive  = !startswith(each.key, "NEXT_PUBLIC_")
+}
+
+resource "vercel_project_domain" "app" {
+  project_id = vercel_project.app.id
+  domain     = var.domain
+}
+"#,
+        CloudTarget::Aws => r#"
+provider "aws" {
+  region = var.aws_region
+}
+
+resource "aws_ecr_repository" "app" {
+  name = var.project_name
+}
+
+resource "aws_iam_role" "apprunner_ecr" {
+  name = "${var.project_name}-apprunner-ecr"
+
+  assume_role_policy = jsonencode({
+    Version = "2012-10-17"
+    Statement = [{
+      Effect    = "Allow"
+      Principal = { Service = "build.apprunner.amazonaws.com" }
+      Action    = "sts:AssumeRole"
+    }]
+  })
+}
+
+resource "aws_iam_role_policy_attachment" "apprunner_ecr" {
+  role       = aws_iam_role.apprunner_ecr.name
+  policy_arn = "arn:aws:iam::aws:policy/service-role/AWSAppRunnerServicePolicyForECRAccess"
+}
+
+resource "aws_apprunner_service" "app" {
+  service_name = var.project_name
+
+  source_configuration {
+    authentication_configuration {
+      access_role_arn = aws_iam_role.apprunner_ecr.arn
+    }
+
+    image_repository {
+      image_identifier      = "${aws_ecr_repository.app.repository_url}:${var.image_tag}"
+      image_repository_type = "ECR"
+
+      image_configuration {
+        port                          = "3000"
+        runtime_environment_variables = local.app_env
+      }
+    }
+  }
+
+  health_check_configuration {
+    protocol = "HTTP"
+    path     = "/"
+  }
+}
+
+resource "aws_apprunner_custom_domain_association" "app" {
+  service_arn = aws_apprunner_service.app.arn
+  domain_name = var.domain
+}
+
+resource "aws_route53_record" "app" {
+  zone_id = var.route53_zone_id
+  name    = var.domain
+  type    = "CNAME"
+  ttl     = 300
+  records = [aws_apprunner_custom_domain_association.app.dns_target]
+}
+"#,
+        CloudTarget::Cloudflare => r#"
+provider "cloudflare" {
+  api_token = var.cloudflare_api_token
+}
+
+# The Worker script itself is deployed by `opennextjs-cloudflare deploy`
+resource "cloudflare_r2_bucket" "cache" {
+  account_id = var.cloudflare_account_id
+  name       = "${var.project_name}-cache"
+}
+
+resource "cloudflare_worker_secret" "app" {
+  for_each = local.app_env
+
+  account_id  = var.cloudflare_account_id
+  script_name = var.project_name
+  name        = each.key
+  secret_text = each.value
+}
+
+resource "cloudflare_workers_custom_domain" "app" {
+  account_id  = var.cloudflare_account_id
+  zone_id     = var.cloudflare_zone_id
+  hostname    = var.domain
+  service     = var.project_name
+  environment = "production"
+}
+"#,
+    }
+}
+
+fn outputs_tf(target: &CloudTarget, database: &Database) -> String {
+    let mut content = match target {
+        CloudTarget::Vercel => r#"output "project_id" {
+  value = vercel_project.app.id
+}
+"#
+        .to_string(),
+        CloudTarget::Aws => r#"output "ecr_repository_url" {
+  value = aws_ecr_repository.app.repository_url
+}
+
+output "service_url" {
+  value = aws_apprunner_service.app.service_url
+}
+"#
+        .to_string(),
+        CloudTarget::Cloudflare => r#"output "cache_bucket" {
+  value = cloudflare_r2_bucket.cache.name
+}
+"#
+        .to_string(),
+    };
+
+    match database {
+        Database::Neon => content.push_str(
+            r#"
+output "database_url" {
+  value     = neon_project.db.connection_uri
+  sensitive = true
+}
+"#,
+        ),
+        Database::Rds => content.push_str(
+            r#"
+output "database_endpoint" {
+  value = aws_db_instance.db.endpoint
+}
+"#,
+        ),
+        Database::None => {}
+    }
+
+    content
+}
+
+fn tfvars_example(target: &CloudTarget, database: &Database, project_name: &str, env_vars: &[String]) -> String {
+    let mut content = format!(
+        "project_name = \"{}\"\ndomain       = \"{}.example.com\"\n",
+        project_name, project_name
+    );
+
+    content.push_str(match target {
+        CloudTarget::Vercel => "\nvercel_api_token = \"\"\ngit_repository   = \"your-org/your-repo\"\n",
+        CloudTarget::Aws => "\naws_region      = \"us-east-1\"\nroute53_zone_id = \"\"\n",
+        CloudTarget::Cloudflare => "\ncloudflare_api_token  = \"\"\ncloudflare_account_id = \"\"\ncloudflare_zone_id    = \"\"\n",
+    });
+
+    match database {
+        Database::Neon => content.push_str("\nneon_api_key = \"\"\n"),
+        Database::Rds => content.push_str("\ndb_password = \"\"\n"),
+        Database::None => {}
+    }
+
+    if !env_vars.is_empty() {
+        content.push_str("\n# Required by installed nstack features\n");
+        for var in env_vars {
+            content.push_str(&format!("{} = \"\"\n", variable_name(var)));
+        }
+    }
+
+    content
+}
+
+pub async fn add_terraform() -> Result<()> {
+    let targets = [CloudTarget::Vercel, CloudTarget::Aws, CloudTarget::Cloudflare];
+    let target_names: Vec<String> = targets
+        .iter()
+        .map(|t| format!("{} - {}", t.as_str(), t.get_description()))
+        .collect();
+
+    let selection = prompt::select("cloud", "Select the cloud to provision", &target_names, 0)?;
+    let target = targets[selection].clone();
+
+    let drizzle_installed = ProjectManifest::is_feature_installed("drizzle");
+    let databases = target.databases();
+    let database_names: Vec<&str> = databases.iter().map(|d| d.get_description()).collect();
+    let database_default = if drizzle_installed { 0 } else { databases.len() - 1 };
+
+    let selection = prompt::select("database", "Provision a database?", &database_names, database_default)?;
+    let database = databases[selection].clone();
+
+    let project_name = prompt::input(
+        "project_name",
+        "Project name",
+        &package_json::package_name().unwrap_or_else(|| "my-app".to_string()),
+    )?;
+
+    let database_env = drizzle::installed_env_variable();
+
+    // The managed database provides the connection string, so it doesn't need its own variable
+    let env_vars: Vec<String> = ProjectManifest::load()?
+        .required_env_vars()
+        .into_iter()
+        .map(|(_, var)| var)
+        .filter(|var| database == Database::None || var != database_env)
+        .collect();
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message(format!("Creating Terraform configuration for {}...", target.as_str()));
+
+    change_set::create_dir_all(project_file("terraform")).context("Failed to create terraform directory")?;
+
+    let main = format!(
+        "{}{}{}",
+        app_env_locals(&database, &env_vars, database_env),
+        database_tf(&database),
+        main_tf(&target)
+    );
+
+    let files = [
+        ("versions.tf", versions_tf(&target, &database)),
+        ("variables.tf", variables_tf(&target, &database, &env_vars)),
+        ("main.tf", main),
+        ("outputs.tf", outputs_tf(&target, &database)),
+        ("terraform.tfvars.example", tfvars_example(&target, &database, &project_name, &env_vars)),
+    ];
+    for (file, content) in files {
+        file_writer::write(format!("terraform/{}", file), content)
+            .context(format!("Failed to create terraform/{}", file))?;
+    }
+
+    gitignore::add_entries(
+        "Terraform",
+        &["terraform/.terraform/", "terraform/*.tfstate*", "terraform/terraform.tfvars"],
+    )?;
+
+    pb.finish_with_message("Terraform configuration created!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• terraform/versions.tf - Provider requirements");
+    println!("• terraform/main.tf - {} resources", target.as_str());
+    println!("• terraform/variables.tf - Inputs, including {} app environment variable(s)", env_vars.len());
+    println!("• terraform/outputs.tf - Useful outputs");
+    println!("• terraform/terraform.tfvars.example - Example values");
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. cp terraform/terraform.tfvars.example terraform/terraform.tfvars and fill in the values");
+    println!("2. cd terraform && terraform init");
+    println!("3. terraform plan, then terraform apply");
+    if target == CloudTarget::Aws {
+        println!("4. Push your image to the created ECR repository (see `nstack add --feature docker`)");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::path::Path;
+use crate::change_set;
+use crate::file_writer;
+use crate::globals_css;
+use crate::next_config;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::root_layout;
+use crate::tailwind::{self, TailwindVersion};
+
+// Matches next-themes' `attribute="class"`: dark styles apply under a `.dark` ancestor
+const DARK_VARIANT: &str = "@custom-variant dark (&:where(.dark, .dark *));";
+
+const THEME_PROVIDER: &str = r#""use client";
+
+import { ThemeProvider as NextThemesProvider } from "next-themes";
+import type { ComponentProps } from "react";
+
+export function ThemeProvider({ children, ...props }: ComponentProps<typeof NextThemesProvider>) {
+  return <NextThemesProvider {...props}>{children}</NextThemesProvider>;
+}
+"#;
+
+fn theme_toggle(use_shadcn_button: bool) -> String {
+    let (button_import, button_open, button_close) = if use_shadcn_button {
+        (
+            "import { Button } from \"@/components/ui/button\";\n",
+            "<Button variant=\"outline\" size=\"icon\"",
+            "</Button>",
+        )
+    } else {
+        (
+            "",
+            "<button\n      type=\"button\"\n      className=\"relative inline-flex size-9 items-center justify-center rounded-md border bg-background shadow-xs transition-colors hover:bg-accent hover:text-accent-foreground\"",
+            "</button>",
+        )
+    };
+
+    format!(
+        r#""use client";
+
+import {{ Moon, Sun }} from "lucide-react";
+import {{ useTheme }} from "next-themes";
+{}
+export function ThemeToggle() {{
+  const {{ resolvedTheme, setTheme }} = useTheme();
+
+  return (
+    {}
+      onClick={{() => setTheme(resolvedTheme === "dark" ? "light" : "dark")}}
+      aria-label="Toggle theme"
+    >
+      {{/* Both icons render on the server; CSS picks one so there is no hydration flash */}}
+      <Sun className="size-[1.2rem] scale-100 rotate-0 transition-all dark:scale-0 dark:-rotate-90" />
+      <Moon className="absolute size-[1.2rem] scale-0 rotate-90 transition-all dark:scale-100 dark:rotate-0" />
+    {}
+  );
+}}
+"#,
+        button_import, button_open, button_close
+    )
+}
+
+/// Makes Tailwind's `dark:` variant follow the `.dark` class set by next-themes.
+/// Returns a description of the change, or `None` when the project was already configured.
+fn ensure_class_dark_mode(project_structure: &ProjectStructure) -> Result<Option<String>> {
+    if TailwindVersion::detect(project_structure) == Some(TailwindVersion::V3) {
+        let Some(config_path) = tailwind::find_config() else {
+            return Ok(None);
+        };
+        if !next_config::set_option_in(&config_path, &["darkMode"], "\"class\"")? {
+            return Ok(None);
+        }
+        return Ok(Some(format!("{} - darkMode: \"class\"", config_path.display())));
+    }
+
+    let css_path = project_structure.get_globals_css_path();
+    if !globals_css::merge(project_structure, DARK_VARIANT)? {
+        return Ok(None);
+    }
+
+    Ok(Some(format!("{} - class-based dark variant", css_path.display())))
+}
+
+pub async fn add_theme() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let components_path = project_structure.get_components_path();
+    let use_shadcn_button = Path::new(&components_path.join("ui/button.tsx")).exists();
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing next-themes...");
+
+    let mut dependencies = vec!["next-themes"];
+    if !package_json::has_dependency("lucide-react") {
+        dependencies.push("lucide-react");
+    }
+    package_manager.install(&dependencies, false)?;
+
+    pb.set_message("Creating theme components...");
+
+    change_set::create_dir_all(&components_path).context("Failed to create components directory")?;
+    file_writer::write(components_path.join("theme-provider.tsx"), THEME_PROVIDER)
+        .context("Failed to create theme-provider.tsx")?;
+    file_writer::write(components_path.join("theme-toggle.tsx"), theme_toggle(use_shadcn_button))
+        .context("Failed to create theme-toggle.tsx")?;
+
+    pb.set_message("Adding ThemeProvider to the root layout...");
+
+    root_layout::add_import(&project_structure, "import { ThemeProvider } from \"@/components/theme-provider\";")?;
+    // next-themes sets the class on <html> before hydration, which React would otherwise warn about
+    root_layout::add_html_attribute(&project_structure, "suppressHydrationWarning")?;
+    root_layout::wrap_children(
+        &project_structure,
+        "<ThemeProvider attribute=\"class\" defaultTheme=\"system\" enableSystem disableTransitionOnChange>",
+        "</ThemeProvider>",
+    )?;
+
+    pb.set_message("Configuring Tailwind dark mode...");
+
+    let tailwind_change = ensure_class_dark_mode(&project_structure)?;
+
+    pb.finish_with_message("Dark mode set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/theme-provider.tsx - next-themes provider, added to the root layout", components_path.display());
+    println!(
+        "• {}/theme-toggle.tsx - Light/dark toggle{}",
+        components_path.display(),
+        if use_shadcn_button { " using the shadcn Button" } else { "" }
+    );
+    if let Some(change) = tailwind_change {
+        println!("• {}", change);
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Render <ThemeToggle /> in your header or navigation");
+    println!("2. Style dark mode with `dark:` variants or .dark CSS variables");
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::path::Path;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::root_layout;
+
+const THEMED_TOASTER: &str = r#""use client";
+
+import { useTheme } from "next-themes";
+import { Toaster as Sonner, type ToasterProps } from "sonner";
+
+export function Toaster(props: ToasterProps) {
+  const { resolvedTheme } = useTheme();
+
+  return <Sonner theme={resolvedTheme === "dark" ? "dark" : "light"} richColors closeButton {...props} />;
+}
+"#;
+
+const SYSTEM_TOASTER: &str = r#""use client";
+
+import { Toaster as Sonner, type ToasterProps } from "sonner";
+
+// Follows the OS color scheme; run `nstack add --feature theme` to follow the app theme instead
+export function Toaster(props: ToasterProps) {
+  return <Sonner theme="system" richColors closeButton {...props} />;
+}
+"#;
+
+const TOAST_HELPERS: &str = r#"import { type ExternalToast, toast } from "sonner";
+
+export class ApiError extends Error {
+  constructor(
+    message: string,
+    readonly status: number,
+  ) {
+    super(message);
+    this.name = "ApiError";
+  }
+}
+
+export function errorMessage(error: unknown, fallback = "Something went wrong"): string {
+  if (error instanceof Error && error.message) return error.message;
+  if (typeof error === "string" && error) return error;
+  return fallback;
+}
+
+type PromiseMessages<T> = {
+  loading: string;
+  success: string | ((data: T) => string);
+  error?: string | ((error: unknown) => string);
+};
+
+export const notify = {
+  success: (message: string, options?: ExternalToast) => toast.success(message, options),
+  info: (message: string, options?: ExternalToast) => toast.info(message, options),
+  /** Accepts anything thrown, including ApiError from fetchJson */
+  error: (error: unknown, options?: ExternalToast) => toast.error(errorMessage(error), options),
+  promise: <T>(promise: Promise<T>, messages: PromiseMessages<T>) =>
+    toast.promise(promise, {
+      loading: messages.loading,
+      success: messages.success,
+      error: messages.error ?? ((error: unknown) => errorMessage(error)),
+    }),
+};
+
+/**
+ * fetch for JSON APIs that throws an ApiError carrying the server's `error` or `message`
+ * field on non-2xx responses, ready to pass to notify.error.
+ */
+export async function fetchJson<T>(input: RequestInfo | URL, init?: RequestInit): Promise<T> {
+  const response = await fetch(input, {
+    ...init,
+    headers: { "Content-Type": "application/json", ...init?.headers },
+  });
+
+  if (!response.ok) {
+    const body = (await response.json().catch(() => null)) as { error?: string; message?: string } | null;
+    throw new ApiError(body?.error ?? body?.message ?? response.statusText, response.status);
+  }
+
+  return (await response.json()) as T;
+}
+"#;
+
+fn toast_demo(has_users_api: bool) -> String {
+    // With drizzle's users API, the form shows real success and validation error responses
+    let (helpers, request) = if has_users_api {
+        ("fetchJson, notify", r#"fetchJson<unknown>("/api/users", {
+      method: "POST",
+      body: JSON.stringify({ name, email }),
+    })"#)
+    } else {
+        ("notify", r#"new Promise<void>((resolve, reject) =>
+      setTimeout(() => (email.endsWith("@example.com") ? resolve() : reject(new Error("Use an @example.com address"))), 800),
+    )"#)
+    };
+
+    format!(
+        r#""use client";
+
+import {{ type FormEvent, useState }} from "react";
+import {{ {} }} from "@/lib/toast";
+
+export function ToastDemo() {{
+  const [pending, setPending] = useState(false);
+
+  async function handleSubmit(event: FormEvent<HTMLFormElement>) {{
+    event.preventDefault();
+    const formElement = event.currentTarget;
+    const form = new FormData(formElement);
+    const name = String(form.get("name") ?? "");
+    const email = String(form.get("email") ?? "");
+
+    const request = {};
+
+    setPending(true);
+    notify.promise(request, {{
+      loading: "Saving...",
+      success: `Saved ${{name || email}}`,
+    }});
+    try {{
+      await request;
+      formElement.reset();
+    }} catch {{
+      // The error toast is already shown by notify.promise
+    }} finally {{
+      setPending(false);
+    }}
+  }}
+
+  return (
+    <div className="flex max-w-sm flex-col gap-4">
+      <div className="flex gap-2">
+        <button type="button" className="rounded-md border px-3 py-1.5 text-sm" onClick={{() => notify.success("Changes saved")}}>
+          Success
+        </button>
+        <button type="button" className="rounded-md border px-3 py-1.5 text-sm" onClick={{() => notify.error(new Error("Could not reach the server"))}}>
+          Error
+        </button>
+      </div>
+      <form onSubmit={{handleSubmit}} className="flex flex-col gap-2">
+        <input name="name" placeholder="Name" className="rounded-md border px-3 py-1.5 text-sm" />
+        <input name="email" type="email" required placeholder="Email" className="rounded-md border px-3 py-1.5 text-sm" />
+        <button type="submit" disabled={{pending}} className="rounded-md bg-primary px-3 py-1.5 text-sm text-primary-foreground disabled:opacity-50">
+          Save
+        </button>
+      </form>
+    </div>
+  );
+}}
+"#,
+        helpers, request
+    )
+}
+
+pub async fn add_toasts() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let themed = package_json::has_dependency("next-themes");
+    let has_users_api = Path::new(&project_structure.get_app_path().join("api/users/route.ts")).exists();
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing sonner...");
+
+    package_manager.install(&["sonner"], false)?;
+
+    pb.set_message("Creating toast components...");
+
+    let components_path = project_structure.get_components_path();
+    let lib_path = project_structure.get_lib_path();
+    change_set::create_dir_all(&components_path).context("Failed to create components directory")?;
+    change_set::create_dir_all(&lib_path).context("Failed to create lib directory")?;
+    file_writer::write(
+        components_path.join("toaster.tsx"),
+        if themed { THEMED_TOASTER } else { SYSTEM_TOASTER },
+    )
+    .context("Failed to create toaster.tsx")?;
+    file_writer::write(lib_path.join("toast.ts"), TOAST_HELPERS)
+        .context("Failed to create toast.ts")?;
+    file_writer::write(components_path.join("toast-demo.tsx"), toast_demo(has_users_api))
+        .context("Failed to create toast-demo.tsx")?;
+
+    pb.set_message("Adding the Toaster to the root layout...");
+
+    root_layout::add_import(&project_structure, "import { Toaster } from \"@/components/toaster\";")?;
+    // useTheme only resolves inside ThemeProvider, so mount the Toaster there when it exists
+    let inside_theme_provider = root_layout::find_root_layout(&project_structure)
+        .and_then(|layout| std::fs::read_to_string(layout).ok())
+        .is_some_and(|content| content.contains("</ThemeProvider>"));
+    root_layout::append_child(
+        &project_structure,
+        if inside_theme_provider { "ThemeProvider" } else { "body" },
+        "<Toaster />",
+    )?;
+
+    pb.finish_with_message("Toasts set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!(
+        "• {}/toaster.tsx - Sonner Toaster{}, added to the root layout",
+        components_path.display(),
+        if themed { " following the next-themes theme" } else { " following the OS color scheme" }
+    );
+    println!("• {}/toast.ts - notify.success/error/promise and fetchJson with typed API errors", lib_path.display());
+    println!("• {}/toast-demo.tsx - Example buttons and form", components_path.display());
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Render <ToastDemo /> on a page to try the variants");
+    println!("2. Wrap API calls with notify.promise(fetchJson(...)) in your forms");
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::json;
+use std::path::PathBuf;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+use crate::tsconfig;
+
+const TSCONFIG_PATH: &str = "tsconfig.json";
+
+// (compiler option, description) pairs offered on top of `strict`
+const STRICT_FLAGS: [(&str, &str); 5] = [
+    ("noUncheckedIndexedAccess", "noUncheckedIndexedAccess - index access may be undefined"),
+    ("exactOptionalPropertyTypes", "exactOptionalPropertyTypes - optional props can't be set to undefined"),
+    ("verbatimModuleSyntax", "verbatimModuleSyntax - type-only imports must use `import type`"),
+    ("noImplicitOverride", "noImplicitOverride - require `override` on overridden members"),
+    ("noFallthroughCasesInSwitch", "noFallthroughCasesInSwitch - disallow switch fallthrough"),
+];
+
+const TS_RESET: &str = r#"// Improves built-in typings, e.g. JSON.parse returns unknown and .filter(Boolean) narrows
+import "@total-typescript/ts-reset";
+"#;
+
+/// Code generated by earlier nstack versions that fails under the stricter flags,
+/// as (path, old snippet, fixed snippet).
+fn generated_code_fixes(project_structure: &ProjectStructure) -> Vec<(PathBuf, &'static str, &'static str)> {
+    let og_helpers = project_structure.get_lib_path().join("og.ts");
+    vec![
+        (
+            project_structure.get_api_path().join("users/route.ts"),
+            "import { NextRequest, NextResponse } from \"next/server\";",
+            "import { type NextRequest, NextResponse } from \"next/server\";",
+        ),
+        (
+            og_helpers.clone(),
+            "  const resource = css.match(/src: url\\((.+?)\\) format\\('(opentype|truetype)'\\)/);\n  if (!resource) {",
+            "  const fontUrl = css.match(/src: url\\((.+?)\\) format\\('(opentype|truetype)'\\)/)?.[1];\n  if (!fontUrl) {",
+        ),
+        (
+            og_helpers.clone(),
+            "  const response = await fetch(resource[1]);",
+            "  const response = await fetch(fontUrl);",
+        ),
+        (
+            og_helpers.clone(),
+            "alt: params.title };\n\n  return {",
+            "alt: params.title };\n  // Spread rather than set to undefined, which exactOptionalPropertyTypes rejects\n  const description = params.subtitle ? { description: params.subtitle } : {};\n\n  return {",
+        ),
+        (
+            og_helpers.clone(),
+            "    title: params.title,\n    description: params.subtitle,",
+            "    title: params.title,\n    ...description,",
+        ),
+        (
+            og_helpers,
+            "      description: params.subtitle,",
+            "      ...description,",
+        ),
+        (
+            project_structure.get_api_path().join("og/route.tsx"),
+            "<OgTemplate title={title} subtitle={subtitle} type={type} price={price} />",
+            "<OgTemplate title={title} type={type} {...(subtitle ? { subtitle } : {})} {...(price ? { price } : {})} />",
+        ),
+    ]
+}
+
+fn apply_generated_code_fixes(project_structure: &ProjectStructure) -> Result<Vec<PathBuf>> {
+    let mut fixed_files = Vec::new();
+
+    for (path, old, new) in generated_code_fixes(project_structure) {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if content.contains(old) {
+            // A targeted edit of the user's file, so it skips file_writer's overwrite prompt
+            change_set::write(&path, content.replace(old, new))
+                .context(format!("Failed to update {}", path.display()))?;
+            if !fixed_files.contains(&path) {
+                fixed_files.push(path);
+            }
+        }
+    }
+
+    Ok(fixed_files)
+}
+
+pub async fn add_ts_strict() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let tsconfig_path = project_file(TSCONFIG_PATH);
+    let content = std::fs::read_to_string(&tsconfig_path)
+        .context("Failed to read tsconfig.json. Is this a TypeScript project?")?;
+    tsconfig::parse(&content).context("Failed to parse tsconfig.json")?;
+
+    let flag_names: Vec<&str> = STRICT_FLAGS.iter().map(|(_, description)| *description).collect();
+    let defaults = vec![true; STRICT_FLAGS.len()];
+    let selections =
+        prompt::multi_select("flags", "Select the compiler flags to enable (space to toggle)", &flag_names, &defaults)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing ts-reset...");
+
+    package_manager.install(&["@total-typescript/ts-reset"], true)?;
+
+    pb.set_message("Updating tsconfig.json...");
+
+    let mut enabled_flags = vec!["strict"];
+    enabled_flags.extend(selections.iter().map(|&index| STRICT_FLAGS[index].0));
+    let options: Vec<(&str, serde_json::Value)> = enabled_flags.iter().map(|&flag| (flag, json!(true))).collect();
+
+    let updated = tsconfig::set_compiler_options(&content, &options).context("Failed to update tsconfig.json")?;
+    change_set::write(&tsconfig_path, updated).context("Failed to update tsconfig.json")?;
+
+    // tsconfig includes **/*.ts, so a root declaration file applies ts-reset everywhere
+    file_writer::write(project_file("reset.d.ts"), TS_RESET).context("Failed to create reset.d.ts")?;
+
+    pb.set_message("Fixing nstack-generated code...");
+
+    let fixed_files = apply_generated_code_fixes(&project_structure)?;
+
+    pb.set_message("Updating package.json scripts...");
+
+    package_json::add_scripts(&[("typecheck", "tsc --noEmit")])?;
+
+    pb.finish_with_message("TypeScript strictness hardened!");
+
+    println!("\n{}", style("Changes:").cyan().bold());
+    println!("• tsconfig.json - Enabled {}", enabled_flags.join(", "));
+    println!("• reset.d.ts - Loads @total-typescript/ts-reset");
+    for file in &fixed_files {
+        println!("• {} - Updated for the stricter settings", file.display());
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Find remaining errors: {}", package_manager.run_script("typecheck"));
+    println!("2. Fix or narrow the reported types before relaxing any flag");
+
+    Ok(())
+}
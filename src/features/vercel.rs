@@ -0,0 +1,208 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use serde_json::{json, Value};
+use crate::change_set;
+use crate::features::CRON_JOBS;
+use crate::file_writer;
+use crate::manifest::ProjectManifest;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, ProjectStructure};
+use crate::prompt;
+
+const REGIONS: [(&str, &str); 8] = [
+    ("iad1", "Washington, D.C., USA (default)"),
+    ("sfo1", "San Francisco, USA"),
+    ("cle1", "Cleveland, USA"),
+    ("fra1", "Frankfurt, Germany"),
+    ("lhr1", "London, United Kingdom"),
+    ("hnd1", "Tokyo, Japan"),
+    ("syd1", "Sydney, Australia"),
+    ("gru1", "São Paulo, Brazil"),
+];
+
+const ENV_PULL_SCRIPT: &str = r#"#!/usr/bin/env sh
+# Pulls environment variables from the linked Vercel project into .env.local.
+# Usage: ./scripts/vercel-env-pull.sh [development|preview|production]
+set -e
+
+ENVIRONMENT="${1:-development}"
+
+if [ ! -f .vercel/project.json ]; then
+  echo "Project is not linked to Vercel yet, running 'vercel link' first..."
+  __VERCEL__ link
+fi
+
+__VERCEL__ env pull .env.local --environment="$ENVIRONMENT" --yes
+echo "Pulled $ENVIRONMENT environment variables into .env.local"
+"#;
+
+/// The cron jobs of installed features, as `(route, schedule)`, for routes the feature
+/// actually wrote; some only write theirs for one provider.
+fn installed_cron_jobs(project_structure: &ProjectStructure) -> Vec<(&'static str, &'static str)> {
+    let app_path = project_structure.get_app_path();
+    CRON_JOBS
+        .iter()
+        .filter(|(feature, _, _)| ProjectManifest::is_feature_installed(feature))
+        .filter(|(_, route, _)| {
+            let dir = app_path.join(route.trim_start_matches('/'));
+            ["route.ts", "route.js"].iter().any(|file| dir.join(file).exists())
+        })
+        .map(|&(_, route, schedule)| (route, schedule))
+        .collect()
+}
+
+fn vercel_json(region: &str, crons: &[(&str, &str)]) -> Value {
+    let mut config = json!({
+        "$schema": "https://openapi.vercel.sh/vercel.json",
+        "regions": [region],
+        "headers": [
+            {
+                "source": "/(.*)",
+                "headers": [
+                    { "key": "X-Content-Type-Options", "value": "nosniff" },
+                    { "key": "X-Frame-Options", "value": "DENY" },
+                    { "key": "Referrer-Policy", "value": "strict-origin-when-cross-origin" }
+                ]
+            },
+            {
+                "source": "/api/(.*)",
+                "headers": [
+                    { "key": "Cache-Control", "value": "no-store" }
+                ]
+            }
+        ]
+    });
+
+    if !crons.is_empty() {
+        config["crons"] = crons
+            .iter()
+            .map(|(path, schedule)| json!({ "path": path, "schedule": schedule }))
+            .collect();
+    }
+
+    config
+}
+
+fn env_docs(required_env: &[(String, String)]) -> String {
+    let mut content = String::from(
+        r#"# Vercel environment variables
+
+Variables required by the features nstack installed in this project.
+Add each one in the Vercel dashboard (Project → Settings → Environment Variables)
+or with `vercel env add <NAME>`, then pull them locally with `scripts/vercel-env-pull.sh`.
+
+| Variable | Required by | Environments |
+| --- | --- | --- |
+"#,
+    );
+
+    for (feature, var) in required_env {
+        // NEXT_PUBLIC_ variables are inlined at build time, so they need to exist in every environment
+        let environments = if var.starts_with("NEXT_PUBLIC_") {
+            "Production, Preview, Development"
+        } else {
+            "Production, Preview"
+        };
+        content.push_str(&format!("| `{}` | {} | {} |\n", var, feature, environments));
+    }
+
+    if required_env.is_empty() {
+        content.push_str("| _none yet_ | | |\n");
+    }
+
+    content
+}
+
+pub async fn add_vercel() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    if ProjectManifest::is_feature_installed("drizzle") {
+        println!(
+            "{}",
+            style("Drizzle detected: pick the region closest to your database.").yellow()
+        );
+    }
+
+    let region_names: Vec<String> = REGIONS
+        .iter()
+        .map(|(id, name)| format!("{} - {}", id, name))
+        .collect();
+    let region_selection = prompt::select("region", "Select the function region", &region_names, 0)?;
+    let region = REGIONS[region_selection].0;
+
+    let add_env_pull = prompt::confirm("env_pull", "Add a `vercel env pull` wrapper script?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Creating vercel.json...");
+
+    let crons = installed_cron_jobs(&project_structure);
+    let config = serde_json::to_string_pretty(&vercel_json(region, &crons))?;
+    file_writer::write(project_file("vercel.json"), config + "\n").context("Failed to create vercel.json")?;
+
+    pb.set_message("Documenting required environment variables...");
+
+    let required_env = ProjectManifest::load()?.required_env_vars();
+    file_writer::write(project_file("VERCEL_ENV.md"), env_docs(&required_env))
+        .context("Failed to create VERCEL_ENV.md")?;
+
+    if add_env_pull {
+        pb.set_message("Creating env pull script...");
+
+        change_set::create_dir_all(project_file("scripts")).context("Failed to create scripts directory")?;
+        let script_path = project_file("scripts/vercel-env-pull.sh");
+        file_writer::write(
+            &script_path,
+            ENV_PULL_SCRIPT.replace("__VERCEL__", &package_manager.dlx_command("vercel")),
+        )
+        .context("Failed to create vercel-env-pull.sh")?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755))
+                .context("Failed to make vercel-env-pull.sh executable")?;
+        }
+
+        package_json::add_scripts(&[("env:pull", "sh scripts/vercel-env-pull.sh")])?;
+    }
+
+    pb.finish_with_message("Vercel configuration created!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• vercel.json - Region {}, security headers{}", region,
+        if crons.is_empty() { String::new() } else { format!(", {} cron job(s)", crons.len()) });
+    println!("• VERCEL_ENV.md - Environment variables required by installed features");
+    if add_env_pull {
+        println!("• scripts/vercel-env-pull.sh - Pulls env vars into .env.local ({} env:pull)",
+            package_manager.to_string());
+    }
+
+    if !required_env.is_empty() {
+        println!("\n{}", style("Required environment variables:").cyan().bold());
+        for (feature, var) in &required_env {
+            println!("• {} ({})", var, feature);
+        }
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Link the project: {}", package_manager.dlx_command("vercel link"));
+    println!("2. Add the variables listed in VERCEL_ENV.md to your Vercel project");
+    println!("3. Deploy: {}", package_manager.dlx_command("vercel deploy"));
+    if !crons.is_empty() {
+        println!("4. Add CRON_SECRET to your Vercel project so cron requests are authorized");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+use crate::compat::NextCapabilities;
+use crate::project_structure::{project_file, Layout, ProjectStructure};
+
+const VITEST_CONFIG: &str = r#"import { defineConfig } from "vitest/config";
+import react from "@vitejs/plugin-react";
+import tsconfigPaths from "vite-tsconfig-paths";
+
+export default defineConfig({
+  // tsconfigPaths resolves the same import aliases as tsconfig.json (e.g. "@/components")
+  plugins: [tsconfigPaths(), react()],
+  test: {
+    environment: "jsdom",
+    setupFiles: ["./vitest.setup.ts"],
+    include: ["**/*.test.{ts,tsx}"],
+    exclude: ["node_modules", ".next", "e2e"],
+    coverage: {
+      provider: "v8",
+      reporter: ["text", "html", "lcov"],
+      include: ["__SOURCE_GLOB__"],
+      exclude: ["**/*.test.{ts,tsx}", "**/*.d.ts", "**/layout.tsx"],
+    },
+  },
+});
+"#;
+
+const VITEST_SETUP: &str = r#"import "@testing-library/jest-dom/vitest";
+import { cleanup } from "@testing-library/react";
+import { afterEach } from "vitest";
+
+afterEach(() => {
+  cleanup();
+});
+"#;
+
+const COUNTER_COMPONENT: &str = r#""use client";
+
+import { useState } from "react";
+
+interface CounterProps {
+  initialCount?: number;
+}
+
+export function Counter({ initialCount = 0 }: CounterProps) {
+  const [count, setCount] = useState(initialCount);
+
+  return (
+    <div>
+      <p aria-live="polite">Count: {count}</p>
+      <button type="button" onClick={() => setCount((c) => c + 1)}>
+        Increment
+      </button>
+      <button type="button" onClick={() => setCount(initialCount)}>
+        Reset
+      </button>
+    </div>
+  );
+}
+"#;
+
+const COUNTER_TEST: &str = r#"import { render, screen } from "@testing-library/react";
+import userEvent from "@testing-library/user-event";
+import { describe, expect, it } from "vitest";
+import { Counter } from "@/components/counter";
+
+describe("Counter", () => {
+  it("renders the initial count", () => {
+    render(<Counter initialCount={5} />);
+    expect(screen.getByText("Count: 5")).toBeInTheDocument();
+  });
+
+  it("increments and resets", async () => {
+    const user = userEvent.setup();
+    render(<Counter />);
+
+    await user.click(screen.getByRole("button", { name: "Increment" }));
+    await user.click(screen.getByRole("button", { name: "Increment" }));
+    expect(screen.getByText("Count: 2")).toBeInTheDocument();
+
+    await user.click(screen.getByRole("button", { name: "Reset" }));
+    expect(screen.getByText("Count: 0")).toBeInTheDocument();
+  });
+});
+"#;
+
+const SUBSCRIBE_ACTION: &str = r#""use server";
+
+export type SubscribeState = {
+  success: boolean;
+  message: string;
+};
+
+const EMAIL_PATTERN = /^[^\s@]+@[^\s@]+\.[^\s@]+$/;
+
+export async function subscribe(
+  _previousState: SubscribeState | null,
+  formData: FormData,
+): Promise<SubscribeState> {
+  const email = formData.get("email");
+
+  if (typeof email !== "string" || !EMAIL_PATTERN.test(email)) {
+    return { success: false, message: "Please enter a valid email address." };
+  }
+
+  // Persist the subscription here (database, email provider, ...)
+  return { success: true, message: `Subscribed ${email}` };
+}
+"#;
+
+const SUBSCRIBE_TEST: &str = r#"import { describe, expect, it } from "vitest";
+import { subscribe } from "@/lib/actions/subscribe";
+
+function formData(values: Record<string, string>) {
+  const data = new FormData();
+  for (const [key, value] of Object.entries(values)) {
+    data.set(key, value);
+  }
+  return data;
+}
+
+describe("subscribe server action", () => {
+  it("rejects an invalid email", async () => {
+    const result = await subscribe(null, formData({ email: "not-an-email" }));
+    expect(result.success).toBe(false);
+  });
+
+  it("accepts a valid email", async () => {
+    const result = await subscribe(null, formData({ email: "jane@example.com" }));
+    expect(result).toEqual({ success: true, message: "Subscribed jane@example.com" });
+  });
+});
+"#;
+
+pub async fn add_vitest() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+    println!(
+        "{}",
+        style(format!(
+            "Project structure: {}",
+            format!("{:?}", project_structure.layout()).to_lowercase()
+        ))
+        .yellow()
+    );
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing Vitest and React Testing Library...");
+
+    package_manager.install(
+        &[
+            "vitest",
+            "@vitejs/plugin-react",
+            "vite-tsconfig-paths",
+            "jsdom",
+            "@testing-library/react",
+            "@testing-library/dom",
+            "@testing-library/jest-dom",
+            "@testing-library/user-event",
+            "@vitest/coverage-v8",
+        ],
+        true,
+    )?;
+
+    pb.set_message("Creating Vitest configuration...");
+
+    let source_glob = match project_structure.layout() {
+        Layout::SrcDir | Layout::SrcPagesRouter => "src/**/*.{ts,tsx}",
+        Layout::AppDir => "{app,components,lib}/**/*.{ts,tsx}",
+        Layout::PagesRouter => "{pages,components,lib}/**/*.{ts,tsx}",
+    };
+    file_writer::write(project_file("vitest.config.mts"), VITEST_CONFIG.replace("__SOURCE_GLOB__", source_glob))
+        .context("Failed to create vitest.config.mts")?;
+    file_writer::write(project_file("vitest.setup.ts"), VITEST_SETUP)
+        .context("Failed to create vitest.setup.ts")?;
+
+    pb.set_message("Creating example tests...");
+
+    let components_path = project_structure.get_components_path();
+    change_set::create_dir_all(&components_path).context("Failed to create components directory")?;
+    file_writer::write(components_path.join("counter.tsx"), COUNTER_COMPONENT)
+        .context("Failed to create counter.tsx")?;
+
+    // The server action example only makes sense where Next.js can run Server Actions
+    let server_actions = NextCapabilities::detect().is_some_and(|capabilities| capabilities.server_actions);
+    let actions_path = project_structure.get_lib_path().join("actions");
+    if server_actions {
+        change_set::create_dir_all(&actions_path).context("Failed to create lib/actions directory")?;
+        file_writer::write(actions_path.join("subscribe.ts"), SUBSCRIBE_ACTION)
+            .context("Failed to create subscribe.ts")?;
+    }
+
+    change_set::create_dir_all(project_file("__tests__")).context("Failed to create __tests__ directory")?;
+    file_writer::write(project_file("__tests__/counter.test.tsx"), COUNTER_TEST)
+        .context("Failed to create counter.test.tsx")?;
+    if server_actions {
+        file_writer::write(project_file("__tests__/subscribe.test.ts"), SUBSCRIBE_TEST)
+            .context("Failed to create subscribe.test.ts")?;
+    }
+
+    pb.set_message("Updating package.json scripts...");
+
+    package_json::add_scripts(&[
+        ("test", "vitest run"),
+        ("test:watch", "vitest"),
+        ("test:coverage", "vitest run --coverage"),
+    ])?;
+
+    pb.finish_with_message("Vitest set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• vitest.config.mts - Vitest config with React plugin and tsconfig path aliases");
+    println!("• vitest.setup.ts - jest-dom matchers and cleanup");
+    println!("• {}/counter.tsx - Example client component", components_path.display());
+    if server_actions {
+        println!("• {}/subscribe.ts - Example server action", actions_path.display());
+        println!("• __tests__/ - Example component and server action tests");
+    } else {
+        println!("• __tests__/ - Example component test");
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    println!("1. Run the tests: {}", package_manager.run_script("test"));
+    println!("2. Watch mode: {}", package_manager.run_script("test:watch"));
+    println!("3. Coverage report: {}", package_manager.run_script("test:coverage"));
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use crate::change_set;
+use crate::file_writer;
+use crate::package_manager::PackageManager;
+use crate::project_structure::ProjectStructure;
+use crate::prompt;
+use crate::root_layout;
+
+const APP_STORE: &str = r#"import { createJSONStorage, devtools, persist } from "zustand/middleware";
+import { createStore } from "zustand/vanilla";
+
+export type Preferences = {
+  compactMode: boolean;
+  sidebarOpen: boolean;
+};
+
+export type AppState = {
+  count: number;
+  preferences: Preferences;
+};
+
+export type AppActions = {
+  increment: () => void;
+  decrement: () => void;
+  setPreference: <K extends keyof Preferences>(key: K, value: Preferences[K]) => void;
+};
+
+export type AppStore = AppState & AppActions;
+
+export const defaultAppState: AppState = {
+  count: 0,
+  preferences: {
+    compactMode: false,
+    sidebarOpen: true,
+  },
+};
+
+/**
+ * Creates a new store instance. Called once per request on the server and once in the
+ * browser by AppStoreProvider, so state is never shared between users.
+ */
+export function createAppStore(initState: AppState = defaultAppState) {
+  return createStore<AppStore>()(
+    devtools(
+      persist(
+        (set) => ({
+          ...initState,
+          increment: () => set((state) => ({ count: state.count + 1 }), false, "increment"),
+          decrement: () => set((state) => ({ count: state.count - 1 }), false, "decrement"),
+          setPreference: (key, value) =>
+            set(
+              (state) => ({ preferences: { ...state.preferences, [key]: value } }),
+              false,
+              "setPreference",
+            ),
+        }),
+        {
+          name: "app-store",
+          storage: createJSONStorage(() => localStorage),
+          // Only preferences survive reloads
+          partialize: (state) => ({ preferences: state.preferences }),
+          // Rehydrated by the provider after mount to avoid hydration mismatches
+          skipHydration: true,
+        },
+      ),
+      { name: "AppStore", enabled: process.env.NODE_ENV !== "production" },
+    ),
+  );
+}
+
+export type AppStoreApi = ReturnType<typeof createAppStore>;
+"#;
+
+const APP_STORE_PROVIDER: &str = r#""use client";
+
+import { createContext, type ReactNode, useContext, useEffect, useRef } from "react";
+import { useStore } from "zustand";
+import { type AppState, type AppStore, type AppStoreApi, createAppStore } from "@/lib/stores/app-store";
+
+const AppStoreContext = createContext<AppStoreApi | undefined>(undefined);
+
+type AppStoreProviderProps = {
+  children: ReactNode;
+  initialState?: AppState;
+};
+
+export function AppStoreProvider({ children, initialState }: AppStoreProviderProps) {
+  const storeRef = useRef<AppStoreApi | null>(null);
+  if (storeRef.current === null) {
+    storeRef.current = createAppStore(initialState);
+  }
+
+  useEffect(() => {
+    void storeRef.current?.persist.rehydrate();
+  }, []);
+
+  return <AppStoreContext.Provider value={storeRef.current}>{children}</AppStoreContext.Provider>;
+}
+
+export function useAppStore<T>(selector: (store: AppStore) => T): T {
+  const store = useContext(AppStoreContext);
+  if (!store) {
+    throw new Error("useAppStore must be used within AppStoreProvider");
+  }
+  return useStore(store, selector);
+}
+"#;
+
+const STORE_DEMO: &str = r#""use client";
+
+import { useAppStore } from "@/components/providers/app-store-provider";
+
+export function StoreDemo() {
+  const count = useAppStore((state) => state.count);
+  const increment = useAppStore((state) => state.increment);
+  const decrement = useAppStore((state) => state.decrement);
+  const compactMode = useAppStore((state) => state.preferences.compactMode);
+  const setPreference = useAppStore((state) => state.setPreference);
+
+  return (
+    <div className="flex flex-col gap-4 rounded-lg border p-4">
+      <div className="flex items-center gap-2">
+        <button type="button" className="rounded border px-3 py-1" onClick={decrement}>
+          -
+        </button>
+        <span className="min-w-8 text-center tabular-nums">{count}</span>
+        <button type="button" className="rounded border px-3 py-1" onClick={increment}>
+          +
+        </button>
+      </div>
+      <label className="flex items-center gap-2 text-sm">
+        <input
+          type="checkbox"
+          checked={compactMode}
+          onChange={(event) => setPreference("compactMode", event.target.checked)}
+        />
+        Compact mode (persisted in localStorage)
+      </label>
+    </div>
+  );
+}
+"#;
+
+pub async fn add_zustand() -> Result<()> {
+    let package_manager = PackageManager::from_project_config()?;
+    let project_structure = ProjectStructure::detect()?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Using package manager: {}",
+            package_manager.to_string()
+        ))
+        .yellow()
+    );
+
+    let wrap_layout = prompt::confirm("wrap_layout", "Wrap the root layout with AppStoreProvider?", true)?;
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message("Installing zustand...");
+
+    package_manager.install(&["zustand"], false)?;
+
+    pb.set_message("Creating store and provider...");
+
+    let stores_path = project_structure.get_lib_path().join("stores");
+    change_set::create_dir_all(&stores_path).context("Failed to create stores directory")?;
+    file_writer::write(stores_path.join("app-store.ts"), APP_STORE)
+        .context("Failed to create app-store.ts")?;
+
+    let components_path = project_structure.get_components_path();
+    let providers_path = components_path.join("providers");
+    change_set::create_dir_all(&providers_path).context("Failed to create providers directory")?;
+    file_writer::write(providers_path.join("app-store-provider.tsx"), APP_STORE_PROVIDER)
+        .context("Failed to create app-store-provider.tsx")?;
+    file_writer::write(components_path.join("store-demo.tsx"), STORE_DEMO)
+        .context("Failed to create store-demo.tsx")?;
+
+    if wrap_layout {
+        pb.set_message("Adding AppStoreProvider to the root layout...");
+
+        root_layout::add_import(
+            &project_structure,
+            "import { AppStoreProvider } from \"@/components/providers/app-store-provider\";",
+        )?;
+        root_layout::wrap_children(&project_structure, "<AppStoreProvider>", "</AppStoreProvider>")?;
+    }
+
+    pb.finish_with_message("Zustand set up successfully!");
+
+    println!("\n{}", style("Files created:").cyan().bold());
+    println!("• {}/app-store.ts - Store factory with persist and devtools middleware", stores_path.display());
+    println!("• {}/app-store-provider.tsx - Per-request provider and useAppStore hook", providers_path.display());
+    println!("• {}/store-demo.tsx - Example component using the store", components_path.display());
+    if wrap_layout {
+        println!("• Root layout wrapped with AppStoreProvider");
+    }
+
+    println!("\n{}", style("Next steps:").green());
+    let mut step = 1;
+    if !wrap_layout {
+        println!("{}. Wrap your layout with <AppStoreProvider>", step);
+        step += 1;
+    }
+    println!("{}. Render <StoreDemo /> on a page to try it out", step);
+    println!("{}. Inspect state with the Redux DevTools browser extension", step + 1);
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anyhow::{Result, Context};
+use console::style;
+use similar::TextDiff;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use crate::{change_set, import_alias, prompt};
+
+/// What to do when a feature would replace a file whose content differs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPolicy {
+    Ask,
+    Force,
+    SkipExisting,
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_policy(policy: ConflictPolicy) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+pub fn policy() -> ConflictPolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        1 => ConflictPolicy::Force,
+        2 => ConflictPolicy::SkipExisting,
+        _ => ConflictPolicy::Ask,
+    }
+}
+
+fn print_diff(path: &Path, old: &str, new: &str) {
+    let diff = TextDiff::from_lines(old, new);
+    let current = format!("{} (current)", path.display());
+    let proposed = format!("{} (new)", path.display());
+    let unified = diff.unified_diff().context_radius(3).header(&current, &proposed).to_string();
+
+    for line in unified.lines() {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            println!("{}", style(line).green());
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            println!("{}", style(line).red());
+        } else if line.starts_with("@@") {
+            println!("{}", style(line).cyan());
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Writes `contents` to `path` like `std::fs::write`, but never silently replaces a file
+/// with different content: depending on `--force` / `--skip-existing` it overwrites,
+/// skips, or asks to overwrite, skip, show a diff, or write `<path>.new` instead.
+/// Generated code imports through `@/`; those imports are rewritten to the project's
+/// own alias first. Returns whether `path` itself was written.
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<bool> {
+    let path = path.as_ref();
+    let rewritten = import_alias::rewrite(path, contents.as_ref());
+    let contents = rewritten.as_ref().map_or(contents.as_ref(), |rewritten| rewritten.as_bytes());
+
+    let existing = match std::fs::read(path) {
+        Ok(existing) => existing,
+        Err(_) => {
+            change_set::write(path, contents)?;
+            return Ok(true);
+        }
+    };
+    if existing == contents {
+        return Ok(true);
+    }
+
+    let policy = match policy() {
+        // Without a terminal there is nobody to ask, so keep the user's file
+        ConflictPolicy::Ask if !std::io::stdin().is_terminal() && !prompt::is_forwarded() => ConflictPolicy::SkipExisting,
+        policy => policy,
+    };
+
+    match policy {
+        ConflictPolicy::Force => {
+            change_set::write(path, contents)?;
+            Ok(true)
+        }
+        ConflictPolicy::SkipExisting => {
+            println!("{}", style(format!("Skipped {}: it already exists", path.display())).yellow());
+            Ok(false)
+        }
+        ConflictPolicy::Ask => {
+            let old = String::from_utf8_lossy(&existing);
+            let new = String::from_utf8_lossy(contents);
+            let options = ["Overwrite", "Skip", "Show diff", "Write alongside as .new"];
+
+            loop {
+                let choice = prompt::choose(&format!("{} already exists and would change", path.display()), &options, 0)?;
+
+                match choice {
+                    0 => {
+                        change_set::write(path, contents)?;
+                        return Ok(true);
+                    }
+                    1 => return Ok(false),
+                    2 => print_diff(path, &old, &new),
+                    _ => {
+                        let mut alongside = path.as_os_str().to_owned();
+                        alongside.push(".new");
+                        change_set::write(&alongside, contents)
+                            .context(format!("Failed to write {}", Path::new(&alongside).display()))?;
+                        println!("{}", style(format!("Wrote {}", Path::new(&alongside).display())).yellow());
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+    }
+}
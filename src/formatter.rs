@@ -0,0 +1,96 @@
+use anyhow::{Result, Context};
+use console::style;
+use std::path::{Path, PathBuf};
+use crate::config;
+use crate::package_manager::PackageManager;
+use crate::project_profile::ProjectProfile;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Formatter {
+    Biome,
+    Prettier,
+}
+
+impl Formatter {
+    /// Biome wins when both are configured, since the biome feature replaces Prettier.
+    pub fn detect() -> Option<Self> {
+        let profile = ProjectProfile::detect();
+        if profile.biome {
+            Some(Formatter::Biome)
+        } else if profile.prettier {
+            Some(Formatter::Prettier)
+        } else {
+            None
+        }
+    }
+
+    fn command(&self) -> &'static [&'static str] {
+        match self {
+            Formatter::Biome => &["biome", "format", "--write"],
+            Formatter::Prettier => &["prettier", "--write", "--ignore-unknown"],
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Formatter::Biome => "Biome",
+            Formatter::Prettier => "Prettier",
+        }
+    }
+
+    fn supports(&self, path: &Path) -> bool {
+        let extensions: &[&str] = match self {
+            Formatter::Biome => &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts", "json", "jsonc", "css"],
+            Formatter::Prettier => &[
+                "js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts", "json", "jsonc", "css", "scss", "md", "mdx",
+                "yml", "yaml", "html",
+            ],
+        };
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extensions.contains(&extension))
+    }
+
+    /// Formats `files` with the project's own formatter binary and settings.
+    pub fn format(&self, files: &[PathBuf]) -> Result<usize> {
+        let files: Vec<&PathBuf> = files.iter().filter(|file| self.supports(file)).collect();
+        if files.is_empty() {
+            return Ok(0);
+        }
+
+        let package_manager = PackageManager::from_project_config()?;
+        let command_line = package_manager.exec_line(self.command());
+
+        // Paths are passed as separate arguments so ones like `app/[locale]/page.tsx` survive
+        let output = command_line
+            .command()
+            .args(&files)
+            .output()
+            .context(format!("Failed to run {}", command_line))?;
+
+        if !output.status.success() {
+            anyhow::bail!("{} exited with {}", command_line, output.status);
+        }
+        Ok(files.len())
+    }
+}
+
+/// Runs the project's formatter on the files a feature just wrote, so they match the
+/// project's style before the first save. Failures only warn: the files are still valid.
+pub fn format_written_files(files: &[PathBuf]) {
+    if config::value("format").as_deref() == Some("false") {
+        return;
+    }
+    let Some(formatter) = Formatter::detect() else {
+        return;
+    };
+
+    match formatter.format(files) {
+        Ok(0) => {}
+        Ok(count) => println!("{}", style(format!("Formatted {} file(s) with {}", count, formatter.name())).dim()),
+        Err(err) => println!(
+            "{}",
+            style(format!("Could not format generated files with {}: {}", formatter.name(), err)).yellow()
+        ),
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::Result;
+use crate::config;
+use crate::package_json;
+use crate::package_manager::PackageManager;
+
+/// Names accepted by `nstack create --framework` and stored as `framework` in .nstack/config.
+pub const FRAMEWORK_NAMES: &[&str] = &["next", "remix", "astro", "sveltekit", "vite", "expo"];
+
+/// Features that only touch package.json, config files and standalone source, so they work
+/// whatever framework the project uses. Everything else scaffolds Next.js routes and layouts.
+const PORTABLE_FEATURES: &[&str] = &[
+    "drizzle",
+    "schema-conventions",
+    "kysely",
+    "mongodb",
+    "gel",
+    "supabase",
+    "firebase",
+    "github-actions",
+    "vitest",
+    "playwright",
+    "cypress",
+    "eslint-prettier",
+    "biome",
+    "husky",
+    "commitlint",
+    "changesets",
+    "ts-strict",
+];
+
+/// Features for Expo apps, which have no DOM and run their own router.
+const MOBILE_FEATURES: &[&str] = &["nativewind", "expo-router"];
+
+/// Features that also fit Expo apps. drizzle switches to expo-sqlite there.
+const EXPO_FEATURES: &[&str] = &[
+    "drizzle",
+    "github-actions",
+    "eslint-prettier",
+    "biome",
+    "husky",
+    "commitlint",
+    "changesets",
+    "ts-strict",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Framework {
+    Next,
+    Remix,
+    Astro,
+    SvelteKit,
+    Vite,
+    Expo,
+}
+
+impl Framework {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "next" => Ok(Framework::Next),
+            "remix" => Ok(Framework::Remix),
+            "astro" => Ok(Framework::Astro),
+            "sveltekit" => Ok(Framework::SvelteKit),
+            "vite" => Ok(Framework::Vite),
+            "expo" => Ok(Framework::Expo),
+            _ => anyhow::bail!("Unknown framework '{}'. Expected one of: {}", name, FRAMEWORK_NAMES.join(", ")),
+        }
+    }
+
+    /// The framework recorded when the project was created, otherwise the one its
+    /// dependencies point at. Projects nstack knows nothing about are treated as Next.js.
+    pub fn from_project_config() -> Self {
+        if let Some(framework) = config::value("framework").and_then(|name| Self::parse(&name).ok()) {
+            return framework;
+        }
+        if package_json::has_dependency("next") {
+            Framework::Next
+        } else if package_json::has_dependency("expo") {
+            Framework::Expo
+        } else if package_json::has_dependency("@remix-run/react") || package_json::has_dependency("@react-router/dev") {
+            Framework::Remix
+        } else if package_json::has_dependency("astro") {
+            Framework::Astro
+        } else if package_json::has_dependency("@sveltejs/kit") {
+            Framework::SvelteKit
+        } else if package_json::has_dependency("vite") {
+            Framework::Vite
+        } else {
+            Framework::Next
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Framework::Next => "next",
+            Framework::Remix => "remix",
+            Framework::Astro => "astro",
+            Framework::SvelteKit => "sveltekit",
+            Framework::Vite => "vite",
+            Framework::Expo => "expo",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Framework::Next => "Next.js",
+            Framework::Remix => "Remix",
+            Framework::Astro => "Astro",
+            Framework::SvelteKit => "SvelteKit",
+            Framework::Vite => "Vite",
+            Framework::Expo => "Expo",
+        }
+    }
+
+    /// The scaffolding command for this framework, run with the project name appended.
+    pub fn create_command(&self, package_manager: &PackageManager) -> (&'static str, Vec<&'static str>) {
+        match (self, package_manager) {
+            (Framework::Next, _) => package_manager.create_next_app_command(),
+            (Framework::Remix, PackageManager::Npm) => ("npx", vec!["create-remix@latest"]),
+            (Framework::Remix, PackageManager::Yarn) => ("yarn", vec!["create", "remix"]),
+            (Framework::Remix, PackageManager::Pnpm) => ("pnpm", vec!["create", "remix"]),
+            (Framework::Remix, PackageManager::Bun) => ("bunx", vec!["create-remix"]),
+            (Framework::Remix, PackageManager::Deno) => ("deno", vec!["run", "-A", "npm:create-remix@latest"]),
+            (Framework::Astro, PackageManager::Npm) => ("npm", vec!["create", "astro@latest"]),
+            (Framework::Astro, PackageManager::Yarn) => ("yarn", vec!["create", "astro"]),
+            (Framework::Astro, PackageManager::Pnpm) => ("pnpm", vec!["create", "astro@latest"]),
+            (Framework::Astro, PackageManager::Bun) => ("bun", vec!["create", "astro"]),
+            (Framework::Astro, PackageManager::Deno) => ("deno", vec!["run", "-A", "npm:create-astro@latest"]),
+            // create-svelte is deprecated in favour of the `sv` CLI
+            (Framework::SvelteKit, PackageManager::Npm | PackageManager::Yarn) => ("npx", vec!["sv", "create"]),
+            (Framework::SvelteKit, PackageManager::Pnpm) => ("pnpm", vec!["dlx", "sv", "create"]),
+            (Framework::SvelteKit, PackageManager::Bun) => ("bunx", vec!["sv", "create"]),
+            (Framework::SvelteKit, PackageManager::Deno) => ("deno", vec!["run", "-A", "npm:sv", "create"]),
+            (Framework::Vite, PackageManager::Npm) => ("npm", vec!["create", "vite@latest"]),
+            (Framework::Vite, PackageManager::Yarn) => ("yarn", vec!["create", "vite"]),
+            (Framework::Vite, PackageManager::Pnpm) => ("pnpm", vec!["create", "vite"]),
+            (Framework::Vite, PackageManager::Bun) => ("bun", vec!["create", "vite"]),
+            (Framework::Vite, PackageManager::Deno) => ("deno", vec!["run", "-A", "npm:create-vite@latest"]),
+            (Framework::Expo, PackageManager::Npm) => ("npx", vec!["create-expo-app@latest"]),
+            (Framework::Expo, PackageManager::Yarn) => ("yarn", vec!["create", "expo-app"]),
+            (Framework::Expo, PackageManager::Pnpm) => ("pnpm", vec!["create", "expo-app"]),
+            (Framework::Expo, PackageManager::Bun) => ("bun", vec!["create", "expo"]),
+            (Framework::Expo, PackageManager::Deno) => ("deno", vec!["run", "-A", "npm:create-expo-app@latest"]),
+        }
+    }
+
+    /// Creator flags that skip its own git setup, so nstack can initialize the repository
+    /// with the user's branch and commit message. A repository made by a creator without such
+    /// a flag is reused.
+    pub fn skip_git_args(&self) -> &'static [&'static str] {
+        match self {
+            Framework::Next => &["--disable-git"],
+            Framework::Remix => &["--no-git-init"],
+            Framework::Astro => &["--no-git"],
+            Framework::SvelteKit | Framework::Vite | Framework::Expo => &[],
+        }
+    }
+
+    /// Creator flags that scaffold without installing dependencies. create-vite never
+    /// installs them, so it needs none.
+    pub fn skip_install_args(&self) -> &'static [&'static str] {
+        match self {
+            Framework::Next => &["--skip-install"],
+            Framework::Remix | Framework::Astro | Framework::SvelteKit | Framework::Expo => &["--no-install"],
+            Framework::Vite => &[],
+        }
+    }
+
+    /// Creator flags that start from `template` instead of the default starter. Accepts
+    /// `github:owner/repo` or a URL.
+    pub fn template_args(&self, template: &str) -> Result<Vec<String>> {
+        let url = match template.strip_prefix("github:") {
+            Some(repo) => format!("https://github.com/{}", repo),
+            None => template.to_string(),
+        };
+        match self {
+            Framework::Next => Ok(vec!["--example".to_string(), url]),
+            Framework::Remix | Framework::Astro | Framework::Expo => Ok(vec!["--template".to_string(), url]),
+            Framework::SvelteKit | Framework::Vite => {
+                anyhow::bail!("{}'s creator only offers its built-in templates", self.display_name())
+            }
+        }
+    }
+
+    pub fn supports(&self, feature: &str) -> bool {
+        match self {
+            Framework::Next => !MOBILE_FEATURES.contains(&feature),
+            Framework::Expo => MOBILE_FEATURES.contains(&feature) || EXPO_FEATURES.contains(&feature),
+            _ => PORTABLE_FEATURES.contains(&feature),
+        }
+    }
+
+    /// What to use instead of a feature this framework does not support, if anything.
+    fn alternative(&self, feature: &str) -> Option<&'static str> {
+        match (self, feature) {
+            (Framework::SvelteKit, "shadcn") => Some("run `npx shadcn-svelte@latest init` for the Svelte port"),
+            (Framework::Remix | Framework::Astro | Framework::Vite, "shadcn") => {
+                Some("run `npx shadcn@latest init`, which detects the framework")
+            }
+            (Framework::Astro | Framework::SvelteKit | Framework::Vite, "og-image") => {
+                Some("use @vercel/og directly from an endpoint")
+            }
+            (Framework::Expo, "theme") => Some("NativeWind's `useColorScheme` covers dark mode"),
+            (Framework::Expo, "playwright" | "cypress") => Some("use Maestro or Detox for end-to-end tests"),
+            (_, "nativewind") => Some("use Tailwind CSS directly"),
+            _ => None,
+        }
+    }
+}
+
+/// Refuses features that scaffold code for another framework than the project uses.
+pub fn check(feature: &str) -> Result<()> {
+    let framework = Framework::from_project_config();
+    if framework.supports(feature) {
+        return Ok(());
+    }
+    match framework.alternative(feature) {
+        Some(alternative) => {
+            anyhow::bail!("{} is not available for {} projects; {}", feature, framework.display_name(), alternative)
+        }
+        None => anyhow::bail!("{} is not available for {} projects", feature, framework.display_name()),
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::{Result, Context};
+use std::fs;
+use std::path::Path;
+use crate::change_set;
+use crate::project_structure::app_dir;
+
+const GITIGNORE_PATH: &str = ".gitignore";
+
+/// Appends `entries` to .gitignore under a `# section` comment, skipping entries already listed.
+pub fn add_entries(section: &str, entries: &[&str]) -> Result<()> {
+    add_entries_in(&app_dir().unwrap_or_default(), section, entries)
+}
+
+/// `add_entries` for the .gitignore of another directory, e.g. a project just created.
+pub fn add_entries_in(dir: &Path, section: &str, entries: &[&str]) -> Result<()> {
+    let gitignore_path = dir.join(GITIGNORE_PATH);
+    let existing_content = if gitignore_path.exists() {
+        fs::read_to_string(&gitignore_path).context("Failed to read .gitignore")?
+    } else {
+        String::new()
+    };
+
+    let missing: Vec<&str> = entries
+        .iter()
+        .copied()
+        .filter(|entry| !existing_content.lines().any(|line| line.trim() == *entry))
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let block = format!("# {}\n{}\n", section, missing.join("\n"));
+    let updated_content = if existing_content.trim().is_empty() {
+        block
+    } else {
+        format!("{}\n\n{}", existing_content.trim_end(), block)
+    };
+    change_set::write(gitignore_path, updated_content).context("Failed to update .gitignore")
+}
@@ -0,0 +1,342 @@
+use anyhow::{Result, Context};
+use std::collections::BTreeMap;
+use std::fs;
+use crate::change_set;
+use crate::project_structure::ProjectStructure;
+
+/// A top-level or nested item of a stylesheet: a `;`-terminated statement (declaration,
+/// `@import`, `@apply`...) or a block such as `:root { ... }` or `@layer base { ... }`.
+struct Node {
+    key: String,
+    start: usize,
+    end: usize,
+    /// Brace positions for blocks
+    body: Option<(usize, usize)>,
+}
+
+/// If a string or comment starts at `index`, returns the index just past it.
+fn skip_literal(bytes: &[u8], index: usize) -> Option<usize> {
+    match bytes[index] {
+        quote @ (b'"' | b'\'') => {
+            let mut i = index + 1;
+            while i < bytes.len() && bytes[i] != quote {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            Some((i + 1).min(bytes.len()))
+        }
+        b'/' if bytes.get(index + 1) == Some(&b'*') => {
+            let end = bytes[index + 2..]
+                .windows(2)
+                .position(|window| window == b"*/")
+                .map_or(bytes.len(), |offset| index + 2 + offset + 2);
+            Some(end)
+        }
+        _ => None,
+    }
+}
+
+fn skip_trivia(bytes: &[u8], mut index: usize, end: usize) -> usize {
+    while index < end {
+        if bytes[index].is_ascii_whitespace() {
+            index += 1;
+        } else if let Some(next) = skip_literal(bytes, index).filter(|_| bytes[index] == b'/') {
+            index = next;
+        } else {
+            break;
+        }
+    }
+    index
+}
+
+fn find_closing(bytes: &[u8], open: usize, end: usize) -> usize {
+    let mut depth = 0;
+    let mut i = open;
+    while i < end {
+        if let Some(next) = skip_literal(bytes, i) {
+            i = next;
+            continue;
+        }
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    end
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// What makes two items the same: the property for declarations, the variant or layer name
+/// for at-rules like `@custom-variant dark (...)`, and the whole text otherwise.
+fn statement_key(text: &str) -> String {
+    let text = normalize(text.trim_end_matches(';'));
+    if text.starts_with("@import") || text.starts_with("@apply") {
+        return text;
+    }
+    if text.starts_with('@') {
+        return text.split(' ').take(2).collect::<Vec<_>>().join(" ");
+    }
+    match text.split_once(':') {
+        Some((property, _)) => property.trim().to_string(),
+        None => text,
+    }
+}
+
+fn parse(css: &str, start: usize, end: usize) -> Vec<Node> {
+    let bytes = css.as_bytes();
+    let mut nodes = Vec::new();
+    let mut i = skip_trivia(bytes, start, end);
+
+    while i < end {
+        let node_start = i;
+        let mut depth = 0;
+        loop {
+            if i >= end {
+                let text = css[node_start..end].trim_end();
+                nodes.push(Node { key: statement_key(text), start: node_start, end: node_start + text.len(), body: None });
+                break;
+            }
+            if let Some(next) = skip_literal(bytes, i) {
+                i = next;
+                continue;
+            }
+            match bytes[i] {
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                b';' if depth == 0 => {
+                    i += 1;
+                    nodes.push(Node { key: statement_key(&css[node_start..i]), start: node_start, end: i, body: None });
+                    break;
+                }
+                b'{' if depth == 0 => {
+                    let close = find_closing(bytes, i, end);
+                    nodes.push(Node {
+                        key: normalize(&css[node_start..i]),
+                        start: node_start,
+                        end: (close + 1).min(end),
+                        body: Some((i, close)),
+                    });
+                    i = close + 1;
+                    break;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        i = skip_trivia(bytes, i, end);
+    }
+    nodes
+}
+
+fn line_indent(css: &str, index: usize) -> &str {
+    let line_start = css[..index].rfind('\n').map_or(0, |newline| newline + 1);
+    let line = &css[line_start..];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Moves `node` from the snippet to a new indentation, keeping its inner structure.
+fn reindent(source: &str, node: &Node, indent: &str) -> String {
+    let from = line_indent(source, node.start);
+    source[node.start..node.end]
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 {
+                line.to_string()
+            } else if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", indent, line.strip_prefix(from).unwrap_or(line))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Collects where each missing node of `source` goes in the block `target[open..=close]`,
+/// recursing into blocks both sides have.
+fn merge_block(
+    target: &str,
+    (open, close): (usize, usize),
+    source: &str,
+    source_nodes: &[Node],
+    insertions: &mut BTreeMap<usize, String>,
+) {
+    let existing = parse(target, open + 1, close);
+    let indent = match existing.first() {
+        Some(first) => line_indent(target, first.start).to_string(),
+        None => format!("{}  ", line_indent(target, open)),
+    };
+    let last = target[..close].trim_end().len();
+    let needs_semicolon = !matches!(target.as_bytes()[last - 1], b';' | b'{' | b'}');
+
+    for node in source_nodes {
+        match existing.iter().find(|existing| existing.key == node.key) {
+            Some(existing) => merge_existing(target, existing, source, node, insertions),
+            None => {
+                let text = insertions.entry(last).or_default();
+                if needs_semicolon && text.is_empty() {
+                    text.push(';');
+                }
+                text.push_str(&format!("\n{}{}", indent, reindent(source, node, &indent)));
+            }
+        }
+    }
+}
+
+fn merge_existing(target: &str, existing: &Node, source: &str, node: &Node, insertions: &mut BTreeMap<usize, String>) {
+    if let (Some(target_body), Some((open, close))) = (existing.body, node.body) {
+        merge_block(target, target_body, source, &parse(source, open + 1, close), insertions);
+    }
+}
+
+/// Returns the stylesheet with what it lacks from `snippet`, or `None` when nothing is missing.
+fn merge_css(content: &str, snippet: &str) -> Option<String> {
+    let existing = parse(content, 0, content.len());
+    let mut insertions: BTreeMap<usize, String> = BTreeMap::new();
+
+    let last_statement = |imports_only: bool| {
+        existing
+            .iter()
+            .rev()
+            .find(|node| node.body.is_none() && (!imports_only || node.key.starts_with("@import")))
+            .map(|node| node.end)
+    };
+    let end = content.trim_end().len();
+
+    for node in parse(snippet, 0, snippet.len()) {
+        if let Some(existing) = existing.iter().find(|existing| existing.key == node.key) {
+            merge_existing(content, existing, snippet, &node, &mut insertions);
+            continue;
+        }
+
+        let text = reindent(snippet, &node, "");
+        if node.body.is_some() {
+            let separator = match insertions.get(&end) {
+                None if end == 0 => "",
+                Some(text) if text.ends_with('\n') => "\n",
+                _ => "\n\n",
+            };
+            insertions.entry(end).or_default().push_str(&format!("{}{}", separator, text));
+            continue;
+        }
+
+        // CSS requires @import before any other rule
+        match last_statement(node.key.starts_with("@import")) {
+            Some(position) => insertions.entry(position).or_default().push_str(&format!("\n{}", text)),
+            None => insertions.entry(0).or_default().push_str(&format!("{}\n", text)),
+        }
+    }
+
+    if insertions.is_empty() {
+        return None;
+    }
+
+    let mut updated = content.to_string();
+    for (position, text) in insertions.into_iter().rev() {
+        updated.insert_str(position, &text);
+    }
+    if !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    Some(updated)
+}
+
+/// Merges `snippet` into the project's globals.css without clobbering user edits: missing
+/// imports and at-rules go after the existing ones, missing blocks go at the end, and blocks
+/// both have (`:root`, `@theme inline`, `@layer base`...) only gain the declarations and rules
+/// they lack, keeping values the project already sets. Returns `Ok(false)` when nothing was missing.
+pub fn merge(project_structure: &ProjectStructure, snippet: &str) -> Result<bool> {
+    let css_path = project_structure.get_globals_css_path();
+    let content = fs::read_to_string(&css_path).unwrap_or_default();
+
+    let Some(updated_content) = merge_css(&content, snippet) else {
+        return Ok(false);
+    };
+    // A project without the stylesheet may not have its directory either, e.g. src/app
+    if let Some(parent) = css_path.parent() {
+        change_set::create_dir_all(parent).context(format!("Failed to create {}", parent.display()))?;
+    }
+    change_set::write(&css_path, updated_content)
+        .context(format!("Failed to update {}", css_path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_an_empty_stylesheet() {
+        let snippet = "@import \"tailwindcss\";\n\n:root {\n  --radius: 0.5rem;\n}\n";
+        assert_eq!(merge_css("", snippet).unwrap(), snippet);
+    }
+
+    #[test]
+    fn adds_imports_after_existing_imports() {
+        let content = "@import \"tailwindcss\";\n\nbody {\n  margin: 0;\n}\n";
+        let updated = merge_css(content, "@import \"tw-animate-css\";\n").unwrap();
+        assert_eq!(updated, "@import \"tailwindcss\";\n@import \"tw-animate-css\";\n\nbody {\n  margin: 0;\n}\n");
+    }
+
+    #[test]
+    fn adds_only_missing_declarations_to_shared_blocks() {
+        let content = ":root {\n  --background: #fff;\n  --radius: 1rem;\n}\n";
+        let snippet = ":root {\n  --radius: 0.5rem;\n  --primary: #000;\n}\n";
+        let updated = merge_css(content, snippet).unwrap();
+        assert_eq!(updated, ":root {\n  --background: #fff;\n  --radius: 1rem;\n  --primary: #000;\n}\n");
+    }
+
+    #[test]
+    fn merges_nested_blocks() {
+        let content = "@layer base {\n  body {\n    color: red;\n  }\n}\n";
+        let snippet = "@layer base {\n  body {\n    color: blue;\n    margin: 0;\n  }\n  h1 {\n    font-weight: 700;\n  }\n}\n";
+        let updated = merge_css(content, snippet).unwrap();
+        assert_eq!(
+            updated,
+            "@layer base {\n  body {\n    color: red;\n    margin: 0;\n  }\n  h1 {\n    font-weight: 700;\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn appends_new_blocks_at_the_end() {
+        let content = "body {\n  margin: 0;\n}";
+        let updated = merge_css(content, ".dark {\n  --background: #000;\n}\n").unwrap();
+        assert_eq!(updated, "body {\n  margin: 0;\n}\n\n.dark {\n  --background: #000;\n}\n");
+    }
+
+    #[test]
+    fn matches_at_rules_by_name() {
+        let content = "@custom-variant dark (&:is(.dark *));\n";
+        assert!(merge_css(content, "@custom-variant dark (&:where(.dark, .dark *));\n").is_none());
+    }
+
+    #[test]
+    fn closes_a_last_declaration_without_semicolon() {
+        let content = ":root {\n  --a: 1\n}\n";
+        let updated = merge_css(content, ":root {\n  --b: 2;\n}\n").unwrap();
+        assert_eq!(updated, ":root {\n  --a: 1;\n  --b: 2;\n}\n");
+    }
+
+    #[test]
+    fn ignores_braces_in_comments_and_strings() {
+        let content = "/* :root { --x: 1; } */\nbody {\n  content: \"}\";\n}\n";
+        let updated = merge_css(content, "body {\n  margin: 0;\n}\n").unwrap();
+        assert_eq!(updated, "/* :root { --x: 1; } */\nbody {\n  content: \"}\";\n  margin: 0;\n}\n");
+    }
+
+    #[test]
+    fn returns_none_when_nothing_is_missing() {
+        let content = "@import \"tailwindcss\";\n\n:root {\n  --radius: 0.5rem;\n}\n";
+        assert!(merge_css(content, ":root {\n  --radius:   0.5rem;\n}\n").is_none());
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::{Result, Context};
+use console::style;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use crate::project_structure::project_file;
+use crate::shell;
+
+const PROJECT_FILE: &str = "nstack.toml";
+
+#[derive(Debug, Default, Deserialize)]
+struct ProjectFile {
+    /// Keyed by feature name, or `*` for every feature
+    #[serde(default)]
+    hooks: BTreeMap<String, FeatureHooks>,
+    /// Checks run after a feature installs packages
+    #[serde(default)]
+    post_install: Vec<PostInstallStep>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostInstallStep {
+    Audit,
+    Dedupe,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FeatureHooks {
+    #[serde(default)]
+    pre: Vec<String>,
+    #[serde(default)]
+    post: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum HookStage {
+    Pre,
+    Post,
+}
+
+impl HookStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HookStage::Pre => "pre",
+            HookStage::Post => "post",
+        }
+    }
+}
+
+fn load() -> Result<ProjectFile> {
+    let path = project_file(PROJECT_FILE);
+    if !path.exists() {
+        return Ok(ProjectFile::default());
+    }
+
+    let content = std::fs::read_to_string(path).context("Failed to read nstack.toml")?;
+    toml::from_str(&content).context("Failed to parse nstack.toml")
+}
+
+/// The `post_install` steps from `nstack.toml`, e.g. `post_install = ["audit", "dedupe"]`.
+pub fn post_install_steps() -> Result<Vec<PostInstallStep>> {
+    Ok(load()?.post_install)
+}
+
+/// Runs the `nstack.toml` hooks for `feature`: the `*` hooks first, then the feature's own.
+/// Hooks see `NSTACK_FEATURE`, `NSTACK_HOOK` and one `NSTACK_OPTION_<KEY>` per prompt answer.
+pub fn run(feature: &str, stage: HookStage, options: &BTreeMap<String, String>) -> Result<()> {
+    let project_file = load()?;
+
+    let commands: Vec<&String> = ["*", feature]
+        .iter()
+        .filter_map(|name| project_file.hooks.get(*name))
+        .flat_map(|hooks| match stage {
+            HookStage::Pre => &hooks.pre,
+            HookStage::Post => &hooks.post,
+        })
+        .collect();
+    if commands.is_empty() {
+        return Ok(());
+    }
+
+    let mut envs = vec![
+        ("NSTACK_FEATURE".to_string(), feature.to_string()),
+        ("NSTACK_HOOK".to_string(), stage.as_str().to_string()),
+    ];
+    for (key, value) in options {
+        envs.push((format!("NSTACK_OPTION_{}", key.to_uppercase()), value.clone()));
+    }
+
+    for command in commands {
+        println!("{}", style(format!("Running {}-{} hook: {}", stage.as_str(), feature, command)).dim());
+        shell::run(command, &envs).context(format!("The {} hook for {} failed", stage.as_str(), feature))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use std::time::Duration;
+use crate::npmrc;
+
+fn env_proxy_set() -> bool {
+    ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"]
+        .iter()
+        .any(|name| std::env::var_os(name).is_some_and(|value| !value.is_empty()))
+}
+
+/// Client for nstack's own requests (feature registry, update check). reqwest honors
+/// `HTTP(S)_PROXY` and `NO_PROXY` itself; without them, the `https-proxy` or `proxy` from
+/// .npmrc is used, so machines set up only for npm work too.
+pub fn client(timeout: Duration) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(concat!("nstack/", env!("CARGO_PKG_VERSION")))
+        .timeout(timeout);
+
+    if !env_proxy_set()
+        && let Some(proxy) = npmrc::value("https-proxy").or_else(|| npmrc::value("proxy"))
+    {
+        let no_proxy = npmrc::value("noproxy").and_then(|hosts| reqwest::NoProxy::from_string(&hosts));
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?.no_proxy(no_proxy));
+    }
+    Ok(builder.build()?)
+}
@@ -0,0 +1,243 @@
+use serde_json::Value;
+use std::path::{Component, Path};
+use crate::project_structure::{project_file, slash_path, ProjectStructure};
+use crate::tsconfig;
+
+/// Generated code imports from the source root as `@/...`, the create-next-app default.
+const DEFAULT_PREFIX: &str = "@/";
+
+const CODE_EXTENSIONS: [&str; 7] = ["ts", "tsx", "js", "jsx", "mjs", "cjs", "mts"];
+
+/// An import specifier and the project directory (or file) it resolves to, e.g. `~` for
+/// `src` from `"~/*": ["./src/*"]`, or `@ui` for `src/components/ui` without a wildcard.
+struct Alias {
+    specifier: String,
+    target: String,
+    wildcard: bool,
+}
+
+fn join(base: &str, path: &str) -> String {
+    match (base.is_empty(), path.is_empty()) {
+        (true, _) => path.to_string(),
+        (_, true) => base.to_string(),
+        _ => format!("{}/{}", base, path),
+    }
+}
+
+/// The alias for a tsconfig `paths` entry such as `"~/*"` pointing at `src`.
+fn path_alias(alias: &str, target: String) -> Alias {
+    Alias {
+        specifier: alias.trim_end_matches('*').trim_end_matches('/').to_string(),
+        wildcard: alias.ends_with('*'),
+        target,
+    }
+}
+
+fn tsconfig_aliases() -> Vec<Alias> {
+    tsconfig::paths()
+        .into_iter()
+        .map(|(alias, target)| path_alias(&alias, target))
+        .collect()
+}
+
+/// The shadcn/ui `aliases` in components.json, e.g. `"components": "~/components"`, which
+/// projects without tsconfig paths for them still resolve through their bundler.
+fn components_json_aliases(source_root: &str) -> Vec<Alias> {
+    let Some(components_json) = std::fs::read_to_string(project_file("components.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+    else {
+        return Vec::new();
+    };
+
+    [("components", "components"), ("ui", "components/ui"), ("lib", "lib"), ("utils", "lib/utils"), ("hooks", "hooks")]
+        .into_iter()
+        .filter_map(|(key, dir)| {
+            let specifier = components_json["aliases"][key].as_str()?;
+            Some(Alias {
+                specifier: specifier.to_string(),
+                target: join(source_root, dir),
+                wildcard: key != "utils",
+            })
+        })
+        .collect()
+}
+
+/// Directory generated `@/` imports are relative to: `src` when the project keeps its code
+/// there, otherwise the project root.
+fn source_root() -> String {
+    match ProjectStructure::detect() {
+        Ok(structure) => slash_path(&structure.get_source_root()),
+        Err(_) if Path::new("src").is_dir() => "src".to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn aliases(source_root: &str) -> Vec<Alias> {
+    let mut aliases = tsconfig_aliases();
+    aliases.extend(components_json_aliases(source_root));
+    aliases
+}
+
+/// The specifier for `target` under the most specific matching alias.
+fn aliased(aliases: &[Alias], target: &str) -> Option<String> {
+    aliases
+        .iter()
+        .filter_map(|alias| {
+            if alias.target == target {
+                Some((alias.target.len(), alias.specifier.clone()))
+            } else if !alias.wildcard {
+                None
+            } else if alias.target.is_empty() {
+                Some((0, format!("{}/{}", alias.specifier, target)))
+            } else {
+                let rest = target.strip_prefix(&alias.target)?.strip_prefix('/')?;
+                Some((alias.target.len(), format!("{}/{}", alias.specifier, rest)))
+            }
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, specifier)| specifier)
+}
+
+/// `./x` or `../x` from the directory `from` to `to`, both relative to the project root.
+fn relative(from: &str, to: &str) -> String {
+    let components = |path: &str| -> Vec<String> {
+        Path::new(path)
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect()
+    };
+    let (from, to) = (components(from), components(to));
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    if parts.is_empty() {
+        parts.push(".".to_string());
+    }
+    parts.extend(to[common..].iter().cloned());
+    parts.join("/")
+}
+
+/// The import specifier for a module under the source root, e.g. `lib/utils`, using the
+/// project's own alias. `None` when no alias reaches it.
+pub fn specifier(module: &str) -> Option<String> {
+    let source_root = source_root();
+    aliased(&aliases(&source_root), &join(&source_root, module))
+}
+
+/// Rewrites the `@/...` imports of generated code at `path` to the project's alias for
+/// the same module, or to a relative import when the project has none. Projects using
+/// the default `@/*` for their source root are left untouched.
+pub fn rewrite(path: &Path, contents: &[u8]) -> Option<String> {
+    let extension = path.extension()?.to_str()?;
+    if !CODE_EXTENSIONS.contains(&extension) {
+        return None;
+    }
+    let contents = std::str::from_utf8(contents).ok()?;
+    if !contents.contains("\"@/") && !contents.contains("'@/") {
+        return None;
+    }
+
+    let source_root = source_root();
+    let file_dir = path.parent().map(slash_path).unwrap_or_default();
+    let rewritten = rewrite_imports(contents, &source_root, &aliases(&source_root), &file_dir);
+
+    (rewritten != contents).then_some(rewritten)
+}
+
+/// Replaces every quoted `@/...` specifier in `contents`, a file in `file_dir`.
+fn rewrite_imports(contents: &str, source_root: &str, aliases: &[Alias], file_dir: &str) -> String {
+    let mut rewritten = String::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(start) = rest.find(DEFAULT_PREFIX) {
+        let quote = start.checked_sub(1).map(|quote| rest.as_bytes()[quote]);
+        let end = match quote {
+            Some(quote @ (b'"' | b'\'')) => rest[start..].find(quote as char).map(|end| start + end),
+            _ => None,
+        };
+        let Some(end) = end else {
+            rewritten.push_str(&rest[..start + DEFAULT_PREFIX.len()]);
+            rest = &rest[start + DEFAULT_PREFIX.len()..];
+            continue;
+        };
+
+        let target = join(source_root, &rest[start + DEFAULT_PREFIX.len()..end]);
+        let specifier = aliased(aliases, &target).unwrap_or_else(|| relative(file_dir, &target));
+        rewritten.push_str(&rest[..start]);
+        rewritten.push_str(&specifier);
+        rest = &rest[end..];
+    }
+    rewritten.push_str(rest);
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tilde_src() -> Vec<Alias> {
+        vec![path_alias("~/*", "src".to_string())]
+    }
+
+    #[test]
+    fn parses_tsconfig_path_aliases() {
+        let alias = path_alias("~/*", "src".to_string());
+        assert_eq!((alias.specifier.as_str(), alias.wildcard), ("~", true));
+
+        let alias = path_alias("@ui", "src/components/ui".to_string());
+        assert_eq!((alias.specifier.as_str(), alias.wildcard), ("@ui", false));
+    }
+
+    #[test]
+    fn picks_the_most_specific_alias() {
+        let mut aliases = tilde_src();
+        aliases.push(path_alias("@ui/*", "src/components/ui".to_string()));
+        aliases.push(path_alias("@utils", "src/lib/utils".to_string()));
+
+        assert_eq!(aliased(&aliases, "src/components/ui/button").as_deref(), Some("@ui/button"));
+        assert_eq!(aliased(&aliases, "src/lib/utils").as_deref(), Some("@utils"));
+        assert_eq!(aliased(&aliases, "src/lib/db").as_deref(), Some("~/lib/db"));
+        assert_eq!(aliased(&aliases, "app/page"), None);
+    }
+
+    #[test]
+    fn root_wildcard_alias_matches_everything() {
+        let aliases = vec![path_alias("#/*", String::new())];
+        assert_eq!(aliased(&aliases, "lib/utils").as_deref(), Some("#/lib/utils"));
+    }
+
+    #[test]
+    fn builds_relative_specifiers() {
+        assert_eq!(relative("src/app", "src/lib/utils"), "../lib/utils");
+        assert_eq!(relative("src", "src/lib/utils"), "./lib/utils");
+        assert_eq!(relative("", "lib/utils"), "./lib/utils");
+        assert_eq!(relative("app/(auth)/login", "components/ui/button"), "../../../components/ui/button");
+    }
+
+    #[test]
+    fn rewrites_quoted_imports_only() {
+        let contents = "import { cn } from \"@/lib/utils\";\nimport { Button } from '@/components/ui/button';\n// see @/lib/utils\nconst email = \"a@/b\";\n";
+        let rewritten = rewrite_imports(contents, "src", &tilde_src(), "src/components");
+        assert_eq!(
+            rewritten,
+            "import { cn } from \"~/lib/utils\";\nimport { Button } from '~/components/ui/button';\n// see @/lib/utils\nconst email = \"a@/b\";\n"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_relative_imports_without_an_alias() {
+        let contents = "export { db } from \"@/db\";\nimport(\"@/lib/auth\");\n";
+        let rewritten = rewrite_imports(contents, "", &[], "app/api/auth");
+        assert_eq!(rewritten, "export { db } from \"../../../db\";\nimport(\"../../../lib/auth\");\n");
+    }
+
+    #[test]
+    fn keeps_default_alias_imports_unchanged() {
+        let aliases = vec![path_alias("@/*", "src".to_string())];
+        let contents = "import { cn } from \"@/lib/utils\";\n";
+        assert_eq!(rewrite_imports(contents, "src", &aliases, "src/app"), contents);
+    }
+}
@@ -0,0 +1,48 @@
+use console::{Term, style};
+use std::io::IsTerminal;
+use crate::change_set;
+
+/// Exit code shells use for a process stopped by SIGINT.
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Handles Ctrl-C for the whole run. Child processes share the terminal's process group, so
+/// the same Ctrl-C already stops installs and generators; this rolls back the feature that
+/// was in flight, restores the terminal and reports what finished.
+pub fn install() {
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            shut_down();
+        }
+    });
+}
+
+fn shut_down() -> ! {
+    // Spinners and prompts hide the cursor, and the feature picker uses raw mode
+    if std::io::stdout().is_terminal() {
+        ratatui::restore();
+        let _ = Term::stdout().show_cursor();
+    }
+    println!("\n\n{}", style("Interrupted").red().bold());
+
+    let completed = change_set::completed_features();
+    if !completed.is_empty() {
+        println!("{} {}", style("Completed:").green(), completed.join(", "));
+    }
+
+    match change_set::rollback() {
+        Ok(Some((feature, files))) => {
+            println!(
+                "{}",
+                style(format!("Rolled back {}: restored {} file(s) it had changed", feature, files)).yellow()
+            );
+            println!("Run your package manager's install command if dependencies were being installed.");
+        }
+        Ok(None) => {}
+        Err(err) => println!(
+            "{}",
+            style(format!("Could not roll back the interrupted feature: {:#}", err)).red()
+        ),
+    }
+
+    std::process::exit(INTERRUPTED_EXIT_CODE);
+}
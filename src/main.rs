@@ -1,29 +1,148 @@
 mod cli;
+mod config;
+mod project_root;
 mod project_structure;
+mod project_profile;
 mod package_manager;
+mod package_json;
+mod manifest;
+mod next_config;
+mod bundler;
+mod tsconfig;
+mod import_alias;
+mod globals_css;
+mod formatter;
+mod tailwind;
+mod conflicts;
+mod compat;
+mod framework;
+mod interrupt;
+mod env_file;
+mod gitignore;
+mod docker_compose;
+mod update_check;
+mod http;
+mod npmrc;
+mod registry;
+mod tui;
+mod root_layout;
+mod readme;
+mod prompt;
+mod shell;
+mod process;
+mod file_writer;
+mod change_set;
+mod hooks;
+mod post_install;
 mod features;
 mod commands;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use std::io::IsTerminal;
+use std::path::Path;
 use cli::{Cli, Commands};
 use features::list_features;
-use commands::create::create_project;
-use commands::add::add_feature;
+use file_writer::ConflictPolicy;
+use framework::Framework;
+use package_manager::InstallOptions;
+use commands::create::{DEFAULT_COMMIT_MESSAGE, GitOptions, create_project, run_stack_wizard};
+use commands::add::{add_feature, add_from_manifest};
+use commands::info::show_info;
+use commands::config::run_config;
+use commands::search::search_features;
+use commands::apply::{PresetOverrides, apply_recipe, create_from_preset};
+use commands::verify::verify_project;
+use commands::install::install_dependencies;
+use commands::pm::run_pm;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    interrupt::install();
+    if cli.force {
+        file_writer::set_policy(ConflictPolicy::Force);
+    } else if cli.skip_existing {
+        file_writer::set_policy(ConflictPolicy::SkipExisting);
+    }
+    process::set_verbose(cli.verbose);
+    npmrc::set_registry(cli.registry.clone());
+    package_manager::set_install_options(InstallOptions {
+        frozen: cli.frozen,
+        prefer_offline: cli.prefer_offline,
+        silent: cli.silent,
+    });
+    // Every other command works on an existing project, wherever inside it nstack runs.
+    // Commands that change or inspect an app's code pick one app in multi-app repositories.
+    // Recipes enter the project themselves, after reading the recipe and only when they do
+    // not create one.
+    match &cli.command {
+        Some(Commands::Create { .. } | Commands::Apply { .. }) => {}
+        Some(Commands::Info | Commands::Config { .. } | Commands::Pm { .. } | Commands::Install | Commands::Restore { .. }) => {
+            project_root::enter(false)?;
+        }
+        _ => project_root::enter(true)?,
+    }
     match cli.command {
-        Commands::Create { name } => {
-            create_project(name).await?;
+        Some(Commands::Create { name, dir, framework, monorepo, no_git, commit_message, branch, skip_install, preset }) => {
+            // Git flags given on the command line replace the preset's git settings
+            let git_given = no_git || commit_message.is_some() || branch.is_some();
+            let git = GitOptions {
+                enabled: !no_git,
+                commit_message: commit_message.unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE.to_string()),
+                branch,
+            };
+            if let Some(preset) = preset {
+                let git = git_given.then_some(git);
+                create_from_preset(&preset, PresetOverrides { name, dir, framework, monorepo, skip_install, git }).await?;
+            } else {
+                let framework = Framework::parse(framework.as_deref().unwrap_or("next"))?;
+                let project_name = create_project(name, dir, framework, monorepo, skip_install, &git, &[]).await?;
+                let app_dir = if monorepo { format!("{}/apps/web", project_name) } else { project_name.clone() };
+                run_stack_wizard(&project_name, &app_dir).await?;
+            }
+        }
+        Some(Commands::Add { from_manifest: true, .. }) => {
+            add_from_manifest()?;
         }
-        Commands::Add { feature } => {
+        Some(Commands::Add { feature, from_manifest: false }) => {
             add_feature(feature).await?;
         }
-        Commands::List => {
-            list_features()?;
+        Some(Commands::List { installed, available }) => {
+            list_features(installed, available)?;
+        }
+        Some(Commands::Info) => {
+            show_info()?;
+        }
+        Some(Commands::Apply { recipe }) => {
+            apply_recipe(&recipe).await?;
+        }
+        Some(Commands::Search { query, category, install }) => {
+            search_features(query, category, install).await?;
+        }
+        Some(Commands::Config { action }) => {
+            run_config(action)?;
+        }
+        Some(Commands::Verify { build }) => {
+            verify_project(build)?;
+        }
+        Some(Commands::Pm { action }) => {
+            run_pm(action)?;
+        }
+        Some(Commands::Install) => {
+            install_dependencies()?;
+        }
+        Some(Commands::Restore { timestamp }) => {
+            change_set::restore(timestamp)?;
+        }
+        // The picker adds features, so it only makes sense inside a project on a terminal
+        None if Path::new("package.json").exists() && std::io::stdout().is_terminal() => {
+            tui::run().await?;
+        }
+        None => {
+            Cli::command().print_help()?;
         }
     }
+    update_check::notify_if_outdated().await;
     Ok(())
 }
@@ -0,0 +1,181 @@
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::package_json;
+use crate::change_set;
+use crate::project_structure::{app_dir, project_file};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Environment variables each feature needs, keyed by feature name
+    #[serde(default)]
+    pub env: BTreeMap<String, Vec<String>>,
+    /// package.json scripts each feature added, keyed by feature name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scripts: BTreeMap<String, Vec<String>>,
+    /// Exact versions of the packages each feature installed, keyed by feature name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub packages: BTreeMap<String, FeaturePackages>,
+}
+
+/// Packages a feature installed, by exact version, split the way package.json splits them.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeaturePackages {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dev_dependencies: BTreeMap<String, String>,
+}
+
+impl ProjectManifest {
+    fn path() -> PathBuf {
+        Path::new(".nstack").join("manifest.json")
+    }
+
+    /// Loads the manifest of the app nstack works on.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&app_dir().unwrap_or_default())
+    }
+
+    /// Loads the manifest of the project in `dir`.
+    pub fn load_from(dir: &Path) -> Result<Self> {
+        let path = dir.join(Self::path());
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .context("Failed to read .nstack/manifest.json")?;
+        serde_json::from_str(&content).context("Failed to parse .nstack/manifest.json")
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::create_dir_all(project_file(".nstack")).context("Failed to create .nstack directory")?;
+        let content = serde_json::to_string_pretty(self)?;
+        change_set::write(project_file(Self::path()), content).context("Failed to write .nstack/manifest.json")
+    }
+
+    pub fn record_feature(name: &str) -> Result<()> {
+        let mut manifest = Self::load()?;
+        if !manifest.features.iter().any(|f| f == name) {
+            manifest.features.push(name.to_string());
+            manifest.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn record_env(feature: &str, vars: &[&str]) -> Result<()> {
+        let mut manifest = Self::load()?;
+        let entry = manifest.env.entry(feature.to_string()).or_default();
+        for var in vars {
+            if !entry.iter().any(|v| v == var) {
+                entry.push(var.to_string());
+            }
+        }
+        manifest.save()
+    }
+
+    pub fn record_scripts(feature: &str, scripts: &[String]) -> Result<()> {
+        if scripts.is_empty() {
+            return Ok(());
+        }
+        let mut manifest = Self::load()?;
+        let entry = manifest.scripts.entry(feature.to_string()).or_default();
+        for script in scripts {
+            if !entry.contains(script) {
+                entry.push(script.clone());
+            }
+        }
+        manifest.save()
+    }
+
+    pub fn record_packages(feature: &str, packages: FeaturePackages) -> Result<()> {
+        if packages.dependencies.is_empty() && packages.dev_dependencies.is_empty() {
+            return Ok(());
+        }
+        let mut manifest = Self::load()?;
+        let entry = manifest.packages.entry(feature.to_string()).or_default();
+        entry.dependencies.extend(packages.dependencies);
+        entry.dev_dependencies.extend(packages.dev_dependencies);
+        manifest.save()
+    }
+
+    /// Returns `(feature, variable)` pairs for every environment variable recorded by
+    /// installed features.
+    pub fn required_env_vars(&self) -> Vec<(String, String)> {
+        self.env
+            .iter()
+            .flat_map(|(feature, vars)| {
+                vars.iter().map(move |var| (feature.clone(), var.clone()))
+            })
+            .collect()
+    }
+
+    /// Checks the manifest first, then falls back to the feature's marker dependency so
+    /// features installed before the manifest existed (or by hand) are still picked up.
+    pub fn is_feature_installed(name: &str) -> bool {
+        let recorded = Self::load()
+            .map(|manifest| manifest.features.iter().any(|f| f == name))
+            .unwrap_or(false);
+
+        recorded || marker_dependency(name).is_some_and(package_json::has_dependency)
+    }
+}
+
+fn marker_dependency(feature: &str) -> Option<&'static str> {
+    match feature {
+        "shadcn" => Some("class-variance-authority"),
+        "drizzle" => Some("drizzle-orm"),
+        "vitest" => Some("vitest"),
+        "playwright" => Some("@playwright/test"),
+        "cypress" => Some("cypress"),
+        "storybook" => Some("storybook"),
+        "eslint-prettier" => Some("eslint-config-prettier"),
+        "biome" => Some("@biomejs/biome"),
+        "husky" => Some("lint-staged"),
+        "commitlint" => Some("@commitlint/cli"),
+        "changesets" => Some("@changesets/cli"),
+        "ts-strict" => Some("@total-typescript/ts-reset"),
+        "zustand" => Some("zustand"),
+        "redux" => Some("@reduxjs/toolkit"),
+        "jotai" => Some("jotai"),
+        "theme" => Some("next-themes"),
+        "i18n" => Some("next-intl"),
+        "motion" => Some("motion"),
+        "data-table" => Some("@tanstack/react-table"),
+        "charts" => Some("recharts"),
+        "toasts" => Some("sonner"),
+        "supabase" => Some("@supabase/ssr"),
+        "firebase" => Some("firebase-admin"),
+        "convex" => Some("convex"),
+        "appwrite" => Some("node-appwrite"),
+        "pocketbase" => Some("pocketbase"),
+        "kysely" => Some("kysely"),
+        "mongodb" => Some("mongoose"),
+        "gel" => Some("gel"),
+        "local-first" => Some("@electric-sql/react"),
+        _ => None,
+    }
+}
+
+/// Auth libraries paired with the route their sign-in page usually lives at.
+const AUTH_LIBRARIES: [(&str, &str); 6] = [
+    ("next-auth", "/api/auth/signin"),
+    ("better-auth", "/sign-in"),
+    ("@clerk/nextjs", "/sign-in"),
+    ("@supabase/ssr", "/login"),
+    ("firebase-admin", "/login"),
+    ("node-appwrite", "/login"),
+];
+
+/// Returns the installed auth library and its sign-in route, if the project has one.
+pub fn installed_auth_library() -> Option<(&'static str, &'static str)> {
+    AUTH_LIBRARIES
+        .into_iter()
+        .find(|(package, _)| package_json::has_dependency(package))
+}
@@ -0,0 +1,604 @@
+use anyhow::{Result, Context};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::change_set;
+use crate::project_structure::{app_dir, project_file};
+
+pub const NEXT_CONFIG_FILES: [&str; 3] = ["next.config.ts", "next.config.mjs", "next.config.js"];
+
+pub fn find_next_config() -> Option<PathBuf> {
+    NEXT_CONFIG_FILES.into_iter().map(project_file).find(|path| path.exists())
+}
+
+fn read_next_config() -> Result<(PathBuf, String)> {
+    let Some(config_path) = find_next_config() else {
+        anyhow::bail!("Could not find next.config.ts, next.config.mjs or next.config.js");
+    };
+
+    let content = fs::read_to_string(&config_path)
+        .context(format!("Failed to read {}", config_path.display()))?;
+    Ok((config_path, content))
+}
+
+fn write_next_config(config_path: &Path, content: String) -> Result<()> {
+    change_set::write(config_path, content)
+        .context(format!("Failed to update {}", config_path.display()))
+}
+
+/// If a string literal or comment starts at `index`, returns the index just past it.
+fn skip_literal(bytes: &[u8], index: usize) -> Option<usize> {
+    match bytes[index] {
+        quote @ (b'"' | b'\'' | b'`') => {
+            let mut i = index + 1;
+            while i < bytes.len() && bytes[i] != quote {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            Some((i + 1).min(bytes.len()))
+        }
+        b'/' if bytes.get(index + 1) == Some(&b'/') => {
+            let end = bytes[index..].iter().position(|&b| b == b'\n').map_or(bytes.len(), |offset| index + offset);
+            Some(end)
+        }
+        b'/' if bytes.get(index + 1) == Some(&b'*') => {
+            let end = bytes[index + 2..]
+                .windows(2)
+                .position(|window| window == b"*/")
+                .map_or(bytes.len(), |offset| index + 2 + offset + 2);
+            Some(end)
+        }
+        _ => None,
+    }
+}
+
+/// Index of the bracket closing the one at `open`, ignoring brackets inside strings and comments.
+fn find_closing(content: &str, open: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        if let Some(next) = skip_literal(bytes, i) {
+            i = next;
+            continue;
+        }
+        match bytes[i] {
+            b'{' | b'[' | b'(' => depth += 1,
+            b'}' | b']' | b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Splits `content[start..end]` at separators that are not nested in brackets, strings or comments.
+fn split_top_level(content: &str, start: usize, end: usize, separator: u8) -> Vec<(usize, usize)> {
+    let bytes = content.as_bytes();
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut part_start = start;
+    let mut i = start;
+    while i < end {
+        if let Some(next) = skip_literal(bytes, i) {
+            i = next;
+            continue;
+        }
+        match bytes[i] {
+            b'{' | b'[' | b'(' => depth += 1,
+            b'}' | b']' | b')' => depth -= 1,
+            b if b == separator && depth == 0 => {
+                parts.push((part_start, i));
+                part_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push((part_start, end));
+    parts
+}
+
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$'
+}
+
+fn skip_whitespace(content: &str, mut index: usize) -> usize {
+    let bytes = content.as_bytes();
+    while index < bytes.len() {
+        if let Some(next) = skip_literal(bytes, index).filter(|_| bytes[index] == b'/') {
+            index = next;
+        } else if bytes[index].is_ascii_whitespace() {
+            index += 1;
+        } else {
+            break;
+        }
+    }
+    index
+}
+
+/// A `key: value` entry of an object literal. `value` is `None` for shorthand, spread and
+/// method entries, whose key still counts as taken.
+struct Entry {
+    key: String,
+    value: Option<(usize, usize)>,
+}
+
+fn object_entries(content: &str, open: usize, close: usize) -> Vec<Entry> {
+    split_top_level(content, open + 1, close, b',')
+        .into_iter()
+        .filter_map(|(start, end)| {
+            let start = skip_whitespace(content, start);
+            let segment = content.get(start..end)?.trim_end();
+            if segment.is_empty() || segment.starts_with("...") {
+                return None;
+            }
+
+            let colon = split_top_level(content, start, end, b':')
+                .first()
+                .map(|&(_, colon)| colon)
+                .filter(|&colon| colon < end);
+            let key_end = colon
+                .or_else(|| segment.find('(').map(|offset| start + offset))
+                .unwrap_or(start + segment.len());
+            let key = content[start..key_end]
+                .trim()
+                .trim_start_matches("async ")
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string();
+            let value = colon.map(|colon| {
+                let value_start = skip_whitespace(content, colon + 1);
+                (value_start, value_start + content[value_start..end].trim_end().len())
+            });
+            Some(Entry { key, value })
+        })
+        .collect()
+}
+
+fn find_export(content: &str) -> Option<(&'static str, usize)> {
+    ["export default ", "module.exports = "]
+        .into_iter()
+        .find_map(|export| content.rfind(export).map(|index| (export, index)))
+}
+
+/// Finds `const|let|var <name>` and returns the index of the object literal assigned to it.
+fn declaration_object(content: &str, name: &str) -> Option<usize> {
+    let bytes = content.as_bytes();
+    ["const ", "let ", "var "].into_iter().find_map(|keyword| {
+        let pattern = format!("{}{}", keyword, name);
+        content.match_indices(&pattern).find_map(|(index, _)| {
+            let after = index + pattern.len();
+            if bytes.get(after).is_some_and(|&b| is_identifier_byte(b)) {
+                return None;
+            }
+            // Skips a type annotation such as `: NextConfig`
+            let equals = after + content[after..].find('=')?;
+            let value = skip_whitespace(content, equals + 1);
+            (bytes.get(value) == Some(&b'{')).then_some(value)
+        })
+    })
+}
+
+/// Locates the object literal Next.js receives, following the export through plugin wrappers
+/// like `withPlugin(nextConfig)` to the declaration. Returns the indices of its braces.
+fn config_object(content: &str) -> Option<(usize, usize)> {
+    let (export, start) = find_export(content)?;
+    let bytes = content.as_bytes();
+    let mut i = skip_whitespace(content, start + export.len());
+
+    loop {
+        match bytes.get(i)? {
+            b'{' => return Some((i, find_closing(content, i)?)),
+            &b if is_identifier_byte(b) => {
+                let identifier_end = i + bytes[i..].iter().position(|&b| !is_identifier_byte(b)).unwrap_or(bytes.len() - i);
+                let next = skip_whitespace(content, identifier_end);
+                if bytes.get(next) == Some(&b'(') {
+                    // Curried plugins like `plugin(options)(nextConfig)` take options first
+                    let close = find_closing(content, next)?;
+                    if content[close + 1..].trim_start().starts_with('(') {
+                        return None;
+                    }
+                    // Plugins take the config as their first argument
+                    i = skip_whitespace(content, next + 1);
+                    continue;
+                }
+                let open = declaration_object(content, &content[i..identifier_end])?;
+                return Some((open, find_closing(content, open)?));
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn line_indent(content: &str, index: usize) -> &str {
+    let line_start = content[..index].rfind('\n').map_or(0, |newline| newline + 1);
+    let line = &content[line_start..];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Renders `{ a: { b: value } }` for the part of `path` that does not exist yet.
+fn nested_value(path: &[&str], value: &str, indent: &str) -> String {
+    match path {
+        [] => value.to_string(),
+        [key, rest @ ..] => {
+            let inner_indent = format!("{}  ", indent);
+            format!("{{\n{}{}: {},\n{}}}", inner_indent, key, nested_value(rest, value, &inner_indent), indent)
+        }
+    }
+}
+
+/// Adds `key: value` as the first entry of the object spanning `open..=close`, so trailing
+/// commas and comments after the last entry never need handling.
+fn insert_entry(content: &str, open: usize, close: usize, key: &str, value: &str) -> String {
+    let entry = format!("{}: {}", key, value);
+    let first = close - content[open + 1..close].trim_start().len();
+
+    if first == close {
+        return format!(
+            "{}\n{}  {},\n{}{}",
+            &content[..=open],
+            line_indent(content, open),
+            entry,
+            line_indent(content, open),
+            &content[close..]
+        );
+    }
+
+    let indent = if content[open..first].contains('\n') {
+        line_indent(content, first).to_string()
+    } else {
+        format!("{}  ", line_indent(content, open))
+    };
+    format!("{}\n{}{},{}", &content[..=open], indent, entry, &content[open + 1..])
+}
+
+/// Quotes differ between projects, so `'a'` and `"a"` count as the same item.
+fn same_item(a: &str, b: &str) -> bool {
+    a.trim().replace('\'', "\"") == b.trim().replace('\'', "\"")
+}
+
+/// Appends the missing `items` to the array literal spanning `open..=close`.
+fn append_items(content: &str, open: usize, close: usize, items: &[&str]) -> Option<String> {
+    let existing: Vec<&str> = split_top_level(content, open + 1, close, b',')
+        .into_iter()
+        .map(|(start, end)| content[start..end].trim())
+        .filter(|item| !item.is_empty())
+        .collect();
+    let missing: Vec<&str> = items
+        .iter()
+        .copied()
+        .filter(|item| !existing.iter().any(|existing| same_item(existing, item)))
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+
+    let last = content[..close].trim_end().len();
+    if existing.is_empty() {
+        return Some(format!("{}{}{}", &content[..=open], missing.join(", "), &content[close..]));
+    }
+
+    let comma = if content[..last].ends_with(',') { "" } else { "," };
+    let multiline = content[open..close].contains('\n');
+    let separator = if multiline {
+        format!("\n{}", line_indent(content, skip_whitespace(content, open + 1)))
+    } else {
+        " ".to_string()
+    };
+    let appended: String = missing.iter().map(|item| format!("{}{}", separator, item)).collect::<Vec<_>>().join(",");
+    let trailing = if multiline && comma.is_empty() { "," } else { "" };
+    Some(format!("{}{}{}{}{}", &content[..last], comma, appended, trailing, &content[last..]))
+}
+
+enum Change<'a> {
+    Set(&'a str),
+    Append(&'a [&'a str]),
+}
+
+/// Applies `change` at `path` inside the object spanning `open..=close`, creating nested
+/// objects on the way. Returns `Ok(None)` when there is nothing to change.
+fn patch_object(content: &str, open: usize, close: usize, path: &[&str], change: &Change) -> Result<Option<String>> {
+    let [key, rest @ ..] = path else {
+        return Ok(None);
+    };
+
+    let Some(entry) = object_entries(content, open, close).into_iter().find(|entry| entry.key == *key) else {
+        let value = match change {
+            Change::Set(value) => value.to_string(),
+            Change::Append(items) => format!("[{}]", items.join(", ")),
+        };
+        let indent = format!("{}  ", line_indent(content, open));
+        return Ok(Some(insert_entry(content, open, close, key, &nested_value(rest, &value, &indent))));
+    };
+
+    let value = entry.value.map(|(start, end)| (start, &content[start..end]));
+    match (rest, change, value) {
+        ([], Change::Set(_), _) => Ok(None),
+        ([], Change::Append(items), Some((start, value))) if value.starts_with('[') => {
+            let close = find_closing(content, start).context("Unbalanced array")?;
+            Ok(append_items(content, start, close, items))
+        }
+        (_, _, Some((start, value))) if !rest.is_empty() && value.starts_with('{') => {
+            let close = find_closing(content, start).context("Unbalanced object")?;
+            patch_object(content, start, close, rest, change)
+        }
+        _ => anyhow::bail!("`{}` is not a literal nstack can merge into", key),
+    }
+}
+
+fn patch(config_path: &Path, path: &[&str], change: Change, manual_hint: &str) -> Result<bool> {
+    let content = fs::read_to_string(config_path)
+        .context(format!("Failed to read {}", config_path.display()))?;
+
+    let Some((open, close)) = config_object(&content) else {
+        anyhow::bail!("Could not locate the config object in {}. {}", config_path.display(), manual_hint);
+    };
+
+    match patch_object(&content, open, close, path, &change)
+        .context(format!("Failed to update {}", config_path.display()))?
+    {
+        Some(updated_content) => {
+            write_next_config(config_path, updated_content)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// The source text of an option such as `["experimental", "appDir"]`, when next.config
+/// sets it to a literal. Options built at runtime or behind plugins read as unset.
+pub fn option(path: &[&str]) -> Option<String> {
+    option_in(&app_dir().unwrap_or_default(), path)
+}
+
+/// `option` for the next.config of the project in `dir`, e.g. a monorepo app.
+pub fn option_in(dir: &Path, path: &[&str]) -> Option<String> {
+    let content = NEXT_CONFIG_FILES
+        .into_iter()
+        .find_map(|file| fs::read_to_string(dir.join(file)).ok())?;
+    let (mut open, mut close) = config_object(&content)?;
+    let (last, parents) = path.split_last()?;
+    for key in parents {
+        let entry = object_entries(&content, open, close).into_iter().find(|entry| entry.key == *key)?;
+        let (start, _) = entry.value?;
+        if content.as_bytes()[start] != b'{' {
+            return None;
+        }
+        (open, close) = (start, find_closing(&content, start)?);
+    }
+    let entry = object_entries(&content, open, close).into_iter().find(|entry| entry.key == *last)?;
+    let (start, end) = entry.value?;
+    Some(content[start..end].to_string())
+}
+
+/// The items of an array option such as `pageExtensions` in the next.config of the project
+/// in `dir`, unquoted.
+pub fn array_option_in(dir: &Path, path: &[&str]) -> Option<Vec<String>> {
+    let value = option_in(dir, path)?;
+    let items = value.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        split_top_level(items, 0, items.len(), b',')
+            .into_iter()
+            .map(|(start, end)| items[start..end].trim().trim_matches(|c| c == '"' || c == '\'' || c == '`').to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+fn next_config_path() -> Result<PathBuf> {
+    find_next_config().context("Could not find next.config.ts, next.config.mjs or next.config.js")
+}
+
+/// Adds `key: value` to the top level of the exported Next.js config object.
+/// Returns `Ok(false)` when the key is already present so user settings are left untouched.
+pub fn set_next_config_option(key: &str, value: &str) -> Result<bool> {
+    set_nested_option(&[key], value)
+}
+
+/// Sets a nested option such as `["images", "unoptimized"]`, creating the parent objects
+/// and merging into existing ones. Returns `Ok(false)` when the option is already set.
+pub fn set_nested_option(path: &[&str], value: &str) -> Result<bool> {
+    set_option_in(&next_config_path()?, path, value)
+}
+
+/// Appends `items` (JavaScript expressions) to an array option such as
+/// `["experimental", "optimizePackageImports"]`, skipping items it already contains.
+/// Returns `Ok(false)` when nothing had to be added.
+pub fn add_to_array_option(path: &[&str], items: &[&str]) -> Result<bool> {
+    add_to_array_option_in(&next_config_path()?, path, items)
+}
+
+/// `set_nested_option` for other config files that export an object the same way,
+/// e.g. tailwind.config.ts.
+pub fn set_option_in(config_path: &Path, path: &[&str], value: &str) -> Result<bool> {
+    patch(config_path, path, Change::Set(value), &format!("Set `{}: {}` manually.", path.join("."), value))
+}
+
+/// `add_to_array_option` for other config files that export an object the same way.
+pub fn add_to_array_option_in(config_path: &Path, path: &[&str], items: &[&str]) -> Result<bool> {
+    patch(
+        config_path,
+        path,
+        Change::Append(items),
+        &format!("Add {} to `{}` manually.", items.join(", "), path.join(".")),
+    )
+}
+
+/// Adds `import <clause> from "module"` (or the require equivalent for CommonJS configs)
+/// to the top of next.config, with optional `setup` code inserted just before the config export.
+/// `clause` is either a default binding (`name`) or named bindings (`{ a, b }`).
+pub fn add_import(clause: &str, module: &str, setup: Option<&str>) -> Result<bool> {
+    let (config_path, content) = read_next_config()?;
+
+    if content.contains(&format!("\"{}\"", module)) || content.contains(&format!("'{}'", module)) {
+        return Ok(false);
+    }
+
+    let import_line = if content.contains("module.exports") {
+        format!("const {} = require(\"{}\");", clause, module)
+    } else {
+        format!("import {} from \"{}\";", clause, module)
+    };
+
+    let mut updated_content = format!("{}\n{}", import_line, content);
+    if let Some(setup) = setup {
+        // Setup code must run before the config is exported
+        updated_content = match find_export(&updated_content) {
+            Some((_, start)) => format!(
+                "{}{}\n\n{}",
+                &updated_content[..start],
+                setup,
+                &updated_content[start..]
+            ),
+            None => format!("{}\n{}\n", updated_content.trim_end(), setup),
+        };
+    }
+
+    write_next_config(&config_path, updated_content)?;
+    Ok(true)
+}
+
+/// Index where the exported expression starting at `start` ends: the first `;` or line
+/// break outside brackets, so multi-line `withA(withB({ ... }))` exports stay intact.
+fn expression_end(content: &str, start: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        if let Some(next) = skip_literal(bytes, i) {
+            i = next;
+            continue;
+        }
+        match bytes[i] {
+            b'{' | b'[' | b'(' => match find_closing(content, i) {
+                Some(close) => i = close,
+                None => return bytes.len(),
+            },
+            b';' | b'\n' => return i,
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// `content` with the exported expression wrapped in `wrapper(...)`, or `None` without an export.
+fn wrapped(content: &str, wrapper: &str) -> Option<String> {
+    let (export, start) = find_export(content)?;
+    let expression_start = start + export.len();
+    let expression_end = expression_end(content, expression_start);
+    let expression = content[expression_start..expression_end].trim();
+
+    Some(format!(
+        "{}{}({}){}",
+        &content[..expression_start],
+        wrapper,
+        expression,
+        &content[expression_end..]
+    ))
+}
+
+/// Wraps the exported config in `wrapper(...)`, e.g. `export default withPlugin(nextConfig);`.
+/// Returns `Ok(false)` when the export is already wrapped by `wrapper`.
+pub fn wrap_export(wrapper: &str) -> Result<bool> {
+    let (config_path, content) = read_next_config()?;
+
+    if content.contains(&format!("{}(", wrapper)) {
+        return Ok(false);
+    }
+
+    let Some(updated_content) = wrapped(&content, wrapper) else {
+        anyhow::bail!("Could not locate the config export in {}. Wrap it with `{}` manually.", config_path.display(), wrapper);
+    };
+
+    write_next_config(&config_path, updated_content)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Patches `content` the way `patch` does, without touching the filesystem.
+    fn patched(content: &str, path: &[&str], change: Change) -> Result<Option<String>> {
+        let (open, close) = config_object(content).context("no config object")?;
+        patch_object(content, open, close, path, &change)
+    }
+
+    #[test]
+    fn sets_option_in_module_exports() {
+        let content = "module.exports = {\n  reactStrictMode: true,\n};\n";
+        let updated = patched(content, &["output"], Change::Set("\"standalone\"")).unwrap().unwrap();
+        assert_eq!(updated, "module.exports = {\n  output: \"standalone\",\n  reactStrictMode: true,\n};\n");
+    }
+
+    #[test]
+    fn sets_option_in_export_default_object() {
+        let content = "export default {};\n";
+        let updated = patched(content, &["output"], Change::Set("\"export\"")).unwrap().unwrap();
+        assert_eq!(updated, "export default {\n  output: \"export\",\n};\n");
+    }
+
+    #[test]
+    fn follows_export_to_next_config_variable() {
+        let content = "import type { NextConfig } from \"next\";\n\nconst nextConfig: NextConfig = {\n  /* config options here */\n};\n\nexport default nextConfig;\n";
+        let updated = patched(content, &["experimental", "typedRoutes"], Change::Set("true")).unwrap().unwrap();
+        assert!(updated.contains("const nextConfig: NextConfig = {\n  experimental: {\n    typedRoutes: true,\n  },\n"));
+        assert!(updated.ends_with("export default nextConfig;\n"));
+    }
+
+    #[test]
+    fn follows_export_through_wrappers() {
+        let content = "const nextConfig = { images: { remotePatterns: [] } };\nexport default withA(withB(nextConfig));\n";
+        let updated = patched(content, &["images", "remotePatterns"], Change::Append(&["{ hostname: \"a.dev\" }"]))
+            .unwrap()
+            .unwrap();
+        assert!(updated.starts_with("const nextConfig = { images: { remotePatterns: [{ hostname: \"a.dev\" }] } };"));
+
+        let inline = "export default withA({\n  reactStrictMode: true,\n});\n";
+        let updated = patched(inline, &["poweredByHeader"], Change::Set("false")).unwrap().unwrap();
+        assert_eq!(updated, "export default withA({\n  poweredByHeader: false,\n  reactStrictMode: true,\n});\n");
+    }
+
+    #[test]
+    fn leaves_existing_options_and_items_alone() {
+        let content = "module.exports = { output: \"export\", transpilePackages: ['ui'] };\n";
+        assert!(patched(content, &["output"], Change::Set("\"standalone\"")).unwrap().is_none());
+        assert!(patched(content, &["transpilePackages"], Change::Append(&["\"ui\""])).unwrap().is_none());
+    }
+
+    #[test]
+    fn ignores_config_text_in_comments_and_strings() {
+        let content = "// export default { fake: true }\nconst nextConfig = {\n  basePath: \"/}\",\n};\nexport default nextConfig;\n";
+        let updated = patched(content, &["trailingSlash"], Change::Set("true")).unwrap().unwrap();
+        assert!(updated.contains("const nextConfig = {\n  trailingSlash: true,\n  basePath: \"/}\",\n};"));
+    }
+
+    #[test]
+    fn fails_on_configs_it_cannot_follow() {
+        // Curried plugins take their options first, so the config is not the first argument
+        assert!(config_object("export default withA({ a: 1 })(nextConfig);\n").is_none());
+        assert!(config_object("export default async () => ({ output: \"export\" });\n").is_none());
+        assert!(config_object("export default function config() { return {}; }\n").is_none());
+        assert!(config_object("const config = createConfig();\nexport default config;\n").is_none());
+        assert!(config_object("const nextConfig = {};\n").is_none());
+
+        let content = "const images = {};\nexport default { images };\n";
+        assert!(patched(content, &["images", "unoptimized"], Change::Set("true")).is_err());
+    }
+
+    #[test]
+    fn wraps_the_exported_expression() {
+        assert_eq!(
+            wrapped("export default nextConfig;\n", "withA").unwrap(),
+            "export default withA(nextConfig);\n"
+        );
+        assert_eq!(
+            wrapped("module.exports = withB({\n  output: \"export\",\n})\n", "withA").unwrap(),
+            "module.exports = withA(withB({\n  output: \"export\",\n}))\n"
+        );
+        assert!(wrapped("const nextConfig = {};\n", "withA").is_none());
+    }
+}
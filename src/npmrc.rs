@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+use crate::config;
+
+/// Registry from `--registry`, overriding every .npmrc for this run.
+static REGISTRY: Mutex<Option<String>> = Mutex::new(None);
+
+pub fn set_registry(registry: Option<String>) {
+    *REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = registry;
+}
+
+fn registry_override() -> Option<String> {
+    REGISTRY.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Expands `${VAR}` references the way npm does, leaving unknown variables empty.
+fn expand_env(value: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&std::env::var(&rest[start + 2..start + end]).unwrap_or_default());
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// The `key=value` settings of an .npmrc file, skipping comments.
+fn read(path: &Path) -> BTreeMap<String, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return BTreeMap::new();
+    };
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with(';'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), expand_env(value.trim().trim_matches('"'))))
+        .collect()
+}
+
+/// A setting from the project's .npmrc, then the user's ~/.npmrc.
+pub fn value(key: &str) -> Option<String> {
+    let user = config::home_dir().map(|home| home.join(".npmrc"));
+    [Some(Path::new(".npmrc").to_path_buf()), user]
+        .into_iter()
+        .flatten()
+        .find_map(|path| read(&path).remove(key))
+        .filter(|value| !value.is_empty())
+}
+
+/// Settings that decide where packages come from: registries, scoped registries and proxies.
+fn is_network_setting(key: &str) -> bool {
+    key == "registry"
+        || key.ends_with(":registry")
+        || matches!(key, "proxy" | "https-proxy" | "noproxy" | "strict-ssl" | "cafile")
+}
+
+/// Passes the registry and proxy settings of the project's .npmrc, and `--registry`, to a
+/// spawned package manager as `npm_config_*` variables. They then apply even where the
+/// command runs outside the project, e.g. a creator scaffolding a new directory or an
+/// install run from the workspace root. Auth tokens stay in the file; npm reads them there.
+pub fn pass_to(command: &mut Command) {
+    for (key, value) in read(Path::new(".npmrc")) {
+        if is_network_setting(&key) {
+            command.env(format!("npm_config_{}", key), value);
+        }
+    }
+    if let Some(registry) = registry_override() {
+        // bun and Deno only read the upper-case name, yarn classic its own
+        command.env("npm_config_registry", &registry);
+        command.env("NPM_CONFIG_REGISTRY", &registry);
+        command.env("YARN_REGISTRY", &registry);
+    }
+}
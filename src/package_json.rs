@@ -0,0 +1,162 @@
+use anyhow::{Result, Context};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::change_set;
+use crate::project_structure::project_file;
+
+const PACKAGE_JSON_PATH: &str = "package.json";
+
+pub fn read() -> Result<Value> {
+    let content = fs::read_to_string(project_file(PACKAGE_JSON_PATH))
+        .context("Failed to read package.json")?;
+    serde_json::from_str(&content).context("Failed to parse package.json")
+}
+
+pub fn has_dependency(name: &str) -> bool {
+    let Ok(package_json) = read() else {
+        return false;
+    };
+
+    ["dependencies", "devDependencies"]
+        .iter()
+        .any(|section| package_json[section].get(name).is_some())
+}
+
+pub fn package_name() -> Option<String> {
+    read()
+        .ok()
+        .and_then(|package_json| package_json["name"].as_str().map(str::to_string))
+}
+
+pub fn has_script(name: &str) -> bool {
+    read()
+        .map(|package_json| package_json["scripts"].get(name).is_some())
+        .unwrap_or(false)
+}
+
+pub fn script_names() -> Vec<String> {
+    read()
+        .ok()
+        .and_then(|package_json| package_json["scripts"].as_object().map(|scripts| scripts.keys().cloned().collect()))
+        .unwrap_or_default()
+}
+
+/// Returns the command of an existing script.
+pub fn script(name: &str) -> Option<String> {
+    read()
+        .ok()
+        .and_then(|package_json| package_json["scripts"][name].as_str().map(str::to_string))
+}
+
+pub fn write(package_json: &Value) -> Result<()> {
+    let content = serde_json::to_string_pretty(package_json)?;
+    change_set::write(project_file(PACKAGE_JSON_PATH), content + "\n").context("Failed to update package.json")
+}
+
+/// Adds the given scripts, keeping any script the project already defines under the same name.
+/// Returns the names of the scripts that were added.
+pub fn add_scripts(scripts: &[(&str, &str)]) -> Result<Vec<String>> {
+    let mut package_json = read()?;
+
+    if !package_json["scripts"].is_object() {
+        package_json["scripts"] = Value::Object(Default::default());
+    }
+    let existing = package_json["scripts"].as_object_mut().unwrap();
+
+    let mut added = Vec::new();
+    for (name, command) in scripts {
+        if !existing.contains_key(*name) {
+            existing.insert(name.to_string(), Value::String(command.to_string()));
+            added.push(name.to_string());
+        }
+    }
+
+    if !added.is_empty() {
+        write(&package_json)?;
+    }
+    Ok(added)
+}
+
+/// Sets the given scripts, replacing any existing script with the same name.
+pub fn set_scripts(scripts: &[(&str, &str)]) -> Result<()> {
+    let mut package_json = read()?;
+
+    if !package_json["scripts"].is_object() {
+        package_json["scripts"] = Value::Object(Default::default());
+    }
+    let existing = package_json["scripts"].as_object_mut().unwrap();
+    for (name, command) in scripts {
+        existing.insert(name.to_string(), Value::String(command.to_string()));
+    }
+
+    write(&package_json)
+}
+
+/// Returns the version range declared for `name`, e.g. `^3.4.1`.
+pub fn dependency_version(name: &str) -> Option<String> {
+    let package_json = read().ok()?;
+
+    ["dependencies", "devDependencies"]
+        .iter()
+        .find_map(|section| package_json[section][name].as_str().map(str::to_string))
+}
+
+/// Every package declared in `section` (`dependencies` or `devDependencies`) with its range.
+pub fn dependencies(section: &str) -> BTreeMap<String, String> {
+    let Ok(package_json) = read() else {
+        return BTreeMap::new();
+    };
+    package_json[section]
+        .as_object()
+        .map(|packages| {
+            packages
+                .iter()
+                .filter_map(|(name, range)| range.as_str().map(|range| (name.clone(), range.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The exact version the package manager resolved for `name`, read from its package.json
+/// in node_modules so it matches the lockfile whichever manager wrote it. Workspaces may
+/// hoist it to the repository's node_modules.
+pub fn installed_version(name: &str) -> Option<String> {
+    let content = [project_file("node_modules"), Path::new("node_modules").to_path_buf()]
+        .iter()
+        .find_map(|node_modules| fs::read_to_string(node_modules.join(name).join("package.json")).ok())?;
+    let package_json: Value = serde_json::from_str(&content).ok()?;
+    package_json["version"].as_str().map(str::to_string)
+}
+
+/// Major version of a dependency, ignoring range operators like `^`, `~` or `>=`.
+pub fn dependency_major_version(name: &str) -> Option<u32> {
+    let version = dependency_version(name)?;
+    version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// The `engines.node` range, e.g. `>=20.9.0`.
+pub fn node_engine() -> Option<String> {
+    read()
+        .ok()
+        .and_then(|package_json| package_json["engines"]["node"].as_str().map(str::to_string))
+}
+
+/// Names of all dependencies and devDependencies.
+pub fn dependency_names() -> Vec<String> {
+    let Ok(package_json) = read() else {
+        return Vec::new();
+    };
+
+    ["dependencies", "devDependencies"]
+        .iter()
+        .filter_map(|section| package_json[section].as_object())
+        .flat_map(|deps| deps.keys().cloned())
+        .collect()
+}
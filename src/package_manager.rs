@@ -1,18 +1,220 @@
 use anyhow::{Result, Context};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::fs;
-use std::path::Path;
+use std::sync::Mutex;
+use crate::config;
+use crate::npmrc;
+use crate::process;
+use crate::project_structure::{app_dir, project_file};
 
+/// How installs behave, from the global `--frozen`, `--prefer-offline` and `--silent` flags.
+#[derive(Debug, Clone, Copy)]
+pub struct InstallOptions {
+    /// Fail instead of changing the lockfile
+    pub frozen: bool,
+    /// Use cached packages without checking the registry for newer ones
+    pub prefer_offline: bool,
+    /// Only print errors
+    pub silent: bool,
+}
+
+static INSTALL_OPTIONS: Mutex<InstallOptions> =
+    Mutex::new(InstallOptions { frozen: false, prefer_offline: false, silent: false });
+
+pub fn set_install_options(options: InstallOptions) {
+    *INSTALL_OPTIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = options;
+}
+
+/// The options for this run: command-line flags, with config keys turning them on for
+/// every run.
+pub fn install_options() -> InstallOptions {
+    let flags = *INSTALL_OPTIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let enabled = |key: &str| config::value(key).as_deref() == Some("true");
+    InstallOptions {
+        frozen: flags.frozen || enabled("frozen_lockfile"),
+        prefer_offline: flags.prefer_offline || enabled("prefer_offline"),
+        silent: flags.silent || enabled("silent_install"),
+    }
+}
+
+/// A package of a monorepo that installs are scoped to.
 #[derive(Debug, Clone)]
+pub struct Workspace {
+    /// The monorepo root, where scoped installs run
+    pub root: PathBuf,
+    /// Directory of the package relative to the root, e.g. `apps/web`
+    pub dir: String,
+    /// The package's name from its package.json
+    pub name: String,
+}
+
+impl Workspace {
+    /// The workspace the app nstack works on belongs to, when an ancestor is a monorepo
+    /// root whose `.nstack/config` lists the app's directory under `workspaces`.
+    pub fn detect() -> Option<Self> {
+        let current = std::env::current_dir().ok()?.join(app_dir().unwrap_or_default());
+        current.ancestors().skip(1).find_map(|root| {
+            let workspaces = config::get_in(root, "workspaces")?;
+            let dir = current.strip_prefix(root).ok()?.to_string_lossy().replace('\\', "/");
+            if !workspaces.split(',').any(|workspace| workspace.trim() == dir) {
+                return None;
+            }
+            let name = crate::package_json::package_name()?;
+            Some(Workspace { root: root.to_path_buf(), dir, name })
+        })
+    }
+}
+
+static WORKSPACE: Mutex<Option<Workspace>> = Mutex::new(None);
+
+/// Scopes this run's installs and removals to `workspace`, run from the monorepo root.
+pub fn set_workspace(workspace: Option<Workspace>) {
+    *WORKSPACE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = workspace;
+}
+
+fn workspace() -> Option<Workspace> {
+    WORKSPACE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// One package manager invocation with every argument kept separate, so a flag like `-D`
+/// is never glued to its subcommand.
+#[derive(Debug, Clone)]
+pub struct CommandLine {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Where to run, the app nstack works on when `None`
+    pub dir: Option<PathBuf>,
+}
+
+impl CommandLine {
+    fn new(program: impl Into<String>, args: &[&str]) -> Self {
+        CommandLine { program: program.into(), args: args.iter().map(|arg| arg.to_string()).collect(), dir: None }
+    }
+
+    /// The process to spawn, with the project's registry settings passed through.
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args);
+        if let Some(dir) = self.dir.clone().or_else(app_dir) {
+            command.current_dir(dir);
+        }
+        npmrc::pass_to(&mut command);
+        command
+    }
+
+    /// Runs the command in the foreground, failing when it exits non-zero.
+    pub fn run(&self) -> Result<()> {
+        let status = self.command().status().context(format!("Failed to run {}", self))?;
+        if !status.success() {
+            anyhow::bail!("Command failed: {}", self);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CommandLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for the command that adds packages, from `PackageManager::add`.
+pub struct InstallCommand<'a> {
+    package_manager: &'a PackageManager,
+    packages: Vec<String>,
+    dev: bool,
+}
+
+impl InstallCommand<'_> {
+    /// Adds the packages as devDependencies.
+    pub fn dev(mut self, dev: bool) -> Self {
+        self.dev = dev;
+        self
+    }
+
+    /// The program and arguments, with this run's install flags and workspace scope.
+    pub fn build(&self) -> CommandLine {
+        let package_manager = self.package_manager;
+        let subcommand: &[&str] = match (package_manager, self.dev) {
+            (PackageManager::Npm, false) => &["install"],
+            (PackageManager::Npm, true) => &["install", "-D"],
+            (_, false) => &["add"],
+            (_, true) => &["add", "-D"],
+        };
+
+        let mut command_line = package_manager.command_line(subcommand);
+        command_line.args.extend(package_manager.install_flags(&install_options()).into_iter().map(str::to_string));
+        command_line.args.extend(self.packages.iter().map(|package| match package_manager {
+            // Deno adds packages from JSR by default, so npm packages need their specifier
+            PackageManager::Deno if !package.starts_with("npm:") && !package.starts_with("jsr:") => {
+                format!("npm:{}", package)
+            }
+            _ => package.clone(),
+        }));
+        command_line
+    }
+
+    pub fn run(&self) -> Result<()> {
+        if install_options().frozen {
+            let unlocked: Vec<&str> = self
+                .packages
+                .iter()
+                .map(String::as_str)
+                .filter(|package| !self.package_manager.is_locked(package))
+                .collect();
+            // Already declared and locked, so there is nothing to install
+            if unlocked.is_empty() {
+                return Ok(());
+            }
+            anyhow::bail!(
+                "Installing {} would change the lockfile, which --frozen and frozen_lockfile do not allow",
+                unlocked.join(", ")
+            );
+        }
+
+        let command_line = self.build();
+        let status = process::run_quiet(&mut command_line.command(), &format!("Installing {}...", self.packages.join(", ")))
+            .context(format!("Failed to run {}", command_line))?;
+        if !status.success() {
+            anyhow::bail!("Failed to install {}", self.packages.join(", "));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum PackageManager {
     Npm,
     Yarn,
     Pnpm,
     Bun,
+    Deno,
 }
 
 impl PackageManager {
+    pub const ALL: [PackageManager; 5] = [
+        PackageManager::Npm,
+        PackageManager::Yarn,
+        PackageManager::Pnpm,
+        PackageManager::Bun,
+        PackageManager::Deno,
+    ];
+
+    /// The package manager whose lockfile is in the app or the current directory, if any.
+    pub fn from_lockfile() -> Option<Self> {
+        Self::ALL.into_iter().find(|pm| pm.find_lockfile().is_some())
+    }
+
+    /// Prefers the project's lockfile, then whichever package manager is installed.
     pub fn detect() -> Result<Self> {
+        if let Some(package_manager) = Self::from_lockfile() {
+            return Ok(package_manager);
+        }
         if Command::new("bun").arg("--version").output().is_ok() {
             return Ok(PackageManager::Bun);
         }
@@ -25,50 +227,174 @@ impl PackageManager {
         if Command::new("npm").arg("--version").output().is_ok() {
             return Ok(PackageManager::Npm);
         }
-        anyhow::bail!("No package manager found. Please install npm, yarn, pnpm, or bun.")
+        if Command::new("deno").arg("--version").output().is_ok() {
+            return Ok(PackageManager::Deno);
+        }
+        anyhow::bail!("No package manager found. Please install npm, yarn, pnpm, bun or deno.")
     }
 
+    pub fn parse(name: &str) -> Result<Self> {
+        match Self::ALL.into_iter().find(|pm| pm.to_string() == name) {
+            Some(package_manager) => Ok(package_manager),
+            None => anyhow::bail!("Unknown package manager '{}'. Use npm, yarn, pnpm, bun or deno", name),
+        }
+    }
+
+    /// Uses `package_manager` from the project or global nstack config, falling back to
+    /// whichever package manager is installed.
     pub fn from_project_config() -> Result<Self> {
-        let config_path = Path::new(".nstack").join("config");
-        if !config_path.exists() {
-            return Self::detect();
-        }
-
-        let config_content = fs::read_to_string(&config_path)
-            .context("Failed to read .nstack/config file")?;
-
-        for line in config_content.lines() {
-            if line.starts_with("package_manager=") {
-                let pm = line.split('=').nth(1).unwrap_or("").trim();
-                return match pm {
-                    "npm" => Ok(PackageManager::Npm),
-                    "yarn" => Ok(PackageManager::Yarn),
-                    "pnpm" => Ok(PackageManager::Pnpm),
-                    "bun" => Ok(PackageManager::Bun),
-                    _ => Self::detect(),
-                };
+        match config::value("package_manager").and_then(|name| Self::parse(&name).ok()) {
+            Some(package_manager) => Ok(package_manager),
+            None => Self::detect(),
+        }
+    }
+
+    /// Starts building the command that adds `packages`, e.g.
+    /// `package_manager.add(&["vitest"]).dev(true).run()`.
+    pub fn add<'a>(&'a self, packages: &[&str]) -> InstallCommand<'a> {
+        InstallCommand {
+            package_manager: self,
+            packages: packages.iter().map(|package| package.to_string()).collect(),
+            dev: false,
+        }
+    }
+
+    /// This manager's flags for `options`. bun and Deno have no prefer-offline mode, so it
+    /// is ignored for them; frozen installs are handled by the callers.
+    fn install_flags(&self, options: &InstallOptions) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        if options.prefer_offline && !matches!(self, PackageManager::Bun | PackageManager::Deno) {
+            flags.push("--prefer-offline");
+        }
+        if options.silent {
+            flags.push(match self {
+                PackageManager::Deno => "--quiet",
+                _ => "--silent",
+            });
+        }
+        flags
+    }
+
+    /// `subcommand`, e.g. `["add", "-D"]`, scoped to the run's workspace when one is set:
+    /// `pnpm --filter web add`, `npm install -w apps/web`, `yarn workspace web add` or
+    /// `bun add --cwd apps/web`. Deno has no filter flag, so it runs in the package.
+    fn command_line(&self, subcommand: &[&str]) -> CommandLine {
+        let mut command_line = CommandLine::new(self.to_string(), subcommand);
+        let Some(workspace) = workspace() else {
+            return command_line;
+        };
+
+        let (before, after): (Vec<&str>, Vec<&str>) = match self {
+            PackageManager::Npm => (vec![], vec!["-w", &workspace.dir]),
+            PackageManager::Yarn => (vec!["workspace", &workspace.name], vec![]),
+            PackageManager::Pnpm => (vec!["--filter", &workspace.name], vec![]),
+            PackageManager::Bun => (vec![], vec!["--cwd", &workspace.dir]),
+            PackageManager::Deno => {
+                command_line.dir = Some(workspace.root.join(&workspace.dir));
+                return command_line;
             }
+        };
+        command_line.args = before
+            .into_iter()
+            .chain(subcommand.iter().copied())
+            .chain(after)
+            .map(str::to_string)
+            .collect();
+        command_line.dir = Some(workspace.root.clone());
+        command_line
+    }
+
+    /// Whether `package`, e.g. `zod` or `@scope/pkg@^2`, is declared in package.json and
+    /// resolved in the lockfile, so adding it would leave the lockfile as it is.
+    fn is_locked(&self, package: &str) -> bool {
+        let package = package.trim_start_matches("npm:");
+        // The version starts at the first `@` that does not open a scope
+        let name = match package.get(1..).and_then(|rest| rest.find('@')) {
+            Some(at) => &package[..at + 1],
+            None => package,
+        };
+        if !crate::package_json::has_dependency(name) {
+            return false;
         }
 
-        Self::detect()
+        let lockfile = match workspace() {
+            Some(workspace) => workspace.root.join(self.lockfile()),
+            None => self.find_lockfile().unwrap_or_else(|| PathBuf::from(self.lockfile())),
+        };
+        let binary = lockfile.extension().is_some_and(|extension| extension == "lockb");
+        std::fs::read(&lockfile).is_ok_and(|content| {
+            // bun.lockb is binary, so the best it offers is the bare name
+            let keys = if binary { vec![name.to_string()] } else { self.lock_keys(name) };
+            keys.iter().any(|key| content.windows(key.len()).any(|window| window == key.as_bytes()))
+        })
     }
 
-    pub fn install_command(&self) -> (&'static str, &'static str) {
+    /// How a resolved `name` starts its entry in this manager's text lockfile, anchored so
+    /// `react` does not match `react-dom` and `pkg` does not match `@scope/pkg`.
+    fn lock_keys(&self, name: &str) -> Vec<String> {
         match self {
-            PackageManager::Npm => ("npm", "install"),
-            PackageManager::Yarn => ("yarn", "add"),
-            PackageManager::Pnpm => ("pnpm", "add"),
-            PackageManager::Bun => ("bun", "add"),
+            // "node_modules/zod": { ... }
+            PackageManager::Npm => vec![format!("node_modules/{}\"", name)],
+            // "zod@^3.0.0": or zod@^3.0.0, zod@^3.1.0:
+            PackageManager::Yarn => vec![format!("\"{}@", name), format!("\n{}@", name), format!(" {}@", name)],
+            // /zod/3.0.0: (v5), /zod@3.0.0: (v6), zod@3.0.0: or '@scope/pkg@1.0.0': (v9)
+            PackageManager::Pnpm => vec![format!(" /{}/", name), format!(" /{}@", name), format!(" {}@", name), format!("'{}@", name)],
+            // "zod": ["zod@3.0.0", ...]
+            PackageManager::Bun => vec![format!("\"{}@", name)],
+            // "npm:zod@3.0.0" (v3) or "zod@3.0.0" (v4)
+            PackageManager::Deno => vec![format!("\"{}@", name), format!("npm:{}@", name)],
         }
     }
 
-    pub fn install_dev_command(&self) -> (&'static str, &'static str) {
+    /// Installs `packages` as dependencies, or devDependencies when `dev` is set.
+    pub fn install(&self, packages: &[&str], dev: bool) -> Result<()> {
+        self.add(packages).dev(dev).run()
+    }
+
+    /// Installs every dependency of the project or workspace in `dir`.
+    /// With `--frozen` it runs the manager's frozen install instead, e.g. `npm ci`.
+    pub fn install_all(&self, dir: &Path) -> Result<()> {
+        let options = install_options();
+        let mut command_line = if options.frozen {
+            self.frozen_install_line()
+        } else {
+            CommandLine::new(self.to_string(), &["install"])
+        };
+        command_line.args.extend(self.install_flags(&options).into_iter().map(str::to_string));
+        command_line.dir = Some(dir.to_path_buf());
+
+        let status = process::run_quiet(&mut command_line.command(), "Installing dependencies...")
+            .context(format!("Failed to run {}", command_line))?;
+        if !status.success() {
+            anyhow::bail!("Failed to install dependencies in {}", dir.display());
+        }
+        Ok(())
+    }
+
+    /// Version range that links a dependency to a package in the same workspace. npm and
+    /// yarn classic resolve `*` to the local package; pnpm and bun need the protocol.
+    pub fn workspace_version(&self) -> &'static str {
         match self {
-            PackageManager::Npm => ("npm", "install -D"),
-            PackageManager::Yarn => ("yarn", "add -D"),
-            PackageManager::Pnpm => ("pnpm", "add -D"),
-            PackageManager::Bun => ("bun", "add -D"),
+            PackageManager::Npm | PackageManager::Yarn => "*",
+            PackageManager::Pnpm | PackageManager::Bun | PackageManager::Deno => "workspace:*",
+        }
+    }
+
+    pub fn uninstall(&self, packages: &[&str]) -> Result<()> {
+        let remove = match self {
+            PackageManager::Npm => "uninstall",
+            PackageManager::Yarn | PackageManager::Pnpm | PackageManager::Bun | PackageManager::Deno => "remove",
+        };
+
+        let mut command_line = self.command_line(&[remove]);
+        command_line.args.extend(packages.iter().map(|package| package.to_string()));
+        let status = process::run_quiet(&mut command_line.command(), &format!("Removing {}...", packages.join(", ")))
+            .context(format!("Failed to run {}", command_line))?;
+
+        if !status.success() {
+            anyhow::bail!("Failed to remove {}", packages.join(", "));
         }
+        Ok(())
     }
 
     pub fn create_next_app_command(&self) -> (&'static str, Vec<&'static str>) {
@@ -77,15 +403,209 @@ impl PackageManager {
             PackageManager::Yarn => ("yarn", vec!["create", "next-app"]),
             PackageManager::Pnpm => ("pnpm", vec!["create", "next-app"]),
             PackageManager::Bun => ("bunx", vec!["create-next-app"]),
+            PackageManager::Deno => ("deno", vec!["run", "-A", "npm:create-next-app@latest"]),
         }
     }
 
+    /// Every lockfile this manager writes, current format first. bun wrote the binary
+    /// bun.lockb before 1.2 and keeps using it in projects that have one.
+    pub fn lockfiles(&self) -> &'static [&'static str] {
+        match self {
+            PackageManager::Npm => &["package-lock.json"],
+            PackageManager::Yarn => &["yarn.lock"],
+            PackageManager::Pnpm => &["pnpm-lock.yaml"],
+            PackageManager::Bun => &["bun.lock", "bun.lockb"],
+            PackageManager::Deno => &["deno.lock"],
+        }
+    }
+
+    /// The project's lockfile for this manager, or the one a fresh install writes.
+    pub fn lockfile(&self) -> &'static str {
+        let lockfiles = self.lockfiles();
+        lockfiles
+            .iter()
+            .copied()
+            .find(|lockfile| project_file(lockfile).exists() || Path::new(lockfile).exists())
+            .unwrap_or(lockfiles[0])
+    }
+
+    /// Path of this manager's lockfile: the app's own, or the repository's when the app's
+    /// dependencies are locked alongside other apps.
+    fn find_lockfile(&self) -> Option<PathBuf> {
+        self.lockfiles()
+            .iter()
+            .flat_map(|lockfile| [project_file(lockfile), PathBuf::from(lockfile)])
+            .find(|path| path.exists())
+    }
+
+    /// Installs exactly what the lockfile lists, failing when package.json disagrees with it.
+    pub fn frozen_install_line(&self) -> CommandLine {
+        match self {
+            PackageManager::Npm => CommandLine::new("npm", &["ci"]),
+            PackageManager::Yarn => CommandLine::new("yarn", &["install", "--frozen-lockfile"]),
+            PackageManager::Pnpm => CommandLine::new("pnpm", &["install", "--frozen-lockfile"]),
+            PackageManager::Bun => CommandLine::new("bun", &["install", "--frozen-lockfile"]),
+            PackageManager::Deno => CommandLine::new("deno", &["install", "--frozen"]),
+        }
+    }
+
+    /// The frozen install as one line, for Dockerfiles and CI workflows.
+    pub fn frozen_install_command(&self) -> String {
+        self.frozen_install_line().to_string()
+    }
+
+    /// Prints the vulnerability report as JSON, or `None` when the manager has no audit.
+    pub fn audit_command(&self) -> Option<CommandLine> {
+        match self {
+            PackageManager::Deno => None,
+            _ => Some(CommandLine::new(self.to_string(), &["audit", "--json"])),
+        }
+    }
+
+    /// Collapses duplicate versions in the lockfile. Yarn classic, bun and Deno have no
+    /// built-in dedupe.
+    pub fn dedupe_command(&self) -> Option<CommandLine> {
+        match self {
+            PackageManager::Npm | PackageManager::Pnpm => Some(CommandLine::new(self.to_string(), &["dedupe"])),
+            PackageManager::Yarn | PackageManager::Bun | PackageManager::Deno => None,
+        }
+    }
+
+    fn exec_prefix(&self) -> &'static [&'static str] {
+        match self {
+            PackageManager::Npm => &["npx"],
+            PackageManager::Yarn => &["yarn"],
+            PackageManager::Pnpm => &["pnpm", "exec"],
+            PackageManager::Bun => &["bunx"],
+            PackageManager::Deno => &["deno", "run", "-A"],
+        }
+    }
+
+    // Runs a package binary without adding it to the project. Yarn classic has no
+    // `dlx`, so fall back to npx which ships with every Node install.
+    fn dlx_prefix(&self) -> &'static [&'static str] {
+        match self {
+            PackageManager::Npm | PackageManager::Yarn => &["npx"],
+            PackageManager::Pnpm => &["pnpm", "dlx"],
+            PackageManager::Bun => &["bunx"],
+            PackageManager::Deno => &["deno", "run", "-A"],
+        }
+    }
+
+    /// `command` behind `prefix`; Deno runs npm binaries through their `npm:` specifier.
+    fn prefixed_command(&self, prefix: &[&str], command: &str) -> String {
+        let specifier = if *self == PackageManager::Deno { "npm:" } else { "" };
+        format!("{} {}{}", prefix.join(" "), specifier, command)
+    }
+
+    /// `args` behind `prefix` as separate arguments, so quoting and spaces in them survive.
+    fn prefixed_line(&self, prefix: &[&str], args: &[&str]) -> CommandLine {
+        let mut command_line = CommandLine::new(prefix[0], &prefix[1..]);
+        for (index, arg) in args.iter().enumerate() {
+            if index == 0 && *self == PackageManager::Deno {
+                command_line.args.push(format!("npm:{}", arg));
+            } else {
+                command_line.args.push(arg.to_string());
+            }
+        }
+        command_line
+    }
+
+    pub fn exec_command(&self, command: &str) -> String {
+        self.prefixed_command(self.exec_prefix(), command)
+    }
+
+    pub fn dlx_command(&self, command: &str) -> String {
+        self.prefixed_command(self.dlx_prefix(), command)
+    }
+
+    /// A binary installed in the project with its arguments, e.g. `exec_line(&["tsc", "--noEmit"])`.
+    pub fn exec_line(&self, args: &[&str]) -> CommandLine {
+        self.prefixed_line(self.exec_prefix(), args)
+    }
+
+    /// Runs a binary installed in the project, e.g. `exec(&["husky"])`.
+    pub fn exec(&self, args: &[&str]) -> Result<()> {
+        self.exec_line(args).run()
+    }
+
+    /// Runs a package binary without installing it, e.g. `dlx(&["shadcn@latest", "add", "table"])`.
+    pub fn dlx(&self, args: &[&str]) -> Result<()> {
+        self.prefixed_line(self.dlx_prefix(), args).run()
+    }
+
+    /// `npm run <script>` and its equivalents, as separate arguments.
+    pub fn run_script_line(&self, script: &str) -> CommandLine {
+        match self {
+            PackageManager::Yarn => CommandLine::new("yarn", &[script]),
+            // deno task runs package.json scripts as well as deno.json tasks
+            PackageManager::Deno => CommandLine::new("deno", &["task", script]),
+            _ => CommandLine::new(self.to_string(), &["run", script]),
+        }
+    }
+
+    pub fn run_script(&self, script: &str) -> String {
+        self.run_script_line(script).to_string()
+    }
+
     pub fn to_string(&self) -> &'static str {
         match self {
             PackageManager::Npm => "npm",
             PackageManager::Yarn => "yarn",
             PackageManager::Pnpm => "pnpm",
             PackageManager::Bun => "bun",
+            PackageManager::Deno => "deno",
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locks(package_manager: PackageManager, lockfile: &str, name: &str) -> bool {
+        package_manager
+            .lock_keys(name)
+            .iter()
+            .any(|key| lockfile.contains(key.as_str()))
+    }
+
+    #[test]
+    fn npm_matches_whole_node_modules_keys() {
+        let lockfile = r#"{ "packages": { "node_modules/react-dom": {}, "node_modules/@scope/pkg": {} } }"#;
+        assert!(locks(PackageManager::Npm, lockfile, "react-dom"));
+        assert!(locks(PackageManager::Npm, lockfile, "@scope/pkg"));
+        assert!(!locks(PackageManager::Npm, lockfile, "react"));
+        assert!(!locks(PackageManager::Npm, lockfile, "pkg"));
+    }
+
+    #[test]
+    fn yarn_matches_classic_and_berry_entries() {
+        let classic = "zod@^3.0.0, zod@^3.1.0:\n  version \"3.1.0\"\n\n\"@scope/pkg@^1.0.0\":\n";
+        assert!(locks(PackageManager::Yarn, classic, "zod"));
+        assert!(locks(PackageManager::Yarn, classic, "@scope/pkg"));
+        assert!(!locks(PackageManager::Yarn, classic, "pkg"));
+
+        let berry = "\"react-dom@npm:^18.0.0\":\n  version: 18.2.0\n";
+        assert!(locks(PackageManager::Yarn, berry, "react-dom"));
+        assert!(!locks(PackageManager::Yarn, berry, "react"));
+    }
+
+    #[test]
+    fn pnpm_matches_every_lockfile_version() {
+        assert!(locks(PackageManager::Pnpm, "packages:\n  /zod/3.0.0:\n", "zod"));
+        assert!(locks(PackageManager::Pnpm, "packages:\n  /zod@3.0.0:\n", "zod"));
+        assert!(locks(PackageManager::Pnpm, "packages:\n  zod@3.0.0:\n", "zod"));
+        assert!(locks(PackageManager::Pnpm, "packages:\n  '@scope/pkg@1.0.0':\n", "@scope/pkg"));
+        assert!(!locks(PackageManager::Pnpm, "packages:\n  /@scope/pkg@1.0.0:\n", "pkg"));
+        assert!(!locks(PackageManager::Pnpm, "importers:\n  .:\n    dependencies:\n      zod:\n", "zod"));
+    }
+
+    #[test]
+    fn bun_and_deno_match_resolved_entries() {
+        assert!(locks(PackageManager::Bun, r#""zod": ["zod@3.0.0", "", {}, "sha512-"],"#, "zod"));
+        assert!(!locks(PackageManager::Bun, r#""zod-form-data": ["zod-form-data@2.0.0"],"#, "zod"));
+        assert!(locks(PackageManager::Deno, r#""npm:zod@3.0.0": "3.0.0""#, "zod"));
+        assert!(locks(PackageManager::Deno, r#""npm": { "zod@3.0.0": {} }"#, "zod"));
+    }
+}
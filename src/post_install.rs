@@ -0,0 +1,117 @@
+use anyhow::{Result, Context};
+use console::style;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use crate::hooks::{self, PostInstallStep};
+use crate::package_manager::PackageManager;
+use crate::process;
+
+const SEVERITIES: [&str; 5] = ["critical", "high", "moderate", "low", "info"];
+
+/// Vulnerability counts by severity from the audit report. npm and pnpm put them under
+/// `metadata.vulnerabilities`, yarn classic prints an `auditSummary` line, and bun lists
+/// advisories per package.
+fn vulnerability_counts(package_manager: &PackageManager, report: &str) -> Option<BTreeMap<&'static str, u64>> {
+    let count = |summary: &Value| {
+        SEVERITIES
+            .iter()
+            .map(|severity| (*severity, summary[severity].as_u64().unwrap_or(0)))
+            .collect()
+    };
+
+    match package_manager {
+        PackageManager::Npm | PackageManager::Pnpm => {
+            let report: Value = serde_json::from_str(report).ok()?;
+            let summary = &report["metadata"]["vulnerabilities"];
+            summary.is_object().then(|| count(summary))
+        }
+        PackageManager::Yarn => report
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .find(|line| line["type"] == "auditSummary")
+            .map(|line| count(&line["data"]["vulnerabilities"])),
+        PackageManager::Bun => {
+            let report: Value = serde_json::from_str(report).ok()?;
+            let mut counts: BTreeMap<&'static str, u64> = SEVERITIES.iter().map(|severity| (*severity, 0)).collect();
+            for advisory in report.as_object()?.values().filter_map(Value::as_array).flatten() {
+                if let Some(total) = advisory["severity"].as_str().and_then(|severity| counts.get_mut(severity)) {
+                    *total += 1;
+                }
+            }
+            Some(counts)
+        }
+        PackageManager::Deno => None,
+    }
+}
+
+fn audit(package_manager: &PackageManager) -> Result<()> {
+    let Some(command_line) = package_manager.audit_command() else {
+        println!("{}", style(format!("Audit: {} has no audit command; skipped", package_manager.to_string())).dim());
+        return Ok(());
+    };
+
+    // Audits exit non-zero when they find vulnerabilities, so only the report matters
+    let output = command_line
+        .command()
+        .output()
+        .context(format!("Failed to run {}", command_line))?;
+    let report = String::from_utf8_lossy(&output.stdout);
+
+    let Some(counts) = vulnerability_counts(package_manager, &report) else {
+        anyhow::bail!("Could not read the report of {}", command_line);
+    };
+    let found: Vec<String> = SEVERITIES
+        .iter()
+        .filter_map(|severity| counts.get(severity).filter(|count| **count > 0).map(|count| format!("{} {}", count, severity)))
+        .collect();
+
+    if found.is_empty() {
+        println!("{}", style("Audit: no known vulnerabilities").green());
+    } else {
+        let serious = counts["critical"] + counts["high"] > 0;
+        let summary = format!("Audit: {}. Run `{}` for details", found.join(", "), command_line.to_string().trim_end_matches(" --json"));
+        if serious {
+            println!("{}", style(summary).red());
+        } else {
+            println!("{}", style(summary).yellow());
+        }
+    }
+    Ok(())
+}
+
+fn dedupe(package_manager: &PackageManager) -> Result<()> {
+    let Some(command_line) = package_manager.dedupe_command() else {
+        println!("{}", style(format!("Dedupe: {} has no dedupe command; skipped", package_manager.to_string())).dim());
+        return Ok(());
+    };
+
+    let lockfile = package_manager.lockfile();
+    let before = std::fs::read(lockfile).ok();
+    let status = process::run_quiet(&mut command_line.command(), "Deduplicating dependencies...")
+        .context(format!("Failed to run {}", command_line))?;
+    if !status.success() {
+        anyhow::bail!("{} failed", command_line);
+    }
+
+    if std::fs::read(lockfile).ok() == before {
+        println!("{}", style("Dedupe: no duplicate dependencies").green());
+    } else {
+        println!("{}", style(format!("Dedupe: collapsed duplicate dependencies in {}", lockfile)).green());
+    }
+    Ok(())
+}
+
+/// Runs the `post_install` steps from nstack.toml once a feature's installs are done. A
+/// failing step only warns; the feature is already in place.
+pub fn run(package_manager: &PackageManager) -> Result<()> {
+    for step in hooks::post_install_steps()? {
+        let result = match step {
+            PostInstallStep::Audit => audit(package_manager),
+            PostInstallStep::Dedupe => dedupe(package_manager),
+        };
+        if let Err(err) = result {
+            println!("{}", style(format!("Post-install check failed: {:#}", err)).yellow());
+        }
+    }
+    Ok(())
+}
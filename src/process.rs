@@ -0,0 +1,86 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Lines of a failed command's output printed when they were not shown as it ran.
+const FAILURE_TAIL: usize = 20;
+
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+pub fn verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+fn is_warning_or_error(line: &str) -> bool {
+    let line = line.to_lowercase();
+    line.contains("warn") || line.contains("error") || line.contains("err!")
+}
+
+fn forward_lines(stream: impl Read + Send + 'static, sender: mpsc::Sender<String>) {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(|line| line.ok()) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Runs `command` under a spinner that shows its latest output line instead of letting it
+/// scroll over the terminal. Warnings and errors are printed above the spinner, and every
+/// line with `--verbose`. When the command fails, its last lines are printed so the cause
+/// is never hidden.
+pub fn run_quiet(command: &mut Command, message: &str) -> Result<ExitStatus> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(format!("Failed to start: {}", message))?;
+
+    let (sender, receiver) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, sender.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, sender);
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.enable_steady_tick(Duration::from_millis(100));
+    pb.set_message(message.to_string());
+
+    let mut tail = VecDeque::with_capacity(FAILURE_TAIL);
+    // Ends once both streams are closed, i.e. the command has exited
+    for line in receiver {
+        if verbose() || is_warning_or_error(&line) {
+            pb.suspend(|| println!("{}", line));
+        } else if !line.trim().is_empty() {
+            let latest: String = line.trim().chars().take(60).collect();
+            pb.set_message(format!("{} {}", message, style(latest).dim()));
+        }
+        if tail.len() == FAILURE_TAIL {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+
+    let status = child.wait().context(format!("Failed to wait for: {}", message))?;
+    pb.finish_and_clear();
+    if !status.success() && !verbose() {
+        for line in tail {
+            eprintln!("{}", line);
+        }
+    }
+    Ok(status)
+}
@@ -0,0 +1,111 @@
+use std::path::Path;
+use crate::package_json;
+
+pub const ESLINT_FLAT_CONFIGS: [&str; 6] = [
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "eslint.config.cjs",
+    "eslint.config.ts",
+    "eslint.config.mts",
+    "eslint.config.cts",
+];
+
+pub const ESLINT_LEGACY_CONFIGS: [&str; 6] = [
+    ".eslintrc.json",
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.yaml",
+    ".eslintrc.yml",
+    ".eslintrc",
+];
+
+pub const BIOME_CONFIGS: [&str; 2] = ["biome.json", "biome.jsonc"];
+
+pub const PRETTIER_CONFIGS: [&str; 11] = [
+    ".prettierrc",
+    ".prettierrc.json",
+    ".prettierrc.json5",
+    ".prettierrc.yaml",
+    ".prettierrc.yml",
+    ".prettierrc.js",
+    ".prettierrc.mjs",
+    ".prettierrc.cjs",
+    "prettier.config.js",
+    "prettier.config.mjs",
+    "prettier.config.cjs",
+];
+
+/// How the project configures ESLint, with the file that does it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EslintConfig {
+    /// `eslint.config.*`, the default since ESLint 9
+    Flat(&'static str),
+    /// `.eslintrc*` or `eslintConfig` in package.json
+    Legacy(&'static str),
+}
+
+impl EslintConfig {
+    pub fn file(&self) -> &'static str {
+        match self {
+            EslintConfig::Flat(file) | EslintConfig::Legacy(file) => file,
+        }
+    }
+}
+
+/// The lint and format tooling a project already uses, so features that add scripts,
+/// hooks or configs build on it instead of assuming ESLint with Prettier.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectProfile {
+    pub eslint: Option<EslintConfig>,
+    pub biome: bool,
+    pub prettier: bool,
+}
+
+fn existing(files: &[&'static str]) -> Option<&'static str> {
+    files.iter().copied().find(|file| Path::new(file).exists())
+}
+
+impl ProjectProfile {
+    pub fn detect() -> Self {
+        let package_json = package_json::read().unwrap_or_default();
+        let eslint = existing(&ESLINT_FLAT_CONFIGS)
+            .map(EslintConfig::Flat)
+            .or_else(|| existing(&ESLINT_LEGACY_CONFIGS).map(EslintConfig::Legacy))
+            .or_else(|| package_json.get("eslintConfig").map(|_| EslintConfig::Legacy("package.json")));
+
+        ProjectProfile {
+            eslint,
+            biome: package_json::has_dependency("@biomejs/biome") || existing(&BIOME_CONFIGS).is_some(),
+            prettier: package_json::has_dependency("prettier")
+                || existing(&PRETTIER_CONFIGS).is_some()
+                || package_json.get("prettier").is_some(),
+        }
+    }
+
+    /// Names the tooling for messages, e.g. "ESLint (flat config in eslint.config.mjs) + Prettier".
+    pub fn describe(&self) -> String {
+        let mut tools = Vec::new();
+        match self.eslint {
+            Some(config @ EslintConfig::Flat(_)) => tools.push(format!("ESLint (flat config in {})", config.file())),
+            Some(config @ EslintConfig::Legacy(_)) => tools.push(format!("ESLint (legacy config in {})", config.file())),
+            None => {}
+        }
+        if self.biome {
+            tools.push("Biome".to_string());
+        }
+        if self.prettier {
+            tools.push("Prettier".to_string());
+        }
+        if tools.is_empty() { "none".to_string() } else { tools.join(" + ") }
+    }
+
+    /// A comment silencing one lint rule on the next line in generated code, in the
+    /// project's linter syntax. Biome needs a reason; ESLint ignores the text after `--`.
+    pub fn ignore_next_line(&self, eslint_rule: &str, biome_rule: &str, reason: &str) -> String {
+        if self.biome && self.eslint.is_none() {
+            format!("// biome-ignore {}: {}", biome_rule, reason)
+        } else {
+            format!("// eslint-disable-next-line {} -- {}", eslint_rule, reason)
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{Result, Context};
+use console::style;
+use serde_json::Value;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use crate::config::{self, ConfigScope};
+use crate::project_structure;
+use crate::prompt;
+
+/// `.nstack/config` key remembering which app to work on in a multi-app repository.
+const APP_KEY: &str = "app";
+
+/// The nearest directory at or above `start` with a package.json. The search stops at a
+/// monorepo root (its `.nstack/config` lists `workspaces`) or a git repository root, so a
+/// project is never confused with an unrelated package.json further up.
+fn find(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        if dir.join("package.json").is_file() {
+            return Some(dir.to_path_buf());
+        }
+        if config::get_in(dir, "workspaces").is_some() || dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+fn is_next_app(dir: &Path) -> bool {
+    let Some(package_json) = std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+    else {
+        return false;
+    };
+    ["dependencies", "devDependencies"].iter().any(|section| package_json[section].get("next").is_some())
+}
+
+fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            !name.starts_with('.') && name != "node_modules"
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Next.js apps in `root` and up to two levels below it, e.g. `apps/web` and `apps/docs`,
+/// relative to `root` (`.` for `root` itself).
+fn next_apps(root: &Path) -> Vec<String> {
+    let mut apps = Vec::new();
+    if is_next_app(root) {
+        apps.push(".".to_string());
+    }
+    for child in child_dirs(root) {
+        for dir in std::iter::once(child.clone()).chain(child_dirs(&child)) {
+            if is_next_app(&dir) && let Ok(relative) = dir.strip_prefix(root) {
+                apps.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    apps
+}
+
+/// The app to work on when `root` holds several Next.js apps: the one remembered in
+/// `.nstack/config`, otherwise the user's pick, which is then remembered.
+fn select_app(root: &Path) -> Result<Option<String>> {
+    let apps = next_apps(root);
+    if apps.len() < 2 {
+        return Ok(None);
+    }
+
+    if let Some(app) = config::get_in(root, APP_KEY).filter(|app| apps.contains(app)) {
+        return Ok(Some(app));
+    }
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!(
+            "Found several Next.js apps ({}). Run nstack inside one, or choose one with `nstack config set app <dir>`",
+            apps.join(", ")
+        );
+    }
+
+    let selection = prompt::select(APP_KEY, "Several Next.js apps found. Which one should nstack work on?", &apps, 0)?;
+    let app = apps[selection].clone();
+    config::set(ConfigScope::Project, APP_KEY, &app)?;
+    println!("{}", style(format!("Remembered {} in .nstack/config; change it with `nstack config set app <dir>`", app)).dim());
+    Ok(Some(app))
+}
+
+/// Moves into the project root when nstack runs from a subfolder such as `src/`, so every
+/// relative path resolves against the project. With `choose_app`, a repository holding
+/// several Next.js apps is narrowed down to one of them, which `ProjectStructure` and
+/// `project_structure::project_file` then resolve paths against. Prints the directory
+/// it switched to and the app it picked.
+pub fn enter(choose_app: bool) -> Result<()> {
+    let current = std::env::current_dir().context("Failed to read the current directory")?;
+    let Some(root) = find(&current) else {
+        return Ok(());
+    };
+    if root != current {
+        std::env::set_current_dir(&root)
+            .context(format!("Failed to enter the project root {}", root.display()))?;
+        println!("{}", style(format!("Using project root {}", root.display())).dim());
+    }
+
+    if choose_app && let Some(app) = select_app(&root)? {
+        println!("{}", style(format!("Working on the app in {}", app)).dim());
+        project_structure::set_app_dir(app);
+    }
+    Ok(())
+}
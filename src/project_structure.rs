@@ -1,45 +1,174 @@
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::{next_config, tsconfig};
 
-#[derive(Debug)]
-pub enum ProjectStructure {
+/// The app nstack works on when a repository holds several, relative to the working
+/// directory. `None` means the working directory is the app.
+static APP_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Points project detection and every project file at the app in `dir`, e.g. `apps/web`.
+pub fn set_app_dir(dir: impl Into<PathBuf>) {
+    *APP_DIR.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(dir.into());
+}
+
+/// The selected app's directory, `None` when it is the working directory.
+pub fn app_dir() -> Option<PathBuf> {
+    APP_DIR.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// `path` inside the app nstack works on, e.g. `apps/web/package.json` for `package.json`
+/// after `set_app_dir("apps/web")`, and `path` itself otherwise.
+pub fn project_file(path: impl AsRef<Path>) -> PathBuf {
+    match app_dir() {
+        Some(dir) => dir.join(path),
+        None => path.as_ref().to_path_buf(),
+    }
+}
+
+/// Where a Next.js project keeps its router.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
     AppDir,
     SrcDir,
+    /// Pages router with `pages/` at the project root
+    PagesRouter,
+    /// Pages router under `src/pages`
+    SrcPagesRouter,
+}
+
+/// The layout of the project in `base`, with every path getter joined onto `base`. The
+/// base is empty for the current directory, so paths stay relative like `src/lib`.
+#[derive(Debug, Clone)]
+pub struct ProjectStructure {
+    base: PathBuf,
+    layout: Layout,
+}
+
+/// Renders a path with `/` separators for generated code and config files, which expect
+/// them on every platform.
+pub fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
 }
 
 impl ProjectStructure {
+    /// The structure of the app nstack works on, the current directory unless
+    /// `set_app_dir` picked one.
     pub fn detect() -> Result<Self> {
-        if Path::new("app").exists() {
-            Ok(ProjectStructure::AppDir)
-        } else if Path::new("src").exists() {
-            Ok(ProjectStructure::SrcDir)
-        } else {
-            anyhow::bail!("Could not detect project structure. Neither 'app' nor 'src' directory found.")
-        }
+        ProjectStructure::detect_in(app_dir().unwrap_or_default())
+    }
+
+    /// Follows Next.js' own lookup: `app` and `pages` at the root win over `src/app` and
+    /// `src/pages`, and `experimental.appDir: false` in next.config turns the app
+    /// directory off. With no router directory yet, tsconfig `include` globs under `src/`
+    /// tell that the project keeps its code in `src`.
+    pub fn detect_in(base: impl Into<PathBuf>) -> Result<Self> {
+        let base = base.into();
+        let app_dir_enabled = next_config::option_in(&base, &["experimental", "appDir"]).as_deref() != Some("false");
+        let candidates = [
+            ("app", Layout::AppDir, app_dir_enabled),
+            ("pages", Layout::PagesRouter, true),
+            ("src/app", Layout::SrcDir, app_dir_enabled),
+            ("src/pages", Layout::SrcPagesRouter, true),
+        ];
+        let found = candidates
+            .into_iter()
+            .find(|(dir, _, enabled)| *enabled && base.join(dir).is_dir())
+            .map(|(_, layout, _)| layout);
+
+        let layout = match found {
+            Some(layout) => layout,
+            None if base.join("src").exists()
+                || tsconfig::include_in(&base).iter().any(|glob| glob.starts_with("src/")) =>
+            {
+                Layout::SrcDir
+            }
+            None if base.join("app").exists() => Layout::AppDir,
+            None => anyhow::bail!(
+                "Could not detect project structure. None of 'app', 'src' or 'pages' directories found in {}.",
+                if base.as_os_str().is_empty() { Path::new(".") } else { &base }.display()
+            ),
+        };
+        Ok(ProjectStructure { base, layout })
+    }
+
+    /// `path` relative to the project with `/` separators, for config files and generated
+    /// code inside the project, e.g. `app/globals.css` for `apps/web/app/globals.css`.
+    pub fn relative_path(&self, path: &Path) -> String {
+        slash_path(path.strip_prefix(&self.base).unwrap_or(path))
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    fn in_source_root(&self, path: &str) -> PathBuf {
+        self.get_source_root().join(path)
+    }
+
+    /// File extensions Next.js treats as pages, from `pageExtensions` in next.config.
+    pub fn page_extensions(&self) -> Vec<String> {
+        next_config::array_option_in(&self.base, &["pageExtensions"])
+            .filter(|extensions| !extensions.is_empty())
+            .unwrap_or_else(|| ["tsx", "ts", "jsx", "js"].map(str::to_string).to_vec())
     }
 
-    pub fn get_globals_css_path(&self) -> &'static str {
-        match self {
-            ProjectStructure::AppDir => "app/globals.css",
-            ProjectStructure::SrcDir => "src/app/globals.css",
+    pub fn get_globals_css_path(&self) -> PathBuf {
+        match self.layout {
+            Layout::AppDir | Layout::SrcDir => self.get_app_path().join("globals.css"),
+            Layout::PagesRouter | Layout::SrcPagesRouter => self.in_source_root("styles").join("globals.css"),
         }
     }
 
-    pub fn get_lib_path(&self) -> &'static str {
-        match self {
-            ProjectStructure::AppDir => "lib",
-            ProjectStructure::SrcDir => "src/lib",
+    /// Where app-router files go. Pages-router projects get an `app` directory next to
+    /// `pages`, which Next.js serves alongside it.
+    pub fn get_app_path(&self) -> PathBuf {
+        self.in_source_root("app")
+    }
+
+    pub fn get_pages_path(&self) -> PathBuf {
+        self.in_source_root("pages")
+    }
+
+    /// The API directory of the project's router: route handlers under `app/api`, or API
+    /// routes under `pages/api`.
+    pub fn get_api_path(&self) -> PathBuf {
+        if self.is_app_router() {
+            self.get_app_path().join("api")
+        } else {
+            self.get_pages_path().join("api")
         }
     }
 
-    pub fn get_db_path(&self) -> &'static str {
-        match self {
-            ProjectStructure::AppDir => "db",
-            ProjectStructure::SrcDir => "src/db",
+    /// Directory the `@/` import alias points at in create-next-app projects.
+    pub fn get_source_root(&self) -> PathBuf {
+        match self.layout {
+            Layout::AppDir | Layout::PagesRouter => self.base.clone(),
+            Layout::SrcDir | Layout::SrcPagesRouter => self.base.join("src"),
         }
     }
 
+    pub fn get_components_path(&self) -> PathBuf {
+        self.in_source_root("components")
+    }
+
+    pub fn get_lib_path(&self) -> PathBuf {
+        self.in_source_root("lib")
+    }
+
+    pub fn get_db_path(&self) -> PathBuf {
+        self.in_source_root("db")
+    }
+
+    pub fn get_i18n_path(&self) -> PathBuf {
+        self.in_source_root("i18n")
+    }
+
+    pub fn get_middleware_path(&self) -> PathBuf {
+        self.in_source_root("middleware.ts")
+    }
+
     pub fn is_app_router(&self) -> bool {
-        matches!(self, ProjectStructure::AppDir)
+        matches!(self.layout, Layout::AppDir | Layout::SrcDir)
     }
 }
@@ -0,0 +1,326 @@
+use anyhow::{Result, Context};
+use dialoguer::{Confirm, Input, MultiSelect, Select, theme::ColorfulTheme};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::sync::Mutex;
+
+/// Set on the nstack processes the picker runs: their prompts become questions on stdout
+/// that the picker answers on stdin.
+pub const FORWARD_ENV: &str = "NSTACK_FORWARD_PROMPTS";
+
+/// Starts the stdout line carrying a forwarded question as JSON.
+pub const QUESTION_MARKER: &str = "\u{1e}nstack-question ";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QuestionKind {
+    Confirm { default: bool },
+    Input { default: String },
+    Select { items: Vec<String>, default: usize },
+    MultiSelect { items: Vec<String>, defaults: Vec<bool> },
+}
+
+/// A prompt forwarded to the picker. It answers with JSON on one line: a bool for
+/// confirms, text for inputs, an index for selects and a list of indices for multi-selects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Question {
+    pub key: String,
+    pub prompt: String,
+    #[serde(flatten)]
+    pub kind: QuestionKind,
+    /// Why the previous answer was rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Answers for the feature being applied from a recipe, keyed by prompt key. `None` when
+/// no recipe is active, so every prompt stays interactive.
+static ANSWERS: Mutex<Option<BTreeMap<String, toml::Value>>> = Mutex::new(None);
+
+/// Every answer given since the last `take_chosen`, whether from a recipe or the terminal.
+static CHOSEN: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
+pub fn set_answers(answers: BTreeMap<String, toml::Value>) {
+    *ANSWERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(answers);
+}
+
+pub fn clear_answers() {
+    *ANSWERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Returns the answers chosen so far and starts a fresh record.
+pub fn take_chosen() -> BTreeMap<String, String> {
+    std::mem::take(&mut *CHOSEN.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+fn record(key: &str, value: String) {
+    CHOSEN
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(key.to_string(), value);
+}
+
+pub fn is_forwarded() -> bool {
+    std::env::var_os(FORWARD_ENV).is_some()
+}
+
+/// Asks the picker and waits for its answer.
+fn forward(key: &str, prompt: &str, kind: QuestionKind, error: Option<String>) -> Result<toml::Value> {
+    let question = Question { key: key.to_string(), prompt: prompt.to_string(), kind, error };
+    {
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}{}", QUESTION_MARKER, serde_json::to_string(&question)?)?;
+        stdout.flush()?;
+    }
+
+    let mut line = String::new();
+    if std::io::stdin().lock().read_line(&mut line)? == 0 {
+        anyhow::bail!("The picker closed before answering '{}'", key);
+    }
+    let value: serde_json::Value = serde_json::from_str(&line).context(format!("Invalid answer for '{}'", key))?;
+    toml::Value::try_from(value).context(format!("Invalid answer for '{}'", key))
+}
+
+fn answer(key: &str) -> Option<toml::Value> {
+    ANSWERS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .and_then(|answers| answers.get(key).cloned())
+}
+
+/// While a recipe is applied, prompts it does not answer show their key so it can be
+/// added to the recipe.
+fn label(key: &str, prompt: &str) -> String {
+    let recipe_active = ANSWERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).is_some();
+    if recipe_active {
+        format!("{} (recipe key: {})", prompt, key)
+    } else {
+        prompt.to_string()
+    }
+}
+
+/// Finds an item by index or by its text. Items often read `Name - description`, so the
+/// name alone matches too.
+fn find_item<T: ToString>(key: &str, items: &[T], value: &toml::Value) -> Result<usize> {
+    if let Some(index) = value.as_integer() {
+        return usize::try_from(index)
+            .ok()
+            .filter(|index| *index < items.len())
+            .context(format!("Recipe answer '{}' is out of range", key));
+    }
+
+    let Some(wanted) = value.as_str().map(str::to_lowercase) else {
+        anyhow::bail!("Recipe answer '{}' must be a string or an index", key);
+    };
+
+    items
+        .iter()
+        .position(|item| {
+            let item = item.to_string().to_lowercase();
+            item == wanted || item.starts_with(&format!("{} ", wanted))
+        })
+        .with_context(|| {
+            let options: Vec<String> = items.iter().map(ToString::to_string).collect();
+            format!("Recipe answer '{}' = {} matches none of: {}", key, value, options.join(", "))
+        })
+}
+
+/// Finds each item a multi-select answer lists, by index or by text.
+fn find_items<T: ToString>(key: &str, items: &[T], value: &toml::Value) -> Result<Vec<usize>> {
+    let Some(values) = value.as_array() else {
+        anyhow::bail!("Recipe answer '{}' must be a list", key);
+    };
+    values.iter().map(|value| find_item(key, items, value)).collect()
+}
+
+pub fn confirm(key: &str, prompt: &str, default: bool) -> Result<bool> {
+    let value = match answer(key) {
+        None if is_forwarded() => Some(forward(key, prompt, QuestionKind::Confirm { default }, None)?),
+        value => value,
+    };
+    let confirmed = match value {
+        Some(value) => value.as_bool().context(format!("Recipe answer '{}' must be true or false", key))?,
+        None => Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(label(key, prompt))
+            .default(default)
+            .interact()?,
+    };
+
+    record(key, confirmed.to_string());
+    Ok(confirmed)
+}
+
+pub fn input(key: &str, prompt: &str, default: &str) -> Result<String> {
+    input_validated(key, prompt, default, |_| Ok(()))
+}
+
+/// Text input whose answer must pass `validate`, from the recipe or the terminal.
+pub fn input_validated<F>(key: &str, prompt: &str, default: &str, validate: F) -> Result<String>
+where
+    F: Fn(&String) -> Result<(), &'static str>,
+{
+    if let Some(value) = answer(key) {
+        let value = match value {
+            toml::Value::String(text) => text,
+            toml::Value::Integer(number) => number.to_string(),
+            _ => anyhow::bail!("Recipe answer '{}' must be text", key),
+        };
+        if let Err(message) = validate(&value) {
+            anyhow::bail!("Recipe answer '{}' is invalid: {}", key, message);
+        }
+        record(key, value.clone());
+        return Ok(value);
+    }
+
+    if is_forwarded() {
+        let mut error = None;
+        loop {
+            let value = match forward(key, prompt, QuestionKind::Input { default: default.to_string() }, error)? {
+                toml::Value::String(text) => text,
+                _ => anyhow::bail!("Answer for '{}' must be text", key),
+            };
+            match validate(&value) {
+                Ok(()) => {
+                    record(key, value.clone());
+                    return Ok(value);
+                }
+                Err(message) => error = Some(message.to_string()),
+            }
+        }
+    }
+
+    let theme = ColorfulTheme::default();
+    let mut input = Input::with_theme(&theme).with_prompt(label(key, prompt));
+    if !default.is_empty() {
+        input = input.default(default.to_string());
+    }
+    let value: String = input.validate_with(|value: &String| validate(value)).interact_text()?;
+
+    record(key, value.clone());
+    Ok(value)
+}
+
+pub fn select<T: ToString>(key: &str, prompt: &str, items: &[T], default: usize) -> Result<usize> {
+    let value = match answer(key) {
+        None if is_forwarded() => {
+            let items = items.iter().map(ToString::to_string).collect();
+            Some(forward(key, prompt, QuestionKind::Select { items, default }, None)?)
+        }
+        value => value,
+    };
+    let selection = match value {
+        Some(value) => find_item(key, items, &value)?,
+        None => Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(label(key, prompt))
+            .items(items)
+            .default(default)
+            .interact()?,
+    };
+
+    record(key, items[selection].to_string());
+    Ok(selection)
+}
+
+pub fn multi_select<T: ToString>(key: &str, prompt: &str, items: &[T], defaults: &[bool]) -> Result<Vec<usize>> {
+    let value = match answer(key) {
+        None if is_forwarded() => {
+            let items = items.iter().map(ToString::to_string).collect();
+            let defaults = defaults.to_vec();
+            Some(forward(key, prompt, QuestionKind::MultiSelect { items, defaults }, None)?)
+        }
+        value => value,
+    };
+    let selections = match value {
+        Some(value) => find_items(key, items, &value)?,
+        None => MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(label(key, prompt))
+            .items(items)
+            .defaults(defaults)
+            .interact()?,
+    };
+
+    let chosen: Vec<String> = selections.iter().map(|&index| items[index].to_string()).collect();
+    record(key, chosen.join(","));
+    Ok(selections)
+}
+
+/// A select recipes never answer, such as what to do about a file conflict. Forwarded to
+/// the picker like the other prompts.
+pub fn choose(prompt: &str, items: &[&str], default: usize) -> Result<usize> {
+    if is_forwarded() {
+        let kind = QuestionKind::Select { items: items.iter().map(ToString::to_string).collect(), default };
+        let value = forward("", prompt, kind, None)?;
+        return find_item(prompt, items, &value);
+    }
+
+    Ok(Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(items)
+        .default(default)
+        .interact()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn questions_round_trip_through_json() {
+        let question = Question {
+            key: "providers".to_string(),
+            prompt: "Which providers?".to_string(),
+            kind: QuestionKind::MultiSelect {
+                items: vec!["GitHub".to_string(), "Google".to_string()],
+                defaults: vec![true, false],
+            },
+            error: None,
+        };
+
+        let line = serde_json::to_string(&question).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&line).unwrap(),
+            json!({
+                "key": "providers",
+                "prompt": "Which providers?",
+                "kind": "multi_select",
+                "items": ["GitHub", "Google"],
+                "defaults": [true, false],
+            })
+        );
+        assert_eq!(serde_json::from_str::<Question>(&line).unwrap(), question);
+
+        let rejected = Question {
+            key: "port".to_string(),
+            prompt: "Port".to_string(),
+            kind: QuestionKind::Input { default: "3000".to_string() },
+            error: Some("Enter a whole number".to_string()),
+        };
+        let line = serde_json::to_string(&rejected).unwrap();
+        assert_eq!(serde_json::from_str::<Question>(&line).unwrap(), rejected);
+    }
+
+    #[test]
+    fn select_answers_match_an_index_or_the_item_name() {
+        let items = ["Neon - serverless Postgres", "Supabase", "SQLite"];
+        assert_eq!(find_item("db", &items, &toml::Value::Integer(1)).unwrap(), 1);
+        assert_eq!(find_item("db", &items, &toml::Value::String("neon".to_string())).unwrap(), 0);
+        assert_eq!(find_item("db", &items, &toml::Value::String("SQLite".to_string())).unwrap(), 2);
+        assert!(find_item("db", &items, &toml::Value::Integer(3)).is_err());
+        assert!(find_item("db", &items, &toml::Value::Integer(-1)).is_err());
+        assert!(find_item("db", &items, &toml::Value::String("mysql".to_string())).is_err());
+        assert!(find_item("db", &items, &toml::Value::Boolean(true)).is_err());
+    }
+
+    #[test]
+    fn multi_select_answers_are_lists_of_items() {
+        let items = ["lint", "typecheck", "test"];
+        let answer = toml::Value::try_from(json!([0, "test"])).unwrap();
+        assert_eq!(find_items("jobs", &items, &answer).unwrap(), vec![0, 2]);
+        assert_eq!(find_items("jobs", &items, &toml::Value::Array(Vec::new())).unwrap(), Vec::<usize>::new());
+        assert!(find_items("jobs", &items, &toml::Value::Integer(0)).is_err());
+        assert!(find_items("jobs", &items, &toml::Value::try_from(json!([0, 5])).unwrap()).is_err());
+    }
+}
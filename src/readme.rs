@@ -0,0 +1,102 @@
+use anyhow::{Result, Context};
+use std::fs;
+use std::path::Path;
+use crate::change_set;
+use crate::features::FEATURES;
+use crate::framework::Framework;
+use crate::manifest::ProjectManifest;
+use crate::package_manager::PackageManager;
+
+const START_MARKER: &str = "<!-- nstack:start -->";
+const END_MARKER: &str = "<!-- nstack:end -->";
+
+fn feature_description(feature: &str) -> String {
+    FEATURES
+        .iter()
+        .find(|(name, _, _)| *name == feature)
+        .map(|(_, _, description)| description.to_string())
+        .unwrap_or_else(|| "Community feature".to_string())
+}
+
+/// The generated section: everything in it comes from the manifest and project config.
+fn stack_section(manifest: &ProjectManifest, package_manager: &PackageManager, framework: Framework) -> String {
+    let mut lines = vec![
+        START_MARKER.to_string(),
+        "## Stack".to_string(),
+        String::new(),
+        "_Generated by nstack from `.nstack/manifest.json`; edits inside this section are overwritten._".to_string(),
+        String::new(),
+        format!("- **Framework:** {}", framework.display_name()),
+        format!("- **Package manager:** {}", package_manager.to_string()),
+        String::new(),
+        "### Getting started".to_string(),
+        String::new(),
+        "```bash".to_string(),
+        format!("{} install", package_manager.to_string()),
+        package_manager.run_script("dev"),
+        "```".to_string(),
+    ];
+
+    if !manifest.features.is_empty() {
+        lines.extend([String::new(), "### Features".to_string(), String::new()]);
+        for feature in &manifest.features {
+            lines.push(format!("- `{}` - {}", feature, feature_description(feature)));
+        }
+    }
+
+    let env_vars = manifest.required_env_vars();
+    if !env_vars.is_empty() {
+        lines.extend([
+            String::new(),
+            "### Environment variables".to_string(),
+            String::new(),
+            "Set these in `.env`:".to_string(),
+            String::new(),
+            "| Variable | Needed by |".to_string(),
+            "|---|---|".to_string(),
+        ]);
+        for (feature, var) in env_vars {
+            lines.push(format!("| `{}` | {} |", var, feature));
+        }
+    }
+
+    if !manifest.scripts.is_empty() {
+        lines.extend([
+            String::new(),
+            "### Scripts".to_string(),
+            String::new(),
+            "| Command | Added by |".to_string(),
+            "|---|---|".to_string(),
+        ]);
+        for (feature, scripts) in &manifest.scripts {
+            for script in scripts {
+                lines.push(format!("| `{}` | {} |", package_manager.run_script(script), feature));
+            }
+        }
+    }
+
+    lines.push(END_MARKER.to_string());
+    lines.join("\n")
+}
+
+/// Writes or refreshes the stack section of `dir`/README.md, leaving the rest of the
+/// README as the project has it.
+pub fn update(dir: &Path, package_manager: &PackageManager, framework: Framework) -> Result<()> {
+    let manifest = ProjectManifest::load_from(dir)?;
+    let section = stack_section(&manifest, package_manager, framework);
+
+    let path = dir.join("README.md");
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let updated_content = match (content.find(START_MARKER), content.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}{}", &content[..start], section, &content[end + END_MARKER.len()..])
+        }
+        _ if content.trim().is_empty() => format!("{}\n", section),
+        _ => format!("{}\n\n{}\n", content.trim_end(), section),
+    };
+
+    if updated_content != content {
+        change_set::write(&path, updated_content).context("Failed to update README.md")?;
+    }
+    Ok(())
+}
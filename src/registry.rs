@@ -0,0 +1,143 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::config;
+use crate::features::FEATURES;
+use crate::http;
+
+const INDEX_URL: &str = "https://raw.githubusercontent.com/happybear-21/nstack/main/registry/index.json";
+const CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// An entry of the remote index. Entries named after a built-in feature only add
+/// popularity; the others are community features installed from `source`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    downloads: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RegistryIndex {
+    features: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RegistryCache {
+    fetched_at: u64,
+    features: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegistryFeature {
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub downloads: u64,
+    pub official: bool,
+    /// What to pass to `nstack add --feature`
+    pub install_target: String,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    Some(config::global_dir()?.join("registry.json"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+async fn fetch_index() -> Result<Vec<IndexEntry>> {
+    let client = http::client(REQUEST_TIMEOUT)?;
+
+    let index: RegistryIndex = client
+        .get(INDEX_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(index.features)
+}
+
+/// Remote index entries, refreshed at most once per day. Falls back to a stale cache, or
+/// to nothing, when offline.
+async fn remote_entries() -> Vec<IndexEntry> {
+    let path = cache_path();
+    let cached = path
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<RegistryCache>(&content).ok());
+
+    if let Some(cache) = &cached
+        && now().saturating_sub(cache.fetched_at) < CACHE_TTL_SECS
+    {
+        return cache.features.clone();
+    }
+
+    match fetch_index().await {
+        Ok(features) => {
+            if let Some(path) = &path {
+                let cache = RegistryCache { fetched_at: now(), features: features.clone() };
+                if let Some(parent) = path.parent() {
+                    let _ = fs::create_dir_all(parent);
+                }
+                if let Ok(content) = serde_json::to_string_pretty(&cache) {
+                    let _ = fs::write(path, content);
+                }
+            }
+            features
+        }
+        Err(_) => cached.map(|cache| cache.features).unwrap_or_default(),
+    }
+}
+
+/// Built-in features merged with the remote index, most popular first.
+pub async fn load() -> Vec<RegistryFeature> {
+    let remote = remote_entries().await;
+
+    let mut features: Vec<RegistryFeature> = FEATURES
+        .iter()
+        .map(|&(name, category, description)| RegistryFeature {
+            name: name.to_string(),
+            description: description.to_string(),
+            category: category.to_string(),
+            downloads: remote.iter().find(|entry| entry.name == name).map_or(0, |entry| entry.downloads),
+            official: true,
+            install_target: name.to_string(),
+        })
+        .collect();
+
+    for entry in remote {
+        if FEATURES.iter().any(|&(name, _, _)| name == entry.name) {
+            continue;
+        }
+        let Some(source) = entry.source else {
+            continue;
+        };
+        features.push(RegistryFeature {
+            name: entry.name,
+            description: entry.description,
+            category: entry.category,
+            downloads: entry.downloads,
+            official: false,
+            install_target: source,
+        });
+    }
+
+    features.sort_by(|a, b| b.downloads.cmp(&a.downloads).then_with(|| a.name.cmp(&b.name)));
+    features
+}
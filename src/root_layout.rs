@@ -0,0 +1,178 @@
+use anyhow::{Result, Context};
+use std::fs;
+use std::path::PathBuf;
+use crate::project_structure::ProjectStructure;
+use crate::change_set;
+use crate::import_alias;
+
+const LAYOUT_FILES: [&str; 3] = ["layout.tsx", "layout.jsx", "layout.js"];
+
+/// Finds the root layout, which lives under `[locale]` once the i18n feature restructures the app.
+pub fn find_root_layout(project_structure: &ProjectStructure) -> Option<PathBuf> {
+    let app_path = project_structure.get_app_path();
+    [app_path.clone(), app_path.join("[locale]")]
+        .into_iter()
+        .flat_map(|dir| LAYOUT_FILES.into_iter().map(move |file| dir.join(file)))
+        .find(|path| path.exists())
+}
+
+/// Directory that new pages should be created in: next to the root layout, so routes end up
+/// under `[locale]` in internationalized projects.
+pub fn routes_dir(project_structure: &ProjectStructure) -> PathBuf {
+    find_root_layout(project_structure)
+        .and_then(|layout| layout.parent().map(PathBuf::from))
+        .unwrap_or_else(|| project_structure.get_app_path())
+}
+
+fn read_root_layout(project_structure: &ProjectStructure) -> Result<(PathBuf, String)> {
+    let Some(layout_path) = find_root_layout(project_structure) else {
+        anyhow::bail!("Could not find the root layout in {}", project_structure.get_app_path().display());
+    };
+
+    let content = fs::read_to_string(&layout_path)
+        .context(format!("Failed to read {}", layout_path.display()))?;
+    Ok((layout_path, content))
+}
+
+/// Adds an import statement after the last existing import of the root layout.
+/// Returns `Ok(false)` when the same line is already present.
+pub fn add_import(project_structure: &ProjectStructure, import_line: &str) -> Result<bool> {
+    let (layout_path, content) = read_root_layout(project_structure)?;
+    let import_line = import_alias::rewrite(&layout_path, import_line.as_bytes())
+        .unwrap_or_else(|| import_line.to_string());
+
+    if content.contains(&import_line) {
+        return Ok(false);
+    }
+
+    change_set::write(&layout_path, insert_import(&content, &import_line))
+        .context(format!("Failed to update {}", layout_path.display()))?;
+
+    Ok(true)
+}
+
+/// Inserts `import_line` after the last import of `content`.
+pub fn insert_import(content: &str, import_line: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    // Imports can span lines, so insert after the line that ends the last import
+    let last_import_end = lines
+        .iter()
+        .rposition(|line| line.starts_with("import "))
+        .map(|start| {
+            (start..lines.len())
+                .find(|&index| lines[index].trim_end().ends_with(';') || lines[index].contains(" from "))
+                .unwrap_or(start)
+        });
+
+    let mut updated: Vec<&str> = lines.clone();
+    match last_import_end {
+        Some(index) => updated.insert(index + 1, import_line),
+        None => updated.insert(0, import_line),
+    }
+
+    let mut result = updated.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Wraps `{children}` in the root layout with `open_tag` ... `close_tag`, e.g.
+/// `<ThemeProvider attribute="class">` and `</ThemeProvider>`.
+/// Returns `Ok(false)` when the layout already renders `close_tag`.
+pub fn wrap_children(project_structure: &ProjectStructure, open_tag: &str, close_tag: &str) -> Result<bool> {
+    let (layout_path, content) = read_root_layout(project_structure)?;
+
+    if content.contains(close_tag) {
+        return Ok(false);
+    }
+
+    let Some(index) = content.rfind("{children}") else {
+        anyhow::bail!(
+            "Could not find {{children}} in {}. Wrap it with {} manually.",
+            layout_path.display(),
+            open_tag
+        );
+    };
+
+    let line_start = content[..index].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let indent: String = content[line_start..]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+
+    let wrapped = format!(
+        "{}\n{}  {{children}}\n{}{}",
+        open_tag, indent, indent, close_tag
+    );
+    let updated_content = format!(
+        "{}{}{}",
+        &content[..index],
+        wrapped,
+        &content[index + "{children}".len()..]
+    );
+
+    change_set::write(&layout_path, updated_content)
+        .context(format!("Failed to update {}", layout_path.display()))?;
+
+    Ok(true)
+}
+
+/// Adds a JSX attribute such as `suppressHydrationWarning` to the `<html>` element.
+/// Returns `Ok(false)` when the attribute is already set.
+pub fn add_html_attribute(project_structure: &ProjectStructure, attribute: &str) -> Result<bool> {
+    let (layout_path, content) = read_root_layout(project_structure)?;
+
+    let Some(index) = content.find("<html") else {
+        anyhow::bail!("Could not find <html> in {}. Add {} manually.", layout_path.display(), attribute);
+    };
+    let Some(tag_end) = content[index..].find('>').map(|offset| index + offset) else {
+        anyhow::bail!("Could not parse <html> in {}. Add {} manually.", layout_path.display(), attribute);
+    };
+
+    if content[index..tag_end].contains(attribute) {
+        return Ok(false);
+    }
+
+    let updated_content = format!(
+        "{} {}{}",
+        content[..tag_end].trim_end(),
+        attribute,
+        &content[tag_end..]
+    );
+
+    change_set::write(&layout_path, updated_content)
+        .context(format!("Failed to update {}", layout_path.display()))?;
+
+    Ok(true)
+}
+
+/// Renders `element` as the last child of `<parent>`, e.g. `<Toaster />` inside `body`.
+/// Returns `Ok(false)` when the element is already present.
+pub fn append_child(project_structure: &ProjectStructure, parent: &str, element: &str) -> Result<bool> {
+    let (layout_path, content) = read_root_layout(project_structure)?;
+
+    if content.contains(element) {
+        return Ok(false);
+    }
+
+    let close_tag = format!("</{}>", parent);
+    let Some(index) = content.rfind(&close_tag) else {
+        anyhow::bail!("Could not find {} in {}. Add {} manually.", close_tag, layout_path.display(), element);
+    };
+
+    let line_start = content[..index].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let before_tag = &content[line_start..index];
+
+    let updated_content = if before_tag.trim().is_empty() {
+        // The closing tag is on its own line: add the element above it, one level deeper
+        format!("{}{}  {}\n{}", &content[..line_start], before_tag, element, &content[line_start..])
+    } else {
+        format!("{}{}{}", &content[..index], element, &content[index..])
+    };
+
+    change_set::write(&layout_path, updated_content)
+        .context(format!("Failed to update {}", layout_path.display()))?;
+
+    Ok(true)
+}
@@ -0,0 +1,31 @@
+use anyhow::{Result, Context};
+use std::process::Command;
+use crate::project_structure::app_dir;
+
+/// Runs a command line through the platform shell so pipes, `&&` and quoting work as
+/// users expect from recipes and hooks. It runs in the app nstack works on.
+pub fn run(command_line: &str, envs: &[(String, String)]) -> Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", command_line]);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.args(["-c", command_line]);
+        command
+    };
+
+    if let Some(dir) = app_dir() {
+        command.current_dir(dir);
+    }
+
+    let status = command
+        .envs(envs.iter().map(|(name, value)| (name, value)))
+        .status()
+        .context(format!("Failed to run {}", command_line))?;
+
+    if !status.success() {
+        anyhow::bail!("`{}` exited with {}", command_line, status);
+    }
+    Ok(())
+}
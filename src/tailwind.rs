@@ -0,0 +1,195 @@
+use anyhow::{Result, Context};
+use console::style;
+use indicatif::ProgressBar;
+use std::path::PathBuf;
+use crate::{file_writer, globals_css, package_json, prompt, root_layout};
+use crate::package_manager::PackageManager;
+use crate::project_structure::{project_file, slash_path, ProjectStructure};
+
+pub const TAILWIND_CONFIG_FILES: [&str; 4] = [
+    "tailwind.config.ts",
+    "tailwind.config.js",
+    "tailwind.config.mjs",
+    "tailwind.config.cjs",
+];
+
+const POSTCSS_CONFIG_FILES: [&str; 4] = [
+    "postcss.config.mjs",
+    "postcss.config.js",
+    "postcss.config.cjs",
+    "postcss.config.json",
+];
+
+const POSTCSS_CONFIG_V4: &str = r#"const config = {
+  plugins: {
+    "@tailwindcss/postcss": {},
+  },
+};
+
+export default config;
+"#;
+
+const POSTCSS_CONFIG_V3: &str = r#"const config = {
+  plugins: {
+    tailwindcss: {},
+    autoprefixer: {},
+  },
+};
+
+export default config;
+"#;
+
+/// Tailwind major versions with different setups: v3 is configured in tailwind.config with a
+/// plugins array, v4 is CSS-first with `@theme` and `@plugin` in globals.css.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TailwindVersion {
+    V3,
+    V4,
+}
+
+impl TailwindVersion {
+    /// Reads the installed major version from package.json, falling back to how globals.css
+    /// loads Tailwind. Returns `None` when the project does not use Tailwind.
+    pub fn detect(project_structure: &ProjectStructure) -> Option<Self> {
+        match package_json::dependency_major_version("tailwindcss") {
+            Some(major) if major <= 3 => return Some(TailwindVersion::V3),
+            Some(_) => return Some(TailwindVersion::V4),
+            None => {}
+        }
+
+        let css = std::fs::read_to_string(project_structure.get_globals_css_path()).unwrap_or_default();
+        if css.contains("@import \"tailwindcss\"") || css.contains("@import 'tailwindcss'") {
+            Some(TailwindVersion::V4)
+        } else if css.contains("@tailwind ") || find_config().is_some() {
+            Some(TailwindVersion::V3)
+        } else {
+            None
+        }
+    }
+
+    /// The version create-next-app sets up for the project's Next.js: v4 since Next.js 15,
+    /// v3 before.
+    fn for_next() -> Self {
+        match package_json::dependency_major_version("next") {
+            Some(major) if major < 15 => TailwindVersion::V3,
+            _ => TailwindVersion::V4,
+        }
+    }
+
+    fn packages(&self) -> &'static [&'static str] {
+        match self {
+            TailwindVersion::V3 => &["tailwindcss@^3", "postcss", "autoprefixer"],
+            TailwindVersion::V4 => &["tailwindcss", "@tailwindcss/postcss"],
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TailwindVersion::V3 => "v3",
+            TailwindVersion::V4 => "v4",
+        }
+    }
+}
+
+pub fn find_config() -> Option<PathBuf> {
+    TAILWIND_CONFIG_FILES.into_iter().map(project_file).find(|path| path.exists())
+}
+
+fn tailwind_config_v3(project_structure: &ProjectStructure) -> String {
+    let globs: Vec<String> = [
+        project_structure.get_app_path(),
+        project_structure.get_pages_path(),
+        project_structure.get_components_path(),
+    ]
+    .iter()
+    .map(|dir| format!("    \"./{}/**/*.{{js,ts,jsx,tsx,mdx}}\",", project_structure.relative_path(dir)))
+    .collect();
+
+    format!(
+        r#"import type {{ Config }} from "tailwindcss";
+
+const config: Config = {{
+  content: [
+{}
+  ],
+  theme: {{
+    extend: {{}},
+  }},
+  plugins: [],
+}};
+
+export default config;
+"#,
+        globs.join("\n")
+    )
+}
+
+/// The Tailwind version of the project, installing and configuring Tailwind first when the
+/// project has none and the user agrees. `feature` names what needs it in the prompt.
+pub fn ensure_installed(
+    project_structure: &ProjectStructure,
+    package_manager: &PackageManager,
+    feature: &str,
+) -> Result<TailwindVersion> {
+    if let Some(version) = TailwindVersion::detect(project_structure) {
+        return Ok(version);
+    }
+
+    let version = TailwindVersion::for_next();
+    let install = prompt::confirm(
+        "tailwind",
+        &format!("{} needs Tailwind CSS, which this project does not use. Install Tailwind {} now?", feature, version.as_str()),
+        true,
+    )?;
+    if !install {
+        anyhow::bail!("{} needs Tailwind CSS. Set up Tailwind first, then add {} again", feature, feature);
+    }
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_message(format!("Installing Tailwind CSS {}...", version.as_str()));
+    package_manager
+        .add(version.packages())
+        .dev(true)
+        .run()
+        .context("Failed to install Tailwind CSS")?;
+
+    pb.set_message("Configuring Tailwind CSS...");
+    let mut notes = Vec::new();
+    match POSTCSS_CONFIG_FILES.into_iter().map(project_file).find(|path| path.exists()) {
+        Some(existing) => notes.push(format!("Add the Tailwind plugin to the plugins of {}", existing.display())),
+        None => {
+            let postcss_config = match version {
+                TailwindVersion::V3 => POSTCSS_CONFIG_V3,
+                TailwindVersion::V4 => POSTCSS_CONFIG_V4,
+            };
+            file_writer::write(project_file("postcss.config.mjs"), postcss_config).context("Failed to create postcss.config.mjs")?;
+        }
+    }
+    if version == TailwindVersion::V3 && find_config().is_none() {
+        file_writer::write(project_file("tailwind.config.ts"), tailwind_config_v3(project_structure))
+            .context("Failed to create tailwind.config.ts")?;
+    }
+
+    let css_path = project_structure.get_globals_css_path();
+    let css_existed = css_path.exists();
+    let directives = match version {
+        TailwindVersion::V3 => "@tailwind base;\n@tailwind components;\n@tailwind utilities;\n",
+        TailwindVersion::V4 => "@import \"tailwindcss\";\n",
+    };
+    globals_css::merge(project_structure, directives)?;
+
+    // A new globals.css only applies once the root layout (or pages/_app) imports it
+    if !css_existed {
+        let imported = project_structure.is_app_router()
+            && root_layout::add_import(project_structure, "import \"@/app/globals.css\";").is_ok();
+        if !imported {
+            notes.push(format!("Import {} in your root layout or pages/_app", slash_path(&css_path)));
+        }
+    }
+
+    pb.finish_with_message(format!("Tailwind CSS {} set up", version.as_str()));
+    for note in notes {
+        println!("{}", style(note).yellow());
+    }
+    Ok(version)
+}
@@ -0,0 +1,342 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use crate::project_structure::project_file;
+
+/// JavaScript projects keep the same settings in jsconfig.json.
+const CONFIG_FILES: [&str; 2] = ["tsconfig.json", "jsconfig.json"];
+
+/// tsconfig.json allows comments and trailing commas; drops both so serde_json can parse it.
+fn strip_jsonc(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                stripped.push(c);
+                while let Some(c) = chars.next() {
+                    stripped.push(c);
+                    match c {
+                        '\\' => stripped.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '}' | ']' => {
+                let trimmed = stripped.trim_end().len();
+                if stripped[..trimmed].ends_with(',') {
+                    stripped.truncate(trimmed - 1);
+                }
+                stripped.push(c);
+            }
+            _ => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// Parses tsconfig.json content the way TypeScript does, comments and trailing commas included.
+pub fn parse(content: &str) -> serde_json::Result<Value> {
+    serde_json::from_str(&strip_jsonc(content))
+}
+
+/// Byte ranges of the strings, punctuation and bare values (numbers, `true`, ...) in JSONC
+/// content, skipping whitespace and comments.
+fn tokens(content: &str) -> Vec<(usize, usize)> {
+    let bytes = content.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                tokens.push((start, i));
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = content[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 2);
+            }
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => {
+                i += 1;
+                tokens.push((start, i));
+            }
+            c if c.is_ascii_whitespace() => i += 1,
+            _ => {
+                while i < bytes.len() && !b"{}[]:,\"/".contains(&bytes[i]) && !bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                tokens.push((start, i));
+            }
+        }
+    }
+    tokens
+}
+
+/// Members of the object opened at `tokens[open]` as (key, first value token, last value
+/// token) token indices, plus the index of its closing brace.
+fn members(content: &str, tokens: &[(usize, usize)], open: usize) -> (Vec<(String, usize, usize)>, usize) {
+    let text = |index: usize| &content[tokens[index].0..tokens[index].1];
+    let mut members = Vec::new();
+    let mut index = open + 1;
+    while index < tokens.len() && text(index) != "}" {
+        if text(index) == "," {
+            index += 1;
+            continue;
+        }
+        let key = serde_json::from_str(text(index)).unwrap_or_default();
+        let value = index + 2;
+        let mut end = value;
+        let mut depth = 0;
+        while end < tokens.len() {
+            match text(end) {
+                "{" | "[" => depth += 1,
+                "}" | "]" => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+            end += 1;
+        }
+        members.push((key, value, end));
+        index = end + 1;
+    }
+    (members, index)
+}
+
+/// Whitespace that starts the line `offset` is on.
+fn indent_at(content: &str, offset: usize) -> &str {
+    let line = &content[content[..offset].rfind('\n').map_or(0, |newline| newline + 1)..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Sets `compilerOptions` entries in tsconfig.json content, editing the text in place so
+/// comments, key order and formatting survive. Existing values are replaced and new
+/// options are added at the top of `compilerOptions`.
+pub fn set_compiler_options(content: &str, options: &[(&str, Value)]) -> serde_json::Result<String> {
+    if !parse(content)?.is_object() {
+        return Err(serde::de::Error::custom("tsconfig.json is not an object"));
+    }
+    let tokens = tokens(content);
+    let (root_members, _) = members(content, &tokens, 0);
+    let root_indent = root_members.first().map_or("  ", |(_, value, _)| indent_at(content, tokens[value - 2].0));
+    let compiler_options = root_members
+        .iter()
+        .find(|(key, value, _)| key == "compilerOptions" && &content[tokens[*value].0..tokens[*value].1] == "{");
+
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    match compiler_options {
+        Some(&(_, open, _)) => {
+            let (members, close) = members(content, &tokens, open);
+            let indent = members
+                .first()
+                .map(|(_, value, _)| indent_at(content, tokens[value - 2].0).to_string())
+                .unwrap_or_else(|| format!("{root_indent}{root_indent}"));
+            let mut added = Vec::new();
+            for (name, value) in options {
+                match members.iter().find(|(key, _, _)| key == name) {
+                    Some(&(_, first, last)) => edits.push((tokens[first].0, tokens[last].1, value.to_string())),
+                    None => added.push(format!("\n{indent}{}: {value}", Value::from(*name))),
+                }
+            }
+            if !added.is_empty() {
+                let (at, text) = if members.is_empty() {
+                    (tokens[close].0, format!("{}\n{}", added.join(","), indent_at(content, tokens[open].0)))
+                } else {
+                    (tokens[open].1, added.join(",") + ",")
+                };
+                edits.push((at, at, text));
+            }
+        }
+        None => {
+            let entries: Vec<String> = options
+                .iter()
+                .map(|(name, value)| format!("\n{root_indent}{root_indent}{}: {value}", Value::from(*name)))
+                .collect();
+            // A `compilerOptions` that is not an object is replaced outright
+            let replaced = root_members.iter().find(|(key, _, _)| key == "compilerOptions");
+            let object = format!("{{{}\n{root_indent}}}", entries.join(","));
+            match replaced {
+                Some(&(_, first, last)) => edits.push((tokens[first].0, tokens[last].1, object)),
+                None => {
+                    let separator = if root_members.is_empty() { "\n" } else { "," };
+                    let at = tokens[0].1;
+                    edits.push((at, at, format!("\n{root_indent}\"compilerOptions\": {object}{separator}")));
+                }
+            }
+        }
+    }
+
+    let mut updated = content.to_string();
+    edits.sort_by_key(|(start, _, _)| std::cmp::Reverse(*start));
+    for (start, end, text) in edits {
+        updated.replace_range(start..end, &text);
+    }
+    Ok(updated)
+}
+
+fn read_file(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    parse(&content).ok()
+}
+
+/// The tsconfig.json (or jsconfig.json) of the project in `dir`, or `None` when it is
+/// missing or unreadable.
+fn read_in(dir: &Path) -> Option<Value> {
+    CONFIG_FILES.into_iter().find_map(|file| read_file(&dir.join(file)))
+}
+
+/// `./src/*` -> `src`, `./*` and `.` -> the directory itself (empty).
+fn normalize(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." | "*" => {}
+            ".." if parts.last().is_some_and(|last| *last != "..") => {
+                parts.pop();
+            }
+            part => parts.push(part.trim_end_matches('*')),
+        }
+    }
+    parts.retain(|part| !part.is_empty());
+    parts.join("/")
+}
+
+/// `compilerOptions.paths` as (alias, target) pairs with targets relative to the working
+/// directory, e.g. `("@/*", "src")` for `"@/*": ["./src/*"]`. Follows relative `extends`
+/// when the project's own config does not set `paths`.
+pub fn paths() -> Vec<(String, String)> {
+    let Some(mut path) = CONFIG_FILES.into_iter().map(project_file).find(|path| path.exists()) else {
+        return Vec::new();
+    };
+
+    // Bounded in case configs extend each other in a cycle
+    for _ in 0..8 {
+        let Some(config) = read_file(&path) else {
+            break;
+        };
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let options = &config["compilerOptions"];
+        if let Some(paths) = options["paths"].as_object() {
+            let base = dir.join(options["baseUrl"].as_str().unwrap_or("."));
+            return paths
+                .iter()
+                .filter_map(|(alias, targets)| {
+                    let target = targets.as_array()?.first()?.as_str()?;
+                    Some((alias.clone(), normalize(&base.join(target))))
+                })
+                .collect();
+        }
+        match config["extends"].as_str() {
+            Some(extends) if extends.starts_with('.') => path = dir.join(extends),
+            _ => break,
+        }
+    }
+    Vec::new()
+}
+
+/// The `include` globs of the project in `dir`, e.g. `["next-env.d.ts", "src/**/*.ts"]`.
+pub fn include_in(dir: &Path) -> Vec<String> {
+    read_in(dir)
+        .and_then(|tsconfig| {
+            tsconfig["include"]
+                .as_array()
+                .map(|globs| globs.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_comments_and_trailing_commas() {
+        let content = r#"{
+  // Path aliases
+  "compilerOptions": {
+    /* "strict": false, */
+    "baseUrl": ".",
+    "paths": { "@/*": ["./src/*"], },
+  },
+  "$schema": "https://json.schemastore.org/tsconfig",
+}"#;
+        let tsconfig = parse(content).unwrap();
+        assert_eq!(tsconfig["compilerOptions"]["paths"]["@/*"][0], "./src/*");
+        assert_eq!(tsconfig["compilerOptions"]["strict"], Value::Null);
+        assert_eq!(tsconfig["$schema"], "https://json.schemastore.org/tsconfig");
+    }
+
+    #[test]
+    fn sets_compiler_options_in_place() {
+        let content = r#"{
+  // Next.js defaults
+  "compilerOptions": {
+    "target": "ES2017",
+    "strict": false, // loosened while migrating
+    "paths": { "@/*": ["./src/*"] },
+  },
+  "include": ["**/*.ts"]
+}
+"#;
+        let options = [("strict", Value::Bool(true)), ("noUncheckedIndexedAccess", Value::Bool(true))];
+        let updated = set_compiler_options(content, &options).unwrap();
+        assert_eq!(updated, r#"{
+  // Next.js defaults
+  "compilerOptions": {
+    "noUncheckedIndexedAccess": true,
+    "target": "ES2017",
+    "strict": true, // loosened while migrating
+    "paths": { "@/*": ["./src/*"] },
+  },
+  "include": ["**/*.ts"]
+}
+"#);
+    }
+
+    #[test]
+    fn adds_missing_compiler_options() {
+        let options = [("strict", Value::Bool(true))];
+
+        let updated = set_compiler_options("{\n  \"include\": []\n}\n", &options).unwrap();
+        assert_eq!(updated, "{\n  \"compilerOptions\": {\n    \"strict\": true\n  },\n  \"include\": []\n}\n");
+
+        let updated = set_compiler_options("{\n\t\"compilerOptions\": {}\n}", &options).unwrap();
+        assert_eq!(updated, "{\n\t\"compilerOptions\": {\n\t\t\"strict\": true\n\t}\n}");
+        assert_eq!(parse(&updated).unwrap()["compilerOptions"]["strict"], true);
+
+        assert!(set_compiler_options("[]", &options).is_err());
+    }
+
+    #[test]
+    fn normalizes_path_targets() {
+        assert_eq!(normalize(Path::new("./src/*")), "src");
+        assert_eq!(normalize(Path::new("./*")), "");
+        assert_eq!(normalize(Path::new("apps/web/../../packages/ui/src/*")), "packages/ui/src");
+        assert_eq!(normalize(Path::new(".\\src\\*")), "src");
+    }
+}
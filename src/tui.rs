@@ -0,0 +1,701 @@
+use anyhow::{Result, Context};
+use console::style;
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, Gauge, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+use serde_json::json;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+use crate::features::FEATURES;
+use crate::manifest::ProjectManifest;
+use crate::package_manager::PackageManager;
+use crate::prompt::{self, Question, QuestionKind};
+
+const PACKAGE_MANAGERS: [&str; 5] = ["npm", "yarn", "pnpm", "bun", "deno"];
+
+enum Screen {
+    Browse,
+    Options,
+    Review,
+    Run,
+}
+
+#[derive(PartialEq)]
+enum Focus {
+    Categories,
+    Features,
+}
+
+/// What to do once the interface closes.
+enum Outcome {
+    Quit,
+    Finished,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Pending,
+    Running,
+    Done,
+    Failed,
+    Skipped,
+}
+
+/// One nstack command the plan runs, e.g. `add --feature drizzle`.
+struct Step {
+    label: String,
+    args: Vec<String>,
+    status: Status,
+}
+
+/// Output of the running step, read on background threads.
+enum Message {
+    Line(String),
+    Question(Question),
+    Exited(bool),
+}
+
+/// A forwarded prompt being answered.
+struct Form {
+    question: Question,
+    input: String,
+    list_state: ListState,
+    checked: Vec<bool>,
+}
+
+impl Form {
+    fn new(question: Question) -> Self {
+        let (input, selected, checked) = match &question.kind {
+            QuestionKind::Confirm { default } => (String::new(), if *default { 0 } else { 1 }, Vec::new()),
+            QuestionKind::Input { default } => (default.clone(), 0, Vec::new()),
+            QuestionKind::Select { default, .. } => (String::new(), *default, Vec::new()),
+            QuestionKind::MultiSelect { items, defaults } => {
+                let checked = (0..items.len()).map(|index| defaults.get(index).copied().unwrap_or(false)).collect();
+                (String::new(), 0, checked)
+            }
+        };
+        Self { question, input, list_state: ListState::default().with_selected(Some(selected)), checked }
+    }
+
+    fn items(&self) -> Vec<String> {
+        match &self.question.kind {
+            QuestionKind::Confirm { .. } => vec!["Yes".to_string(), "No".to_string()],
+            QuestionKind::Input { .. } => Vec::new(),
+            QuestionKind::Select { items, .. } | QuestionKind::MultiSelect { items, .. } => items.clone(),
+        }
+    }
+
+    /// Handles one key press and returns the answer once the form is submitted.
+    fn handle_key(&mut self, code: KeyCode) -> Option<serde_json::Value> {
+        let selected = self.list_state.selected().unwrap_or(0);
+        match (&self.question.kind, code) {
+            (QuestionKind::Input { .. }, KeyCode::Char(c)) => self.input.push(c),
+            (QuestionKind::Input { .. }, KeyCode::Backspace) => {
+                self.input.pop();
+            }
+            (QuestionKind::Input { .. }, KeyCode::Enter) => return Some(json!(self.input)),
+            (QuestionKind::Confirm { .. }, KeyCode::Char('y')) => return Some(json!(true)),
+            (QuestionKind::Confirm { .. }, KeyCode::Char('n')) => return Some(json!(false)),
+            (QuestionKind::Confirm { .. }, KeyCode::Enter) => return Some(json!(selected == 0)),
+            (QuestionKind::Select { .. }, KeyCode::Enter) => return Some(json!(selected)),
+            (QuestionKind::MultiSelect { .. }, KeyCode::Char(' ')) => {
+                if let Some(checked) = self.checked.get_mut(selected) {
+                    *checked = !*checked;
+                }
+            }
+            (QuestionKind::MultiSelect { .. }, KeyCode::Enter) => {
+                let indices: Vec<usize> = (0..self.checked.len()).filter(|index| self.checked[*index]).collect();
+                return Some(json!(indices));
+            }
+            (_, KeyCode::Up | KeyCode::Char('k')) => self.list_state.select_previous(),
+            (_, KeyCode::Down | KeyCode::Char('j')) => {
+                let last = self.items().len().saturating_sub(1);
+                self.list_state.select(Some((selected + 1).min(last)));
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// The plan being carried out on the run screen.
+struct Run {
+    steps: Vec<Step>,
+    log: Vec<String>,
+    receiver: Option<Receiver<Message>>,
+    stdin: Option<ChildStdin>,
+    form: Option<Form>,
+    /// Set by quitting mid-run: the running step finishes, the rest are skipped
+    stop_requested: bool,
+}
+
+impl Run {
+    fn current(&self) -> Option<usize> {
+        self.steps.iter().position(|step| step.status == Status::Running)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.current().is_none() && !self.steps.iter().any(|step| step.status == Status::Pending)
+    }
+
+    fn failed(&self) -> Option<&Step> {
+        self.steps.iter().find(|step| step.status == Status::Failed)
+    }
+
+    /// Starts the next pending step once none is running.
+    fn advance(&mut self) {
+        if self.current().is_some() {
+            return;
+        }
+        let Some(index) = self.steps.iter().position(|step| step.status == Status::Pending) else {
+            return;
+        };
+        if self.stop_requested || self.failed().is_some() {
+            for step in self.steps.iter_mut().filter(|step| step.status == Status::Pending) {
+                step.status = Status::Skipped;
+            }
+            return;
+        }
+
+        let total = self.steps.len();
+        self.log.push(format!("[{}/{}] {}", index + 1, total, self.steps[index].label));
+        match spawn(&self.steps[index].args) {
+            Ok((receiver, stdin)) => {
+                self.steps[index].status = Status::Running;
+                self.receiver = Some(receiver);
+                self.stdin = Some(stdin);
+            }
+            Err(error) => {
+                self.steps[index].status = Status::Failed;
+                self.log.push(format!("Error: {:#}", error));
+            }
+        }
+    }
+
+    /// Applies everything the running step printed since the last frame.
+    fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        let messages: Vec<Message> = receiver.try_iter().collect();
+        for message in messages {
+            match message {
+                Message::Line(line) => self.log.push(line),
+                Message::Question(question) => self.form = Some(Form::new(question)),
+                Message::Exited(success) => {
+                    if let Some(index) = self.current() {
+                        self.steps[index].status = if success { Status::Done } else { Status::Failed };
+                    }
+                    self.receiver = None;
+                    self.stdin = None;
+                    self.form = None;
+                }
+            }
+        }
+    }
+
+    fn answer(&mut self, value: serde_json::Value) {
+        self.form = None;
+        if let Some(stdin) = &mut self.stdin {
+            // A closed pipe means the step already exited, which `poll` reports
+            let _ = writeln!(stdin, "{}", value).and_then(|_| stdin.flush());
+        }
+    }
+}
+
+/// Runs this nstack binary with the picker's own flags, e.g. `--force`, and `args`. Its
+/// prompts come back as questions and its output as lines.
+fn spawn(args: &[String]) -> Result<(Receiver<Message>, ChildStdin)> {
+    let global_flags: Vec<String> = std::env::args().skip(1).collect();
+    let mut child = Command::new(std::env::current_exe().context("Failed to locate the nstack binary")?)
+        .args(&global_flags)
+        .args(args)
+        .env(prompt::FORWARD_ENV, "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(format!("Failed to run nstack {}", args.join(" ")))?;
+
+    let stdin = child.stdin.take().context("Failed to open the step's input")?;
+    let stdout = child.stdout.take().context("Failed to read the step's output")?;
+    let stderr = child.stderr.take().context("Failed to read the step's output")?;
+
+    let (sender, receiver) = mpsc::channel();
+    let stderr_sender = sender.clone();
+    std::thread::spawn(move || {
+        let errors = std::thread::spawn(move || forward_lines(stderr, &stderr_sender));
+        forward_lines(stdout, &sender);
+        let _ = errors.join();
+        let success = child.wait().is_ok_and(|status| status.success());
+        let _ = sender.send(Message::Exited(success));
+    });
+    Ok((receiver, stdin))
+}
+
+fn forward_lines(output: impl Read, sender: &Sender<Message>) {
+    for line in BufReader::new(output).lines().map_while(Result::ok) {
+        let message = match line.strip_prefix(prompt::QUESTION_MARKER).and_then(|json| serde_json::from_str(json).ok()) {
+            Some(question) => Message::Question(question),
+            None => Message::Line(line),
+        };
+        if sender.send(message).is_err() {
+            break;
+        }
+    }
+}
+
+struct App {
+    screen: Screen,
+    focus: Focus,
+    categories: Vec<&'static str>,
+    category_state: ListState,
+    feature_state: ListState,
+    options_state: ListState,
+    review_state: ListState,
+    /// Feature names in the order they were picked, which is the install order
+    selected: Vec<&'static str>,
+    installed: Vec<&'static str>,
+    current_package_manager: &'static str,
+    run: Option<Run>,
+}
+
+impl App {
+    fn new(package_manager: &'static str) -> Self {
+        let mut categories: Vec<&'static str> = Vec::new();
+        for &(_, category, _) in FEATURES {
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+
+        let installed = FEATURES
+            .iter()
+            .map(|&(name, _, _)| name)
+            .filter(|name| ProjectManifest::is_feature_installed(name))
+            .collect();
+
+        let package_manager_index = PACKAGE_MANAGERS.iter().position(|pm| *pm == package_manager).unwrap_or(0);
+
+        Self {
+            screen: Screen::Browse,
+            focus: Focus::Categories,
+            categories,
+            category_state: ListState::default().with_selected(Some(0)),
+            feature_state: ListState::default().with_selected(Some(0)),
+            options_state: ListState::default().with_selected(Some(package_manager_index)),
+            review_state: ListState::default().with_selected(Some(0)),
+            selected: Vec::new(),
+            installed,
+            current_package_manager: package_manager,
+            run: None,
+        }
+    }
+
+    fn current_category(&self) -> &'static str {
+        self.categories[self.category_state.selected().unwrap_or(0)]
+    }
+
+    fn visible_features(&self) -> Vec<(&'static str, &'static str)> {
+        let category = self.current_category();
+        FEATURES
+            .iter()
+            .filter(|&&(_, c, _)| c == category)
+            .map(|&(name, _, description)| (name, description))
+            .collect()
+    }
+
+    fn toggle_current(&mut self) {
+        let features = self.visible_features();
+        let Some(&(name, _)) = self.feature_state.selected().and_then(|index| features.get(index)) else {
+            return;
+        };
+
+        if let Some(position) = self.selected.iter().position(|selected| *selected == name) {
+            self.selected.remove(position);
+        } else {
+            self.selected.push(name);
+        }
+    }
+
+    fn package_manager(&self) -> &'static str {
+        PACKAGE_MANAGERS[self.options_state.selected().unwrap_or(0)]
+    }
+
+    /// The plan: switching package managers goes through `nstack pm use`, which also
+    /// migrates lockfiles and scripts, before the features are added in order.
+    fn steps(&self) -> Vec<Step> {
+        let mut steps = Vec::new();
+        if self.package_manager() != self.current_package_manager {
+            steps.push(Step {
+                label: format!("Switch to {}", self.package_manager()),
+                args: vec!["pm".to_string(), "use".to_string(), self.package_manager().to_string()],
+                status: Status::Pending,
+            });
+        }
+        for feature in &self.selected {
+            steps.push(Step {
+                label: format!("Add {}", feature),
+                args: vec!["add".to_string(), "--feature".to_string(), feature.to_string()],
+                status: Status::Pending,
+            });
+        }
+        steps
+    }
+
+    /// Handles one key press and returns an outcome when the interface should close.
+    fn handle_key(&mut self, code: KeyCode) -> Option<Outcome> {
+        match self.screen {
+            Screen::Browse => match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Some(Outcome::Quit),
+                KeyCode::Tab | KeyCode::Right | KeyCode::Left | KeyCode::BackTab => {
+                    self.focus = if self.focus == Focus::Categories { Focus::Features } else { Focus::Categories };
+                }
+                KeyCode::Up | KeyCode::Char('k') => match self.focus {
+                    Focus::Categories => {
+                        self.category_state.select_previous();
+                        self.feature_state.select(Some(0));
+                    }
+                    Focus::Features => self.feature_state.select_previous(),
+                },
+                KeyCode::Down | KeyCode::Char('j') => match self.focus {
+                    Focus::Categories => {
+                        let last = self.categories.len() - 1;
+                        let next = self.category_state.selected().map_or(0, |index| (index + 1).min(last));
+                        self.category_state.select(Some(next));
+                        self.feature_state.select(Some(0));
+                    }
+                    Focus::Features => {
+                        let last = self.visible_features().len().saturating_sub(1);
+                        let next = self.feature_state.selected().map_or(0, |index| (index + 1).min(last));
+                        self.feature_state.select(Some(next));
+                    }
+                },
+                KeyCode::Char(' ') if self.focus == Focus::Features => self.toggle_current(),
+                KeyCode::Enter if self.focus == Focus::Categories => self.focus = Focus::Features,
+                KeyCode::Enter if !self.selected.is_empty() => self.screen = Screen::Options,
+                KeyCode::Enter => self.toggle_current(),
+                _ => {}
+            },
+            Screen::Options => match code {
+                KeyCode::Esc => self.screen = Screen::Browse,
+                KeyCode::Up | KeyCode::Char('k') => self.options_state.select_previous(),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    let next = self.options_state.selected().map_or(0, |index| (index + 1).min(PACKAGE_MANAGERS.len() - 1));
+                    self.options_state.select(Some(next));
+                }
+                KeyCode::Enter => self.screen = Screen::Review,
+                _ => {}
+            },
+            Screen::Review => match code {
+                KeyCode::Esc => self.screen = Screen::Options,
+                KeyCode::Up | KeyCode::Char('k') => self.review_state.select_previous(),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    let next = self.review_state.selected().map_or(0, |index| (index + 1).min(self.selected.len().saturating_sub(1)));
+                    self.review_state.select(Some(next));
+                }
+                // Reorder the plan
+                KeyCode::Char('K') => {
+                    if let Some(index) = self.review_state.selected().filter(|index| *index > 0) {
+                        self.selected.swap(index, index - 1);
+                        self.review_state.select(Some(index - 1));
+                    }
+                }
+                KeyCode::Char('J') => {
+                    if let Some(index) = self.review_state.selected().filter(|index| index + 1 < self.selected.len()) {
+                        self.selected.swap(index, index + 1);
+                        self.review_state.select(Some(index + 1));
+                    }
+                }
+                KeyCode::Enter => {
+                    self.run = Some(Run {
+                        steps: self.steps(),
+                        log: Vec::new(),
+                        receiver: None,
+                        stdin: None,
+                        form: None,
+                        stop_requested: false,
+                    });
+                    self.screen = Screen::Run;
+                }
+                _ => {}
+            },
+            Screen::Run => {
+                let run = self.run.as_mut()?;
+                if let Some(form) = &mut run.form {
+                    if let Some(value) = form.handle_key(code) {
+                        run.answer(value);
+                    }
+                } else if run.is_finished() {
+                    if matches!(code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+                        return Some(Outcome::Finished);
+                    }
+                } else if matches!(code, KeyCode::Esc | KeyCode::Char('q')) {
+                    run.stop_requested = true;
+                }
+            }
+        }
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [main_area, help_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+        let help = match (&self.screen, &self.run) {
+            (Screen::Browse, _) => "↑↓ move · tab switch pane · space select · enter continue · q quit",
+            (Screen::Options, _) => "↑↓ choose package manager · enter review · esc back",
+            (Screen::Review, _) => "↑↓ move · J/K reorder · enter install · esc back",
+            (Screen::Run, Some(run)) if run.form.as_ref().is_some_and(|form| matches!(form.question.kind, QuestionKind::MultiSelect { .. })) => {
+                "↑↓ move · space toggle · enter confirm"
+            }
+            (Screen::Run, Some(run)) if run.form.is_some() => "↑↓ move · enter confirm",
+            (Screen::Run, Some(run)) if run.is_finished() => "enter exit",
+            (Screen::Run, Some(run)) if run.stop_requested => "stopping after the current step...",
+            (Screen::Run, _) => "q stop after the current step",
+        };
+        frame.render_widget(Paragraph::new(help).style(Style::default().fg(Color::DarkGray)), help_area);
+
+        match self.screen {
+            Screen::Browse => self.render_browse(frame, main_area),
+            Screen::Options => self.render_options(frame, main_area),
+            Screen::Review => self.render_review(frame, main_area),
+            Screen::Run => self.render_run(frame, main_area),
+        }
+    }
+
+    fn render_browse(&mut self, frame: &mut Frame, area: Rect) {
+        let [lists_area, description_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(4)]).areas(area);
+        let [categories_area, features_area] =
+            Layout::horizontal([Constraint::Length(16), Constraint::Min(0)]).areas(lists_area);
+
+        let highlight = Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD);
+        let border = |focused: bool| {
+            if focused { Style::default().fg(Color::Cyan) } else { Style::default() }
+        };
+
+        let categories = List::new(self.categories.iter().map(|category| ListItem::new(*category)))
+            .block(Block::bordered().title(" Categories ").border_style(border(self.focus == Focus::Categories)))
+            .highlight_style(highlight);
+        frame.render_stateful_widget(categories, categories_area, &mut self.category_state);
+
+        let features = self.visible_features();
+        let items: Vec<ListItem> = features
+            .iter()
+            .map(|(name, _)| {
+                let checkbox = if self.selected.contains(name) { "[x] " } else { "[ ] " };
+                let mut spans = vec![Span::raw(checkbox), Span::raw(*name)];
+                if self.installed.contains(name) {
+                    spans.push(Span::styled("  installed", Style::default().fg(Color::Green)));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let title = format!(" Features · {} selected ", self.selected.len());
+        let list = List::new(items)
+            .block(Block::bordered().title(title).border_style(border(self.focus == Focus::Features)))
+            .highlight_style(highlight);
+        frame.render_stateful_widget(list, features_area, &mut self.feature_state);
+
+        let description = self
+            .feature_state
+            .selected()
+            .and_then(|index| features.get(index))
+            .map_or("", |(_, description)| *description);
+        frame.render_widget(
+            Paragraph::new(description).wrap(Wrap { trim: true }).block(Block::bordered().title(" Description ")),
+            description_area,
+        );
+    }
+
+    fn render_options(&mut self, frame: &mut Frame, area: Rect) {
+        let items = PACKAGE_MANAGERS.iter().map(|pm| {
+            if *pm == self.current_package_manager {
+                ListItem::new(format!("{} (current)", pm))
+            } else {
+                ListItem::new(format!("{} (switches with `nstack pm use {}`)", pm, pm))
+            }
+        });
+        let list = List::new(items)
+            .block(Block::bordered().title(" Package manager "))
+            .highlight_symbol("› ")
+            .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        frame.render_stateful_widget(list, area, &mut self.options_state);
+    }
+
+    fn render_review(&mut self, frame: &mut Frame, area: Rect) {
+        let [summary_area, plan_area] = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+
+        let switch = if self.package_manager() == self.current_package_manager {
+            String::new()
+        } else {
+            format!(" after switching from {}", self.current_package_manager)
+        };
+        let summary = format!(
+            "{} feature(s) with {}{}. Each feature's options are asked here as it runs.",
+            self.selected.len(),
+            self.package_manager(),
+            switch
+        );
+        frame.render_widget(
+            Paragraph::new(summary).wrap(Wrap { trim: true }).block(Block::bordered().title(" Plan ")),
+            summary_area,
+        );
+
+        let items = self.selected.iter().enumerate().map(|(index, name)| {
+            let description = FEATURES.iter().find(|(n, _, _)| n == name).map_or("", |(_, _, d)| *d);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}. {}", index + 1, name), Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  {}", description), Style::default().fg(Color::DarkGray)),
+            ]))
+        });
+        let list = List::new(items)
+            .block(Block::bordered().title(" Install order "))
+            .highlight_style(Style::default().fg(Color::Cyan));
+        frame.render_stateful_widget(list, plan_area, &mut self.review_state);
+    }
+
+    fn render_run(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(run) = &mut self.run else {
+            return;
+        };
+        let [gauge_area, body_area] = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+        let [steps_area, log_area] = Layout::horizontal([Constraint::Length(32), Constraint::Min(0)]).areas(body_area);
+
+        let total = run.steps.len();
+        let done = run.steps.iter().filter(|step| matches!(step.status, Status::Done | Status::Failed | Status::Skipped)).count();
+        let label = match (run.current(), run.failed()) {
+            (Some(index), _) => format!("[{}/{}] {}", index + 1, total, run.steps[index].label),
+            (None, Some(step)) => format!("Stopped: {} failed", step.label),
+            (None, None) if run.is_finished() => format!("Finished {} step(s)", total),
+            (None, None) => "Starting...".to_string(),
+        };
+        let color = if run.failed().is_some() { Color::Red } else { Color::Cyan };
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(" Progress "))
+                .gauge_style(Style::default().fg(color))
+                .ratio(if total == 0 { 1.0 } else { done as f64 / total as f64 })
+                .label(label),
+            gauge_area,
+        );
+
+        let steps = run.steps.iter().map(|step| {
+            let (mark, color) = match step.status {
+                Status::Pending => ("·", Color::DarkGray),
+                Status::Running => ("›", Color::Cyan),
+                Status::Done => ("✓", Color::Green),
+                Status::Failed => ("✗", Color::Red),
+                Status::Skipped => ("-", Color::DarkGray),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", mark), Style::default().fg(color)),
+                Span::raw(step.label.clone()),
+            ]))
+        });
+        frame.render_widget(List::new(steps).block(Block::bordered().title(" Steps ")), steps_area);
+
+        // Follow the end of the output
+        let height = log_area.height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = run.log.iter().skip(run.log.len().saturating_sub(height)).map(|line| Line::raw(line.as_str())).collect();
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Output ")), log_area);
+
+        if let Some(form) = &mut run.form {
+            render_form(frame, area, form);
+        }
+    }
+}
+
+fn render_form(frame: &mut Frame, area: Rect, form: &mut Form) {
+    let items = form.items();
+    let height = match form.question.kind {
+        QuestionKind::Input { .. } => 4,
+        _ => items.len().min(12) as u16 + 2,
+    } + 2;
+    let [popup] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+    let [popup] = Layout::horizontal([Constraint::Percentage(70)]).flex(Flex::Center).areas(popup);
+    frame.render_widget(Clear, popup);
+
+    let block = Block::bordered().title(format!(" {} ", form.question.prompt)).border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let [error_area, field_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+    if let Some(error) = &form.question.error {
+        frame.render_widget(Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)), error_area);
+    }
+
+    match form.question.kind {
+        QuestionKind::Input { .. } => {
+            frame.render_widget(Paragraph::new(format!("{}▏", form.input)).block(Block::bordered()), field_area);
+        }
+        QuestionKind::MultiSelect { .. } => {
+            let rows = items.iter().zip(&form.checked).map(|(item, checked)| {
+                ListItem::new(format!("{} {}", if *checked { "[x]" } else { "[ ]" }, item))
+            });
+            let list = List::new(rows).highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+            frame.render_stateful_widget(list, field_area, &mut form.list_state);
+        }
+        _ => {
+            let list = List::new(items.iter().map(|item| ListItem::new(item.as_str())))
+                .highlight_symbol("› ")
+                .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+            frame.render_stateful_widget(list, field_area, &mut form.list_state);
+        }
+    }
+}
+
+fn run_app(terminal: &mut DefaultTerminal, app: &mut App) -> Result<Outcome> {
+    loop {
+        if let Some(run) = &mut app.run {
+            run.poll();
+            run.advance();
+        }
+        terminal.draw(|frame| app.render(frame))?;
+
+        // Poll so output from a running step keeps flowing between key presses
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && let Some(outcome) = app.handle_key(key.code)
+        {
+            return Ok(outcome);
+        }
+    }
+}
+
+/// Full-screen feature picker shown when nstack runs without a subcommand. The plan runs
+/// inside it: each step is a separate nstack process whose output streams into the
+/// interface and whose prompts become forms.
+pub async fn run() -> Result<()> {
+    let current_package_manager = PackageManager::from_project_config()
+        .map(|pm| pm.to_string())
+        .unwrap_or("npm");
+    let mut app = App::new(current_package_manager);
+
+    let mut terminal = ratatui::try_init()?;
+    let outcome = run_app(&mut terminal, &mut app);
+    ratatui::restore();
+
+    let (Outcome::Finished, Some(run)) = (outcome?, app.run) else {
+        return Ok(());
+    };
+
+    // Keep the output, next steps included, once the interface is gone
+    for line in &run.log {
+        println!("{}", line);
+    }
+    if let Some(step) = run.failed() {
+        anyhow::bail!("Stopped: {} failed", step.label);
+    }
+    let added = run.steps.iter().filter(|step| step.status == Status::Done).count();
+    println!("\n{}", style(format!("Finished {} of {} step(s)", added, run.steps.len())).green().bold());
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::config;
+use crate::http;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/happybear-21/nstack/releases/latest";
+const RELEASES_URL: &str = "https://github.com/happybear-21/nstack/releases/tag";
+const CHECK_INTERVAL_SECS: u64 = 24 * 60 * 60;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Last check result, stored globally so every project shares it.
+#[derive(Debug, Serialize, Deserialize)]
+struct UpdateCache {
+    checked_at: u64,
+    /// Release tag as published, e.g. `v0.2.0`
+    latest_tag: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    Some(config::global_dir()?.join("update-check.json"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn is_disabled() -> bool {
+    std::env::var_os("NSTACK_NO_UPDATE_CHECK").is_some()
+        || std::env::var_os("CI").is_some()
+        || config::value("update_check").as_deref() == Some("false")
+}
+
+/// Parses `v1.2.3` or `1.2.3-beta.1` into comparable numbers, ignoring pre-release tags.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches('v');
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+
+    Some((parts.next()??, parts.next().flatten().unwrap_or(0), parts.next().flatten().unwrap_or(0)))
+}
+
+async fn fetch_latest_tag() -> Result<String> {
+    let client = http::client(REQUEST_TIMEOUT)?;
+
+    let release: Release = client
+        .get(LATEST_RELEASE_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(release.tag_name)
+}
+
+/// Returns the latest release tag, hitting the network at most once per day.
+async fn latest_tag() -> Option<String> {
+    let path = cache_path()?;
+
+    let cached = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<UpdateCache>(&content).ok());
+    if let Some(cache) = &cached
+        && now().saturating_sub(cache.checked_at) < CHECK_INTERVAL_SECS
+    {
+        return Some(cache.latest_tag.clone());
+    }
+
+    // A failed check is cached too, so being offline does not add a timeout to every command
+    let latest_tag = match fetch_latest_tag().await {
+        Ok(tag) => tag,
+        Err(_) => cached
+            .map(|cache| cache.latest_tag)
+            .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
+    };
+
+    let cache = UpdateCache { checked_at: now(), latest_tag: latest_tag.clone() };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&cache) {
+        let _ = fs::write(&path, content);
+    }
+
+    Some(latest_tag)
+}
+
+/// Prints a one-line notice when a newer nstack release exists. Network and cache errors
+/// are ignored so the check never gets in the way of the command that just ran.
+/// Turn it off with `nstack config set --global update_check false` or
+/// `NSTACK_NO_UPDATE_CHECK=1`; it is always skipped on CI.
+pub async fn notify_if_outdated() {
+    if is_disabled() {
+        return;
+    }
+
+    let current = env!("CARGO_PKG_VERSION");
+    let Some(latest_tag) = latest_tag().await else {
+        return;
+    };
+
+    let is_newer = matches!(
+        (parse_version(&latest_tag), parse_version(current)),
+        (Some(latest), Some(current)) if latest > current
+    );
+    if is_newer {
+        println!(
+            "\n{}",
+            style(format!(
+                "A new version of nstack is available: {} → {} (changelog: {}/{})",
+                current,
+                latest_tag.trim_start_matches('v'),
+                RELEASES_URL,
+                latest_tag
+            ))
+            .yellow()
+        );
+    }
+}